@@ -1,7 +1,10 @@
 use crate::message::MessageSender;
 use crate::{
     absm::{
-        command::{AddLayerCommand, RemoveLayerCommand, SetLayerMaskCommand, SetLayerNameCommand},
+        command::{
+            AddLayerCommand, RemoveLayerCommand, SetLayerBlendModeCommand, SetLayerMaskCommand,
+            SetLayerNameCommand,
+        },
         fetch_selection,
         selection::AbsmSelection,
     },
@@ -15,7 +18,7 @@ use crate::{
     send_sync_message,
 };
 use fyrox::{
-    animation::machine::{LayerMask, MachineLayer},
+    animation::machine::{LayerBlendMode, LayerMask, MachineLayer},
     core::pool::Handle,
     fxhash::FxHashSet,
     gui::{
@@ -47,6 +50,7 @@ pub struct Toolbar {
     pub add_layer: Handle<UiNode>,
     pub remove_layer: Handle<UiNode>,
     pub edit_mask: Handle<UiNode>,
+    pub blend_mode: Handle<UiNode>,
     pub node_selector: Handle<UiNode>,
 }
 
@@ -64,6 +68,7 @@ impl Toolbar {
         let add_layer;
         let remove_layer;
         let edit_mask;
+        let blend_mode;
         let panel = StackPanelBuilder::new(
             WidgetBuilder::new()
                 .with_child({
@@ -146,6 +151,24 @@ impl Toolbar {
                     )
                     .build(ctx);
                     edit_mask
+                })
+                .with_child({
+                    blend_mode = DropdownListBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_width(80.0)
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Defines how the pose of the layer is combined with the poses of the previous layers.",
+                            )),
+                    )
+                    .with_items(vec![
+                        make_dropdown_list_option(ctx, "Override"),
+                        make_dropdown_list_option(ctx, "Additive"),
+                    ])
+                    .with_selected(0)
+                    .build(ctx);
+                    blend_mode
                 }),
         )
         .with_orientation(Orientation::Horizontal)
@@ -159,6 +182,7 @@ impl Toolbar {
             add_layer,
             remove_layer,
             edit_mask,
+            blend_mode,
             node_selector: Handle::NONE,
         }
     }
@@ -194,6 +218,20 @@ impl Toolbar {
                     Selection::Absm(new_selection),
                     editor_scene.selection.clone(),
                 ));
+            } else if message.destination() == self.blend_mode
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(layer_index) = selection.layer {
+                    sender.do_scene_command(SetLayerBlendModeCommand {
+                        absm_node_handle: selection.absm_node_handle,
+                        layer_index,
+                        blend_mode: if *index == 0 {
+                            LayerBlendMode::Override
+                        } else {
+                            LayerBlendMode::Additive
+                        },
+                    });
+                }
             }
         } else if let Some(TextMessage::Text(text)) = message.data() {
             if message.destination() == self.layer_name
@@ -383,6 +421,18 @@ impl Toolbar {
                         layer.name().to_string(),
                     ),
                 );
+
+                send_sync_message(
+                    ui,
+                    DropdownListMessage::selection(
+                        self.blend_mode,
+                        MessageDirection::ToWidget,
+                        Some(match layer.blend_mode() {
+                            LayerBlendMode::Override => 0,
+                            LayerBlendMode::Additive => 1,
+                        }),
+                    ),
+                );
             }
         }
     }
@@ -791,10 +791,13 @@ impl Toolbar {
         )
         .with_filter(Filter::new(|p: &Path| {
             if let Some(ext) = p.extension() {
-                // TODO: Here we allow importing only FBX files, but they can contain
+                // TODO: Here we allow importing only FBX and glTF files, but they can contain
                 // multiple animations and it might be good to also add animation selector
                 // that will be used to select a particular animation to import.
-                ext.to_string_lossy().as_ref() == "fbx"
+                matches!(
+                    ext.to_string_lossy().to_lowercase().as_str(),
+                    "fbx" | "gltf" | "glb"
+                )
             } else {
                 p.is_dir()
             }
@@ -154,7 +154,7 @@ impl AssetItemBuilder {
                             resource_manager.request::<Texture, _>(&path),
                         ))
                     }
-                    "fbx" | "rgs" => {
+                    "fbx" | "gltf" | "glb" | "rgs" => {
                         kind = AssetKind::Model;
                         load_image(include_bytes!("../../resources/embed/model.png"))
                     }
@@ -221,7 +221,7 @@ fn is_engine_resource(ext: &OsStr) -> bool {
     let ext = ext.to_string_lossy().to_lowercase();
     matches!(
         ext.as_str(),
-        "rgs" | "fbx" | "jpg" | "tga" | "png" | "bmp" | "ogg" | "wav" | "shader"
+        "rgs" | "fbx" | "gltf" | "glb" | "jpg" | "tga" | "png" | "bmp" | "ogg" | "wav" | "shader"
     )
 }
 
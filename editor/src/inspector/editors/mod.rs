@@ -17,6 +17,8 @@ use crate::{
 use fyrox::{
     animation::{
         machine::{
+            ik::{ChainIk, IkChain, IkSolver, IkTarget, LookAtIk, TwoBoneIk},
+            layer::LayerBlendMode,
             node::{
                 blendspace::{BlendSpace, BlendSpacePoint},
                 BasePoseNode,
@@ -50,6 +52,7 @@ use fyrox::{
             CompressionOptions, MipFilter, TextureMagnificationFilter, TextureMinificationFilter,
             TextureResource, TextureWrapMode,
         },
+        tileset::{TileSet, TileSetResource},
     },
     scene::{
         base::{
@@ -86,6 +89,11 @@ use fyrox::{
         rigidbody::RigidBodyType,
         sound::{
             self,
+            chorus::Chorus,
+            compressor::Compressor,
+            delay::Delay,
+            distortion::{Distortion, WaveshaperKind},
+            equalizer::{Equalizer, EqualizerBand, EqualizerBandKind},
             filter::{
                 AllPassFilterEffect, BandPassFilterEffect, HighPassFilterEffect,
                 HighShelfFilterEffect, LowPassFilterEffect, LowShelfFilterEffect,
@@ -94,7 +102,10 @@ use fyrox::{
             Attenuate, AudioBus, Biquad, DistanceModel, Effect, EffectWrapper, SoundBuffer,
             SoundBufferResource, Status,
         },
-        terrain::{Chunk, Layer},
+        terrain::{
+            foliage::{FoliageLayer, FoliageMesh},
+            Chunk, Layer,
+        },
         transform::Transform,
     },
 };
@@ -147,6 +158,8 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
 
     container.register_inheritable_vec_collection::<Surface>();
     container.register_inheritable_vec_collection::<Layer>();
+    container.register_inheritable_vec_collection::<FoliageLayer>();
+    container.insert(VecCollectionPropertyEditorDefinition::<FoliageMesh>::new());
     container.register_inheritable_vec_collection::<EmitterWrapper>();
     container.register_inheritable_vec_collection::<LevelOfDetail>();
     container.register_inheritable_vec_collection::<ErasedHandle>();
@@ -196,6 +209,11 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     );
     container.insert(InheritablePropertyEditorDefinition::<Option<CurveResource>>::new());
 
+    container.insert(ResourceFieldPropertyEditorDefinition::<TileSet>::new(
+        Rc::new(|resource_manager, path| block_on(resource_manager.request::<TileSet, _>(path))),
+    ));
+    container.insert(InheritablePropertyEditorDefinition::<Option<TileSetResource>>::new());
+
     container.insert(ResourceFieldPropertyEditorDefinition::<Shader>::new(
         Rc::new(|resource_manager, path| block_on(resource_manager.request::<Shader, _>(path))),
     ));
@@ -215,6 +233,13 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<RevoluteJoint>();
     container.register_inheritable_inspectable::<PrismaticJoint>();
     container.register_inheritable_inspectable::<dim2::joint::PrismaticJoint>();
+    container.register_inheritable_inspectable::<SixDofJoint>();
+    container.register_inheritable_inspectable::<dim2::joint::ThreeDofJoint>();
+    container.register_inheritable_inspectable::<RopeJoint>();
+    container.register_inheritable_inspectable::<dim2::joint::RopeJoint>();
+    container.register_inheritable_inspectable::<JointMotor>();
+    container.register_inheritable_inspectable::<JointDof>();
+    container.register_inheritable_enum::<JointMotorModel, _>();
 
     container.register_inheritable_inspectable::<Base>();
     container.register_inheritable_inspectable::<BaseLight>();
@@ -230,6 +255,15 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(InspectablePropertyEditorDefinition::<LowShelfFilterEffect>::new());
     container.insert(InspectablePropertyEditorDefinition::<HighShelfFilterEffect>::new());
     container.insert(InspectablePropertyEditorDefinition::<Reverb>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Compressor>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Delay>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Chorus>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Distortion>::new());
+    container.insert(EnumPropertyEditorDefinition::<WaveshaperKind>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Equalizer>::new());
+    container.insert(InspectablePropertyEditorDefinition::<EqualizerBand>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<EqualizerBand>::new());
+    container.insert(EnumPropertyEditorDefinition::<EqualizerBandKind>::new());
 
     container.register_inheritable_enum::<Emitter, _>();
 
@@ -292,6 +326,12 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<dim2::collider::TrimeshShape>();
     container.register_inheritable_inspectable::<HeightfieldShape>();
     container.register_inheritable_inspectable::<dim2::collider::HeightfieldShape>();
+    container
+        .insert(EnumPropertyEditorDefinition::<dim2::collider::OneWayPlatform>::new_optional());
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<dim2::collider::OneWayPlatform>,
+    >::new());
+    container.register_inheritable_inspectable::<dim2::collider::OneWayPlatform>();
     container.register_inheritable_inspectable::<ConvexPolyhedronShape>();
     container.insert(SpriteSheetFramesContainerEditorDefinition);
 
@@ -330,6 +370,16 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(MachinePropertyEditorDefinition);
     container.insert(InheritablePropertyEditorDefinition::<Machine>::new());
 
+    container.insert(InspectablePropertyEditorDefinition::<IkChain>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<IkChain>::new());
+    container.insert(EnumPropertyEditorDefinition::<IkSolver>::new());
+    container.insert(EnumPropertyEditorDefinition::<IkTarget>::new());
+    container.insert(EnumPropertyEditorDefinition::<IkTarget>::new_optional());
+    container.insert(InspectablePropertyEditorDefinition::<TwoBoneIk>::new());
+    container.insert(InspectablePropertyEditorDefinition::<ChainIk>::new());
+    container.insert(InspectablePropertyEditorDefinition::<LookAtIk>::new());
+    container.insert(EnumPropertyEditorDefinition::<LayerBlendMode>::new());
+
     container.insert(EnumPropertyEditorDefinition::<LogicNode>::new());
     container.insert(InspectablePropertyEditorDefinition::<AndNode>::new());
     container.insert(InspectablePropertyEditorDefinition::<OrNode>::new());
@@ -2,7 +2,10 @@ use crate::scene::commands::RevertSceneNodePropertyCommand;
 use crate::{
     scene::commands::{
         make_set_node_property_command,
-        terrain::{AddTerrainLayerCommand, DeleteTerrainLayerCommand},
+        terrain::{
+            AddTerrainFoliageLayerCommand, AddTerrainLayerCommand,
+            DeleteTerrainFoliageLayerCommand, DeleteTerrainLayerCommand,
+        },
     },
     SceneCommand,
 };
@@ -36,6 +39,21 @@ impl SceneNodePropertyChangedHandler {
                 },
                 _ => None,
             }
+        } else if args.path() == Terrain::FOLIAGE_LAYERS
+            && args.owner_type_id == TypeId::of::<Terrain>()
+        {
+            match args.value {
+                FieldKind::Collection(ref collection_changed) => match **collection_changed {
+                    CollectionChanged::Add(_) => Some(SceneCommand::new(
+                        AddTerrainFoliageLayerCommand::new(handle),
+                    )),
+                    CollectionChanged::Remove(index) => Some(SceneCommand::new(
+                        DeleteTerrainFoliageLayerCommand::new(handle, index),
+                    )),
+                    CollectionChanged::ItemChanged { .. } => None,
+                },
+                _ => None,
+            }
         } else {
             None
         }
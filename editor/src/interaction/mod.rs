@@ -18,6 +18,7 @@ pub mod rotate_mode;
 pub mod scale_mode;
 pub mod select_mode;
 pub mod terrain;
+pub mod tilemap;
 
 pub trait BaseInteractionMode {
     fn as_any(&self) -> &dyn Any;
@@ -143,4 +144,5 @@ pub enum InteractionModeKind {
     Rotate = 3,
     Navmesh = 4,
     Terrain = 5,
+    TileMap = 6,
 }
@@ -3,7 +3,10 @@ use crate::{
     make_color_material,
     message::MessageSender,
     scene::{
-        commands::terrain::{ModifyTerrainHeightCommand, ModifyTerrainLayerMaskCommand},
+        commands::terrain::{
+            ModifyTerrainFoliageMaskCommand, ModifyTerrainHeightCommand,
+            ModifyTerrainLayerMaskCommand,
+        },
         EditorScene, Selection,
     },
     settings::Settings,
@@ -18,19 +21,26 @@ use fyrox::{
         log::{Log, MessageKind},
         math::vector_to_quat,
         pool::Handle,
+        reflect::prelude::*,
     },
     engine::Engine,
     gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
         inspector::{
             editors::{
-                enumeration::EnumPropertyEditorDefinition, PropertyEditorDefinitionContainer,
+                enumeration::EnumPropertyEditorDefinition,
+                inspectable::InspectablePropertyEditorDefinition,
+                PropertyEditorDefinitionContainer,
             },
             Inspector, InspectorBuilder, InspectorContext, InspectorMessage,
         },
         message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
-        BuildContext, UiNode, UserInterface,
+        BuildContext, Thickness, UiNode, UserInterface,
     },
     scene::{
         base::BaseBuilder,
@@ -41,10 +51,48 @@ use fyrox::{
             MeshBuilder, RenderPath,
         },
         node::Node,
-        terrain::{Brush, BrushMode, BrushShape, Terrain, TerrainRayCastResult},
+        terrain::{
+            tools::{
+                FractalNoise, HeightMapError, HeightMapFormat, HydraulicErosion, ThermalErosion,
+            },
+            Brush, BrushMode, BrushShape, Terrain, TerrainRayCastResult,
+        },
     },
 };
-use std::rc::Rc;
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+/// Settings of the procedural tools, that affect the whole terrain.
+#[derive(Clone, Debug, Reflect)]
+pub struct TerrainTools {
+    #[reflect(
+        description = "Fractal noise that will be used to generate heights of the whole terrain."
+    )]
+    pub noise: FractalNoise,
+    pub hydraulic_erosion: HydraulicErosion,
+    pub thermal_erosion: ThermalErosion,
+    #[reflect(description = "File format of imported and exported height maps.")]
+    pub height_map_format: HeightMapFormat,
+    #[reflect(
+        description = "Normalized values of imported and exported height maps are mapped to this range of heights (in meters)."
+    )]
+    pub height_range: Range<f32>,
+}
+
+impl Default for TerrainTools {
+    fn default() -> Self {
+        Self {
+            noise: Default::default(),
+            hydraulic_erosion: Default::default(),
+            thermal_erosion: Default::default(),
+            height_map_format: Default::default(),
+            height_range: 0.0..100.0,
+        }
+    }
+}
 
 pub struct TerrainInteractionMode {
     heightmaps: Vec<Vec<f32>>,
@@ -54,6 +102,8 @@ pub struct TerrainInteractionMode {
     brush_gizmo: BrushGizmo,
     brush: Brush,
     brush_panel: BrushPanel,
+    tools: TerrainTools,
+    tools_panel: ToolsPanel,
 }
 
 impl TerrainInteractionMode {
@@ -70,8 +120,13 @@ impl TerrainInteractionMode {
 
         let brush_panel = BrushPanel::new(&mut engine.user_interface.build_ctx(), &brush);
 
+        let tools = TerrainTools::default();
+        let tools_panel = ToolsPanel::new(&mut engine.user_interface.build_ctx(), &tools);
+
         Self {
             brush_panel,
+            tools,
+            tools_panel,
             heightmaps: Default::default(),
             brush_gizmo: BrushGizmo::new(editor_scene, engine),
             interacting: false,
@@ -116,6 +171,14 @@ impl BrushGizmo {
     }
 }
 
+fn copy_height_maps(terrain: &Terrain) -> Vec<Vec<f32>> {
+    terrain
+        .chunks_ref()
+        .iter()
+        .map(|c| c.heightmap_owned())
+        .collect()
+}
+
 fn copy_layer_masks(terrain: &Terrain, layer: usize) -> Vec<Vec<u8>> {
     let mut masks = Vec::new();
 
@@ -126,6 +189,16 @@ fn copy_layer_masks(terrain: &Terrain, layer: usize) -> Vec<Vec<u8>> {
     masks
 }
 
+fn copy_foliage_masks(terrain: &Terrain, layer: usize) -> Vec<Vec<u8>> {
+    let mut masks = Vec::new();
+
+    for chunk in terrain.chunks_ref() {
+        masks.push(chunk.foliage_masks[layer].data_ref().data().to_vec());
+    }
+
+    masks
+}
+
 impl InteractionMode for TerrainInteractionMode {
     fn on_left_mouse_button_down(
         &mut self,
@@ -156,16 +229,21 @@ impl InteractionMode for TerrainInteractionMode {
                     }
 
                     match self.brush.mode {
-                        BrushMode::ModifyHeightMap { .. } | BrushMode::FlattenHeightMap { .. } => {
-                            self.heightmaps = terrain
-                                .chunks_ref()
-                                .iter()
-                                .map(|c| c.heightmap_owned())
-                                .collect();
+                        BrushMode::ModifyHeightMap { .. }
+                        | BrushMode::FlattenHeightMap { .. }
+                        | BrushMode::SmoothHeightMap { .. }
+                        | BrushMode::NoiseHeightMap { .. } => {
+                            self.heightmaps = copy_height_maps(terrain);
                         }
                         BrushMode::DrawOnMask { layer, .. } => {
                             self.masks = copy_layer_masks(terrain, layer);
                         }
+                        BrushMode::DrawOnFoliageMask { layer, .. } => {
+                            if layer >= terrain.foliage_layers().len() {
+                                return;
+                            }
+                            self.masks = copy_foliage_masks(terrain, layer);
+                        }
                     }
 
                     self.interacting = true;
@@ -189,20 +267,16 @@ impl InteractionMode for TerrainInteractionMode {
 
                 if let Some(terrain) = &graph[handle].cast::<Terrain>() {
                     if self.interacting {
-                        let new_heightmaps = terrain
-                            .chunks_ref()
-                            .iter()
-                            .map(|c| c.heightmap_owned())
-                            .collect();
-
                         match self.brush.mode {
                             BrushMode::ModifyHeightMap { .. }
-                            | BrushMode::FlattenHeightMap { .. } => {
+                            | BrushMode::FlattenHeightMap { .. }
+                            | BrushMode::SmoothHeightMap { .. }
+                            | BrushMode::NoiseHeightMap { .. } => {
                                 self.message_sender.do_scene_command(
                                     ModifyTerrainHeightCommand::new(
                                         handle,
                                         std::mem::take(&mut self.heightmaps),
-                                        new_heightmaps,
+                                        copy_height_maps(terrain),
                                     ),
                                 );
                             }
@@ -216,6 +290,16 @@ impl InteractionMode for TerrainInteractionMode {
                                     ),
                                 );
                             }
+                            BrushMode::DrawOnFoliageMask { layer, .. } => {
+                                self.message_sender.do_scene_command(
+                                    ModifyTerrainFoliageMaskCommand::new(
+                                        handle,
+                                        std::mem::take(&mut self.masks),
+                                        copy_foliage_masks(terrain, layer),
+                                        layer,
+                                    ),
+                                );
+                            }
                         }
 
                         self.interacting = false;
@@ -257,7 +341,8 @@ impl InteractionMode for TerrainInteractionMode {
                                         *amount *= -1.0;
                                     }
                                 }
-                                BrushMode::DrawOnMask { alpha, .. } => {
+                                BrushMode::DrawOnMask { alpha, .. }
+                                | BrushMode::DrawOnFoliageMask { alpha, .. } => {
                                     if engine.user_interface.keyboard_modifiers().shift {
                                         *alpha = -1.0;
                                     }
@@ -267,6 +352,12 @@ impl InteractionMode for TerrainInteractionMode {
                                         *height *= -1.0;
                                     }
                                 }
+                                BrushMode::SmoothHeightMap { .. } => {}
+                                BrushMode::NoiseHeightMap { amount, .. } => {
+                                    if engine.user_interface.keyboard_modifiers().shift {
+                                        *amount *= -1.0;
+                                    }
+                                }
                             }
 
                             if self.interacting {
@@ -304,6 +395,11 @@ impl InteractionMode for TerrainInteractionMode {
             MessageDirection::ToWidget,
             false,
         ));
+        engine.user_interface.send_message(WindowMessage::open(
+            self.tools_panel.window,
+            MessageDirection::ToWidget,
+            false,
+        ));
     }
 
     fn deactivate(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
@@ -314,26 +410,146 @@ impl InteractionMode for TerrainInteractionMode {
             self.brush_panel.window,
             MessageDirection::ToWidget,
         ));
+        engine.user_interface.send_message(WindowMessage::close(
+            self.tools_panel.window,
+            MessageDirection::ToWidget,
+        ));
     }
 
     fn handle_ui_message(
         &mut self,
         message: &UiMessage,
         editor_scene: &mut EditorScene,
-        _engine: &mut Engine,
+        engine: &mut Engine,
     ) {
         if let Selection::Graph(selection) = &editor_scene.selection {
             if selection.is_single_selection() {
                 self.brush_panel.handle_ui_message(message, &mut self.brush);
+                self.tools_panel.handle_ui_message(message, &mut self.tools);
+
+                let handle = selection.nodes()[0];
+
+                if let Some(ButtonMessage::Click) = message.data() {
+                    let tools = &self.tools;
+                    if message.destination() == self.tools_panel.generate {
+                        self.modify_terrain_height(editor_scene, engine, handle, |terrain| {
+                            terrain.generate_height_map(&tools.noise);
+                            Ok(())
+                        });
+                    } else if message.destination() == self.tools_panel.hydraulic_erosion {
+                        self.modify_terrain_height(editor_scene, engine, handle, |terrain| {
+                            terrain.apply_hydraulic_erosion(&tools.hydraulic_erosion);
+                            Ok(())
+                        });
+                    } else if message.destination() == self.tools_panel.thermal_erosion {
+                        self.modify_terrain_height(editor_scene, engine, handle, |terrain| {
+                            terrain.apply_thermal_erosion(&tools.thermal_erosion);
+                            Ok(())
+                        });
+                    } else if message.destination() == self.tools_panel.import {
+                        self.tools_panel.open_file_selector(
+                            &engine.user_interface,
+                            self.tools_panel.import_selector,
+                        );
+                    } else if message.destination() == self.tools_panel.export {
+                        self.tools_panel.open_file_selector(
+                            &engine.user_interface,
+                            self.tools_panel.export_selector,
+                        );
+                    }
+                } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+                    if message.destination() == self.tools_panel.import_selector {
+                        match std::fs::read(path) {
+                            Ok(data) => {
+                                let tools = &self.tools;
+                                self.modify_terrain_height(
+                                    editor_scene,
+                                    engine,
+                                    handle,
+                                    |terrain| {
+                                        terrain.import_height_map(
+                                            &data,
+                                            tools.height_map_format,
+                                            tools.height_range.clone(),
+                                        )
+                                    },
+                                );
+                            }
+                            Err(e) => Log::err(format!(
+                                "Unable to read height map from {}. Reason: {:?}",
+                                path.display(),
+                                e
+                            )),
+                        }
+                    } else if message.destination() == self.tools_panel.export_selector {
+                        let graph = &engine.scenes[editor_scene.scene].graph;
+                        if let Some(terrain) = graph[handle].cast::<Terrain>() {
+                            match terrain.export_height_map(
+                                self.tools.height_map_format,
+                                self.tools.height_range.clone(),
+                            ) {
+                                Ok(data) => {
+                                    if let Err(e) = std::fs::write(path, data) {
+                                        Log::err(format!(
+                                            "Unable to write height map to {}. Reason: {:?}",
+                                            path.display(),
+                                            e
+                                        ))
+                                    }
+                                }
+                                Err(e) => {
+                                    Log::err(format!("Unable to export height map. Reason: {}", e))
+                                }
+                            }
+                        }
+                    }
+                }
             }
         }
     }
 
     fn on_drop(&mut self, engine: &mut Engine) {
-        engine.user_interface.send_message(WidgetMessage::remove(
+        for widget in [
             self.brush_panel.window,
-            MessageDirection::ToWidget,
-        ));
+            self.tools_panel.window,
+            self.tools_panel.import_selector,
+            self.tools_panel.export_selector,
+        ] {
+            engine
+                .user_interface
+                .send_message(WidgetMessage::remove(widget, MessageDirection::ToWidget));
+        }
+    }
+}
+
+impl TerrainInteractionMode {
+    /// Applies the function to the terrain and registers the changes of heights in the command stack, so
+    /// they could be undone.
+    fn modify_terrain_height<F>(
+        &self,
+        editor_scene: &EditorScene,
+        engine: &mut Engine,
+        handle: Handle<Node>,
+        func: F,
+    ) where
+        F: FnOnce(&mut Terrain) -> Result<(), HeightMapError>,
+    {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        if let Some(terrain) = graph[handle].cast_mut::<Terrain>() {
+            let old_heightmaps = copy_height_maps(terrain);
+
+            match func(terrain) {
+                Ok(()) => {
+                    self.message_sender
+                        .do_scene_command(ModifyTerrainHeightCommand::new(
+                            handle,
+                            old_heightmaps,
+                            copy_height_maps(terrain),
+                        ));
+                }
+                Err(e) => Log::err(format!("Unable to modify terrain. Reason: {}", e)),
+            }
+        }
     }
 }
 
@@ -351,18 +567,34 @@ fn make_brush_mode_enum_property_editor_definition() -> EnumPropertyEditorDefini
                 alpha: 1.0,
             },
             2 => BrushMode::FlattenHeightMap { height: 0.0 },
+            3 => BrushMode::SmoothHeightMap { factor: 0.5 },
+            4 => BrushMode::NoiseHeightMap {
+                amount: 0.1,
+                frequency: 0.5,
+                seed: 0,
+            },
+            5 => BrushMode::DrawOnFoliageMask {
+                layer: 0,
+                alpha: 1.0,
+            },
             _ => unreachable!(),
         },
         index_generator: |v| match v {
             BrushMode::ModifyHeightMap { .. } => 0,
             BrushMode::DrawOnMask { .. } => 1,
             BrushMode::FlattenHeightMap { .. } => 2,
+            BrushMode::SmoothHeightMap { .. } => 3,
+            BrushMode::NoiseHeightMap { .. } => 4,
+            BrushMode::DrawOnFoliageMask { .. } => 5,
         },
         names_generator: || {
             vec![
                 "Modify Height Map".to_string(),
                 "Draw On Mask".to_string(),
                 "Flatten Height Map".to_string(),
+                "Smooth Height Map".to_string(),
+                "Noise Height Map".to_string(),
+                "Draw On Foliage Mask".to_string(),
             ]
         },
     }
@@ -453,3 +685,149 @@ impl BrushPanel {
         Some(())
     }
 }
+
+struct ToolsPanel {
+    window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    generate: Handle<UiNode>,
+    hydraulic_erosion: Handle<UiNode>,
+    thermal_erosion: Handle<UiNode>,
+    import: Handle<UiNode>,
+    export: Handle<UiNode>,
+    import_selector: Handle<UiNode>,
+    export_selector: Handle<UiNode>,
+}
+
+fn make_height_map_filter() -> Filter {
+    Filter::new(|p: &Path| {
+        if let Some(ext) = p.extension() {
+            let ext = ext.to_string_lossy().to_lowercase();
+            ext == "png" || ext == "raw" || ext == "r16"
+        } else {
+            p.is_dir()
+        }
+    })
+}
+
+fn make_tool_button(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_height(24.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl ToolsPanel {
+    fn new(ctx: &mut BuildContext, tools: &TerrainTools) -> Self {
+        let property_editors = PropertyEditorDefinitionContainer::new();
+        property_editors.insert(InspectablePropertyEditorDefinition::<FractalNoise>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<HydraulicErosion>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<ThermalErosion>::new());
+        property_editors.insert(EnumPropertyEditorDefinition::<HeightMapFormat>::new());
+
+        let context = InspectorContext::from_object(
+            tools,
+            ctx,
+            Rc::new(property_editors),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+            Default::default(),
+        );
+
+        let inspector = InspectorBuilder::new(WidgetBuilder::new())
+            .with_context(context)
+            .build(ctx);
+        let generate = make_tool_button(ctx, "Generate From Noise");
+        let hydraulic_erosion = make_tool_button(ctx, "Apply Hydraulic Erosion");
+        let thermal_erosion = make_tool_button(ctx, "Apply Thermal Erosion");
+        let import = make_tool_button(ctx, "Import Height Map...");
+        let export = make_tool_button(ctx, "Export Height Map...");
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+            .can_close(false)
+            .with_content(
+                ScrollViewerBuilder::new(WidgetBuilder::new())
+                    .with_content(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .with_child(inspector)
+                                .with_child(generate)
+                                .with_child(hydraulic_erosion)
+                                .with_child(thermal_erosion)
+                                .with_child(import)
+                                .with_child(export),
+                        )
+                        .build(ctx),
+                    )
+                    .build(ctx),
+            )
+            .open(false)
+            .with_title(WindowTitle::text("Terrain Tools"))
+            .build(ctx);
+
+        let import_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::text("Import Height Map")),
+        )
+        .with_filter(make_height_map_filter())
+        .build(ctx);
+
+        let export_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::text("Export Height Map")),
+        )
+        .with_mode(FileBrowserMode::Save {
+            default_file_name: PathBuf::from("heightmap.png"),
+        })
+        .with_filter(make_height_map_filter())
+        .build(ctx);
+
+        Self {
+            window,
+            inspector,
+            generate,
+            hydraulic_erosion,
+            thermal_erosion,
+            import,
+            export,
+            import_selector,
+            export_selector,
+        }
+    }
+
+    fn open_file_selector(&self, ui: &UserInterface, file_selector: Handle<UiNode>) {
+        ui.send_message(WindowMessage::open_modal(
+            file_selector,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        ui.send_message(FileSelectorMessage::path(
+            file_selector,
+            MessageDirection::ToWidget,
+            std::env::current_dir().unwrap_or_default(),
+        ));
+    }
+
+    fn handle_ui_message(&self, message: &UiMessage, tools: &mut TerrainTools) {
+        if message.destination() == self.inspector
+            && message.direction() == MessageDirection::FromWidget
+        {
+            if let Some(InspectorMessage::PropertyChanged(msg)) = message.data::<InspectorMessage>()
+            {
+                PropertyAction::from_field_kind(&msg.value).apply(
+                    &msg.path(),
+                    tools,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,417 @@
+use crate::{
+    interaction::InteractionMode,
+    message::MessageSender,
+    scene::{commands::tilemap::SetTileMapTilesCommand, EditorScene, Selection},
+    settings::Settings,
+    MSG_SYNC_FLAG,
+};
+use fyrox::{
+    core::{
+        algebra::{Point3, Vector2, Vector3},
+        color::Color,
+        log::{Log, MessageKind},
+        math::{plane::Plane, Rect},
+        pool::Handle,
+        reflect::prelude::*,
+    },
+    engine::Engine,
+    fxhash::FxHashMap,
+    gui::{
+        inspector::{
+            editors::{
+                enumeration::EnumPropertyEditorDefinition, PropertyEditorDefinitionContainer,
+            },
+            Inspector, InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, UiNode, UserInterface,
+    },
+    scene::{
+        base::BaseBuilder,
+        camera::Camera,
+        dim2::{rectangle::RectangleBuilder, tilemap::TileMap},
+        graph::Graph,
+        node::Node,
+    },
+};
+use std::rc::Rc;
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Reflect, AsRefStr, EnumString, EnumVariantNames)]
+pub enum TileMapBrushTool {
+    Paint,
+    Erase,
+    Fill,
+    Rect,
+}
+
+#[derive(Clone, Debug, Reflect)]
+pub struct TileMapBrush {
+    pub tool: TileMapBrushTool,
+    #[reflect(description = "Index of a tile in the tile set of the tile map.")]
+    pub tile: u32,
+}
+
+pub struct TileMapInteractionMode {
+    message_sender: MessageSender,
+    brush: TileMapBrush,
+    brush_panel: BrushPanel,
+    brush_gizmo: Handle<Node>,
+    interacting: bool,
+    rect_start: Option<Vector2<i32>>,
+    last_tile: Option<Vector2<i32>>,
+    // Tiles that were at modified positions before the current stroke.
+    original_tiles: FxHashMap<Vector2<i32>, Option<u32>>,
+}
+
+impl TileMapInteractionMode {
+    pub fn new(
+        editor_scene: &EditorScene,
+        engine: &mut Engine,
+        message_sender: MessageSender,
+    ) -> Self {
+        let brush = TileMapBrush {
+            tool: TileMapBrushTool::Paint,
+            tile: 0,
+        };
+
+        let brush_panel = BrushPanel::new(&mut engine.user_interface.build_ctx(), &brush);
+
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        let brush_gizmo = RectangleBuilder::new(
+            BaseBuilder::new()
+                .with_name("TileMapBrush")
+                .with_visibility(false),
+        )
+        .with_color(Color::from_rgba(0, 255, 0, 100))
+        .build(graph);
+        graph.link_nodes(brush_gizmo, editor_scene.editor_objects_root);
+
+        Self {
+            message_sender,
+            brush,
+            brush_panel,
+            brush_gizmo,
+            interacting: false,
+            rect_start: None,
+            last_tile: None,
+            original_tiles: Default::default(),
+        }
+    }
+
+    fn set_tile(&mut self, tile_map: &mut TileMap, position: Vector2<i32>, tile: Option<u32>) {
+        self.original_tiles
+            .entry(position)
+            .or_insert_with(|| tile_map.tile(position));
+        tile_map.set_tile(position, tile);
+    }
+
+    fn apply_brush(&mut self, tile_map: &mut TileMap, position: Vector2<i32>, erase: bool) {
+        let tile = if erase { None } else { Some(self.brush.tile) };
+        match self.brush.tool {
+            TileMapBrushTool::Paint => self.set_tile(tile_map, position, tile),
+            TileMapBrushTool::Erase => self.set_tile(tile_map, position, None),
+            TileMapBrushTool::Fill => {
+                if tile_map.tile(position) != tile {
+                    for position in tile_map.flood_fill_region(position) {
+                        self.set_tile(tile_map, position, tile);
+                    }
+                }
+            }
+            // Rectangle is filled when the mouse button is released.
+            TileMapBrushTool::Rect => (),
+        }
+    }
+
+    fn update_gizmo(&self, graph: &mut Graph, tile_map: Handle<Node>, tile: Vector2<i32>) {
+        let rect = match (self.brush.tool, self.rect_start) {
+            (TileMapBrushTool::Rect, Some(start)) => tile_rect(start, tile),
+            _ => Rect::new(tile.x, tile.y, 1, 1),
+        };
+
+        let tile_map = match graph[tile_map].cast::<TileMap>() {
+            Some(tile_map) => tile_map,
+            None => return,
+        };
+        let min = tile_map.tile_rect(rect.position);
+        let max = tile_map.tile_rect(rect.position + rect.size - Vector2::new(1, 1));
+        let size = Vector2::new(max.x() + max.w() - min.x(), max.y() + max.h() - min.y());
+        let center = tile_map.global_transform().transform_point(&Point3::new(
+            min.x() + size.x * 0.5,
+            min.y() + size.y * 0.5,
+            0.0,
+        ));
+
+        graph[self.brush_gizmo]
+            .local_transform_mut()
+            // Put the gizmo slightly closer to the camera, so it won't be hidden by the tiles.
+            .set_position(Vector3::new(center.x, center.y, center.z - 0.01))
+            .set_scale(Vector3::new(size.x, size.y, 1.0));
+    }
+}
+
+fn tile_rect(a: Vector2<i32>, b: Vector2<i32>) -> Rect<i32> {
+    let min = a.inf(&b);
+    let max = a.sup(&b);
+    Rect::new(min.x, min.y, max.x - min.x + 1, max.y - min.y + 1)
+}
+
+fn selected_tile_map(editor_scene: &EditorScene, graph: &Graph) -> Option<Handle<Node>> {
+    if let Selection::Graph(selection) = &editor_scene.selection {
+        if selection.is_single_selection() {
+            let handle = selection.nodes()[0];
+            if graph.try_get_of_type::<TileMap>(handle).is_some() {
+                return Some(handle);
+            }
+        }
+    }
+    None
+}
+
+fn pick_tile(
+    graph: &Graph,
+    camera: Handle<Node>,
+    tile_map: Handle<Node>,
+    mouse_position: Vector2<f32>,
+    frame_size: Vector2<f32>,
+) -> Option<Vector2<i32>> {
+    let camera = graph[camera].cast::<Camera>()?;
+    let tile_map = graph[tile_map].cast::<TileMap>()?;
+    let ray = camera.make_ray(mouse_position, frame_size);
+    let plane = Plane::from_normal_and_point(&tile_map.look_vector(), &tile_map.global_position())?;
+    let point = ray.plane_intersection_point(&plane)?;
+    Some(tile_map.world_to_tile(point))
+}
+
+impl InteractionMode for TileMapInteractionMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let erase = engine.user_interface.keyboard_modifiers().shift;
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        if let Some(handle) = selected_tile_map(editor_scene, graph) {
+            if let Some(tile) = pick_tile(
+                graph,
+                editor_scene.camera_controller.camera,
+                handle,
+                mouse_pos,
+                frame_size,
+            ) {
+                self.interacting = true;
+                self.rect_start = Some(tile);
+                self.last_tile = Some(tile);
+                if let Some(tile_map) = graph[handle].cast_mut::<TileMap>() {
+                    self.apply_brush(tile_map, tile, erase);
+                }
+            }
+        }
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        if !self.interacting {
+            return;
+        }
+        self.interacting = false;
+
+        let erase = engine.user_interface.keyboard_modifiers().shift;
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        let handle = match selected_tile_map(editor_scene, graph) {
+            Some(handle) => handle,
+            None => return,
+        };
+        let tile_map = match graph[handle].cast_mut::<TileMap>() {
+            Some(tile_map) => tile_map,
+            None => return,
+        };
+
+        if let (TileMapBrushTool::Rect, Some(start), Some(end)) =
+            (self.brush.tool, self.rect_start, self.last_tile)
+        {
+            let rect = tile_rect(start, end);
+            let tile = if erase { None } else { Some(self.brush.tile) };
+            for y in rect.y()..(rect.y() + rect.h()) {
+                for x in rect.x()..(rect.x() + rect.w()) {
+                    self.set_tile(tile_map, Vector2::new(x, y), tile);
+                }
+            }
+        }
+        self.rect_start = None;
+
+        // Revert the changes made during the stroke and do them again using a command, so they
+        // could be undone.
+        let mut tiles = Vec::new();
+        for (position, original) in self.original_tiles.drain() {
+            let new = tile_map.set_tile(position, original);
+            if new != original {
+                tiles.push((position, new));
+            }
+        }
+
+        if !tiles.is_empty() {
+            self.message_sender
+                .do_scene_command(SetTileMapTilesCommand::new(handle, tiles));
+        }
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        _mouse_offset: Vector2<f32>,
+        mouse_position: Vector2<f32>,
+        camera: Handle<Node>,
+        editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+        frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let erase = engine.user_interface.keyboard_modifiers().shift;
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        let handle = match selected_tile_map(editor_scene, graph) {
+            Some(handle) => handle,
+            None => {
+                graph[self.brush_gizmo].set_visibility(false);
+                return;
+            }
+        };
+
+        if let Some(tile) = pick_tile(graph, camera, handle, mouse_position, frame_size) {
+            if self.interacting && self.last_tile != Some(tile) {
+                if let Some(tile_map) = graph[handle].cast_mut::<TileMap>() {
+                    if matches!(
+                        self.brush.tool,
+                        TileMapBrushTool::Paint | TileMapBrushTool::Erase
+                    ) {
+                        self.apply_brush(tile_map, tile, erase);
+                    }
+                }
+            }
+            self.last_tile = Some(tile);
+
+            graph[self.brush_gizmo].set_visibility(true);
+            self.update_gizmo(graph, handle, tile);
+        }
+    }
+
+    fn activate(&mut self, _editor_scene: &EditorScene, engine: &mut Engine) {
+        self.brush_panel
+            .sync_to_model(&mut engine.user_interface, &self.brush);
+
+        engine.user_interface.send_message(WindowMessage::open(
+            self.brush_panel.window,
+            MessageDirection::ToWidget,
+            false,
+        ));
+    }
+
+    fn deactivate(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
+        engine.scenes[editor_scene.scene].graph[self.brush_gizmo].set_visibility(false);
+
+        engine.user_interface.send_message(WindowMessage::close(
+            self.brush_panel.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        _editor_scene: &mut EditorScene,
+        _engine: &mut Engine,
+    ) {
+        self.brush_panel.handle_ui_message(message, &mut self.brush);
+    }
+
+    fn on_drop(&mut self, engine: &mut Engine) {
+        engine.user_interface.send_message(WidgetMessage::remove(
+            self.brush_panel.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+}
+
+struct BrushPanel {
+    window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+}
+
+impl BrushPanel {
+    fn new(ctx: &mut BuildContext, brush: &TileMapBrush) -> Self {
+        let property_editors = PropertyEditorDefinitionContainer::new();
+        property_editors.insert(EnumPropertyEditorDefinition::<TileMapBrushTool>::new());
+
+        let context = InspectorContext::from_object(
+            brush,
+            ctx,
+            Rc::new(property_editors),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+            Default::default(),
+        );
+
+        let inspector;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(200.0).with_height(120.0))
+            .can_close(false)
+            .with_content({
+                inspector = InspectorBuilder::new(WidgetBuilder::new())
+                    .with_context(context)
+                    .build(ctx);
+                inspector
+            })
+            .open(false)
+            .with_title(WindowTitle::text("Tile Map Brush"))
+            .build(ctx);
+
+        Self { window, inspector }
+    }
+
+    fn sync_to_model(&self, ui: &mut UserInterface, brush: &TileMapBrush) {
+        let ctx = ui
+            .node(self.inspector)
+            .cast::<Inspector>()
+            .expect("Must be Inspector!")
+            .context()
+            .clone();
+
+        if let Err(e) = ctx.sync(brush, ui, 0, true, Default::default()) {
+            Log::writeln(
+                MessageKind::Error,
+                format!(
+                    "Failed to sync tile map BrushPanel's inspector. Reason: {:?}",
+                    e
+                ),
+            )
+        }
+    }
+
+    fn handle_ui_message(&self, message: &UiMessage, brush: &mut TileMapBrush) {
+        if message.destination() == self.inspector
+            && message.direction() == MessageDirection::FromWidget
+        {
+            if let Some(InspectorMessage::PropertyChanged(msg)) = message.data::<InspectorMessage>()
+            {
+                PropertyAction::from_field_kind(&msg.value).apply(
+                    &msg.path(),
+                    brush,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+            }
+        }
+    }
+}
@@ -53,6 +53,7 @@ use crate::{
         scale_mode::ScaleInteractionMode,
         select_mode::SelectInteractionMode,
         terrain::TerrainInteractionMode,
+        tilemap::TileMapInteractionMode,
         InteractionMode, InteractionModeKind,
     },
     light::LightPanel,
@@ -104,6 +105,7 @@ use fyrox::{
         key::HotKey,
         message::{MessageDirection, UiMessage},
         messagebox::{MessageBoxBuilder, MessageBoxButtons, MessageBoxMessage, MessageBoxResult},
+        style::Theme,
         ttf::Font,
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
@@ -384,7 +386,7 @@ impl SaveSceneConfirmationDialog {
     }
 
     fn handle_message(&mut self, message: &Message, sender: &MessageSender) {
-        if let Message::SaveScene(_) = message {
+        if let Message::SaveScene(_) | Message::SaveSceneAs { .. } = message {
             match std::mem::replace(&mut self.action, SaveSceneConfirmationDialogAction::None) {
                 SaveSceneConfirmationDialogAction::None => {}
                 SaveSceneConfirmationDialogAction::OpenLoadSceneDialog => {
@@ -539,6 +541,9 @@ impl Editor {
             )
             .unwrap(),
         );
+        // The look of the editor is defined by the default theme, it must be set before any widget
+        // is created, so built-in widgets will get their styles from it.
+        engine.user_interface.set_theme(Theme::default());
 
         let configurator = Configurator::new(
             message_sender.clone(),
@@ -878,6 +883,11 @@ impl Editor {
                 &mut self.engine,
                 self.message_sender.clone(),
             )),
+            Box::new(TileMapInteractionMode::new(
+                &editor_scene,
+                &mut self.engine,
+                self.message_sender.clone(),
+            )),
         ];
 
         self.command_stack = CommandStack::new(false);
@@ -966,6 +976,8 @@ impl Editor {
                 sender.send(Message::SetInteractionMode(InteractionModeKind::Navmesh));
             } else if hot_key == key_bindings.enable_terrain_mode {
                 sender.send(Message::SetInteractionMode(InteractionModeKind::Terrain));
+            } else if hot_key == key_bindings.enable_tile_map_mode {
+                sender.send(Message::SetInteractionMode(InteractionModeKind::TileMap));
             } else if hot_key == key_bindings.load_scene {
                 sender.send(Message::OpenLoadSceneDialog);
             } else if hot_key == key_bindings.save_scene {
@@ -1746,6 +1758,12 @@ impl Editor {
                         self.world_viewer.sync_selection = true;
                     }
                     Message::SaveScene(path) => self.save_current_scene(path),
+                    Message::SaveSceneAs { path, format } => {
+                        if let Some(editor_scene) = self.scene.as_mut() {
+                            editor_scene.format = format;
+                        }
+                        self.save_current_scene(path)
+                    }
                     Message::LoadScene(scene_path) => {
                         self.load_scene(scene_path);
                         needs_sync = true;
@@ -1806,8 +1824,10 @@ impl Editor {
                             .open_load_file_selector(&mut self.engine.user_interface);
                     }
                     Message::OpenSaveSceneDialog => {
-                        self.menu
-                            .open_save_file_selector(&mut self.engine.user_interface);
+                        self.menu.open_save_file_selector(
+                            self.scene.as_ref(),
+                            &mut self.engine.user_interface,
+                        );
                     }
                     Message::OpenSaveSceneConfirmationDialog(action) => {
                         self.save_scene_dialog
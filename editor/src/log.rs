@@ -4,6 +4,7 @@ use crate::{
 };
 use fyrox::{
     core::{
+        algebra::Vector2,
         log::{LogMessage, MessageKind},
         pool::Handle,
         scope_profile,
@@ -13,21 +14,106 @@ use fyrox::{
         button::ButtonMessage,
         copypasta::ClipboardProvider,
         dropdown_list::DropdownListMessage,
-        formatted_text::WrapMode,
+        formatted_text::{FormattedTextBuilder, WrapMode},
         grid::{Column, GridBuilder, Row},
-        list_view::{ListView, ListViewBuilder, ListViewMessage},
+        list_view::{ItemProvider, ListViewBuilder, ListViewMessage},
         menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
         message::{MessageDirection, UiMessage},
         popup::{Placement, PopupBuilder, PopupMessage},
         scroll_viewer::ScrollViewerBuilder,
         stack_panel::StackPanelBuilder,
-        text::{Text, TextBuilder},
-        widget::WidgetBuilder,
+        text::{Text, TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowTitle},
         BuildContext, HorizontalAlignment, Orientation, RcUiNodeHandle, Thickness, UiNode,
+        UserInterface, VerticalAlignment,
     },
 };
-use std::sync::mpsc::Receiver;
+use std::{cell::RefCell, rc::Rc, sync::mpsc::Receiver};
+
+/// Minimal height of a row of the log.
+const ROW_HEIGHT: f32 = 20.0;
+/// Vertical (and horizontal) space around the text of a row: border stroke and text margin.
+const ROW_PADDING: f32 = 4.0;
+
+struct LogEntry {
+    kind: MessageKind,
+    text: String,
+}
+
+/// Creates rows of the log on demand, so the log can hold any amount of messages.
+struct LogItemProvider {
+    entries: Rc<RefCell<Vec<LogEntry>>>,
+    context_menu: RcUiNodeHandle,
+}
+
+impl ItemProvider for LogItemProvider {
+    fn create_item(&self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        BorderBuilder::new(
+            WidgetBuilder::new().with_child(
+                TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_context_menu(self.context_menu.clone())
+                        .with_margin(Thickness::uniform(1.0)),
+                )
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_wrap(WrapMode::Word)
+                .build(ctx),
+            ),
+        )
+        .build(ctx)
+    }
+
+    fn bind_item(&self, ui: &UserInterface, item: Handle<UiNode>, index: usize) {
+        let entries = self.entries.borrow();
+        let entry = match entries.get(index) {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        ui.send_message(WidgetMessage::background(
+            item,
+            MessageDirection::ToWidget,
+            Brush::Solid(if index % 2 == 0 {
+                Color::opaque(70, 70, 70)
+            } else {
+                Color::opaque(40, 40, 40)
+            }),
+        ));
+
+        if let Some(&text) = ui.node(item).children().first() {
+            ui.send_message(WidgetMessage::foreground(
+                text,
+                MessageDirection::ToWidget,
+                Brush::Solid(match entry.kind {
+                    MessageKind::Information => Color::opaque(210, 210, 210),
+                    MessageKind::Warning => Color::ORANGE,
+                    MessageKind::Error => Color::RED,
+                }),
+            ));
+            ui.send_message(TextMessage::text(
+                text,
+                MessageDirection::ToWidget,
+                entry.text.clone(),
+            ));
+        }
+    }
+
+    fn item_height(&self, ui: &UserInterface, index: usize, width: f32) -> Option<f32> {
+        let entries = self.entries.borrow();
+        let entry = entries.get(index)?;
+
+        // Long messages are wrapped, so the row must be tall enough to fit every line.
+        let text_size = FormattedTextBuilder::new(ui.default_font.clone())
+            .with_text(entry.text.clone())
+            .with_wrap(WrapMode::Word)
+            .with_constraint(Vector2::new((width - ROW_PADDING).max(0.0), f32::INFINITY))
+            .build()
+            .build();
+
+        Some((text_size.y + ROW_PADDING).max(ROW_HEIGHT))
+    }
+}
 
 struct ContextMenu {
     menu: RcUiNodeHandle,
@@ -88,6 +174,7 @@ pub struct LogPanel {
     severity: MessageKind,
     severity_list: Handle<UiNode>,
     context_menu: ContextMenu,
+    entries: Rc<RefCell<Vec<LogEntry>>>,
 }
 
 impl LogPanel {
@@ -95,6 +182,8 @@ impl LogPanel {
         let messages;
         let clear;
         let severity_list;
+        let context_menu = ContextMenu::new(ctx);
+        let entries = Rc::new(RefCell::new(Vec::new()));
         let window = WindowBuilder::new(WidgetBuilder::new())
             .can_minimize(false)
             .with_title(WindowTitle::Text("Message Log".to_owned()))
@@ -154,6 +243,13 @@ impl LogPanel {
                                 .with_vertical_scroll_allowed(true)
                                 .build(ctx),
                             )
+                            .with_item_provider(
+                                Rc::new(LogItemProvider {
+                                    entries: entries.clone(),
+                                    context_menu: context_menu.menu.clone(),
+                                }),
+                                ROW_HEIGHT,
+                            )
                             .build(ctx);
                             messages
                         }),
@@ -165,8 +261,6 @@ impl LogPanel {
             )
             .build(ctx);
 
-        let context_menu = ContextMenu::new(ctx);
-
         Self {
             window,
             messages,
@@ -175,6 +269,7 @@ impl LogPanel {
             severity: MessageKind::Warning,
             severity_list,
             context_menu,
+            entries,
         }
     }
 
@@ -183,11 +278,14 @@ impl LogPanel {
 
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             if message.destination() == self.clear {
-                engine.user_interface.send_message(ListViewMessage::items(
-                    self.messages,
-                    MessageDirection::ToWidget,
-                    vec![],
-                ));
+                self.entries.borrow_mut().clear();
+                engine
+                    .user_interface
+                    .send_message(ListViewMessage::item_count(
+                        self.messages,
+                        MessageDirection::ToWidget,
+                        0,
+                    ));
             }
         } else if let Some(DropdownListMessage::SelectionChanged(Some(idx))) =
             message.data::<DropdownListMessage>()
@@ -208,68 +306,34 @@ impl LogPanel {
     }
 
     pub fn update(&mut self, engine: &mut Engine) {
-        let mut count = engine
-            .user_interface
-            .node(self.messages)
-            .cast::<ListView>()
-            .map(|v| v.items().len())
-            .unwrap_or_default();
-
-        let mut item_to_bring_into_view = Handle::NONE;
+        let mut entries = self.entries.borrow_mut();
+        let prev_count = entries.len();
 
         while let Ok(msg) = self.receiver.try_recv() {
             if msg.kind < self.severity {
                 continue;
             }
 
-            let text = format!("[{:.2}s] {}", msg.time.as_secs_f32(), msg.content);
-
-            let ctx = &mut engine.user_interface.build_ctx();
-            let item = BorderBuilder::new(
-                WidgetBuilder::new()
-                    .with_background(Brush::Solid(if count % 2 == 0 {
-                        Color::opaque(70, 70, 70)
-                    } else {
-                        Color::opaque(40, 40, 40)
-                    }))
-                    .with_child(
-                        TextBuilder::new(
-                            WidgetBuilder::new()
-                                .with_context_menu(self.context_menu.menu.clone())
-                                .with_margin(Thickness::uniform(1.0))
-                                .with_foreground(Brush::Solid(match msg.kind {
-                                    MessageKind::Information => Color::opaque(210, 210, 210),
-                                    MessageKind::Warning => Color::ORANGE,
-                                    MessageKind::Error => Color::RED,
-                                })),
-                        )
-                        .with_text(text)
-                        .with_wrap(WrapMode::Word)
-                        .build(ctx),
-                    ),
-            )
-            .build(ctx);
+            entries.push(LogEntry {
+                kind: msg.kind,
+                text: format!("[{:.2}s] {}", msg.time.as_secs_f32(), msg.content),
+            });
+        }
 
+        if entries.len() != prev_count {
             engine
                 .user_interface
-                .send_message(ListViewMessage::add_item(
+                .send_message(ListViewMessage::item_count(
                     self.messages,
                     MessageDirection::ToWidget,
-                    item,
+                    entries.len(),
                 ));
-
-            item_to_bring_into_view = item;
-
-            count += 1;
-        }
-
-        if item_to_bring_into_view.is_some() {
             engine
                 .user_interface
-                .send_message(ListViewMessage::bring_item_into_view(
+                .send_message(ListViewMessage::bring_index_into_view(
                     self.messages,
                     MessageDirection::ToWidget,
-                    item_to_bring_into_view,
+                    entries.len() - 1,
                 ));
         }
     }
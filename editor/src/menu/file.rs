@@ -7,7 +7,7 @@ use crate::{
     Engine, Message, Mode, Panels, SaveSceneConfirmationDialogAction,
 };
 use fyrox::{
-    core::pool::Handle,
+    core::{pool::Handle, visitor::VisitorFormat},
     gui::{
         file_browser::{FileSelectorBuilder, FileSelectorMessage},
         menu::MenuItemMessage,
@@ -24,6 +24,7 @@ pub struct FileMenu {
     new_scene: Handle<UiNode>,
     pub save: Handle<UiNode>,
     pub save_as: Handle<UiNode>,
+    pub save_as_text: Handle<UiNode>,
     load: Handle<UiNode>,
     pub close_scene: Handle<UiNode>,
     exit: Handle<UiNode>,
@@ -36,6 +37,7 @@ pub struct FileMenu {
     pub recent_files_container: Handle<UiNode>,
     pub recent_files: Vec<Handle<UiNode>>,
     pub open_scene_settings: Handle<UiNode>,
+    save_format: VisitorFormat,
 }
 
 fn make_recent_files_items(
@@ -54,6 +56,7 @@ impl FileMenu {
         let new_scene;
         let save;
         let save_as;
+        let save_as_text;
         let close_scene;
         let load;
         let open_settings;
@@ -91,6 +94,10 @@ impl FileMenu {
                         create_menu_item_shortcut("Save Scene As...", "Ctrl+Shift+S", vec![], ctx);
                     save_as
                 },
+                {
+                    save_as_text = create_menu_item("Save Scene As Text...", vec![], ctx);
+                    save_as_text
+                },
                 {
                     load = create_menu_item_shortcut("Load Scene...", "Ctrl+L", vec![], ctx);
                     load
@@ -141,6 +148,7 @@ impl FileMenu {
             new_scene,
             save,
             save_as,
+            save_as_text,
             close_scene,
             load,
             exit,
@@ -151,6 +159,7 @@ impl FileMenu {
             recent_files_container,
             recent_files,
             open_scene_settings,
+            save_format: Default::default(),
         }
     }
 
@@ -176,7 +185,13 @@ impl FileMenu {
         ));
     }
 
-    pub fn open_save_file_selector(&self, ui: &mut UserInterface) {
+    pub fn open_save_file_selector(
+        &mut self,
+        editor_scene: Option<&EditorScene>,
+        ui: &mut UserInterface,
+    ) {
+        // Keep the format the scene was loaded or last saved with.
+        self.save_format = editor_scene.map_or(VisitorFormat::Binary, |s| s.format);
         ui.send_message(WindowMessage::open_modal(
             self.save_file_selector,
             MessageDirection::ToWidget,
@@ -203,7 +218,10 @@ impl FileMenu {
 
         if let Some(FileSelectorMessage::Commit(path)) = message.data::<FileSelectorMessage>() {
             if message.destination() == self.save_file_selector {
-                sender.send(Message::SaveScene(path.to_owned()));
+                sender.send(Message::SaveSceneAs {
+                    path: path.to_owned(),
+                    format: self.save_format,
+                });
             } else if message.destination() == self.load_file_selector {
                 sender.send(Message::LoadScene(path.to_owned()));
             }
@@ -213,6 +231,9 @@ impl FileMenu {
                     sender.send(Message::SaveScene(scene_path.clone()));
                 } else {
                     // If scene wasn't saved yet - open Save As window.
+                    self.save_format = editor_scene
+                        .as_ref()
+                        .map_or(VisitorFormat::Binary, |s| s.format);
                     engine
                         .user_interface
                         .send_message(WindowMessage::open_modal(
@@ -228,7 +249,14 @@ impl FileMenu {
                             std::env::current_dir().unwrap(),
                         ));
                 }
-            } else if message.destination() == self.save_as {
+            } else if message.destination() == self.save_as
+                || message.destination() == self.save_as_text
+            {
+                self.save_format = if message.destination() == self.save_as_text {
+                    VisitorFormat::Text
+                } else {
+                    VisitorFormat::Binary
+                };
                 engine
                     .user_interface
                     .send_message(WindowMessage::open_modal(
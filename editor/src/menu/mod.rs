@@ -137,8 +137,12 @@ impl Menu {
         self.file_menu.open_load_file_selector(ui)
     }
 
-    pub fn open_save_file_selector(&self, ui: &mut UserInterface) {
-        self.file_menu.open_save_file_selector(ui)
+    pub fn open_save_file_selector(
+        &mut self,
+        editor_scene: Option<&EditorScene>,
+        ui: &mut UserInterface,
+    ) {
+        self.file_menu.open_save_file_selector(editor_scene, ui)
     }
 
     pub fn sync_to_model(&mut self, editor_scene: Option<&EditorScene>, ui: &mut UserInterface) {
@@ -148,6 +152,7 @@ impl Menu {
             self.file_menu.close_scene,
             self.file_menu.save,
             self.file_menu.save_as,
+            self.file_menu.save_as_text,
             self.create_entity_menu.menu,
             self.edit_menu.menu,
             self.file_menu.open_scene_settings,
@@ -2,27 +2,36 @@ use crate::menu::create_menu_item;
 use fyrox::{
     core::pool::Handle,
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
-    scene::{base::BaseBuilder, collider::*, joint::*, node::Node, rigidbody::RigidBodyBuilder},
+    scene::{
+        base::BaseBuilder, character_controller::CharacterControllerBuilder, collider::*, joint::*,
+        node::Node, rigidbody::RigidBodyBuilder,
+    },
 };
 
 pub struct PhysicsMenu {
     pub menu: Handle<UiNode>,
     create_rigid_body: Handle<UiNode>,
+    create_character_controller: Handle<UiNode>,
     create_revolute_joint: Handle<UiNode>,
     create_ball_joint: Handle<UiNode>,
     create_prismatic_joint: Handle<UiNode>,
     create_fixed_joint: Handle<UiNode>,
+    create_six_dof_joint: Handle<UiNode>,
+    create_rope_joint: Handle<UiNode>,
     create_collider: Handle<UiNode>,
 }
 
 impl PhysicsMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_rigid_body;
+        let create_character_controller;
         let create_collider;
         let create_revolute_joint;
         let create_ball_joint;
         let create_prismatic_joint;
         let create_fixed_joint;
+        let create_six_dof_joint;
+        let create_rope_joint;
         let menu = create_menu_item(
             "Physics",
             vec![
@@ -34,6 +43,11 @@ impl PhysicsMenu {
                     create_collider = create_menu_item("Collider", vec![], ctx);
                     create_collider
                 },
+                {
+                    create_character_controller =
+                        create_menu_item("Character Controller", vec![], ctx);
+                    create_character_controller
+                },
                 {
                     create_revolute_joint = create_menu_item("Revolute Joint", vec![], ctx);
                     create_revolute_joint
@@ -50,6 +64,14 @@ impl PhysicsMenu {
                     create_fixed_joint = create_menu_item("Fixed Joint", vec![], ctx);
                     create_fixed_joint
                 },
+                {
+                    create_six_dof_joint = create_menu_item("Six DOF Joint", vec![], ctx);
+                    create_six_dof_joint
+                },
+                {
+                    create_rope_joint = create_menu_item("Rope Joint", vec![], ctx);
+                    create_rope_joint
+                },
             ],
             ctx,
         );
@@ -57,10 +79,13 @@ impl PhysicsMenu {
         Self {
             menu,
             create_rigid_body,
+            create_character_controller,
             create_revolute_joint,
             create_ball_joint,
             create_prismatic_joint,
             create_fixed_joint,
+            create_six_dof_joint,
+            create_rope_joint,
             create_collider,
         }
     }
@@ -75,6 +100,13 @@ impl PhysicsMenu {
                         .with_params(JointParams::RevoluteJoint(Default::default()))
                         .build_node(),
                 )
+            } else if message.destination() == self.create_character_controller {
+                Some(
+                    CharacterControllerBuilder::new(
+                        BaseBuilder::new().with_name("Character Controller"),
+                    )
+                    .build_node(),
+                )
             } else if message.destination() == self.create_ball_joint {
                 Some(
                     JointBuilder::new(BaseBuilder::new().with_name("Ball Joint"))
@@ -93,6 +125,18 @@ impl PhysicsMenu {
                         .with_params(JointParams::FixedJoint(Default::default()))
                         .build_node(),
                 )
+            } else if message.destination() == self.create_six_dof_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Six DOF Joint"))
+                        .with_params(JointParams::SixDofJoint(Default::default()))
+                        .build_node(),
+                )
+            } else if message.destination() == self.create_rope_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Rope Joint"))
+                        .with_params(JointParams::RopeJoint(Default::default()))
+                        .build_node(),
+                )
             } else if message.destination == self.create_collider {
                 Some(
                     ColliderBuilder::new(BaseBuilder::new().with_name("Collider"))
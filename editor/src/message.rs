@@ -6,6 +6,7 @@ use fyrox::{
     core::{
         log::Log,
         pool::{ErasedHandle, Handle},
+        visitor::VisitorFormat,
     },
     gui::UiNode,
     material::SharedMaterial,
@@ -23,6 +24,12 @@ pub enum Message {
         old_selection: Selection,
     },
     SaveScene(PathBuf),
+    /// Saves current scene to the given path using the given format. The format will be used for
+    /// further saves of the scene.
+    SaveSceneAs {
+        path: PathBuf,
+        format: VisitorFormat,
+    },
     LoadScene(PathBuf),
     CloseScene,
     SetInteractionMode(InteractionModeKind),
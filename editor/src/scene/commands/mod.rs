@@ -27,6 +27,7 @@ pub mod mesh;
 pub mod navmesh;
 pub mod sound_context;
 pub mod terrain;
+pub mod tilemap;
 
 #[macro_export]
 macro_rules! get_set_swap {
@@ -3,7 +3,10 @@ use fyrox::resource::texture::{TextureKind, TexturePixelKind, TextureResourceExt
 use fyrox::{
     core::pool::Handle,
     resource::texture::TextureResource,
-    scene::{node::Node, terrain::Layer},
+    scene::{
+        node::Node,
+        terrain::{foliage::FoliageLayer, Layer},
+    },
     utils,
 };
 
@@ -88,6 +91,84 @@ impl Command for DeleteTerrainLayerCommand {
     }
 }
 
+#[derive(Debug)]
+pub struct AddTerrainFoliageLayerCommand {
+    terrain: Handle<Node>,
+    layer: Option<FoliageLayer>,
+    masks: Vec<TextureResource>,
+}
+
+impl AddTerrainFoliageLayerCommand {
+    pub fn new(terrain_handle: Handle<Node>) -> Self {
+        Self {
+            terrain: terrain_handle,
+            layer: Some(Default::default()),
+            masks: Default::default(),
+        }
+    }
+}
+
+impl Command for AddTerrainFoliageLayerCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Add Terrain Foliage Layer".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let terrain = context.scene.graph[self.terrain].as_terrain_mut();
+        terrain.add_foliage_layer(self.layer.take().unwrap(), std::mem::take(&mut self.masks));
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let terrain = context.scene.graph[self.terrain].as_terrain_mut();
+        let (layer, masks) = terrain.pop_foliage_layer().unwrap();
+        self.layer = Some(layer);
+        self.masks = masks;
+    }
+}
+
+#[derive(Debug)]
+pub struct DeleteTerrainFoliageLayerCommand {
+    terrain: Handle<Node>,
+    layer: Option<FoliageLayer>,
+    index: usize,
+    masks: Vec<TextureResource>,
+}
+
+impl DeleteTerrainFoliageLayerCommand {
+    pub fn new(terrain: Handle<Node>, index: usize) -> Self {
+        Self {
+            terrain,
+            layer: Default::default(),
+            index,
+            masks: Default::default(),
+        }
+    }
+}
+
+impl Command for DeleteTerrainFoliageLayerCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Delete Terrain Foliage Layer".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let (layer, masks) = context.scene.graph[self.terrain]
+            .as_terrain_mut()
+            .remove_foliage_layer(self.index);
+
+        self.layer = Some(layer);
+        self.masks = masks;
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let terrain = context.scene.graph[self.terrain].as_terrain_mut();
+        terrain.insert_foliage_layer(
+            self.layer.take().unwrap(),
+            std::mem::take(&mut self.masks),
+            self.index,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct ModifyTerrainHeightCommand {
     terrain: Handle<Node>,
@@ -212,3 +293,62 @@ impl Command for ModifyTerrainLayerMaskCommand {
         self.swap(context);
     }
 }
+
+#[derive(Debug)]
+pub struct ModifyTerrainFoliageMaskCommand {
+    terrain: Handle<Node>,
+    old_masks: Vec<Vec<u8>>,
+    new_masks: Vec<Vec<u8>>,
+    layer: usize,
+}
+
+impl ModifyTerrainFoliageMaskCommand {
+    pub fn new(
+        terrain: Handle<Node>,
+        old_masks: Vec<Vec<u8>>,
+        new_masks: Vec<Vec<u8>>,
+        layer: usize,
+    ) -> Self {
+        Self {
+            terrain,
+            old_masks,
+            new_masks,
+            layer,
+        }
+    }
+
+    pub fn swap(&mut self, context: &mut SceneContext) {
+        let terrain = context.scene.graph[self.terrain].as_terrain_mut();
+
+        // `chunks_mut` marks foliage of every chunk for regeneration.
+        for (i, chunk) in terrain.chunks_mut().iter_mut().enumerate() {
+            let old = &mut self.old_masks[i];
+            let new = &mut self.new_masks[i];
+            let chunk_mask = &mut chunk.foliage_masks[self.layer];
+
+            let mut texture_data = chunk_mask.data_ref();
+
+            for (mask_pixel, new_pixel) in
+                texture_data.modify().data_mut().iter_mut().zip(new.iter())
+            {
+                *mask_pixel = *new_pixel;
+            }
+
+            std::mem::swap(old, new);
+        }
+    }
+}
+
+impl Command for ModifyTerrainFoliageMaskCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Modify Terrain Foliage Mask".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{command::Command, scene::commands::SceneContext};
+use fyrox::{
+    core::{algebra::Vector2, pool::Handle},
+    scene::{dim2::tilemap::TileMap, node::Node},
+};
+
+#[derive(Debug)]
+pub struct SetTileMapTilesCommand {
+    tile_map: Handle<Node>,
+    // Each entry is a tile position and a tile that will be put there on next execute or revert.
+    tiles: Vec<(Vector2<i32>, Option<u32>)>,
+}
+
+impl SetTileMapTilesCommand {
+    pub fn new(tile_map: Handle<Node>, tiles: Vec<(Vector2<i32>, Option<u32>)>) -> Self {
+        Self { tile_map, tiles }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        let tile_map = context.scene.graph[self.tile_map]
+            .cast_mut::<TileMap>()
+            .expect("Must be a tile map!");
+        for (position, tile) in self.tiles.iter_mut() {
+            *tile = tile_map.set_tile(*position, *tile);
+        }
+    }
+}
+
+impl Command for SetTileMapTilesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Modify Tile Map".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
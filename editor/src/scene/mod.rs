@@ -6,7 +6,12 @@ use crate::{
 };
 use fyrox::core::log::Log;
 use fyrox::{
-    core::{color::Color, math::aabb::AxisAlignedBoundingBox, pool::Handle, visitor::Visitor},
+    core::{
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        visitor::{Visitor, VisitorFormat},
+    },
     engine::Engine,
     scene::{
         base::BaseBuilder,
@@ -23,8 +28,8 @@ use fyrox::{
     },
 };
 use std::fs::File;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 
 pub mod clipboard;
 pub mod property;
@@ -37,6 +42,9 @@ pub mod commands;
 pub struct EditorScene {
     pub has_unsaved_changes: bool,
     pub path: Option<PathBuf>,
+    /// Format that will be used to save the scene. It is detected automatically when a scene is
+    /// loaded, so the scene will be saved back in the same format.
+    pub format: VisitorFormat,
     pub scene: Handle<Scene>,
     // Handle to a root for all editor nodes.
     pub editor_objects_root: Handle<Node>,
@@ -48,6 +56,16 @@ pub struct EditorScene {
     pub graph_switches: GraphUpdateSwitches,
 }
 
+fn detect_scene_format(path: &Path) -> Option<VisitorFormat> {
+    let mut header = Vec::new();
+    File::open(path)
+        .ok()?
+        .take(64)
+        .read_to_end(&mut header)
+        .ok()?;
+    VisitorFormat::detect(&header)
+}
+
 pub fn is_scene_needs_to_be_saved(editor_scene: Option<&EditorScene>) -> bool {
     editor_scene
         .as_ref()
@@ -80,6 +98,10 @@ impl EditorScene {
         scene.graph.physics2d.integration_parameters.dt = Some(0.0);
 
         EditorScene {
+            format: path
+                .as_deref()
+                .and_then(detect_scene_format)
+                .unwrap_or_default(),
             path,
             editor_objects_root,
             scene_content_root,
@@ -129,7 +151,7 @@ impl EditorScene {
 
             let mut visitor = Visitor::new();
             pure_scene.save("Scene", &mut visitor).unwrap();
-            if let Err(e) = visitor.save_binary(&path) {
+            if let Err(e) = visitor.save_with_format(&path, self.format) {
                 Err(format!("Failed to save scene! Reason: {}", e))
             } else {
                 if settings.debugging.save_scene_in_text_form {
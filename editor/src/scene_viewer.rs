@@ -68,6 +68,7 @@ pub struct SceneViewer {
     scale_mode: Handle<UiNode>,
     navmesh_mode: Handle<UiNode>,
     terrain_mode: Handle<UiNode>,
+    tile_map_mode: Handle<UiNode>,
     camera_projection: Handle<UiNode>,
     play: Handle<UiNode>,
     stop: Handle<UiNode>,
@@ -153,6 +154,10 @@ impl SceneViewer {
             "Edit Terrain\n\nTerrain edit mode allows you to modify selected \
         terrain.";
 
+        let tile_map_mode_tooltip =
+            "Edit Tile Map - Shortcut: [7]\n\nTile map edit mode allows you to paint, erase and \
+        fill tiles of selected tile map. Hold Shift to erase tiles with any tool.";
+
         let frame;
         let select_mode;
         let move_mode;
@@ -160,6 +165,7 @@ impl SceneViewer {
         let scale_mode;
         let navmesh_mode;
         let terrain_mode;
+        let tile_map_mode;
         let selection_frame;
         let camera_projection;
         let play;
@@ -224,6 +230,15 @@ impl SceneViewer {
                         false,
                     );
                     terrain_mode
+                })
+                .with_child({
+                    tile_map_mode = make_interaction_mode_button(
+                        ctx,
+                        include_bytes!("../resources/embed/tile_map.png"),
+                        tile_map_mode_tooltip,
+                        false,
+                    );
+                    tile_map_mode
                 }),
         )
         .build(ctx);
@@ -428,6 +443,7 @@ impl SceneViewer {
             select_mode,
             navmesh_mode,
             terrain_mode,
+            tile_map_mode,
             camera_projection,
             click_mouse_pos: None,
             play,
@@ -464,6 +480,7 @@ impl SceneViewer {
                 InteractionModeKind::Rotate => self.rotate_mode,
                 InteractionModeKind::Navmesh => self.navmesh_mode,
                 InteractionModeKind::Terrain => self.terrain_mode,
+                InteractionModeKind::TileMap => self.tile_map_mode,
             };
 
             for mode_button in [
@@ -473,6 +490,7 @@ impl SceneViewer {
                 self.rotate_mode,
                 self.navmesh_mode,
                 self.terrain_mode,
+                self.tile_map_mode,
             ] {
                 let decorator = engine
                     .user_interface
@@ -520,6 +538,9 @@ impl SceneViewer {
             } else if message.destination() == self.terrain_mode {
                 self.sender
                     .send(Message::SetInteractionMode(InteractionModeKind::Terrain));
+            } else if message.destination() == self.tile_map_mode {
+                self.sender
+                    .send(Message::SetInteractionMode(InteractionModeKind::TileMap));
             } else if message.destination() == self.play {
                 self.sender.send(Message::SwitchToBuildMode);
             } else if message.destination() == self.stop {
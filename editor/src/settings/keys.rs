@@ -26,6 +26,8 @@ pub struct KeyBindings {
     pub enable_scale_mode: HotKey,
     pub enable_navmesh_mode: HotKey,
     pub enable_terrain_mode: HotKey,
+    #[serde(default = "default_enable_tile_map_mode")]
+    pub enable_tile_map_mode: HotKey,
     pub save_scene: HotKey,
     pub load_scene: HotKey,
     pub copy_selection: HotKey,
@@ -35,6 +37,10 @@ pub struct KeyBindings {
     pub remove_selection: HotKey,
 }
 
+fn default_enable_tile_map_mode() -> HotKey {
+    HotKey::from_key_code(KeyCode::Key7)
+}
+
 impl Default for KeyBindings {
     fn default() -> Self {
         Self {
@@ -55,6 +61,7 @@ impl Default for KeyBindings {
             enable_scale_mode: HotKey::from_key_code(KeyCode::Key4),
             enable_navmesh_mode: HotKey::from_key_code(KeyCode::Key5),
             enable_terrain_mode: HotKey::from_key_code(KeyCode::Key6),
+            enable_tile_map_mode: default_enable_tile_map_mode(),
             save_scene: HotKey::ctrl_key(KeyCode::S),
             load_scene: HotKey::ctrl_key(KeyCode::L),
             copy_selection: HotKey::ctrl_key(KeyCode::C),
@@ -24,15 +24,23 @@ fn impl_visit_struct(
         quote! { Ok(()) }
     } else {
         // `field.visit(..)?;` parts
-        let field_visits =
-            utils::create_field_visits(true, field_args.fields.iter(), field_args.style);
+        let field_visits = utils::create_field_visits(
+            true,
+            ty_args.migrate.is_some(),
+            field_args.fields.iter(),
+            field_args.style,
+        );
+
+        let (visit_version, migrate) = utils::create_version_visits(ty_args);
 
         quote! {
             let mut region = match visitor.enter_region(name) {
                 Ok(x) => x,
                 Err(err) => return Err(err),
             };
+            #visit_version
             #(#field_visits)*
+            #migrate
             Ok(())
         }
     };
@@ -155,7 +163,12 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
 
         match style {
             ast::Style::Struct => {
-                let field_visits = utils::create_field_visits(false, fields.iter(), style);
+                let field_visits = utils::create_field_visits(
+                    false,
+                    ty_args.migrate.is_some(),
+                    fields.iter(),
+                    style,
+                );
 
                 let idents = fields.iter().map(|field| {
                     let ident = &field.ident;
@@ -169,7 +182,12 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
                 }
             }
             ast::Style::Tuple => {
-                let field_visits = utils::create_field_visits(false, fields.iter(), style);
+                let field_visits = utils::create_field_visits(
+                    false,
+                    ty_args.migrate.is_some(),
+                    fields.iter(),
+                    style,
+                );
 
                 let idents = (0..fields.len()).map(|i| format_ident!("f{}", Index::from(i)));
 
@@ -185,6 +203,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
         }
     });
 
+    let (visit_version, migrate) = utils::create_version_visits(ty_args);
+
     utils::create_impl(
         ty_args,
         variant_args.iter().flat_map(|v| v.fields.iter()).cloned(),
@@ -194,6 +214,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
                  Err(err) => return Err(err),
              };
 
+             #visit_version
+
              let mut id = id(self);
              if let Err(err) = id.visit("Id", &mut region) {
                  return Err(err);
@@ -210,6 +232,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
                  #(#variant_visits)*
              }
 
+             #migrate
+
              return Ok(());
 
              #fn_id
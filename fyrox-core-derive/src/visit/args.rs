@@ -9,6 +9,20 @@ pub struct TypeArgs {
     pub generics: Generics,
     pub data: ast::Data<VariantArgs, FieldArgs>,
     // attrs: Vec<Attribute>
+    // ---
+    /// `#[visit(version = N)]`
+    ///
+    /// Current version of the type's data. It is saved along with the data and compared with the
+    /// stored version on load.
+    #[darling(default)]
+    pub version: Option<u32>,
+
+    /// `#[visit(migrate = "path::to::fn")]`
+    ///
+    /// Function that upgrades data of older versions, called after all the fields were visited
+    /// with `fn(&mut Self, old_version: u32, region: &mut Visitor) -> VisitResult` signature.
+    #[darling(default)]
+    pub migrate: Option<Path>,
 }
 
 /// Parsed from struct's or enum variant's field
@@ -32,6 +46,12 @@ pub struct FieldArgs {
     #[darling(default)]
     pub rename: Option<String>,
 
+    /// `#[visit(rename_from = "..")]`
+    ///
+    /// Old name(s) of the field, used on load when there is no data under the current name
+    #[darling(multiple)]
+    pub rename_from: Vec<String>,
+
     /// `#[visit(optional)]`
     ///
     /// Ignore missing field
@@ -52,6 +52,8 @@ fn create_impl_generics(
 pub fn create_field_visits<'a>(
     // false if enum variant
     is_struct: bool,
+    // true if the type has a `migrate` function, that is responsible for fields missing in old data
+    has_migrate: bool,
     fields: impl Iterator<Item = &'a args::FieldArgs>,
     field_style: ast::Style,
 ) -> Vec<TokenStream2> {
@@ -104,12 +106,12 @@ pub fn create_field_visits<'a>(
                 None => name,
             };
 
-            (ident, name, field.optional)
+            (ident, name, &field.rename_from, field.optional)
         })
         .collect::<Vec<_>>();
 
     let mut no_dup = FxHashSet::default();
-    for name in visit_args.iter().map(|(_, name, _)| name) {
+    for name in visit_args.iter().map(|(_, name, _, _)| name) {
         if !no_dup.insert(name) {
             panic!("duplicate visiting names detected!");
         }
@@ -119,11 +121,37 @@ pub fn create_field_visits<'a>(
 
     visit_args
         .iter()
-        .map(|(ident, name, optional)| {
+        .map(|(ident, name, rename_from, optional)| {
+            let name = if rename_from.is_empty() {
+                quote!(#name)
+            } else {
+                // Use the first old name that exists in the data, if there's no data under the
+                // current name.
+                let rename_from = rename_from.iter();
+                quote! {
+                    if !region.is_reading() || region.has_field_or_region(#name) {
+                        #name
+                    } #(else if region.has_field_or_region(#rename_from) {
+                        #rename_from
+                    })* else {
+                        #name
+                    }
+                }
+            };
+
             if *optional {
                 quote! {
                     #prefix #ident.visit(#name, &mut region).ok();
                 }
+            } else if has_migrate {
+                // Old data may not have the field at all, `migrate` will fill it in later.
+                quote! {
+                    if let Err(err) = #prefix #ident.visit(#name, &mut region) {
+                        if !is_outdated {
+                            return Err(err);
+                        }
+                    }
+                }
             } else {
                 quote! {
                     if let Err(err) = #prefix #ident.visit(#name, &mut region) {
@@ -134,3 +162,47 @@ pub fn create_field_visits<'a>(
         })
         .collect::<Vec<_>>()
 }
+
+/// Version related parts of `visit` body: one that goes right after entering the region and one
+/// that goes after all the fields were visited.
+pub fn create_version_visits(ty_args: &args::TypeArgs) -> (TokenStream2, TokenStream2) {
+    let version = match ty_args.version {
+        Some(version) => version,
+        None => {
+            assert!(
+                ty_args.migrate.is_none(),
+                "`migrate` function requires `version` to be specified!"
+            );
+            return (quote!(), quote!());
+        }
+    };
+
+    let visit_version = quote! {
+        let data_version = match region.visit_version(#version) {
+            Ok(x) => x,
+            Err(err) => return Err(err),
+        };
+    };
+
+    match &ty_args.migrate {
+        Some(migrate) => (
+            quote! {
+                #visit_version
+                let is_outdated = region.is_reading() && data_version < #version;
+            },
+            quote! {
+                if is_outdated {
+                    if let Err(err) = #migrate(self, data_version, &mut region) {
+                        return Err(err);
+                    }
+                }
+            },
+        ),
+        None => (
+            visit_version,
+            quote! {
+                let _ = data_version;
+            },
+        ),
+    }
+}
@@ -8,10 +8,11 @@ use std::{env, fs::File, io::Write, path::PathBuf};
 use futures::executor::block_on;
 use fyrox_core::visitor::prelude::*;
 
-/// Saves given `data` and overwrites `data_default` with the saved data.
+/// Saves given `data` and overwrites `data_default` with the saved data. `data_default` could be
+/// of a different type to test compatibility of data layouts.
 ///
 /// Test the equality after running this method!
-pub fn save_load<T: Visit>(test_name: &str, data: &mut T, data_default: &mut T) {
+pub fn save_load<T: Visit, U: Visit>(test_name: &str, data: &mut T, data_default: &mut U) {
     // Locate output path
     let (bin, txt) = {
         let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
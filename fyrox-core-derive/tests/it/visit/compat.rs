@@ -1,4 +1,4 @@
-//! Fight the compatibility hell with attributes! .. someday :)
+//! Fight the compatibility hell with attributes!
 
 use fyrox_core::visitor::prelude::*;
 
@@ -25,3 +25,141 @@ fn rename() {
 
     assert_eq!(data, data_default);
 }
+
+#[derive(Debug, Clone, PartialEq, Visit)]
+pub struct OldNames {
+    pub x: f32,
+    pub old_y: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Visit)]
+pub struct NewNames {
+    #[visit(rename_from = "X")]
+    pub position_x: f32,
+    #[visit(rename_from = "VeryOldY", rename_from = "OldY")]
+    pub position_y: f32,
+}
+
+#[test]
+fn rename_from() {
+    let mut old = OldNames { x: 1.0, old_y: 2.0 };
+    let mut new = NewNames {
+        position_x: 0.0,
+        position_y: 0.0,
+    };
+
+    super::save_load("rename_from_old", &mut old, &mut new);
+
+    assert_eq!(new.position_x, old.x);
+    assert_eq!(new.position_y, old.old_y);
+
+    // New data must be saved and loaded under new names.
+    let mut data = NewNames {
+        position_x: 3.0,
+        position_y: 4.0,
+    };
+
+    super::save_load("rename_from_new", &mut data, &mut new);
+
+    assert_eq!(data, new);
+}
+
+/// Initial version of the data, without any version specified (which means version 0).
+#[derive(Debug, Clone, PartialEq, Visit)]
+pub struct CircleV0 {
+    pub diameter: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Visit)]
+#[visit(version = 1, migrate = "Self::migrate")]
+pub struct Circle {
+    pub radius: f32,
+}
+
+impl Circle {
+    fn migrate(&mut self, version: u32, region: &mut Visitor) -> VisitResult {
+        assert_eq!(version, 0);
+
+        let mut diameter = 0.0f32;
+        diameter.visit("Diameter", region)?;
+        self.radius = diameter * 0.5;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Visit)]
+#[visit(version = 2, migrate = "migrate_shape")]
+pub enum Shape {
+    Circle(#[visit(optional)] f32),
+    Square(f32),
+}
+
+fn migrate_shape(shape: &mut Shape, version: u32, _region: &mut Visitor) -> VisitResult {
+    // Version 1 stored radius of circle, now it is diameter.
+    if version == 1 {
+        if let Shape::Circle(diameter) = shape {
+            *diameter *= 2.0;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn version_migration() {
+    let mut old = CircleV0 { diameter: 4.0 };
+    let mut new = Circle { radius: 0.0 };
+
+    super::save_load("version_migration_old", &mut old, &mut new);
+
+    assert_eq!(new.radius, 2.0);
+
+    // Up-to-date data must be loaded as is, without migration.
+    let mut data = Circle { radius: 5.0 };
+
+    super::save_load("version_migration_new", &mut data, &mut new);
+
+    assert_eq!(data, new);
+}
+
+#[test]
+fn version_is_saved() {
+    let mut visitor = Visitor::new();
+    let mut circle = Circle { radius: 1.0 };
+    circle.visit("Circle", &mut visitor).unwrap();
+    let mut old = CircleV0 { diameter: 1.0 };
+    old.visit("Old", &mut visitor).unwrap();
+
+    let mut visitor = Visitor::load_from_memory(visitor.save_binary_to_vec().unwrap()).unwrap();
+    {
+        let mut region = visitor.enter_region("Circle").unwrap();
+        assert_eq!(region.visit_version(1).unwrap(), 1);
+    }
+    {
+        let mut region = visitor.enter_region("Old").unwrap();
+        assert_eq!(region.visit_version(1).unwrap(), 0);
+    }
+}
+
+#[test]
+fn enum_version_migration() {
+    // Pretend that the data was saved with version 1.
+    let mut visitor = Visitor::new();
+    {
+        let mut region = visitor.enter_region("Data").unwrap();
+        region.visit_version(1).unwrap();
+        let mut id = 0u32;
+        id.visit("Id", &mut region).unwrap();
+        let mut radius = 1.5f32;
+        radius.visit("0", &mut region).unwrap();
+    }
+
+    let mut visitor = Visitor::load_from_memory(visitor.save_binary_to_vec().unwrap()).unwrap();
+    let mut shape = Shape::Square(0.0);
+    shape.visit("Data", &mut visitor).unwrap();
+    assert_eq!(shape, Shape::Circle(3.0));
+
+    let mut data = Shape::Circle(3.0);
+    super::save_load("enum_version_migration", &mut data, &mut shape);
+    assert_eq!(data, shape);
+}
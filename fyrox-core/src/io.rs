@@ -1,4 +1,9 @@
-use std::{io::Error, path::Path};
+use crate::vfs::Vfs;
+use std::{
+    fmt::{Display, Formatter},
+    io::Error,
+    path::Path,
+};
 
 #[derive(Debug)]
 pub enum FileLoadError {
@@ -6,6 +11,15 @@ pub enum FileLoadError {
     Custom(String),
 }
 
+impl Display for FileLoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileLoadError::Io(v) => Display::fmt(v, f),
+            FileLoadError::Custom(v) => Display::fmt(v, f),
+        }
+    }
+}
+
 impl From<std::io::Error> for FileLoadError {
     fn from(e: Error) -> Self {
         Self::Io(e)
@@ -27,7 +41,23 @@ impl From<wasm_bindgen::JsValue> for FileLoadError {
     }
 }
 
+/// Loads a file at the given path. The path is resolved through the [virtual file system](crate::vfs),
+/// which means that the file could be loaded from a mounted directory, a pak archive or an in-memory
+/// overlay. If no mount point contains the file, the path is loaded from the platform's file system.
 pub async fn load_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, FileLoadError> {
+    let vfs = Vfs::snapshot();
+    vfs.load_file(path).await
+}
+
+/// Checks if a file exists at the given path, taking the [virtual file system](crate::vfs) into account.
+pub async fn exists<P: AsRef<Path>>(path: P) -> bool {
+    let vfs = Vfs::snapshot();
+    vfs.exists(path).await
+}
+
+/// Loads a file directly from the platform's file system (or Android assets / fetch), bypassing
+/// the virtual file system.
+pub async fn load_file_native<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, FileLoadError> {
     #[cfg(all(not(target_os = "android"), not(target_arch = "wasm32")))]
     {
         use std::fs::File;
@@ -73,7 +103,9 @@ pub async fn load_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, FileLoadError
     }
 }
 
-pub async fn exists<P: AsRef<Path>>(path: P) -> bool {
+/// Checks if a file exists in the platform's file system (or Android assets / fetch), bypassing
+/// the virtual file system.
+pub async fn exists_native<P: AsRef<Path>>(path: P) -> bool {
     #[cfg(all(not(target_os = "android"), not(target_arch = "wasm32")))]
     {
         path.as_ref().exists()
@@ -43,6 +43,7 @@ pub mod reflect;
 pub mod sparse;
 pub mod sstorage;
 pub mod variable;
+pub mod vfs;
 pub mod visitor;
 pub mod watcher;
 
@@ -0,0 +1,528 @@
+//! Virtual file system (VFS) with mount points.
+//!
+//! # Overview
+//!
+//! Every file that the engine loads (resources, scenes, options files, etc.) is requested through
+//! [`crate::io::load_file`], which resolves the path using the global [`Vfs`] instance. The VFS is
+//! a list of mount points which are searched in priority order, the first mount point that contains
+//! the requested file wins. There are three kinds of mount points:
+//!
+//! - [`MountSource::Directory`] - a directory in the native file system (on Android - a directory in
+//!   the assets, on WebAssembly - a path on the web server).
+//! - [`MountSource::Archive`] - a pak archive (see [`pak`] module docs), a single file with an index
+//!   and optionally compressed entries. It is the preferred way of shipping game data.
+//! - [`MountSource::Memory`] - an in-memory overlay, that could be used to override files at runtime
+//!   (for example, for mods or procedurally generated content).
+//!
+//! Each mount point has a prefix which is stripped from a requested path before looking up the file
+//! in the mount point. For example, if an archive is mounted with `data` prefix, then `data/foo.rgs`
+//! request will look for `foo.rgs` entry in the archive.
+//!
+//! If no mount point contains the requested file, the VFS falls back to the native file system (this
+//! could be disabled by [`Vfs::set_native_fallback`]). This means that when nothing is mounted, the
+//! engine behaves exactly as if there is no VFS at all.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use fyrox_core::vfs::{pak::PakArchive, MemoryOverlay, Vfs};
+//! # async fn mount() {
+//! let archive = PakArchive::open("data.pak").await.unwrap();
+//!
+//! let mut vfs = Vfs::global().write();
+//! vfs.mount_archive("", archive, 0);
+//!
+//! // Files in the overlay will override the files from the archive.
+//! let overlay = MemoryOverlay::default();
+//! overlay.insert("data/settings.ron", b"()".to_vec());
+//! vfs.mount_memory("", overlay, 1);
+//! # }
+//! ```
+
+use crate::{
+    io::{self, FileLoadError},
+    parking_lot::RwLock,
+    vfs::pak::PakArchive,
+};
+use fxhash::FxHashMap;
+use std::{
+    fmt::{Debug, Formatter},
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+pub mod pak;
+
+lazy_static! {
+    static ref VFS: RwLock<Vfs> = RwLock::new(Vfs::default());
+}
+
+/// Normalizes a path to the form used as a key in archives and memory overlays: components are
+/// separated by `/`, `.` components are removed and `..` components are collapsed. Both `/` and `\`
+/// are treated as separators on every platform.
+pub fn normalize_path<P: AsRef<Path>>(path: P) -> String {
+    let path = path.as_ref().to_string_lossy().replace('\\', "/");
+    let mut components: Vec<String> = Vec::new();
+    for component in Path::new(&path).components() {
+        match component {
+            Component::Normal(name) => components.push(name.to_string_lossy().to_string()),
+            Component::ParentDir => {
+                components.pop();
+            }
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+        }
+    }
+    components.join("/")
+}
+
+/// Normalizes a path (see [`normalize_path`]) for matching against mount point prefixes. Unlike
+/// [`normalize_path`], it keeps absolute paths absolute.
+fn normalize_mount_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let normalized = normalize_path(&path);
+    if path.as_ref().has_root() {
+        Path::new("/").join(normalized)
+    } else {
+        PathBuf::from(normalized)
+    }
+}
+
+/// An in-memory set of files. The overlay is shared - every clone of it refers to the same set of
+/// files, so it is possible to modify the overlay after it was mounted.
+#[derive(Clone, Default)]
+pub struct MemoryOverlay {
+    files: Arc<RwLock<FxHashMap<String, Arc<Vec<u8>>>>>,
+}
+
+impl Debug for MemoryOverlay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.files.read().keys()).finish()
+    }
+}
+
+impl MemoryOverlay {
+    /// Adds a new file or replaces an existing one.
+    pub fn insert<P: AsRef<Path>>(&self, path: P, data: Vec<u8>) {
+        self.files
+            .write()
+            .insert(normalize_path(path), Arc::new(data));
+    }
+
+    /// Removes a file from the overlay. Returns `true` if the file was in the overlay.
+    pub fn remove<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.files.write().remove(&normalize_path(path)).is_some()
+    }
+
+    /// Returns `true` if the overlay contains a file at the given path.
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.files.read().contains_key(&normalize_path(path))
+    }
+
+    /// Returns a copy of the content of a file at the given path.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<Vec<u8>> {
+        self.files
+            .read()
+            .get(&normalize_path(path))
+            .map(|data| (**data).clone())
+    }
+
+    /// Removes every file from the overlay.
+    pub fn clear(&self) {
+        self.files.write().clear();
+    }
+}
+
+/// A source of files for a mount point.
+#[derive(Debug)]
+pub enum MountSource {
+    /// A directory in the native file system.
+    Directory(PathBuf),
+    /// A pak archive.
+    Archive(PakArchive),
+    /// An in-memory overlay.
+    Memory(MemoryOverlay),
+}
+
+/// A unique identifier of a mount point, it could be used to unmount the mount point.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MountId(u64);
+
+/// A mount point of the virtual file system. See module docs for more info.
+#[derive(Debug)]
+pub struct MountPoint {
+    id: MountId,
+    prefix: PathBuf,
+    priority: i32,
+    source: MountSource,
+}
+
+enum Lookup {
+    Data(Result<Vec<u8>, FileLoadError>),
+    Native(PathBuf),
+}
+
+impl MountPoint {
+    /// Returns the unique id of the mount point.
+    pub fn id(&self) -> MountId {
+        self.id
+    }
+
+    /// Returns the prefix of the mount point.
+    pub fn prefix(&self) -> &Path {
+        &self.prefix
+    }
+
+    /// Returns the priority of the mount point.
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Returns the source of the mount point.
+    pub fn source(&self) -> &MountSource {
+        &self.source
+    }
+
+    fn local_path(&self, path: &Path) -> Option<PathBuf> {
+        normalize_mount_path(path)
+            .strip_prefix(&self.prefix)
+            .ok()
+            .map(|local_path| local_path.to_path_buf())
+    }
+
+    async fn lookup(&self, path: &Path, load: bool) -> Option<Lookup> {
+        let local_path = self.local_path(path)?;
+        let local_path = local_path.as_path();
+        match self.source {
+            MountSource::Directory(ref directory) => {
+                let native_path = directory.join(local_path);
+                if io::exists_native(&native_path).await {
+                    Some(Lookup::Native(native_path))
+                } else {
+                    None
+                }
+            }
+            MountSource::Archive(ref archive) => {
+                let key = normalize_path(local_path);
+                if load {
+                    archive.read(&key).map(Lookup::Data)
+                } else if archive.contains(&key) {
+                    Some(Lookup::Data(Ok(Vec::new())))
+                } else {
+                    None
+                }
+            }
+            MountSource::Memory(ref overlay) => {
+                if load {
+                    overlay.get(local_path).map(|data| Lookup::Data(Ok(data)))
+                } else if overlay.contains(local_path) {
+                    Some(Lookup::Data(Ok(Vec::new())))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Virtual file system, see module docs for more info. The instance is cheap to clone, since mount
+/// points are reference counted.
+#[derive(Clone, Debug)]
+pub struct Vfs {
+    mount_points: Vec<Arc<MountPoint>>,
+    next_id: u64,
+    native_fallback: bool,
+}
+
+impl Default for Vfs {
+    fn default() -> Self {
+        Self {
+            mount_points: Default::default(),
+            next_id: 0,
+            native_fallback: true,
+        }
+    }
+}
+
+impl Vfs {
+    /// Returns a reference to the global VFS instance, that is used by [`crate::io::load_file`].
+    pub fn global() -> &'static RwLock<Vfs> {
+        &VFS
+    }
+
+    /// Returns a copy of the global VFS instance. Use this method in `async` code, to not hold the
+    /// lock across `.await` points.
+    pub fn snapshot() -> Vfs {
+        VFS.read().clone()
+    }
+
+    /// Mounts a directory of the native file system at the given prefix.
+    pub fn mount_directory<P: AsRef<Path>, D: AsRef<Path>>(
+        &mut self,
+        prefix: P,
+        directory: D,
+        priority: i32,
+    ) -> MountId {
+        self.mount(
+            prefix,
+            MountSource::Directory(directory.as_ref().to_path_buf()),
+            priority,
+        )
+    }
+
+    /// Mounts a pak archive at the given prefix.
+    pub fn mount_archive<P: AsRef<Path>>(
+        &mut self,
+        prefix: P,
+        archive: PakArchive,
+        priority: i32,
+    ) -> MountId {
+        self.mount(prefix, MountSource::Archive(archive), priority)
+    }
+
+    /// Mounts an in-memory overlay at the given prefix.
+    pub fn mount_memory<P: AsRef<Path>>(
+        &mut self,
+        prefix: P,
+        overlay: MemoryOverlay,
+        priority: i32,
+    ) -> MountId {
+        self.mount(prefix, MountSource::Memory(overlay), priority)
+    }
+
+    /// Mounts a source at the given prefix. Mount points with higher priority are searched first,
+    /// if two mount points have the same priority, the one that was mounted last is searched first.
+    pub fn mount<P: AsRef<Path>>(
+        &mut self,
+        prefix: P,
+        source: MountSource,
+        priority: i32,
+    ) -> MountId {
+        let id = MountId(self.next_id);
+        self.next_id += 1;
+
+        let position = self
+            .mount_points
+            .iter()
+            .position(|mp| mp.priority <= priority)
+            .unwrap_or(self.mount_points.len());
+
+        self.mount_points.insert(
+            position,
+            Arc::new(MountPoint {
+                id,
+                prefix: normalize_mount_path(prefix),
+                priority,
+                source,
+            }),
+        );
+
+        id
+    }
+
+    /// Removes a mount point with the given id. Returns `true` if the mount point existed.
+    pub fn unmount(&mut self, id: MountId) -> bool {
+        if let Some(position) = self.mount_points.iter().position(|mp| mp.id == id) {
+            self.mount_points.remove(position);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes every mount point.
+    pub fn unmount_all(&mut self) {
+        self.mount_points.clear();
+    }
+
+    /// Returns an iterator over mount points in the order of search.
+    pub fn mount_points(&self) -> impl Iterator<Item = &MountPoint> {
+        self.mount_points.iter().map(|mp| &**mp)
+    }
+
+    /// Sets whether the native file system should be used if no mount point contains a requested
+    /// file. Disabling the fallback is useful to ensure that a shipped game loads everything from
+    /// its archives. Enabled by default.
+    pub fn set_native_fallback(&mut self, enabled: bool) {
+        self.native_fallback = enabled;
+    }
+
+    /// Returns `true` if the native file system is used when no mount point contains a requested file.
+    pub fn is_native_fallback_enabled(&self) -> bool {
+        self.native_fallback
+    }
+
+    /// Loads a file at the given path, see module docs for the path resolution rules.
+    pub async fn load_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, FileLoadError> {
+        let path = path.as_ref();
+        for mount_point in self.mount_points.iter() {
+            match mount_point.lookup(path, true).await {
+                Some(Lookup::Data(result)) => return result,
+                Some(Lookup::Native(native_path)) => {
+                    return io::load_file_native(native_path).await
+                }
+                None => (),
+            }
+        }
+
+        if self.native_fallback {
+            io::load_file_native(path).await
+        } else {
+            Err(FileLoadError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!(
+                    "File {} is not found in the virtual file system!",
+                    path.display()
+                ),
+            )))
+        }
+    }
+
+    /// Checks if a file at the given path exists in any of mount points (or in the native file
+    /// system, if the fallback is enabled).
+    pub async fn exists<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path = path.as_ref();
+        for mount_point in self.mount_points.iter() {
+            if mount_point.lookup(path, false).await.is_some() {
+                return true;
+            }
+        }
+
+        self.native_fallback && io::exists_native(path).await
+    }
+
+    /// Returns a path in the native file system for the given path, if the file is provided by a
+    /// directory mount point (or by the native file system fallback). Returns `None` if the file
+    /// does not exist or if it is located in an archive or in an in-memory overlay. This method is
+    /// useful for code that wants to stream data directly from a file.
+    pub async fn native_path<P: AsRef<Path>>(&self, path: P) -> Option<PathBuf> {
+        let path = path.as_ref();
+        for mount_point in self.mount_points.iter() {
+            match mount_point.lookup(path, false).await {
+                Some(Lookup::Data(_)) => return None,
+                Some(Lookup::Native(native_path)) => return Some(native_path),
+                None => (),
+            }
+        }
+
+        if self.native_fallback && io::exists_native(path).await {
+            Some(path.to_path_buf())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        futures::executor::block_on,
+        vfs::{
+            normalize_path,
+            pak::{PakArchive, PakCompression, PakWriter},
+            MemoryOverlay, Vfs,
+        },
+    };
+    use std::{io::Cursor, path::Path};
+
+    fn make_archive(files: &[(&str, &[u8])]) -> PakArchive {
+        let mut writer = PakWriter::new(Cursor::new(Vec::new())).unwrap();
+        for (path, data) in files {
+            writer.add(path, data, PakCompression::Deflate, 6).unwrap();
+        }
+        PakArchive::from_memory(writer.finish().unwrap().into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("./data/textures/../foo.png"), "data/foo.png");
+        assert_eq!(
+            normalize_path(Path::new("data").join("bar.rgs")),
+            "data/bar.rgs"
+        );
+    }
+
+    #[test]
+    fn test_priority() {
+        let mut vfs = Vfs::default();
+        vfs.set_native_fallback(false);
+
+        vfs.mount_archive("", make_archive(&[("data/a.txt", b"archive")]), 0);
+
+        let overlay = MemoryOverlay::default();
+        vfs.mount_memory("", overlay.clone(), 1);
+
+        assert_eq!(block_on(vfs.load_file("data/a.txt")).unwrap(), b"archive");
+
+        // Overlay has higher priority and should override the file from the archive.
+        overlay.insert("data/a.txt", b"overlay".to_vec());
+        assert_eq!(block_on(vfs.load_file("data/a.txt")).unwrap(), b"overlay");
+        assert_eq!(block_on(vfs.native_path("data/a.txt")), None);
+
+        overlay.remove("data/a.txt");
+        assert_eq!(block_on(vfs.load_file("data/a.txt")).unwrap(), b"archive");
+    }
+
+    #[test]
+    fn test_same_priority_last_mounted_first() {
+        let mut vfs = Vfs::default();
+        vfs.set_native_fallback(false);
+
+        let first = vfs.mount_archive("", make_archive(&[("a.txt", b"first")]), 0);
+        vfs.mount_archive("", make_archive(&[("a.txt", b"second")]), 0);
+        assert_eq!(block_on(vfs.load_file("a.txt")).unwrap(), b"second");
+
+        assert!(vfs.unmount(first));
+        assert!(!vfs.unmount(first));
+        assert_eq!(vfs.mount_points().count(), 1);
+    }
+
+    #[test]
+    fn test_prefix() {
+        let mut vfs = Vfs::default();
+        vfs.set_native_fallback(false);
+
+        vfs.mount_archive("data", make_archive(&[("scene.rgs", b"scene")]), 0);
+
+        assert_eq!(block_on(vfs.load_file("data/scene.rgs")).unwrap(), b"scene");
+        assert!(block_on(vfs.exists("data/scene.rgs")));
+        assert!(!block_on(vfs.exists("scene.rgs")));
+        assert!(block_on(vfs.load_file("scene.rgs")).is_err());
+
+        // Paths must match the prefix regardless of their form.
+        assert_eq!(block_on(vfs.load_file("./data/scene.rgs")).unwrap(), b"scene");
+        assert_eq!(block_on(vfs.load_file("data\\scene.rgs")).unwrap(), b"scene");
+        assert_eq!(
+            block_on(vfs.load_file("data/textures/../scene.rgs")).unwrap(),
+            b"scene"
+        );
+
+        vfs.mount_archive("./assets/", make_archive(&[("a.png", b"png")]), 0);
+        assert_eq!(block_on(vfs.load_file("assets/a.png")).unwrap(), b"png");
+    }
+
+    #[test]
+    fn test_directory_and_native_fallback() {
+        let root = std::env::temp_dir().join("fyrox_vfs_test_directory");
+        std::fs::create_dir_all(root.join("textures")).unwrap();
+        std::fs::write(root.join("textures/a.txt"), b"directory").unwrap();
+
+        let mut vfs = Vfs::default();
+        vfs.set_native_fallback(false);
+        vfs.mount_directory("data", &root, 0);
+
+        assert_eq!(
+            block_on(vfs.load_file("data/textures/a.txt")).unwrap(),
+            b"directory"
+        );
+        assert_eq!(
+            block_on(vfs.native_path("data/textures/a.txt")),
+            Some(root.join("textures/a.txt"))
+        );
+
+        let native_path = root.join("textures/a.txt");
+        assert!(block_on(vfs.load_file(&native_path)).is_err());
+        vfs.set_native_fallback(true);
+        assert_eq!(block_on(vfs.load_file(&native_path)).unwrap(), b"directory");
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,572 @@
+//! Pak archives - a simple archive format for shipping game data.
+//!
+//! # Format
+//!
+//! All numbers are stored in little-endian byte order.
+//!
+//! ```text
+//! Header:
+//!     magic: [u8; 4] = "FPAK"
+//!     version: u32
+//!     index_offset: u64
+//! Data:
+//!     Entries data, one after another.
+//! Index (at index_offset):
+//!     entry_count: u32
+//!     entry_count times:
+//!         path_len: u32
+//!         path: [u8; path_len] - normalized path in UTF-8 (see `normalize_path`)
+//!         offset: u64 - offset of the entry data from the beginning of the archive
+//!         stored_size: u64 - size of the (possibly compressed) entry data
+//!         size: u64 - size of the entry data after decompression
+//!         compression: u8 - see `PakCompression`
+//! ```
+//!
+//! The index is stored at the end of the archive, so the archive could be written in a single pass.
+//! Every entry is compressed separately, which allows reading any entry without touching the others.
+//!
+//! Use [`pack_directory`] to turn a project folder into an archive.
+
+use crate::{
+    byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt},
+    io::FileLoadError,
+    vfs::normalize_path,
+};
+use fxhash::FxHashMap;
+use std::{
+    fmt::{Debug, Formatter},
+    io::{Cursor, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+/// Magic bytes at the beginning of every pak archive.
+pub const PAK_MAGIC: &[u8; 4] = b"FPAK";
+
+/// Current version of the pak archive format.
+pub const PAK_VERSION: u32 = 1;
+
+/// A compression method of a pak entry.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PakCompression {
+    /// Entry data is stored as is.
+    None = 0,
+    /// Entry data is compressed using Deflate.
+    Deflate = 1,
+}
+
+impl PakCompression {
+    fn from_u8(value: u8) -> Result<Self, FileLoadError> {
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Deflate),
+            _ => Err(FileLoadError::Custom(format!(
+                "Unknown pak entry compression {}!",
+                value
+            ))),
+        }
+    }
+}
+
+/// An entry of a pak archive index.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PakEntry {
+    /// Offset of the entry data from the beginning of the archive.
+    pub offset: u64,
+    /// Size of the (possibly compressed) entry data.
+    pub stored_size: u64,
+    /// Size of the entry data after decompression.
+    pub size: u64,
+    /// Compression method of the entry.
+    pub compression: PakCompression,
+}
+
+enum PakSource {
+    #[cfg(all(not(target_os = "android"), not(target_arch = "wasm32")))]
+    File(crate::parking_lot::Mutex<std::fs::File>),
+    Memory(Vec<u8>),
+}
+
+/// A read-only pak archive, see module docs for more info.
+pub struct PakArchive {
+    entries: FxHashMap<String, PakEntry>,
+    source: PakSource,
+}
+
+impl Debug for PakArchive {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PakArchive")
+            .field("entries", &self.entries.len())
+            .finish()
+    }
+}
+
+fn corrupted() -> FileLoadError {
+    FileLoadError::Custom("Pak archive is corrupted!".to_string())
+}
+
+fn read_index<R: Read + Seek>(
+    reader: &mut R,
+) -> Result<FxHashMap<String, PakEntry>, FileLoadError> {
+    // Every size and offset in the index is checked against the length of the archive, so a
+    // truncated or malformed archive can't cause huge allocations or out-of-bounds reads.
+    let length = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(0))?;
+
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != PAK_MAGIC {
+        return Err(FileLoadError::Custom("Not a pak archive!".to_string()));
+    }
+
+    let version = reader.read_u32::<LittleEndian>()?;
+    if version != PAK_VERSION {
+        return Err(FileLoadError::Custom(format!(
+            "Unsupported pak archive version {}!",
+            version
+        )));
+    }
+
+    let index_offset = reader.read_u64::<LittleEndian>()?;
+    if index_offset > length {
+        return Err(corrupted());
+    }
+    reader.seek(SeekFrom::Start(index_offset))?;
+
+    let entry_count = reader.read_u32::<LittleEndian>()?;
+    let mut entries = FxHashMap::default();
+    for _ in 0..entry_count {
+        let path_len = reader.read_u32::<LittleEndian>()? as u64;
+        if path_len > length - reader.stream_position()? {
+            return Err(corrupted());
+        }
+        let mut path = vec![0; path_len as usize];
+        reader.read_exact(&mut path)?;
+        let path = String::from_utf8(path)
+            .map_err(|_| FileLoadError::Custom("Invalid pak entry path!".to_string()))?;
+
+        let entry = PakEntry {
+            offset: reader.read_u64::<LittleEndian>()?,
+            stored_size: reader.read_u64::<LittleEndian>()?,
+            size: reader.read_u64::<LittleEndian>()?,
+            compression: PakCompression::from_u8(reader.read_u8()?)?,
+        };
+
+        if entry
+            .offset
+            .checked_add(entry.stored_size)
+            .map_or(true, |end| end > length)
+        {
+            return Err(corrupted());
+        }
+
+        entries.insert(path, entry);
+    }
+
+    Ok(entries)
+}
+
+fn decompress(entry: &PakEntry, data: Vec<u8>) -> Result<Vec<u8>, FileLoadError> {
+    let data = match entry.compression {
+        PakCompression::None => data,
+        PakCompression::Deflate => {
+            miniz_oxide::inflate::decompress_to_vec_with_limit(&data, entry.size as usize).map_err(
+                |e| FileLoadError::Custom(format!("Unable to decompress pak entry: {:?}", e)),
+            )?
+        }
+    };
+
+    if data.len() as u64 != entry.size {
+        return Err(FileLoadError::Custom(
+            "Pak entry size mismatch, the archive is corrupted!".to_string(),
+        ));
+    }
+
+    Ok(data)
+}
+
+impl PakArchive {
+    /// Opens an archive at the given path of the native file system. On PC only the index is read,
+    /// entries are read on demand. On other platforms the archive is loaded into memory entirely.
+    pub async fn open<P: AsRef<Path>>(path: P) -> Result<Self, FileLoadError> {
+        #[cfg(all(not(target_os = "android"), not(target_arch = "wasm32")))]
+        {
+            let mut file = std::io::BufReader::new(std::fs::File::open(path)?);
+            let entries = read_index(&mut file)?;
+            Ok(Self {
+                entries,
+                source: PakSource::File(crate::parking_lot::Mutex::new(file.into_inner())),
+            })
+        }
+
+        #[cfg(any(target_os = "android", target_arch = "wasm32"))]
+        {
+            Self::from_memory(crate::io::load_file_native(path).await?)
+        }
+    }
+
+    /// Creates an archive from a memory block.
+    pub fn from_memory(data: Vec<u8>) -> Result<Self, FileLoadError> {
+        let entries = read_index(&mut Cursor::new(&data))?;
+        Ok(Self {
+            entries,
+            source: PakSource::Memory(data),
+        })
+    }
+
+    /// Returns `true` if the archive contains an entry with the given normalized path.
+    pub fn contains(&self, path: &str) -> bool {
+        self.entries.contains_key(path)
+    }
+
+    /// Returns an entry with the given normalized path.
+    pub fn entry(&self, path: &str) -> Option<&PakEntry> {
+        self.entries.get(path)
+    }
+
+    /// Returns an iterator over the paths of all entries of the archive.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(|path| path.as_str())
+    }
+
+    /// Returns total amount of entries in the archive.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the archive has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Reads and decompresses an entry with the given normalized path. Returns `None` if there is
+    /// no such entry.
+    pub fn read(&self, path: &str) -> Option<Result<Vec<u8>, FileLoadError>> {
+        let entry = self.entries.get(path)?;
+        Some(self.read_entry(entry))
+    }
+
+    fn read_entry(&self, entry: &PakEntry) -> Result<Vec<u8>, FileLoadError> {
+        let data = match self.source {
+            #[cfg(all(not(target_os = "android"), not(target_arch = "wasm32")))]
+            PakSource::File(ref file) => {
+                let mut file = file.lock();
+                file.seek(SeekFrom::Start(entry.offset))?;
+                let mut data = vec![0; entry.stored_size as usize];
+                file.read_exact(&mut data)?;
+                data
+            }
+            PakSource::Memory(ref memory) => {
+                let begin = usize::try_from(entry.offset).map_err(|_| corrupted())?;
+                let end = usize::try_from(entry.stored_size)
+                    .ok()
+                    .and_then(|size| begin.checked_add(size))
+                    .ok_or_else(corrupted)?;
+                memory.get(begin..end).ok_or_else(corrupted)?.to_vec()
+            }
+        };
+
+        decompress(entry, data)
+    }
+}
+
+/// Writes pak archives, see module docs for more info.
+pub struct PakWriter<W: Write + Seek> {
+    writer: W,
+    entries: Vec<(String, PakEntry)>,
+}
+
+impl<W: Write + Seek> PakWriter<W> {
+    /// Creates a new writer and writes the archive header.
+    pub fn new(mut writer: W) -> Result<Self, FileLoadError> {
+        writer.write_all(PAK_MAGIC)?;
+        writer.write_u32::<LittleEndian>(PAK_VERSION)?;
+        // Index offset, will be written in `finish`.
+        writer.write_u64::<LittleEndian>(0)?;
+        Ok(Self {
+            writer,
+            entries: Default::default(),
+        })
+    }
+
+    /// Adds a new entry to the archive. Compression `level` is in `0..=10` range and ignored for
+    /// [`PakCompression::None`]. If compression does not reduce the size of the data, the entry is
+    /// stored uncompressed.
+    pub fn add<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        data: &[u8],
+        compression: PakCompression,
+        level: u8,
+    ) -> Result<(), FileLoadError> {
+        let (stored, compression) = match compression {
+            PakCompression::None => (None, PakCompression::None),
+            PakCompression::Deflate => {
+                let compressed = miniz_oxide::deflate::compress_to_vec(data, level.min(10));
+                if compressed.len() < data.len() {
+                    (Some(compressed), PakCompression::Deflate)
+                } else {
+                    (None, PakCompression::None)
+                }
+            }
+        };
+        let stored = stored.as_deref().unwrap_or(data);
+
+        let offset = self.writer.stream_position()?;
+        self.writer.write_all(stored)?;
+
+        self.entries.push((
+            normalize_path(path),
+            PakEntry {
+                offset,
+                stored_size: stored.len() as u64,
+                size: data.len() as u64,
+                compression,
+            },
+        ));
+
+        Ok(())
+    }
+
+    /// Writes the index and returns the inner writer.
+    pub fn finish(mut self) -> Result<W, FileLoadError> {
+        let index_offset = self.writer.stream_position()?;
+
+        self.writer
+            .write_u32::<LittleEndian>(self.entries.len() as u32)?;
+        for (path, entry) in self.entries.iter() {
+            self.writer.write_u32::<LittleEndian>(path.len() as u32)?;
+            self.writer.write_all(path.as_bytes())?;
+            self.writer.write_u64::<LittleEndian>(entry.offset)?;
+            self.writer.write_u64::<LittleEndian>(entry.stored_size)?;
+            self.writer.write_u64::<LittleEndian>(entry.size)?;
+            self.writer.write_u8(entry.compression as u8)?;
+        }
+
+        self.writer.seek(SeekFrom::Start(
+            (PAK_MAGIC.len() + std::mem::size_of::<u32>()) as u64,
+        ))?;
+        self.writer.write_u64::<LittleEndian>(index_offset)?;
+        self.writer.seek(SeekFrom::End(0))?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+/// A set of options for [`pack_directory`].
+#[derive(Clone, Debug)]
+pub struct PackOptions {
+    /// Compression level in `0..=10` range.
+    pub level: u8,
+    /// Names of files and directories that will not be added to the archive.
+    pub ignored_names: Vec<String>,
+    /// Extensions of files that will not be added to the archive.
+    pub ignored_extensions: Vec<String>,
+    /// Extensions of files that will be stored without compression, usually these are files that
+    /// are already compressed.
+    pub stored_extensions: Vec<String>,
+}
+
+impl Default for PackOptions {
+    fn default() -> Self {
+        Self {
+            level: 6,
+            ignored_names: ["target", ".git", ".idea", ".vscode"]
+                .map(String::from)
+                .to_vec(),
+            ignored_extensions: ["rs", "toml", "lock", "pak", "log"]
+                .map(String::from)
+                .to_vec(),
+            stored_extensions: ["png", "jpg", "jpeg", "ogg", "zip", "glb"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+fn has_extension(path: &Path, extensions: &[String]) -> bool {
+    path.extension().map_or(false, |ext| {
+        extensions
+            .iter()
+            .any(|e| ext.eq_ignore_ascii_case(e.as_str()))
+    })
+}
+
+fn collect_files(
+    directory: &Path,
+    options: &PackOptions,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), FileLoadError> {
+    let mut entries = std::fs::read_dir(directory)?.collect::<Result<Vec<_>, _>>()?;
+    // Sort entries to make archives reproducible.
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+
+        if options
+            .ignored_names
+            .iter()
+            .any(|name| entry.file_name() == name.as_str())
+        {
+            continue;
+        }
+
+        if entry.file_type()?.is_dir() {
+            collect_files(&path, options, files)?;
+        } else if !has_extension(&path, &options.ignored_extensions) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Packs every file of the `source` directory (recursively) into a pak archive at `destination`.
+/// Paths of entries are relative to the `source` directory, so the archive should be mounted with
+/// empty prefix to make the paths the same as before packing, if the game is running from `source`
+/// directory. Returns total amount of packed files.
+pub fn pack_directory<S: AsRef<Path>, D: AsRef<Path>>(
+    source: S,
+    destination: D,
+    options: &PackOptions,
+) -> Result<usize, FileLoadError> {
+    let source = source.as_ref();
+
+    let mut files = Vec::new();
+    collect_files(source, options, &mut files)?;
+
+    let file = std::fs::File::create(destination)?;
+    let mut writer = PakWriter::new(std::io::BufWriter::new(file))?;
+    for path in files.iter() {
+        let data = std::fs::read(path)?;
+
+        let compression = if has_extension(path, &options.stored_extensions) {
+            PakCompression::None
+        } else {
+            PakCompression::Deflate
+        };
+
+        let relative_path = path.strip_prefix(source).unwrap_or(path);
+        writer.add(relative_path, &data, compression, options.level)?;
+    }
+    writer.finish()?;
+
+    Ok(files.len())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::vfs::pak::{pack_directory, PackOptions, PakArchive, PakCompression, PakWriter};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_round_trip() {
+        let compressible = vec![42u8; 4096];
+        let incompressible = (0..=255u8).collect::<Vec<_>>();
+
+        let mut writer = PakWriter::new(Cursor::new(Vec::new())).unwrap();
+        writer
+            .add(
+                "a/compressible.bin",
+                &compressible,
+                PakCompression::Deflate,
+                6,
+            )
+            .unwrap();
+        writer
+            .add(
+                "./b/../incompressible.bin",
+                &incompressible,
+                PakCompression::Deflate,
+                6,
+            )
+            .unwrap();
+        writer
+            .add("stored.bin", &compressible, PakCompression::None, 6)
+            .unwrap();
+        let data = writer.finish().unwrap().into_inner();
+
+        let archive = PakArchive::from_memory(data).unwrap();
+        assert_eq!(archive.len(), 3);
+
+        let entry = archive.entry("a/compressible.bin").unwrap();
+        assert_eq!(entry.compression, PakCompression::Deflate);
+        assert!(entry.stored_size < entry.size);
+        assert_eq!(
+            archive.read("a/compressible.bin").unwrap().unwrap(),
+            compressible
+        );
+
+        // Compression makes no sense for such data, so it must be stored as is.
+        let entry = archive.entry("incompressible.bin").unwrap();
+        assert_eq!(entry.compression, PakCompression::None);
+        assert_eq!(
+            archive.read("incompressible.bin").unwrap().unwrap(),
+            incompressible
+        );
+
+        assert_eq!(archive.read("stored.bin").unwrap().unwrap(), compressible);
+        assert!(archive.read("missing.bin").is_none());
+    }
+
+    #[test]
+    fn test_invalid_archive() {
+        assert!(PakArchive::from_memory(b"NOPE".to_vec()).is_err());
+
+        let mut writer = PakWriter::new(Cursor::new(Vec::new())).unwrap();
+        writer
+            .add("a.bin", &[1, 2, 3], PakCompression::None, 0)
+            .unwrap();
+        let data = writer.finish().unwrap().into_inner();
+        assert!(PakArchive::from_memory(data.clone()).is_ok());
+
+        let index_offset = u64::from_le_bytes(data[8..16].try_into().unwrap()) as usize;
+        let path_len_offset = index_offset + 4;
+        let stored_size_offset = path_len_offset + 4 + "a.bin".len() + 8;
+
+        // Index offset is out of bounds.
+        let mut corrupted = data.clone();
+        corrupted[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(PakArchive::from_memory(corrupted).is_err());
+
+        // Huge path length must not cause huge allocation.
+        let mut corrupted = data.clone();
+        corrupted[path_len_offset..path_len_offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(PakArchive::from_memory(corrupted).is_err());
+
+        // Entry data is out of bounds and its end overflows.
+        let mut corrupted = data;
+        corrupted[stored_size_offset..stored_size_offset + 8]
+            .copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(PakArchive::from_memory(corrupted).is_err());
+    }
+
+    #[test]
+    fn test_pack_directory() {
+        let root = std::env::temp_dir().join("fyrox_pak_test_pack_directory");
+        let project = root.join("project");
+        std::fs::create_dir_all(project.join("data/textures")).unwrap();
+        std::fs::create_dir_all(project.join("target")).unwrap();
+        std::fs::write(project.join("data/scene.rgs"), b"scene").unwrap();
+        std::fs::write(project.join("data/textures/a.png"), b"png").unwrap();
+        std::fs::write(project.join("target/junk.bin"), b"junk").unwrap();
+        std::fs::write(project.join("Cargo.toml"), b"[package]").unwrap();
+
+        let pak_path = root.join("data.pak");
+        let count = pack_directory(&project, &pak_path, &PackOptions::default()).unwrap();
+        assert_eq!(count, 2);
+
+        let archive = futures::executor::block_on(PakArchive::open(&pak_path)).unwrap();
+        assert_eq!(archive.read("data/scene.rgs").unwrap().unwrap(), b"scene");
+        assert_eq!(
+            archive.read("data/textures/a.png").unwrap().unwrap(),
+            b"png"
+        );
+        assert!(!archive.contains("target/junk.bin"));
+        assert!(!archive.contains("Cargo.toml"));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
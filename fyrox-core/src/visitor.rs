@@ -15,6 +15,8 @@ pub mod prelude {
     pub use super::{Visit, VisitError, VisitResult, Visitor};
 }
 
+mod text;
+
 use crate::{
     algebra::{
         Complex, Const, Matrix, Matrix2, Matrix3, Matrix4, Quaternion, RawStorage, RawStorageMut,
@@ -25,7 +27,6 @@ use crate::{
     replace_slashes,
 };
 
-use base64::Engine;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use fxhash::FxHashMap;
 use std::any::TypeId;
@@ -243,155 +244,6 @@ impl<'a, T: Pod> Visit for PodVecView<'a, T> {
     }
 }
 
-impl FieldKind {
-    fn as_string(&self) -> String {
-        match self {
-            Self::Bool(data) => format!("<bool = {}>, ", data),
-            Self::U8(data) => format!("<u8 = {}>, ", data),
-            Self::I8(data) => format!("<i8 = {}>, ", data),
-            Self::U16(data) => format!("<u16 = {}>, ", data),
-            Self::I16(data) => format!("<i16 = {}>, ", data),
-            Self::U32(data) => format!("<u32 = {}>, ", data),
-            Self::I32(data) => format!("<i32 = {}>, ", data),
-            Self::U64(data) => format!("<u64 = {}>, ", data),
-            Self::I64(data) => format!("<i64 = {}>, ", data),
-            Self::F32(data) => format!("<f32 = {}>, ", data),
-            Self::F64(data) => format!("<f64 = {}>, ", data),
-            Self::Vector2F32(data) => format!("<vec2f32 = {}; {}>, ", data.x, data.y),
-            Self::Vector3F32(data) => format!("<vec3f32 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4F32(data) => {
-                format!(
-                    "<vec4f32 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-            Self::Vector2F64(data) => format!("<vec2f64 = {}; {}>, ", data.x, data.y),
-            Self::Vector3F64(data) => format!("<vec3f64 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4F64(data) => {
-                format!(
-                    "<vec4f64 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-            Self::Vector2I8(data) => format!("<vec2i8 = {}; {}>, ", data.x, data.y),
-            Self::Vector3I8(data) => format!("<vec3i8 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4I8(data) => {
-                format!(
-                    "<vec4i8 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-            Self::Vector2U8(data) => format!("<vec2u8 = {}; {}>, ", data.x, data.y),
-            Self::Vector3U8(data) => format!("<vec3u8 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4U8(data) => {
-                format!(
-                    "<vec4u8 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-
-            Self::Vector2I16(data) => format!("<vec2i16 = {}; {}>, ", data.x, data.y),
-            Self::Vector3I16(data) => format!("<vec3i16 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4I16(data) => {
-                format!(
-                    "<vec4i16 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-            Self::Vector2U16(data) => format!("<vec2u16 = {}; {}>, ", data.x, data.y),
-            Self::Vector3U16(data) => format!("<vec3u16 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4U16(data) => {
-                format!(
-                    "<vec4u16 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-
-            Self::Vector2I32(data) => format!("<vec2i32 = {}; {}>, ", data.x, data.y),
-            Self::Vector3I32(data) => format!("<vec3i32 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4I32(data) => {
-                format!(
-                    "<vec4i32 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-            Self::Vector2U32(data) => format!("<vec2u32 = {}; {}>, ", data.x, data.y),
-            Self::Vector3U32(data) => format!("<vec3u32 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4U32(data) => {
-                format!(
-                    "<vec4u32 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-
-            Self::Vector2I64(data) => format!("<vec2i64 = {}; {}>, ", data.x, data.y),
-            Self::Vector3I64(data) => format!("<vec3i64 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4I64(data) => {
-                format!(
-                    "<vec4i64 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-            Self::Vector2U64(data) => format!("<vec2u64 = {}; {}>, ", data.x, data.y),
-            Self::Vector3U64(data) => format!("<vec3u64 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4U64(data) => {
-                format!(
-                    "<vec4u64 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-
-            Self::UnitQuaternion(data) => {
-                format!("<quat = {}; {}; {}; {}>, ", data.i, data.j, data.k, data.w)
-            }
-            Self::Matrix4(data) => {
-                let mut out = String::from("<mat4 = ");
-                for f in data.iter() {
-                    out += format!("{}; ", f).as_str();
-                }
-                out
-            }
-            Self::Data(data) => {
-                let out = match String::from_utf8(data.clone()) {
-                    Ok(s) => s,
-                    Err(_) => base64::engine::general_purpose::STANDARD.encode(data),
-                };
-                format!("<data = {}>, ", out)
-            }
-            Self::Matrix3(data) => {
-                let mut out = String::from("<mat3 = ");
-                for f in data.iter() {
-                    out += format!("{}; ", f).as_str();
-                }
-                out
-            }
-
-            Self::Uuid(uuid) => uuid.to_string(),
-            Self::UnitComplex(data) => {
-                format!("<complex = {}; {}>, ", data.re, data.im)
-            }
-            FieldKind::PodArray {
-                type_id,
-                element_size,
-                bytes,
-            } => {
-                let base64_encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
-                format!(
-                    "<podarray = {}; {}; [{}]>",
-                    type_id, element_size, base64_encoded
-                )
-            }
-            Self::Matrix2(data) => {
-                let mut out = String::from("<mat2 = ");
-                for f in data.iter() {
-                    out += format!("{}; ", f).as_str();
-                }
-                out
-            }
-        }
-    }
-}
-
 macro_rules! impl_field_data {
     ($type_name:ty, $($kind:tt)*) => {
         impl Visit for $type_name {
@@ -533,6 +385,7 @@ pub enum VisitError {
     UnexpectedRcNullIndex,
     PoisonedMutex,
     FileLoadError(FileLoadError),
+    InvalidTextFormat { line: usize, reason: String },
 }
 
 impl Display for VisitError {
@@ -555,6 +408,9 @@ impl Display for VisitError {
             Self::UnexpectedRcNullIndex => write!(f, "unexpected rc null index"),
             Self::PoisonedMutex => write!(f, "attempt to lock poisoned mutex"),
             Self::FileLoadError(e) => write!(f, "file load error: {:?}", e),
+            Self::InvalidTextFormat { line, reason } => {
+                write!(f, "invalid text format at line {}: {}", line, reason)
+            }
         }
     }
 }
@@ -634,6 +490,13 @@ impl_visitable_elementary_field!(i32, write_i32, read_i32, LittleEndian);
 impl_visitable_elementary_field!(u64, write_u64, read_u64, LittleEndian);
 impl_visitable_elementary_field!(i64, write_i64, read_i64, LittleEndian);
 
+/// Builds a unit quaternion from the components in the order they are stored in (`i, j, k, w`). The
+/// quaternion is normalized to compensate rounding errors of the stored components. Every format must
+/// load quaternions using this function, so the same data produces the same quaternion.
+fn read_unit_quaternion(i: f32, j: f32, k: f32, w: f32) -> UnitQuaternion<f32> {
+    UnitQuaternion::new_normalize(Quaternion::new(w, i, j, k))
+}
+
 impl Field {
     pub fn new(name: &str, kind: FieldKind) -> Self {
         Self {
@@ -901,14 +764,14 @@ impl Field {
                     let y = file.read_f32::<LittleEndian>()?;
                     let z = file.read_f32::<LittleEndian>()?;
                     let w = file.read_f32::<LittleEndian>()?;
-                    UnitQuaternion::new_normalize(Quaternion::new(w, x, y, z))
+                    read_unit_quaternion(x, y, z, w)
                 }),
                 13 => FieldKind::Matrix4({
                     let mut f = [0.0f32; 16];
                     for n in &mut f {
                         *n = file.read_f32::<LittleEndian>()?;
                     }
-                    Matrix4::from_row_slice(&f)
+                    Matrix4::from_column_slice(&f)
                 }),
                 14 => FieldKind::Data({
                     let len = file.read_u32::<LittleEndian>()? as usize;
@@ -922,7 +785,7 @@ impl Field {
                     for n in &mut f {
                         *n = file.read_f32::<LittleEndian>()?;
                     }
-                    Matrix3::from_row_slice(&f)
+                    Matrix3::from_column_slice(&f)
                 }),
                 17 => FieldKind::Vector2F32({
                     let x = file.read_f32::<LittleEndian>()?;
@@ -959,11 +822,11 @@ impl Field {
                     }
                 }
                 22 => FieldKind::Matrix2({
-                    let mut f = [0.0f32; 3];
+                    let mut f = [0.0f32; 4];
                     for n in &mut f {
                         *n = file.read_f32::<LittleEndian>()?;
                     }
-                    Matrix2::from_row_slice(&f)
+                    Matrix2::from_column_slice(&f)
                 }),
                 23 => FieldKind::Vector2F64(read_vec_n(file)?),
                 24 => FieldKind::Vector3F64(read_vec_n(file)?),
@@ -1005,10 +868,6 @@ impl Field {
             },
         ))
     }
-
-    fn as_string(&self) -> String {
-        format!("{}{}", self.name, self.kind.as_string())
-    }
 }
 
 pub struct VisitorNode {
@@ -1096,10 +955,45 @@ impl Blackboard {
     }
 }
 
+/// Format of serialized visitor data.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum VisitorFormat {
+    /// Compact binary format, fast to read and write. This is the default format.
+    Binary,
+    /// Human-readable text format. It stores exactly the same data as the binary format, but
+    /// each field is written on its own line, which makes it suitable for diffing and merging
+    /// with version control systems. It is slower to read and write and takes more space.
+    Text,
+}
+
+impl Default for VisitorFormat {
+    fn default() -> Self {
+        Self::Binary
+    }
+}
+
+impl VisitorFormat {
+    /// Tries to detect the format of the given data by its header.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.starts_with(Visitor::MAGIC.as_bytes()) {
+            Some(Self::Binary)
+        } else if data
+            .strip_prefix("\u{feff}".as_bytes())
+            .unwrap_or(data)
+            .starts_with(text::TEXT_MAGIC.as_bytes())
+        {
+            Some(Self::Text)
+        } else {
+            None
+        }
+    }
+}
+
 pub struct Visitor {
     nodes: Pool<VisitorNode>,
     rc_map: FxHashMap<u64, Rc<dyn Any>>,
     arc_map: FxHashMap<u64, Arc<dyn Any + Send + Sync>>,
+    shared_ids: FxHashMap<usize, u64>,
     reading: bool,
     current_node: Handle<VisitorNode>,
     root: Handle<VisitorNode>,
@@ -1119,6 +1013,9 @@ impl Default for Visitor {
 impl Visitor {
     const MAGIC: &'static str = "RG3D";
 
+    /// Name of the field that stores version of a region, see [`Self::visit_version`].
+    pub const VERSION_FIELD: &'static str = "__Version";
+
     pub fn new() -> Self {
         let mut nodes = Pool::new();
         let root = nodes.spawn(VisitorNode::new("__ROOT__", Handle::NONE));
@@ -1126,6 +1023,7 @@ impl Visitor {
             nodes,
             rc_map: FxHashMap::default(),
             arc_map: FxHashMap::default(),
+            shared_ids: FxHashMap::default(),
             reading: false,
             current_node: root,
             root,
@@ -1192,6 +1090,68 @@ impl Visitor {
             .map(|n| n.name.as_str())
     }
 
+    /// Checks whether the current region has a field or a child region with the given name.
+    pub fn has_field_or_region(&self, name: &str) -> bool {
+        self.nodes
+            .try_borrow(self.current_node)
+            .map_or(false, |node| {
+                node.fields.iter().any(|field| field.name == name)
+                    || node
+                        .children
+                        .iter()
+                        .any(|child| self.nodes.borrow(*child).name == name)
+            })
+    }
+
+    /// Saves the given version of data in the current region (when writing) or reads the version
+    /// of data stored in the current region (when reading). Data that was saved without a
+    /// version has version `0`. The version could then be used to upgrade old data on load.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fyrox_core::visitor::prelude::*;
+    ///
+    /// #[derive(Default)]
+    /// struct Foo {
+    ///     radius: f32,
+    /// }
+    ///
+    /// impl Visit for Foo {
+    ///     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+    ///         let mut region = visitor.enter_region(name)?;
+    ///
+    ///         let version = region.visit_version(1)?;
+    ///         if version < 1 {
+    ///             // Version 0 stored diameter instead of radius.
+    ///             let mut diameter = 0.0f32;
+    ///             diameter.visit("Diameter", &mut region)?;
+    ///             self.radius = diameter * 0.5;
+    ///         } else {
+    ///             self.radius.visit("Radius", &mut region)?;
+    ///         }
+    ///
+    ///         Ok(())
+    ///     }
+    /// }
+    /// ```
+    pub fn visit_version(&mut self, version: u32) -> Result<u32, VisitError> {
+        if self.reading {
+            match self.find_field(Self::VERSION_FIELD) {
+                Some(Field {
+                    kind: FieldKind::U32(version),
+                    ..
+                }) => Ok(*version),
+                Some(_) => Err(VisitError::FieldTypeDoesNotMatch),
+                None => Ok(0),
+            }
+        } else {
+            let mut version = version;
+            version.visit(Self::VERSION_FIELD, self)?;
+            Ok(version)
+        }
+    }
+
     fn leave_region(&mut self) -> VisitResult {
         self.current_node = self.nodes.borrow(self.current_node).parent;
         if self.current_node.is_none() {
@@ -1201,37 +1161,25 @@ impl Visitor {
         }
     }
 
-    fn print_node(
-        &self,
-        node_handle: Handle<VisitorNode>,
-        nesting: usize,
-        out_string: &mut String,
-    ) {
-        let offset = (0..nesting).map(|_| "\t").collect::<String>();
-        let node = self.nodes.borrow(node_handle);
-        *out_string += format!(
-            "{}{}[Fields={}, Children={}]: ",
-            offset,
-            node.name,
-            node.fields.len(),
-            node.children.len()
-        )
-        .as_str();
-        for field in node.fields.iter() {
-            *out_string += field.as_string().as_str();
-        }
-
-        *out_string += "\n";
+    /// Returns a string with the content of the visitor in text format. See [`VisitorFormat::Text`]
+    /// for more info.
+    pub fn save_text(&self) -> String {
+        text::write(&self.nodes, self.root)
+    }
 
-        for child_handle in node.children.iter() {
-            self.print_node(*child_handle, nesting + 1, out_string);
-        }
+    /// Saves the content of the visitor in text format to the given file.
+    pub fn save_text_to_file<P: AsRef<Path>>(&self, path: P) -> VisitResult {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(self.save_text().as_bytes())?;
+        Ok(())
     }
 
-    pub fn save_text(&self) -> String {
-        let mut out_string = String::new();
-        self.print_node(self.root, 0, &mut out_string);
-        out_string
+    /// Saves the content of the visitor in the given format to the given file.
+    pub fn save_with_format<P: AsRef<Path>>(&self, path: P, format: VisitorFormat) -> VisitResult {
+        match format {
+            VisitorFormat::Binary => self.save_binary(path),
+            VisitorFormat::Text => self.save_text_to_file(path),
+        }
     }
 
     pub fn save_binary_to_memory<W: Write>(&self, mut writer: W) -> VisitResult {
@@ -1298,29 +1246,61 @@ impl Visitor {
         Ok(handle)
     }
 
+    /// Loads a visitor from the given file. Despite its name, the method accepts files in any of
+    /// supported formats (see [`VisitorFormat`]), the format is detected automatically.
     pub async fn load_binary<P: AsRef<Path>>(path: P) -> Result<Self, VisitError> {
         Self::load_from_memory(io::load_file(path).await?)
     }
 
-    pub fn load_from_memory(data: Vec<u8>) -> Result<Self, VisitError> {
-        let mut reader = Cursor::new(data);
-        let mut magic: [u8; 4] = Default::default();
-        reader.read_exact(&mut magic)?;
-        if !magic.eq(Self::MAGIC.as_bytes()) {
-            return Err(VisitError::NotSupportedFormat);
-        }
-        let mut visitor = Self {
-            nodes: Pool::new(),
+    fn new_for_reading(nodes: Pool<VisitorNode>, root: Handle<VisitorNode>) -> Self {
+        Self {
+            nodes,
             rc_map: Default::default(),
             arc_map: Default::default(),
+            shared_ids: Default::default(),
             reading: true,
-            current_node: Handle::NONE,
-            root: Handle::NONE,
+            current_node: root,
+            root,
             blackboard: Blackboard::new(),
-        };
-        visitor.root = visitor.load_node_binary(&mut reader)?;
-        visitor.current_node = visitor.root;
-        Ok(visitor)
+        }
+    }
+
+    /// Loads a visitor from the given data. The data could be in any of supported formats (see
+    /// [`VisitorFormat`]), the format is detected automatically.
+    pub fn load_from_memory(data: Vec<u8>) -> Result<Self, VisitError> {
+        match VisitorFormat::detect(&data) {
+            Some(VisitorFormat::Binary) => {
+                let mut reader = Cursor::new(data);
+                reader.set_position(Self::MAGIC.len() as u64);
+                let mut visitor = Self::new_for_reading(Pool::new(), Handle::NONE);
+                visitor.root = visitor.load_node_binary(&mut reader)?;
+                visitor.current_node = visitor.root;
+                Ok(visitor)
+            }
+            Some(VisitorFormat::Text) => {
+                Self::load_text(std::str::from_utf8(&data).map_err(|e| {
+                    VisitError::InvalidTextFormat {
+                        line: 0,
+                        reason: e.to_string(),
+                    }
+                })?)
+            }
+            None => Err(VisitError::NotSupportedFormat),
+        }
+    }
+
+    /// Loads a visitor from a string in text format. See [`VisitorFormat::Text`] for more info.
+    pub fn load_text(text: &str) -> Result<Self, VisitError> {
+        let (nodes, root) = text::read(text)?;
+        Ok(Self::new_for_reading(nodes, root))
+    }
+
+    /// Returns an id for a shared object (`Rc`/`Arc`) with the given address. Ids are assigned
+    /// sequentially in the order objects are visited, which makes them stable across multiple
+    /// saves of the same data (unlike raw addresses).
+    fn shared_object_id(&mut self, address: usize) -> u64 {
+        let next_id = self.shared_ids.len() as u64 + 1;
+        *self.shared_ids.entry(address).or_insert(next_id)
     }
 }
 
@@ -1386,6 +1366,8 @@ where
             } else {
                 self.as_mut().unwrap().visit("Data", &mut region)?;
             }
+        } else if region.reading {
+            *self = None;
         }
 
         Ok(())
@@ -1494,7 +1476,7 @@ where
             let raw = rc_to_raw(self);
 
             // Save it as id.
-            let mut index = raw as u64;
+            let mut index = region.shared_object_id(raw as usize);
             index.visit("Id", &mut region)?;
 
             if let Entry::Vacant(entry) = region.rc_map.entry(index) {
@@ -1582,7 +1564,7 @@ where
             let raw = arc_to_raw(self);
 
             // Save it as id.
-            let mut index = raw as u64;
+            let mut index = region.shared_object_id(raw as usize);
             index.visit("Id", &mut region)?;
 
             if let Entry::Vacant(entry) = region.arc_map.entry(index) {
@@ -1629,7 +1611,7 @@ where
             let raw = rc_to_raw(&rc);
 
             // Save it as id.
-            let mut index = raw as u64;
+            let mut index = region.shared_object_id(raw as usize);
             index.visit("Id", &mut region)?;
 
             if let Entry::Vacant(entry) = region.rc_map.entry(index) {
@@ -1679,7 +1661,7 @@ where
             let raw = arc_to_raw(&arc);
 
             // Save it as id.
-            let mut index = raw as u64;
+            let mut index = region.shared_object_id(raw as usize);
             index.visit("Id", &mut region)?;
 
             if let Entry::Vacant(entry) = region.arc_map.entry(index) {
@@ -1841,8 +1823,12 @@ impl<T: Visit> Visit for Range<T> {
 
 #[cfg(test)]
 mod test {
-    use crate::visitor::{Data, Visit, VisitResult, Visitor};
-    use std::{fs::File, io::Write, path::Path, rc::Rc};
+    use crate::{
+        algebra::{Matrix2, Matrix4, UnitQuaternion, Vector2, Vector3, Vector4},
+        visitor::{Data, Visit, VisitError, VisitResult, Visitor, VisitorFormat},
+    };
+    use std::{fs::File, io::Write, path::Path, path::PathBuf, rc::Rc, sync::Arc};
+    use uuid::Uuid;
 
     #[derive(Visit, Default)]
     pub struct Model {
@@ -1945,4 +1931,187 @@ mod test {
             objects.visit("Objects", &mut visitor).unwrap();
         }
     }
+
+    #[derive(Default, Debug, PartialEq)]
+    struct Everything {
+        boolean: bool,
+        byte: u8,
+        signed: i64,
+        float: f32,
+        double: f64,
+        vec2: Vector2<i32>,
+        vec3: Vector3<f32>,
+        vec4: Option<Vector4<u8>>,
+        quat: UnitQuaternion<f32>,
+        mat2: Matrix2<f32>,
+        mat4: Matrix4<f32>,
+        uuid: Uuid,
+        name: String,
+        path: PathBuf,
+        items: Vec<u16>,
+        blob: Vec<u8>,
+    }
+
+    impl Everything {
+        fn sample() -> Self {
+            Self {
+                boolean: true,
+                byte: 200,
+                signed: -1234567890123,
+                float: 0.1,
+                double: -1.0e-300,
+                vec2: Vector2::new(-1, 2),
+                vec3: Vector3::new(1.0 / 3.0, f32::MAX, f32::MIN_POSITIVE),
+                vec4: Some(Vector4::new(1, 2, 3, 255)),
+                quat: UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3),
+                mat2: Matrix2::new(1.0, 2.0, 3.0, 4.0),
+                mat4: Matrix4::new_translation(&Vector3::new(1.0, 2.0, 3.0)),
+                uuid: Uuid::new_v4(),
+                name: "Quotes \", backslashes \\, new\nlines and \u{1}".to_owned(),
+                path: PathBuf::from("data/models/model.fbx"),
+                items: vec![1, 2, 3],
+                blob: vec![0xFF, 0x00, 0xFE],
+            }
+        }
+    }
+
+    impl Visit for Everything {
+        fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+            let mut region = visitor.enter_region(name)?;
+            self.boolean.visit("Boolean", &mut region)?;
+            self.byte.visit("Byte", &mut region)?;
+            self.signed.visit("Signed", &mut region)?;
+            self.float.visit("Float", &mut region)?;
+            self.double.visit("Double", &mut region)?;
+            self.vec2.visit("Vec2", &mut region)?;
+            self.vec3.visit("Vec3", &mut region)?;
+            self.vec4.visit("Vec4", &mut region)?;
+            self.quat.visit("Quat", &mut region)?;
+            self.mat2.visit("Mat2", &mut region)?;
+            self.mat4.visit("Mat4", &mut region)?;
+            self.uuid.visit("Uuid", &mut region)?;
+            self.name
+                .visit("Name with spaces and \"quotes\"", &mut region)?;
+            self.path.visit("Path", &mut region)?;
+            self.items.visit("Items", &mut region)?;
+            let mut proxy = Data {
+                vec: &mut self.blob,
+            };
+            proxy.visit("Blob", &mut region)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn text_format_round_trip() {
+        let mut original = Everything::sample();
+        let mut shared = Rc::new(Resource::new(ResourceKind::Model(Model { data: 42 })));
+        let mut other_shared = shared.clone();
+        let mut arc = Arc::new(Everything::sample());
+
+        let mut visitor = Visitor::new();
+        original.visit("Everything", &mut visitor).unwrap();
+        shared.visit("Shared", &mut visitor).unwrap();
+        other_shared.visit("OtherShared", &mut visitor).unwrap();
+        arc.visit("Arc", &mut visitor).unwrap();
+
+        let text = visitor.save_text();
+        let binary = visitor.save_binary_to_vec().unwrap();
+        assert_eq!(
+            VisitorFormat::detect(text.as_bytes()),
+            Some(VisitorFormat::Text)
+        );
+        assert_eq!(VisitorFormat::detect(&binary), Some(VisitorFormat::Binary));
+
+        let mut loaded = Vec::new();
+        for data in [text.clone().into_bytes(), binary] {
+            let is_text = VisitorFormat::detect(&data) == Some(VisitorFormat::Text);
+            let mut visitor = Visitor::load_from_memory(data).unwrap();
+
+            let mut everything = Everything::default();
+            everything.visit("Everything", &mut visitor).unwrap();
+
+            let mut shared: Rc<Resource> = Rc::new(Default::default());
+            shared.visit("Shared", &mut visitor).unwrap();
+            let mut other_shared: Rc<Resource> = Rc::new(Default::default());
+            other_shared.visit("OtherShared", &mut visitor).unwrap();
+            assert!(Rc::ptr_eq(&shared, &other_shared));
+            assert!(matches!(
+                shared.kind,
+                ResourceKind::Model(Model { data: 42 })
+            ));
+
+            let mut arc: Arc<Everything> = Arc::new(Default::default());
+            arc.visit("Arc", &mut visitor).unwrap();
+
+            // Loading the text and saving it back must produce exactly the same text. The binary
+            // format keeps its legacy node order, so it can't be compared the same way.
+            if is_text {
+                assert_eq!(visitor.save_text(), text);
+            }
+
+            drop(visitor);
+            loaded.push((everything, Arc::try_unwrap(arc).unwrap()));
+        }
+
+        // Both formats must load exactly the same data, including the layout of matrices.
+        for (everything, arc) in loaded {
+            assert_eq!(everything, original);
+            assert_eq!(arc.mat2, original.mat2);
+            assert_eq!(arc.mat4, original.mat4);
+        }
+    }
+
+    #[test]
+    fn text_format_is_stable() {
+        let save = || {
+            let mut visitor = Visitor::new();
+            let mut shared = Rc::new(Resource::new(ResourceKind::Model(Model { data: 1 })));
+            let mut objects = vec![Foo::new(shared.clone()), Foo::new(shared.clone())];
+            shared.visit("Shared", &mut visitor).unwrap();
+            objects.visit("Objects", &mut visitor).unwrap();
+            visitor.save_text()
+        };
+
+        assert_eq!(save(), save());
+    }
+
+    #[test]
+    fn option_none_replaces_some_on_read() {
+        let mut visitor = Visitor::new();
+        let mut value: Option<u32> = None;
+        value.visit("Value", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let mut value = Some(1u32);
+        value.visit("Value", &mut visitor).unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn text_format_errors() {
+        assert!(matches!(
+            Visitor::load_text("FYROX-TEXT 1\n__ROOT__ {\n    Field: u8 = 256\n}\n"),
+            Err(VisitError::InvalidTextFormat { line: 3, .. })
+        ));
+        assert!(matches!(
+            Visitor::load_text("FYROX-TEXT 1\n__ROOT__ {\n    Node {\n}\n"),
+            Err(VisitError::InvalidTextFormat { line: 5, .. })
+        ));
+        assert!(matches!(
+            Visitor::load_text("Something else"),
+            Err(VisitError::NotSupportedFormat)
+        ));
+
+        // Comments and arbitrary formatting are allowed.
+        let mut visitor = Visitor::load_text(
+            "FYROX-TEXT 1 // Header\n__ROOT__ { Node { Value: vec2f32 = ( 1.5 ,-2 ) } }",
+        )
+        .unwrap();
+        let mut region = visitor.enter_region("Node").unwrap();
+        let mut value = Vector2::default();
+        value.visit("Value", &mut region).unwrap();
+        assert_eq!(value, Vector2::new(1.5f32, -2.0));
+    }
 }
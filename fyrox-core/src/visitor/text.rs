@@ -0,0 +1,609 @@
+//! Human-readable text representation of the visitor tree.
+//!
+//! The text format stores exactly the same tree of nodes and fields as the binary format, so
+//! anything that can be saved in binary form can be saved as text and loaded back with identical
+//! results. It is meant to be friendly to version control systems: every field occupies its own
+//! line, nodes are indented by their depth in the tree and ids of shared objects (`Rc`/`Arc`) are
+//! assigned sequentially, so saving the same data twice produces the same output.
+//!
+//! A small example of the format:
+//!
+//! ```text
+//! FYROX-TEXT 1
+//! __ROOT__ {
+//!     Objects {
+//!         Length: u32 = 1
+//!         Item0 {
+//!             Name: str = "Player"
+//!             Position: vec3f32 = (1.0, 2.0, -0.5)
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Each node is a name followed by a block in curly braces, each field is a name followed by a
+//! colon, a type tag, an equals sign and a value. Names that are not plain identifiers are written
+//! as quoted strings. Line comments starting with `//` are allowed and ignored.
+
+use crate::{
+    algebra::{Complex, Matrix2, Matrix3, Matrix4, SVector, Scalar, UnitComplex},
+    pool::{Handle, Pool},
+    visitor::{read_unit_quaternion, Field, FieldKind, VisitError, VisitorNode},
+};
+use base64::Engine;
+use std::{fmt::Debug, fmt::Display, str::FromStr};
+use uuid::Uuid;
+
+/// Magic string at the beginning of every file in text format.
+pub const TEXT_MAGIC: &str = "FYROX-TEXT";
+
+/// Current version of the text format.
+const TEXT_VERSION: u32 = 1;
+
+const INDENT: &str = "    ";
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn write_string(string: &str, out: &mut String) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => *out += format!("\\u{{{:x}}}", c as u32).as_str(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_name(name: &str, out: &mut String) {
+    if is_identifier(name) {
+        out.push_str(name);
+    } else {
+        write_string(name, out);
+    }
+}
+
+fn write_list<T: Debug>(items: impl Iterator<Item = T>, out: &mut String) {
+    out.push('(');
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        *out += format!("{:?}", item).as_str();
+    }
+    out.push(')');
+}
+
+fn write_value<T: Debug>(tag: &str, value: T, out: &mut String) {
+    *out += format!("{} = {:?}", tag, value).as_str();
+}
+
+fn write_vector<T: Debug + Scalar, const N: usize>(
+    tag: &str,
+    vec: &SVector<T, N>,
+    out: &mut String,
+) {
+    *out += format!("{} = ", tag).as_str();
+    write_list(vec.iter(), out);
+}
+
+fn write_field_kind(kind: &FieldKind, out: &mut String) {
+    match kind {
+        FieldKind::Bool(v) => write_value("bool", v, out),
+        FieldKind::U8(v) => write_value("u8", v, out),
+        FieldKind::I8(v) => write_value("i8", v, out),
+        FieldKind::U16(v) => write_value("u16", v, out),
+        FieldKind::I16(v) => write_value("i16", v, out),
+        FieldKind::U32(v) => write_value("u32", v, out),
+        FieldKind::I32(v) => write_value("i32", v, out),
+        FieldKind::U64(v) => write_value("u64", v, out),
+        FieldKind::I64(v) => write_value("i64", v, out),
+        FieldKind::F32(v) => write_value("f32", v, out),
+        FieldKind::F64(v) => write_value("f64", v, out),
+        FieldKind::UnitQuaternion(q) => {
+            out.push_str("quat = ");
+            write_list([q.i, q.j, q.k, q.w].iter(), out);
+        }
+        FieldKind::Matrix4(m) => {
+            out.push_str("mat4 = ");
+            write_list(m.iter(), out);
+        }
+        FieldKind::Matrix3(m) => {
+            out.push_str("mat3 = ");
+            write_list(m.iter(), out);
+        }
+        FieldKind::Matrix2(m) => {
+            out.push_str("mat2 = ");
+            write_list(m.iter(), out);
+        }
+        FieldKind::Data(data) => match std::str::from_utf8(data) {
+            Ok(string) => {
+                out.push_str("str = ");
+                write_string(string, out);
+            }
+            Err(_) => {
+                out.push_str("base64 = ");
+                write_string(&base64::engine::general_purpose::STANDARD.encode(data), out);
+            }
+        },
+        FieldKind::Uuid(uuid) => {
+            out.push_str("uuid = ");
+            write_string(&uuid.to_string(), out);
+        }
+        FieldKind::UnitComplex(c) => {
+            out.push_str("complex = ");
+            write_list([c.re, c.im].iter(), out);
+        }
+        FieldKind::PodArray {
+            type_id,
+            element_size,
+            bytes,
+        } => {
+            *out += format!("podarray = ({}, {}, ", type_id, element_size).as_str();
+            write_string(
+                &base64::engine::general_purpose::STANDARD.encode(bytes),
+                out,
+            );
+            out.push(')');
+        }
+        FieldKind::Vector2F32(v) => write_vector("vec2f32", v, out),
+        FieldKind::Vector3F32(v) => write_vector("vec3f32", v, out),
+        FieldKind::Vector4F32(v) => write_vector("vec4f32", v, out),
+        FieldKind::Vector2F64(v) => write_vector("vec2f64", v, out),
+        FieldKind::Vector3F64(v) => write_vector("vec3f64", v, out),
+        FieldKind::Vector4F64(v) => write_vector("vec4f64", v, out),
+        FieldKind::Vector2U8(v) => write_vector("vec2u8", v, out),
+        FieldKind::Vector3U8(v) => write_vector("vec3u8", v, out),
+        FieldKind::Vector4U8(v) => write_vector("vec4u8", v, out),
+        FieldKind::Vector2I8(v) => write_vector("vec2i8", v, out),
+        FieldKind::Vector3I8(v) => write_vector("vec3i8", v, out),
+        FieldKind::Vector4I8(v) => write_vector("vec4i8", v, out),
+        FieldKind::Vector2U16(v) => write_vector("vec2u16", v, out),
+        FieldKind::Vector3U16(v) => write_vector("vec3u16", v, out),
+        FieldKind::Vector4U16(v) => write_vector("vec4u16", v, out),
+        FieldKind::Vector2I16(v) => write_vector("vec2i16", v, out),
+        FieldKind::Vector3I16(v) => write_vector("vec3i16", v, out),
+        FieldKind::Vector4I16(v) => write_vector("vec4i16", v, out),
+        FieldKind::Vector2U32(v) => write_vector("vec2u32", v, out),
+        FieldKind::Vector3U32(v) => write_vector("vec3u32", v, out),
+        FieldKind::Vector4U32(v) => write_vector("vec4u32", v, out),
+        FieldKind::Vector2I32(v) => write_vector("vec2i32", v, out),
+        FieldKind::Vector3I32(v) => write_vector("vec3i32", v, out),
+        FieldKind::Vector4I32(v) => write_vector("vec4i32", v, out),
+        FieldKind::Vector2U64(v) => write_vector("vec2u64", v, out),
+        FieldKind::Vector3U64(v) => write_vector("vec3u64", v, out),
+        FieldKind::Vector4U64(v) => write_vector("vec4u64", v, out),
+        FieldKind::Vector2I64(v) => write_vector("vec2i64", v, out),
+        FieldKind::Vector3I64(v) => write_vector("vec3i64", v, out),
+        FieldKind::Vector4I64(v) => write_vector("vec4i64", v, out),
+    }
+}
+
+fn write_node(
+    nodes: &Pool<VisitorNode>,
+    node_handle: Handle<VisitorNode>,
+    depth: usize,
+    out: &mut String,
+) {
+    let node = nodes.borrow(node_handle);
+
+    let offset = INDENT.repeat(depth);
+
+    out.push_str(&offset);
+    write_name(&node.name, out);
+    out.push_str(" {\n");
+
+    for field in node.fields.iter() {
+        out.push_str(&offset);
+        out.push_str(INDENT);
+        write_name(&field.name, out);
+        out.push_str(": ");
+        write_field_kind(&field.kind, out);
+        out.push('\n');
+    }
+
+    for child in node.children.iter() {
+        write_node(nodes, *child, depth + 1, out);
+    }
+
+    out.push_str(&offset);
+    out.push_str("}\n");
+}
+
+/// Writes the tree starting from the given root node in text format.
+pub fn write(nodes: &Pool<VisitorNode>, root: Handle<VisitorNode>) -> String {
+    let mut out = format!("{} {}\n", TEXT_MAGIC, TEXT_VERSION);
+    write_node(nodes, root, 0, &mut out);
+    out
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    String(String),
+    OpenBrace,
+    CloseBrace,
+    OpenParen,
+    CloseParen,
+    Colon,
+    Equals,
+    Comma,
+    End,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "`{}`", word),
+            Token::String(string) => write!(f, "string {:?}", string),
+            Token::OpenBrace => write!(f, "`{{`"),
+            Token::CloseBrace => write!(f, "`}}`"),
+            Token::OpenParen => write!(f, "`(`"),
+            Token::CloseParen => write!(f, "`)`"),
+            Token::Colon => write!(f, "`:`"),
+            Token::Equals => write!(f, "`=`"),
+            Token::Comma => write!(f, "`,`"),
+            Token::End => write!(f, "end of file"),
+        }
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+' | '.')
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    peeked: Option<(Token, usize)>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            line: 1,
+            peeked: None,
+        }
+    }
+
+    fn error<T>(&self, line: usize, reason: impl Into<String>) -> Result<T, VisitError> {
+        Err(VisitError::InvalidTextFormat {
+            line,
+            reason: reason.into(),
+        })
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        loop {
+            match self.chars.peek() {
+                Some('\n') => {
+                    self.line += 1;
+                    self.chars.next();
+                }
+                Some(c) if c.is_whitespace() => {
+                    self.chars.next();
+                }
+                Some('/') => {
+                    let mut lookahead = self.chars.clone();
+                    lookahead.next();
+                    if lookahead.peek() == Some(&'/') {
+                        while !matches!(self.chars.peek(), Some('\n') | None) {
+                            self.chars.next();
+                        }
+                    } else {
+                        return;
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn read_string(&mut self, line: usize) -> Result<String, VisitError> {
+        let mut string = String::new();
+        loop {
+            match self.chars.next() {
+                None => return self.error(line, "unterminated string"),
+                Some('"') => return Ok(string),
+                Some('\\') => match self.chars.next() {
+                    Some('"') => string.push('"'),
+                    Some('\\') => string.push('\\'),
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some('u') => {
+                        if self.chars.next() != Some('{') {
+                            return self.error(self.line, "expected `{` after `\\u`");
+                        }
+                        let mut code = String::new();
+                        loop {
+                            match self.chars.next() {
+                                Some('}') => break,
+                                Some(c) if c.is_ascii_hexdigit() => code.push(c),
+                                _ => return self.error(self.line, "invalid unicode escape"),
+                            }
+                        }
+                        match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                            Some(c) => string.push(c),
+                            None => return self.error(self.line, "invalid unicode escape"),
+                        }
+                    }
+                    _ => return self.error(self.line, "invalid escape sequence"),
+                },
+                Some(c) => {
+                    if c == '\n' {
+                        self.line += 1;
+                    }
+                    string.push(c)
+                }
+            }
+        }
+    }
+
+    fn lex(&mut self) -> Result<(Token, usize), VisitError> {
+        self.skip_whitespace_and_comments();
+        let line = self.line;
+        let token = match self.chars.next() {
+            None => Token::End,
+            Some('{') => Token::OpenBrace,
+            Some('}') => Token::CloseBrace,
+            Some('(') => Token::OpenParen,
+            Some(')') => Token::CloseParen,
+            Some(':') => Token::Colon,
+            Some('=') => Token::Equals,
+            Some(',') => Token::Comma,
+            Some('"') => Token::String(self.read_string(line)?),
+            Some(c) if is_word_char(c) => {
+                let mut word = String::from(c);
+                while let Some(&c) = self.chars.peek() {
+                    if is_word_char(c) {
+                        word.push(c);
+                        self.chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                Token::Word(word)
+            }
+            Some(c) => return self.error(line, format!("unexpected character `{}`", c)),
+        };
+        Ok((token, line))
+    }
+
+    fn next(&mut self) -> Result<(Token, usize), VisitError> {
+        match self.peeked.take() {
+            Some(token) => Ok(token),
+            None => self.lex(),
+        }
+    }
+
+    fn peek(&mut self) -> Result<&Token, VisitError> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.lex()?);
+        }
+        Ok(&self.peeked.as_ref().unwrap().0)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), VisitError> {
+        let (token, line) = self.next()?;
+        if token == expected {
+            Ok(())
+        } else {
+            self.error(line, format!("expected {}, got {}", expected, token))
+        }
+    }
+
+    fn name(&mut self) -> Result<(String, usize), VisitError> {
+        match self.next()? {
+            (Token::Word(word), line) => Ok((word, line)),
+            (Token::String(string), line) => Ok((string, line)),
+            (token, line) => self.error(line, format!("expected name, got {}", token)),
+        }
+    }
+
+    fn word(&mut self) -> Result<(String, usize), VisitError> {
+        match self.next()? {
+            (Token::Word(word), line) => Ok((word, line)),
+            (token, line) => self.error(line, format!("expected value, got {}", token)),
+        }
+    }
+
+    fn string(&mut self) -> Result<(String, usize), VisitError> {
+        match self.next()? {
+            (Token::String(string), line) => Ok((string, line)),
+            (token, line) => self.error(line, format!("expected string, got {}", token)),
+        }
+    }
+
+    fn number<T: FromStr>(&mut self) -> Result<T, VisitError> {
+        let (word, line) = self.word()?;
+        match word.parse() {
+            Ok(value) => Ok(value),
+            Err(_) => self.error(line, format!("`{}` is not a valid number", word)),
+        }
+    }
+
+    fn bool(&mut self) -> Result<bool, VisitError> {
+        let (word, line) = self.word()?;
+        match word.as_str() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => self.error(line, format!("`{}` is not a valid boolean", word)),
+        }
+    }
+
+    fn base64(&mut self) -> Result<Vec<u8>, VisitError> {
+        let (string, line) = self.string()?;
+        match base64::engine::general_purpose::STANDARD.decode(string) {
+            Ok(bytes) => Ok(bytes),
+            Err(e) => self.error(line, format!("invalid base64 data: {}", e)),
+        }
+    }
+
+    fn list<T: FromStr + Default + Copy, const N: usize>(&mut self) -> Result<[T; N], VisitError> {
+        let mut items = [T::default(); N];
+        self.expect(Token::OpenParen)?;
+        for (i, item) in items.iter_mut().enumerate() {
+            if i > 0 {
+                self.expect(Token::Comma)?;
+            }
+            *item = self.number()?;
+        }
+        self.expect(Token::CloseParen)?;
+        Ok(items)
+    }
+
+    fn vector<T, const N: usize>(&mut self) -> Result<SVector<T, N>, VisitError>
+    where
+        T: FromStr + Default + Copy + Scalar,
+    {
+        Ok(SVector::from(self.list::<T, N>()?))
+    }
+
+    fn field_kind(&mut self) -> Result<FieldKind, VisitError> {
+        let (tag, line) = self.word()?;
+        self.expect(Token::Equals)?;
+        Ok(match tag.as_str() {
+            "bool" => FieldKind::Bool(self.bool()?),
+            "u8" => FieldKind::U8(self.number()?),
+            "i8" => FieldKind::I8(self.number()?),
+            "u16" => FieldKind::U16(self.number()?),
+            "i16" => FieldKind::I16(self.number()?),
+            "u32" => FieldKind::U32(self.number()?),
+            "i32" => FieldKind::I32(self.number()?),
+            "u64" => FieldKind::U64(self.number()?),
+            "i64" => FieldKind::I64(self.number()?),
+            "f32" => FieldKind::F32(self.number()?),
+            "f64" => FieldKind::F64(self.number()?),
+            "quat" => {
+                let [i, j, k, w] = self.list::<f32, 4>()?;
+                FieldKind::UnitQuaternion(read_unit_quaternion(i, j, k, w))
+            }
+            "mat4" => FieldKind::Matrix4(Matrix4::from_column_slice(&self.list::<f32, 16>()?)),
+            "mat3" => FieldKind::Matrix3(Matrix3::from_column_slice(&self.list::<f32, 9>()?)),
+            "mat2" => FieldKind::Matrix2(Matrix2::from_column_slice(&self.list::<f32, 4>()?)),
+            "str" => FieldKind::Data(self.string()?.0.into_bytes()),
+            "base64" => FieldKind::Data(self.base64()?),
+            "uuid" => {
+                let (string, line) = self.string()?;
+                match Uuid::parse_str(&string) {
+                    Ok(uuid) => FieldKind::Uuid(uuid),
+                    Err(e) => return self.error(line, format!("invalid uuid: {}", e)),
+                }
+            }
+            "complex" => {
+                let [re, im] = self.list::<f32, 2>()?;
+                FieldKind::UnitComplex(UnitComplex::from_complex(Complex::new(re, im)))
+            }
+            "podarray" => {
+                self.expect(Token::OpenParen)?;
+                let type_id = self.number()?;
+                self.expect(Token::Comma)?;
+                let element_size = self.number()?;
+                self.expect(Token::Comma)?;
+                let bytes = self.base64()?;
+                self.expect(Token::CloseParen)?;
+                FieldKind::PodArray {
+                    type_id,
+                    element_size,
+                    bytes,
+                }
+            }
+            "vec2f32" => FieldKind::Vector2F32(self.vector()?),
+            "vec3f32" => FieldKind::Vector3F32(self.vector()?),
+            "vec4f32" => FieldKind::Vector4F32(self.vector()?),
+            "vec2f64" => FieldKind::Vector2F64(self.vector()?),
+            "vec3f64" => FieldKind::Vector3F64(self.vector()?),
+            "vec4f64" => FieldKind::Vector4F64(self.vector()?),
+            "vec2u8" => FieldKind::Vector2U8(self.vector()?),
+            "vec3u8" => FieldKind::Vector3U8(self.vector()?),
+            "vec4u8" => FieldKind::Vector4U8(self.vector()?),
+            "vec2i8" => FieldKind::Vector2I8(self.vector()?),
+            "vec3i8" => FieldKind::Vector3I8(self.vector()?),
+            "vec4i8" => FieldKind::Vector4I8(self.vector()?),
+            "vec2u16" => FieldKind::Vector2U16(self.vector()?),
+            "vec3u16" => FieldKind::Vector3U16(self.vector()?),
+            "vec4u16" => FieldKind::Vector4U16(self.vector()?),
+            "vec2i16" => FieldKind::Vector2I16(self.vector()?),
+            "vec3i16" => FieldKind::Vector3I16(self.vector()?),
+            "vec4i16" => FieldKind::Vector4I16(self.vector()?),
+            "vec2u32" => FieldKind::Vector2U32(self.vector()?),
+            "vec3u32" => FieldKind::Vector3U32(self.vector()?),
+            "vec4u32" => FieldKind::Vector4U32(self.vector()?),
+            "vec2i32" => FieldKind::Vector2I32(self.vector()?),
+            "vec3i32" => FieldKind::Vector3I32(self.vector()?),
+            "vec4i32" => FieldKind::Vector4I32(self.vector()?),
+            "vec2u64" => FieldKind::Vector2U64(self.vector()?),
+            "vec3u64" => FieldKind::Vector3U64(self.vector()?),
+            "vec4u64" => FieldKind::Vector4U64(self.vector()?),
+            "vec2i64" => FieldKind::Vector2I64(self.vector()?),
+            "vec3i64" => FieldKind::Vector3I64(self.vector()?),
+            "vec4i64" => FieldKind::Vector4I64(self.vector()?),
+            _ => return self.error(line, format!("unknown field type `{}`", tag)),
+        })
+    }
+
+    /// Parses the body of a node (everything after its opening brace) and puts it in the pool.
+    fn node(
+        &mut self,
+        name: String,
+        parent: Handle<VisitorNode>,
+        nodes: &mut Pool<VisitorNode>,
+    ) -> Result<Handle<VisitorNode>, VisitError> {
+        let handle = nodes.spawn(VisitorNode::new(&name, parent));
+        loop {
+            if let Token::CloseBrace = self.peek()? {
+                self.next()?;
+                return Ok(handle);
+            }
+
+            let (name, _) = self.name()?;
+            match self.next()? {
+                (Token::Colon, _) => {
+                    let kind = self.field_kind()?;
+                    nodes
+                        .borrow_mut(handle)
+                        .fields
+                        .push(Field::new(&name, kind));
+                }
+                (Token::OpenBrace, _) => {
+                    let child = self.node(name, handle, nodes)?;
+                    nodes.borrow_mut(handle).children.push(child);
+                }
+                (token, line) => {
+                    return self.error(line, format!("expected `:` or `{{`, got {}", token))
+                }
+            }
+        }
+    }
+}
+
+/// Parses a text produced by [`write`] and returns the pool of nodes with the root node handle.
+pub fn read(text: &str) -> Result<(Pool<VisitorNode>, Handle<VisitorNode>), VisitError> {
+    let mut parser = Parser::new(text.strip_prefix('\u{feff}').unwrap_or(text));
+
+    match parser.word()? {
+        (magic, _) if magic == TEXT_MAGIC => (),
+        _ => return Err(VisitError::NotSupportedFormat),
+    }
+    let (version, line) = parser.word()?;
+    if version.parse::<u32>().ok() != Some(TEXT_VERSION) {
+        return parser.error(line, format!("unsupported version `{}`", version));
+    }
+
+    let mut nodes = Pool::new();
+    let (name, _) = parser.name()?;
+    parser.expect(Token::OpenBrace)?;
+    let root = parser.node(name, Handle::NONE, &mut nodes)?;
+    parser.expect(Token::End)?;
+
+    Ok((nodes, root))
+}
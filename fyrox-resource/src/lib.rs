@@ -53,6 +53,10 @@ pub const SOUND_BUFFER_RESOURCE_UUID: Uuid = uuid!("f6a077b7-c8ff-4473-a95b-0289
 pub const SHADER_RESOURCE_UUID: Uuid = uuid!("f1346417-b726-492a-b80f-c02096c6c019");
 /// Type UUID of curve resource. It is defined here to load old versions of resources.
 pub const CURVE_RESOURCE_UUID: Uuid = uuid!("f28b949f-28a2-4b68-9089-59c234f58b6b");
+/// Type UUID of UI document resource, which is loaded from `.ui` files.
+pub const UI_DOCUMENT_RESOURCE_UUID: Uuid = uuid!("5b3e8f4c-1d2a-4e7b-9c6f-0a8d7e2b4c91");
+/// Type UUID of tile set resource, which is loaded from `.tileset` files.
+pub const TILE_SET_RESOURCE_UUID: Uuid = uuid!("a8c3e1f2-6b4d-4f9a-8e27-3d5c9b1f0a64");
 
 /// A trait for resource data.
 pub trait ResourceData: 'static + Debug + Visit + Send + Reflect {
@@ -23,7 +23,8 @@ pub trait ResourceLoader: 'static {
     /// Returns `self` as `&mut dyn Any`. It is useful for downcasting to a particular type.
     fn as_any_mut(&mut self) -> &mut dyn Any;
 
-    /// Loads or reloads a resource.
+    /// Loads or reloads a resource. Implementations should read files using [`fyrox_core::io::load_file`],
+    /// so the paths are resolved through the virtual file system.
     fn load(
         &self,
         resource: UntypedResource,
@@ -52,7 +53,8 @@ pub trait ResourceLoader: Send + 'static {
     /// Returns `self` as `&mut dyn Any`. It is useful for downcasting to a particular type.
     fn as_any_mut(&mut self) -> &mut dyn Any;
 
-    /// Loads or reloads a resource.
+    /// Loads or reloads a resource. Implementations should read files using [`fyrox_core::io::load_file`],
+    /// so the paths are resolved through the virtual file system.
     fn load(
         &self,
         resource: UntypedResource,
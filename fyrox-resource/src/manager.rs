@@ -12,10 +12,12 @@ use crate::{
 use fxhash::FxHashMap;
 use fyrox_core::{
     futures::future::join_all,
+    io::FileLoadError,
     log::Log,
     make_relative_path, notify,
     parking_lot::{Mutex, MutexGuard},
     uuid::Uuid,
+    vfs::{pak::PakArchive, MountId, Vfs},
     watcher::FileSystemWatcher,
     TypeUuidProvider, VecExtensions,
 };
@@ -142,6 +144,11 @@ impl ResourceManager {
     /// Actual resource state can be fetched by [`Resource::state`] method. If you know for sure that the resource
     /// is already loaded, then you can use [`Resource::data_ref`] to obtain a reference to the actual resource data.
     /// Keep in mind, that this method will panic if the resource non in `Ok` state.
+    ///
+    /// ## Virtual file system
+    ///
+    /// The path is resolved through the [virtual file system](fyrox_core::vfs), so the resource could be loaded from
+    /// a mounted directory, a pak archive (see [`Self::mount_archive`]) or an in-memory overlay.
     pub fn request<T, P>(&self, path: P) -> Resource<T>
     where
         P: AsRef<Path>,
@@ -158,6 +165,26 @@ impl ResourceManager {
         }
     }
 
+    /// Opens a pak archive at the given path and mounts it in the global [virtual file system](fyrox_core::vfs)
+    /// at the given prefix. Every resource requested after this call will be searched in the archive first (unless
+    /// there is a mount point with higher priority). Use an empty prefix to mount an archive produced from a
+    /// project folder by [`fyrox_core::vfs::pak::pack_directory`].
+    pub async fn mount_archive<P, A>(
+        &self,
+        prefix: P,
+        archive_path: A,
+        priority: i32,
+    ) -> Result<MountId, FileLoadError>
+    where
+        P: AsRef<Path>,
+        A: AsRef<Path>,
+    {
+        let archive = PakArchive::open(archive_path).await?;
+        Ok(Vfs::global()
+            .write()
+            .mount_archive(prefix, archive, priority))
+    }
+
     /// Same as [`Self::request`], but returns untyped resource.
     pub fn request_untyped<P>(&self, path: P, type_uuid: Uuid) -> UntypedResource
     where
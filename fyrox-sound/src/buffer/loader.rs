@@ -8,6 +8,7 @@ use fyrox_resource::{
     loader::{BoxedLoaderFuture, ResourceLoader},
     options::{try_get_import_settings, ImportOptions},
     untyped::UntypedResource,
+    ResourceData,
 };
 use serde::{Deserialize, Serialize};
 use std::any::Any;
@@ -67,7 +68,11 @@ impl ResourceLoader for SoundBufferLoader {
                         SoundBuffer::raw_generic(source)
                     };
                     match buffer {
-                        Ok(sound_buffer) => {
+                        Ok(mut sound_buffer) => {
+                            // Data source could be a memory block (if the file is in an archive),
+                            // so the path must be set explicitly.
+                            sound_buffer.set_path(path.clone());
+
                             resource.commit_ok(sound_buffer);
 
                             event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
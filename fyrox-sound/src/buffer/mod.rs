@@ -104,23 +104,35 @@ pub trait RawStreamingDataSource: Iterator<Item = f32> + Send + Sync + Debug {
 }
 
 impl DataSource {
-    /// Tries to create new `File` data source from given path. May fail if file does not exists.
+    /// Tries to create new `File` data source from given path. May fail if file does not exists. The path
+    /// is resolved through the virtual file system, if the file is located in an archive or in an in-memory
+    /// overlay, the data source will be `Memory`.
     pub async fn from_file<P>(path: P) -> Result<Self, FileLoadError>
     where
         P: AsRef<Path>,
     {
-        Ok(DataSource::File {
-            path: path.as_ref().to_path_buf(),
-
-            #[cfg(not(target_arch = "wasm32"))]
-            data: std::io::BufReader::new(match std::fs::File::open(path) {
-                Ok(file) => file,
-                Err(e) => return Err(FileLoadError::Io(e)),
-            }),
-
-            #[cfg(target_arch = "wasm32")]
-            data: Cursor::new(fyrox_core::io::load_file(path).await?),
-        })
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let vfs = fyrox_core::vfs::Vfs::snapshot();
+            match vfs.native_path(path.as_ref()).await {
+                Some(native_path) => Ok(DataSource::File {
+                    path: path.as_ref().to_path_buf(),
+                    data: std::io::BufReader::new(match std::fs::File::open(native_path) {
+                        Ok(file) => file,
+                        Err(e) => return Err(FileLoadError::Io(e)),
+                    }),
+                }),
+                None => Ok(DataSource::Memory(Cursor::new(vfs.load_file(path).await?))),
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(DataSource::File {
+                path: path.as_ref().to_path_buf(),
+                data: Cursor::new(fyrox_core::io::load_file(path).await?),
+            })
+        }
     }
 
     /// Creates new data source from given memory block. This function does not checks if this is valid source or
@@ -459,7 +459,41 @@ impl AudioBusGraph {
         }
     }
 
+    /// Copies input signal of sidechain buses to the effects that use it. It must be done before any effect
+    /// is applied, so every effect "hears" unprocessed signal of a sidechain bus.
+    fn feed_sidechains(&mut self) {
+        let handles = self
+            .buses
+            .pair_iter()
+            .filter(|(_, bus)| bus.effects.iter().any(|e| e.sidechain_bus().is_some()))
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            let (ticket, mut bus) = self.buses.take_reserve(handle);
+
+            for effect in bus.effects.iter_mut() {
+                let sidechain_input = effect.sidechain_bus().and_then(|name| {
+                    if name == bus.name {
+                        // Sidechain from the same bus is the same as no sidechain at all.
+                        None
+                    } else {
+                        self.buses
+                            .iter()
+                            .find(|other| other.name == name)
+                            .map(|other| other.ping_pong_buffer.input_ref())
+                    }
+                });
+                effect.set_sidechain_input(sidechain_input);
+            }
+
+            self.buses.put_back(ticket, bus);
+        }
+    }
+
     pub(crate) fn end_render(&mut self, output_device_buffer: &mut [(f32, f32)]) {
+        self.feed_sidechains();
+
         let mut leafs = Vec::new();
         for (handle, bus) in self.buses.pair_iter_mut() {
             bus.apply_effects();
@@ -504,7 +538,7 @@ impl AudioBusGraph {
 mod test {
     use crate::{
         bus::{AudioBus, AudioBusGraph},
-        effects::{Attenuate, Effect},
+        effects::{compressor::Compressor, Attenuate, Effect},
     };
 
     #[test]
@@ -586,4 +620,41 @@ mod test {
 
         assert_eq!(output_buffer[0], (0.75, 0.75));
     }
+
+    #[test]
+    fn test_sidechain_compression() {
+        let mut output_buffer = [(0.0f32, 0.0f32); 4];
+
+        let mut graph = AudioBusGraph::new();
+
+        let mut compressor = Compressor::new();
+        compressor.set_attack_time(0.0);
+        compressor.set_sidechain_bus("Voice");
+
+        let mut music = AudioBus::new("Music".to_string());
+        music.add_effect(Effect::Compressor(compressor));
+        let music = graph.add_bus(music, graph.root);
+        let voice = graph.add_bus(AudioBus::new("Voice".to_string()), music);
+
+        let mut render = |graph: &mut AudioBusGraph, voice_level: f32| {
+            output_buffer = [(0.0, 0.0); 4];
+            graph.begin_render(output_buffer.len());
+            for sample in graph.buses[music].input_buffer() {
+                *sample = (0.05, 0.05);
+            }
+            for sample in graph.buses[voice].input_buffer() {
+                *sample = (voice_level, voice_level);
+            }
+            graph.end_render(&mut output_buffer);
+            output_buffer[0].0
+        };
+
+        // No voice - music is not affected.
+        assert_eq!(render(&mut graph, 0.0), 0.05);
+
+        // Loud voice "ducks" the music: 0 dB voice is 20 dB above the threshold, so the music must be
+        // attenuated by 15 dB.
+        let expected = 1.0 + 0.05 * 10.0f32.powf(-15.0 / 20.0);
+        assert!((render(&mut graph, 1.0) - expected).abs() < 1.0e-4);
+    }
 }
@@ -60,6 +60,19 @@ impl DelayLine {
     pub fn last(&self) -> f32 {
         self.last
     }
+
+    /// Returns a sample that was fed `delay` samples ago, fractional delays are linearly interpolated.
+    /// The delay is clamped to `[1.0; len]` range. Useful for effects with modulated delay time.
+    pub fn tap(&self, delay: f32) -> f32 {
+        let len = self.samples.0.len();
+        let delay = delay.clamp(1.0, len as f32);
+        let whole = delay.floor() as usize;
+        let fraction = delay - whole as f32;
+        let index = |delay: usize| (self.pos as usize + len - delay.min(len)) % len;
+        let a = self.samples.0[index(whole)];
+        let b = self.samples.0[index(whole + 1)];
+        a + (b - a) * fraction
+    }
 }
 
 impl Default for DelayLine {
@@ -0,0 +1,270 @@
+//! Chorus and flanger effects.
+//!
+//! # Overview
+//!
+//! Chorus mixes the input signal with its copy delayed by a slowly changing amount of time. It makes a single
+//! sound source sound like a few slightly detuned sources playing together. Flanger is the same effect with much
+//! shorter delay time and some feedback, it produces characteristic "jet plane" sweeping sound (see
+//! [`Chorus::flanger`]).
+
+use crate::{context::SAMPLE_RATE, dsp::DelayLine, effects::EffectRenderTrait};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+use std::f32::consts::PI;
+
+/// See module docs.
+#[derive(Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct Chorus {
+    #[reflect(
+        description = "Average delay time (in seconds) of the delayed copy of the signal.",
+        setter = "set_delay_time",
+        min_value = 0.0
+    )]
+    delay_time: f32,
+
+    #[reflect(
+        description = "Amplitude (in seconds) of delay time modulation.",
+        setter = "set_depth",
+        min_value = 0.0
+    )]
+    depth: f32,
+
+    #[reflect(
+        description = "Frequency (in Hertz) of delay time modulation.",
+        setter = "set_rate",
+        min_value = 0.0
+    )]
+    rate: f32,
+
+    #[reflect(
+        description = "Amount of the delayed signal fed back to the delay line.",
+        setter = "set_feedback",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    feedback: f32,
+
+    #[reflect(
+        description = "Amount of the input signal passed to output without any processing.",
+        setter = "set_dry",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    dry: f32,
+
+    #[reflect(
+        description = "Amount of the delayed signal passed to output.",
+        setter = "set_wet",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    wet: f32,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    phase: f32,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    left: DelayLine,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    right: DelayLine,
+}
+
+impl Default for Chorus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Chorus {
+    /// Creates new chorus effect with 20 ms delay time modulated by 5 ms at 0.8 Hz.
+    pub fn new() -> Self {
+        Self {
+            delay_time: 0.02,
+            depth: 0.005,
+            rate: 0.8,
+            feedback: 0.0,
+            dry: 1.0,
+            wet: 0.5,
+            phase: 0.0,
+            left: Default::default(),
+            right: Default::default(),
+        }
+    }
+
+    /// Creates new flanger effect with 3 ms delay time modulated by 2 ms at 0.25 Hz and strong feedback.
+    pub fn flanger() -> Self {
+        Self {
+            delay_time: 0.003,
+            depth: 0.002,
+            rate: 0.25,
+            feedback: 0.7,
+            wet: 0.7,
+            ..Self::new()
+        }
+    }
+
+    /// Sets average delay time (in seconds) of the delayed copy of the signal.
+    pub fn set_delay_time(&mut self, delay_time: f32) {
+        self.delay_time = delay_time.max(0.0);
+    }
+
+    /// Returns average delay time in seconds.
+    pub fn delay_time(&self) -> f32 {
+        self.delay_time
+    }
+
+    /// Sets amplitude (in seconds) of delay time modulation. The delay time will oscillate in
+    /// `[delay_time - depth; delay_time + depth]` range.
+    pub fn set_depth(&mut self, depth: f32) {
+        self.depth = depth.max(0.0);
+    }
+
+    /// Returns amplitude of delay time modulation in seconds.
+    pub fn depth(&self) -> f32 {
+        self.depth
+    }
+
+    /// Sets frequency (in Hertz) of delay time modulation.
+    pub fn set_rate(&mut self, rate: f32) {
+        self.rate = rate.max(0.0);
+    }
+
+    /// Returns frequency of delay time modulation in Hertz.
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    /// Sets the amount of the delayed signal fed back to the delay line. The value is clamped to `[0.0; 0.99]`
+    /// range to keep the effect stable.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.clamp(0.0, 0.99);
+    }
+
+    /// Returns current feedback.
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets how much of the delayed signal should be passed to output.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+}
+
+impl EffectRenderTrait for Chorus {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        let sample_rate = SAMPLE_RATE as f32;
+        // Two extra samples for interpolation.
+        let len = ((self.delay_time + self.depth) * sample_rate).ceil() as usize + 2;
+        if self.left.len() != len {
+            self.left = DelayLine::new(len);
+            self.right = DelayLine::new(len);
+        }
+
+        let phase_step = self.rate / sample_rate;
+
+        for ((left, right), (output_left, output_right)) in input.iter().zip(output) {
+            // Right channel is modulated with 90 degrees phase shift to widen the stereo image.
+            let modulation_left = (2.0 * PI * self.phase).sin();
+            let modulation_right = (2.0 * PI * (self.phase + 0.25)).sin();
+
+            let delayed_left = self
+                .left
+                .tap((self.delay_time + self.depth * modulation_left) * sample_rate);
+            let delayed_right = self
+                .right
+                .tap((self.delay_time + self.depth * modulation_right) * sample_rate);
+
+            self.left.feed(*left + delayed_left * self.feedback);
+            self.right.feed(*right + delayed_right * self.feedback);
+
+            *output_left = *left * self.dry + delayed_left * self.wet;
+            *output_right = *right * self.dry + delayed_right * self.wet;
+
+            self.phase = (self.phase + phase_step).fract();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        context::SAMPLE_RATE,
+        effects::{chorus::Chorus, EffectRenderTrait},
+    };
+
+    #[test]
+    fn test_unmodulated_chorus_is_delay() {
+        let mut chorus = Chorus::new();
+        chorus.set_delay_time(50.0 / SAMPLE_RATE as f32);
+        chorus.set_depth(0.0);
+        chorus.set_dry(0.0);
+        chorus.set_wet(1.0);
+
+        let mut input = vec![(0.0, 0.0); 100];
+        input[0] = (1.0, 1.0);
+        let mut output = vec![(0.0, 0.0); input.len()];
+        chorus.render(&input, &mut output);
+
+        for (i, (left, right)) in output.into_iter().enumerate() {
+            let expected = if i == 50 { 1.0 } else { 0.0 };
+            assert!((left - expected).abs() < 1.0e-4, "{i}: {left}");
+            assert!((right - expected).abs() < 1.0e-4, "{i}: {right}");
+        }
+    }
+
+    #[test]
+    fn test_modulation() {
+        let mut chorus = Chorus::new();
+        chorus.set_dry(0.0);
+        chorus.set_wet(1.0);
+        chorus.set_rate(5.0);
+
+        // Delay time changes over time, so a constant tone becomes out of phase between channels.
+        let input = (0..SAMPLE_RATE)
+            .map(|i| {
+                let s = (2.0 * std::f32::consts::PI * 440.0 * i as f32 / SAMPLE_RATE as f32).sin();
+                (s, s)
+            })
+            .collect::<Vec<_>>();
+        let mut output = vec![(0.0, 0.0); input.len()];
+        chorus.render(&input, &mut output);
+
+        let difference = output
+            .iter()
+            .map(|(left, right)| (left - right).abs())
+            .fold(0.0f32, f32::max);
+        assert!(difference > 0.1);
+        assert!(output
+            .iter()
+            .all(|(l, r)| l.abs() <= 1.01 && r.abs() <= 1.01));
+    }
+
+    #[test]
+    fn test_flanger_is_stable() {
+        let mut flanger = Chorus::flanger();
+        let mut output = vec![(0.0, 0.0); SAMPLE_RATE as usize];
+        flanger.render(&vec![(0.5, -0.5); SAMPLE_RATE as usize], &mut output);
+        assert!(output
+            .iter()
+            .all(|(l, r)| l.is_finite() && l.abs() < 10.0 && r.abs() < 10.0));
+    }
+}
@@ -0,0 +1,336 @@
+//! Dynamic range compressor and limiter.
+//!
+//! # Overview
+//!
+//! Compressor reduces the volume of loud sounds, making the difference between loud and quiet parts of the
+//! signal smaller. When the level of the signal exceeds the threshold, the part above the threshold is divided
+//! by the ratio. Limiter is a compressor with infinite ratio, it does not let the signal go above the threshold
+//! (see [`Compressor::limiter`]).
+//!
+//! # Sidechain
+//!
+//! The level of the signal could be measured on the signal of another audio bus, it is called sidechain
+//! compression. For example, it could be used to "duck" music when a character speaks:
+//!
+//! ```
+//! use fyrox_sound::{
+//!     bus::AudioBus,
+//!     context::SoundContext,
+//!     effects::{compressor::Compressor, Effect},
+//! };
+//!
+//! fn add_ducking(context: &mut SoundContext) {
+//!     let mut compressor = Compressor::new();
+//!     compressor.set_threshold(-30.0);
+//!     compressor.set_ratio(8.0);
+//!     compressor.set_sidechain_bus("Voice");
+//!
+//!     let mut music = AudioBus::new("Music".to_string());
+//!     music.add_effect(Effect::Compressor(compressor));
+//!
+//!     let mut state = context.state();
+//!     let graph = state.bus_graph_mut();
+//!     let primary_bus = graph.primary_bus_handle();
+//!     graph.add_bus(music, primary_bus);
+//!     graph.add_bus(AudioBus::new("Voice".to_string()), primary_bus);
+//! }
+//! ```
+
+use crate::{context::SAMPLE_RATE, effects::EffectRenderTrait};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+
+fn db_to_gain(db: f32) -> f32 {
+    10.0f32.powf(db / 20.0)
+}
+
+fn gain_to_db(gain: f32) -> f32 {
+    20.0 * gain.max(1.0e-9).log10()
+}
+
+/// Calculates a coefficient of one-pole smoothing filter, that reaches ~63% of the target value
+/// after the given time.
+fn time_coefficient(time: f32) -> f32 {
+    if time <= 0.0 {
+        0.0
+    } else {
+        (-1.0 / (time * SAMPLE_RATE as f32)).exp()
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct Compressor {
+    #[reflect(
+        description = "Level (in decibels) above which the signal is compressed.",
+        setter = "set_threshold",
+        max_value = 0.0
+    )]
+    threshold: f32,
+
+    #[reflect(
+        description = "Amount of compression, for example 4.0 means that 4 dB above the threshold become 1 dB.",
+        setter = "set_ratio",
+        min_value = 1.0
+    )]
+    ratio: f32,
+
+    #[reflect(
+        description = "Time (in seconds) the compressor takes to react on loud signal.",
+        setter = "set_attack_time",
+        min_value = 0.0
+    )]
+    attack_time: f32,
+
+    #[reflect(
+        description = "Time (in seconds) the compressor takes to return to unity gain.",
+        setter = "set_release_time",
+        min_value = 0.0
+    )]
+    release_time: f32,
+
+    #[reflect(
+        description = "Gain (in decibels) applied to the compressed signal.",
+        setter = "set_makeup_gain"
+    )]
+    makeup_gain: f32,
+
+    #[reflect(
+        description = "Name of an audio bus which signal is used to measure the level. Empty - use own signal."
+    )]
+    sidechain_bus: String,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    gain_reduction: f32,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    sidechain: Option<Vec<(f32, f32)>>,
+}
+
+impl Default for Compressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Compressor {
+    /// Creates new compressor with -20 dB threshold, 4:1 ratio, 10 ms attack time and 100 ms release time.
+    pub fn new() -> Self {
+        Self {
+            threshold: -20.0,
+            ratio: 4.0,
+            attack_time: 0.01,
+            release_time: 0.1,
+            makeup_gain: 0.0,
+            sidechain_bus: Default::default(),
+            gain_reduction: 0.0,
+            sidechain: None,
+        }
+    }
+
+    /// Creates new limiter (compressor with infinite ratio) with the given threshold in decibels and
+    /// instant attack.
+    pub fn limiter(threshold: f32) -> Self {
+        Self {
+            threshold,
+            ratio: f32::INFINITY,
+            attack_time: 0.0,
+            release_time: 0.05,
+            ..Self::new()
+        }
+    }
+
+    /// Sets the level (in decibels) above which the signal is compressed. Usually it is in `[-60.0; 0.0]`
+    /// range.
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold;
+    }
+
+    /// Returns current threshold in decibels.
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Sets the amount of compression. For example, 4.0 means that every 4 dB above the threshold become
+    /// 1 dB. Use [`f32::INFINITY`] to turn the compressor into a limiter.
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.max(1.0);
+    }
+
+    /// Returns current ratio.
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Sets the time (in seconds) the compressor takes to react on loud signal.
+    pub fn set_attack_time(&mut self, attack_time: f32) {
+        self.attack_time = attack_time.max(0.0);
+    }
+
+    /// Returns current attack time in seconds.
+    pub fn attack_time(&self) -> f32 {
+        self.attack_time
+    }
+
+    /// Sets the time (in seconds) the compressor takes to return to unity gain after the signal became
+    /// quiet.
+    pub fn set_release_time(&mut self, release_time: f32) {
+        self.release_time = release_time.max(0.0);
+    }
+
+    /// Returns current release time in seconds.
+    pub fn release_time(&self) -> f32 {
+        self.release_time
+    }
+
+    /// Sets the gain (in decibels) applied to the compressed signal to compensate volume loss.
+    pub fn set_makeup_gain(&mut self, makeup_gain: f32) {
+        self.makeup_gain = makeup_gain;
+    }
+
+    /// Returns current makeup gain in decibels.
+    pub fn makeup_gain(&self) -> f32 {
+        self.makeup_gain
+    }
+
+    /// Sets the name of an audio bus, which signal will be used to measure the level. The signal is taken
+    /// from the input of the bus before any of its effects were applied. Empty name means that the
+    /// compressor uses its own input.
+    pub fn set_sidechain_bus<S: AsRef<str>>(&mut self, name: S) {
+        self.sidechain_bus = name.as_ref().to_owned();
+    }
+
+    /// Returns the name of the sidechain audio bus.
+    pub fn sidechain_bus(&self) -> &str {
+        &self.sidechain_bus
+    }
+
+    /// Returns current gain reduction in decibels (zero or negative value).
+    pub fn gain_reduction(&self) -> f32 {
+        self.gain_reduction
+    }
+
+    pub(crate) fn set_sidechain_input(&mut self, input: Option<&[(f32, f32)]>) {
+        match input {
+            Some(input) => {
+                let sidechain = self.sidechain.get_or_insert_with(Default::default);
+                sidechain.clear();
+                sidechain.extend_from_slice(input);
+            }
+            None => self.sidechain = None,
+        }
+    }
+}
+
+impl EffectRenderTrait for Compressor {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        let attack = time_coefficient(self.attack_time);
+        let release = time_coefficient(self.release_time);
+        let makeup = db_to_gain(self.makeup_gain);
+        let slope = 1.0 / self.ratio - 1.0;
+
+        for (i, ((left, right), (output_left, output_right))) in
+            input.iter().zip(output.iter_mut()).enumerate()
+        {
+            let (detector_left, detector_right) = self
+                .sidechain
+                .as_ref()
+                .and_then(|sidechain| sidechain.get(i).copied())
+                .unwrap_or((*left, *right));
+
+            let level = gain_to_db(detector_left.abs().max(detector_right.abs()));
+            let target = (level - self.threshold).max(0.0) * slope;
+
+            // Gain reduction is negative, so the lower target means that the signal became louder.
+            let coefficient = if target < self.gain_reduction {
+                attack
+            } else {
+                release
+            };
+            self.gain_reduction = target + coefficient * (self.gain_reduction - target);
+
+            let gain = db_to_gain(self.gain_reduction) * makeup;
+            *output_left = *left * gain;
+            *output_right = *right * gain;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::effects::{compressor::Compressor, EffectRenderTrait};
+
+    fn render(compressor: &mut Compressor, input: &[(f32, f32)]) -> Vec<(f32, f32)> {
+        let mut output = vec![(0.0, 0.0); input.len()];
+        compressor.render(input, &mut output);
+        output
+    }
+
+    #[test]
+    fn test_quiet_signal_is_not_affected() {
+        let mut compressor = Compressor::new();
+        let input = vec![(0.05, -0.05); 256];
+        assert_eq!(render(&mut compressor, &input), input);
+    }
+
+    #[test]
+    fn test_compression() {
+        let mut compressor = Compressor::new();
+        compressor.set_threshold(-20.0);
+        compressor.set_ratio(4.0);
+        compressor.set_attack_time(0.0);
+
+        // 0 dB is 20 dB above the threshold, it must become 5 dB above (-15 dB).
+        let output = render(&mut compressor, &[(1.0, 1.0); 16]);
+        let expected = 10.0f32.powf(-15.0 / 20.0);
+        for (left, right) in output {
+            assert!((left - expected).abs() < 1.0e-4);
+            assert!((right - expected).abs() < 1.0e-4);
+        }
+    }
+
+    #[test]
+    fn test_limiter() {
+        let mut limiter = Compressor::limiter(-6.0);
+        let output = render(&mut limiter, &[(2.0, -2.0); 16]);
+        let max = 10.0f32.powf(-6.0 / 20.0);
+        for (left, right) in output {
+            assert!(left.abs() <= max + 1.0e-4);
+            assert!(right.abs() <= max + 1.0e-4);
+        }
+    }
+
+    #[test]
+    fn test_release() {
+        let mut compressor = Compressor::new();
+        compressor.set_attack_time(0.0);
+        render(&mut compressor, &[(1.0, 1.0); 16]);
+        assert!(compressor.gain_reduction() < -14.0);
+
+        // Gain must recover smoothly after the signal became quiet.
+        render(&mut compressor, &[(0.0, 0.0); 16]);
+        let reduction = compressor.gain_reduction();
+        assert!(reduction < -10.0);
+        render(&mut compressor, &[(0.0, 0.0); 44100]);
+        assert!(compressor.gain_reduction() > reduction);
+        assert!(compressor.gain_reduction() > -0.01);
+    }
+
+    #[test]
+    fn test_sidechain() {
+        let mut compressor = Compressor::new();
+        compressor.set_attack_time(0.0);
+        compressor.set_sidechain_input(Some(&[(1.0, 1.0); 16]));
+
+        // Quiet input must be attenuated because of loud sidechain signal.
+        let output = render(&mut compressor, &[(0.05, 0.05); 16]);
+        assert!(output.iter().all(|(left, _)| *left < 0.05 * 0.2));
+
+        compressor.set_sidechain_input(Some(&[(0.0, 0.0); 16]));
+        render(&mut compressor, &[(0.05, 0.05); 44100]);
+        compressor.set_sidechain_input(None);
+        let output = render(&mut compressor, &[(0.05, 0.05); 16]);
+        assert!(output.iter().all(|(left, _)| (*left - 0.05).abs() < 1.0e-3));
+    }
+}
@@ -0,0 +1,186 @@
+//! Feedback delay (echo) effect.
+//!
+//! # Overview
+//!
+//! Delay repeats the input signal after some time, each repetition is quieter than the previous one. The amount
+//! of repetitions is defined by the feedback, the delayed signal is fed back to the input of the delay line.
+
+use crate::{context::SAMPLE_RATE, dsp::DelayLine, effects::EffectRenderTrait};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+
+/// See module docs.
+#[derive(Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct Delay {
+    #[reflect(
+        description = "Time (in seconds) between repetitions.",
+        setter = "set_delay_time",
+        min_value = 0.0
+    )]
+    delay_time: f32,
+
+    #[reflect(
+        description = "Amount of the delayed signal fed back to the delay line. Defines the amount of repetitions.",
+        setter = "set_feedback",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    feedback: f32,
+
+    #[reflect(
+        description = "Amount of the input signal passed to output without any processing.",
+        setter = "set_dry",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    dry: f32,
+
+    #[reflect(
+        description = "Amount of the delayed signal passed to output.",
+        setter = "set_wet",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    wet: f32,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    left: DelayLine,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    right: DelayLine,
+}
+
+impl Default for Delay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Delay {
+    /// Creates new delay effect with 250 ms delay time and 0.4 feedback.
+    pub fn new() -> Self {
+        Self {
+            delay_time: 0.25,
+            feedback: 0.4,
+            dry: 1.0,
+            wet: 0.5,
+            left: Default::default(),
+            right: Default::default(),
+        }
+    }
+
+    /// Sets the time (in seconds) between repetitions. Changing the time clears the delay line.
+    pub fn set_delay_time(&mut self, delay_time: f32) {
+        self.delay_time = delay_time.max(0.0);
+    }
+
+    /// Returns current delay time in seconds.
+    pub fn delay_time(&self) -> f32 {
+        self.delay_time
+    }
+
+    /// Sets the amount of the delayed signal fed back to the delay line. The value is clamped to `[0.0; 0.99]`
+    /// range to keep the effect stable.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.clamp(0.0, 0.99);
+    }
+
+    /// Returns current feedback.
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets how much of the delayed signal should be passed to output.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+
+    fn delay_len(&self) -> usize {
+        ((self.delay_time * SAMPLE_RATE as f32).round() as usize).max(1)
+    }
+}
+
+impl EffectRenderTrait for Delay {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        let len = self.delay_len();
+        if self.left.len() != len {
+            self.left = DelayLine::new(len);
+            self.right = DelayLine::new(len);
+        }
+
+        for ((left, right), (output_left, output_right)) in input.iter().zip(output) {
+            let delayed_left = self.left.tap(len as f32);
+            let delayed_right = self.right.tap(len as f32);
+
+            self.left.feed(*left + delayed_left * self.feedback);
+            self.right.feed(*right + delayed_right * self.feedback);
+
+            *output_left = *left * self.dry + delayed_left * self.wet;
+            *output_right = *right * self.dry + delayed_right * self.wet;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        context::SAMPLE_RATE,
+        effects::{delay::Delay, EffectRenderTrait},
+    };
+
+    #[test]
+    fn test_echo() {
+        let mut delay = Delay::new();
+        delay.set_delay_time(100.0 / SAMPLE_RATE as f32);
+        delay.set_feedback(0.5);
+        delay.set_dry(1.0);
+        delay.set_wet(1.0);
+
+        let mut input = vec![(0.0, 0.0); 350];
+        input[0] = (1.0, -1.0);
+        let mut output = vec![(0.0, 0.0); input.len()];
+        delay.render(&input, &mut output);
+
+        assert_eq!(output[0], (1.0, -1.0));
+        assert_eq!(output[100], (1.0, -1.0));
+        assert_eq!(output[200], (0.5, -0.5));
+        assert_eq!(output[300], (0.25, -0.25));
+
+        let silent = output
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i % 100 != 0)
+            .all(|(_, sample)| *sample == (0.0, 0.0));
+        assert!(silent);
+    }
+
+    #[test]
+    fn test_state_is_kept_between_buffers() {
+        let mut delay = Delay::new();
+        delay.set_delay_time(10.0 / SAMPLE_RATE as f32);
+        delay.set_dry(0.0);
+
+        let mut output = [(0.0, 0.0); 8];
+        delay.render(&[(1.0, 1.0); 8], &mut output);
+        assert_eq!(output, [(0.0, 0.0); 8]);
+
+        delay.render(&[(0.0, 0.0); 8], &mut output);
+        assert_eq!(output[2], (0.5, 0.5));
+    }
+}
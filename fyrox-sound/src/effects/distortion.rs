@@ -0,0 +1,217 @@
+//! Waveshaper distortion effect.
+//!
+//! # Overview
+//!
+//! Distortion amplifies the input signal and then passes it through a non-linear function (waveshaper), that
+//! limits its amplitude. It adds new harmonics to the signal, making it sound "dirty" or "aggressive". See
+//! [`WaveshaperKind`] for the list of available shapes.
+
+use crate::effects::EffectRenderTrait;
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Non-linear function that is used to shape the signal.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum WaveshaperKind {
+    /// Smoothly saturates the signal using hyperbolic tangent. Produces "warm" tube-like sound.
+    #[default]
+    SoftClip,
+    /// Cuts everything above unit amplitude. Produces harsh sound with lots of high harmonics.
+    HardClip,
+    /// Folds the signal back when it exceeds unit amplitude. Produces metallic, synthesizer-like sound.
+    Foldback,
+}
+
+impl WaveshaperKind {
+    fn shape(self, sample: f32) -> f32 {
+        match self {
+            WaveshaperKind::SoftClip => sample.tanh(),
+            WaveshaperKind::HardClip => sample.clamp(-1.0, 1.0),
+            WaveshaperKind::Foldback => 1.0 - ((sample + 1.0).rem_euclid(4.0) - 2.0).abs(),
+        }
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct Distortion {
+    #[reflect(description = "Shape of the distortion.")]
+    kind: WaveshaperKind,
+
+    #[reflect(
+        description = "Amplification of the signal before shaping. The higher the value, the stronger the distortion.",
+        setter = "set_drive",
+        min_value = 0.0
+    )]
+    drive: f32,
+
+    #[reflect(
+        description = "Gain of the shaped signal.",
+        setter = "set_gain",
+        min_value = 0.0
+    )]
+    gain: f32,
+
+    #[reflect(
+        description = "Amount of the input signal passed to output without any processing.",
+        setter = "set_dry",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    dry: f32,
+
+    #[reflect(
+        description = "Amount of the distorted signal passed to output.",
+        setter = "set_wet",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    wet: f32,
+}
+
+impl Default for Distortion {
+    fn default() -> Self {
+        Self::new(WaveshaperKind::SoftClip)
+    }
+}
+
+impl Distortion {
+    /// Creates new distortion effect of the given kind with drive of 4.0.
+    pub fn new(kind: WaveshaperKind) -> Self {
+        Self {
+            kind,
+            drive: 4.0,
+            gain: 0.5,
+            dry: 0.0,
+            wet: 1.0,
+        }
+    }
+
+    /// Sets new shape of the distortion.
+    pub fn set_kind(&mut self, kind: WaveshaperKind) {
+        self.kind = kind;
+    }
+
+    /// Returns current shape of the distortion.
+    pub fn kind(&self) -> WaveshaperKind {
+        self.kind
+    }
+
+    /// Sets amplification of the signal before shaping. The higher the value, the stronger the distortion.
+    pub fn set_drive(&mut self, drive: f32) {
+        self.drive = drive.max(0.0);
+    }
+
+    /// Returns current drive.
+    pub fn drive(&self) -> f32 {
+        self.drive
+    }
+
+    /// Sets gain of the shaped signal.
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain.max(0.0);
+    }
+
+    /// Returns current gain.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets how much of the distorted signal should be passed to output.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+}
+
+impl EffectRenderTrait for Distortion {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        let wet = self.wet * self.gain;
+        for ((left, right), (output_left, output_right)) in input.iter().zip(output) {
+            *output_left = *left * self.dry + self.kind.shape(*left * self.drive) * wet;
+            *output_right = *right * self.dry + self.kind.shape(*right * self.drive) * wet;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::effects::{
+        distortion::{Distortion, WaveshaperKind},
+        EffectRenderTrait,
+    };
+
+    fn render(distortion: &mut Distortion, input: &[f32]) -> Vec<f32> {
+        let input = input.iter().map(|s| (*s, *s)).collect::<Vec<_>>();
+        let mut output = vec![(0.0, 0.0); input.len()];
+        distortion.render(&input, &mut output);
+        output.into_iter().map(|(left, _)| left).collect()
+    }
+
+    #[test]
+    fn test_waveshapers() {
+        let input = [0.0, 0.1, -0.1, 0.5, 0.75, 1.0, -1.0];
+
+        for kind in [
+            WaveshaperKind::SoftClip,
+            WaveshaperKind::HardClip,
+            WaveshaperKind::Foldback,
+        ] {
+            let mut distortion = Distortion::new(kind);
+            distortion.set_gain(1.0);
+            let output = render(&mut distortion, &input);
+            assert!(output.iter().all(|s| s.abs() <= 1.0), "{kind:?}");
+            // Shapes are odd functions.
+            assert_eq!(output[1], -output[2], "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn test_shapes() {
+        let mut hard = Distortion::new(WaveshaperKind::HardClip);
+        hard.set_drive(2.0);
+        hard.set_gain(1.0);
+        assert_eq!(render(&mut hard, &[0.25, 0.75, -0.75]), [0.5, 1.0, -1.0]);
+
+        let mut foldback = Distortion::new(WaveshaperKind::Foldback);
+        foldback.set_drive(1.0);
+        foldback.set_gain(1.0);
+        assert_eq!(
+            render(&mut foldback, &[0.5, 1.5, 2.0, -1.5]),
+            [0.5, 0.5, 0.0, -0.5]
+        );
+
+        let mut soft = Distortion::new(WaveshaperKind::SoftClip);
+        soft.set_drive(1.0);
+        soft.set_gain(1.0);
+        soft.set_dry(1.0);
+        soft.set_wet(0.0);
+        assert_eq!(render(&mut soft, &[0.3, -2.0]), [0.3, -2.0]);
+    }
+}
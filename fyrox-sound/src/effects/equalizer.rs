@@ -0,0 +1,303 @@
+//! Multi-band parametric equalizer.
+//!
+//! # Overview
+//!
+//! Equalizer changes the volume of specific frequency bands of the signal. Each band is a second-order filter
+//! with its own kind, center frequency, gain and quality. Bands are applied one after another.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::effects::{
+//!     equalizer::{Equalizer, EqualizerBand, EqualizerBandKind},
+//!     Effect,
+//! };
+//!
+//! fn make_telephone_effect() -> Effect {
+//!     Effect::Equalizer(Equalizer::new(vec![
+//!         EqualizerBand::new(EqualizerBandKind::HighPass, 300.0, 0.0, 0.7),
+//!         EqualizerBand::new(EqualizerBandKind::Peak, 1500.0, 6.0, 1.0),
+//!         EqualizerBand::new(EqualizerBandKind::LowPass, 3400.0, 0.0, 0.7),
+//!     ]))
+//! }
+//! ```
+
+use crate::{
+    context::SAMPLE_RATE,
+    dsp::filters::{Biquad, BiquadKind},
+    effects::EffectRenderTrait,
+};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Kind of an equalizer band.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum EqualizerBandKind {
+    /// Boosts or cuts frequencies below the band frequency.
+    LowShelf,
+    /// Boosts or cuts frequencies around the band frequency.
+    #[default]
+    Peak,
+    /// Boosts or cuts frequencies above the band frequency.
+    HighShelf,
+    /// Removes frequencies above the band frequency, gain is ignored.
+    LowPass,
+    /// Removes frequencies below the band frequency, gain is ignored.
+    HighPass,
+}
+
+/// A single band of [`Equalizer`].
+#[derive(Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct EqualizerBand {
+    #[reflect(description = "Kind of the band.")]
+    kind: EqualizerBandKind,
+
+    #[reflect(
+        description = "Center (or cutoff) frequency of the band in Hertz.",
+        min_value = 0.0
+    )]
+    frequency: f32,
+
+    #[reflect(description = "Gain of the band in decibels.")]
+    gain: f32,
+
+    #[reflect(
+        description = "Band width, the higher the value the narrower the band.",
+        min_value = 0.01
+    )]
+    quality: f32,
+
+    #[reflect(hidden)]
+    left: Biquad,
+
+    #[reflect(hidden)]
+    right: Biquad,
+}
+
+impl Default for EqualizerBand {
+    fn default() -> Self {
+        Self::new(EqualizerBandKind::Peak, 1000.0, 0.0, 1.0)
+    }
+}
+
+impl EqualizerBand {
+    /// Creates new band, where `frequency` is a center (or cutoff) frequency in Hertz, `gain` is a gain in decibels
+    /// and `quality` defines the band width (the higher the value the narrower the band).
+    pub fn new(kind: EqualizerBandKind, frequency: f32, gain: f32, quality: f32) -> Self {
+        let mut band = Self {
+            kind,
+            frequency,
+            gain,
+            quality,
+            left: Default::default(),
+            right: Default::default(),
+        };
+        band.update();
+        band
+    }
+
+    /// Sets new kind of the band.
+    pub fn set_kind(&mut self, kind: EqualizerBandKind) {
+        self.kind = kind;
+        self.update();
+    }
+
+    /// Returns current kind of the band.
+    pub fn kind(&self) -> EqualizerBandKind {
+        self.kind
+    }
+
+    /// Sets new center (or cutoff) frequency of the band in Hertz.
+    pub fn set_frequency(&mut self, frequency: f32) {
+        self.frequency = frequency;
+        self.update();
+    }
+
+    /// Returns center (or cutoff) frequency of the band in Hertz.
+    pub fn frequency(&self) -> f32 {
+        self.frequency
+    }
+
+    /// Sets new gain of the band in decibels.
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain;
+        self.update();
+    }
+
+    /// Returns gain of the band in decibels.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Sets new quality of the band.
+    pub fn set_quality(&mut self, quality: f32) {
+        self.quality = quality;
+        self.update();
+    }
+
+    /// Returns quality of the band.
+    pub fn quality(&self) -> f32 {
+        self.quality
+    }
+
+    fn update(&mut self) {
+        let kind = || match self.kind {
+            EqualizerBandKind::LowShelf => BiquadKind::LowShelf,
+            EqualizerBandKind::Peak => BiquadKind::Peak,
+            EqualizerBandKind::HighShelf => BiquadKind::HighShelf,
+            EqualizerBandKind::LowPass => BiquadKind::LowPass,
+            EqualizerBandKind::HighPass => BiquadKind::HighPass,
+        };
+        let fc = (self.frequency / SAMPLE_RATE as f32).clamp(0.0, 0.5);
+        // Biquad uses square root of the linear gain for shelf and peak filters.
+        let gain = 10.0f32.powf(self.gain / 40.0);
+        let quality = self.quality.max(0.01);
+        self.left.tune(kind(), fc, gain, quality);
+        self.right.tune(kind(), fc, gain, quality);
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct Equalizer {
+    #[reflect(description = "Bands of the equalizer, they are applied one after another.")]
+    bands: Vec<EqualizerBand>,
+}
+
+impl Default for Equalizer {
+    /// Creates flat three-band equalizer with low shelf at 200 Hz, peak at 1 kHz and high shelf at 5 kHz.
+    fn default() -> Self {
+        Self::new(vec![
+            EqualizerBand::new(EqualizerBandKind::LowShelf, 200.0, 0.0, 0.7),
+            EqualizerBand::new(EqualizerBandKind::Peak, 1000.0, 0.0, 1.0),
+            EqualizerBand::new(EqualizerBandKind::HighShelf, 5000.0, 0.0, 0.7),
+        ])
+    }
+}
+
+impl Equalizer {
+    /// Creates new equalizer with the given set of bands.
+    pub fn new(bands: Vec<EqualizerBand>) -> Self {
+        Self { bands }
+    }
+
+    /// Returns a shared reference to the bands of the equalizer.
+    pub fn bands(&self) -> &[EqualizerBand] {
+        &self.bands
+    }
+
+    /// Returns a mutable reference to the bands of the equalizer.
+    pub fn bands_mut(&mut self) -> &mut Vec<EqualizerBand> {
+        &mut self.bands
+    }
+}
+
+impl EffectRenderTrait for Equalizer {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        // Bands could be edited directly via reflection, so make sure that the filters are in actual
+        // state. It is cheap, since it is done once per buffer.
+        for band in self.bands.iter_mut() {
+            band.update();
+        }
+
+        for ((left, right), (output_left, output_right)) in input.iter().zip(output) {
+            let mut sample_left = *left;
+            let mut sample_right = *right;
+            for band in self.bands.iter_mut() {
+                sample_left = band.left.feed(sample_left);
+                sample_right = band.right.feed(sample_right);
+            }
+            *output_left = sample_left;
+            *output_right = sample_right;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        context::SAMPLE_RATE,
+        effects::{
+            equalizer::{Equalizer, EqualizerBand, EqualizerBandKind},
+            EffectRenderTrait,
+        },
+    };
+    use std::f32::consts::PI;
+
+    /// Renders one second of a sine tone and returns its peak amplitude in the second half of the output (to skip
+    /// filter's transition period).
+    fn tone_amplitude(equalizer: &mut Equalizer, frequency: f32) -> f32 {
+        let input = (0..SAMPLE_RATE)
+            .map(|i| {
+                let s = (2.0 * PI * frequency * i as f32 / SAMPLE_RATE as f32).sin();
+                (s, s)
+            })
+            .collect::<Vec<_>>();
+        let mut output = vec![(0.0, 0.0); input.len()];
+        equalizer.render(&input, &mut output);
+        output[output.len() / 2..]
+            .iter()
+            .map(|(left, _)| left.abs())
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_flat_equalizer() {
+        let mut equalizer = Equalizer::default();
+        for frequency in [50.0, 1000.0, 10000.0] {
+            let amplitude = tone_amplitude(&mut equalizer, frequency);
+            assert!((amplitude - 1.0).abs() < 0.01, "{frequency}: {amplitude}");
+        }
+    }
+
+    #[test]
+    fn test_peak_band() {
+        let band = || EqualizerBand::new(EqualizerBandKind::Peak, 1000.0, 12.0, 2.0);
+
+        let mut equalizer = Equalizer::new(vec![band()]);
+        let amplitude = tone_amplitude(&mut equalizer, 1000.0);
+        // +12 dB is ~3.98 times louder.
+        assert!((amplitude - 3.98).abs() < 0.05, "{amplitude}");
+
+        let mut equalizer = Equalizer::new(vec![band()]);
+        let amplitude = tone_amplitude(&mut equalizer, 100.0);
+        assert!((amplitude - 1.0).abs() < 0.05, "{amplitude}");
+    }
+
+    #[test]
+    fn test_band_pass_chain() {
+        let bands = || {
+            vec![
+                EqualizerBand::new(EqualizerBandKind::HighPass, 300.0, 0.0, 0.7),
+                EqualizerBand::new(EqualizerBandKind::LowPass, 3400.0, 0.0, 0.7),
+            ]
+        };
+
+        let pass = tone_amplitude(&mut Equalizer::new(bands()), 1000.0);
+        let low = tone_amplitude(&mut Equalizer::new(bands()), 40.0);
+        let high = tone_amplitude(&mut Equalizer::new(bands()), 15000.0);
+        assert!(pass > 0.9, "{pass}");
+        assert!(low < 0.1, "{low}");
+        assert!(high < 0.1, "{high}");
+    }
+
+    #[test]
+    fn test_editing_via_fields() {
+        let mut equalizer = Equalizer::new(vec![EqualizerBand::default()]);
+        equalizer.bands_mut()[0].gain = -12.0;
+        let amplitude = tone_amplitude(&mut equalizer, 1000.0);
+        assert!((amplitude - 0.251).abs() < 0.01, "{amplitude}");
+    }
+}
@@ -1,16 +1,26 @@
 //! Contins everything related to audio effects that can be applied to an audio bus.
 
-use crate::{
-    effects::filter::{
+use crate::effects::{
+    chorus::Chorus,
+    compressor::Compressor,
+    delay::Delay,
+    distortion::Distortion,
+    equalizer::Equalizer,
+    filter::{
         AllPassFilterEffect, BandPassFilterEffect, HighPassFilterEffect, HighShelfFilterEffect,
         LowPassFilterEffect, LowShelfFilterEffect,
     },
-    effects::reverb::Reverb,
+    reverb::Reverb,
 };
 use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
 use std::ops::{Deref, DerefMut};
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+pub mod chorus;
+pub mod compressor;
+pub mod delay;
+pub mod distortion;
+pub mod equalizer;
 pub mod filter;
 pub mod reverb;
 
@@ -91,6 +101,16 @@ pub enum Effect {
     LowShelfFilter(LowShelfFilterEffect),
     /// See [`HighShelfFilterEffect`] docs for more info.
     HighShelfFilter(HighShelfFilterEffect),
+    /// See [`Compressor`] docs for more info.
+    Compressor(Compressor),
+    /// See [`Delay`] docs for more info.
+    Delay(Delay),
+    /// See [`Chorus`] docs for more info.
+    Chorus(Chorus),
+    /// See [`Distortion`] docs for more info.
+    Distortion(Distortion),
+    /// See [`Equalizer`] docs for more info.
+    Equalizer(Equalizer),
 }
 
 impl Default for Effect {
@@ -99,6 +119,24 @@ impl Default for Effect {
     }
 }
 
+impl Effect {
+    /// Returns a name of an audio bus, which signal is used by the effect as sidechain input (if any).
+    pub(crate) fn sidechain_bus(&self) -> Option<&str> {
+        match self {
+            Effect::Compressor(compressor) if !compressor.sidechain_bus().is_empty() => {
+                Some(compressor.sidechain_bus())
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn set_sidechain_input(&mut self, input: Option<&[(f32, f32)]>) {
+        if let Effect::Compressor(compressor) = self {
+            compressor.set_sidechain_input(input)
+        }
+    }
+}
+
 pub(crate) trait EffectRenderTrait {
     fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]);
 }
@@ -114,6 +152,11 @@ macro_rules! static_dispatch {
             Effect::AllPassFilter(v) => v.$func($($args),*),
             Effect::LowShelfFilter(v) => v.$func($($args),*),
             Effect::HighShelfFilter(v) => v.$func($($args),*),
+            Effect::Compressor(v) => v.$func($($args),*),
+            Effect::Delay(v) => v.$func($($args),*),
+            Effect::Chorus(v) => v.$func($($args),*),
+            Effect::Distortion(v) => v.$func($($args),*),
+            Effect::Equalizer(v) => v.$func($($args),*),
         }
     };
 }
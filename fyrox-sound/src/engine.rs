@@ -4,10 +4,14 @@
 //!
 //! Sound engine manages contexts, feeds output device with data.
 
-use crate::context::{SoundContext, SAMPLE_RATE};
+use crate::{
+    context::{SoundContext, SAMPLE_RATE},
+    offline::OfflineRenderer,
+};
 use fyrox_core::visitor::{Visit, VisitResult, Visitor};
 use std::error::Error;
 use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 
 /// Sound engine manages contexts, feeds output device with data. Sound engine instance can be cloned,
 /// however this is always a "shallow" clone, because actual sound engine data is wrapped in Arc.
@@ -81,6 +85,15 @@ impl SoundEngine {
         self.state().output_device = None;
     }
 
+    /// Renders the given amount of time without an output device (as fast as possible) and returns
+    /// interleaved stereo samples at the given sample rate. This is a shortcut for a single call of
+    /// [`OfflineRenderer::render`], use [`OfflineRenderer`] directly if you need to render in multiple
+    /// parts or to write the result to a WAV file. This method must be used only if the engine was
+    /// created via [`SoundEngine::without_device`].
+    pub fn render_offline(&self, duration: Duration, sample_rate: u32) -> Vec<(f32, f32)> {
+        OfflineRenderer::new(self.clone(), sample_rate).render(duration)
+    }
+
     /// Provides direct access to actual engine data.
     pub fn state(&self) -> MutexGuard<State> {
         self.0.lock().unwrap()
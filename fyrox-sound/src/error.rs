@@ -53,6 +53,9 @@ pub enum SoundError {
 
     /// A buffer is not loaded yet, consider to `await` it before use.
     BufferIsNotLoaded,
+
+    /// WAV encoder specific error, can occur while writing rendered sound to a WAV file.
+    WavEncoder(hound::Error),
 }
 
 impl From<std::io::Error> for SoundError {
@@ -73,6 +76,15 @@ impl From<lewton::VorbisError> for SoundError {
     }
 }
 
+impl From<hound::Error> for SoundError {
+    fn from(e: hound::Error) -> Self {
+        match e {
+            hound::Error::IoError(io) => SoundError::Io(io),
+            _ => SoundError::WavEncoder(e),
+        }
+    }
+}
+
 impl Display for SoundError {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         match self {
@@ -93,6 +105,7 @@ impl Display for SoundError {
             SoundError::DecoderError(de) => write!(f, "internal decoder error: {:?}", de),
             SoundError::BufferFailedToLoad => write!(f, "a buffer failed to load"),
             SoundError::BufferIsNotLoaded => write!(f, "a buffer is not loaded yet"),
+            SoundError::WavEncoder(e) => write!(f, "wav encoder error: {}", e),
         }
     }
 }
@@ -7,6 +7,8 @@
 //! - Streaming.
 //! - Head-related transfer function support ([HRTF](https://en.wikipedia.org/wiki/Head-related_transfer_function)).
 //! - Reverb effect.
+//! - Compressor/limiter with sidechain, delay, chorus/flanger, distortion and parametric equalizer effects.
+//! - Offline (non-realtime) rendering to WAV files.
 //!
 //! ## Examples
 //!
@@ -71,6 +73,7 @@ pub mod effects;
 pub mod engine;
 pub mod error;
 pub mod listener;
+pub mod offline;
 pub mod renderer;
 pub mod source;
 
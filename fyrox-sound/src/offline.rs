@@ -0,0 +1,369 @@
+//! Offline (non-realtime) rendering.
+//!
+//! # Overview
+//!
+//! Offline renderer mixes all the contexts of a sound engine as fast as possible, without any audio output
+//! device. The result is fully deterministic, which makes it useful for golden-file tests of mixes and for
+//! generating audio previews on machines without sound hardware (for example, on CI servers). The output
+//! could be written to a WAV file or taken as a set of raw samples.
+//!
+//! The engine always mixes sounds at [`SAMPLE_RATE`], it is a compile-time constant that is used by every
+//! part of the engine (filters, reverb, HRTF), so the mix itself can't be done at a different rate. If the
+//! requested sample rate is different, the output is resampled using windowed sinc interpolation, which also
+//! removes frequencies that can't be represented at the requested rate. The resampler has a few limitations:
+//!
+//! - Frequencies close to the Nyquist frequency of the lower of two sample rates are slightly attenuated.
+//! - The signal before the beginning of rendering is considered silent, so the first few samples of the
+//! output may differ from what a realtime output would produce.
+//!
+//! Use [`SAMPLE_RATE`] as the output sample rate to get exact samples of the mix.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use fyrox_sound::{
+//!     context::SoundContext,
+//!     engine::SoundEngine,
+//!     offline::{OfflineRenderer, WavSampleFormat},
+//! };
+//! use std::time::Duration;
+//!
+//! let engine = SoundEngine::without_device();
+//! let context = SoundContext::new();
+//! engine.state().add_context(context.clone());
+//!
+//! // Add some sources to the context here.
+//!
+//! let mut renderer = OfflineRenderer::new(engine, 48000);
+//! renderer
+//!     .render_to_wav_file("preview.wav", Duration::from_secs(3), WavSampleFormat::Int16)
+//!     .unwrap();
+//! ```
+//!
+//! # Important notes
+//!
+//! Offline renderer must be used with an engine created by [`SoundEngine::without_device`], otherwise the
+//! output device will advance the contexts concurrently with the renderer.
+
+use crate::{
+    context::SAMPLE_RATE,
+    engine::{SoundEngine, State},
+    error::SoundError,
+};
+use std::{
+    io::{Seek, Write},
+    path::Path,
+    time::Duration,
+};
+
+/// Format of samples in a WAV file.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WavSampleFormat {
+    /// 32-bit floating point samples. Samples are written as is, without clamping.
+    #[default]
+    Float32,
+    /// 16-bit integer samples. Samples are clamped to `[-1.0; 1.0]` range.
+    Int16,
+}
+
+/// Amount of zero crossings of the resampling filter at each side of its center. The more crossings - the
+/// sharper the filter and the slower the resampling.
+const SINC_ZERO_CROSSINGS: usize = 16;
+
+/// See module docs.
+pub struct OfflineRenderer {
+    engine: SoundEngine,
+    sample_rate: u32,
+    // Samples rendered at the engine's sample rate, but not yet consumed by the resampler.
+    pending: Vec<(f32, f32)>,
+    // Fractional position of the resampler in the pending samples.
+    position: f64,
+    // Cutoff frequency of the resampling filter relative to the Nyquist frequency of the engine.
+    cutoff: f64,
+    // Half-width of the resampling filter in the engine's samples.
+    half_width: usize,
+}
+
+impl OfflineRenderer {
+    /// Creates new offline renderer for the given engine, that produces samples at the given sample rate.
+    /// The sample rate must be greater than zero.
+    pub fn new(engine: SoundEngine, sample_rate: u32) -> Self {
+        assert_ne!(sample_rate, 0);
+
+        // Downsampling must remove everything above the Nyquist frequency of the output.
+        let cutoff = (sample_rate as f64 / SAMPLE_RATE as f64).min(1.0);
+        let half_width = (SINC_ZERO_CROSSINGS as f64 / cutoff).ceil() as usize;
+
+        Self {
+            engine,
+            sample_rate,
+            // The signal before the beginning is silent.
+            pending: vec![(0.0, 0.0); half_width],
+            position: half_width as f64,
+            cutoff,
+            half_width,
+        }
+    }
+
+    /// Returns sample rate of the output.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns amount of output samples (per channel) that corresponds to the given duration.
+    pub fn samples_count(&self, duration: Duration) -> usize {
+        (duration.as_secs_f64() * self.sample_rate as f64).round() as usize
+    }
+
+    /// Renders the given amount of time and returns interleaved stereo samples. Consecutive calls produce
+    /// continuous signal, so the rendering could be split in multiple parts.
+    ///
+    /// ## Deadlocks
+    ///
+    /// This method internally locks the engine and its contexts, so it must be called when all of them are
+    /// unlocked.
+    pub fn render(&mut self, duration: Duration) -> Vec<(f32, f32)> {
+        let count = self.samples_count(duration);
+        let mut output = Vec::with_capacity(count);
+        for _ in 0..count {
+            output.push(self.next_sample());
+        }
+        output
+    }
+
+    /// Renders the given amount of time and writes it as stereo WAV data to the given writer.
+    pub fn render_to_wav<W>(
+        &mut self,
+        writer: W,
+        duration: Duration,
+        format: WavSampleFormat,
+    ) -> Result<(), SoundError>
+    where
+        W: Write + Seek,
+    {
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: self.sample_rate,
+            bits_per_sample: match format {
+                WavSampleFormat::Float32 => 32,
+                WavSampleFormat::Int16 => 16,
+            },
+            sample_format: match format {
+                WavSampleFormat::Float32 => hound::SampleFormat::Float,
+                WavSampleFormat::Int16 => hound::SampleFormat::Int,
+            },
+        };
+
+        let mut wav_writer = hound::WavWriter::new(writer, spec)?;
+
+        for _ in 0..self.samples_count(duration) {
+            let (left, right) = self.next_sample();
+            match format {
+                WavSampleFormat::Float32 => {
+                    wav_writer.write_sample(left)?;
+                    wav_writer.write_sample(right)?;
+                }
+                WavSampleFormat::Int16 => {
+                    wav_writer.write_sample(to_i16(left))?;
+                    wav_writer.write_sample(to_i16(right))?;
+                }
+            }
+        }
+
+        wav_writer.finalize()?;
+
+        Ok(())
+    }
+
+    /// Renders the given amount of time and writes it to a WAV file at the given path. The file will be
+    /// overwritten if it exists.
+    pub fn render_to_wav_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        duration: Duration,
+        format: WavSampleFormat,
+    ) -> Result<(), SoundError> {
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.render_to_wav(file, duration, format)
+    }
+
+    fn next_sample(&mut self) -> (f32, f32) {
+        let index = self.position as usize;
+
+        while self.pending.len() < index + self.half_width + 1 {
+            let begin = self.pending.len();
+            self.pending
+                .resize(begin + State::render_buffer_len(), (0.0, 0.0));
+            self.engine.state().render(&mut self.pending[begin..]);
+        }
+
+        let sample = if self.position.fract() == 0.0 && self.cutoff == 1.0 {
+            // Exactly at an engine's sample, there's nothing to filter.
+            self.pending[index]
+        } else {
+            let (mut left, mut right) = (0.0f64, 0.0f64);
+            for k in (index + 1 - self.half_width)..=(index + self.half_width) {
+                let weight = self.kernel(self.position - k as f64);
+                let (sample_left, sample_right) = self.pending[k];
+                left += sample_left as f64 * weight;
+                right += sample_right as f64 * weight;
+            }
+            (left as f32, right as f32)
+        };
+
+        self.position += SAMPLE_RATE as f64 / self.sample_rate as f64;
+
+        // Drop consumed samples from time to time, not on every sample. The filter needs `half_width` samples
+        // before the current position, so they must be kept.
+        let consumed = (self.position as usize).saturating_sub(self.half_width);
+        if consumed >= State::render_buffer_len() {
+            self.pending.drain(..consumed);
+            self.position -= consumed as f64;
+        }
+
+        sample
+    }
+
+    /// Low-pass sinc filter with Blackman window, `distance` is in the engine's samples.
+    fn kernel(&self, distance: f64) -> f64 {
+        let x = distance / self.half_width as f64;
+        if x.abs() >= 1.0 {
+            return 0.0;
+        }
+
+        let window = 0.42
+            + 0.5 * (std::f64::consts::PI * x).cos()
+            + 0.08 * (2.0 * std::f64::consts::PI * x).cos();
+
+        let t = std::f64::consts::PI * self.cutoff * distance;
+        let sinc = if t == 0.0 { 1.0 } else { t.sin() / t };
+
+        self.cutoff * sinc * window
+    }
+}
+
+fn to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::{DataSource, SoundBufferResource, SoundBufferResourceExtension},
+        context::{SoundContext, SAMPLE_RATE},
+        engine::SoundEngine,
+        offline::{OfflineRenderer, WavSampleFormat},
+        source::{SoundSourceBuilder, Status},
+    };
+    use std::{io::Cursor, time::Duration};
+
+    fn make_engine() -> SoundEngine {
+        make_tone_engine(440.0)
+    }
+
+    fn make_tone_engine(frequency: f32) -> SoundEngine {
+        let engine = SoundEngine::without_device();
+        let context = SoundContext::new();
+        engine.state().add_context(context.clone());
+
+        let samples = (0..SAMPLE_RATE)
+            .map(|i| (2.0 * std::f32::consts::PI * frequency * i as f32 / SAMPLE_RATE as f32).sin())
+            .collect();
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: SAMPLE_RATE as usize,
+            channel_count: 1,
+            samples,
+        })
+        .unwrap();
+        let source = SoundSourceBuilder::new()
+            .with_buffer(buffer)
+            .with_status(Status::Playing)
+            .with_looping(true)
+            .build()
+            .unwrap();
+        context.state().add_source(source);
+
+        engine
+    }
+
+    #[test]
+    fn test_empty_engine_is_silent() {
+        let mut renderer = OfflineRenderer::new(SoundEngine::without_device(), SAMPLE_RATE);
+        let output = renderer.render(Duration::from_millis(100));
+        assert_eq!(output.len(), 4410);
+        assert!(output.iter().all(|sample| *sample == (0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_rendering_is_deterministic() {
+        let duration = Duration::from_millis(250);
+        let first = OfflineRenderer::new(make_engine(), SAMPLE_RATE).render(duration);
+        let second = OfflineRenderer::new(make_engine(), SAMPLE_RATE).render(duration);
+        assert_eq!(first, second);
+        assert!(first.iter().any(|(left, _)| left.abs() > 0.1));
+    }
+
+    #[test]
+    fn test_split_rendering_is_continuous() {
+        let whole = OfflineRenderer::new(make_engine(), 22050).render(Duration::from_millis(500));
+
+        let mut renderer = OfflineRenderer::new(make_engine(), 22050);
+        let mut parts = renderer.render(Duration::from_millis(100));
+        parts.extend(renderer.render(Duration::from_millis(400)));
+
+        assert_eq!(whole, parts);
+    }
+
+    #[test]
+    fn test_resampling() {
+        let native =
+            OfflineRenderer::new(make_engine(), SAMPLE_RATE).render(Duration::from_secs(1));
+        let resampled = OfflineRenderer::new(make_engine(), 22050).render(Duration::from_secs(1));
+        assert_eq!(resampled.len(), 22050);
+        // Half of the sample rate means every second sample of the native output, 440 Hz tone is far
+        // below the cutoff so it must pass through the filter almost unchanged. The first samples are
+        // skipped, because the filter considers the signal before the beginning as silent.
+        for (i, sample) in resampled.iter().enumerate().skip(64) {
+            assert!((sample.0 - native[i * 2].0).abs() < 1.0e-2);
+            assert!((sample.1 - native[i * 2].1).abs() < 1.0e-2);
+        }
+    }
+
+    #[test]
+    fn test_resampling_removes_aliasing() {
+        let peak = |samples: &[(f32, f32)]| {
+            samples
+                .iter()
+                .skip(64)
+                .fold(0.0f32, |peak, (left, _)| peak.max(left.abs()))
+        };
+
+        // 15 kHz can't be represented at 22050 Hz and must be filtered out instead of folding to 7050 Hz.
+        let native = OfflineRenderer::new(make_tone_engine(15000.0), SAMPLE_RATE)
+            .render(Duration::from_millis(100));
+        let resampled = OfflineRenderer::new(make_tone_engine(15000.0), 22050)
+            .render(Duration::from_millis(100));
+        assert!(peak(&native) > 0.1);
+        assert!(peak(&resampled) < 0.01 * peak(&native));
+    }
+
+    #[test]
+    fn test_wav_output() {
+        let mut data = Cursor::new(Vec::new());
+        OfflineRenderer::new(make_engine(), 48000)
+            .render_to_wav(
+                &mut data,
+                Duration::from_millis(100),
+                WavSampleFormat::Int16,
+            )
+            .unwrap();
+
+        data.set_position(0);
+        let reader = hound::WavReader::new(data).unwrap();
+        let spec = reader.spec();
+        assert_eq!(spec.channels, 2);
+        assert_eq!(spec.sample_rate, 48000);
+        assert_eq!(spec.bits_per_sample, 16);
+        assert_eq!(reader.duration(), 4800);
+    }
+}
@@ -1,8 +1,17 @@
 use crate::{
-    core::{algebra::Vector2, math::Rect, pool::Handle, scope_profile},
+    core::{
+        algebra::Vector2,
+        math::Rect,
+        pool::Handle,
+        scope_profile,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
     message::UiMessage,
+    style::{Style, Theme},
     widget::{Widget, WidgetBuilder},
     BuildContext, Control, MessageDirection, Thickness, UiNode, UserInterface, BRUSH_PRIMARY,
 };
@@ -11,21 +20,39 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct Border {
     pub widget: Widget,
     pub stroke_thickness: Thickness,
+    /// Radius of rounded corners. Zero means sharp corners. Rounded border uses left stroke
+    /// thickness for all sides.
+    #[visit(optional)] // Backward compatibility
+    pub corner_radius: f32,
 }
 
 crate::define_widget_deref!(Border);
 
+impl Default for Border {
+    fn default() -> Self {
+        Self::new(WidgetBuilder::new().build())
+    }
+}
+
+impl TypeUuidProvider for Border {
+    fn type_uuid() -> Uuid {
+        uuid!("b2a60bf9-356a-468e-926b-265d6cfa8bf7")
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum BorderMessage {
     StrokeThickness(Thickness),
+    CornerRadius(f32),
 }
 
 impl BorderMessage {
     define_constructor!(BorderMessage:StrokeThickness => fn stroke_thickness(Thickness), layout: false);
+    define_constructor!(BorderMessage:CornerRadius => fn corner_radius(f32), layout: false);
 }
 
 impl Control for Border {
@@ -83,7 +110,16 @@ impl Control for Border {
 
     fn draw(&self, drawing_context: &mut DrawingContext) {
         let bounds = self.widget.bounding_rect();
-        DrawingContext::push_rect_filled(drawing_context, &bounds, None);
+
+        if self.corner_radius > 0.0 {
+            drawing_context.push_rounded_rect_filled(
+                &bounds,
+                self.corner_radius,
+                Self::CORNER_SUBDIVISIONS,
+            );
+        } else {
+            DrawingContext::push_rect_filled(drawing_context, &bounds, None);
+        }
         drawing_context.commit(
             self.clip_bounds(),
             self.widget.background(),
@@ -91,7 +127,16 @@ impl Control for Border {
             None,
         );
 
-        drawing_context.push_rect_vary(&bounds, self.stroke_thickness);
+        if self.corner_radius > 0.0 {
+            drawing_context.push_rounded_rect(
+                &bounds,
+                self.stroke_thickness.left,
+                self.corner_radius,
+                Self::CORNER_SUBDIVISIONS,
+            );
+        } else {
+            drawing_context.push_rect_vary(&bounds, self.stroke_thickness);
+        }
         drawing_context.commit(
             self.clip_bounds(),
             self.widget.foreground(),
@@ -106,22 +151,46 @@ impl Control for Border {
         if message.destination() == self.handle()
             && message.direction() == MessageDirection::ToWidget
         {
-            if let Some(BorderMessage::StrokeThickness(thickness)) = message.data() {
-                if *thickness != self.stroke_thickness {
-                    self.stroke_thickness = *thickness;
-                    ui.send_message(message.reverse());
-                    self.invalidate_layout();
+            match message.data() {
+                Some(BorderMessage::StrokeThickness(thickness)) => {
+                    self.widget.override_style_property(Style::STROKE_THICKNESS);
+                    if *thickness != self.stroke_thickness {
+                        self.stroke_thickness = *thickness;
+                        ui.send_message(message.reverse());
+                        self.invalidate_layout();
+                    }
+                }
+                Some(BorderMessage::CornerRadius(corner_radius)) => {
+                    self.widget.override_style_property(Style::CORNER_RADIUS);
+                    if *corner_radius != self.corner_radius {
+                        self.corner_radius = *corner_radius;
+                        ui.send_message(message.reverse());
+                    }
                 }
+                None => (),
             }
         }
     }
+
+    fn apply_style(&mut self, style: &Style) {
+        self.widget.apply_style(style);
+        if let Some(stroke_thickness) = style.thickness(Style::STROKE_THICKNESS) {
+            self.stroke_thickness = stroke_thickness;
+        }
+        if let Some(corner_radius) = style.float(Style::CORNER_RADIUS) {
+            self.corner_radius = corner_radius;
+        }
+    }
 }
 
 impl Border {
+    const CORNER_SUBDIVISIONS: usize = 8;
+
     pub fn new(widget: Widget) -> Self {
         Self {
             widget,
             stroke_thickness: Thickness::uniform(1.0),
+            corner_radius: 0.0,
         }
     }
 }
@@ -129,6 +198,7 @@ impl Border {
 pub struct BorderBuilder {
     pub widget_builder: WidgetBuilder,
     pub stroke_thickness: Option<Thickness>,
+    pub corner_radius: Option<f32>,
 }
 
 impl BorderBuilder {
@@ -136,6 +206,7 @@ impl BorderBuilder {
         Self {
             widget_builder,
             stroke_thickness: None,
+            corner_radius: None,
         }
     }
 
@@ -144,15 +215,32 @@ impl BorderBuilder {
         self
     }
 
+    pub fn with_corner_radius(mut self, corner_radius: f32) -> Self {
+        self.corner_radius = Some(corner_radius);
+        self
+    }
+
     pub fn build_border(mut self) -> Border {
         if self.widget_builder.foreground.is_none() {
             self.widget_builder.foreground = Some(BRUSH_PRIMARY);
         }
+        if self.widget_builder.style.is_none() {
+            self.widget_builder.style = Some(Theme::BORDER.to_owned());
+        }
+        if self.stroke_thickness.is_some() {
+            self.widget_builder
+                .override_style_property(Style::STROKE_THICKNESS);
+        }
+        if self.corner_radius.is_some() {
+            self.widget_builder
+                .override_style_property(Style::CORNER_RADIUS);
+        }
         Border {
             widget: self.widget_builder.build(),
             stroke_thickness: self
                 .stroke_thickness
                 .unwrap_or_else(|| Thickness::uniform(1.0)),
+            corner_radius: self.corner_radius.unwrap_or_default(),
         }
     }
 
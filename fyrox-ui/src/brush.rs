@@ -1,13 +1,14 @@
 use crate::core::algebra::Vector2;
 use crate::core::color::Color;
+use crate::core::visitor::prelude::*;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Default, Visit)]
 pub struct GradientPoint {
     pub stop: f32,
     pub color: Color,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Visit)]
 pub enum Brush {
     Solid(Color),
     LinearGradient {
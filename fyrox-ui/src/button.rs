@@ -1,14 +1,20 @@
 use crate::{
     border::BorderBuilder,
-    core::pool::Handle,
+    core::{
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     decorator::DecoratorBuilder,
     define_constructor,
     message::{MessageDirection, UiMessage},
+    style::Theme,
     text::TextBuilder,
     ttf::SharedFont,
     widget::{Widget, WidgetBuilder, WidgetMessage},
-    BuildContext, Control, HorizontalAlignment, NodeHandleMapping, Thickness, UiNode,
-    UserInterface, VerticalAlignment, BRUSH_DARKER, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST,
+    BuildContext, Control, HorizontalAlignment, NodeHandleMapping, UiNode, UserInterface,
+    VerticalAlignment,
 };
 use std::{
     any::{Any, TypeId},
@@ -26,7 +32,7 @@ impl ButtonMessage {
     define_constructor!(ButtonMessage:Content => fn content(ButtonContent), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct Button {
     pub widget: Widget,
     pub decorator: Handle<UiNode>,
@@ -35,6 +41,22 @@ pub struct Button {
 
 crate::define_widget_deref!(Button);
 
+impl Default for Button {
+    fn default() -> Self {
+        Self {
+            widget: WidgetBuilder::new().build(),
+            decorator: Default::default(),
+            content: Default::default(),
+        }
+    }
+}
+
+impl TypeUuidProvider for Button {
+    fn type_uuid() -> Uuid {
+        uuid!("1d9cb666-dd54-4b99-b173-dea7b6c51f24")
+    }
+}
+
 impl Control for Button {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -69,6 +91,13 @@ impl Control for Button {
                         ui.capture_mouse(message.destination());
                         message.set_handled(true);
                     }
+                    WidgetMessage::Activate if !message.handled() => {
+                        ui.send_message(ButtonMessage::click(
+                            self.handle(),
+                            MessageDirection::FromWidget,
+                        ));
+                        message.set_handled(true);
+                    }
                     _ => (),
                 }
             }
@@ -130,7 +159,7 @@ impl ButtonContent {
                 .with_text(text)
                 .with_horizontal_text_alignment(HorizontalAlignment::Center)
                 .with_vertical_text_alignment(VerticalAlignment::Center)
-                .with_font(font.clone().unwrap_or_else(|| ctx.ui.default_font.clone()))
+                .with_opt_font(font.clone())
                 .build(ctx),
             Self::Node(node) => *node,
         }
@@ -172,22 +201,24 @@ impl ButtonBuilder {
         self
     }
 
-    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+    pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        self.widget_builder.tab_stop.get_or_insert(true);
+
         let content = self.content.map(|c| c.build(ctx)).unwrap_or_default();
 
+        // Visual look of a button is defined by its decorator, so the style of the button is
+        // moved to the default decorator.
+        let style = if self.back.is_none() {
+            self.widget_builder.style.take()
+        } else {
+            None
+        };
+
         let back = self.back.unwrap_or_else(|| {
-            DecoratorBuilder::new(
-                BorderBuilder::new(
-                    WidgetBuilder::new()
-                        .with_foreground(BRUSH_DARKER)
-                        .with_child(content),
-                )
-                .with_stroke_thickness(Thickness::uniform(1.0)),
-            )
-            .with_normal_brush(BRUSH_LIGHT)
-            .with_hover_brush(BRUSH_LIGHTER)
-            .with_pressed_brush(BRUSH_LIGHTEST)
-            .build(ctx)
+            let mut widget_builder = WidgetBuilder::new().with_child(content);
+            widget_builder.style = Some(style.unwrap_or_else(|| Theme::BUTTON.to_owned()));
+
+            DecoratorBuilder::new(BorderBuilder::new(widget_builder)).build(ctx)
         });
 
         if content.is_some() {
@@ -1,5 +1,13 @@
 use crate::{
-    core::{algebra::Vector2, math::Rect, pool::Handle, scope_profile},
+    core::{
+        algebra::Vector2,
+        math::Rect,
+        pool::Handle,
+        scope_profile,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     message::UiMessage,
     widget::{Widget, WidgetBuilder},
     BuildContext, Control, UiNode, UserInterface,
@@ -10,13 +18,25 @@ use std::{
 };
 
 /// Allows user to directly set position and size of a node
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct Canvas {
     pub widget: Widget,
 }
 
 crate::define_widget_deref!(Canvas);
 
+impl Default for Canvas {
+    fn default() -> Self {
+        Self::new(WidgetBuilder::new().build())
+    }
+}
+
+impl TypeUuidProvider for Canvas {
+    fn type_uuid() -> Uuid {
+        uuid!("e413e9eb-c1a2-488c-ad52-98886c4ec4f5")
+    }
+}
+
 impl Control for Canvas {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -1,15 +1,21 @@
 use crate::{
     border::BorderBuilder,
     brush::Brush,
-    core::{color::Color, pool::Handle},
+    core::{
+        color::Color,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, UiMessage},
+    style::Theme,
     vector_image::{Primitive, VectorImageBuilder},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, MouseButton, NodeHandleMapping, Thickness, UiNode,
-    UserInterface, VerticalAlignment, BRUSH_BRIGHT, BRUSH_BRIGHT_BLUE, BRUSH_DARKEST, BRUSH_LIGHT,
-    BRUSH_TEXT,
+    UserInterface, VerticalAlignment, BRUSH_TEXT,
 };
 use fyrox_core::algebra::Vector2;
 use std::{
@@ -26,7 +32,7 @@ impl CheckBoxMessage {
     define_constructor!(CheckBoxMessage:Check => fn checked(Option<bool>), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct CheckBox {
     pub widget: Widget,
     pub checked: Option<bool>,
@@ -37,6 +43,44 @@ pub struct CheckBox {
 
 crate::define_widget_deref!(CheckBox);
 
+impl Default for CheckBox {
+    fn default() -> Self {
+        Self {
+            widget: WidgetBuilder::new().build(),
+            checked: Some(false),
+            check_mark: Default::default(),
+            uncheck_mark: Default::default(),
+            undefined_mark: Default::default(),
+        }
+    }
+}
+
+impl TypeUuidProvider for CheckBox {
+    fn type_uuid() -> Uuid {
+        uuid!("bf1ce0e3-099f-4eae-91ef-ddbaaedea5f6")
+    }
+}
+
+impl CheckBox {
+    fn toggle(&self, ui: &UserInterface) {
+        if let Some(value) = self.checked {
+            // Invert state if it is defined.
+            ui.send_message(CheckBoxMessage::checked(
+                self.handle(),
+                MessageDirection::ToWidget,
+                Some(!value),
+            ));
+        } else {
+            // Switch from undefined state to checked.
+            ui.send_message(CheckBoxMessage::checked(
+                self.handle(),
+                MessageDirection::ToWidget,
+                Some(true),
+            ));
+        }
+    }
+}
+
 impl Control for CheckBox {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -72,23 +116,17 @@ impl Control for CheckBox {
                     {
                         ui.release_mouse_capture();
 
-                        if let Some(value) = self.checked {
-                            // Invert state if it is defined.
-                            ui.send_message(CheckBoxMessage::checked(
-                                self.handle(),
-                                MessageDirection::ToWidget,
-                                Some(!value),
-                            ));
-                        } else {
-                            // Switch from undefined state to checked.
-                            ui.send_message(CheckBoxMessage::checked(
-                                self.handle(),
-                                MessageDirection::ToWidget,
-                                Some(true),
-                            ));
-                        }
+                        self.toggle(ui);
                     }
                 }
+                WidgetMessage::Activate
+                    if !message.handled()
+                        && (message.destination() == self.handle()
+                            || self.widget.has_descendant(message.destination(), ui)) =>
+                {
+                    self.toggle(ui);
+                    message.set_handled(true);
+                }
                 _ => (),
             }
         } else if let Some(&CheckBoxMessage::Check(value)) = message.data::<CheckBoxMessage>() {
@@ -196,11 +234,13 @@ impl CheckBoxBuilder {
         self
     }
 
-    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+    pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        self.widget_builder.tab_stop.get_or_insert(true);
+
         let check_mark = self.check_mark.unwrap_or_else(|| {
             BorderBuilder::new(
                 WidgetBuilder::new()
-                    .with_background(BRUSH_BRIGHT_BLUE)
+                    .with_style(Theme::CHECK_BOX_MARK)
                     .with_child(
                         VectorImageBuilder::new(
                             WidgetBuilder::new()
@@ -228,7 +268,6 @@ impl CheckBoxBuilder {
                         .build(ctx),
                     ),
             )
-            .with_stroke_thickness(Thickness::uniform(0.0))
             .build(ctx)
         });
         ctx[check_mark].set_visibility(self.checked.unwrap_or(false));
@@ -251,8 +290,7 @@ impl CheckBoxBuilder {
             BorderBuilder::new(
                 WidgetBuilder::new()
                     .with_margin(Thickness::uniform(4.0))
-                    .with_background(BRUSH_BRIGHT)
-                    .with_foreground(Brush::Solid(Color::TRANSPARENT)),
+                    .with_style(Theme::CHECK_BOX_UNDEFINED_MARK),
             )
             .build(ctx)
         });
@@ -266,10 +304,8 @@ impl CheckBoxBuilder {
             BorderBuilder::new(
                 WidgetBuilder::new()
                     .with_vertical_alignment(VerticalAlignment::Center)
-                    .with_background(BRUSH_DARKEST)
-                    .with_foreground(BRUSH_LIGHT),
+                    .with_style(Theme::CHECK_BOX),
             )
-            .with_stroke_thickness(Thickness::uniform(1.0))
             .build(ctx)
         });
 
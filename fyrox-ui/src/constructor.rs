@@ -0,0 +1,154 @@
+//! A special container that is able to create widgets by their type UUID and (de)serialize them.
+//!
+//! Widgets are stored as trait objects, so there is no way to find out which concrete type should be
+//! created when a widget is loaded. The container maps type UUIDs of widgets to their constructors,
+//! and it must be registered in the blackboard of a [`Visitor`] that is used to (de)serialize UI
+//! nodes. Every widget that should be serializable must be registered in the container, all
+//! built-in widgets that support serialization are registered by [`WidgetConstructorContainer::new`].
+
+use crate::{
+    border::Border,
+    button::Button,
+    canvas::Canvas,
+    check_box::CheckBox,
+    core::{
+        parking_lot::Mutex,
+        uuid::Uuid,
+        visitor::{Visit, VisitResult, Visitor},
+        TypeUuidProvider,
+    },
+    decorator::Decorator,
+    grid::Grid,
+    image::Image,
+    progress_bar::ProgressBar,
+    stack_panel::StackPanel,
+    text::Text,
+    vector_image::VectorImage,
+    wrap_panel::WrapPanel,
+    Control, UiNode,
+};
+use fxhash::FxHashMap;
+use std::{
+    any::TypeId,
+    fmt::{Debug, Formatter},
+};
+
+/// A simple type alias for boxed widget constructor.
+pub type WidgetConstructor = Box<dyn FnMut() -> UiNode + Send>;
+
+type WidgetVisitor = fn(&mut UiNode, &str, &mut Visitor) -> VisitResult;
+
+struct WidgetConstructorEntry {
+    constructor: WidgetConstructor,
+    visitor: WidgetVisitor,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: FxHashMap<Uuid, WidgetConstructorEntry>,
+    type_uuids: FxHashMap<TypeId, Uuid>,
+}
+
+/// A special container that is able to create widgets by their type UUID. See module docs for
+/// more info.
+#[derive(Default)]
+pub struct WidgetConstructorContainer {
+    inner: Mutex<Inner>,
+}
+
+impl Debug for WidgetConstructorContainer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WidgetConstructorContainer ({} entries)", self.len())
+    }
+}
+
+fn visit_widget<T>(node: &mut UiNode, name: &str, visitor: &mut Visitor) -> VisitResult
+where
+    T: Control + Visit,
+{
+    node.cast_mut::<T>()
+        .expect("Widget type must match its type uuid!")
+        .visit(name, visitor)
+}
+
+impl WidgetConstructorContainer {
+    /// Creates default widget constructor container with constructors for built-in widgets.
+    pub fn new() -> Self {
+        let container = WidgetConstructorContainer::default();
+
+        container.add::<Border>();
+        container.add::<Button>();
+        container.add::<Canvas>();
+        container.add::<CheckBox>();
+        container.add::<Decorator>();
+        container.add::<Grid>();
+        container.add::<Image>();
+        container.add::<ProgressBar>();
+        container.add::<StackPanel>();
+        container.add::<Text>();
+        container.add::<VectorImage>();
+        container.add::<WrapPanel>();
+
+        container
+    }
+
+    /// Adds new type constructor for a given type. Panics if there is a constructor for the type
+    /// already.
+    pub fn add<T>(&self)
+    where
+        T: TypeUuidProvider + Control + Visit + Default,
+    {
+        let mut inner = self.inner.lock();
+
+        let previous = inner.entries.insert(
+            T::type_uuid(),
+            WidgetConstructorEntry {
+                constructor: Box::new(|| UiNode::new(T::default())),
+                visitor: visit_widget::<T>,
+            },
+        );
+        assert!(previous.is_none());
+
+        inner.type_uuids.insert(TypeId::of::<T>(), T::type_uuid());
+    }
+
+    /// Unregisters type constructor.
+    pub fn remove(&self, type_uuid: Uuid) {
+        let mut inner = self.inner.lock();
+        inner.entries.remove(&type_uuid);
+        inner.type_uuids.retain(|_, uuid| *uuid != type_uuid);
+    }
+
+    /// Makes an attempt to create a widget using provided type UUID. It may fail if there is no
+    /// widget constructor for specified type UUID.
+    pub fn try_create(&self, type_uuid: &Uuid) -> Option<UiNode> {
+        self.inner
+            .lock()
+            .entries
+            .get_mut(type_uuid)
+            .map(|e| (e.constructor)())
+    }
+
+    /// Returns type UUID of the given widget, or `None` if the type of the widget is not registered.
+    pub fn type_uuid_of(&self, node: &UiNode) -> Option<Uuid> {
+        self.inner
+            .lock()
+            .type_uuids
+            .get(&node.as_any().type_id())
+            .cloned()
+    }
+
+    pub(crate) fn visitor_of(&self, type_uuid: &Uuid) -> Option<WidgetVisitor> {
+        self.inner.lock().entries.get(type_uuid).map(|e| e.visitor)
+    }
+
+    /// Returns total amount of constructors.
+    pub fn len(&self) -> usize {
+        self.inner.lock().entries.len()
+    }
+
+    /// Returns true if the container is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
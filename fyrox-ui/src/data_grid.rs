@@ -0,0 +1,875 @@
+//! Data grid is a multi-column table widget with resizable and sortable column headers, row selection
+//! and cell templates. It is built on top of virtualized [`ListView`], so it creates widgets only for
+//! visible rows and is able to show hundreds of thousands of rows.
+//!
+//! The data grid does not own the data - it knows only the amount of rows, every column has a
+//! [`CellTemplate`] that creates cell widgets and fills them with the data of a particular row. Row
+//! indices that are passed to templates and that are used in [`DataGridMessage`] are always the
+//! indices of rows in your data source, the data grid maps them to their visual positions when the
+//! rows are sorted.
+//!
+//! ```rust,no_run
+//! # use fyrox_ui::{
+//! #     core::pool::Handle,
+//! #     data_grid::{DataGridBuilder, DataGridColumn, TextCellTemplate},
+//! #     widget::WidgetBuilder,
+//! #     BuildContext, UiNode,
+//! # };
+//! # use std::rc::Rc;
+//! fn make_inventory_view(ctx: &mut BuildContext, items: Rc<Vec<(String, u32)>>) -> Handle<UiNode> {
+//!     let names = items.clone();
+//!     let counts = items.clone();
+//!     DataGridBuilder::new(WidgetBuilder::new())
+//!         .with_column(
+//!             DataGridColumn::new(
+//!                 "Name",
+//!                 200.0,
+//!                 Rc::new(TextCellTemplate::new(move |row| names[row].0.clone())),
+//!             )
+//!             .with_sortable(true),
+//!         )
+//!         .with_column(DataGridColumn::new(
+//!             "Count",
+//!             80.0,
+//!             Rc::new(TextCellTemplate::new(move |row| counts[row].1.to_string())),
+//!         ))
+//!         .with_row_count(items.len())
+//!         .build(ctx)
+//! }
+//! ```
+
+use crate::{
+    border::BorderBuilder,
+    core::pool::Handle,
+    decorator::DecoratorBuilder,
+    define_constructor,
+    grid::{Column, GridBuilder, Row},
+    list_view::{ItemProvider, ListViewBuilder, ListViewMessage},
+    message::{CursorIcon, MessageDirection, UiMessage},
+    scroll_panel::{ScrollPanelBuilder, ScrollPanelMessage},
+    scroll_viewer::{ScrollViewer, ScrollViewerBuilder},
+    stack_panel::StackPanelBuilder,
+    text::{TextBuilder, TextMessage},
+    utils::{make_arrow, ArrowDirection},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, HorizontalAlignment, NodeHandleMapping, Orientation, Thickness, UiNode,
+    UserInterface, VerticalAlignment, BRUSH_DARKER, BRUSH_LIGHT,
+};
+use fxhash::FxHashMap;
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    cmp::Ordering,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+};
+
+/// Width of the splitter between column headers, it is included in the column width.
+const SPLITTER_WIDTH: f32 = 4.0;
+
+/// Creates and fills widgets of the cells of a data grid column.
+pub trait CellTemplate {
+    /// Creates a new cell widget. The widget will be reused for different rows.
+    fn create_cell(&self, ctx: &mut BuildContext) -> Handle<UiNode>;
+
+    /// Fills the given cell widget (created by [`CellTemplate::create_cell`]) with the data of the
+    /// given row. Use messages to modify the widget.
+    fn bind_cell(&self, ui: &UserInterface, cell: Handle<UiNode>, row: usize);
+
+    /// Compares values of the column in two rows. It is used only for sortable columns.
+    fn compare(
+        &self,
+        #[allow(unused_variables)] a: usize,
+        #[allow(unused_variables)] b: usize,
+    ) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+/// A cell template that shows a text provided by a closure. Sorting compares the texts.
+pub struct TextCellTemplate<F> {
+    text: F,
+}
+
+impl<F> TextCellTemplate<F>
+where
+    F: Fn(usize) -> String,
+{
+    pub fn new(text: F) -> Self {
+        Self { text }
+    }
+}
+
+impl<F> CellTemplate for TextCellTemplate<F>
+where
+    F: Fn(usize) -> String,
+{
+    fn create_cell(&self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left(2.0)))
+            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .build(ctx)
+    }
+
+    fn bind_cell(&self, ui: &UserInterface, cell: Handle<UiNode>, row: usize) {
+        ui.send_message(TextMessage::text(
+            cell,
+            MessageDirection::ToWidget,
+            (self.text)(row),
+        ));
+    }
+
+    fn compare(&self, a: usize, b: usize) -> Ordering {
+        (self.text)(a).cmp(&(self.text)(b))
+    }
+}
+
+/// Description of a data grid column.
+#[derive(Clone)]
+pub struct DataGridColumn {
+    pub header: String,
+    pub width: f32,
+    pub min_width: f32,
+    pub sortable: bool,
+    pub template: Rc<dyn CellTemplate>,
+}
+
+impl DataGridColumn {
+    pub fn new<S: AsRef<str>>(header: S, width: f32, template: Rc<dyn CellTemplate>) -> Self {
+        Self {
+            header: header.as_ref().to_owned(),
+            width,
+            min_width: 20.0,
+            sortable: false,
+            template,
+        }
+    }
+
+    pub fn with_min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    /// Allows to sort rows by clicking on the header of the column.
+    pub fn with_sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DataGridSort {
+    pub column: usize,
+    pub direction: SortDirection,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataGridMessage {
+    /// Sets the amount of rows in the data source.
+    RowCount(usize),
+    /// Re-binds visible rows and re-applies sorting, should be used when the data has changed.
+    Refresh,
+    /// Selected row (index in the data source).
+    SelectionChanged(Option<usize>),
+    ColumnWidth {
+        column: usize,
+        width: f32,
+    },
+    /// Sorts the rows by a column, `None` restores the order of the data source.
+    Sort(Option<DataGridSort>),
+    /// Scrolls the grid so the row (index in the data source) will be visible.
+    BringRowIntoView(usize),
+}
+
+impl DataGridMessage {
+    define_constructor!(DataGridMessage:RowCount => fn row_count(usize), layout: false);
+    define_constructor!(DataGridMessage:Refresh => fn refresh(), layout: false);
+    define_constructor!(DataGridMessage:SelectionChanged => fn selection(Option<usize>), layout: false);
+    define_constructor!(DataGridMessage:ColumnWidth => fn column_width(column: usize, width: f32), layout: false);
+    define_constructor!(DataGridMessage:Sort => fn sort(Option<DataGridSort>), layout: false);
+    define_constructor!(DataGridMessage:BringRowIntoView => fn bring_row_into_view(usize), layout: false);
+}
+
+/// Widgets of a column header.
+#[derive(Clone, Debug)]
+pub struct ColumnHeader {
+    pub cell: Handle<UiNode>,
+    pub splitter: Handle<UiNode>,
+    pub ascending_arrow: Handle<UiNode>,
+    pub descending_arrow: Handle<UiNode>,
+}
+
+#[derive(Clone, Debug)]
+struct ColumnDrag {
+    column: usize,
+    start_x: f32,
+    start_width: f32,
+}
+
+/// State that is shared between a data grid and the provider of its rows.
+struct DataGridState {
+    columns: Vec<DataGridColumn>,
+    /// Maps visual row positions to row indices in the data source.
+    order: Vec<usize>,
+    /// Cells of every realized row.
+    rows: FxHashMap<Handle<UiNode>, Vec<Handle<UiNode>>>,
+}
+
+struct RowProvider {
+    state: Rc<RefCell<DataGridState>>,
+}
+
+impl ItemProvider for RowProvider {
+    fn create_item(&self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let mut state = self.state.borrow_mut();
+
+        let cells = state
+            .columns
+            .iter()
+            .map(|column| {
+                let cell = column.template.create_cell(ctx);
+                ctx[cell].set_width(column.width);
+                cell
+            })
+            .collect::<Vec<_>>();
+
+        let row = DecoratorBuilder::new(BorderBuilder::new(
+            WidgetBuilder::new().with_child(
+                StackPanelBuilder::new(WidgetBuilder::new().with_children(cells.iter().cloned()))
+                    .with_orientation(Orientation::Horizontal)
+                    .build(ctx),
+            ),
+        ))
+        .build(ctx);
+
+        state.rows.insert(row, cells);
+
+        row
+    }
+
+    fn bind_item(&self, ui: &UserInterface, item: Handle<UiNode>, index: usize) {
+        let state = self.state.borrow();
+
+        if let (Some(cells), Some(&row)) = (state.rows.get(&item), state.order.get(index)) {
+            for (column, &cell) in state.columns.iter().zip(cells.iter()) {
+                column.template.bind_cell(ui, cell, row);
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DataGrid {
+    pub widget: Widget,
+    pub list: Handle<UiNode>,
+    pub header_panel: Handle<UiNode>,
+    pub body_panel: Handle<UiNode>,
+    pub headers: Vec<ColumnHeader>,
+    pub sort: Option<DataGridSort>,
+    pub selected: Option<usize>,
+    state: Rc<RefCell<DataGridState>>,
+    drag: Option<ColumnDrag>,
+}
+
+crate::define_widget_deref!(DataGrid);
+
+impl DataGrid {
+    /// Returns a handle of the inner virtualized list view that shows the rows.
+    pub fn list_view(&self) -> Handle<UiNode> {
+        self.list
+    }
+
+    /// Returns selected row (index in the data source).
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn sort(&self) -> Option<DataGridSort> {
+        self.sort
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.state.borrow().order.len()
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.state.borrow().columns.len()
+    }
+
+    pub fn column_width(&self, column: usize) -> Option<f32> {
+        self.state.borrow().columns.get(column).map(|c| c.width)
+    }
+
+    /// Returns an index of a row in the data source that is shown at the given visual position.
+    pub fn row_at(&self, position: usize) -> Option<usize> {
+        self.state.borrow().order.get(position).cloned()
+    }
+
+    /// Returns a visual position of a row (index in the data source).
+    pub fn position_of(&self, row: usize) -> Option<usize> {
+        self.state.borrow().order.iter().position(|r| *r == row)
+    }
+
+    /// Returns an index of a column whose header cell contains the given node.
+    fn header_column(&self, ui: &UserInterface, mut node: Handle<UiNode>) -> Option<usize> {
+        while node.is_some() && node != self.handle {
+            if let Some(column) = self.headers.iter().position(|h| h.cell == node) {
+                return Some(column);
+            }
+            node = ui.node(node).parent();
+        }
+        None
+    }
+
+    fn apply_sort(&self) {
+        let mut state = self.state.borrow_mut();
+        let DataGridState {
+            ref columns,
+            ref mut order,
+            ..
+        } = *state;
+
+        match self
+            .sort
+            .and_then(|sort| columns.get(sort.column).map(|c| (c, sort)))
+        {
+            Some((column, sort)) => {
+                let template = &column.template;
+                order.sort_by(|&a, &b| match sort.direction {
+                    SortDirection::Ascending => template.compare(a, b),
+                    SortDirection::Descending => template.compare(b, a),
+                });
+            }
+            None => order.sort_unstable(),
+        }
+    }
+
+    fn sync_sort_arrows(&self, ui: &UserInterface) {
+        for (i, header) in self.headers.iter().enumerate() {
+            let direction = self.sort.and_then(|sort| {
+                if sort.column == i {
+                    Some(sort.direction)
+                } else {
+                    None
+                }
+            });
+
+            ui.send_message(WidgetMessage::visibility(
+                header.ascending_arrow,
+                MessageDirection::ToWidget,
+                direction == Some(SortDirection::Ascending),
+            ));
+            ui.send_message(WidgetMessage::visibility(
+                header.descending_arrow,
+                MessageDirection::ToWidget,
+                direction == Some(SortDirection::Descending),
+            ));
+        }
+    }
+
+    /// Selects a visual position of the selected row in the list view, it should be done every time
+    /// when the order of the rows changes.
+    fn sync_list_selection(&self, ui: &UserInterface) {
+        ui.send_message(ListViewMessage::selection(
+            self.list,
+            MessageDirection::ToWidget,
+            self.selected.and_then(|row| self.position_of(row)),
+        ));
+    }
+}
+
+impl Control for DataGrid {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn resolve(&mut self, node_map: &NodeHandleMapping) {
+        node_map.resolve(&mut self.list);
+        node_map.resolve(&mut self.header_panel);
+        node_map.resolve(&mut self.body_panel);
+        for header in self.headers.iter_mut() {
+            node_map.resolve(&mut header.cell);
+            node_map.resolve(&mut header.splitter);
+            node_map.resolve(&mut header.ascending_arrow);
+            node_map.resolve(&mut header.descending_arrow);
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<DataGridMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match *msg {
+                    DataGridMessage::RowCount(count) => {
+                        self.state.borrow_mut().order = (0..count).collect();
+                        self.apply_sort();
+
+                        if self.selected.map_or(false, |row| row >= count) {
+                            self.selected = None;
+                            ui.send_message(DataGridMessage::selection(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                None,
+                            ));
+                        }
+
+                        ui.send_message(ListViewMessage::item_count(
+                            self.list,
+                            MessageDirection::ToWidget,
+                            count,
+                        ));
+                        self.sync_list_selection(ui);
+                    }
+                    DataGridMessage::Refresh => {
+                        self.apply_sort();
+                        ui.send_message(ListViewMessage::refresh(
+                            self.list,
+                            MessageDirection::ToWidget,
+                        ));
+                        self.sync_list_selection(ui);
+                    }
+                    DataGridMessage::SelectionChanged(selection) => {
+                        if self.selected != selection {
+                            self.selected = selection;
+                            self.sync_list_selection(ui);
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    DataGridMessage::ColumnWidth { column, width } => {
+                        let mut state = self.state.borrow_mut();
+                        if let Some(column_ref) = state.columns.get_mut(column) {
+                            let width = width.max(column_ref.min_width);
+                            if column_ref.width != width {
+                                column_ref.width = width;
+
+                                ui.send_message(WidgetMessage::width(
+                                    self.headers[column].cell,
+                                    MessageDirection::ToWidget,
+                                    width - SPLITTER_WIDTH,
+                                ));
+                                for cells in state.rows.values() {
+                                    ui.send_message(WidgetMessage::width(
+                                        cells[column],
+                                        MessageDirection::ToWidget,
+                                        width,
+                                    ));
+                                }
+
+                                ui.send_message(DataGridMessage::column_width(
+                                    self.handle,
+                                    MessageDirection::FromWidget,
+                                    column,
+                                    width,
+                                ));
+                            }
+                        }
+                    }
+                    DataGridMessage::Sort(sort) => {
+                        if self.sort != sort {
+                            self.sort = sort;
+                            self.apply_sort();
+                            self.sync_sort_arrows(ui);
+                            ui.send_message(ListViewMessage::refresh(
+                                self.list,
+                                MessageDirection::ToWidget,
+                            ));
+                            self.sync_list_selection(ui);
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    DataGridMessage::BringRowIntoView(row) => {
+                        if let Some(position) = self.position_of(row) {
+                            ui.send_message(ListViewMessage::bring_index_into_view(
+                                self.list,
+                                MessageDirection::ToWidget,
+                                position,
+                            ));
+                        }
+                    }
+                }
+            }
+        } else if let Some(ListViewMessage::SelectionChanged(selection)) = message.data() {
+            if message.destination() == self.list
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let selected = selection.and_then(|position| self.row_at(position));
+                if self.selected != selected {
+                    self.selected = selected;
+                    ui.send_message(DataGridMessage::selection(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        selected,
+                    ));
+                }
+            }
+        } else if let Some(&ScrollPanelMessage::HorizontalScroll(scroll)) = message.data() {
+            // Keep the header in sync with the horizontal scroll of the rows.
+            if message.destination() == self.body_panel {
+                ui.send_message(ScrollPanelMessage::horizontal_scroll(
+                    self.header_panel,
+                    MessageDirection::ToWidget,
+                    scroll,
+                ));
+            }
+        } else if let Some(msg) = message.data::<WidgetMessage>() {
+            match *msg {
+                WidgetMessage::MouseDown { pos, .. } if !message.handled() => {
+                    if let Some(column) = self
+                        .headers
+                        .iter()
+                        .position(|h| h.splitter == message.destination())
+                    {
+                        self.drag = Some(ColumnDrag {
+                            column,
+                            start_x: pos.x,
+                            start_width: self.column_width(column).unwrap_or_default(),
+                        });
+                        ui.capture_mouse(message.destination());
+                        message.set_handled(true);
+                    }
+                }
+                WidgetMessage::MouseMove { pos, .. } => {
+                    if let Some(drag) = self.drag.as_ref() {
+                        ui.send_message(DataGridMessage::column_width(
+                            self.handle,
+                            MessageDirection::ToWidget,
+                            drag.column,
+                            drag.start_width + pos.x - drag.start_x,
+                        ));
+                    }
+                }
+                WidgetMessage::MouseUp { .. } => {
+                    if self.drag.take().is_some() {
+                        ui.release_mouse_capture();
+                        message.set_handled(true);
+                    } else if !message.handled() {
+                        if let Some(column) = self.header_column(ui, message.destination()) {
+                            let sortable = self.state.borrow().columns[column].sortable;
+                            if sortable {
+                                let direction = match self.sort {
+                                    Some(DataGridSort {
+                                        column: sort_column,
+                                        direction: SortDirection::Ascending,
+                                    }) if sort_column == column => SortDirection::Descending,
+                                    _ => SortDirection::Ascending,
+                                };
+                                ui.send_message(DataGridMessage::sort(
+                                    self.handle,
+                                    MessageDirection::ToWidget,
+                                    Some(DataGridSort { column, direction }),
+                                ));
+                            }
+                            message.set_handled(true);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+pub struct DataGridBuilder {
+    widget_builder: WidgetBuilder,
+    columns: Vec<DataGridColumn>,
+    row_count: usize,
+    row_height: f32,
+    header_height: f32,
+}
+
+impl DataGridBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            columns: Default::default(),
+            row_count: 0,
+            row_height: 22.0,
+            header_height: 24.0,
+        }
+    }
+
+    pub fn with_column(mut self, column: DataGridColumn) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    pub fn with_columns(mut self, columns: Vec<DataGridColumn>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    pub fn with_row_count(mut self, row_count: usize) -> Self {
+        self.row_count = row_count;
+        self
+    }
+
+    pub fn with_row_height(mut self, row_height: f32) -> Self {
+        self.row_height = row_height;
+        self
+    }
+
+    pub fn with_header_height(mut self, header_height: f32) -> Self {
+        self.header_height = header_height;
+        self
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let mut headers = Vec::new();
+        let mut header_widgets = Vec::new();
+        for column in self.columns.iter() {
+            let ascending_arrow;
+            let descending_arrow;
+            let cell = BorderBuilder::new(
+                WidgetBuilder::new()
+                    .with_width(column.width - SPLITTER_WIDTH)
+                    .with_background(BRUSH_DARKER)
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .with_child(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_margin(Thickness::left(2.0))
+                                            .on_column(0),
+                                    )
+                                    .with_text(&column.header)
+                                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                                    .build(ctx),
+                                )
+                                .with_child({
+                                    ascending_arrow = make_arrow(ctx, ArrowDirection::Top, 8.0);
+                                    ascending_arrow
+                                })
+                                .with_child({
+                                    descending_arrow = make_arrow(ctx, ArrowDirection::Bottom, 8.0);
+                                    descending_arrow
+                                }),
+                        )
+                        .add_row(Row::stretch())
+                        .add_column(Column::stretch())
+                        .add_column(Column::strict(12.0))
+                        .build(ctx),
+                    ),
+            )
+            .build(ctx);
+
+            for &arrow in &[ascending_arrow, descending_arrow] {
+                ctx[arrow].set_column(1).set_visibility(false);
+            }
+
+            let splitter = BorderBuilder::new(
+                WidgetBuilder::new()
+                    .with_width(SPLITTER_WIDTH)
+                    .with_background(BRUSH_LIGHT)
+                    .with_cursor(Some(CursorIcon::EwResize)),
+            )
+            .build(ctx);
+
+            header_widgets.push(cell);
+            header_widgets.push(splitter);
+            headers.push(ColumnHeader {
+                cell,
+                splitter,
+                ascending_arrow,
+                descending_arrow,
+            });
+        }
+
+        let header_panel = ScrollPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_height(self.header_height)
+                .on_row(0)
+                .with_child(
+                    StackPanelBuilder::new(
+                        WidgetBuilder::new()
+                            .with_horizontal_alignment(HorizontalAlignment::Left)
+                            .with_children(header_widgets),
+                    )
+                    .with_orientation(Orientation::Horizontal)
+                    .build(ctx),
+                ),
+        )
+        .with_horizontal_scroll_allowed(true)
+        .with_vertical_scroll_allowed(false)
+        .build(ctx);
+
+        let state = Rc::new(RefCell::new(DataGridState {
+            columns: self.columns,
+            order: (0..self.row_count).collect(),
+            rows: Default::default(),
+        }));
+
+        let scroll_viewer = ScrollViewerBuilder::new(WidgetBuilder::new())
+            .with_horizontal_scroll_allowed(true)
+            .with_vertical_scroll_allowed(true)
+            .build(ctx);
+        let body_panel = ctx[scroll_viewer]
+            .cast::<ScrollViewer>()
+            .expect("Must be ScrollViewer")
+            .scroll_panel;
+
+        let list = ListViewBuilder::new(WidgetBuilder::new().on_row(1))
+            .with_scroll_viewer(scroll_viewer)
+            .with_item_provider(
+                Rc::new(RowProvider {
+                    state: state.clone(),
+                }),
+                self.row_height,
+            )
+            .with_item_count(self.row_count)
+            .build(ctx);
+
+        let data_grid = DataGrid {
+            widget: self
+                .widget_builder
+                .with_child(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .with_child(header_panel)
+                            .with_child(list),
+                    )
+                    .add_row(Row::auto())
+                    .add_row(Row::stretch())
+                    .add_column(Column::stretch())
+                    .build(ctx),
+                )
+                .build(),
+            list,
+            header_panel,
+            body_panel,
+            headers,
+            sort: None,
+            selected: None,
+            state,
+            drag: None,
+        };
+
+        ctx.add_node(UiNode::new(data_grid))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        data_grid::{
+            DataGrid, DataGridBuilder, DataGridColumn, DataGridMessage, DataGridSort,
+            SortDirection, TextCellTemplate,
+        },
+        list_view::ListView,
+        message::MessageDirection,
+        text::Text,
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+    use std::rc::Rc;
+
+    fn update(ui: &mut UserInterface, screen_size: Vector2<f32>) {
+        for _ in 0..3 {
+            ui.update(screen_size, 0.0);
+            while ui.poll_message().is_some() {}
+        }
+    }
+
+    fn first_cell_text(ui: &UserInterface, data_grid: &DataGrid, column: usize) -> String {
+        let list = ui.node(data_grid.list).cast::<ListView>().unwrap();
+        let row = list.items()[0];
+        let cells = data_grid.state.borrow().rows[&row].clone();
+        ui.node(cells[column]).cast::<Text>().unwrap().text()
+    }
+
+    #[test]
+    fn test_data_grid() {
+        let screen_size = Vector2::new(400.0, 300.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let names = Rc::new(
+            (0..10000)
+                .map(|i| format!("Item{:05}", i))
+                .collect::<Vec<_>>(),
+        );
+        let names_ref = names.clone();
+
+        let data_grid =
+            DataGridBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(300.0))
+                .with_column(
+                    DataGridColumn::new(
+                        "Name",
+                        150.0,
+                        Rc::new(TextCellTemplate::new(move |row| names_ref[row].clone())),
+                    )
+                    .with_sortable(true),
+                )
+                .with_column(DataGridColumn::new(
+                    "Index",
+                    80.0,
+                    Rc::new(TextCellTemplate::new(|row| row.to_string())),
+                ))
+                .with_row_count(names.len())
+                .build(&mut ui.build_ctx());
+
+        update(&mut ui, screen_size);
+
+        {
+            let data_grid_ref = ui.node(data_grid).cast::<DataGrid>().unwrap();
+            let list = ui.node(data_grid_ref.list).cast::<ListView>().unwrap();
+            // Only visible rows must be realized.
+            assert!(!list.item_containers().is_empty());
+            assert!(list.item_containers().len() < 20);
+            assert_eq!(first_cell_text(&ui, data_grid_ref, 0), "Item00000");
+        }
+
+        ui.send_message(DataGridMessage::sort(
+            data_grid,
+            MessageDirection::ToWidget,
+            Some(DataGridSort {
+                column: 0,
+                direction: SortDirection::Descending,
+            }),
+        ));
+        ui.send_message(DataGridMessage::selection(
+            data_grid,
+            MessageDirection::ToWidget,
+            Some(9998),
+        ));
+        update(&mut ui, screen_size);
+
+        {
+            let data_grid_ref = ui.node(data_grid).cast::<DataGrid>().unwrap();
+            assert_eq!(first_cell_text(&ui, data_grid_ref, 0), "Item09999");
+            assert_eq!(first_cell_text(&ui, data_grid_ref, 1), "9999");
+            assert_eq!(data_grid_ref.row_at(0), Some(9999));
+            // Selection is kept in terms of data source rows.
+            assert_eq!(data_grid_ref.selected(), Some(9998));
+            let list = ui.node(data_grid_ref.list).cast::<ListView>().unwrap();
+            assert_eq!(list.selected(), Some(1));
+        }
+
+        ui.send_message(DataGridMessage::column_width(
+            data_grid,
+            MessageDirection::ToWidget,
+            1,
+            5.0,
+        ));
+        update(&mut ui, screen_size);
+
+        let data_grid_ref = ui.node(data_grid).cast::<DataGrid>().unwrap();
+        // Width is clamped by the min width of the column.
+        assert_eq!(data_grid_ref.column_width(1), Some(20.0));
+        let list = ui.node(data_grid_ref.list).cast::<ListView>().unwrap();
+        let cells = data_grid_ref.state.borrow().rows[&list.items()[0]].clone();
+        assert_eq!(ui.node(cells[1]).width(), 20.0);
+    }
+}
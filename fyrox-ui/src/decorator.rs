@@ -1,10 +1,18 @@
 use crate::{
     border::{Border, BorderBuilder},
     brush::Brush,
-    core::{algebra::Vector2, color::Color, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     draw::DrawingContext,
     message::{MessageDirection, UiMessage},
+    style::{Style, Theme},
     widget::{Widget, WidgetMessage},
     BuildContext, Control, NodeHandleMapping, UiNode, UserInterface, BRUSH_BRIGHT, BRUSH_DARKER,
     BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST,
@@ -42,7 +50,7 @@ impl DecoratorMessage {
 ///
 /// This element is widely used to provide some generic visual behaviour for various
 /// widgets. For example it used to decorate button, items in items control.
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct Decorator {
     pub border: Border,
     pub normal_brush: Brush,
@@ -68,6 +76,27 @@ impl DerefMut for Decorator {
     }
 }
 
+impl Default for Decorator {
+    fn default() -> Self {
+        Self {
+            border: Default::default(),
+            normal_brush: BRUSH_LIGHT,
+            hover_brush: BRUSH_LIGHTER,
+            pressed_brush: BRUSH_LIGHTEST,
+            selected_brush: BRUSH_BRIGHT,
+            disabled_brush: Brush::Solid(Color::opaque(50, 50, 50)),
+            is_selected: false,
+            is_pressable: true,
+        }
+    }
+}
+
+impl TypeUuidProvider for Decorator {
+    fn type_uuid() -> Uuid {
+        uuid!("c744ea15-1f50-4f99-b2e9-722c08bff06c")
+    }
+}
+
 impl Control for Decorator {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         self.border.query_component(type_id).or_else(|| {
@@ -124,6 +153,7 @@ impl Control for Decorator {
                 }
                 DecoratorMessage::HoverBrush(brush) => {
                     self.hover_brush = brush.clone();
+                    self.override_style_property(Style::HOVER_BRUSH);
                     if self.is_mouse_directly_over {
                         ui.send_message(WidgetMessage::background(
                             self.handle(),
@@ -134,6 +164,7 @@ impl Control for Decorator {
                 }
                 DecoratorMessage::NormalBrush(brush) => {
                     self.normal_brush = brush.clone();
+                    self.override_style_property(Style::NORMAL_BRUSH);
                     if !self.is_selected && !self.is_mouse_directly_over {
                         ui.send_message(WidgetMessage::background(
                             self.handle(),
@@ -144,9 +175,11 @@ impl Control for Decorator {
                 }
                 DecoratorMessage::PressedBrush(brush) => {
                     self.pressed_brush = brush.clone();
+                    self.override_style_property(Style::PRESSED_BRUSH);
                 }
                 DecoratorMessage::SelectedBrush(brush) => {
                     self.selected_brush = brush.clone();
+                    self.override_style_property(Style::SELECTED_BRUSH);
                     if self.is_selected {
                         ui.send_message(WidgetMessage::background(
                             self.handle(),
@@ -210,6 +243,29 @@ impl Control for Decorator {
             }
         }
     }
+
+    fn apply_style(&mut self, style: &Style) {
+        self.border.apply_style(style);
+
+        for (name, brush) in [
+            (Style::NORMAL_BRUSH, &mut self.normal_brush),
+            (Style::HOVER_BRUSH, &mut self.hover_brush),
+            (Style::PRESSED_BRUSH, &mut self.pressed_brush),
+            (Style::SELECTED_BRUSH, &mut self.selected_brush),
+            (Style::DISABLED_BRUSH, &mut self.disabled_brush),
+        ] {
+            if let Some(new_brush) = style.brush(name) {
+                *brush = new_brush;
+            }
+        }
+
+        // Background of a decorator is defined by its state.
+        self.border.widget.background = if self.is_selected {
+            self.selected_brush.clone()
+        } else {
+            self.normal_brush.clone()
+        };
+    }
 }
 
 pub struct DecoratorBuilder {
@@ -273,6 +329,22 @@ impl DecoratorBuilder {
     }
 
     pub fn build(mut self, ui: &mut BuildContext) -> Handle<UiNode> {
+        let widget_builder = &mut self.border_builder.widget_builder;
+        if widget_builder.style.is_none() {
+            widget_builder.style = Some(Theme::DECORATOR.to_owned());
+        }
+        for (name, brush) in [
+            (Style::NORMAL_BRUSH, &self.normal_brush),
+            (Style::HOVER_BRUSH, &self.hover_brush),
+            (Style::PRESSED_BRUSH, &self.pressed_brush),
+            (Style::SELECTED_BRUSH, &self.selected_brush),
+            (Style::DISABLED_BRUSH, &self.disabled_brush),
+        ] {
+            if brush.is_some() {
+                widget_builder.override_style_property(name);
+            }
+        }
+
         let normal_brush = self.normal_brush.unwrap_or(BRUSH_LIGHT);
         let selected_brush = self.selected_brush.unwrap_or(BRUSH_BRIGHT);
 
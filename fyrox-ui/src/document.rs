@@ -0,0 +1,628 @@
+//! UI document is a serializable hierarchy of widgets, that could be saved to a file and then
+//! instantiated in a user interface any number of times.
+//!
+//! # Overview
+//!
+//! Usually, user interface is created from code using widget builders. It is fine for small UIs, but
+//! for larger ones it is much more convenient to describe the UI as data. A document could be created
+//! from a part of existing user interface using [`UiDocument::from_ui`], saved to a file and loaded
+//! back using [`UiDocument::from_file`]. Loaded document could be instantiated in a user interface
+//! using [`UiDocument::instantiate`], the instance provides quick access to its widgets by their
+//! names.
+//!
+//! # Limitations
+//!
+//! Only widgets registered in [`WidgetConstructorContainer`] could be stored in a document, an attempt
+//! to save a document with an unregistered widget will result in an error. Some properties of the
+//! widgets are not stored:
+//!
+//! - Fonts - text widgets will use built-in font after loading.
+//! - Textures - images must be assigned after instantiation.
+//! - Tooltips, context menus and user data.
+//! - Handles of the widgets that are not part of the document.
+
+use crate::{
+    constructor::WidgetConstructorContainer,
+    core::{
+        pool::{Handle, PayloadContainer, Pool},
+        visitor::{prelude::*, VisitorFormat},
+    },
+    NodeHandleMapping, UiNode, UserInterface,
+};
+use fxhash::FxHashMap;
+use std::{path::Path, sync::Arc};
+
+/// A wrapper for widget pool record that allows to define custom visit method for UI documents.
+#[derive(Default)]
+pub struct WidgetContainer(Option<UiNode>);
+
+impl Visit for WidgetContainer {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        let mut is_some = u8::from(self.is_some());
+        is_some.visit("IsSome", &mut region)?;
+
+        if is_some != 0 {
+            if region.is_reading() {
+                // Actual widget will be created by the visit method of UiNode.
+                let mut node = UiNode::new(crate::canvas::Canvas::default());
+                node.visit("Data", &mut region)?;
+                self.0 = Some(node);
+            } else {
+                self.0.as_mut().unwrap().visit("Data", &mut region)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PayloadContainer for WidgetContainer {
+    type Element = UiNode;
+
+    fn new_empty() -> Self {
+        Self(None)
+    }
+
+    fn new(element: Self::Element) -> Self {
+        Self(Some(element))
+    }
+
+    fn is_some(&self) -> bool {
+        self.0.is_some()
+    }
+
+    fn as_ref(&self) -> Option<&Self::Element> {
+        self.0.as_ref()
+    }
+
+    fn as_mut(&mut self) -> Option<&mut Self::Element> {
+        self.0.as_mut()
+    }
+
+    fn replace(&mut self, element: Self::Element) -> Option<Self::Element> {
+        self.0.replace(element)
+    }
+
+    fn take(&mut self) -> Option<Self::Element> {
+        self.0.take()
+    }
+}
+
+/// See module docs.
+#[derive(Visit)]
+pub struct UiDocument {
+    nodes: Pool<UiNode, WidgetContainer>,
+    root: Handle<UiNode>,
+}
+
+impl Default for UiDocument {
+    fn default() -> Self {
+        Self {
+            nodes: Pool::new(),
+            root: Handle::NONE,
+        }
+    }
+}
+
+/// A result of instantiation of a [`UiDocument`].
+#[derive(Debug, Clone, Default)]
+pub struct UiDocumentInstance {
+    root: Handle<UiNode>,
+    names: FxHashMap<String, Handle<UiNode>>,
+}
+
+impl UiDocumentInstance {
+    /// Returns a handle of the root widget of the instance.
+    pub fn root(&self) -> Handle<UiNode> {
+        self.root
+    }
+
+    /// Returns a handle of a widget with the given name. If there are multiple widgets with the same
+    /// name, the first one in depth-first order is returned. Returns [`Handle::NONE`] if there is no
+    /// such widget.
+    pub fn find_by_name(&self, name: &str) -> Handle<UiNode> {
+        self.names.get(name).cloned().unwrap_or_default()
+    }
+}
+
+impl UiDocument {
+    /// Creates new document from a widget (and all its descendants) of the given user interface.
+    pub fn from_ui(ui: &UserInterface, root: Handle<UiNode>) -> Self {
+        let mut document = Self::default();
+        let mut map = NodeHandleMapping::default();
+
+        document.root = document.copy_from_ui_recursive(ui, root, &mut map);
+
+        for node in document.nodes.iter_mut() {
+            node.resolve(&map);
+        }
+
+        document
+    }
+
+    fn copy_from_ui_recursive(
+        &mut self,
+        ui: &UserInterface,
+        node_handle: Handle<UiNode>,
+        map: &mut NodeHandleMapping,
+    ) -> Handle<UiNode> {
+        let node = ui.node(node_handle);
+
+        let mut cloned = UiNode(node.clone_boxed());
+        cloned.tooltip = None;
+        cloned.context_menu = None;
+        cloned.user_data = None;
+        cloned.parent = Handle::NONE;
+
+        let copy_handle = self.nodes.spawn(cloned);
+        map.add_mapping(node_handle, copy_handle);
+
+        let children = node
+            .children()
+            .iter()
+            .map(|child| self.copy_from_ui_recursive(ui, *child, map))
+            .collect::<Vec<_>>();
+
+        for child in children.iter() {
+            self.nodes[*child].parent = copy_handle;
+        }
+        self.nodes[copy_handle].children = children;
+
+        copy_handle
+    }
+
+    /// Returns a handle of the root widget of the document.
+    pub fn root(&self) -> Handle<UiNode> {
+        self.root
+    }
+
+    /// Returns a reference to widgets of the document.
+    pub fn nodes(&self) -> &Pool<UiNode, WidgetContainer> {
+        &self.nodes
+    }
+
+    /// Searches for a widget with the given name in the document, the search is done in depth-first
+    /// order starting from the root. Returns [`Handle::NONE`] if there is no such widget.
+    pub fn find_by_name(&self, name: &str) -> Handle<UiNode> {
+        let mut stack = vec![self.root];
+        while let Some(handle) = stack.pop() {
+            if let Some(node) = self.nodes.try_borrow(handle) {
+                if node.name() == name {
+                    return handle;
+                }
+                stack.extend(node.children().iter().rev());
+            }
+        }
+        Handle::NONE
+    }
+
+    /// Creates a copy of the document in the given user interface. The root widget of the copy will
+    /// be attached to the root canvas of the user interface.
+    pub fn instantiate(&self, ui: &mut UserInterface) -> UiDocumentInstance {
+        let mut map = NodeHandleMapping::default();
+
+        let root = self.instantiate_recursive(ui, self.root, &mut map);
+
+        for &node_handle in map.hash_map.values() {
+            ui.nodes[node_handle].resolve(&map);
+        }
+
+        let mut names = FxHashMap::default();
+        let mut stack = vec![root];
+        while let Some(handle) = stack.pop() {
+            let node = ui.node(handle);
+            names.entry(node.name().to_owned()).or_insert(handle);
+            stack.extend(node.children().iter().rev());
+        }
+
+        UiDocumentInstance { root, names }
+    }
+
+    fn instantiate_recursive(
+        &self,
+        ui: &mut UserInterface,
+        node_handle: Handle<UiNode>,
+        map: &mut NodeHandleMapping,
+    ) -> Handle<UiNode> {
+        let node = &self.nodes[node_handle];
+
+        let mut cloned = UiNode(node.clone_boxed());
+        // Parent is a handle in the document, the actual one will be set when linking.
+        cloned.parent = Handle::NONE;
+
+        let children = node
+            .children()
+            .iter()
+            .map(|child| self.instantiate_recursive(ui, *child, map))
+            .collect::<Vec<_>>();
+
+        cloned.set_children(children);
+        let copy_handle = ui.add_node(cloned);
+        map.add_mapping(node_handle, copy_handle);
+        copy_handle
+    }
+
+    /// Saves the document into a memory buffer. Constructor container must contain constructors for
+    /// every widget of the document.
+    pub fn save_to_vec(
+        &mut self,
+        widget_constructors: Arc<WidgetConstructorContainer>,
+    ) -> Result<Vec<u8>, VisitError> {
+        let mut visitor = Visitor::new();
+        visitor.blackboard.register(widget_constructors);
+        self.visit("UiDocument", &mut visitor)?;
+        visitor.save_binary_to_vec()
+    }
+
+    /// Saves the document to a file in the given format. Constructor container must contain
+    /// constructors for every widget of the document.
+    pub fn save<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        format: VisitorFormat,
+        widget_constructors: Arc<WidgetConstructorContainer>,
+    ) -> VisitResult {
+        let mut visitor = Visitor::new();
+        visitor.blackboard.register(widget_constructors);
+        self.visit("UiDocument", &mut visitor)?;
+        visitor.save_with_format(path, format)
+    }
+
+    /// Loads a document from the given data, the data could be in any of supported formats.
+    pub fn load_from_memory(
+        data: Vec<u8>,
+        widget_constructors: Arc<WidgetConstructorContainer>,
+    ) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::load_from_memory(data)?;
+        Self::load(&mut visitor, widget_constructors)
+    }
+
+    /// Loads a document from the given file.
+    pub async fn from_file<P: AsRef<Path>>(
+        path: P,
+        widget_constructors: Arc<WidgetConstructorContainer>,
+    ) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        Self::load(&mut visitor, widget_constructors)
+    }
+
+    fn load(
+        visitor: &mut Visitor,
+        widget_constructors: Arc<WidgetConstructorContainer>,
+    ) -> Result<Self, VisitError> {
+        visitor.blackboard.register(widget_constructors);
+        let mut document = Self::default();
+        document.visit("UiDocument", visitor)?;
+        Ok(document)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        border::{Border, BorderBuilder},
+        brush::Brush,
+        button::{Button, ButtonBuilder},
+        canvas::{Canvas, CanvasBuilder},
+        check_box::{CheckBox, CheckBoxBuilder},
+        constructor::WidgetConstructorContainer,
+        core::{algebra::Vector2, color::Color, math::Rect, pool::Handle},
+        decorator::{Decorator, DecoratorBuilder},
+        document::{UiDocument, UiDocumentInstance},
+        formatted_text::WrapMode,
+        grid::{Column, Grid, GridBuilder, Row},
+        image::{Image, ImageBuilder},
+        progress_bar::{ProgressBar, ProgressBarBuilder},
+        stack_panel::{StackPanel, StackPanelBuilder},
+        style::Style,
+        text::{Text, TextBuilder},
+        widget::WidgetBuilder,
+        wrap_panel::{WrapPanel, WrapPanelBuilder},
+        HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
+    };
+    use std::sync::Arc;
+
+    /// Saves the widget with its descendants to a document, loads the document back and instantiates
+    /// it in a new user interface.
+    fn round_trip(ui: &UserInterface, root: Handle<UiNode>) -> (UserInterface, UiDocumentInstance) {
+        let constructors = Arc::new(WidgetConstructorContainer::new());
+        let data = UiDocument::from_ui(ui, root)
+            .save_to_vec(constructors.clone())
+            .unwrap();
+        let document = UiDocument::load_from_memory(data, constructors).unwrap();
+        let mut other_ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let instance = document.instantiate(&mut other_ui);
+        (other_ui, instance)
+    }
+
+    #[test]
+    fn test_document_round_trip() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let ctx = &mut ui.build_ctx();
+        let text = TextBuilder::new(WidgetBuilder::new().with_name("Title").on_row(0))
+            .with_text("Hello")
+            .build(ctx);
+        let button = ButtonBuilder::new(WidgetBuilder::new().with_name("Ok").on_row(1))
+            .with_text("Ok")
+            .build(ctx);
+        let grid = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_name("Root")
+                .with_child(text)
+                .with_child(button),
+        )
+        .add_row(Row::strict(20.0))
+        .add_row(Row::stretch())
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        let constructors = Arc::new(WidgetConstructorContainer::new());
+
+        let data = UiDocument::from_ui(&ui, grid)
+            .save_to_vec(constructors.clone())
+            .unwrap();
+        let document = UiDocument::load_from_memory(data, constructors).unwrap();
+        assert_eq!(
+            document.find_by_name("Ok"),
+            document.nodes()[document.root()].children()[1]
+        );
+
+        let mut other_ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let instance = document.instantiate(&mut other_ui);
+
+        let root = other_ui.node(instance.root());
+        assert_eq!(root.parent(), other_ui.root());
+        let grid = root.cast::<Grid>().unwrap();
+        assert_eq!(grid.rows.borrow().len(), 2);
+        assert_eq!(grid.rows.borrow()[0].desired_size, 20.0);
+
+        let title = other_ui
+            .node(instance.find_by_name("Title"))
+            .cast::<Text>()
+            .unwrap();
+        assert_eq!(title.text(), "Hello");
+        assert_eq!(title.row(), 0);
+
+        let ok = other_ui
+            .node(instance.find_by_name("Ok"))
+            .cast::<Button>()
+            .unwrap();
+        assert_eq!(ok.row(), 1);
+        // Internal handles must point to the instantiated widgets.
+        assert!(ok.children().contains(&ok.decorator));
+        assert!(other_ui.try_get_node(ok.content).is_some());
+    }
+
+    #[test]
+    fn test_widget_round_trip() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let canvas = CanvasBuilder::new(
+            WidgetBuilder::new()
+                .with_name("Canvas")
+                .with_style("Custom")
+                .with_background(Brush::Solid(Color::RED))
+                .with_margin(Thickness::uniform(2.0))
+                .with_width(30.0)
+                .with_height(40.0)
+                .with_min_size(Vector2::new(1.0, 2.0))
+                .with_max_size(Vector2::new(50.0, 60.0))
+                .with_desired_position(Vector2::new(3.0, 4.0))
+                .with_vertical_alignment(VerticalAlignment::Bottom)
+                .with_horizontal_alignment(HorizontalAlignment::Right)
+                .with_visibility(false)
+                .with_enabled(false)
+                .with_opacity(Some(0.5))
+                .with_tab_index(Some(3))
+                .with_tab_stop(true)
+                .on_row(1)
+                .on_column(2),
+        )
+        .build(&mut ui.build_ctx());
+
+        let (other_ui, instance) = round_trip(&ui, canvas);
+        let canvas = other_ui
+            .node(instance.find_by_name("Canvas"))
+            .cast::<Canvas>()
+            .unwrap();
+        assert_eq!(canvas.style(), Some("Custom"));
+        assert!(canvas.is_style_overridden(Style::BACKGROUND));
+        assert!(canvas.is_style_overridden(Style::MARGIN));
+        assert!(!canvas.is_style_overridden(Style::FOREGROUND));
+        assert_eq!(canvas.background(), Brush::Solid(Color::RED));
+        assert_eq!(canvas.margin(), Thickness::uniform(2.0));
+        assert_eq!(canvas.width(), 30.0);
+        assert_eq!(canvas.height(), 40.0);
+        assert_eq!(canvas.min_size(), Vector2::new(1.0, 2.0));
+        assert_eq!(canvas.max_size(), Vector2::new(50.0, 60.0));
+        assert_eq!(canvas.desired_local_position(), Vector2::new(3.0, 4.0));
+        assert_eq!(canvas.vertical_alignment(), VerticalAlignment::Bottom);
+        assert_eq!(canvas.horizontal_alignment(), HorizontalAlignment::Right);
+        assert!(!canvas.visibility());
+        assert!(!canvas.enabled());
+        assert_eq!(canvas.opacity(), Some(0.5));
+        assert_eq!(canvas.tab_index, Some(3));
+        assert!(canvas.tab_stop);
+        assert_eq!(canvas.row(), 1);
+        assert_eq!(canvas.column(), 2);
+    }
+
+    #[test]
+    fn test_border_round_trip() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let border = BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_name("Border")
+                .with_foreground(Brush::Solid(Color::GREEN)),
+        )
+        .with_stroke_thickness(Thickness::uniform(3.0))
+        .with_corner_radius(5.0)
+        .build(&mut ui.build_ctx());
+
+        let (other_ui, instance) = round_trip(&ui, border);
+        let border = other_ui
+            .node(instance.find_by_name("Border"))
+            .cast::<Border>()
+            .unwrap();
+        assert_eq!(border.foreground(), Brush::Solid(Color::GREEN));
+        assert_eq!(border.stroke_thickness, Thickness::uniform(3.0));
+        assert_eq!(border.corner_radius, 5.0);
+    }
+
+    #[test]
+    fn test_decorator_round_trip() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let decorator = DecoratorBuilder::new(BorderBuilder::new(
+            WidgetBuilder::new().with_name("Decorator"),
+        ))
+        .with_normal_brush(Brush::Solid(Color::RED))
+        .with_hover_brush(Brush::Solid(Color::GREEN))
+        .with_pressed_brush(Brush::Solid(Color::BLUE))
+        .with_selected_brush(Brush::Solid(Color::WHITE))
+        .with_disabled_brush(Brush::Solid(Color::BLACK))
+        .with_selected(true)
+        .with_pressable(false)
+        .build(&mut ui.build_ctx());
+
+        let (other_ui, instance) = round_trip(&ui, decorator);
+        let decorator = other_ui
+            .node(instance.find_by_name("Decorator"))
+            .cast::<Decorator>()
+            .unwrap();
+        assert_eq!(decorator.normal_brush, Brush::Solid(Color::RED));
+        assert_eq!(decorator.hover_brush, Brush::Solid(Color::GREEN));
+        assert_eq!(decorator.pressed_brush, Brush::Solid(Color::BLUE));
+        assert_eq!(decorator.selected_brush, Brush::Solid(Color::WHITE));
+        assert_eq!(decorator.disabled_brush, Brush::Solid(Color::BLACK));
+        assert!(decorator.is_selected);
+        assert!(!decorator.is_pressable);
+        assert_eq!(decorator.background(), Brush::Solid(Color::WHITE));
+    }
+
+    #[test]
+    fn test_check_box_round_trip() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let check_box = CheckBoxBuilder::new(WidgetBuilder::new().with_name("CheckBox"))
+            .checked(None)
+            .build(&mut ui.build_ctx());
+
+        let (other_ui, instance) = round_trip(&ui, check_box);
+        let check_box = other_ui
+            .node(instance.find_by_name("CheckBox"))
+            .cast::<CheckBox>()
+            .unwrap();
+        assert_eq!(check_box.checked, None);
+        // Marks must point to the instantiated widgets.
+        for mark in [
+            check_box.check_mark,
+            check_box.uncheck_mark,
+            check_box.undefined_mark,
+        ] {
+            assert!(other_ui.try_get_node(mark).is_some());
+        }
+        assert!(other_ui.node(check_box.undefined_mark).visibility());
+        assert!(!other_ui.node(check_box.check_mark).visibility());
+    }
+
+    #[test]
+    fn test_image_round_trip() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let image = ImageBuilder::new(WidgetBuilder::new().with_name("Image"))
+            .with_flip(true)
+            .with_uv_rect(Rect::new(0.25, 0.5, 0.5, 0.25))
+            .with_checkerboard_background(true)
+            .build(&mut ui.build_ctx());
+
+        let (other_ui, instance) = round_trip(&ui, image);
+        let image = other_ui
+            .node(instance.find_by_name("Image"))
+            .cast::<Image>()
+            .unwrap();
+        assert!(image.flip);
+        assert_eq!(image.uv_rect, Rect::new(0.25, 0.5, 0.5, 0.25));
+        assert!(image.checkerboard_background);
+    }
+
+    #[test]
+    fn test_progress_bar_round_trip() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let progress_bar = ProgressBarBuilder::new(WidgetBuilder::new().with_name("ProgressBar"))
+            .with_progress(0.75)
+            .build(&mut ui.build_ctx());
+
+        let (other_ui, instance) = round_trip(&ui, progress_bar);
+        let progress_bar = other_ui
+            .node(instance.find_by_name("ProgressBar"))
+            .cast::<ProgressBar>()
+            .unwrap();
+        assert_eq!(progress_bar.progress(), 0.75);
+        assert!(other_ui.try_get_node(progress_bar.indicator).is_some());
+        assert!(other_ui.try_get_node(progress_bar.body).is_some());
+    }
+
+    #[test]
+    fn test_panels_round_trip() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let ctx = &mut ui.build_ctx();
+        let wrap_panel = WrapPanelBuilder::new(WidgetBuilder::new().with_name("WrapPanel"))
+            .with_orientation(Orientation::Vertical)
+            .build(ctx);
+        let stack_panel = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_name("StackPanel")
+                .with_child(wrap_panel),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
+        let (other_ui, instance) = round_trip(&ui, stack_panel);
+        let stack_panel_ref = other_ui
+            .node(instance.find_by_name("StackPanel"))
+            .cast::<StackPanel>()
+            .unwrap();
+        assert_eq!(stack_panel_ref.orientation, Orientation::Horizontal);
+        let wrap_panel = instance.find_by_name("WrapPanel");
+        assert_eq!(stack_panel_ref.children(), &[wrap_panel]);
+        let wrap_panel_ref = other_ui.node(wrap_panel).cast::<WrapPanel>().unwrap();
+        assert_eq!(wrap_panel_ref.orientation(), Orientation::Vertical);
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let text = TextBuilder::new(WidgetBuilder::new().with_name("Text"))
+            .with_text("Some text")
+            .with_font_size(20.0)
+            .with_wrap(WrapMode::Word)
+            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .with_horizontal_text_alignment(HorizontalAlignment::Right)
+            .with_shadow(true)
+            .with_shadow_brush(Brush::Solid(Color::RED))
+            .with_shadow_dilation(2.0)
+            .with_shadow_offset(Vector2::new(3.0, 4.0))
+            .build(&mut ui.build_ctx());
+
+        let (other_ui, instance) = round_trip(&ui, text);
+        let text = other_ui
+            .node(instance.find_by_name("Text"))
+            .cast::<Text>()
+            .unwrap();
+        assert_eq!(text.text(), "Some text");
+        assert_eq!(text.wrap_mode(), WrapMode::Word);
+        let formatted_text = text.formatted_text.borrow();
+        assert_eq!(formatted_text.font_size(), Some(20.0));
+        assert_eq!(
+            formatted_text.vertical_alignment(),
+            VerticalAlignment::Center
+        );
+        assert_eq!(
+            formatted_text.horizontal_alignment(),
+            HorizontalAlignment::Right
+        );
+        assert!(formatted_text.shadow);
+        assert_eq!(formatted_text.shadow_brush, Brush::Solid(Color::RED));
+        assert_eq!(formatted_text.shadow_dilation, 2.0);
+        assert_eq!(formatted_text.shadow_offset, Vector2::new(3.0, 4.0));
+    }
+}
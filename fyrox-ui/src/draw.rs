@@ -352,7 +352,7 @@ pub trait Draw {
     ) {
         // Restrict corner radius in available rectangle.
         let min_axis = rect.w().min(rect.h());
-        corner_radius = corner_radius.max(min_axis * 0.5);
+        corner_radius = corner_radius.min(min_axis * 0.5);
 
         let offset = thickness * 0.5;
 
@@ -436,6 +436,78 @@ pub trait Draw {
         }
     }
 
+    fn push_rounded_rect_filled(
+        &mut self,
+        rect: &Rect<f32>,
+        mut corner_radius: f32,
+        corner_subdivisions: usize,
+    ) {
+        // Restrict corner radius in available rectangle.
+        let min_axis = rect.w().min(rect.h());
+        corner_radius = corner_radius.min(min_axis * 0.5);
+
+        let tex_coord = |p: Vector2<f32>| {
+            Vector2::new(
+                (p.x - rect.x()) / rect.w().max(f32::EPSILON),
+                (p.y - rect.y()) / rect.h().max(f32::EPSILON),
+            )
+        };
+
+        // Rounded rectangle is convex, so it could be triangulated as a fan around its center.
+        let center = rect.position + rect.size.scale(0.5);
+        let center_index = self.last_vertex_index();
+        self.push_vertex(center, tex_coord(center));
+
+        let corners = [
+            (
+                Vector2::new(
+                    rect.x() + rect.w() - corner_radius,
+                    rect.y() + rect.h() - corner_radius,
+                ),
+                0.0f32,
+            ),
+            (
+                Vector2::new(
+                    rect.x() + corner_radius,
+                    rect.y() + rect.h() - corner_radius,
+                ),
+                90.0,
+            ),
+            (
+                Vector2::new(rect.x() + corner_radius, rect.y() + corner_radius),
+                180.0,
+            ),
+            (
+                Vector2::new(
+                    rect.x() + rect.w() - corner_radius,
+                    rect.y() + corner_radius,
+                ),
+                270.0,
+            ),
+        ];
+
+        let subdivisions = corner_subdivisions.max(1);
+        let mut count = 0;
+        for (corner_center, start_angle) in corners {
+            for i in 0..=subdivisions {
+                let angle = (start_angle + 90.0 * i as f32 / subdivisions as f32).to_radians();
+                let position =
+                    corner_center + Vector2::new(angle.cos(), angle.sin()).scale(corner_radius);
+                self.push_vertex(position, tex_coord(position));
+                count += 1;
+            }
+        }
+
+        let first_vertex = center_index + 1;
+        for i in 0..count {
+            self.push_triangle(
+                center_index,
+                first_vertex + i,
+                first_vertex + (i + 1) % count,
+            );
+        }
+    }
+
     fn push_bezier(
         &mut self,
         p0: Vector2<f32>,
@@ -663,54 +735,88 @@ impl DrawingContext {
         position: Vector2<f32>,
         formatted_text: &FormattedText,
     ) {
-        let font = formatted_text.get_font();
-
         // Draw shadow, if any.
         if formatted_text.shadow {
-            for element in formatted_text.get_glyphs() {
-                let bounds = element.get_bounds();
-
-                let final_bounds = Rect::new(
-                    position.x + bounds.x(),
-                    position.y + bounds.y(),
-                    bounds.w(),
-                    bounds.h(),
-                )
-                .inflate(
-                    formatted_text.shadow_dilation,
-                    formatted_text.shadow_dilation,
-                )
-                .translate(formatted_text.shadow_offset);
-
-                self.push_rect_filled(&final_bounds, Some(element.get_tex_coords()));
-            }
+            self.draw_text_glyphs(clip_bounds, position, formatted_text, true);
+        }
+
+        self.draw_text_glyphs(clip_bounds, position, formatted_text, false);
 
+        for underline in formatted_text.get_underlines() {
+            let bounds = underline.get_bounds().translate(position);
+            self.push_rect_filled(&bounds, None);
             self.commit(
                 clip_bounds,
-                formatted_text.shadow_brush.clone(),
-                CommandTexture::Font(font.clone()),
+                formatted_text.run_brush(Some(underline.run())),
+                CommandTexture::None,
                 None,
-            )
+            );
         }
+    }
 
-        for element in formatted_text.get_glyphs() {
-            let bounds = element.get_bounds();
+    fn draw_text_glyphs(
+        &mut self,
+        clip_bounds: Rect<f32>,
+        position: Vector2<f32>,
+        formatted_text: &FormattedText,
+        shadow: bool,
+    ) {
+        let glyphs = formatted_text.get_glyphs();
+
+        // Glyphs are grouped in batches that share the same texture and brush.
+        let mut begin = 0;
+        while begin < glyphs.len() {
+            let first = &glyphs[begin];
+            let brush = if shadow {
+                formatted_text.shadow_brush.clone()
+            } else if first.image().is_some() {
+                Brush::Solid(Color::WHITE)
+            } else {
+                formatted_text.run_brush(first.run())
+            };
+
+            let mut end = begin + 1;
+            while let Some(glyph) = glyphs.get(end) {
+                let same_batch = glyph.font_style() == first.font_style()
+                    && glyph.image() == first.image()
+                    && (shadow
+                        || glyph.image().is_some()
+                        || formatted_text.run_brush(glyph.run()) == brush);
+                if !same_batch {
+                    break;
+                }
+                end += 1;
+            }
 
-            let final_bounds = Rect::new(
-                position.x + bounds.x(),
-                position.y + bounds.y(),
-                bounds.w(),
-                bounds.h(),
-            );
+            // Images have no shadows.
+            if !(shadow && first.image().is_some()) {
+                let (texture, atlas_size) = match first.image() {
+                    Some(image) => (CommandTexture::Texture(image.clone()), 1),
+                    None => {
+                        let font = formatted_text.font_of(first.font_style());
+                        let atlas_size = font.0.lock().atlas_size();
+                        (CommandTexture::Font(font), atlas_size)
+                    }
+                };
+
+                for glyph in &glyphs[begin..end] {
+                    let mut bounds = glyph.get_bounds().translate(position);
+                    if shadow {
+                        bounds = bounds
+                            .inflate(
+                                formatted_text.shadow_dilation,
+                                formatted_text.shadow_dilation,
+                            )
+                            .translate(formatted_text.shadow_offset);
+                    }
 
-            self.push_rect_filled(&final_bounds, Some(element.get_tex_coords()));
-        }
+                    self.push_rect_filled(&bounds, Some(&glyph.get_tex_coords(atlas_size)));
+                }
 
-        self.commit(
-            clip_bounds,
-            formatted_text.brush(),
-            CommandTexture::Font(font),
-            None,
-        )
+                self.commit(clip_bounds, brush, texture, None);
+            }
+
+            begin = end;
+        }
     }
 }
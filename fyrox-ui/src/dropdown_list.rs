@@ -8,7 +8,8 @@ use crate::{
     grid::{Column, GridBuilder, Row},
     list_view::{ListViewBuilder, ListViewMessage},
     message::{MessageDirection, UiMessage},
-    popup::{Placement, PopupBuilder, PopupMessage},
+    navigation::NavigationDirection,
+    popup::{Placement, Popup, PopupBuilder, PopupMessage},
     utils::{make_arrow, ArrowDirection},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, NodeHandleMapping, Thickness, UiNode, UserInterface, BRUSH_DARKER,
@@ -82,14 +83,54 @@ impl Control for DropdownList {
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
-        if let Some(WidgetMessage::MouseDown { .. }) = message.data::<WidgetMessage>() {
+        if let Some(msg) = message.data::<WidgetMessage>() {
             if message.destination() == self.handle()
                 || self.widget.has_descendant(message.destination(), ui)
             {
-                ui.send_message(DropdownListMessage::open(
-                    self.handle,
-                    MessageDirection::ToWidget,
-                ));
+                match *msg {
+                    WidgetMessage::MouseDown { .. } => {
+                        ui.send_message(DropdownListMessage::open(
+                            self.handle,
+                            MessageDirection::ToWidget,
+                        ));
+                    }
+                    WidgetMessage::Activate if !message.handled() => {
+                        ui.send_message(if self.is_open(ui) {
+                            DropdownListMessage::close(self.handle, MessageDirection::ToWidget)
+                        } else {
+                            DropdownListMessage::open(self.handle, MessageDirection::ToWidget)
+                        });
+                        message.set_handled(true);
+                    }
+                    WidgetMessage::Navigate(direction)
+                        if !message.handled() && self.is_open(ui) =>
+                    {
+                        // Open list is navigated by changing its selection.
+                        let new_selection = match (direction, self.selection) {
+                            (NavigationDirection::Up, Some(selection)) => {
+                                Some(selection.saturating_sub(1))
+                            }
+                            (NavigationDirection::Down, Some(selection)) => {
+                                Some((selection + 1).min(self.items.len().saturating_sub(1)))
+                            }
+                            (NavigationDirection::Up | NavigationDirection::Down, None)
+                                if !self.items.is_empty() =>
+                            {
+                                Some(0)
+                            }
+                            _ => self.selection,
+                        };
+                        if new_selection != self.selection {
+                            ui.send_message(DropdownListMessage::selection(
+                                self.handle,
+                                MessageDirection::ToWidget,
+                                new_selection,
+                            ));
+                        }
+                        message.set_handled(true);
+                    }
+                    _ => (),
+                }
             }
         } else if let Some(msg) = message.data::<DropdownListMessage>() {
             if message.destination() == self.handle()
@@ -197,6 +238,13 @@ impl Control for DropdownList {
 }
 
 impl DropdownList {
+    /// Returns `true` if the list of items is shown.
+    pub fn is_open(&self, ui: &UserInterface) -> bool {
+        ui.try_get_node(self.popup)
+            .and_then(|n| n.cast::<Popup>())
+            .map_or(false, |p| p.is_open)
+    }
+
     pub fn selection(&self) -> Option<usize> {
         self.selection
     }
@@ -280,10 +328,12 @@ impl DropdownListBuilder {
         self
     }
 
-    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode>
+    pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode>
     where
         Self: Sized,
     {
+        self.widget_builder.tab_stop.get_or_insert(true);
+
         let items_control = ListViewBuilder::new(
             WidgetBuilder::new().with_max_size(Vector2::new(f32::INFINITY, 200.0)),
         )
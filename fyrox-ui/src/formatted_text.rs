@@ -1,15 +1,285 @@
+//! Formatted text is a text with a layout (lines, glyphs, alignment, wrapping) and optional
+//! inline styling.
+//!
+//! # Rich text
+//!
+//! Parts of a text could be styled using [`TextRun`]s, every run defines a brush, a font style,
+//! a size and an underline for a range of characters. Runs could be created manually (see
+//! [`FormattedText::set_runs`]) or parsed from a simple BBCode-like markup (see
+//! [`FormattedText::set_markup`] and [`parse_markup`]). The following tags are supported:
+//!
+//! - `[b]bold[/b]` - bold font (see [`FormattedText::set_bold_font`]).
+//! - `[i]italic[/i]` - italic font (see [`FormattedText::set_italic_font`]).
+//! - `[u]underline[/u]` - underlined text.
+//! - `[color=#FF0000]red[/color]` - brush of the text, the color is defined in `#RRGGBB` or
+//!   `#RRGGBBAA` format.
+//! - `[size=24]large[/size]` - size of the font.
+//! - `[img=name]` - inline image with the given name (see [`FormattedText::add_inline_image`]),
+//!   the image is square and its size matches the ascender of the font.
+//!
+//! Tags could be nested, closing tag restores the style that was active before the opening tag.
+//! Unknown or malformed tags are kept as is, `[[` could be used to insert `[` character.
+
 use crate::{
     brush::Brush,
-    core::{algebra::Vector2, color::Color, math::Rect},
+    core::{algebra::Vector2, color::Color, math::Rect, visitor::prelude::*},
+    draw::SharedTexture,
     ttf::SharedFont,
     Font, HorizontalAlignment, VerticalAlignment,
 };
+use fxhash::FxHashMap;
 use std::ops::Range;
 
+/// A character that is used as a placeholder for inline images in the text.
+pub const IMAGE_PLACEHOLDER: char = '\u{FFFC}';
+
+/// Style of a font of a text run.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default, Visit)]
+pub enum FontStyle {
+    /// Regular font.
+    #[default]
+    Regular,
+    /// Bold font.
+    Bold,
+    /// Italic font.
+    Italic,
+    /// Bold and italic font.
+    BoldItalic,
+}
+
+impl FontStyle {
+    /// Returns bold variant of the style.
+    pub fn with_bold(self) -> Self {
+        match self {
+            FontStyle::Regular | FontStyle::Bold => FontStyle::Bold,
+            FontStyle::Italic | FontStyle::BoldItalic => FontStyle::BoldItalic,
+        }
+    }
+
+    /// Returns italic variant of the style.
+    pub fn with_italic(self) -> Self {
+        match self {
+            FontStyle::Regular | FontStyle::Italic => FontStyle::Italic,
+            FontStyle::Bold | FontStyle::BoldItalic => FontStyle::BoldItalic,
+        }
+    }
+}
+
+/// A styled range of characters of a text. Properties that are not set are taken from the text
+/// itself. See module docs for more info.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextRun {
+    /// Range of characters of the run.
+    pub range: Range<usize>,
+    /// Brush of the run.
+    pub brush: Option<Brush>,
+    /// Style of the font of the run.
+    pub font_style: FontStyle,
+    /// Size of the font of the run.
+    pub size: Option<f32>,
+    /// Whether the run is underlined or not.
+    pub underline: bool,
+    /// Name of an inline image. The run of an image must contain exactly one
+    /// [`IMAGE_PLACEHOLDER`] character.
+    pub image: Option<String>,
+}
+
+impl TextRun {
+    fn has_same_style(&self, other: &TextRun) -> bool {
+        self.brush == other.brush
+            && self.font_style == other.font_style
+            && self.size == other.size
+            && self.underline == other.underline
+            && self.image.is_none()
+            && other.image.is_none()
+    }
+
+    fn is_plain(&self) -> bool {
+        self.has_same_style(&TextRun::default())
+    }
+}
+
+impl Visit for TextRun {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        let mut start = self.range.start as u32;
+        start.visit("Start", &mut region)?;
+        let mut end = self.range.end as u32;
+        end.visit("End", &mut region)?;
+        if region.is_reading() {
+            self.range = start as usize..end as usize;
+        }
+
+        let mut has_brush = self.brush.is_some();
+        has_brush.visit("HasBrush", &mut region)?;
+        if has_brush {
+            let mut brush = self.brush.clone().unwrap_or(Brush::Solid(Color::WHITE));
+            brush.visit("Brush", &mut region)?;
+            self.brush = Some(brush);
+        }
+
+        self.font_style.visit("FontStyle", &mut region)?;
+        self.size.visit("Size", &mut region)?;
+        self.underline.visit("Underline", &mut region)?;
+        self.image.visit("Image", &mut region)?;
+
+        Ok(())
+    }
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#')?;
+    let rgba = u32::from_str_radix(hex, 16).ok()?;
+    match hex.len() {
+        6 => Some(Color::opaque(
+            (rgba >> 16) as u8,
+            (rgba >> 8) as u8,
+            rgba as u8,
+        )),
+        8 => Some(Color::from_rgba(
+            (rgba >> 24) as u8,
+            (rgba >> 16) as u8,
+            (rgba >> 8) as u8,
+            rgba as u8,
+        )),
+        _ => None,
+    }
+}
+
+struct MarkupOutput {
+    text: String,
+    length: usize,
+    runs: Vec<TextRun>,
+}
+
+fn push_styled_char(c: char, style: &TextRun, output: &mut MarkupOutput) {
+    let index = output.length;
+    output.text.push(c);
+    output.length += 1;
+
+    if style.is_plain() {
+        return;
+    }
+
+    match output.runs.last_mut() {
+        Some(last) if last.range.end == index && last.has_same_style(style) => {
+            last.range.end += 1;
+        }
+        _ => output.runs.push(TextRun {
+            range: index..index + 1,
+            ..style.clone()
+        }),
+    }
+}
+
+/// Parses the given markup (see module docs for syntax) and returns plain text and a set of
+/// styled runs of the text.
+pub fn parse_markup(markup: &str) -> (String, Vec<TextRun>) {
+    let mut output = MarkupOutput {
+        text: String::new(),
+        length: 0,
+        runs: Vec::new(),
+    };
+    let mut style = TextRun::default();
+    let mut stack: Vec<(&str, TextRun)> = Vec::new();
+
+    let mut rest = markup;
+    while let Some(c) = rest.chars().next() {
+        if c == '[' {
+            if let Some(stripped) = rest.strip_prefix("[[") {
+                push_styled_char('[', &style, &mut output);
+                rest = stripped;
+                continue;
+            }
+
+            if let Some(tag_end) = rest.find(']') {
+                let tag = &rest[1..tag_end];
+
+                let handled = if let Some(name) = tag.strip_prefix('/') {
+                    if let Some(position) = stack.iter().rposition(|(n, _)| *n == name.trim()) {
+                        style = stack[position].1.clone();
+                        stack.truncate(position);
+                        true
+                    } else {
+                        false
+                    }
+                } else {
+                    let (name, value) = match tag.split_once('=') {
+                        Some((name, value)) => (name.trim(), Some(value.trim())),
+                        None => (tag.trim(), None),
+                    };
+
+                    let mut new_style = style.clone();
+                    let handled = match (name, value) {
+                        ("b", None) => {
+                            new_style.font_style = new_style.font_style.with_bold();
+                            true
+                        }
+                        ("i", None) => {
+                            new_style.font_style = new_style.font_style.with_italic();
+                            true
+                        }
+                        ("u", None) => {
+                            new_style.underline = true;
+                            true
+                        }
+                        ("color", Some(value)) => match parse_color(value) {
+                            Some(color) => {
+                                new_style.brush = Some(Brush::Solid(color));
+                                true
+                            }
+                            None => false,
+                        },
+                        ("size", Some(value)) => match value.parse::<f32>() {
+                            Ok(size) if size > 0.0 => {
+                                new_style.size = Some(size);
+                                true
+                            }
+                            _ => false,
+                        },
+                        ("img", Some(value)) if !value.is_empty() => {
+                            let image_style = TextRun {
+                                image: Some(value.to_owned()),
+                                ..style.clone()
+                            };
+                            push_styled_char(IMAGE_PLACEHOLDER, &image_style, &mut output);
+                            // Images have no closing tag.
+                            rest = &rest[tag_end + 1..];
+                            continue;
+                        }
+                        _ => false,
+                    };
+
+                    if handled {
+                        stack.push((name, std::mem::replace(&mut style, new_style)));
+                    }
+
+                    handled
+                };
+
+                if handled {
+                    rest = &rest[tag_end + 1..];
+                    continue;
+                }
+            }
+        }
+
+        push_styled_char(c, &style, &mut output);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    (output.text, output.runs)
+}
+
 #[derive(Debug, Clone)]
 pub struct TextGlyph {
     bounds: Rect<f32>,
-    tex_coords: [Vector2<f32>; 4],
+    // Bounds of the glyph in the font atlas (in pixels), `None` for missing glyphs and images.
+    atlas_bounds: Option<Rect<f32>>,
+    font_style: FontStyle,
+    run: Option<usize>,
+    image: Option<SharedTexture>,
 }
 
 impl TextGlyph {
@@ -17,8 +287,65 @@ impl TextGlyph {
         self.bounds
     }
 
-    pub fn get_tex_coords(&self) -> &[Vector2<f32>; 4] {
-        &self.tex_coords
+    /// Calculates texture coordinates of the glyph for the given size of the font atlas. The
+    /// atlas could grow when new glyphs are added, so texture coordinates must be calculated
+    /// using current size of the atlas (see [`crate::ttf::Font::atlas_size`]). Texture
+    /// coordinates of inline images always cover the whole image.
+    pub fn get_tex_coords(&self, atlas_size: usize) -> [Vector2<f32>; 4] {
+        if self.image.is_some() {
+            return [
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 0.0),
+                Vector2::new(1.0, 1.0),
+                Vector2::new(0.0, 1.0),
+            ];
+        }
+
+        match self.atlas_bounds {
+            Some(bounds) => {
+                let k = 1.0 / atlas_size as f32;
+                [
+                    bounds.left_top_corner().scale(k),
+                    bounds.right_top_corner().scale(k),
+                    bounds.right_bottom_corner().scale(k),
+                    bounds.left_bottom_corner().scale(k),
+                ]
+            }
+            None => Default::default(),
+        }
+    }
+
+    /// Returns style of the font of the glyph, see [`FormattedText::font_of`].
+    pub fn font_style(&self) -> FontStyle {
+        self.font_style
+    }
+
+    /// Returns index of the run of the glyph (if any).
+    pub fn run(&self) -> Option<usize> {
+        self.run
+    }
+
+    /// Returns texture of the inline image (if the glyph is an image).
+    pub fn image(&self) -> Option<&SharedTexture> {
+        self.image.as_ref()
+    }
+}
+
+/// An underline of a part of a text.
+#[derive(Debug, Clone)]
+pub struct TextUnderline {
+    bounds: Rect<f32>,
+    run: usize,
+}
+
+impl TextUnderline {
+    pub fn get_bounds(&self) -> Rect<f32> {
+        self.bounds
+    }
+
+    /// Returns index of the run of the underline.
+    pub fn run(&self) -> usize {
+        self.run
     }
 }
 
@@ -60,7 +387,7 @@ impl TextLine {
 }
 
 /// Wrapping mode for formatted text.
-#[derive(Copy, Clone, PartialOrd, PartialEq, Hash, Debug, Eq)]
+#[derive(Copy, Clone, PartialOrd, PartialEq, Hash, Debug, Eq, Visit)]
 pub enum WrapMode {
     /// No wrapping needed.
     NoWrap,
@@ -104,6 +431,16 @@ pub struct FormattedText {
     lines: Vec<TextLine>,
     // Final glyphs for draw buffer.
     glyphs: Vec<TextGlyph>,
+    underlines: Vec<TextUnderline>,
+    runs: Vec<TextRun>,
+    markup: bool,
+    bold_font: Option<SharedFont>,
+    italic_font: Option<SharedFont>,
+    bold_italic_font: Option<SharedFont>,
+    images: FxHashMap<String, SharedTexture>,
+    // Advances of the characters (including kerning), calculated by the last build.
+    advances: Vec<f32>,
+    font_size: Option<f32>,
     vertical_alignment: VerticalAlignment,
     horizontal_alignment: HorizontalAlignment,
     brush: Brush,
@@ -116,6 +453,36 @@ pub struct FormattedText {
     pub shadow_offset: Vector2<f32>,
 }
 
+impl Visit for FormattedText {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        // Font is not serialized, the text is stored as a string and converted back to
+        // characters using current font. Markup is not stored, only the plain text with its runs.
+        let mut text = self.text();
+        text.visit("Text", &mut region)?;
+        if region.is_reading() {
+            self.set_plain_text(text);
+        }
+
+        self.vertical_alignment
+            .visit("VerticalAlignment", &mut region)?;
+        self.horizontal_alignment
+            .visit("HorizontalAlignment", &mut region)?;
+        self.brush.visit("Brush", &mut region)?;
+        self.wrap.visit("Wrap", &mut region)?;
+        self.shadow.visit("Shadow", &mut region)?;
+        self.shadow_brush.visit("ShadowBrush", &mut region)?;
+        self.shadow_dilation.visit("ShadowDilation", &mut region)?;
+        self.shadow_offset.visit("ShadowOffset", &mut region)?;
+        let _ = self.font_size.visit("FontSize", &mut region); // Backward compatibility.
+        let _ = self.runs.visit("Runs", &mut region); // Backward compatibility.
+        let _ = self.markup.visit("Markup", &mut region); // Backward compatibility.
+
+        Ok(())
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 struct Word {
     width: f32,
@@ -136,10 +503,108 @@ impl FormattedText {
         self
     }
 
+    /// Sets a font, that will be used for bold text. Regular font is used if there is no bold font.
+    pub fn set_bold_font(&mut self, font: Option<SharedFont>) -> &mut Self {
+        self.bold_font = font;
+        self
+    }
+
+    /// Sets a font, that will be used for italic text. Regular font is used if there is no italic
+    /// font.
+    pub fn set_italic_font(&mut self, font: Option<SharedFont>) -> &mut Self {
+        self.italic_font = font;
+        self
+    }
+
+    /// Sets a font, that will be used for bold italic text. Bold or italic font is used if there
+    /// is no bold italic font.
+    pub fn set_bold_italic_font(&mut self, font: Option<SharedFont>) -> &mut Self {
+        self.bold_italic_font = font;
+        self
+    }
+
+    /// Returns a font, that is used for the given font style.
+    pub fn font_of(&self, style: FontStyle) -> SharedFont {
+        match style {
+            FontStyle::Regular => None,
+            FontStyle::Bold => self.bold_font.clone(),
+            FontStyle::Italic => self.italic_font.clone(),
+            FontStyle::BoldItalic => self
+                .bold_italic_font
+                .clone()
+                .or_else(|| self.bold_font.clone())
+                .or_else(|| self.italic_font.clone()),
+        }
+        .unwrap_or_else(|| self.font.clone())
+    }
+
+    /// Adds a named image, that could be used in the markup using `[img=name]` tag.
+    pub fn add_inline_image(&mut self, name: &str, texture: SharedTexture) -> &mut Self {
+        self.images.insert(name.to_owned(), texture);
+        self
+    }
+
+    /// Removes a named image.
+    pub fn remove_inline_image(&mut self, name: &str) -> Option<SharedTexture> {
+        self.images.remove(name)
+    }
+
+    /// Enables or disables markup parsing. When enabled, [`FormattedText::set_text`] parses the
+    /// markup (see module docs) instead of using the text as is.
+    pub fn set_markup(&mut self, markup: bool) -> &mut Self {
+        self.markup = markup;
+        self
+    }
+
+    /// Returns true if markup parsing is enabled.
+    pub fn is_markup(&self) -> bool {
+        self.markup
+    }
+
+    /// Sets styled runs of the text. Runs must not overlap.
+    pub fn set_runs(&mut self, runs: Vec<TextRun>) -> &mut Self {
+        self.runs = runs;
+        self
+    }
+
+    /// Returns styled runs of the text.
+    pub fn runs(&self) -> &[TextRun] {
+        &self.runs
+    }
+
+    /// Returns a brush of the given run, or the brush of the text if there is no run or the run
+    /// has no brush.
+    pub fn run_brush(&self, run: Option<usize>) -> Brush {
+        run.and_then(|run| self.runs.get(run))
+            .and_then(|run| run.brush.clone())
+            .unwrap_or_else(|| self.brush.clone())
+    }
+
+    /// Returns underlines calculated by the last build.
+    pub fn get_underlines(&self) -> &[TextUnderline] {
+        &self.underlines
+    }
+
+    /// Sets size of the font, `None` means the default height of the font.
+    pub fn set_font_size(&mut self, font_size: Option<f32>) -> &mut Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Returns size of the font, `None` means the default height of the font.
+    pub fn font_size(&self) -> Option<f32> {
+        self.font_size
+    }
+
     pub fn get_lines(&self) -> &[TextLine] {
         &self.lines
     }
 
+    /// Returns advances of the characters (including kerning) calculated by the last build.
+    pub fn get_advances(&self) -> &[f32] {
+        &self.advances
+    }
+
     pub fn set_vertical_alignment(&mut self, vertical_alignment: VerticalAlignment) -> &mut Self {
         self.vertical_alignment = vertical_alignment;
         self
@@ -188,17 +653,35 @@ impl FormattedText {
 
     pub fn get_range_width<T: IntoIterator<Item = usize>>(&self, range: T) -> f32 {
         let mut width = 0.0;
-        let font = self.font.0.lock();
+        let mut font = self.font.0.lock();
+        let size = self.font_size.unwrap_or_else(|| font.height());
         for index in range {
             // We can't trust the range values, check to prevent panic.
-            if let Some(glyph) = self.text.get(index) {
-                width += font.glyph_advance(glyph.char_code);
+            if let Some(character) = self.text.get(index) {
+                width += font.glyph_advance_at(character.char_code, size);
+                if let Some(next) = self.text.get(index + 1) {
+                    width += font.kerning(character.char_code, next.char_code, size);
+                }
             }
         }
         width
     }
 
+    /// Sets new text. If markup is enabled (see [`FormattedText::set_markup`]), the text is parsed
+    /// as markup, otherwise the text is used as is and all runs are removed.
     pub fn set_text<P: AsRef<str>>(&mut self, text: P) -> &mut Self {
+        if self.markup {
+            let (text, runs) = parse_markup(text.as_ref());
+            self.set_plain_text(text);
+            self.runs = runs;
+        } else {
+            self.set_plain_text(text);
+            self.runs.clear();
+        }
+        self
+    }
+
+    fn set_plain_text<P: AsRef<str>>(&mut self, text: P) {
         // Convert text to UTF32.
         self.text.clear();
 
@@ -207,10 +690,18 @@ impl FormattedText {
         for code in text.as_ref().chars().map(|c| c as u32) {
             self.text.push(Character::from_char_with_font(code, &font));
         }
+    }
 
-        drop(font);
-
-        self
+    // Characters inserted at the end of a run continue the run (the same way as text editors do).
+    fn shift_runs(&mut self, position: usize, count: usize) {
+        for run in self.runs.iter_mut() {
+            if run.range.start >= position {
+                run.range.start += count;
+                run.range.end += count;
+            } else if run.range.end >= position && run.image.is_none() {
+                run.range.end += count;
+            }
+        }
     }
 
     pub fn set_wrap(&mut self, wrap: WrapMode) -> &mut Self {
@@ -255,38 +746,132 @@ impl FormattedText {
 
         drop(font);
 
+        self.shift_runs(index, 1);
+
         self
     }
 
     pub fn insert_str(&mut self, str: &str, position: usize) -> &mut Self {
         let font = self.font.0.lock();
 
+        let mut count = 0;
         for (i, code) in str.chars().enumerate() {
             self.text.insert(
                 position + i,
                 Character::from_char_with_font(code as u32, &font),
             );
+            count += 1;
         }
 
         drop(font);
 
+        self.shift_runs(position, count);
+
         self
     }
 
     pub fn remove_range(&mut self, range: Range<usize>) -> &mut Self {
-        self.text.drain(range);
+        self.text.drain(range.clone());
+
+        let removed = range.end - range.start;
+        let map = |index: usize| {
+            if index <= range.start {
+                index
+            } else if index >= range.end {
+                index - removed
+            } else {
+                range.start
+            }
+        };
+        for run in self.runs.iter_mut() {
+            run.range = map(run.range.start)..map(run.range.end);
+        }
+        self.runs.retain(|run| !run.range.is_empty());
+
         self
     }
 
     pub fn remove_at(&mut self, index: usize) -> &mut Self {
-        self.text.remove(index);
-        self
+        self.remove_range(index..index + 1)
     }
 
     pub fn build(&mut self) -> Vector2<f32> {
-        let font = self.font.0.lock();
+        let fonts = [
+            self.font.clone(),
+            self.font_of(FontStyle::Bold),
+            self.font_of(FontStyle::Italic),
+            self.font_of(FontStyle::BoldItalic),
+        ];
+        // The same font could be used for multiple styles, every font must be locked only once.
+        let mut slots = [0, 1, 2, 3];
+        for (i, slot) in slots.iter_mut().enumerate() {
+            *slot = fonts.iter().position(|font| *font == fonts[i]).unwrap_or(i);
+        }
+        let mut guards = fonts
+            .iter()
+            .enumerate()
+            .map(|(i, font)| {
+                if slots[i] == i {
+                    Some(font.0.lock())
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let default_size = self
+            .font_size
+            .unwrap_or_else(|| guards[0].as_ref().unwrap().height());
+        let default_ascender = guards[0].as_ref().unwrap().ascender_at(default_size);
+
+        // Runs are ignored for masked text.
+        let mut char_runs = vec![None; self.text.len()];
+        if self.mask_char.is_none() {
+            for (run_index, run) in self.runs.iter().enumerate() {
+                for char_run in char_runs
+                    .iter_mut()
+                    .take(run.range.end)
+                    .skip(run.range.start)
+                {
+                    *char_run = Some(run_index);
+                }
+            }
+        }
+
+        // Resolve style of every character and make sure that every glyph is in the atlas.
+        // Missing glyphs have invalid index.
+        let mut styles = Vec::with_capacity(self.text.len());
+        for (character, &run_index) in self.text.iter_mut().zip(char_runs.iter()) {
+            let run = run_index.map(|run| &self.runs[run]);
+            let font_style = run.map_or(FontStyle::Regular, |run| run.font_style);
+            let style = CharStyle {
+                run: run_index,
+                font_style,
+                slot: slots[font_style as usize],
+                size: run.and_then(|run| run.size).unwrap_or(default_size),
+                ascender: 0.0,
+                is_image: run.map_or(false, |run| run.image.is_some()),
+            };
+            let font = guards[style.slot].as_mut().unwrap();
+            if !style.is_image {
+                character.glyph_index = font
+                    .cache_glyph(character.char_code, style.size)
+                    .map_or(u32::MAX, |index| index as u32);
+            }
+            styles.push(CharStyle {
+                ascender: font.ascender_at(style.size),
+                ..style
+            });
+        }
+        if let Some(mask_char) = self.mask_char.as_mut() {
+            mask_char.glyph_index = guards[0]
+                .as_mut()
+                .unwrap()
+                .cache_glyph(mask_char.char_code, default_size)
+                .map_or(u32::MAX, |index| index as u32);
+        }
 
-        let masked_text;
+        let masked_text: Vec<Character>;
         let text = if let Some(mask_char) = self.mask_char {
             masked_text = (0..self.text.len()).map(|_| mask_char).collect();
             &masked_text
@@ -294,16 +879,35 @@ impl FormattedText {
             &self.text
         };
 
+        self.advances.clear();
+        for (i, (character, style)) in text.iter().zip(styles.iter()).enumerate() {
+            let font = guards[style.slot].as_ref().unwrap();
+            let mut advance = if style.is_image {
+                style.ascender
+            } else {
+                match font.glyphs().get(character.glyph_index as usize) {
+                    Some(glyph) => glyph.advance,
+                    None => style.size,
+                }
+            };
+            if let (Some(next), Some(next_style)) = (text.get(i + 1), styles.get(i + 1)) {
+                if next_style.slot == style.slot
+                    && next_style.size == style.size
+                    && !style.is_image
+                    && !next_style.is_image
+                {
+                    advance += font.kerning(character.char_code, next.char_code, style.size);
+                }
+            }
+            self.advances.push(advance);
+        }
+
         // Split on lines.
-        let mut total_height = 0.0;
         let mut current_line = TextLine::new();
         let mut word: Option<Word> = None;
         self.lines.clear();
         for (i, character) in text.iter().enumerate() {
-            let advance = match font.glyphs().get(character.glyph_index as usize) {
-                Some(glyph) => glyph.advance,
-                None => font.height(),
-            };
+            let advance = self.advances[i];
             let is_new_line =
                 character.char_code == u32::from(b'\n') || character.char_code == u32::from(b'\r');
             let new_width = current_line.width + advance;
@@ -335,7 +939,6 @@ impl FormattedText {
                 current_line.begin = if is_new_line { i + 1 } else { i };
                 current_line.end = current_line.begin;
                 current_line.width = advance;
-                total_height += font.ascender();
             } else {
                 match self.wrap {
                     WrapMode::NoWrap => {
@@ -348,7 +951,6 @@ impl FormattedText {
                             current_line.begin = if is_new_line { i + 1 } else { i };
                             current_line.end = current_line.begin + 1;
                             current_line.width = advance;
-                            total_height += font.ascender();
                         } else {
                             current_line.width = new_width;
                             current_line.end += 1;
@@ -365,7 +967,6 @@ impl FormattedText {
                                     self.lines.push(current_line);
                                     current_line.begin = current_line.end;
                                     current_line.width = 0.0;
-                                    total_height += font.ascender();
                                 } else if current_line.width + word.width > self.constraint.x {
                                     // The word will exceed horizontal constraint, we have to
                                     // commit current line and move the word in the next line.
@@ -373,7 +974,6 @@ impl FormattedText {
                                     current_line.begin = i - word.length;
                                     current_line.end = i;
                                     current_line.width = word.width;
-                                    total_height += font.ascender();
                                 } else {
                                     // The word does not exceed horizontal constraint, append it
                                     // to the line.
@@ -394,16 +994,26 @@ impl FormattedText {
         }
         // Commit rest of text.
         if current_line.begin != current_line.end {
-            for character in text.iter().skip(current_line.end) {
-                let advance = match font.glyphs().get(character.glyph_index as usize) {
-                    Some(glyph) => glyph.advance,
-                    None => font.height(),
-                };
+            for advance in self.advances.iter().skip(current_line.end) {
                 current_line.width += advance;
             }
             current_line.end = self.text.len();
             self.lines.push(current_line);
-            total_height += font.ascender();
+        }
+
+        // Height of a line is defined by the highest character in it.
+        let mut total_height = 0.0;
+        for line in self.lines.iter_mut() {
+            let begin = line.begin.min(styles.len());
+            let end = line.end.min(styles.len());
+            line.height = styles[begin..end]
+                .iter()
+                .map(|style| style.ascender)
+                .fold(None, |height: Option<f32>, ascender| {
+                    Some(height.map_or(ascender, |height| height.max(ascender)))
+                })
+                .unwrap_or(default_ascender);
+            total_height += line.height;
         }
 
         // Align lines according to desired alignment.
@@ -430,6 +1040,7 @@ impl FormattedText {
 
         // Generate glyphs for each text line.
         self.glyphs.clear();
+        self.underlines.clear();
 
         let cursor_y_start = match self.vertical_alignment {
             VerticalAlignment::Top => 0.0,
@@ -459,50 +1070,104 @@ impl FormattedText {
         let mut cursor = Vector2::new(cursor_x_start, cursor_y_start);
         for line in self.lines.iter_mut() {
             cursor.x = line.x_offset;
-
-            for &character in text.iter().take(line.end).skip(line.begin) {
-                match font.glyphs().get(character.glyph_index as usize) {
-                    Some(glyph) => {
-                        // Insert glyph
-                        let rect = Rect::new(
-                            cursor.x + glyph.left.floor(),
-                            cursor.y + font.ascender().floor()
-                                - glyph.top.floor()
-                                - glyph.bitmap_height as f32,
-                            glyph.bitmap_width as f32,
-                            glyph.bitmap_height as f32,
-                        );
-                        let text_glyph = TextGlyph {
-                            bounds: rect,
-                            tex_coords: glyph.tex_coords,
-                        };
-                        self.glyphs.push(text_glyph);
-
-                        cursor.x += glyph.advance;
-                    }
-                    None => {
-                        // Insert invalid symbol
-                        let rect = Rect::new(
+            let baseline = cursor.y + line.height.floor();
+
+            for (i, (character, style)) in text
+                .iter()
+                .zip(styles.iter())
+                .enumerate()
+                .take(line.end)
+                .skip(line.begin)
+            {
+                let advance = self.advances[i];
+
+                if style.is_image {
+                    let image = style
+                        .run
+                        .and_then(|run| self.runs[run].image.as_ref())
+                        .and_then(|name| self.images.get(name))
+                        .cloned();
+                    self.glyphs.push(TextGlyph {
+                        bounds: Rect::new(
                             cursor.x,
-                            cursor.y + font.ascender(),
-                            font.height(),
-                            font.height(),
-                        );
-                        self.glyphs.push(TextGlyph {
-                            bounds: rect,
-                            tex_coords: [Vector2::default(); 4],
-                        });
-                        cursor.x += rect.w();
+                            baseline - style.ascender,
+                            style.ascender,
+                            style.ascender,
+                        ),
+                        atlas_bounds: None,
+                        font_style: style.font_style,
+                        run: style.run,
+                        image,
+                    });
+                } else {
+                    let font = guards[style.slot].as_ref().unwrap();
+                    match font.glyphs().get(character.glyph_index as usize) {
+                        Some(glyph) => {
+                            // Insert glyph
+                            let rect = Rect::new(
+                                cursor.x + glyph.left.floor(),
+                                baseline - glyph.top.floor() - glyph.bitmap_height as f32,
+                                glyph.bitmap_width as f32,
+                                glyph.bitmap_height as f32,
+                            );
+                            let text_glyph = TextGlyph {
+                                bounds: rect,
+                                atlas_bounds: Some(Rect::new(
+                                    glyph.atlas_position.x as f32,
+                                    glyph.atlas_position.y as f32,
+                                    glyph.bitmap_width as f32,
+                                    glyph.bitmap_height as f32,
+                                )),
+                                font_style: style.font_style,
+                                run: style.run,
+                                image: None,
+                            };
+                            self.glyphs.push(text_glyph);
+                        }
+                        None => {
+                            // Insert invalid symbol
+                            let rect = Rect::new(cursor.x, baseline, style.size, style.size);
+                            self.glyphs.push(TextGlyph {
+                                bounds: rect,
+                                atlas_bounds: None,
+                                font_style: style.font_style,
+                                run: style.run,
+                                image: None,
+                            });
+                        }
+                    }
+                }
+
+                if let Some(run) = style.run.filter(|run| self.runs[*run].underline) {
+                    let thickness = (style.size / 16.0).max(1.0);
+                    let y = baseline + thickness;
+                    match self.underlines.last_mut() {
+                        Some(last)
+                            if last.run == run
+                                && last.bounds.y() == y
+                                && (last.bounds.x() + last.bounds.w() - cursor.x).abs()
+                                    < f32::EPSILON =>
+                        {
+                            last.bounds.size.x += advance;
+                        }
+                        _ => self.underlines.push(TextUnderline {
+                            bounds: Rect::new(cursor.x, y, advance, thickness),
+                            run,
+                        }),
                     }
                 }
+
+                cursor.x += advance;
             }
-            line.height = font.ascender();
             line.y_offset = cursor.y;
-            cursor.y += font.ascender();
+            cursor.y += line.height;
         }
 
         // Minus here is because descender has negative value.
-        let mut full_size = Vector2::new(0.0, total_height - font.descender());
+        let mut full_size = Vector2::new(
+            0.0,
+            total_height - guards[0].as_ref().unwrap().descender_at(default_size),
+        );
         for line in self.lines.iter() {
             full_size.x = line.width.max(full_size.x);
         }
@@ -510,8 +1175,25 @@ impl FormattedText {
     }
 }
 
+#[derive(Copy, Clone)]
+struct CharStyle {
+    run: Option<usize>,
+    font_style: FontStyle,
+    // Index of the locked font.
+    slot: usize,
+    size: f32,
+    ascender: f32,
+    is_image: bool,
+}
+
 pub struct FormattedTextBuilder {
     font: SharedFont,
+    font_size: Option<f32>,
+    bold_font: Option<SharedFont>,
+    italic_font: Option<SharedFont>,
+    bold_italic_font: Option<SharedFont>,
+    images: FxHashMap<String, SharedTexture>,
+    markup: bool,
     brush: Brush,
     constraint: Vector2<f32>,
     text: String,
@@ -530,6 +1212,12 @@ impl FormattedTextBuilder {
     pub fn new(font: SharedFont) -> FormattedTextBuilder {
         FormattedTextBuilder {
             font,
+            font_size: None,
+            bold_font: None,
+            italic_font: None,
+            bold_italic_font: None,
+            images: Default::default(),
+            markup: false,
             text: "".to_owned(),
             horizontal_alignment: HorizontalAlignment::Left,
             vertical_alignment: VerticalAlignment::Top,
@@ -544,6 +1232,42 @@ impl FormattedTextBuilder {
         }
     }
 
+    /// Sets desired size of the font, `None` means the default height of the font.
+    pub fn with_font_size(mut self, font_size: Option<f32>) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets a font, that will be used for bold text.
+    pub fn with_bold_font(mut self, font: Option<SharedFont>) -> Self {
+        self.bold_font = font;
+        self
+    }
+
+    /// Sets a font, that will be used for italic text.
+    pub fn with_italic_font(mut self, font: Option<SharedFont>) -> Self {
+        self.italic_font = font;
+        self
+    }
+
+    /// Sets a font, that will be used for bold italic text.
+    pub fn with_bold_italic_font(mut self, font: Option<SharedFont>) -> Self {
+        self.bold_italic_font = font;
+        self
+    }
+
+    /// Adds a named image, that could be used in the markup using `[img=name]` tag.
+    pub fn with_inline_image(mut self, name: &str, texture: SharedTexture) -> Self {
+        self.images.insert(name.to_owned(), texture);
+        self
+    }
+
+    /// Enables or disables markup parsing, see [`FormattedText::set_markup`].
+    pub fn with_markup(mut self, markup: bool) -> Self {
+        self.markup = markup;
+        self
+    }
+
     pub fn with_vertical_alignment(mut self, vertical_alignment: VerticalAlignment) -> Self {
         self.vertical_alignment = vertical_alignment;
         self
@@ -605,31 +1329,290 @@ impl FormattedTextBuilder {
     }
 
     pub fn build(self) -> FormattedText {
-        let font = self.font.0.lock();
-        FormattedText {
-            text: self
-                .text
-                .chars()
-                .map(|c| Character::from_char_with_font(c as u32, &font))
-                .collect(),
+        let mask_char = {
+            let font = self.font.0.lock();
+            self.mask_char
+                .map(|code| Character::from_char_with_font(u32::from(code), &font))
+        };
+        let mut formatted_text = FormattedText {
+            text: Vec::new(),
             lines: Vec::new(),
             glyphs: Vec::new(),
+            underlines: Vec::new(),
+            runs: Vec::new(),
+            markup: self.markup,
+            bold_font: self.bold_font,
+            italic_font: self.italic_font,
+            bold_italic_font: self.bold_italic_font,
+            images: self.images,
+            advances: Vec::new(),
+            font_size: self.font_size,
             vertical_alignment: self.vertical_alignment,
             horizontal_alignment: self.horizontal_alignment,
             brush: self.brush,
             constraint: self.constraint,
             wrap: self.wrap,
-            mask_char: self
-                .mask_char
-                .map(|code| Character::from_char_with_font(u32::from(code), &font)),
+            mask_char,
             shadow: self.shadow,
             shadow_brush: self.shadow_brush,
-            font: {
-                drop(font);
-                self.font
-            },
+            font: self.font,
             shadow_dilation: self.shadow_dilation,
             shadow_offset: self.shadow_offset,
-        }
+        };
+        formatted_text.set_text(self.text);
+        formatted_text
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        brush::Brush,
+        core::algebra::Vector2,
+        core::color::Color,
+        formatted_text::{
+            parse_markup, FontStyle, FormattedTextBuilder, TextRun, WrapMode, IMAGE_PLACEHOLDER,
+        },
+        ttf::{FontBuilder, SharedFont},
+    };
+
+    fn test_font() -> SharedFont {
+        SharedFont::new(
+            FontBuilder::new()
+                .build_from_memory(include_bytes!("../test_data/DejaVuSerif.ttf").as_slice())
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_kerning() {
+        let font = test_font();
+        let mut text = FormattedTextBuilder::new(font.clone())
+            .with_text("AV".to_owned())
+            .with_constraint(Vector2::new(f32::INFINITY, f32::INFINITY))
+            .build();
+        let size = text.build();
+
+        let font = font.0.lock();
+        let a = font.glyph_advance('A' as u32);
+        let v = font.glyph_advance('V' as u32);
+        let kerning = font.kerning('A' as u32, 'V' as u32, font.height());
+        assert!(kerning < 0.0);
+        assert_eq!(text.get_advances(), &[a + kerning, v]);
+        assert_eq!(size.x, a + v + kerning);
+        // Second glyph is shifted by kerning.
+        let glyphs = text.get_glyphs();
+        assert_eq!(
+            glyphs[1].get_bounds().x(),
+            a + kerning + font.glyph('V' as u32).unwrap().left.floor()
+        );
+    }
+
+    #[test]
+    fn test_font_size() {
+        let font = SharedFont::new(FontBuilder::new().build_builtin().unwrap());
+        let mut small = FormattedTextBuilder::new(font.clone())
+            .with_text("Hello".to_owned())
+            .build();
+        let mut large = FormattedTextBuilder::new(font.clone())
+            .with_text("Hello".to_owned())
+            .with_font_size(Some(32.0))
+            .build();
+
+        let small_size = small.build();
+        let large_size = large.build();
+        assert!(large_size.x > small_size.x * 1.5);
+        assert!(large_size.y > small_size.y * 1.5);
+        assert_eq!(large.get_lines()[0].height, font.0.lock().ascender_at(32.0));
+    }
+
+    #[test]
+    fn test_missing_glyphs() {
+        let font = SharedFont::new(FontBuilder::new().build_builtin().unwrap());
+        let mut text = FormattedTextBuilder::new(font.clone())
+            .with_text("AЯ".to_owned())
+            .with_wrap(WrapMode::NoWrap)
+            .build();
+        text.build();
+        // Missing glyph is rendered as a stub.
+        assert_eq!(text.get_advances()[1], font.0.lock().height());
+        assert_eq!(
+            text.get_glyphs()[1].get_tex_coords(256),
+            [Vector2::default(); 4]
+        );
+
+        font.0.lock().add_fallback(&test_font().0.lock());
+        text.build();
+        assert_ne!(
+            text.get_glyphs()[1].get_tex_coords(256),
+            [Vector2::default(); 4]
+        );
+    }
+
+    #[test]
+    fn test_parse_markup() {
+        let (text, runs) =
+            parse_markup("[b]Bold[/b] [color=#FF0000]red [i]both[/i][/color] [[x] [unknown]");
+        assert_eq!(text, "Bold red both [x] [unknown]");
+        assert_eq!(
+            runs,
+            vec![
+                TextRun {
+                    range: 0..4,
+                    font_style: FontStyle::Bold,
+                    ..Default::default()
+                },
+                TextRun {
+                    range: 5..9,
+                    brush: Some(Brush::Solid(Color::opaque(255, 0, 0))),
+                    ..Default::default()
+                },
+                TextRun {
+                    range: 9..13,
+                    brush: Some(Brush::Solid(Color::opaque(255, 0, 0))),
+                    font_style: FontStyle::Italic,
+                    ..Default::default()
+                },
+            ]
+        );
+
+        // Closing tag restores the style before the opening tag, even if tags are not nested
+        // properly.
+        let (text, runs) = parse_markup("[u][size=20]a[/u]b[img=icon][/size]");
+        assert_eq!(text, format!("ab{}[/size]", IMAGE_PLACEHOLDER));
+        assert_eq!(
+            runs,
+            vec![
+                TextRun {
+                    range: 0..1,
+                    size: Some(20.0),
+                    underline: true,
+                    ..Default::default()
+                },
+                TextRun {
+                    range: 2..3,
+                    image: Some("icon".to_owned()),
+                    ..Default::default()
+                },
+            ]
+        );
+
+        // Malformed tags are kept as is.
+        let (text, runs) = parse_markup("[color=red]a[size=-1]b[b");
+        assert_eq!(text, "[color=red]a[size=-1]b[b");
+        assert!(runs.is_empty());
+    }
+
+    #[test]
+    fn test_rich_text_layout() {
+        let font = SharedFont::new(FontBuilder::new().build_builtin().unwrap());
+        let bold = test_font();
+        let mut text = FormattedTextBuilder::new(font.clone())
+            .with_bold_font(Some(bold.clone()))
+            .with_markup(true)
+            .with_text("small [b][size=32]LARGE[/size][/b]\nnext".to_owned())
+            .with_constraint(Vector2::new(f32::INFINITY, f32::INFINITY))
+            .build();
+        assert_eq!(text.text(), "small LARGE\nnext");
+        text.build();
+
+        let lines = text.get_lines();
+        assert_eq!(lines.len(), 2);
+        // The first line is as high as its largest characters.
+        assert_eq!(lines[0].height, bold.0.lock().ascender_at(32.0));
+        assert_eq!(lines[1].height, font.0.lock().ascender());
+        assert_eq!(lines[1].y_offset, lines[0].height);
+
+        let glyphs = text.get_glyphs();
+        assert_eq!(glyphs[0].font_style(), FontStyle::Regular);
+        assert_eq!(glyphs[6].font_style(), FontStyle::Bold);
+        assert_eq!(glyphs[6].run(), Some(0));
+        let mut bold_font = bold.0.lock();
+        let advance = bold_font.glyph_advance_at('L' as u32, 32.0);
+        assert_eq!(
+            text.get_advances()[6],
+            advance + bold_font.kerning('L' as u32, 'A' as u32, 32.0)
+        );
+        drop(bold_font);
+        assert_eq!(text.font_of(FontStyle::Bold), bold);
+        assert_eq!(text.font_of(FontStyle::Italic), font);
+    }
+
+    #[test]
+    fn test_rich_text_word_wrap() {
+        let font = SharedFont::new(FontBuilder::new().build_builtin().unwrap());
+        let mut text = FormattedTextBuilder::new(font)
+            .with_markup(true)
+            .with_text("[color=#00FF00]aaa bbb[/color] [size=24]ccc[/size] ".to_owned())
+            .with_wrap(WrapMode::Word)
+            .build();
+        text.set_constraint(Vector2::new(
+            text.get_range_width(0..7) + 1.0,
+            f32::INFINITY,
+        ));
+        text.build();
+
+        let lines = text.get_lines();
+        assert_eq!(lines.len(), 2);
+        assert_eq!((lines[0].begin, lines[0].end), (0, 8));
+        assert_eq!((lines[1].begin, lines[1].end), (8, 12));
+        // Every glyph keeps its style after wrapping.
+        let glyphs = text.get_glyphs();
+        assert_eq!(
+            text.run_brush(glyphs[4].run()),
+            Brush::Solid(Color::opaque(0, 255, 0))
+        );
+        assert_eq!(glyphs[8].run(), Some(1));
+        // Wrapped word starts at the beginning of the next line.
+        assert!(glyphs[8].get_bounds().x() < text.get_advances()[8]);
+        assert!(glyphs[8].get_bounds().y() > glyphs[0].get_bounds().y());
+    }
+
+    #[test]
+    fn test_runs_editing() {
+        let font = SharedFont::new(FontBuilder::new().build_builtin().unwrap());
+        let mut text = FormattedTextBuilder::new(font)
+            .with_markup(true)
+            .with_text("a[b]bc[/b]d[img=icon]".to_owned())
+            .build();
+        assert_eq!(text.runs()[0].range, 1..3);
+        assert_eq!(text.runs()[1].range, 4..5);
+
+        // Typing at the end of a run continues the run.
+        text.insert_char('x', 3);
+        assert_eq!(text.runs()[0].range, 1..4);
+        assert_eq!(text.runs()[1].range, 5..6);
+
+        text.insert_str("yy", 0);
+        assert_eq!(text.runs()[0].range, 3..6);
+        assert_eq!(text.runs()[1].range, 7..8);
+
+        text.remove_range(4..8);
+        assert_eq!(text.text(), "yyab");
+        assert_eq!(text.runs().len(), 1);
+        assert_eq!(text.runs()[0].range, 3..4);
+
+        // Plain text removes all runs.
+        text.set_markup(false);
+        text.set_text("[b]plain[/b]");
+        assert_eq!(text.text(), "[b]plain[/b]");
+        assert!(text.runs().is_empty());
+    }
+
+    #[test]
+    fn test_underline() {
+        let font = SharedFont::new(FontBuilder::new().build_builtin().unwrap());
+        let mut text = FormattedTextBuilder::new(font)
+            .with_markup(true)
+            .with_text("a [u]under line[/u] b".to_owned())
+            .with_constraint(Vector2::new(f32::INFINITY, f32::INFINITY))
+            .build();
+        text.build();
+
+        let underlines = text.get_underlines();
+        assert_eq!(underlines.len(), 1);
+        let bounds = underlines[0].get_bounds();
+        assert_eq!(bounds.x(), text.get_range_width(0..2));
+        assert!((bounds.w() - text.get_advances()[2..12].iter().sum::<f32>()).abs() < 0.001);
     }
 }
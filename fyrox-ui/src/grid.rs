@@ -1,5 +1,13 @@
 use crate::{
-    core::{algebra::Vector2, math::Rect, pool::Handle, scope_profile},
+    core::{
+        algebra::Vector2,
+        math::Rect,
+        pool::Handle,
+        scope_profile,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     draw::{CommandTexture, Draw, DrawingContext},
     message::UiMessage,
     widget::{Widget, WidgetBuilder},
@@ -11,14 +19,15 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Default, Visit)]
 pub enum SizeMode {
+    #[default]
     Strict,
     Auto,
     Stretch,
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Default, Visit)]
 pub struct GridDimension {
     pub size_mode: SizeMode,
     pub desired_size: f32,
@@ -53,19 +62,41 @@ pub type Column = GridDimension;
 pub type Row = GridDimension;
 
 /// Automatically arranges children by rows and columns
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct Grid {
     pub widget: Widget,
     pub rows: RefCell<Vec<Row>>,
     pub columns: RefCell<Vec<Column>>,
     pub draw_border: bool,
     pub border_thickness: f32,
+    #[visit(skip)]
     pub cells: RefCell<Vec<Cell>>,
+    #[visit(skip)]
     pub groups: RefCell<[Vec<usize>; 4]>,
 }
 
 crate::define_widget_deref!(Grid);
 
+impl Default for Grid {
+    fn default() -> Self {
+        Self {
+            widget: WidgetBuilder::new().build(),
+            rows: Default::default(),
+            columns: Default::default(),
+            draw_border: false,
+            border_thickness: 1.0,
+            cells: Default::default(),
+            groups: Default::default(),
+        }
+    }
+}
+
+impl TypeUuidProvider for Grid {
+    fn type_uuid() -> Uuid {
+        uuid!("4415eb65-1f3e-4c62-8e9a-aa6f29b9032c")
+    }
+}
+
 #[derive(Clone)]
 pub struct Cell {
     pub nodes: Vec<Handle<UiNode>>,
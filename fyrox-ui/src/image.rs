@@ -1,7 +1,15 @@
 use crate::{
     brush::Brush,
     color::draw_checker_board,
-    core::{algebra::Vector2, color::Color, math::Rect, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        math::Rect,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext, SharedTexture},
     message::{MessageDirection, UiMessage},
@@ -28,9 +36,10 @@ impl ImageMessage {
     define_constructor!(ImageMessage:CheckerboardBackground => fn checkerboard_background(bool), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct Image {
     pub widget: Widget,
+    #[visit(skip)]
     pub texture: Option<SharedTexture>,
     pub flip: bool,
     pub uv_rect: Rect<f32>,
@@ -39,6 +48,24 @@ pub struct Image {
 
 crate::define_widget_deref!(Image);
 
+impl Default for Image {
+    fn default() -> Self {
+        Self {
+            widget: WidgetBuilder::new().build(),
+            texture: None,
+            flip: false,
+            uv_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+            checkerboard_background: false,
+        }
+    }
+}
+
+impl TypeUuidProvider for Image {
+    fn type_uuid() -> Uuid {
+        uuid!("c42ddcb9-e0d8-4d47-ba4e-1ac4e0fcdd75")
+    }
+}
+
 impl Control for Image {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -21,9 +21,12 @@ pub mod button;
 pub mod canvas;
 pub mod check_box;
 pub mod color;
+pub mod constructor;
 pub mod curve;
+pub mod data_grid;
 pub mod decorator;
 pub mod dock;
+pub mod document;
 pub mod draw;
 pub mod dropdown_list;
 pub mod expander;
@@ -37,6 +40,7 @@ pub mod list_view;
 pub mod menu;
 pub mod message;
 pub mod messagebox;
+pub mod navigation;
 pub mod numeric;
 pub mod popup;
 pub mod progress_bar;
@@ -47,11 +51,13 @@ pub mod scroll_panel;
 pub mod scroll_viewer;
 pub mod searchbar;
 pub mod stack_panel;
+pub mod style;
 pub mod tab_control;
 pub mod text;
 pub mod text_box;
 pub mod tree;
 pub mod ttf;
+pub mod tween;
 pub mod utils;
 pub mod uuid;
 pub mod vec;
@@ -63,20 +69,26 @@ pub mod wrap_panel;
 use crate::{
     brush::Brush,
     canvas::Canvas,
+    constructor::WidgetConstructorContainer,
     core::{
         algebra::{Matrix3, Vector2},
         color::Color,
         math::Rect,
         pool::{Handle, Pool},
         scope_profile,
+        uuid::Uuid,
+        visitor::prelude::*,
     },
     draw::{CommandTexture, Draw, DrawingContext},
     message::{
-        ButtonState, CursorIcon, KeyboardModifiers, MessageDirection, MouseButton, OsEvent,
-        UiMessage,
+        ButtonState, CursorIcon, KeyCode, KeyboardModifiers, MessageDirection, MouseButton,
+        OsEvent, UiMessage,
     },
+    navigation::NavigationDirection,
     popup::{Placement, PopupMessage},
+    style::{Style, Theme},
     ttf::{Font, FontBuilder, SharedFont},
+    tween::{Storyboard, StoryboardMessage},
     widget::{Widget, WidgetBuilder, WidgetMessage},
 };
 use copypasta::ClipboardContext;
@@ -118,7 +130,7 @@ pub const BRUSH_BRIGHT_BLUE: Brush = Brush::Solid(COLOR_BRIGHT_BLUE);
 pub const BRUSH_TEXT: Brush = Brush::Solid(COLOR_TEXT);
 pub const BRUSH_FOREGROUND: Brush = Brush::Solid(COLOR_FOREGROUND);
 
-#[derive(Copy, Clone, PartialEq, Debug, Eq)]
+#[derive(Copy, Clone, PartialEq, Debug, Eq, Visit)]
 pub enum HorizontalAlignment {
     Stretch,
     Left,
@@ -126,7 +138,7 @@ pub enum HorizontalAlignment {
     Right,
 }
 
-#[derive(Copy, Clone, PartialEq, Debug, Eq)]
+#[derive(Copy, Clone, PartialEq, Debug, Eq, Visit)]
 pub enum VerticalAlignment {
     Stretch,
     Top,
@@ -134,7 +146,7 @@ pub enum VerticalAlignment {
     Bottom,
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug, Visit)]
 pub struct Thickness {
     pub left: f32,
     pub top: f32,
@@ -198,7 +210,7 @@ impl Deref for RcUiNodeHandle {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Visit)]
 pub enum Orientation {
     Vertical,
     Horizontal,
@@ -451,6 +463,13 @@ pub trait Control: BaseControl + Deref<Target = Widget> + DerefMut {
 
     fn on_remove(&self, #[allow(unused_variables)] sender: &Sender<UiMessage>) {}
 
+    /// Applies the given (resolved) style to the widget. Default implementation applies common properties
+    /// of a widget (see [`Widget::apply_style`]), controls with additional visual properties should override
+    /// it and call the default implementation of their inner widget. See [`crate::style`] for more info.
+    fn apply_style(&mut self, style: &Style) {
+        self.deref_mut().apply_style(style)
+    }
+
     fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
         scope_profile!();
 
@@ -607,6 +626,53 @@ impl UiNode {
     }
 }
 
+fn widget_constructors(visitor: &Visitor) -> Result<&WidgetConstructorContainer, VisitError> {
+    visitor
+        .blackboard
+        .get::<WidgetConstructorContainer>()
+        .ok_or_else(|| {
+            VisitError::User(
+                "Visitor environment must contain widget constructor container!".to_string(),
+            )
+        })
+}
+
+impl Visit for UiNode {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        let visit = if region.is_reading() {
+            let mut type_uuid = Uuid::default();
+            type_uuid.visit("TypeUuid", &mut region)?;
+
+            let constructors = widget_constructors(&region)?;
+            let unknown_type =
+                || VisitError::User(format!("Unknown widget type uuid {}!", type_uuid));
+            *self = constructors
+                .try_create(&type_uuid)
+                .ok_or_else(unknown_type)?;
+            constructors
+                .visitor_of(&type_uuid)
+                .ok_or_else(unknown_type)?
+        } else {
+            let constructors = widget_constructors(&region)?;
+            let mut type_uuid = constructors.type_uuid_of(self).ok_or_else(|| {
+                VisitError::User(format!(
+                    "Widget of type {} is not registered in widget constructor container!",
+                    self.type_name()
+                ))
+            })?;
+            let visit = constructors.visitor_of(&type_uuid).unwrap();
+
+            type_uuid.visit("TypeUuid", &mut region)?;
+
+            visit
+        };
+
+        visit(self, "WidgetData", &mut region)
+    }
+}
+
 pub struct BuildContext<'a> {
     ui: &'a mut UserInterface,
 }
@@ -703,6 +769,7 @@ pub enum LayoutEvent {
     MeasurementInvalidated(Handle<UiNode>),
     ArrangementInvalidated(Handle<UiNode>),
     VisibilityChanged(Handle<UiNode>),
+    TransformChanged(Handle<UiNode>),
 }
 
 #[derive(Clone, Debug)]
@@ -738,8 +805,16 @@ pub struct UserInterface {
     layout_events_sender: Sender<LayoutEvent>,
     need_update_global_transform: bool,
     pub default_font: SharedFont,
+    theme: Theme,
     double_click_entries: FxHashMap<MouseButton, DoubleClickEntry>,
     pub double_click_time_slice: f32,
+    keyboard_navigation: bool,
+    /// `true` if the focused widget has received focus by navigation and the focus visual should be
+    /// drawn around it.
+    focus_visual_visible: bool,
+    /// A brush that is used to draw a frame around a widget that has received focus by navigation.
+    pub focus_visual_brush: Brush,
+    storyboards: Pool<Storyboard>,
 }
 
 fn is_on_screen(node: &UiNode, nodes: &Pool<UiNode>) -> bool {
@@ -826,6 +901,16 @@ fn is_node_enabled(nodes: &Pool<UiNode>, handle: Handle<UiNode>) -> bool {
     enabled
 }
 
+/// Resolves the style of the node from the theme, properties that were set explicitly on the node are
+/// excluded from the resulting style.
+fn resolve_style(theme: &Theme, node: &UiNode) -> Option<Style> {
+    let mut style = theme.resolve(node.style()?)?;
+    for property in node.style_overrides.iter() {
+        style.remove(property);
+    }
+    Some(style)
+}
+
 impl UserInterface {
     pub fn new(screen_size: Vector2<f32>) -> UserInterface {
         let (sender, receiver) = mpsc::channel();
@@ -858,8 +943,13 @@ impl UserInterface {
             layout_events_sender,
             need_update_global_transform: Default::default(),
             default_font,
+            theme: Default::default(),
             double_click_entries: Default::default(),
             double_click_time_slice: 0.5, // 500 ms is standard in most operating systems.
+            keyboard_navigation: false,
+            focus_visual_visible: false,
+            focus_visual_brush: BRUSH_BRIGHT_BLUE,
+            storyboards: Pool::new(),
         };
         ui.root_canvas = ui.add_node(UiNode::new(Canvas::new(WidgetBuilder::new().build())));
         ui.keyboard_focus_node = ui.root_canvas;
@@ -870,6 +960,30 @@ impl UserInterface {
         self.keyboard_modifiers
     }
 
+    /// Returns current theme of the user interface.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Sets new theme of the user interface and re-applies styles of every widget that has a style.
+    /// See [`crate::style`] for more info.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+
+        for node in self.nodes.iter_mut() {
+            if let Some(style) = resolve_style(&self.theme, node) {
+                node.apply_style(&style);
+            }
+        }
+    }
+
+    fn apply_style(&mut self, node_handle: Handle<UiNode>) {
+        let node = &mut self.nodes[node_handle];
+        if let Some(style) = resolve_style(&self.theme, node) {
+            node.apply_style(&style);
+        }
+    }
+
     pub fn build_ctx(&mut self) -> BuildContext<'_> {
         BuildContext { ui: self }
     }
@@ -1005,6 +1119,9 @@ impl UserInterface {
                 LayoutEvent::VisibilityChanged(node) => {
                     self.update_global_visibility(node);
                 }
+                LayoutEvent::TransformChanged(_) => {
+                    self.need_update_global_transform = true;
+                }
             }
         }
     }
@@ -1043,6 +1160,8 @@ impl UserInterface {
             node.update(dt, &sender)
         }
 
+        self.update_storyboards(dt);
+
         self.update_tooltips(dt);
 
         if !self.drag_context.is_dragging {
@@ -1061,6 +1180,45 @@ impl UserInterface {
         }
     }
 
+    fn update_storyboards(&mut self, dt: f32) {
+        let mut storyboards = std::mem::take(&mut self.storyboards);
+
+        for (handle, storyboard) in storyboards.pair_iter_mut() {
+            if storyboard.update(dt, self) {
+                self.send_message(StoryboardMessage::completed(
+                    storyboard.owner(),
+                    MessageDirection::FromWidget,
+                    handle,
+                ));
+            }
+        }
+
+        storyboards.retain(|storyboard| !storyboard.is_finished());
+
+        self.storyboards = storyboards;
+    }
+
+    /// Starts playing the given storyboard, it will be advanced on every [`Self::update`] call. See
+    /// [`crate::tween`] module docs for more info.
+    pub fn play_storyboard(&mut self, storyboard: Storyboard) -> Handle<Storyboard> {
+        self.storyboards.spawn(storyboard)
+    }
+
+    /// Stops the storyboard, animated properties keep their current values.
+    pub fn stop_storyboard(&mut self, handle: Handle<Storyboard>) {
+        if self.storyboards.is_valid_handle(handle) {
+            self.storyboards.free(handle);
+        }
+    }
+
+    pub fn is_storyboard_playing(&self, handle: Handle<Storyboard>) -> bool {
+        self.storyboards.is_valid_handle(handle)
+    }
+
+    pub fn storyboard(&self, handle: Handle<Storyboard>) -> Option<&Storyboard> {
+        self.storyboards.try_borrow(handle)
+    }
+
     pub fn cursor(&self) -> CursorIcon {
         self.cursor_icon
     }
@@ -1096,6 +1254,24 @@ impl UserInterface {
             }
         }
 
+        if self.focus_visual_visible
+            && self.keyboard_focus_node.is_some()
+            && self.keyboard_focus_node != self.root_canvas
+        {
+            if let Some(node) = self.nodes.try_borrow(self.keyboard_focus_node) {
+                if node.is_globally_visible() {
+                    let bounds = node.screen_bounds().inflate(1.0, 1.0);
+                    self.drawing_context.push_rect(&bounds, 2.0);
+                    self.drawing_context.commit(
+                        bounds,
+                        self.focus_visual_brush.clone(),
+                        CommandTexture::None,
+                        None,
+                    );
+                }
+            }
+        }
+
         // Debug info rendered on top of other.
         if self.visual_debug {
             if self.picked_node.is_some() {
@@ -1686,6 +1862,15 @@ impl UserInterface {
                                 self.request_focus(self.root_canvas);
                             }
                         }
+                        &WidgetMessage::Navigate(direction)
+                            if !message.handled()
+                                && message.direction() == MessageDirection::ToWidget =>
+                        {
+                            self.navigate(message.destination(), direction);
+                        }
+                        WidgetMessage::KeyDown(_) if self.keyboard_navigation => {
+                            self.map_key_to_navigation(&message);
+                        }
                         WidgetMessage::Topmost => {
                             if message.destination().is_some() {
                                 self.make_topmost(message.destination());
@@ -1736,6 +1921,17 @@ impl UserInterface {
                                 node.set_tooltip(tooltip.clone());
                             }
                         }
+                        WidgetMessage::Style(style)
+                            if message.destination().is_some()
+                                && message.direction() == MessageDirection::ToWidget =>
+                        {
+                            let node = self.nodes.borrow_mut(message.destination());
+                            if node.style != *style {
+                                node.style = style.clone();
+                                self.apply_style(message.destination());
+                                self.send_message(message.reverse());
+                            }
+                        }
                         WidgetMessage::Center => {
                             if message.destination().is_some() {
                                 let node = self.node(message.destination());
@@ -1929,6 +2125,68 @@ impl UserInterface {
         self.captured_node
     }
 
+    /// Returns a handle of the widget that has keyboard focus.
+    pub fn keyboard_focus_node(&self) -> Handle<UiNode> {
+        self.keyboard_focus_node
+    }
+
+    /// Enables or disables mapping of keyboard to focus navigation: Tab and Shift+Tab move focus in the
+    /// tab order, arrows move focus spatially, Enter and Space activate focused widget. Only the keys
+    /// that weren't handled by the focused widget are mapped. It is disabled by default. See
+    /// [`crate::navigation`] for more info.
+    pub fn set_keyboard_navigation(&mut self, enabled: bool) {
+        self.keyboard_navigation = enabled;
+    }
+
+    pub fn is_keyboard_navigation_enabled(&self) -> bool {
+        self.keyboard_navigation
+    }
+
+    /// Returns `true` if the focus visual is drawn around the focused widget. It is shown when a
+    /// widget receives focus by navigation and hidden when focus is changed by a mouse.
+    pub fn is_focus_visual_visible(&self) -> bool {
+        self.focus_visual_visible
+    }
+
+    fn navigate(&mut self, origin: Handle<UiNode>, direction: NavigationDirection) {
+        let target = navigation::find_navigation_target(self, origin, direction);
+        if target.is_some() {
+            self.request_focus(target);
+            self.focus_visual_visible = true;
+        }
+    }
+
+    fn map_key_to_navigation(&self, message: &UiMessage) {
+        if let Some(&WidgetMessage::KeyDown(key)) = message.data() {
+            if message.handled() || message.direction() != MessageDirection::FromWidget {
+                return;
+            }
+
+            let direction = match key {
+                KeyCode::Tab if self.keyboard_modifiers.shift => NavigationDirection::Previous,
+                KeyCode::Tab => NavigationDirection::Next,
+                KeyCode::Up => NavigationDirection::Up,
+                KeyCode::Down => NavigationDirection::Down,
+                KeyCode::Left => NavigationDirection::Left,
+                KeyCode::Right => NavigationDirection::Right,
+                KeyCode::Return | KeyCode::NumpadEnter | KeyCode::Space => {
+                    self.send_message(WidgetMessage::activate(
+                        message.destination(),
+                        MessageDirection::ToWidget,
+                    ));
+                    return;
+                }
+                _ => return,
+            };
+
+            self.send_message(WidgetMessage::navigate(
+                message.destination(),
+                MessageDirection::ToWidget,
+                direction,
+            ));
+        }
+    }
+
     // Tries to set new picked node (a node under the cursor) and returns `true` if the node was
     // changed.
     fn try_set_picked_node(&mut self, node: Handle<UiNode>) -> bool {
@@ -2034,6 +2292,7 @@ impl UserInterface {
                         }
 
                         self.request_focus(self.picked_node);
+                        self.focus_visual_visible = false;
 
                         if self.picked_node.is_some() {
                             self.send_message(WidgetMessage::mouse_down(
@@ -2270,6 +2529,7 @@ impl UserInterface {
             self.preview_set.insert(node_handle);
         }
         node.handle = node_handle;
+        self.apply_style(node_handle);
         node_handle
     }
 
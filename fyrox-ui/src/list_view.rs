@@ -1,20 +1,28 @@
+//! List view is a widget that shows a list of items and allows to select one of them. It supports two
+//! modes: the simple one where every item is a separate widget, and the virtualized one, where the list
+//! view creates widgets only for visible items and reuses them when the list is scrolled. The latter
+//! is the way to go for lists with thousands of items, see [`ListViewBuilder::with_item_provider`] and
+//! [`ItemProvider`] for more info.
+
 use crate::{
     border::BorderBuilder,
     brush::Brush,
-    core::{color::Color, pool::Handle},
+    core::{algebra::Vector2, color::Color, pool::Handle},
     decorator::{Decorator, DecoratorMessage},
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
     message::{MessageDirection, UiMessage},
+    scroll_panel::{ScrollPanel, ScrollPanelMessage},
     scroll_viewer::{ScrollViewer, ScrollViewerBuilder, ScrollViewerMessage},
     stack_panel::StackPanelBuilder,
+    style::Theme,
     widget::{Widget, WidgetBuilder, WidgetMessage},
-    BuildContext, Control, NodeHandleMapping, Thickness, UiNode, UserInterface, BRUSH_DARK,
-    BRUSH_LIGHT,
+    BuildContext, Control, NodeHandleMapping, Thickness, UiNode, UserInterface, VerticalAlignment,
 };
 use std::{
     any::{Any, TypeId},
-    ops::{Deref, DerefMut},
+    ops::{Deref, DerefMut, Range},
+    rc::Rc,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -24,6 +32,16 @@ pub enum ListViewMessage {
     AddItem(Handle<UiNode>),
     RemoveItem(Handle<UiNode>),
     BringItemIntoView(Handle<UiNode>),
+    /// Scrolls the list so an item with given index will be visible. Works in both modes.
+    BringIndexIntoView(usize),
+    /// Sets the total amount of items of a virtualized list view. Visible items will be re-bound.
+    ItemCount(usize),
+    /// Re-measures every item and re-binds visible items of a virtualized list view, should be used
+    /// when the data of the items has changed.
+    Refresh,
+    /// Realizes the items in the given range. It is sent by a virtualized list view to itself when the
+    /// visible range changes (on scrolling or resizing), there is no need to send it manually.
+    VisibleRange(Range<usize>),
 }
 
 impl ListViewMessage {
@@ -32,6 +50,73 @@ impl ListViewMessage {
     define_constructor!(ListViewMessage:AddItem => fn add_item(Handle<UiNode>), layout: false);
     define_constructor!(ListViewMessage:RemoveItem => fn remove_item(Handle<UiNode>), layout: false);
     define_constructor!(ListViewMessage:BringItemIntoView => fn bring_item_into_view(Handle<UiNode>), layout: false);
+    define_constructor!(ListViewMessage:BringIndexIntoView => fn bring_index_into_view(usize), layout: false);
+    define_constructor!(ListViewMessage:ItemCount => fn item_count(usize), layout: false);
+    define_constructor!(ListViewMessage:Refresh => fn refresh(), layout: false);
+    define_constructor!(ListViewMessage:VisibleRange => fn visible_range(Range<usize>), layout: false);
+}
+
+/// Provides widgets for the items of a virtualized list view. The list view creates only as much item
+/// widgets as needed to fill its visible area and reuses them when the list is scrolled, so the widgets
+/// must be filled with the data of a particular item in [`ItemProvider::bind_item`].
+pub trait ItemProvider {
+    /// Creates a new item widget. The widget will be reused for different items.
+    fn create_item(&self, ctx: &mut BuildContext) -> Handle<UiNode>;
+
+    /// Fills the given item widget (created by [`ItemProvider::create_item`]) with the data of an item
+    /// at the given index. Use messages to modify the widget.
+    fn bind_item(&self, ui: &UserInterface, item: Handle<UiNode>, index: usize);
+
+    /// Returns height of an item at the given index, when the item is laid out with the given width.
+    /// `None` means the default height passed to [`ListViewBuilder::with_item_provider`]. Override it
+    /// if items have different heights, for example if they contain a text with word wrapping.
+    fn item_height(&self, _ui: &UserInterface, _index: usize, _width: f32) -> Option<f32> {
+        None
+    }
+}
+
+/// State of a virtualized list view.
+#[derive(Clone)]
+pub struct Virtualization {
+    pub provider: Rc<dyn ItemProvider>,
+    pub item_count: usize,
+    /// Default height of an item.
+    pub item_height: f32,
+    /// Vertical positions of the items, `offsets[i]` is the top of i-th item and the last value is
+    /// the total height of all items, so it has `item_count + 1` values.
+    pub offsets: Vec<f32>,
+    /// Width that was used to measure the items, `None` if the items weren't measured yet.
+    pub measured_width: Option<f32>,
+    /// Range of items that are currently bound to the item containers.
+    pub visible_range: Range<usize>,
+    /// A panel with the total height of all items that hosts the items panel.
+    pub host: Handle<UiNode>,
+}
+
+impl Virtualization {
+    /// Returns total height of all items.
+    pub fn total_height(&self) -> f32 {
+        self.offsets.last().cloned().unwrap_or_default()
+    }
+
+    /// Returns height of an item at the given index.
+    pub fn height_of(&self, index: usize) -> f32 {
+        self.offsets[index + 1] - self.offsets[index]
+    }
+
+    /// Re-calculates positions of the items starting from the given index.
+    fn measure(&mut self, ui: &UserInterface, from: usize) {
+        self.offsets.truncate(from + 1);
+        let mut offset = self.total_height();
+        for index in from..self.item_count {
+            offset += self
+                .measured_width
+                .and_then(|width| self.provider.item_height(ui, index, width))
+                .unwrap_or(self.item_height)
+                .max(0.0);
+            self.offsets.push(offset);
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -42,6 +127,7 @@ pub struct ListView {
     pub panel: Handle<UiNode>,
     pub items: Vec<Handle<UiNode>>,
     pub scroll_viewer: Handle<UiNode>,
+    pub virtualization: Option<Virtualization>,
 }
 
 crate::define_widget_deref!(ListView);
@@ -55,6 +141,7 @@ impl ListView {
             panel: Default::default(),
             items: Default::default(),
             scroll_viewer: Default::default(),
+            virtualization: None,
         }
     }
 
@@ -74,14 +161,198 @@ impl ListView {
         self.scroll_viewer
     }
 
+    /// Returns `true` if the list view creates widgets only for visible items.
+    pub fn is_virtualized(&self) -> bool {
+        self.virtualization.is_some()
+    }
+
+    /// Returns total amount of items in the list. For virtualized list views it could be much larger
+    /// than the amount of item containers.
+    pub fn item_count(&self) -> usize {
+        match self.virtualization {
+            Some(ref virtualization) => virtualization.item_count,
+            None => self.items.len(),
+        }
+    }
+
+    /// Returns an index of an item that is currently shown by the given item container.
+    pub fn container_index(&self, container: Handle<UiNode>) -> Option<usize> {
+        let position = self.item_containers.iter().position(|c| *c == container)?;
+        match self.virtualization {
+            Some(ref virtualization) => {
+                let index = virtualization.visible_range.start + position;
+                if index < virtualization.visible_range.end {
+                    Some(index)
+                } else {
+                    None
+                }
+            }
+            None => Some(position),
+        }
+    }
+
+    fn scroll_panel(&self, ui: &UserInterface) -> Handle<UiNode> {
+        ui.node(self.scroll_viewer)
+            .cast::<ScrollViewer>()
+            .map(|sv| sv.scroll_panel)
+            .unwrap_or_default()
+    }
+
+    /// Calculates a range of items that should be realized to fill the visible area of a virtualized
+    /// list view.
+    fn calculate_visible_range(&self, ui: &UserInterface) -> Option<Range<usize>> {
+        let virtualization = self.virtualization.as_ref()?;
+        let scroll_panel = ui.node(self.scroll_panel(ui)).cast::<ScrollPanel>()?;
+
+        let top = scroll_panel.scroll.y.max(0.0);
+        let bottom = top + scroll_panel.actual_local_size().y;
+        let first = virtualization
+            .offsets
+            .partition_point(|&offset| offset <= top)
+            .saturating_sub(1)
+            .min(virtualization.item_count);
+        let last = virtualization
+            .offsets
+            .partition_point(|&offset| offset < bottom)
+            .clamp(first, virtualization.item_count);
+
+        Some(first..last)
+    }
+
+    /// Returns width of the items of a virtualized list view, `None` if the list view wasn't arranged
+    /// yet.
+    fn items_width(&self, ui: &UserInterface) -> Option<f32> {
+        Some(ui.node(self.scroll_panel(ui)).actual_local_size().x).filter(|width| *width > 0.0)
+    }
+
+    /// Re-measures the items starting from the given index and updates the height of the items host.
+    fn measure(&mut self, ui: &mut UserInterface, from: usize) {
+        let width = self.items_width(ui);
+        if let Some(virtualization) = self.virtualization.as_mut() {
+            if virtualization.measured_width != width {
+                // All the items must be re-measured with the new width.
+                virtualization.measured_width = width;
+                virtualization.measure(ui, 0);
+            } else {
+                virtualization.measure(ui, from);
+            }
+
+            ui.send_message(WidgetMessage::height(
+                virtualization.host,
+                MessageDirection::ToWidget,
+                virtualization.total_height(),
+            ));
+        }
+    }
+
+    fn realize(&mut self, ui: &mut UserInterface, range: Range<usize>) {
+        let virtualization = match self.virtualization.as_mut() {
+            Some(virtualization) => virtualization,
+            None => return,
+        };
+
+        // Containers are never destroyed, unused ones are just hidden and wait for reuse.
+        while self.item_containers.len() < range.len() {
+            let ctx = &mut ui.build_ctx();
+            let item = virtualization.provider.create_item(ctx);
+            let container = generate_item_container(ctx, item);
+            ctx[container].set_height(virtualization.item_height);
+            ctx.link(container, self.panel);
+            self.item_containers.push(container);
+            self.items.push(item);
+        }
+
+        for (i, (&container, &item)) in self
+            .item_containers
+            .iter()
+            .zip(self.items.iter())
+            .enumerate()
+        {
+            let index = range.start + i;
+            let visible = index < range.end;
+            if ui.node(container).visibility() != visible {
+                ui.send_message(WidgetMessage::visibility(
+                    container,
+                    MessageDirection::ToWidget,
+                    visible,
+                ));
+            }
+            if visible {
+                let height = virtualization.height_of(index);
+                if ui.node(container).height() != height {
+                    ui.send_message(WidgetMessage::height(
+                        container,
+                        MessageDirection::ToWidget,
+                        height,
+                    ));
+                }
+                virtualization.provider.bind_item(ui, item, index);
+            }
+        }
+
+        ui.send_message(WidgetMessage::margin(
+            self.panel,
+            MessageDirection::ToWidget,
+            Thickness {
+                left: 0.0,
+                top: virtualization.offsets[range.start],
+                right: 0.0,
+                bottom: 0.0,
+            },
+        ));
+
+        virtualization.visible_range = range;
+    }
+
+    fn bring_index_into_view(&self, ui: &UserInterface, index: usize) {
+        match self.virtualization {
+            Some(ref virtualization) => {
+                if index >= virtualization.item_count {
+                    return;
+                }
+
+                let scroll_panel_handle = self.scroll_panel(ui);
+                if let Some(scroll_panel) = ui.node(scroll_panel_handle).cast::<ScrollPanel>() {
+                    let viewport = scroll_panel.actual_local_size().y;
+                    let top = virtualization.offsets[index];
+                    let bottom = virtualization.offsets[index + 1];
+                    let scroll = if top < scroll_panel.scroll.y {
+                        top
+                    } else if bottom > scroll_panel.scroll.y + viewport {
+                        bottom - viewport
+                    } else {
+                        return;
+                    };
+                    // Scroll the panel directly, because the max value of the scroll bar could be not
+                    // updated yet if the item count was changed just now.
+                    ui.send_message(ScrollPanelMessage::vertical_scroll(
+                        scroll_panel_handle,
+                        MessageDirection::ToWidget,
+                        scroll.max(0.0),
+                    ));
+                }
+            }
+            None => {
+                if let Some(&container) = self.item_containers.get(index) {
+                    ui.send_message(ScrollViewerMessage::bring_into_view(
+                        self.scroll_viewer,
+                        MessageDirection::ToWidget,
+                        container,
+                    ));
+                }
+            }
+        }
+    }
+
     fn fix_selection(&self, ui: &UserInterface) {
         // Check if current selection is out-of-bounds.
         if let Some(selected_index) = self.selected_index {
-            if selected_index >= self.items.len() {
-                let new_selection = if self.items.is_empty() {
+            let item_count = self.item_count();
+            if selected_index >= item_count {
+                let new_selection = if item_count == 0 {
                     None
                 } else {
-                    Some(self.items.len() - 1)
+                    Some(item_count - 1)
                 };
 
                 ui.send_message(ListViewMessage::selection(
@@ -94,10 +365,10 @@ impl ListView {
     }
 
     fn sync_decorators(&self, ui: &UserInterface) {
-        for (i, &container) in self.item_containers.iter().enumerate() {
+        for &container in self.item_containers.iter() {
             let select = match self.selected_index {
                 None => false,
-                Some(selected_index) => i == selected_index,
+                Some(selected_index) => self.container_index(container) == Some(selected_index),
             };
             if let Some(container) = ui.node(container).cast::<ListViewItem>() {
                 let mut stack = container.children().to_vec();
@@ -160,9 +431,7 @@ impl Control for ListViewItem {
                     .node(parent_list_view)
                     .cast::<ListView>()
                     .expect("Parent of ListViewItem must be ListView!")
-                    .item_containers
-                    .iter()
-                    .position(|c| *c == self.handle)
+                    .container_index(self.handle)
                     .expect("ListViewItem must be used as a child of ListView");
 
                 // Explicitly set selection on parent items control. This will send
@@ -191,6 +460,41 @@ impl Control for ListView {
         node_map.resolve(&mut self.panel);
         node_map.resolve_slice(&mut self.items);
         node_map.resolve_slice(&mut self.item_containers);
+        if let Some(virtualization) = self.virtualization.as_mut() {
+            node_map.resolve(&mut virtualization.host);
+        }
+    }
+
+    fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
+        let size = self.widget.arrange_override(ui, final_size);
+
+        // Size of the scroll panel is known only after arrangement, so this is the right place to
+        // find out which items are visible now and whether they must be re-measured.
+        if self
+            .virtualization
+            .as_ref()
+            .map_or(false, |v| v.measured_width != self.items_width(ui))
+        {
+            ui.send_message(ListViewMessage::refresh(
+                self.handle,
+                MessageDirection::ToWidget,
+            ));
+        }
+        if let Some(range) = self.calculate_visible_range(ui) {
+            if self
+                .virtualization
+                .as_ref()
+                .map_or(false, |v| v.visible_range != range)
+            {
+                ui.send_message(ListViewMessage::visible_range(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    range,
+                ));
+            }
+        }
+
+        size
     }
 
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
@@ -201,6 +505,13 @@ impl Control for ListView {
                 && message.direction() == MessageDirection::ToWidget
             {
                 match msg {
+                    ListViewMessage::Items(_)
+                    | ListViewMessage::AddItem(_)
+                    | ListViewMessage::RemoveItem(_)
+                        if self.is_virtualized() =>
+                    {
+                        // Items of a virtualized list view are managed by its item provider.
+                    }
                     ListViewMessage::Items(items) => {
                         // Remove previous items.
                         for child in ui.node(self.panel).children() {
@@ -271,6 +582,41 @@ impl Control for ListView {
                             ));
                         }
                     }
+                    &ListViewMessage::BringIndexIntoView(index) => {
+                        self.bring_index_into_view(ui, index);
+                    }
+                    &ListViewMessage::ItemCount(count) => {
+                        if let Some(virtualization) = self.virtualization.as_mut() {
+                            // Only new items must be measured.
+                            let first_changed = virtualization.item_count.min(count);
+                            virtualization.item_count = count;
+
+                            let range = virtualization.visible_range.start.min(count)
+                                ..virtualization.visible_range.end.min(count);
+                            self.measure(ui, first_changed);
+                            self.realize(ui, range);
+                            self.fix_selection(ui);
+                            self.sync_decorators(ui);
+
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    ListViewMessage::Refresh => {
+                        if let Some(range) = self
+                            .virtualization
+                            .as_ref()
+                            .map(|v| v.visible_range.clone())
+                        {
+                            self.measure(ui, 0);
+                            self.realize(ui, range);
+                        }
+                    }
+                    ListViewMessage::VisibleRange(range) => {
+                        if self.is_virtualized() {
+                            self.realize(ui, range.clone());
+                            self.sync_decorators(ui);
+                        }
+                    }
                 }
             }
         }
@@ -282,6 +628,8 @@ pub struct ListViewBuilder {
     items: Vec<Handle<UiNode>>,
     panel: Option<Handle<UiNode>>,
     scroll_viewer: Option<Handle<UiNode>>,
+    item_provider: Option<(Rc<dyn ItemProvider>, f32)>,
+    item_count: usize,
 }
 
 impl ListViewBuilder {
@@ -291,6 +639,8 @@ impl ListViewBuilder {
             items: Vec::new(),
             panel: None,
             scroll_viewer: None,
+            item_provider: None,
+            item_count: 0,
         }
     }
 
@@ -309,8 +659,28 @@ impl ListViewBuilder {
         self
     }
 
+    /// Makes the list view virtualized: item widgets will be created by the given provider only for
+    /// visible items and will be reused on scrolling. Every item has the given height, unless the provider
+    /// measures items by itself (see [`ItemProvider::item_height`]). Items set by [`Self::with_items`] are
+    /// ignored in this mode.
+    pub fn with_item_provider(mut self, provider: Rc<dyn ItemProvider>, item_height: f32) -> Self {
+        self.item_provider = Some((provider, item_height));
+        self
+    }
+
+    /// Sets initial amount of items of a virtualized list view.
+    pub fn with_item_count(mut self, count: usize) -> Self {
+        self.item_count = count;
+        self
+    }
+
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
-        let item_containers = generate_item_containers(ctx, &self.items);
+        let (items, item_containers) = if self.item_provider.is_some() {
+            (Vec::new(), Vec::new())
+        } else {
+            let item_containers = generate_item_containers(ctx, &self.items);
+            (self.items, item_containers)
+        };
 
         let panel = self.panel.unwrap_or_else(|| {
             StackPanelBuilder::new(
@@ -319,13 +689,36 @@ impl ListViewBuilder {
             .build(ctx)
         });
 
-        let back = BorderBuilder::new(
-            WidgetBuilder::new()
-                .with_background(BRUSH_DARK)
-                .with_foreground(BRUSH_LIGHT),
-        )
-        .with_stroke_thickness(Thickness::uniform(1.0))
-        .build(ctx);
+        let (content, virtualization) = match self.item_provider {
+            Some((provider, item_height)) => {
+                ctx[panel].set_vertical_alignment(VerticalAlignment::Top);
+
+                let host = StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_height(self.item_count as f32 * item_height)
+                        .with_child(panel),
+                )
+                .build(ctx);
+
+                let virtualization = Virtualization {
+                    provider,
+                    item_count: self.item_count,
+                    item_height,
+                    // Items will be measured when the size of the list view is known.
+                    offsets: (0..=self.item_count)
+                        .map(|i| i as f32 * item_height)
+                        .collect(),
+                    measured_width: None,
+                    visible_range: 0..0,
+                    host,
+                };
+
+                (host, Some(virtualization))
+            }
+            None => (panel, None),
+        };
+
+        let back = BorderBuilder::new(WidgetBuilder::new().with_style(Theme::LIST_VIEW)).build(ctx);
 
         let scroll_viewer = self.scroll_viewer.unwrap_or_else(|| {
             ScrollViewerBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(0.0)))
@@ -334,9 +727,9 @@ impl ListViewBuilder {
         let scroll_viewer_ref = ctx[scroll_viewer]
             .cast_mut::<ScrollViewer>()
             .expect("ListView must have ScrollViewer");
-        scroll_viewer_ref.set_content(panel);
+        scroll_viewer_ref.set_content(content);
         let content_presenter = scroll_viewer_ref.scroll_panel;
-        ctx.link(panel, content_presenter);
+        ctx.link(content, content_presenter);
 
         ctx.link(scroll_viewer, back);
 
@@ -344,9 +737,10 @@ impl ListViewBuilder {
             widget: self.widget_builder.with_child(back).build(),
             selected_index: None,
             item_containers,
-            items: self.items,
+            items,
             panel,
             scroll_viewer,
+            virtualization,
         };
 
         ctx.add_node(UiNode::new(list_box))
@@ -370,3 +764,162 @@ fn generate_item_containers(
         .map(|&item| generate_item_container(ctx, item))
         .collect()
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector2, pool::Handle},
+        list_view::{ItemProvider, ListView, ListViewBuilder, ListViewMessage},
+        message::MessageDirection,
+        text::{Text, TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        BuildContext, UiNode, UserInterface,
+    };
+    use std::rc::Rc;
+
+    struct NumberProvider;
+
+    impl ItemProvider for NumberProvider {
+        fn create_item(&self, ctx: &mut BuildContext) -> Handle<UiNode> {
+            TextBuilder::new(WidgetBuilder::new()).build(ctx)
+        }
+
+        fn bind_item(&self, ui: &UserInterface, item: Handle<UiNode>, index: usize) {
+            ui.send_message(TextMessage::text(
+                item,
+                MessageDirection::ToWidget,
+                index.to_string(),
+            ));
+        }
+    }
+
+    fn update(ui: &mut UserInterface, screen_size: Vector2<f32>) {
+        for _ in 0..3 {
+            ui.update(screen_size, 0.0);
+            while ui.poll_message().is_some() {}
+        }
+    }
+
+    fn visible_texts(ui: &UserInterface, list_view: Handle<UiNode>) -> Vec<String> {
+        let list_view = ui.node(list_view).cast::<ListView>().unwrap();
+        list_view
+            .items()
+            .iter()
+            .zip(list_view.item_containers())
+            .filter(|(_, container)| ui.node(**container).visibility())
+            .map(|(item, _)| ui.node(*item).cast::<Text>().unwrap().text())
+            .collect()
+    }
+
+    #[test]
+    fn test_virtualization() {
+        let screen_size = Vector2::new(200.0, 200.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let list_view =
+            ListViewBuilder::new(WidgetBuilder::new().with_width(200.0).with_height(200.0))
+                .with_item_provider(Rc::new(NumberProvider), 20.0)
+                .with_item_count(100_000)
+                .build(&mut ui.build_ctx());
+
+        update(&mut ui, screen_size);
+
+        let container_count = ui
+            .node(list_view)
+            .cast::<ListView>()
+            .unwrap()
+            .item_containers()
+            .len();
+        assert!(container_count > 0 && container_count < 15);
+        assert_eq!(visible_texts(&ui, list_view)[0], "0");
+
+        ui.send_message(ListViewMessage::bring_index_into_view(
+            list_view,
+            MessageDirection::ToWidget,
+            50_000,
+        ));
+        update(&mut ui, screen_size);
+
+        // Containers must be reused.
+        let list_view_ref = ui.node(list_view).cast::<ListView>().unwrap();
+        assert_eq!(list_view_ref.item_containers().len(), container_count);
+        assert!(list_view_ref
+            .virtualization
+            .as_ref()
+            .unwrap()
+            .visible_range
+            .contains(&50_000));
+        assert!(visible_texts(&ui, list_view).contains(&"50000".to_string()));
+
+        ui.send_message(ListViewMessage::item_count(
+            list_view,
+            MessageDirection::ToWidget,
+            3,
+        ));
+        update(&mut ui, screen_size);
+
+        assert_eq!(visible_texts(&ui, list_view), vec!["0", "1", "2"]);
+    }
+
+    struct TallOddsProvider;
+
+    impl ItemProvider for TallOddsProvider {
+        fn create_item(&self, ctx: &mut BuildContext) -> Handle<UiNode> {
+            NumberProvider.create_item(ctx)
+        }
+
+        fn bind_item(&self, ui: &UserInterface, item: Handle<UiNode>, index: usize) {
+            NumberProvider.bind_item(ui, item, index)
+        }
+
+        fn item_height(&self, _ui: &UserInterface, index: usize, _width: f32) -> Option<f32> {
+            Some(if index % 2 == 0 { 20.0 } else { 40.0 })
+        }
+    }
+
+    #[test]
+    fn test_virtualization_with_different_heights() {
+        let screen_size = Vector2::new(200.0, 200.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let list_view =
+            ListViewBuilder::new(WidgetBuilder::new().with_width(200.0).with_height(200.0))
+                .with_item_provider(Rc::new(TallOddsProvider), 20.0)
+                .with_item_count(1000)
+                .build(&mut ui.build_ctx());
+
+        update(&mut ui, screen_size);
+
+        let list_view_ref = ui.node(list_view).cast::<ListView>().unwrap();
+        let virtualization = list_view_ref.virtualization.as_ref().unwrap();
+        assert!(virtualization.measured_width.is_some());
+        assert_eq!(virtualization.total_height(), 30_000.0);
+        assert_eq!(ui.node(virtualization.host).height(), 30_000.0);
+        assert_eq!(
+            visible_texts(&ui, list_view),
+            vec!["0", "1", "2", "3", "4", "5", "6"]
+        );
+        for (i, container) in list_view_ref.item_containers().iter().take(7).enumerate() {
+            assert_eq!(
+                ui.node(*container).height(),
+                if i % 2 == 0 { 20.0 } else { 40.0 }
+            );
+        }
+
+        ui.send_message(ListViewMessage::bring_index_into_view(
+            list_view,
+            MessageDirection::ToWidget,
+            501,
+        ));
+        update(&mut ui, screen_size);
+
+        let list_view_ref = ui.node(list_view).cast::<ListView>().unwrap();
+        assert!(list_view_ref
+            .virtualization
+            .as_ref()
+            .unwrap()
+            .visible_range
+            .contains(&501));
+        assert!(visible_texts(&ui, list_view).contains(&"501".to_string()));
+    }
+}
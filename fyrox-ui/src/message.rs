@@ -14,7 +14,7 @@
 //! internal library code can send such messages without a risk of breaking anything.
 
 use crate::{
-    core::{algebra::Vector2, pool::Handle, reflect::prelude::*},
+    core::{algebra::Vector2, pool::Handle, reflect::prelude::*, visitor::prelude::*},
     UiNode,
 };
 use serde::{Deserialize, Serialize};
@@ -508,7 +508,7 @@ pub enum KeyCode {
     Plus,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Visit)]
 pub enum CursorIcon {
     Default,
     Crosshair,
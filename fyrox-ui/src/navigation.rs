@@ -0,0 +1,301 @@
+//! Focus navigation allows to move keyboard focus between widgets without a mouse, for example by
+//! keyboard arrows or a gamepad.
+//!
+//! Only widgets that are *tab stops* (see [`crate::widget::WidgetBuilder::with_tab_stop`]) could be
+//! focused by navigation. Buttons, check boxes, dropdown lists and text boxes are tab stops by default.
+//! There are two kinds of navigation:
+//!
+//! - Tab order navigation ([`NavigationDirection::Next`] and [`NavigationDirection::Previous`]) -
+//!   moves focus in the order defined by tab indices of widgets; widgets without tab index go after
+//!   widgets with it in the order of the tree.
+//! - Spatial navigation ([`NavigationDirection::Up`], [`NavigationDirection::Down`], etc.) - moves
+//!   focus to the closest widget in the given direction on the screen.
+//!
+//! Navigation is triggered by [`crate::widget::WidgetMessage::Navigate`] message, and focused widget
+//! could be activated (pressed) by [`crate::widget::WidgetMessage::Activate`]. Game code could feed
+//! gamepad input into the UI like this:
+//!
+//! ```rust
+//! # use fyrox_ui::{
+//! #     message::MessageDirection,
+//! #     navigation::NavigationDirection,
+//! #     widget::WidgetMessage,
+//! #     UserInterface,
+//! # };
+//! fn on_dpad_down(ui: &UserInterface) {
+//!     ui.send_message(WidgetMessage::navigate(
+//!         ui.keyboard_focus_node(),
+//!         MessageDirection::ToWidget,
+//!         NavigationDirection::Down,
+//!     ));
+//! }
+//!
+//! fn on_a_button(ui: &UserInterface) {
+//!     ui.send_message(WidgetMessage::activate(
+//!         ui.keyboard_focus_node(),
+//!         MessageDirection::ToWidget,
+//!     ));
+//! }
+//! ```
+//!
+//! Keyboard (Tab, Shift+Tab, arrows, Enter and Space) could be mapped to navigation messages
+//! automatically, see [`UserInterface::set_keyboard_navigation`].
+
+use crate::{
+    core::{algebra::Vector2, pool::Handle},
+    UiNode, UserInterface,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NavigationDirection {
+    /// Next widget in the tab order.
+    Next,
+    /// Previous widget in the tab order.
+    Previous,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Collects every widget that could be focused by navigation in the tab order.
+pub fn collect_tab_stops(ui: &UserInterface, root: Handle<UiNode>) -> Vec<Handle<UiNode>> {
+    let mut tab_stops = Vec::new();
+    let mut stack = vec![root];
+    while let Some(handle) = stack.pop() {
+        let node = ui.node(handle);
+        if !node.is_globally_visible() || !node.enabled() {
+            continue;
+        }
+        if node.is_tab_stop() {
+            tab_stops.push(handle);
+        }
+        // Reverse order, so children will be visited in the order of the tree.
+        stack.extend(node.children().iter().rev());
+    }
+
+    // Stable sort keeps the order of the tree for widgets with the same tab index.
+    tab_stops.sort_by_key(|handle| ui.node(*handle).tab_index().unwrap_or(usize::MAX));
+
+    tab_stops
+}
+
+fn center(ui: &UserInterface, handle: Handle<UiNode>) -> Vector2<f32> {
+    ui.node(handle).screen_bounds().center()
+}
+
+/// Finds a widget that should be focused when navigating from the `origin` widget in the given
+/// direction. If the origin is not a tab stop (for example nothing is focused), the first (or the
+/// last for [`NavigationDirection::Previous`]) widget in the tab order is returned.
+pub fn find_navigation_target(
+    ui: &UserInterface,
+    origin: Handle<UiNode>,
+    direction: NavigationDirection,
+) -> Handle<UiNode> {
+    let tab_stops = collect_tab_stops(ui, ui.root());
+
+    // Focus could be on an inner part of a control (for example on a text of a button when it was
+    // clicked), navigation should start from the control itself.
+    let mut origin = origin;
+    while origin.is_some() && !ui.node(origin).is_tab_stop() {
+        origin = ui.node(origin).parent();
+    }
+
+    let origin_position = match tab_stops.iter().position(|h| *h == origin) {
+        Some(position) => position,
+        None => {
+            return match direction {
+                NavigationDirection::Previous => tab_stops.last(),
+                _ => tab_stops.first(),
+            }
+            .cloned()
+            .unwrap_or_default()
+        }
+    };
+
+    match direction {
+        NavigationDirection::Next => tab_stops[(origin_position + 1) % tab_stops.len()],
+        NavigationDirection::Previous => {
+            tab_stops[(origin_position + tab_stops.len() - 1) % tab_stops.len()]
+        }
+        NavigationDirection::Up
+        | NavigationDirection::Down
+        | NavigationDirection::Left
+        | NavigationDirection::Right => {
+            let axis = match direction {
+                NavigationDirection::Up => Vector2::new(0.0, -1.0),
+                NavigationDirection::Down => Vector2::new(0.0, 1.0),
+                NavigationDirection::Left => Vector2::new(-1.0, 0.0),
+                _ => Vector2::new(1.0, 0.0),
+            };
+
+            let origin_center = center(ui, origin);
+
+            let mut best = Handle::NONE;
+            let mut best_score = f32::MAX;
+            for &candidate in tab_stops.iter() {
+                if candidate == origin {
+                    continue;
+                }
+
+                let offset = center(ui, candidate) - origin_center;
+                let distance_along = offset.dot(&axis);
+                if distance_along <= f32::EPSILON {
+                    // Candidate is not in the requested direction.
+                    continue;
+                }
+                let distance_across = (offset - axis.scale(distance_along)).norm();
+
+                // Prefer widgets that are on the same line, even if they're farther away.
+                let score = distance_along + distance_across * 2.0;
+                if score < best_score {
+                    best_score = score;
+                    best = candidate;
+                }
+            }
+
+            best
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBox, CheckBoxBuilder},
+        core::{algebra::Vector2, pool::Handle},
+        grid::{Column, GridBuilder, Row},
+        message::{ButtonState, KeyCode, MessageDirection, OsEvent, UiMessage},
+        navigation::NavigationDirection,
+        widget::{WidgetBuilder, WidgetMessage},
+        UiNode, UserInterface,
+    };
+
+    fn update(ui: &mut UserInterface) -> Vec<UiMessage> {
+        let screen_size = Vector2::new(400.0, 400.0);
+        let mut messages = Vec::new();
+        for _ in 0..2 {
+            ui.update(screen_size, 0.0);
+            while let Some(message) = ui.poll_message() {
+                messages.push(message);
+            }
+        }
+        messages
+    }
+
+    fn navigate(ui: &mut UserInterface, direction: NavigationDirection) -> Handle<UiNode> {
+        ui.send_message(WidgetMessage::navigate(
+            ui.keyboard_focus_node(),
+            MessageDirection::ToWidget,
+            direction,
+        ));
+        update(ui);
+        ui.keyboard_focus_node()
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut ui = UserInterface::new(Vector2::new(400.0, 400.0));
+        let ctx = &mut ui.build_ctx();
+
+        // 2x2 grid of buttons:
+        // b0 b1
+        // b2 b3
+        let buttons = (0..4)
+            .map(|i| {
+                ButtonBuilder::new(WidgetBuilder::new().on_row(i / 2).on_column(i % 2))
+                    .with_text("Button")
+                    .build(ctx)
+            })
+            .collect::<Vec<_>>();
+        GridBuilder::new(
+            WidgetBuilder::new()
+                .with_width(200.0)
+                .with_height(100.0)
+                .with_children(buttons.iter().cloned()),
+        )
+        .add_rows(vec![Row::stretch(), Row::stretch()])
+        .add_columns(vec![Column::stretch(), Column::stretch()])
+        .build(ctx);
+
+        update(&mut ui);
+        assert!(!ui.is_focus_visual_visible());
+
+        // Nothing is focused, first widget in the tab order must be focused.
+        assert_eq!(navigate(&mut ui, NavigationDirection::Next), buttons[0]);
+        assert!(ui.is_focus_visual_visible());
+        assert_eq!(navigate(&mut ui, NavigationDirection::Right), buttons[1]);
+        assert_eq!(navigate(&mut ui, NavigationDirection::Down), buttons[3]);
+        assert_eq!(navigate(&mut ui, NavigationDirection::Left), buttons[2]);
+        assert_eq!(navigate(&mut ui, NavigationDirection::Up), buttons[0]);
+        // There's nothing to the left, focus must stay.
+        assert_eq!(navigate(&mut ui, NavigationDirection::Left), buttons[0]);
+        assert_eq!(navigate(&mut ui, NavigationDirection::Previous), buttons[3]);
+        assert_eq!(navigate(&mut ui, NavigationDirection::Next), buttons[0]);
+
+        // Tab indices define the tab order.
+        ui.send_message(WidgetMessage::tab_index(
+            buttons[3],
+            MessageDirection::ToWidget,
+            Some(0),
+        ));
+        ui.send_message(WidgetMessage::tab_index(
+            buttons[1],
+            MessageDirection::ToWidget,
+            Some(1),
+        ));
+        update(&mut ui);
+        assert_eq!(navigate(&mut ui, NavigationDirection::Previous), buttons[1]);
+        assert_eq!(navigate(&mut ui, NavigationDirection::Previous), buttons[3]);
+
+        // Activation clicks the button.
+        ui.send_message(WidgetMessage::activate(
+            ui.keyboard_focus_node(),
+            MessageDirection::ToWidget,
+        ));
+        let messages = update(&mut ui);
+        assert!(messages.contains(&ButtonMessage::click(
+            buttons[3],
+            MessageDirection::FromWidget
+        )));
+    }
+
+    #[test]
+    fn test_keyboard_navigation() {
+        let mut ui = UserInterface::new(Vector2::new(400.0, 400.0));
+        let ctx = &mut ui.build_ctx();
+
+        let check_box = CheckBoxBuilder::new(WidgetBuilder::new().with_width(20.0))
+            .checked(Some(false))
+            .build(ctx);
+
+        update(&mut ui);
+
+        // Keyboard mapping is disabled by default.
+        ui.process_os_event(&OsEvent::KeyboardInput {
+            button: KeyCode::Tab,
+            state: ButtonState::Pressed,
+        });
+        update(&mut ui);
+        assert_eq!(ui.keyboard_focus_node(), ui.root());
+
+        ui.set_keyboard_navigation(true);
+        ui.process_os_event(&OsEvent::KeyboardInput {
+            button: KeyCode::Tab,
+            state: ButtonState::Pressed,
+        });
+        update(&mut ui);
+        assert_eq!(ui.keyboard_focus_node(), check_box);
+
+        ui.process_os_event(&OsEvent::KeyboardInput {
+            button: KeyCode::Space,
+            state: ButtonState::Pressed,
+        });
+        update(&mut ui);
+        assert_eq!(
+            ui.node(check_box).cast::<CheckBox>().unwrap().checked,
+            Some(true)
+        );
+    }
+}
@@ -2,7 +2,14 @@ use crate::{
     border::BorderBuilder,
     brush::Brush,
     canvas::CanvasBuilder,
-    core::{algebra::Vector2, color::Color, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     message::{MessageDirection, UiMessage},
     widget::{Widget, WidgetBuilder, WidgetMessage},
@@ -22,7 +29,7 @@ impl ProgressBarMessage {
     define_constructor!(ProgressBarMessage:Progress => fn progress(f32), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct ProgressBar {
     pub widget: Widget,
     pub progress: f32,
@@ -32,6 +39,23 @@ pub struct ProgressBar {
 
 crate::define_widget_deref!(ProgressBar);
 
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self {
+            widget: WidgetBuilder::new().build(),
+            progress: 0.0,
+            indicator: Default::default(),
+            body: Default::default(),
+        }
+    }
+}
+
+impl TypeUuidProvider for ProgressBar {
+    fn type_uuid() -> Uuid {
+        uuid!("073f1fba-5dcb-4821-b992-34d764be71d1")
+    }
+}
+
 impl Control for ProgressBar {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -1,18 +1,18 @@
 use crate::{
     border::BorderBuilder,
-    brush::Brush,
     button::{ButtonBuilder, ButtonMessage},
     canvas::CanvasBuilder,
-    core::{algebra::Vector2, color::Color, pool::Handle},
+    core::{algebra::Vector2, pool::Handle},
     decorator::DecoratorBuilder,
     define_constructor,
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, UiMessage},
+    style::Theme,
     text::{TextBuilder, TextMessage},
     utils::{make_arrow, ArrowDirection},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, NodeHandleMapping, Orientation, Thickness, UiNode,
-    UserInterface, VerticalAlignment, BRUSH_DARK, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST,
+    UserInterface, VerticalAlignment,
 };
 use std::{
     any::{Any, TypeId},
@@ -431,17 +431,11 @@ impl ScrollBarBuilder {
         };
 
         let indicator = self.indicator.unwrap_or_else(|| {
-            DecoratorBuilder::new(
-                BorderBuilder::new(
-                    WidgetBuilder::new()
-                        .with_margin(Thickness::uniform(1.0))
-                        .with_foreground(Brush::Solid(Color::TRANSPARENT)),
-                )
-                .with_stroke_thickness(Thickness::uniform(1.0)),
-            )
-            .with_normal_brush(BRUSH_LIGHT)
-            .with_hover_brush(BRUSH_LIGHTER)
-            .with_pressed_brush(BRUSH_LIGHTEST)
+            DecoratorBuilder::new(BorderBuilder::new(
+                WidgetBuilder::new()
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_style(Theme::SCROLL_BAR_INDICATOR),
+            ))
             .build(ctx)
         });
 
@@ -517,9 +511,7 @@ impl ScrollBarBuilder {
         .build(ctx);
 
         let body = self.body.unwrap_or_else(|| {
-            BorderBuilder::new(WidgetBuilder::new().with_background(BRUSH_DARK))
-                .with_stroke_thickness(Thickness::uniform(1.0))
-                .build(ctx)
+            BorderBuilder::new(WidgetBuilder::new().with_style(Theme::SCROLL_BAR)).build(ctx)
         });
         ctx.link(grid, body);
 
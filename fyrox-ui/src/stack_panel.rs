@@ -1,5 +1,13 @@
 use crate::{
-    core::{algebra::Vector2, math::Rect, pool::Handle, scope_profile},
+    core::{
+        algebra::Vector2,
+        math::Rect,
+        pool::Handle,
+        scope_profile,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     message::UiMessage,
     widget::{Widget, WidgetBuilder},
     BuildContext, Control, Orientation, UiNode, UserInterface,
@@ -9,7 +17,7 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct StackPanel {
     pub widget: Widget,
     pub orientation: Orientation,
@@ -17,6 +25,21 @@ pub struct StackPanel {
 
 crate::define_widget_deref!(StackPanel);
 
+impl Default for StackPanel {
+    fn default() -> Self {
+        Self {
+            widget: WidgetBuilder::new().build(),
+            orientation: Orientation::Vertical,
+        }
+    }
+}
+
+impl TypeUuidProvider for StackPanel {
+    fn type_uuid() -> Uuid {
+        uuid!("1d84db64-54f3-4f1e-8f82-25e8a12d3e50")
+    }
+}
+
 impl Control for StackPanel {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
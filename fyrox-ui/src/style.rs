@@ -0,0 +1,598 @@
+//! Style is a named set of properties (brushes, fonts, margins, etc.) that could be applied to widgets. Styles
+//! are stored in a [`Theme`], which is attached to a user interface.
+//!
+//! # Overview
+//!
+//! Every widget could have a name of a style (see [`crate::widget::WidgetBuilder::with_style`]). When such
+//! widget is added to a user interface, its style is resolved from the current theme of the user interface
+//! and applied to the widget (see [`crate::Control::apply_style`]). When the theme is changed (see
+//! [`crate::UserInterface::set_theme`]), styles of all widgets are re-applied, which allows to re-skin the
+//! whole user interface at runtime.
+//!
+//! Built-in widgets (borders, decorators, buttons, texts, text boxes, check boxes, scroll bars, list views
+//! and windows) get a style with a well-known name (see [`Theme::BORDER`], [`Theme::BUTTON`], etc.) if no
+//! style was set explicitly. Their look is defined by the styles of the default theme ([`Theme::default`]),
+//! which reproduces the look of the editor, so custom themes should be based on the default one.
+//!
+//! A style could inherit properties of some other style (see [`Style::with_parent`]), properties of a style
+//! override properties of its parent. Properties that are not defined in a style (and its ancestors) are
+//! left untouched, so styles could be used to change only a part of widget's look. Properties that were set
+//! explicitly (for example, by [`crate::widget::WidgetBuilder::with_background`] or by a message) are never
+//! changed by a style, see [`crate::widget::Widget::style_overrides`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use fyrox_ui::{
+//!     core::color::Color,
+//!     style::{Style, Theme},
+//!     brush::Brush,
+//!     Thickness,
+//! };
+//!
+//! fn make_theme() -> Theme {
+//!     let mut theme = Theme::default();
+//!     theme.add_style(
+//!         "AccentButton",
+//!         Style::new()
+//!             .with_parent(Theme::BUTTON)
+//!             .with(Style::NORMAL_BRUSH, Brush::Solid(Color::opaque(80, 118, 178)))
+//!             .with(Style::CORNER_RADIUS, 4.0)
+//!             .with(Style::MARGIN, Thickness::uniform(2.0)),
+//!     );
+//!     theme
+//! }
+//! ```
+
+use crate::{
+    brush::Brush, ttf::SharedFont, Thickness, BRUSH_BRIGHT, BRUSH_BRIGHT_BLUE, BRUSH_DARK,
+    BRUSH_DARKER, BRUSH_DARKEST, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST, BRUSH_PRIMARY,
+    BRUSH_TEXT,
+};
+use fxhash::FxHashMap;
+use fyrox_core::color::Color;
+
+/// A value of a style property.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StyleProperty {
+    /// A brush property (background, foreground, etc.).
+    Brush(Brush),
+    /// A thickness property (margin, stroke thickness, etc.).
+    Thickness(Thickness),
+    /// A numeric property (corner radius, etc.).
+    Float(f32),
+    /// A font property.
+    Font(SharedFont),
+}
+
+impl From<Brush> for StyleProperty {
+    fn from(brush: Brush) -> Self {
+        Self::Brush(brush)
+    }
+}
+
+impl From<Thickness> for StyleProperty {
+    fn from(thickness: Thickness) -> Self {
+        Self::Thickness(thickness)
+    }
+}
+
+impl From<f32> for StyleProperty {
+    fn from(value: f32) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<SharedFont> for StyleProperty {
+    fn from(font: SharedFont) -> Self {
+        Self::Font(font)
+    }
+}
+
+/// A named set of properties. See module docs for more info.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    parent: Option<String>,
+    properties: FxHashMap<String, StyleProperty>,
+}
+
+impl Style {
+    /// Background brush of a widget.
+    pub const BACKGROUND: &'static str = "Background";
+    /// Foreground brush of a widget.
+    pub const FOREGROUND: &'static str = "Foreground";
+    /// Margin of a widget.
+    pub const MARGIN: &'static str = "Margin";
+    /// Font of a text.
+    pub const FONT: &'static str = "Font";
+    /// Corner radius of a border.
+    pub const CORNER_RADIUS: &'static str = "CornerRadius";
+    /// Stroke thickness of a border.
+    pub const STROKE_THICKNESS: &'static str = "StrokeThickness";
+    /// Brush of a decorator in normal state.
+    pub const NORMAL_BRUSH: &'static str = "NormalBrush";
+    /// Brush of a decorator when it is hovered by mouse cursor.
+    pub const HOVER_BRUSH: &'static str = "HoverBrush";
+    /// Brush of a decorator when it is pressed.
+    pub const PRESSED_BRUSH: &'static str = "PressedBrush";
+    /// Brush of a decorator when it is selected.
+    pub const SELECTED_BRUSH: &'static str = "SelectedBrush";
+    /// Brush of a decorator when it is disabled.
+    pub const DISABLED_BRUSH: &'static str = "DisabledBrush";
+    /// Brush of a caret of a text box.
+    pub const CARET_BRUSH: &'static str = "CaretBrush";
+    /// Brush of a selection of a text box.
+    pub const SELECTION_BRUSH: &'static str = "SelectionBrush";
+
+    /// Creates new empty style.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a name of a style, which properties will be inherited.
+    pub fn with_parent(mut self, parent: &str) -> Self {
+        self.parent = Some(parent.to_owned());
+        self
+    }
+
+    /// Sets the value of a property.
+    pub fn with<P: Into<StyleProperty>>(mut self, name: &str, value: P) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Returns a name of a parent style (if any).
+    pub fn parent(&self) -> Option<&str> {
+        self.parent.as_deref()
+    }
+
+    /// Sets new parent of the style.
+    pub fn set_parent(&mut self, parent: Option<String>) {
+        self.parent = parent;
+    }
+
+    /// Sets the value of a property and returns its previous value (if any).
+    pub fn set<P: Into<StyleProperty>>(&mut self, name: &str, value: P) -> Option<StyleProperty> {
+        self.properties.insert(name.to_owned(), value.into())
+    }
+
+    /// Removes a property from the style and returns its value (if any).
+    pub fn remove(&mut self, name: &str) -> Option<StyleProperty> {
+        self.properties.remove(name)
+    }
+
+    /// Returns a value of a property. Only properties defined in this style are checked, use
+    /// [`Theme::resolve`] to get a style with all inherited properties.
+    pub fn get(&self, name: &str) -> Option<&StyleProperty> {
+        self.properties.get(name)
+    }
+
+    /// Returns a brush property, or `None` if there is no such property or it has different type.
+    pub fn brush(&self, name: &str) -> Option<Brush> {
+        match self.get(name) {
+            Some(StyleProperty::Brush(brush)) => Some(brush.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns a thickness property, or `None` if there is no such property or it has different type.
+    pub fn thickness(&self, name: &str) -> Option<Thickness> {
+        match self.get(name) {
+            Some(StyleProperty::Thickness(thickness)) => Some(*thickness),
+            _ => None,
+        }
+    }
+
+    /// Returns a numeric property, or `None` if there is no such property or it has different type.
+    pub fn float(&self, name: &str) -> Option<f32> {
+        match self.get(name) {
+            Some(StyleProperty::Float(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns a font property, or `None` if there is no such property or it has different type.
+    pub fn font(&self, name: &str) -> Option<SharedFont> {
+        match self.get(name) {
+            Some(StyleProperty::Font(font)) => Some(font.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over all properties defined in this style.
+    pub fn properties(&self) -> impl Iterator<Item = (&str, &StyleProperty)> {
+        self.properties
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+}
+
+/// A set of named styles. See module docs for more info.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    styles: FxHashMap<String, Style>,
+}
+
+impl Default for Theme {
+    /// Creates a theme, that reproduces the look of the editor and defines the styles of all built-in
+    /// widgets.
+    fn default() -> Self {
+        let mut theme = Self::new();
+        theme.add_style(
+            Self::WIDGET,
+            Style::new()
+                .with(Style::BACKGROUND, BRUSH_PRIMARY)
+                .with(Style::FOREGROUND, Brush::Solid(Color::WHITE)),
+        );
+        theme.add_style(
+            Self::BORDER,
+            Style::new()
+                .with_parent(Self::WIDGET)
+                .with(Style::FOREGROUND, BRUSH_PRIMARY)
+                .with(Style::STROKE_THICKNESS, Thickness::uniform(1.0))
+                .with(Style::CORNER_RADIUS, 0.0),
+        );
+        theme.add_style(
+            Self::DECORATOR,
+            Style::new()
+                .with_parent(Self::BORDER)
+                .with(Style::FOREGROUND, BRUSH_DARKER)
+                .with(Style::NORMAL_BRUSH, BRUSH_LIGHT)
+                .with(Style::HOVER_BRUSH, BRUSH_LIGHTER)
+                .with(Style::PRESSED_BRUSH, BRUSH_LIGHTEST)
+                .with(Style::SELECTED_BRUSH, BRUSH_BRIGHT)
+                .with(
+                    Style::DISABLED_BRUSH,
+                    Brush::Solid(Color::opaque(50, 50, 50)),
+                ),
+        );
+        theme.add_style(Self::BUTTON, Style::new().with_parent(Self::DECORATOR));
+        theme.add_style(
+            Self::TEXT,
+            Style::new()
+                .with_parent(Self::WIDGET)
+                .with(Style::FOREGROUND, BRUSH_TEXT),
+        );
+        theme.add_style(
+            Self::TEXT_BOX,
+            Style::new()
+                .with_parent(Self::TEXT)
+                .with(Style::BACKGROUND, BRUSH_DARKER)
+                .with(Style::CARET_BRUSH, Brush::Solid(Color::WHITE))
+                .with(Style::SELECTION_BRUSH, BRUSH_BRIGHT_BLUE),
+        );
+        theme.add_style(
+            Self::CHECK_BOX,
+            Style::new()
+                .with_parent(Self::BORDER)
+                .with(Style::BACKGROUND, BRUSH_DARKEST)
+                .with(Style::FOREGROUND, BRUSH_LIGHT),
+        );
+        theme.add_style(
+            Self::CHECK_BOX_MARK,
+            Style::new()
+                .with_parent(Self::BORDER)
+                .with(Style::BACKGROUND, BRUSH_BRIGHT_BLUE)
+                .with(Style::STROKE_THICKNESS, Thickness::uniform(0.0)),
+        );
+        theme.add_style(
+            Self::CHECK_BOX_UNDEFINED_MARK,
+            Style::new()
+                .with_parent(Self::BORDER)
+                .with(Style::BACKGROUND, BRUSH_BRIGHT)
+                .with(Style::FOREGROUND, Brush::Solid(Color::TRANSPARENT)),
+        );
+        theme.add_style(
+            Self::SCROLL_BAR,
+            Style::new()
+                .with_parent(Self::BORDER)
+                .with(Style::BACKGROUND, BRUSH_DARK),
+        );
+        theme.add_style(
+            Self::SCROLL_BAR_INDICATOR,
+            Style::new()
+                .with_parent(Self::DECORATOR)
+                .with(Style::FOREGROUND, Brush::Solid(Color::TRANSPARENT)),
+        );
+        theme.add_style(
+            Self::LIST_VIEW,
+            Style::new()
+                .with_parent(Self::BORDER)
+                .with(Style::BACKGROUND, BRUSH_DARK)
+                .with(Style::FOREGROUND, BRUSH_LIGHT),
+        );
+        theme.add_style(
+            Self::WINDOW,
+            Style::new()
+                .with_parent(Self::BORDER)
+                .with(Style::FOREGROUND, Brush::Solid(Color::opaque(60, 60, 64))),
+        );
+        theme.add_style(
+            Self::WINDOW_HEADER,
+            Style::new()
+                .with_parent(Self::BORDER)
+                .with(Style::BACKGROUND, Brush::Solid(Color::opaque(60, 60, 64)))
+                .with(Style::STROKE_THICKNESS, Thickness::uniform(0.0)),
+        );
+        theme.add_style(
+            Self::WINDOW_HEADER_BUTTON,
+            Style::new()
+                .with_parent(Self::DECORATOR)
+                .with(Style::STROKE_THICKNESS, Thickness::uniform(0.0))
+                .with(Style::NORMAL_BRUSH, Brush::Solid(Color::TRANSPARENT))
+                .with(Style::HOVER_BRUSH, BRUSH_LIGHT)
+                .with(Style::PRESSED_BRUSH, BRUSH_LIGHTEST),
+        );
+        theme
+    }
+}
+
+impl Theme {
+    /// Name of the base style of all widgets in the default theme.
+    pub const WIDGET: &'static str = "Widget";
+    /// Name of the style for borders in the default theme.
+    pub const BORDER: &'static str = "Border";
+    /// Name of the style for decorators in the default theme.
+    pub const DECORATOR: &'static str = "Decorator";
+    /// Name of the style for buttons in the default theme.
+    pub const BUTTON: &'static str = "Button";
+    /// Name of the style for texts in the default theme.
+    pub const TEXT: &'static str = "Text";
+    /// Name of the style for text boxes in the default theme.
+    pub const TEXT_BOX: &'static str = "TextBox";
+    /// Name of the style for the box of check boxes in the default theme.
+    pub const CHECK_BOX: &'static str = "CheckBox";
+    /// Name of the style for the check mark of check boxes in the default theme.
+    pub const CHECK_BOX_MARK: &'static str = "CheckBoxMark";
+    /// Name of the style for the mark of check boxes in undefined state in the default theme.
+    pub const CHECK_BOX_UNDEFINED_MARK: &'static str = "CheckBoxUndefinedMark";
+    /// Name of the style for the track of scroll bars in the default theme.
+    pub const SCROLL_BAR: &'static str = "ScrollBar";
+    /// Name of the style for the indicator of scroll bars in the default theme.
+    pub const SCROLL_BAR_INDICATOR: &'static str = "ScrollBarIndicator";
+    /// Name of the style for the background of list views in the default theme.
+    pub const LIST_VIEW: &'static str = "ListView";
+    /// Name of the style for the frame of windows in the default theme.
+    pub const WINDOW: &'static str = "Window";
+    /// Name of the style for the header of windows in the default theme.
+    pub const WINDOW_HEADER: &'static str = "WindowHeader";
+    /// Name of the style for the buttons in the header of windows in the default theme.
+    pub const WINDOW_HEADER_BUTTON: &'static str = "WindowHeaderButton";
+
+    /// Maximum depth of style inheritance, it is used to prevent infinite loops when styles
+    /// inherit each other.
+    const MAX_INHERITANCE_DEPTH: usize = 32;
+
+    /// Creates new theme without any styles.
+    pub fn new() -> Self {
+        Self {
+            styles: Default::default(),
+        }
+    }
+
+    /// Adds new style to the theme and returns previous style with the same name (if any).
+    pub fn add_style(&mut self, name: &str, style: Style) -> Option<Style> {
+        self.styles.insert(name.to_owned(), style)
+    }
+
+    /// Removes a style from the theme.
+    pub fn remove_style(&mut self, name: &str) -> Option<Style> {
+        self.styles.remove(name)
+    }
+
+    /// Returns a reference to a style with the given name.
+    pub fn style(&self, name: &str) -> Option<&Style> {
+        self.styles.get(name)
+    }
+
+    /// Returns a reference to a style with the given name.
+    pub fn style_mut(&mut self, name: &str) -> Option<&mut Style> {
+        self.styles.get_mut(name)
+    }
+
+    /// Returns an iterator over all styles of the theme.
+    pub fn styles(&self) -> impl Iterator<Item = (&str, &Style)> {
+        self.styles
+            .iter()
+            .map(|(name, style)| (name.as_str(), style))
+    }
+
+    /// Collects all properties of a style with the given name and of its ancestors. Properties
+    /// of a style override the properties of its parent. The resulting style has no parent.
+    /// Returns `None` if there is no such style.
+    pub fn resolve(&self, name: &str) -> Option<Style> {
+        let mut resolved = Style::new();
+
+        let mut current = Some(self.style(name)?);
+        let mut depth = 0;
+        while let Some(style) = current {
+            for (name, value) in style.properties.iter() {
+                if !resolved.properties.contains_key(name) {
+                    resolved.properties.insert(name.clone(), value.clone());
+                }
+            }
+
+            depth += 1;
+            if depth >= Self::MAX_INHERITANCE_DEPTH {
+                break;
+            }
+
+            current = style.parent().and_then(|parent| self.style(parent));
+        }
+
+        Some(resolved)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        border::{Border, BorderBuilder},
+        brush::Brush,
+        button::{Button, ButtonBuilder},
+        core::{algebra::Vector2, color::Color},
+        decorator::Decorator,
+        message::MessageDirection,
+        style::{Style, StyleProperty, Theme},
+        text_box::{TextBox, TextBoxBuilder},
+        widget::{WidgetBuilder, WidgetMessage},
+        Thickness, UserInterface, BRUSH_DARKER, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_PRIMARY,
+        BRUSH_TEXT,
+    };
+
+    #[test]
+    fn test_inheritance() {
+        let mut theme = Theme::new();
+        theme.add_style(
+            "Base",
+            Style::new()
+                .with(Style::BACKGROUND, Brush::Solid(Color::RED))
+                .with(Style::MARGIN, Thickness::uniform(1.0)),
+        );
+        theme.add_style(
+            "Derived",
+            Style::new()
+                .with_parent("Base")
+                .with(Style::BACKGROUND, Brush::Solid(Color::GREEN)),
+        );
+
+        let resolved = theme.resolve("Derived").unwrap();
+        assert_eq!(resolved.parent(), None);
+        assert_eq!(
+            resolved.brush(Style::BACKGROUND),
+            Some(Brush::Solid(Color::GREEN))
+        );
+        assert_eq!(
+            resolved.thickness(Style::MARGIN),
+            Some(Thickness::uniform(1.0))
+        );
+        // Type mismatch.
+        assert_eq!(resolved.float(Style::MARGIN), None);
+        assert!(theme.resolve("Unknown").is_none());
+    }
+
+    #[test]
+    fn test_cyclic_inheritance() {
+        let mut theme = Theme::new();
+        theme.add_style("A", Style::new().with_parent("B").with("A", 1.0));
+        theme.add_style("B", Style::new().with_parent("A").with("B", 2.0));
+
+        let resolved = theme.resolve("A").unwrap();
+        assert_eq!(resolved.get("A"), Some(&StyleProperty::Float(1.0)));
+        assert_eq!(resolved.get("B"), Some(&StyleProperty::Float(2.0)));
+    }
+
+    #[test]
+    fn test_theme_switching() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let button = ButtonBuilder::new(WidgetBuilder::new().with_style(Theme::BUTTON))
+            .build(&mut ui.build_ctx());
+        let decorator = ui.node(button).cast::<Button>().unwrap().decorator;
+
+        let decorator_ref = ui.node(decorator).cast::<Decorator>().unwrap();
+        assert_eq!(decorator_ref.style(), Some(Theme::BUTTON));
+        assert_eq!(decorator_ref.normal_brush, BRUSH_LIGHT);
+
+        let mut theme = Theme::default();
+        theme
+            .style_mut(Theme::BUTTON)
+            .unwrap()
+            .set(Style::NORMAL_BRUSH, Brush::Solid(Color::RED));
+        theme
+            .style_mut(Theme::BORDER)
+            .unwrap()
+            .set(Style::CORNER_RADIUS, 4.0);
+        ui.set_theme(theme);
+
+        let decorator_ref = ui.node(decorator).cast::<Decorator>().unwrap();
+        assert_eq!(decorator_ref.normal_brush, Brush::Solid(Color::RED));
+        assert_eq!(decorator_ref.background(), Brush::Solid(Color::RED));
+        assert_eq!(decorator_ref.border.corner_radius, 4.0);
+
+        // Explicitly set properties are not affected.
+        let border =
+            BorderBuilder::new(WidgetBuilder::new().with_background(Brush::Solid(Color::GREEN)))
+                .with_corner_radius(2.0)
+                .build(&mut ui.build_ctx());
+        let border_ref = ui.node(border).cast::<Border>().unwrap();
+        assert_eq!(border_ref.style(), Some(Theme::BORDER));
+        assert_eq!(border_ref.corner_radius, 2.0);
+        assert_eq!(border_ref.background(), Brush::Solid(Color::GREEN));
+
+        let mut theme = Theme::default();
+        theme
+            .style_mut(Theme::WIDGET)
+            .unwrap()
+            .set(Style::BACKGROUND, Brush::Solid(Color::BLUE));
+        ui.set_theme(theme);
+        let border_ref = ui.node(border).cast::<Border>().unwrap();
+        assert_eq!(border_ref.corner_radius, 2.0);
+        assert_eq!(border_ref.background(), Brush::Solid(Color::GREEN));
+    }
+
+    #[test]
+    fn test_default_theme_keeps_look() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let text_box = TextBoxBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+        let text_box_ref = ui.node(text_box).cast::<TextBox>().unwrap();
+        assert_eq!(text_box_ref.style(), Some(Theme::TEXT_BOX));
+        assert_eq!(text_box_ref.background(), BRUSH_DARKER);
+        assert_eq!(text_box_ref.foreground(), BRUSH_TEXT);
+        assert_eq!(text_box_ref.caret_brush, Brush::Solid(Color::WHITE));
+
+        let button = ButtonBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+        let decorator = ui.node(button).cast::<Button>().unwrap().decorator;
+        let decorator_ref = ui.node(decorator).cast::<Decorator>().unwrap();
+        assert_eq!(decorator_ref.style(), Some(Theme::BUTTON));
+        assert_eq!(decorator_ref.background(), BRUSH_LIGHT);
+        assert_eq!(decorator_ref.foreground(), BRUSH_DARKER);
+        assert_eq!(decorator_ref.hover_brush, BRUSH_LIGHTER);
+
+        let border = BorderBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+        let border_ref = ui.node(border).cast::<Border>().unwrap();
+        assert_eq!(border_ref.background(), BRUSH_PRIMARY);
+        assert_eq!(border_ref.foreground(), BRUSH_PRIMARY);
+        assert_eq!(border_ref.stroke_thickness, Thickness::uniform(1.0));
+    }
+
+    #[test]
+    fn test_theme_reaches_built_in_widgets() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let text_box = TextBoxBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+        let custom_text_box =
+            TextBoxBuilder::new(WidgetBuilder::new().with_background(Brush::Solid(Color::GREEN)))
+                .with_caret_brush(Brush::Solid(Color::GREEN))
+                .build(&mut ui.build_ctx());
+
+        let mut theme = Theme::default();
+        theme
+            .style_mut(Theme::TEXT_BOX)
+            .unwrap()
+            .set(Style::BACKGROUND, Brush::Solid(Color::RED));
+        theme
+            .style_mut(Theme::TEXT_BOX)
+            .unwrap()
+            .set(Style::CARET_BRUSH, Brush::Solid(Color::RED));
+        ui.set_theme(theme);
+
+        let text_box_ref = ui.node(text_box).cast::<TextBox>().unwrap();
+        assert_eq!(text_box_ref.background(), Brush::Solid(Color::RED));
+        assert_eq!(text_box_ref.caret_brush, Brush::Solid(Color::RED));
+
+        let custom_text_box_ref = ui.node(custom_text_box).cast::<TextBox>().unwrap();
+        assert_eq!(custom_text_box_ref.background(), Brush::Solid(Color::GREEN));
+        assert_eq!(custom_text_box_ref.caret_brush, Brush::Solid(Color::GREEN));
+
+        // Properties set by messages are not changed by the theme either.
+        ui.send_message(WidgetMessage::background(
+            text_box,
+            MessageDirection::ToWidget,
+            Brush::Solid(Color::BLUE),
+        ));
+        while ui.poll_message().is_some() {}
+        ui.set_theme(Theme::default());
+        let text_box_ref = ui.node(text_box).cast::<TextBox>().unwrap();
+        assert_eq!(text_box_ref.background(), Brush::Solid(Color::BLUE));
+        assert_eq!(text_box_ref.caret_brush, Brush::Solid(Color::WHITE));
+    }
+}
@@ -1,13 +1,22 @@
 use crate::{
     brush::Brush,
-    core::{algebra::Vector2, color::Color, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
-    draw::DrawingContext,
+    draw::{DrawingContext, SharedTexture},
     formatted_text::{FormattedText, FormattedTextBuilder, WrapMode},
     message::{MessageDirection, UiMessage},
+    style::{Style, Theme},
     ttf::SharedFont,
     widget::{Widget, WidgetBuilder},
     BuildContext, Control, HorizontalAlignment, UiNode, UserInterface, VerticalAlignment,
+    BRUSH_TEXT,
 };
 use std::{
     any::{Any, TypeId},
@@ -20,6 +29,8 @@ pub enum TextMessage {
     Text(String),
     Wrap(WrapMode),
     Font(SharedFont),
+    /// Size of the font, `None` means the default height of the font.
+    FontSize(Option<f32>),
     VerticalAlignment(VerticalAlignment),
     HorizontalAlignment(HorizontalAlignment),
     Shadow(bool),
@@ -32,6 +43,7 @@ impl TextMessage {
     define_constructor!(TextMessage:Text => fn text(String), layout: false);
     define_constructor!(TextMessage:Wrap=> fn wrap(WrapMode), layout: false);
     define_constructor!(TextMessage:Font => fn font(SharedFont), layout: false);
+    define_constructor!(TextMessage:FontSize => fn font_size(Option<f32>), layout: false);
     define_constructor!(TextMessage:VerticalAlignment => fn vertical_alignment(VerticalAlignment), layout: false);
     define_constructor!(TextMessage:HorizontalAlignment => fn horizontal_alignment(HorizontalAlignment), layout: false);
     define_constructor!(TextMessage:Shadow => fn shadow(bool), layout: false);
@@ -40,7 +52,7 @@ impl TextMessage {
     define_constructor!(TextMessage:ShadowOffset => fn shadow_offset(Vector2<f32>), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct Text {
     pub widget: Widget,
     pub formatted_text: RefCell<FormattedText>,
@@ -48,6 +60,21 @@ pub struct Text {
 
 crate::define_widget_deref!(Text);
 
+impl Default for Text {
+    fn default() -> Self {
+        Self {
+            widget: WidgetBuilder::new().build(),
+            formatted_text: RefCell::new(FormattedTextBuilder::new(SharedFont::default()).build()),
+        }
+    }
+}
+
+impl TypeUuidProvider for Text {
+    fn type_uuid() -> Uuid {
+        uuid!("df716047-e793-44d1-9415-32d6ccc35f22")
+    }
+}
+
 impl Control for Text {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -94,12 +121,20 @@ impl Control for Text {
                         }
                     }
                     TextMessage::Font(font) => {
+                        self.widget.override_style_property(Style::FONT);
                         if &text_ref.get_font() != font {
                             text_ref.set_font(font.clone());
                             drop(text_ref);
                             self.invalidate_layout();
                         }
                     }
+                    &TextMessage::FontSize(font_size) => {
+                        if text_ref.font_size() != font_size {
+                            text_ref.set_font_size(font_size);
+                            drop(text_ref);
+                            self.invalidate_layout();
+                        }
+                    }
                     &TextMessage::HorizontalAlignment(horizontal_alignment) => {
                         if text_ref.horizontal_alignment() != horizontal_alignment {
                             text_ref.set_horizontal_alignment(horizontal_alignment);
@@ -146,6 +181,13 @@ impl Control for Text {
             }
         }
     }
+
+    fn apply_style(&mut self, style: &Style) {
+        self.widget.apply_style(style);
+        if let Some(font) = style.font(Style::FONT) {
+            self.formatted_text.get_mut().set_font(font);
+        }
+    }
 }
 
 impl Text {
@@ -174,6 +216,12 @@ pub struct TextBuilder {
     widget_builder: WidgetBuilder,
     text: Option<String>,
     font: Option<SharedFont>,
+    font_size: Option<f32>,
+    markup: bool,
+    bold_font: Option<SharedFont>,
+    italic_font: Option<SharedFont>,
+    bold_italic_font: Option<SharedFont>,
+    images: Vec<(String, SharedTexture)>,
     vertical_text_alignment: VerticalAlignment,
     horizontal_text_alignment: HorizontalAlignment,
     wrap: WrapMode,
@@ -189,6 +237,12 @@ impl TextBuilder {
             widget_builder,
             text: None,
             font: None,
+            font_size: None,
+            markup: false,
+            bold_font: None,
+            italic_font: None,
+            bold_italic_font: None,
+            images: Default::default(),
             vertical_text_alignment: VerticalAlignment::Top,
             horizontal_text_alignment: HorizontalAlignment::Left,
             wrap: WrapMode::NoWrap,
@@ -214,6 +268,44 @@ impl TextBuilder {
         self
     }
 
+    /// Sets desired size of the font, by default the default height of the font is used. Glyphs
+    /// of the new size are rasterized on demand, there is no need to load the font again.
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Enables markup (see [`crate::formatted_text`] module docs for syntax), the text will be
+    /// parsed as markup, including the text that is set using [`TextMessage::Text`].
+    pub fn with_markup(mut self, markup: bool) -> Self {
+        self.markup = markup;
+        self
+    }
+
+    /// Sets a font, that will be used for bold parts of the markup.
+    pub fn with_bold_font(mut self, font: SharedFont) -> Self {
+        self.bold_font = Some(font);
+        self
+    }
+
+    /// Sets a font, that will be used for italic parts of the markup.
+    pub fn with_italic_font(mut self, font: SharedFont) -> Self {
+        self.italic_font = Some(font);
+        self
+    }
+
+    /// Sets a font, that will be used for bold italic parts of the markup.
+    pub fn with_bold_italic_font(mut self, font: SharedFont) -> Self {
+        self.bold_italic_font = Some(font);
+        self
+    }
+
+    /// Adds a named image, that could be used in the markup using `[img=name]` tag.
+    pub fn with_inline_image(mut self, name: &str, texture: SharedTexture) -> Self {
+        self.images.push((name.to_owned(), texture));
+        self
+    }
+
     pub fn with_vertical_text_alignment(mut self, valign: VerticalAlignment) -> Self {
         self.vertical_text_alignment = valign;
         self
@@ -255,30 +347,44 @@ impl TextBuilder {
     }
 
     pub fn build(mut self, ui: &mut BuildContext) -> Handle<UiNode> {
+        if self.widget_builder.foreground.is_none() {
+            self.widget_builder.foreground = Some(BRUSH_TEXT);
+        }
+        if self.widget_builder.style.is_none() {
+            self.widget_builder.style = Some(Theme::TEXT.to_owned());
+        }
+        if self.font.is_some() {
+            self.widget_builder.override_style_property(Style::FONT);
+        }
+
         let font = if let Some(font) = self.font {
             font
         } else {
             ui.default_font()
         };
 
-        if self.widget_builder.foreground.is_none() {
-            self.widget_builder.foreground = Some(Brush::Solid(Color::opaque(220, 220, 220)));
+        let mut formatted_text = FormattedTextBuilder::new(font)
+            .with_markup(self.markup)
+            .with_bold_font(self.bold_font)
+            .with_italic_font(self.italic_font)
+            .with_bold_italic_font(self.bold_italic_font)
+            .with_text(self.text.unwrap_or_default())
+            .with_font_size(self.font_size)
+            .with_vertical_alignment(self.vertical_text_alignment)
+            .with_horizontal_alignment(self.horizontal_text_alignment)
+            .with_wrap(self.wrap)
+            .with_shadow(self.shadow)
+            .with_shadow_brush(self.shadow_brush)
+            .with_shadow_dilation(self.shadow_dilation)
+            .with_shadow_offset(self.shadow_offset)
+            .build();
+        for (name, texture) in self.images {
+            formatted_text.add_inline_image(&name, texture);
         }
 
         let text = Text {
             widget: self.widget_builder.build(),
-            formatted_text: RefCell::new(
-                FormattedTextBuilder::new(font)
-                    .with_text(self.text.unwrap_or_default())
-                    .with_vertical_alignment(self.vertical_text_alignment)
-                    .with_horizontal_alignment(self.horizontal_text_alignment)
-                    .with_wrap(self.wrap)
-                    .with_shadow(self.shadow)
-                    .with_shadow_brush(self.shadow_brush)
-                    .with_shadow_dilation(self.shadow_dilation)
-                    .with_shadow_offset(self.shadow_offset)
-                    .build(),
-            ),
+            formatted_text: RefCell::new(formatted_text),
         };
         ui.add_node(UiNode::new(text))
     }
@@ -10,6 +10,7 @@ use crate::{
     draw::{CommandTexture, Draw, DrawingContext},
     formatted_text::{FormattedText, FormattedTextBuilder, WrapMode},
     message::{CursorIcon, KeyCode, MessageDirection, MouseButton, UiMessage},
+    style::{Style, Theme},
     text::TextMessage,
     ttf::SharedFont,
     widget::{Widget, WidgetBuilder, WidgetMessage},
@@ -409,27 +410,34 @@ impl TextBox {
         self.formatted_text.borrow_mut().get_raw_text().len()
     }
 
+    fn font_size(&self) -> f32 {
+        let formatted_text = self.formatted_text.borrow();
+        formatted_text
+            .font_size()
+            .unwrap_or_else(|| formatted_text.get_font().0.lock().height())
+    }
+
+    fn line_height(&self) -> f32 {
+        let font_size = self.font_size();
+        let font = self.formatted_text.borrow().get_font();
+        let line_height = font.0.lock().ascender_at(font_size);
+        line_height
+    }
+
     pub fn caret_local_position(&self) -> Vector2<f32> {
         let text = self.formatted_text.borrow();
 
-        let font = text.get_font();
         let mut caret_pos = Vector2::default();
 
-        let font = font.0.lock();
         if let Some(line) = text.get_lines().get(self.caret_position.line) {
-            let text = text.get_raw_text();
+            let advances = text.get_advances();
             caret_pos += Vector2::new(line.x_offset, line.y_offset);
             for (offset, char_index) in (line.begin..line.end).enumerate() {
                 if offset >= self.caret_position.offset {
                     break;
                 }
-                if let Some(glyph) = text
-                    .get(char_index)
-                    .and_then(|c| font.glyphs().get(c.glyph_index as usize))
-                {
-                    caret_pos.x += glyph.advance;
-                } else {
-                    caret_pos.x += font.height();
+                if let Some(advance) = advances.get(char_index) {
+                    caret_pos.x += advance;
                 }
             }
         }
@@ -450,7 +458,7 @@ impl TextBox {
         let local_bounds = self.bounding_rect();
         let caret_view_position = self.point_to_view_pos(self.caret_local_position());
         // Move view position to contain the caret + add some spacing.
-        let spacing_step = self.formatted_text.borrow().get_font().0.lock().ascender();
+        let spacing_step = self.line_height();
         let spacing = spacing_step * 3.0;
         let top_left_corner = local_bounds.left_top_corner();
         let bottom_right_corner = local_bounds.right_bottom_corner();
@@ -551,33 +559,27 @@ impl TextBox {
             return None;
         }
 
-        let font = self.formatted_text.borrow().get_font();
-        let font = font.0.lock();
-        for (line_index, line) in self.formatted_text.borrow().get_lines().iter().enumerate() {
+        let formatted_text = self.formatted_text.borrow();
+        for (line_index, line) in formatted_text.get_lines().iter().enumerate() {
             let line_screen_bounds = Rect::new(
                 line.x_offset - self.view_position.x,
                 line.y_offset - self.view_position.y,
                 line.width,
-                font.ascender(),
+                line.height,
             );
             if line_screen_bounds.contains(point_to_check) {
                 let mut x = line_screen_bounds.x();
                 // Check each character in line.
                 for (offset, index) in (line.begin..line.end).enumerate() {
-                    let character = self.formatted_text.borrow().get_raw_text()[index];
-                    let (width, height, advance) =
-                        if let Some(glyph) = font.glyphs().get(character.glyph_index as usize) {
-                            (
-                                glyph.bitmap_width as f32,
-                                glyph.bitmap_height as f32,
-                                glyph.advance,
-                            )
-                        } else {
-                            // Stub
-                            let h = font.height();
-                            (h, h, h)
-                        };
-                    let char_screen_bounds = Rect::new(x, line_screen_bounds.y(), width, height);
+                    // Characters could have different fonts and sizes, so the whole cell of a
+                    // character is checked.
+                    let advance = formatted_text
+                        .get_advances()
+                        .get(index)
+                        .cloned()
+                        .unwrap_or_default();
+                    let char_screen_bounds =
+                        Rect::new(x, line_screen_bounds.y(), advance, line.height);
                     if char_screen_bounds.contains(point_to_check) {
                         let char_bounds_center_x =
                             char_screen_bounds.x() + char_screen_bounds.w() * 0.5;
@@ -598,11 +600,11 @@ impl TextBox {
 
         // Additionally check each line again, but now check if the cursor is either at left or right side of the cursor.
         // This allows us to set caret at lines by clicking at either ends of it.
-        for (line_index, line) in self.formatted_text.borrow().get_lines().iter().enumerate() {
+        for (line_index, line) in formatted_text.get_lines().iter().enumerate() {
             let line_x_begin = line.x_offset - self.view_position.x;
             let line_x_end = line_x_begin + line.width;
             let line_y_begin = line.y_offset - self.view_position.y;
-            let line_y_end = line_y_begin + font.ascender();
+            let line_y_end = line_y_begin + line.height;
             if (line_y_begin..line_y_end).contains(&point_to_check.y) {
                 if point_to_check.x < line_x_begin {
                     return Some(Position {
@@ -793,12 +795,13 @@ impl Control for TextBox {
 
         if self.caret_visible {
             let caret_pos = self.point_to_view_pos(self.caret_local_position());
-            let caret_bounds = Rect::new(
-                caret_pos.x,
-                caret_pos.y,
-                2.0,
-                self.formatted_text.borrow().get_font().0.lock().height(),
-            );
+            let caret_height = self
+                .formatted_text
+                .borrow()
+                .get_lines()
+                .get(self.caret_position.line)
+                .map_or_else(|| self.font_size(), |line| line.height);
+            let caret_bounds = Rect::new(caret_pos.x, caret_pos.y, 2.0, caret_height);
             drawing_context.push_rect_filled(&caret_bounds, None);
             drawing_context.commit(
                 self.clip_bounds(),
@@ -1159,6 +1162,7 @@ impl Control for TextBox {
                             }
                         }
                         TextMessage::Font(font) => {
+                            self.widget.override_style_property(Style::FONT);
                             if &text.get_font() != font {
                                 text.set_font(font.clone());
                                 drop(text);
@@ -1166,6 +1170,14 @@ impl Control for TextBox {
                                 ui.send_message(message.reverse());
                             }
                         }
+                        TextMessage::FontSize(font_size) => {
+                            if text.font_size() != *font_size {
+                                text.set_font_size(*font_size);
+                                drop(text);
+                                self.invalidate_layout();
+                                ui.send_message(message.reverse());
+                            }
+                        }
                         TextMessage::VerticalAlignment(alignment) => {
                             if &text.vertical_alignment() != alignment {
                                 text.set_vertical_alignment(*alignment);
@@ -1220,12 +1232,14 @@ impl Control for TextBox {
                 if message.direction() == MessageDirection::ToWidget {
                     match msg {
                         TextBoxMessage::SelectionBrush(brush) => {
+                            self.widget.override_style_property(Style::SELECTION_BRUSH);
                             if &self.selection_brush != brush {
                                 self.selection_brush = brush.clone();
                                 ui.send_message(message.reverse());
                             }
                         }
                         TextBoxMessage::CaretBrush(brush) => {
+                            self.widget.override_style_property(Style::CARET_BRUSH);
                             if &self.caret_brush != brush {
                                 self.caret_brush = brush.clone();
                                 ui.send_message(message.reverse());
@@ -1254,14 +1268,27 @@ impl Control for TextBox {
             }
         }
     }
+
+    fn apply_style(&mut self, style: &Style) {
+        self.widget.apply_style(style);
+        if let Some(caret_brush) = style.brush(Style::CARET_BRUSH) {
+            self.caret_brush = caret_brush;
+        }
+        if let Some(selection_brush) = style.brush(Style::SELECTION_BRUSH) {
+            self.selection_brush = selection_brush;
+        }
+        if let Some(font) = style.font(Style::FONT) {
+            self.formatted_text.get_mut().set_font(font);
+        }
+    }
 }
 
 pub struct TextBoxBuilder {
     widget_builder: WidgetBuilder,
     font: Option<SharedFont>,
     text: String,
-    caret_brush: Brush,
-    selection_brush: Brush,
+    caret_brush: Option<Brush>,
+    selection_brush: Option<Brush>,
     filter: Option<Rc<RefCell<FilterCallback>>>,
     vertical_alignment: VerticalAlignment,
     horizontal_alignment: HorizontalAlignment,
@@ -1275,6 +1302,9 @@ pub struct TextBoxBuilder {
     shadow_dilation: f32,
     shadow_offset: Vector2<f32>,
     skip_chars: Vec<u32>,
+    markup: bool,
+    bold_font: Option<SharedFont>,
+    italic_font: Option<SharedFont>,
 }
 
 impl TextBoxBuilder {
@@ -1283,8 +1313,8 @@ impl TextBoxBuilder {
             widget_builder,
             font: None,
             text: "".to_owned(),
-            caret_brush: Brush::Solid(Color::WHITE),
-            selection_brush: Brush::Solid(Color::opaque(80, 118, 178)),
+            caret_brush: None,
+            selection_brush: None,
             filter: None,
             vertical_alignment: VerticalAlignment::Top,
             horizontal_alignment: HorizontalAlignment::Left,
@@ -1298,6 +1328,9 @@ impl TextBoxBuilder {
             shadow_dilation: 1.0,
             shadow_offset: Vector2::new(1.0, 1.0),
             skip_chars: Default::default(),
+            markup: false,
+            bold_font: None,
+            italic_font: None,
         }
     }
 
@@ -1312,12 +1345,12 @@ impl TextBoxBuilder {
     }
 
     pub fn with_caret_brush(mut self, brush: Brush) -> Self {
-        self.caret_brush = brush;
+        self.caret_brush = Some(brush);
         self
     }
 
     pub fn with_selection_brush(mut self, brush: Brush) -> Self {
-        self.selection_brush = brush;
+        self.selection_brush = Some(brush);
         self
     }
 
@@ -1356,6 +1389,25 @@ impl TextBoxBuilder {
         self
     }
 
+    /// Enables markup (see [`crate::formatted_text`] module docs for syntax). Text that is typed
+    /// by the user is not parsed, it inherits the style of the preceding character.
+    pub fn with_markup(mut self, markup: bool) -> Self {
+        self.markup = markup;
+        self
+    }
+
+    /// Sets a font, that will be used for bold parts of the markup.
+    pub fn with_bold_font(mut self, font: SharedFont) -> Self {
+        self.bold_font = Some(font);
+        self
+    }
+
+    /// Sets a font, that will be used for italic parts of the markup.
+    pub fn with_italic_font(mut self, font: SharedFont) -> Self {
+        self.italic_font = Some(font);
+        self
+    }
+
     pub fn with_mask_char(mut self, mask_char: Option<char>) -> Self {
         self.mask_char = mask_char;
         self
@@ -1396,6 +1448,7 @@ impl TextBoxBuilder {
     }
 
     pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        self.widget_builder.tab_stop.get_or_insert(true);
         if self.widget_builder.foreground.is_none() {
             self.widget_builder.foreground = Some(BRUSH_TEXT);
         }
@@ -1405,6 +1458,18 @@ impl TextBoxBuilder {
         if self.widget_builder.cursor.is_none() {
             self.widget_builder.cursor = Some(CursorIcon::Text);
         }
+        if self.widget_builder.style.is_none() {
+            self.widget_builder.style = Some(Theme::TEXT_BOX.to_owned());
+        }
+        for (name, is_set) in [
+            (Style::FONT, self.font.is_some()),
+            (Style::CARET_BRUSH, self.caret_brush.is_some()),
+            (Style::SELECTION_BRUSH, self.selection_brush.is_some()),
+        ] {
+            if is_set {
+                self.widget_builder.override_style_property(name);
+            }
+        }
 
         let text_box = TextBox {
             widget: self.widget_builder.build(),
@@ -1414,6 +1479,9 @@ impl TextBoxBuilder {
             blink_interval: 0.5,
             formatted_text: RefCell::new(
                 FormattedTextBuilder::new(self.font.unwrap_or_else(|| ctx.default_font()))
+                    .with_markup(self.markup)
+                    .with_bold_font(self.bold_font)
+                    .with_italic_font(self.italic_font)
                     .with_text(self.text)
                     .with_horizontal_alignment(self.horizontal_alignment)
                     .with_vertical_alignment(self.vertical_alignment)
@@ -1427,8 +1495,10 @@ impl TextBoxBuilder {
             ),
             selection_range: None,
             selecting: false,
-            selection_brush: self.selection_brush,
-            caret_brush: self.caret_brush,
+            selection_brush: self
+                .selection_brush
+                .unwrap_or(Brush::Solid(Color::opaque(80, 118, 178))),
+            caret_brush: self.caret_brush.unwrap_or(Brush::Solid(Color::WHITE)),
             has_focus: false,
             filter: self.filter,
             commit_mode: self.commit_mode,
@@ -3,6 +3,7 @@ use crate::{
     draw::SharedTexture,
 };
 use fxhash::FxHashMap;
+use lazy_static::lazy_static;
 use std::{
     borrow::Cow,
     fmt::{Debug, Formatter},
@@ -20,19 +21,53 @@ pub struct FontGlyph {
     pub bitmap_width: usize,
     pub bitmap_height: usize,
     pub pixels: Vec<u8>,
+    /// Position of the glyph in the atlas (in pixels).
+    pub atlas_position: Vector2<usize>,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+struct GlyphKey {
+    unicode: u32,
+    // Bit representation of the size, f32 does not implement Hash and Eq.
+    size: u32,
+}
+
+impl GlyphKey {
+    fn new(unicode: u32, size: f32) -> Self {
+        Self {
+            unicode,
+            size: size.to_bits(),
+        }
+    }
+}
+
+/// Font is a set of font faces (the main face and a chain of fallback faces) with a glyph cache.
+///
+/// # Glyph cache
+///
+/// Glyphs are rasterized on demand (see [`Font::cache_glyph`]) for every requested size and put in
+/// a single atlas, which grows automatically when there is no free space left in it. Every time
+/// when the atlas changes, [`Font::texture`] is reset, so the renderer will re-upload the atlas to
+/// GPU. Positions of the glyphs in the atlas (in pixels) never change, so it is safe to keep them,
+/// while texture coordinates must be re-calculated using current atlas size.
+///
+/// # Fallback fonts
+///
+/// If a character is missing in the main face, the glyph is taken from the first fallback face (see
+/// [`Font::add_fallback`]) that contains the character. This is useful to render characters of
+/// different scripts (CJK, emoji, etc.) using a single font.
 pub struct Font {
+    faces: Vec<Arc<fontdue::Font>>,
     height: f32,
     glyphs: Vec<FontGlyph>,
     ascender: f32,
     descender: f32,
-    char_map: FxHashMap<u32, usize>,
+    glyph_map: FxHashMap<GlyphKey, Option<usize>>,
     atlas: Vec<u8>,
     atlas_size: usize,
+    packer: RectPacker<usize>,
     pub texture: Option<SharedTexture>,
 }
-
 #[derive(Debug, Clone)]
 pub struct SharedFont(pub Arc<Mutex<Font>>);
 
@@ -46,6 +81,19 @@ impl SharedFont {
     }
 }
 
+lazy_static! {
+    static ref BUILT_IN_FONT: SharedFont =
+        SharedFont::new(FontBuilder::new().build_builtin().unwrap());
+}
+
+impl Default for SharedFont {
+    /// Returns shared instance of the built-in font. It is used by widgets that were created
+    /// without a build context (for example, during deserialization).
+    fn default() -> Self {
+        BUILT_IN_FONT.clone()
+    }
+}
+
 impl From<Arc<Mutex<Font>>> for SharedFont {
     fn from(arc: Arc<Mutex<Font>>) -> Self {
         SharedFont(arc)
@@ -143,6 +191,17 @@ impl Font {
         ]
     }
 
+    /// Initial size of the atlas (in pixels) of a font without pre-rasterized glyphs.
+    pub const INITIAL_ATLAS_SIZE: usize = 256;
+
+    /// Maximum size of the atlas (in pixels), glyphs that do not fit in the atlas of this size
+    /// are not rendered.
+    pub const MAX_ATLAS_SIZE: usize = 8192;
+
+    const GLYPH_BORDER: usize = 2;
+
+    /// Creates new font from the given data. Glyphs from the given char set are rasterized
+    /// immediately using the given height, any other glyphs will be rasterized on demand.
     pub fn from_memory(
         data: impl Deref<Target = [u8]>,
         height: f32,
@@ -152,39 +211,37 @@ impl Font {
         let font_metrics = fontdue_font.horizontal_line_metrics(height).unwrap();
 
         let mut font = Font {
+            faces: vec![Arc::new(fontdue_font)],
             height,
             glyphs: Vec::new(),
             ascender: font_metrics.ascent,
             descender: font_metrics.descent,
-            char_map: FxHashMap::default(),
+            glyph_map: FxHashMap::default(),
             atlas: Vec::new(),
             atlas_size: 0,
+            packer: RectPacker::new(0, 0),
             texture: None,
         };
 
-        let mut index = 0;
+        let mut area = 0.0;
+        let mut rasterized = Vec::new();
         for range in char_set {
             for unicode in range.start..range.end {
                 if let Some(character) = std::char::from_u32(unicode) {
-                    let (metrics, bitmap) = fontdue_font.rasterize(character, height);
-
-                    font.glyphs.push(FontGlyph {
-                        left: metrics.xmin as f32,
-                        top: metrics.ymin as f32,
-                        pixels: bitmap,
-                        advance: metrics.advance_width,
-                        tex_coords: Default::default(),
-                        bitmap_width: metrics.width,
-                        bitmap_height: metrics.height,
-                    });
-
-                    font.char_map.insert(unicode, index);
-                    index += 1;
+                    let (metrics, bitmap) = font.faces[0].rasterize(character, height);
+                    area += (metrics.width + Self::GLYPH_BORDER) as f32
+                        * (metrics.height + Self::GLYPH_BORDER) as f32;
+                    rasterized.push((unicode, metrics, bitmap));
                 }
             }
         }
 
-        font.pack();
+        font.resize_atlas(((1.3 * f32::sqrt(area)) as usize).max(Self::INITIAL_ATLAS_SIZE));
+
+        for (unicode, metrics, bitmap) in rasterized {
+            let index = font.add_glyph(metrics, bitmap);
+            font.glyph_map.insert(GlyphKey::new(unicode, height), index);
+        }
 
         Ok(font)
     }
@@ -201,39 +258,208 @@ impl Font {
         }
     }
 
+    /// Adds faces of the given font (its main face and all its fallbacks) to the end of the
+    /// fallback chain of this font. Glyphs that were cached before are not affected, except the
+    /// missing ones - they will be looked up again in the new chain.
+    pub fn add_fallback(&mut self, fallback: &Font) {
+        self.faces.extend(fallback.faces.iter().cloned());
+        self.glyph_map.retain(|_, index| index.is_some());
+    }
+
+    /// Returns total amount of fallback faces of the font.
+    pub fn fallback_count(&self) -> usize {
+        self.faces.len() - 1
+    }
+
+    /// Returns index of the first face (0 - main face, 1.. - fallbacks) that contains the given
+    /// character.
+    fn face_index(&self, character: char) -> Option<usize> {
+        self.faces
+            .iter()
+            .position(|face| face.lookup_glyph_index(character) != 0)
+    }
+
+    /// Returns index of a glyph of the given character of the given size, the glyph is rasterized
+    /// and put in the atlas if it is not in the cache yet. Returns `None` if there is no such
+    /// character in the main face and in all fallback faces.
+    pub fn cache_glyph(&mut self, unicode: u32, size: f32) -> Option<usize> {
+        let key = GlyphKey::new(unicode, size);
+
+        if let Some(index) = self.glyph_map.get(&key) {
+            return *index;
+        }
+
+        let index = std::char::from_u32(unicode).and_then(|character| {
+            let face_index = self.face_index(character)?;
+            let (metrics, bitmap) = self.faces[face_index].rasterize(character, size);
+            self.add_glyph(metrics, bitmap)
+        });
+
+        self.glyph_map.insert(key, index);
+
+        index
+    }
+
+    /// Returns kerning (horizontal offset that should be added to the advance of the left
+    /// character) for the given pair of characters of the given size. Kerning is applied only
+    /// if both characters are taken from the same face.
+    pub fn kerning(&self, left: u32, right: u32, size: f32) -> f32 {
+        let (left, right) = match (std::char::from_u32(left), std::char::from_u32(right)) {
+            (Some(left), Some(right)) => (left, right),
+            _ => return 0.0,
+        };
+
+        match (self.face_index(left), self.face_index(right)) {
+            (Some(left_face), Some(right_face)) if left_face == right_face => self.faces[left_face]
+                .horizontal_kern(left, right, size)
+                .unwrap_or_default(),
+            _ => 0.0,
+        }
+    }
+
+    fn add_glyph(&mut self, metrics: fontdue::Metrics, pixels: Vec<u8>) -> Option<usize> {
+        let border = Self::GLYPH_BORDER;
+        let (width, height) = (metrics.width + border, metrics.height + border);
+
+        let bounds = loop {
+            if let Some(bounds) = self.packer.find_free(width, height) {
+                break bounds;
+            }
+
+            if self.atlas_size >= Self::MAX_ATLAS_SIZE {
+                println!("Insufficient atlas size!");
+                return None;
+            }
+
+            self.resize_atlas(
+                (self.atlas_size * 2).clamp(Self::INITIAL_ATLAS_SIZE, Self::MAX_ATLAS_SIZE),
+            );
+        };
+
+        let bx = bounds.x() + border / 2;
+        let by = bounds.y() + border / 2;
+
+        // Copy glyph pixels to atlas pixels
+        for (src_row, row) in (by..by + metrics.height).enumerate() {
+            for (src_col, col) in (bx..bx + metrics.width).enumerate() {
+                self.atlas[row * self.atlas_size + col] = pixels[src_row * metrics.width + src_col];
+            }
+        }
+
+        let mut glyph = FontGlyph {
+            left: metrics.xmin as f32,
+            top: metrics.ymin as f32,
+            pixels,
+            advance: metrics.advance_width,
+            tex_coords: Default::default(),
+            bitmap_width: metrics.width,
+            bitmap_height: metrics.height,
+            atlas_position: Vector2::new(bx, by),
+        };
+        glyph.tex_coords = Self::tex_coords(&glyph, self.atlas_size);
+        self.glyphs.push(glyph);
+
+        // Atlas has changed and must be re-uploaded to GPU.
+        self.texture = None;
+
+        Some(self.glyphs.len() - 1)
+    }
+
+    /// Grows the atlas to the new size. Existing content of the atlas is kept at the same place,
+    /// so positions of the glyphs (in pixels) remain valid.
+    fn resize_atlas(&mut self, new_size: usize) {
+        let old_size = self.atlas_size;
+        let mut atlas = vec![0; new_size * new_size];
+        for row in 0..old_size {
+            atlas[row * new_size..row * new_size + old_size]
+                .copy_from_slice(&self.atlas[row * old_size..(row + 1) * old_size]);
+        }
+        self.atlas = atlas;
+        self.atlas_size = new_size;
+
+        self.packer = RectPacker::new(new_size, new_size);
+        if old_size > 0 {
+            // Occupy the region with old content, the first rectangle is always placed at the origin.
+            self.packer.find_free(old_size, old_size);
+        }
+
+        for glyph in self.glyphs.iter_mut() {
+            glyph.tex_coords = Self::tex_coords(glyph, new_size);
+        }
+
+        self.texture = None;
+    }
+
+    fn tex_coords(glyph: &FontGlyph, atlas_size: usize) -> [Vector2<f32>; 4] {
+        let k = 1.0 / atlas_size as f32;
+        let tx = glyph.atlas_position.x as f32 * k;
+        let ty = glyph.atlas_position.y as f32 * k;
+        let tw = glyph.bitmap_width as f32 * k;
+        let th = glyph.bitmap_height as f32 * k;
+        [
+            Vector2::new(tx, ty),
+            Vector2::new(tx + tw, ty),
+            Vector2::new(tx + tw, ty + th),
+            Vector2::new(tx, ty + th),
+        ]
+    }
+
+    /// Returns a glyph of the given character of the default height, if it is in the cache.
     #[inline]
     pub fn glyph(&self, unicode: u32) -> Option<&FontGlyph> {
-        match self.char_map.get(&unicode) {
-            Some(glyph_index) => self.glyphs.get(*glyph_index),
-            None => None,
-        }
+        self.glyph_index(unicode)
+            .and_then(|glyph_index| self.glyphs.get(glyph_index))
     }
 
+    /// Returns index of a glyph of the given character of the default height, if it is in the cache.
     #[inline]
     pub fn glyph_index(&self, unicode: u32) -> Option<usize> {
-        self.char_map.get(&unicode).cloned()
+        self.glyph_map
+            .get(&GlyphKey::new(unicode, self.height))
+            .cloned()
+            .flatten()
     }
 
+    /// Returns all cached glyphs of all sizes.
     #[inline]
     pub fn glyphs(&self) -> &[FontGlyph] {
         &self.glyphs
     }
 
+    /// Returns default height of the font.
     #[inline]
     pub fn height(&self) -> f32 {
         self.height
     }
 
+    /// Returns ascender of the font of the default height.
     #[inline]
     pub fn ascender(&self) -> f32 {
         self.ascender
     }
 
+    /// Returns descender of the font of the default height.
     #[inline]
     pub fn descender(&self) -> f32 {
         self.descender
     }
 
+    /// Returns ascender of the font of the given size.
+    #[inline]
+    pub fn ascender_at(&self, size: f32) -> f32 {
+        self.faces[0]
+            .horizontal_line_metrics(size)
+            .map_or(size, |metrics| metrics.ascent)
+    }
+
+    /// Returns descender of the font of the given size.
+    #[inline]
+    pub fn descender_at(&self, size: f32) -> f32 {
+        self.faces[0]
+            .horizontal_line_metrics(size)
+            .map_or(0.0, |metrics| metrics.descent)
+    }
+
     #[inline]
     pub fn atlas_pixels(&self) -> &[u8] {
         self.atlas.as_slice()
@@ -249,54 +475,13 @@ impl Font {
         self.glyph(c).map_or(self.height(), |glyph| glyph.advance)
     }
 
+    /// Returns advance of the glyph of the given character of the given size, the glyph is
+    /// rasterized if needed.
     #[inline]
-    fn compute_atlas_size(&self, border: usize) -> usize {
-        let mut area = 0.0;
-        for glyph in self.glyphs.iter() {
-            area += (glyph.bitmap_width + border) as f32 * (glyph.bitmap_height + border) as f32;
-        }
-        (1.3 * area.sqrt()) as usize
-    }
-
-    fn pack(&mut self) {
-        let border = 2;
-        self.atlas_size = self.compute_atlas_size(border);
-        self.atlas = vec![0; self.atlas_size * self.atlas_size];
-        let k = 1.0 / self.atlas_size as f32;
-        let mut rect_packer = RectPacker::new(self.atlas_size, self.atlas_size);
-        for glyph in self.glyphs.iter_mut() {
-            if let Some(bounds) =
-                rect_packer.find_free(glyph.bitmap_width + border, glyph.bitmap_height + border)
-            {
-                let bw = bounds.w() - border;
-                let bh = bounds.h() - border;
-                let bx = bounds.x() + border / 2;
-                let by = bounds.y() + border / 2;
-
-                let tw = bw as f32 * k;
-                let th = bh as f32 * k;
-                let tx = bx as f32 * k;
-                let ty = by as f32 * k;
-
-                glyph.tex_coords[0] = Vector2::new(tx, ty);
-                glyph.tex_coords[1] = Vector2::new(tx + tw, ty);
-                glyph.tex_coords[2] = Vector2::new(tx + tw, ty + th);
-                glyph.tex_coords[3] = Vector2::new(tx, ty + th);
-
-                let row_end = by + bh;
-                let col_end = bx + bw;
-
-                // Copy glyph pixels to atlas pixels
-                for (src_row, row) in (by..row_end).enumerate() {
-                    for (src_col, col) in (bx..col_end).enumerate() {
-                        self.atlas[row * self.atlas_size + col] =
-                            glyph.pixels[src_row * bw + src_col];
-                    }
-                }
-            } else {
-                println!("Insufficient atlas size!");
-            }
-        }
+    pub fn glyph_advance_at(&mut self, c: u32, size: f32) -> f32 {
+        self.cache_glyph(c, size)
+            .and_then(|index| self.glyphs.get(index))
+            .map_or(size, |glyph| glyph.advance)
     }
 }
 
@@ -359,3 +544,80 @@ impl<'a> FontBuilder<'a> {
             .unwrap_or_else(|| Font::default_char_set())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::ttf::{Font, FontBuilder};
+
+    fn test_font() -> Font {
+        FontBuilder::new()
+            .with_char_set(&[][..])
+            .build_from_memory(include_bytes!("../test_data/DejaVuSerif.ttf").as_slice())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_glyph_sizes() {
+        let mut font = FontBuilder::new().build_builtin().unwrap();
+
+        let default = font.glyph_index('A' as u32).unwrap();
+        assert_eq!(font.cache_glyph('A' as u32, font.height()), Some(default));
+
+        let large = font.cache_glyph('A' as u32, 32.0).unwrap();
+        assert_ne!(default, large);
+        assert_eq!(font.cache_glyph('A' as u32, 32.0), Some(large));
+        assert!(font.glyphs()[large].bitmap_height > font.glyphs()[default].bitmap_height);
+        assert!(font.glyph_advance_at('A' as u32, 32.0) > font.glyph_advance('A' as u32));
+    }
+
+    #[test]
+    fn test_atlas_growth() {
+        let mut font = FontBuilder::new()
+            .with_char_set(&[][..])
+            .build_builtin()
+            .unwrap();
+        assert_eq!(font.atlas_size(), Font::INITIAL_ATLAS_SIZE);
+
+        let first = font.cache_glyph('W' as u32, 64.0).unwrap();
+        let first_position = font.glyphs()[first].atlas_position;
+        let first_pixels = font.glyphs()[first].pixels.clone();
+
+        for unicode in 'A' as u32..='z' as u32 {
+            font.cache_glyph(unicode, 64.0).unwrap();
+        }
+        assert!(font.atlas_size() > Font::INITIAL_ATLAS_SIZE);
+        assert!(font.texture.is_none());
+
+        // Glyphs must stay at the same place after growth.
+        let glyph = &font.glyphs()[first];
+        assert_eq!(glyph.atlas_position, first_position);
+        for row in 0..glyph.bitmap_height {
+            let begin = (first_position.y + row) * font.atlas_size() + first_position.x;
+            assert_eq!(
+                &font.atlas_pixels()[begin..begin + glyph.bitmap_width],
+                &first_pixels[row * glyph.bitmap_width..(row + 1) * glyph.bitmap_width]
+            );
+        }
+    }
+
+    #[test]
+    fn test_fallback() {
+        let mut font = FontBuilder::new().build_builtin().unwrap();
+        assert_eq!(font.cache_glyph('Я' as u32, 16.0), None);
+
+        font.add_fallback(&test_font());
+        assert_eq!(font.fallback_count(), 1);
+        assert!(font.cache_glyph('Я' as u32, 16.0).is_some());
+    }
+
+    #[test]
+    fn test_kerning() {
+        let font = test_font();
+        assert!(font.kerning('A' as u32, 'V' as u32, 16.0) < 0.0);
+        assert_eq!(font.kerning('A' as u32, 'A' as u32, 16.0), 0.0);
+        // No kerning between glyphs of different faces.
+        let mut builtin = FontBuilder::new().build_builtin().unwrap();
+        builtin.add_fallback(&font);
+        assert_eq!(builtin.kerning('A' as u32, 'Я' as u32, 16.0), 0.0);
+    }
+}
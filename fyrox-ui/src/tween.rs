@@ -0,0 +1,656 @@
+//! Tweens animate widget properties over time, storyboards combine tweens into sequences.
+//!
+//! A [`Tween`] changes a single property of a widget (opacity, size, position, brush color or render
+//! transform) from one value to another during given amount of time, progress of the tween could be
+//! shaped by an [`Easing`] function. A [`Storyboard`] is a set of tweens with their own start times,
+//! it could be played once, looped or played back and forth. Storyboards are played by
+//! [`UserInterface::play_storyboard`] and advanced automatically in [`UserInterface::update`].
+//!
+//! Tweens do not modify widgets directly, instead they send usual widget messages (such as
+//! [`WidgetMessage::Opacity`]) to their targets, so every widget reacts to animated values exactly the
+//! same way as if the values were set by game code. When a storyboard is finished, the UI sends
+//! [`StoryboardMessage::Completed`] from its owner widget.
+//!
+//! ```rust
+//! # use fyrox_ui::{
+//! #     core::{algebra::Vector2, pool::Handle},
+//! #     tween::{Easing, StoryboardBuilder, Tween, TweenProperty},
+//! #     UiNode, UserInterface,
+//! # };
+//! fn show_window(ui: &mut UserInterface, window: Handle<UiNode>) {
+//!     let storyboard = StoryboardBuilder::new()
+//!         // Fade in and slide from the left at the same time...
+//!         .then(Tween::new(window, TweenProperty::Opacity { from: 0.0, to: 1.0 }, 0.3))
+//!         .with(
+//!             Tween::new(
+//!                 window,
+//!                 TweenProperty::Position {
+//!                     from: Vector2::new(-200.0, 100.0),
+//!                     to: Vector2::new(100.0, 100.0),
+//!                 },
+//!                 0.3,
+//!             )
+//!             .with_easing(Easing::QuadraticOut),
+//!         )
+//!         // ...then grow a bit.
+//!         .then(Tween::new(window, TweenProperty::Width { from: 300.0, to: 400.0 }, 0.2))
+//!         .build();
+//!
+//!     ui.play_storyboard(storyboard);
+//! }
+//! ```
+
+use crate::{
+    brush::Brush,
+    core::{
+        algebra::{Matrix3, Vector2},
+        color::Color,
+        curve::Curve,
+        pool::Handle,
+    },
+    define_constructor,
+    message::{MessageDirection, UiMessage},
+    widget::WidgetMessage,
+    UiNode, UserInterface,
+};
+use std::{
+    fmt::{Debug, Formatter},
+    rc::Rc,
+};
+
+/// Easing function defines how the progress of a tween changes over time.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Easing {
+    Linear,
+    QuadraticIn,
+    QuadraticOut,
+    QuadraticInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    /// Arbitrary easing curve. The curve maps normalized time in `[0; 1]` range to progress, which is
+    /// usually in `[0; 1]` range too, but could go beyond it to get "overshooting" animations.
+    Curve(Curve),
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+impl Easing {
+    /// Maps normalized time of a tween in `[0; 1]` range to its progress.
+    pub fn ease(&self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::QuadraticIn => t * t,
+            Easing::QuadraticOut => t * (2.0 - t),
+            Easing::QuadraticInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => {
+                let k = t - 1.0;
+                k * k * k + 1.0
+            }
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let k = 2.0 * t - 2.0;
+                    0.5 * k * k * k + 1.0
+                }
+            }
+            Easing::Curve(curve) => curve.value_at(t),
+        }
+    }
+}
+
+/// Render transform that could be animated by [`TweenProperty::RenderTransform`]. Scale and rotation
+/// are applied around the center of a widget.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TweenTransform {
+    pub translation: Vector2<f32>,
+    pub scale: Vector2<f32>,
+    /// Rotation angle in radians.
+    pub rotation: f32,
+}
+
+impl Default for TweenTransform {
+    fn default() -> Self {
+        Self {
+            translation: Vector2::default(),
+            scale: Vector2::new(1.0, 1.0),
+            rotation: 0.0,
+        }
+    }
+}
+
+impl TweenTransform {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            translation: self.translation.lerp(&other.translation, t),
+            scale: self.scale.lerp(&other.scale, t),
+            rotation: lerpf(self.rotation, other.rotation, t),
+        }
+    }
+
+    /// Builds a render transform matrix for a widget with given local position and size.
+    pub fn matrix(&self, position: Vector2<f32>, size: Vector2<f32>) -> Matrix3<f32> {
+        // Render transform is applied in the parent's space, so the pivot is the center of the
+        // widget in the parent's space.
+        let pivot = position + size.scale(0.5);
+        Matrix3::new_translation(&(self.translation + pivot))
+            * Matrix3::new_rotation(self.rotation)
+            * Matrix3::new_nonuniform_scaling(&self.scale)
+            * Matrix3::new_translation(&-pivot)
+    }
+}
+
+/// A callback for [`TweenProperty::Custom`], it receives the user interface, the target of a tween and
+/// eased progress of the tween.
+pub type TweenCallback = dyn Fn(&UserInterface, Handle<UiNode>, f32);
+
+/// A property of a widget that is animated by a tween.
+#[derive(Clone)]
+pub enum TweenProperty {
+    Opacity {
+        from: f32,
+        to: f32,
+    },
+    Width {
+        from: f32,
+        to: f32,
+    },
+    Height {
+        from: f32,
+        to: f32,
+    },
+    /// Desired position of a widget, see [`WidgetMessage::DesiredPosition`].
+    Position {
+        from: Vector2<f32>,
+        to: Vector2<f32>,
+    },
+    /// Solid background brush.
+    Background {
+        from: Color,
+        to: Color,
+    },
+    /// Solid foreground brush.
+    Foreground {
+        from: Color,
+        to: Color,
+    },
+    RenderTransform {
+        from: TweenTransform,
+        to: TweenTransform,
+    },
+    /// Custom property, the callback should send appropriate message(s) by itself.
+    Custom(Rc<TweenCallback>),
+}
+
+impl Debug for TweenProperty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TweenProperty::Opacity { from, to } => write!(f, "Opacity({from} -> {to})"),
+            TweenProperty::Width { from, to } => write!(f, "Width({from} -> {to})"),
+            TweenProperty::Height { from, to } => write!(f, "Height({from} -> {to})"),
+            TweenProperty::Position { from, to } => write!(f, "Position({from:?} -> {to:?})"),
+            TweenProperty::Background { from, to } => {
+                write!(f, "Background({from:?} -> {to:?})")
+            }
+            TweenProperty::Foreground { from, to } => {
+                write!(f, "Foreground({from:?} -> {to:?})")
+            }
+            TweenProperty::RenderTransform { from, to } => {
+                write!(f, "RenderTransform({from:?} -> {to:?})")
+            }
+            TweenProperty::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+fn lerpf(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+impl TweenProperty {
+    fn apply(&self, ui: &UserInterface, target: Handle<UiNode>, progress: f32) {
+        let message = match self {
+            TweenProperty::Opacity { from, to } => WidgetMessage::opacity(
+                target,
+                MessageDirection::ToWidget,
+                Some(lerpf(*from, *to, progress)),
+            ),
+            TweenProperty::Width { from, to } => WidgetMessage::width(
+                target,
+                MessageDirection::ToWidget,
+                lerpf(*from, *to, progress),
+            ),
+            TweenProperty::Height { from, to } => WidgetMessage::height(
+                target,
+                MessageDirection::ToWidget,
+                lerpf(*from, *to, progress),
+            ),
+            TweenProperty::Position { from, to } => WidgetMessage::desired_position(
+                target,
+                MessageDirection::ToWidget,
+                from.lerp(to, progress),
+            ),
+            TweenProperty::Background { from, to } => WidgetMessage::background(
+                target,
+                MessageDirection::ToWidget,
+                Brush::Solid(from.lerp(*to, progress)),
+            ),
+            TweenProperty::Foreground { from, to } => WidgetMessage::foreground(
+                target,
+                MessageDirection::ToWidget,
+                Brush::Solid(from.lerp(*to, progress)),
+            ),
+            TweenProperty::RenderTransform { from, to } => {
+                let node = ui.node(target);
+                WidgetMessage::render_transform(
+                    target,
+                    MessageDirection::ToWidget,
+                    from.lerp(to, progress)
+                        .matrix(node.actual_local_position(), node.actual_local_size()),
+                )
+            }
+            TweenProperty::Custom(callback) => {
+                callback(ui, target, progress);
+                return;
+            }
+        };
+
+        ui.send_message(message);
+    }
+}
+
+/// Animates a single property of a widget.
+#[derive(Clone, Debug)]
+pub struct Tween {
+    pub target: Handle<UiNode>,
+    pub property: TweenProperty,
+    /// Duration of the tween in seconds.
+    pub duration: f32,
+    /// Time (in seconds) from the start of a storyboard at which the tween starts.
+    pub delay: f32,
+    pub easing: Easing,
+}
+
+impl Tween {
+    pub fn new(target: Handle<UiNode>, property: TweenProperty, duration: f32) -> Self {
+        Self {
+            target,
+            property,
+            duration: duration.max(0.0),
+            delay: 0.0,
+            easing: Default::default(),
+        }
+    }
+
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.delay = delay.max(0.0);
+        self
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Returns time (from the start of a storyboard) at which the tween ends.
+    pub fn end(&self) -> f32 {
+        self.delay + self.duration
+    }
+
+    fn progress(&self, time: f32) -> f32 {
+        let t = if self.duration > 0.0 {
+            ((time - self.delay) / self.duration).clamp(0.0, 1.0)
+        } else if time >= self.delay {
+            1.0
+        } else {
+            0.0
+        };
+        self.easing.ease(t)
+    }
+}
+
+/// Defines what a storyboard does when it reaches its end.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LoopMode {
+    /// Storyboard is played once and then removed.
+    Once,
+    /// Storyboard starts over when it reaches its end.
+    Loop,
+    /// Storyboard is played backwards when it reaches its end, and forwards again when it reaches
+    /// its beginning.
+    PingPong,
+}
+
+impl Default for LoopMode {
+    fn default() -> Self {
+        Self::Once
+    }
+}
+
+/// A set of tweens with their own start times, see [`StoryboardBuilder`].
+#[derive(Clone, Debug)]
+pub struct Storyboard {
+    tweens: Vec<Tween>,
+    loop_mode: LoopMode,
+    owner: Handle<UiNode>,
+    duration: f32,
+    time: f32,
+    backwards: bool,
+    started: bool,
+    finished: bool,
+}
+
+impl Storyboard {
+    pub fn tweens(&self) -> &[Tween] {
+        &self.tweens
+    }
+
+    pub fn loop_mode(&self) -> LoopMode {
+        self.loop_mode
+    }
+
+    /// Returns a widget from which [`StoryboardMessage::Completed`] will be sent.
+    pub fn owner(&self) -> Handle<UiNode> {
+        self.owner
+    }
+
+    /// Returns total duration of the storyboard in seconds.
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    /// Returns current playback time of the storyboard in seconds.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Returns `true` if the storyboard has reached its end and will be removed.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Applies every tween that is active in `[from; to]` time span.
+    fn apply(&self, ui: &UserInterface, from: f32, to: f32) {
+        let (min, max) = if from < to { (from, to) } else { (to, from) };
+        for tween in self.tweens.iter() {
+            if tween.delay <= max && tween.end() >= min && ui.try_get_node(tween.target).is_some() {
+                tween.property.apply(ui, tween.target, tween.progress(to));
+            }
+        }
+    }
+
+    /// Advances the storyboard and sends messages with animated values. Returns `true` if the
+    /// storyboard is finished.
+    pub(crate) fn update(&mut self, dt: f32, ui: &UserInterface) -> bool {
+        let mut prev_time = self.time;
+        if !self.started {
+            // Make sure that tweens that start at zero will be applied on the first update.
+            self.started = true;
+            prev_time = 0.0;
+        }
+
+        if self.duration <= 0.0 {
+            self.apply(ui, 0.0, 0.0);
+            self.finished = true;
+            return true;
+        }
+
+        let mut time = if self.backwards {
+            self.time - dt
+        } else {
+            self.time + dt
+        };
+
+        let mut finished = false;
+        match self.loop_mode {
+            LoopMode::Once => {
+                if time >= self.duration {
+                    time = self.duration;
+                    finished = true;
+                }
+            }
+            LoopMode::Loop => {
+                if time >= self.duration {
+                    // Finish current cycle first, so every tween reaches its final value.
+                    self.apply(ui, prev_time, self.duration);
+                    prev_time = 0.0;
+                    time %= self.duration;
+                }
+            }
+            LoopMode::PingPong => {
+                if time >= self.duration {
+                    self.apply(ui, prev_time, self.duration);
+                    prev_time = self.duration;
+                    time = (2.0 * self.duration - time).max(0.0);
+                    self.backwards = true;
+                } else if time <= 0.0 {
+                    self.apply(ui, prev_time, 0.0);
+                    prev_time = 0.0;
+                    time = (-time).min(self.duration);
+                    self.backwards = false;
+                }
+            }
+        }
+
+        self.apply(ui, prev_time, time);
+        self.time = time;
+        self.finished = finished;
+
+        finished
+    }
+}
+
+/// Builds storyboards step by step. Every [`StoryboardBuilder::then`] starts a new step after every
+/// previously added tween, and every [`StoryboardBuilder::with`] adds a tween that runs in parallel
+/// with the current step. Delays of tweens are relative to the start of their step.
+pub struct StoryboardBuilder {
+    tweens: Vec<Tween>,
+    loop_mode: LoopMode,
+    owner: Handle<UiNode>,
+    step_start: f32,
+    end: f32,
+}
+
+impl Default for StoryboardBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StoryboardBuilder {
+    pub fn new() -> Self {
+        Self {
+            tweens: Default::default(),
+            loop_mode: Default::default(),
+            owner: Default::default(),
+            step_start: 0.0,
+            end: 0.0,
+        }
+    }
+
+    /// Adds a tween that starts after every previously added tween.
+    pub fn then(mut self, tween: Tween) -> Self {
+        self.step_start = self.end;
+        self.with(tween)
+    }
+
+    /// Adds a tween that starts at the same time as the current step.
+    pub fn with(mut self, mut tween: Tween) -> Self {
+        tween.delay += self.step_start;
+        self.end = self.end.max(tween.end());
+        self.tweens.push(tween);
+        self
+    }
+
+    pub fn with_loop_mode(mut self, loop_mode: LoopMode) -> Self {
+        self.loop_mode = loop_mode;
+        self
+    }
+
+    /// Sets a widget from which [`StoryboardMessage::Completed`] will be sent. By default it is the
+    /// target of the first tween.
+    pub fn with_owner(mut self, owner: Handle<UiNode>) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    pub fn build(self) -> Storyboard {
+        Storyboard {
+            owner: if self.owner.is_some() {
+                self.owner
+            } else {
+                self.tweens
+                    .first()
+                    .map(|tween| tween.target)
+                    .unwrap_or_default()
+            },
+            tweens: self.tweens,
+            loop_mode: self.loop_mode,
+            duration: self.end,
+            time: 0.0,
+            backwards: false,
+            started: false,
+            finished: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoryboardMessage {
+    /// Sent from the owner of a storyboard when the storyboard has finished. Looped storyboards
+    /// never finish, and storyboards stopped by [`UserInterface::stop_storyboard`] do not send it.
+    Completed(Handle<Storyboard>),
+}
+
+impl StoryboardMessage {
+    define_constructor!(StoryboardMessage:Completed => fn completed(Handle<Storyboard>), layout: false);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        border::BorderBuilder,
+        core::{algebra::Vector2, pool::Handle},
+        message::{MessageDirection, UiMessage},
+        tween::{LoopMode, Storyboard, StoryboardBuilder, StoryboardMessage, Tween, TweenProperty},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+
+    fn update(ui: &mut UserInterface, dt: f32) -> Vec<UiMessage> {
+        ui.update(Vector2::new(400.0, 400.0), dt);
+        let mut messages = Vec::new();
+        while let Some(message) = ui.poll_message() {
+            messages.push(message);
+        }
+        messages
+    }
+
+    fn completed(messages: &[UiMessage]) -> Option<Handle<Storyboard>> {
+        messages.iter().find_map(|m| {
+            m.data()
+                .map(|StoryboardMessage::Completed(storyboard)| *storyboard)
+        })
+    }
+
+    #[test]
+    fn test_storyboard_sequence() {
+        let mut ui = UserInterface::new(Vector2::new(400.0, 400.0));
+        let border = BorderBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+
+        let storyboard = ui.play_storyboard(
+            StoryboardBuilder::new()
+                .then(Tween::new(
+                    border,
+                    TweenProperty::Opacity { from: 0.0, to: 1.0 },
+                    1.0,
+                ))
+                .with(Tween::new(
+                    border,
+                    TweenProperty::Width {
+                        from: 0.0,
+                        to: 100.0,
+                    },
+                    0.5,
+                ))
+                .then(Tween::new(
+                    border,
+                    TweenProperty::Opacity { from: 1.0, to: 0.5 },
+                    1.0,
+                ))
+                .build(),
+        );
+        assert_eq!(ui.storyboard(storyboard).unwrap().duration(), 2.0);
+
+        update(&mut ui, 0.5);
+        assert_eq!(ui.node(border).opacity(), Some(0.5));
+        assert_eq!(ui.node(border).width(), 100.0);
+
+        update(&mut ui, 1.0);
+        assert_eq!(ui.node(border).opacity(), Some(0.75));
+
+        let messages = update(&mut ui, 1.0);
+        assert_eq!(ui.node(border).opacity(), Some(0.5));
+        assert_eq!(completed(&messages), Some(storyboard));
+        assert!(messages.iter().any(|m| m.destination() == border
+            && m.direction() == MessageDirection::FromWidget
+            && m.data::<StoryboardMessage>().is_some()));
+        assert!(!ui.is_storyboard_playing(storyboard));
+    }
+
+    #[test]
+    fn test_storyboard_loop_modes() {
+        let mut ui = UserInterface::new(Vector2::new(400.0, 400.0));
+        let border = BorderBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+
+        let tween = Tween::new(
+            border,
+            TweenProperty::Height {
+                from: 0.0,
+                to: 100.0,
+            },
+            1.0,
+        );
+
+        let looped = ui.play_storyboard(
+            StoryboardBuilder::new()
+                .then(tween.clone())
+                .with_loop_mode(LoopMode::Loop)
+                .build(),
+        );
+        update(&mut ui, 0.75);
+        assert_eq!(ui.node(border).height(), 75.0);
+        let messages = update(&mut ui, 0.5);
+        assert_eq!(ui.node(border).height(), 25.0);
+        assert_eq!(completed(&messages), None);
+        ui.stop_storyboard(looped);
+        assert!(!ui.is_storyboard_playing(looped));
+
+        ui.play_storyboard(
+            StoryboardBuilder::new()
+                .then(tween)
+                .with_loop_mode(LoopMode::PingPong)
+                .build(),
+        );
+        update(&mut ui, 0.75);
+        assert_eq!(ui.node(border).height(), 75.0);
+        update(&mut ui, 0.5);
+        assert_eq!(ui.node(border).height(), 75.0);
+        update(&mut ui, 0.5);
+        assert_eq!(ui.node(border).height(), 25.0);
+        update(&mut ui, 0.5);
+        assert_eq!(ui.node(border).height(), 25.0);
+    }
+}
@@ -1,5 +1,14 @@
 use crate::{
-    core::{algebra::Vector2, color::Color, math::Rect, math::Vector2Ext, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        math::Rect,
+        math::Vector2Ext,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     draw::{CommandTexture, Draw, DrawingContext},
     message::UiMessage,
     widget::{Widget, WidgetBuilder},
@@ -42,7 +51,53 @@ fn line_thickness_vector(a: Vector2<f32>, b: Vector2<f32>, thickness: f32) -> Ve
     }
 }
 
+impl Default for Primitive {
+    fn default() -> Self {
+        Self::Triangle {
+            points: Default::default(),
+        }
+    }
+}
+
 impl Primitive {
+    /// Creates new primitive from given id.
+    pub fn new(id: u32) -> Result<Self, String> {
+        match id {
+            0 => Ok(Self::Triangle {
+                points: Default::default(),
+            }),
+            1 => Ok(Self::Line {
+                begin: Default::default(),
+                end: Default::default(),
+                thickness: 0.0,
+            }),
+            2 => Ok(Self::Circle {
+                center: Default::default(),
+                radius: 0.0,
+                segments: 0,
+            }),
+            3 => Ok(Self::Rectangle {
+                rect: Default::default(),
+                thickness: 0.0,
+            }),
+            4 => Ok(Self::RectangleFilled {
+                rect: Default::default(),
+            }),
+            _ => Err(format!("Invalid primitive id {}!", id)),
+        }
+    }
+
+    /// Returns id of current primitive kind.
+    pub fn id(&self) -> u32 {
+        match self {
+            Self::Triangle { .. } => 0,
+            Self::Line { .. } => 1,
+            Self::Circle { .. } => 2,
+            Self::Rectangle { .. } => 3,
+            Self::RectangleFilled { .. } => 4,
+        }
+    }
+
     pub fn bounds(&self) -> (Vector2<f32>, Vector2<f32>) {
         match self {
             Primitive::Triangle { points } => {
@@ -79,7 +134,50 @@ impl Primitive {
     }
 }
 
-#[derive(Clone)]
+impl Visit for Primitive {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        let mut kind_id = self.id();
+        kind_id.visit("KindId", &mut region)?;
+        if region.is_reading() {
+            *self = Primitive::new(kind_id)?;
+        }
+
+        match self {
+            Primitive::Triangle { points } => points.visit("Points", &mut region)?,
+            Primitive::Line {
+                begin,
+                end,
+                thickness,
+            } => {
+                begin.visit("Begin", &mut region)?;
+                end.visit("End", &mut region)?;
+                thickness.visit("Thickness", &mut region)?;
+            }
+            Primitive::Circle {
+                center,
+                radius,
+                segments,
+            } => {
+                center.visit("Center", &mut region)?;
+                radius.visit("Radius", &mut region)?;
+                let mut segment_count = *segments as u32;
+                segment_count.visit("Segments", &mut region)?;
+                *segments = segment_count as usize;
+            }
+            Primitive::Rectangle { rect, thickness } => {
+                rect.visit("Rect", &mut region)?;
+                thickness.visit("Thickness", &mut region)?;
+            }
+            Primitive::RectangleFilled { rect } => rect.visit("Rect", &mut region)?,
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Visit)]
 pub struct VectorImage {
     pub widget: Widget,
     pub primitives: Vec<Primitive>,
@@ -87,6 +185,21 @@ pub struct VectorImage {
 
 crate::define_widget_deref!(VectorImage);
 
+impl Default for VectorImage {
+    fn default() -> Self {
+        Self {
+            widget: WidgetBuilder::new().build(),
+            primitives: Default::default(),
+        }
+    }
+}
+
+impl TypeUuidProvider for VectorImage {
+    fn type_uuid() -> Uuid {
+        uuid!("3bf9781b-5f59-4acc-b3ef-15e4074b56c2")
+    }
+}
+
 impl Control for VectorImage {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
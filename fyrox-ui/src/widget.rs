@@ -3,12 +3,16 @@ use crate::{
     core::{algebra::Vector2, math::Rect, pool::Handle},
     define_constructor,
     message::{CursorIcon, KeyCode, MessageDirection, UiMessage},
+    navigation::NavigationDirection,
+    style::Style,
     HorizontalAlignment, LayoutEvent, MouseButton, MouseState, RcUiNodeHandle, Thickness, UiNode,
     UserInterface, VerticalAlignment, BRUSH_FOREGROUND, BRUSH_PRIMARY,
 };
+use fxhash::FxHashSet;
 use fyrox_core::{
     algebra::{Matrix3, Point2},
     uuid::Uuid,
+    visitor::prelude::*,
 };
 use std::{
     any::Any,
@@ -291,6 +295,37 @@ pub enum WidgetMessage {
     /// A request to set new tooltip for a widget. Old tooltip will be removed only if its reference
     /// counter was 1.
     Tooltip(Option<RcUiNodeHandle>),
+
+    /// A request to set new style for a widget. The style will be resolved from the current theme of
+    /// the user interface and applied to the widget. See [`crate::style`] for more info.
+    ///
+    /// Direction: **From/To UI**
+    Style(Option<String>),
+
+    /// A request to set new tab index of a widget, see [`crate::navigation`] for more info.
+    ///
+    /// Direction: **To UI**
+    TabIndex(Option<usize>),
+
+    /// A request to include or exclude a widget from focus navigation, see [`crate::navigation`] for
+    /// more info.
+    ///
+    /// Direction: **To UI**
+    TabStop(bool),
+
+    /// A request to move keyboard focus from a widget (usually the focused one) in the given direction.
+    /// The message bubbles up, so a widget or its parent could handle it by itself (for example an open
+    /// dropdown list changes its selection), otherwise the focus is moved by the user interface. See
+    /// [`crate::navigation`] for more info.
+    ///
+    /// Direction: **To UI**
+    Navigate(NavigationDirection),
+
+    /// A request to activate a widget (usually the focused one) - the same as clicking on it, buttons
+    /// are clicked, check boxes are toggled, dropdown lists are opened or closed.
+    ///
+    /// Direction: **To UI**
+    Activate,
 }
 
 impl WidgetMessage {
@@ -325,8 +360,13 @@ impl WidgetMessage {
     define_constructor!(WidgetMessage:RenderTransform => fn render_transform(Matrix3<f32>), layout: false);
     define_constructor!(WidgetMessage:ContextMenu => fn context_menu(Option<RcUiNodeHandle>), layout: false);
     define_constructor!(WidgetMessage:Tooltip => fn tooltip(Option<RcUiNodeHandle>), layout: false);
+    define_constructor!(WidgetMessage:Style => fn style(Option<String>), layout: false);
     define_constructor!(WidgetMessage:Focus => fn focus(), layout: false);
     define_constructor!(WidgetMessage:Unfocus => fn unfocus(), layout: false);
+    define_constructor!(WidgetMessage:TabIndex => fn tab_index(Option<usize>), layout: false);
+    define_constructor!(WidgetMessage:TabStop => fn tab_stop(bool), layout: false);
+    define_constructor!(WidgetMessage:Navigate => fn navigate(NavigationDirection), layout: false);
+    define_constructor!(WidgetMessage:Activate => fn activate(), layout: false);
 
     // Internal messages. Do not use.
     define_constructor!(WidgetMessage:MouseDown => fn mouse_down(pos: Vector2<f32>, button: MouseButton), layout: false);
@@ -348,6 +388,11 @@ impl WidgetMessage {
 pub struct Widget {
     pub handle: Handle<UiNode>,
     pub name: String,
+    /// Name of a style of the widget in the theme of the user interface, see [`crate::style`].
+    pub style: Option<String>,
+    /// Names of style properties that were set explicitly (for example, by [`WidgetBuilder::with_background`]
+    /// or [`WidgetMessage::Background`]), such properties are not changed by the style of the widget.
+    pub style_overrides: FxHashSet<String>,
     /// Desired position relative to parent node
     pub desired_local_position: Vector2<f32>,
     /// Explicit width for node or automatic if NaN (means value is undefined). Default is NaN
@@ -391,6 +436,10 @@ pub struct Widget {
     pub tooltip_time: f32,
     pub context_menu: Option<RcUiNodeHandle>,
     pub clip_to_bounds: bool,
+    /// Position of the widget in the tab order, see [`crate::navigation`].
+    pub tab_index: Option<usize>,
+    /// Whether the widget could be focused by navigation or not, see [`crate::navigation`].
+    pub tab_stop: bool,
     pub layout_transform: Matrix3<f32>,
     pub render_transform: Matrix3<f32>,
     pub visual_transform: Matrix3<f32>,
@@ -415,6 +464,68 @@ pub struct Widget {
     pub clip_bounds: Cell<Rect<f32>>,
 }
 
+fn visit_usize(value: &mut usize, name: &str, visitor: &mut Visitor) -> VisitResult {
+    let mut value_u32 = *value as u32;
+    value_u32.visit(name, visitor)?;
+    *value = value_u32 as usize;
+    Ok(())
+}
+
+impl Visit for Widget {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        // Only "persistent" properties are serialized, layout state, tooltips, context menus and user
+        // data are runtime-only.
+        self.name.visit("Name", &mut region)?;
+        let _ = self.style.visit("Style", &mut region); // Backward compatibility.
+        let _ = self.style_overrides.visit("StyleOverrides", &mut region); // Backward compatibility.
+        self.desired_local_position
+            .visit("DesiredLocalPosition", &mut region)?;
+        self.width.visit("Width", &mut region)?;
+        self.height.visit("Height", &mut region)?;
+        self.min_size.visit("MinSize", &mut region)?;
+        self.max_size.visit("MaxSize", &mut region)?;
+        self.background.visit("Background", &mut region)?;
+        self.foreground.visit("Foreground", &mut region)?;
+        visit_usize(&mut self.row, "Row", &mut region)?;
+        visit_usize(&mut self.column, "Column", &mut region)?;
+        self.vertical_alignment
+            .visit("VerticalAlignment", &mut region)?;
+        self.horizontal_alignment
+            .visit("HorizontalAlignment", &mut region)?;
+        self.margin.visit("Margin", &mut region)?;
+        self.visibility.visit("Visibility", &mut region)?;
+        self.children.visit("Children", &mut region)?;
+        self.parent.visit("Parent", &mut region)?;
+        self.hit_test_visibility
+            .visit("HitTestVisibility", &mut region)?;
+        visit_usize(&mut self.z_index, "ZIndex", &mut region)?;
+        self.allow_drag.visit("AllowDrag", &mut region)?;
+        self.allow_drop.visit("AllowDrop", &mut region)?;
+        self.draw_on_top.visit("DrawOnTop", &mut region)?;
+        self.enabled.visit("Enabled", &mut region)?;
+        self.cursor.visit("Cursor", &mut region)?;
+        self.opacity.visit("Opacity", &mut region)?;
+        self.tooltip_time.visit("TooltipTime", &mut region)?;
+        self.clip_to_bounds.visit("ClipToBounds", &mut region)?;
+        let mut tab_index = self.tab_index.map(|i| i as u32);
+        let _ = tab_index.visit("TabIndex", &mut region); // Backward compatibility.
+        self.tab_index = tab_index.map(|i| i as usize);
+        let _ = self.tab_stop.visit("TabStop", &mut region); // Backward compatibility.
+        self.layout_transform
+            .visit("LayoutTransform", &mut region)?;
+        self.render_transform
+            .visit("RenderTransform", &mut region)?;
+        self.preview_messages
+            .visit("PreviewMessages", &mut region)?;
+        self.handle_os_events.visit("HandleOsEvents", &mut region)?;
+        self.id.visit("Id", &mut region)?;
+
+        Ok(())
+    }
+}
+
 impl Widget {
     #[inline]
     pub fn handle(&self) -> Handle<UiNode> {
@@ -432,6 +543,43 @@ impl Widget {
         self
     }
 
+    /// Returns a name of the style of the widget (if any).
+    #[inline]
+    pub fn style(&self) -> Option<&str> {
+        self.style.as_deref()
+    }
+
+    /// Returns `true` if the property with the given name was set explicitly and must not be changed by
+    /// the style of the widget.
+    #[inline]
+    pub fn is_style_overridden(&self, property: &str) -> bool {
+        self.style_overrides.contains(property)
+    }
+
+    /// Marks the property with the given name as set explicitly, so it will not be changed by the style
+    /// of the widget.
+    #[inline]
+    pub fn override_style_property(&mut self, property: &str) {
+        if !self.style_overrides.contains(property) {
+            self.style_overrides.insert(property.to_owned());
+        }
+    }
+
+    /// Applies common properties (background, foreground, margin) of the given style to the widget.
+    /// Properties that are not defined in the style are left untouched.
+    pub fn apply_style(&mut self, style: &Style) {
+        if let Some(background) = style.brush(Style::BACKGROUND) {
+            self.background = background;
+        }
+        if let Some(foreground) = style.brush(Style::FOREGROUND) {
+            self.foreground = foreground;
+        }
+        if let Some(margin) = style.thickness(Style::MARGIN) {
+            self.margin = margin;
+        }
+        self.invalidate_layout();
+    }
+
     #[inline]
     pub fn actual_local_size(&self) -> Vector2<f32> {
         self.actual_local_size.get()
@@ -531,6 +679,14 @@ impl Widget {
         }
     }
 
+    /// Requests recalculation of visual transforms without re-arranging the widget.
+    #[inline]
+    pub fn invalidate_visual_transform(&self) {
+        if let Some(layout_events_sender) = self.layout_events_sender.as_ref() {
+            let _ = layout_events_sender.send(LayoutEvent::TransformChanged(self.handle));
+        }
+    }
+
     #[inline]
     pub fn is_hit_test_visible(&self) -> bool {
         self.hit_test_visibility
@@ -765,8 +921,14 @@ impl Widget {
             if let Some(msg) = msg.data::<WidgetMessage>() {
                 match msg {
                     &WidgetMessage::Opacity(opacity) => self.opacity = opacity,
-                    WidgetMessage::Background(background) => self.background = background.clone(),
-                    WidgetMessage::Foreground(foreground) => self.foreground = foreground.clone(),
+                    WidgetMessage::Background(background) => {
+                        self.background = background.clone();
+                        self.override_style_property(Style::BACKGROUND);
+                    }
+                    WidgetMessage::Foreground(foreground) => {
+                        self.foreground = foreground.clone();
+                        self.override_style_property(Style::FOREGROUND);
+                    }
                     WidgetMessage::Name(name) => self.name = name.clone(),
                     &WidgetMessage::Width(width) => {
                         if self.width != width {
@@ -817,6 +979,7 @@ impl Widget {
                         }
                     }
                     &WidgetMessage::Margin(margin) => {
+                        self.override_style_property(Style::MARGIN);
                         if self.margin != margin {
                             self.margin = margin;
                             self.invalidate_layout();
@@ -846,8 +1009,17 @@ impl Widget {
                             self.invalidate_layout();
                         }
                     }
-                    WidgetMessage::RenderTransform(transform) => {
+                    WidgetMessage::RenderTransform(transform)
+                        if &self.render_transform != transform =>
+                    {
                         self.render_transform = *transform;
+                        self.invalidate_visual_transform();
+                    }
+                    &WidgetMessage::TabIndex(tab_index) => {
+                        self.tab_index = tab_index;
+                    }
+                    &WidgetMessage::TabStop(tab_stop) => {
+                        self.tab_stop = tab_stop;
                     }
                     _ => (),
                 }
@@ -1010,6 +1182,28 @@ impl Widget {
         self.enabled
     }
 
+    #[inline]
+    pub fn set_tab_index(&mut self, tab_index: Option<usize>) -> &mut Self {
+        self.tab_index = tab_index;
+        self
+    }
+
+    #[inline]
+    pub fn tab_index(&self) -> Option<usize> {
+        self.tab_index
+    }
+
+    #[inline]
+    pub fn set_tab_stop(&mut self, tab_stop: bool) -> &mut Self {
+        self.tab_stop = tab_stop;
+        self
+    }
+
+    #[inline]
+    pub fn is_tab_stop(&self) -> bool {
+        self.tab_stop
+    }
+
     #[inline]
     pub fn set_cursor(&mut self, cursor: Option<CursorIcon>) {
         self.cursor = cursor;
@@ -1097,6 +1291,9 @@ macro_rules! define_widget_deref {
 
 pub struct WidgetBuilder {
     pub name: String,
+    pub style: Option<String>,
+    /// Names of style properties that were set explicitly, see [`Widget::style_overrides`].
+    pub style_overrides: FxHashSet<String>,
     pub width: f32,
     pub height: f32,
     pub desired_position: Vector2<f32>,
@@ -1128,6 +1325,9 @@ pub struct WidgetBuilder {
     pub layout_transform: Matrix3<f32>,
     pub render_transform: Matrix3<f32>,
     pub clip_to_bounds: bool,
+    pub tab_index: Option<usize>,
+    /// `None` means that the control decides whether it is a tab stop or not.
+    pub tab_stop: Option<bool>,
     pub id: Uuid,
 }
 
@@ -1141,6 +1341,8 @@ impl WidgetBuilder {
     pub fn new() -> Self {
         Self {
             name: Default::default(),
+            style: None,
+            style_overrides: Default::default(),
             width: f32::NAN,
             height: f32::NAN,
             vertical_alignment: VerticalAlignment::Stretch,
@@ -1172,6 +1374,8 @@ impl WidgetBuilder {
             layout_transform: Matrix3::identity(),
             render_transform: Matrix3::identity(),
             clip_to_bounds: true,
+            tab_index: None,
+            tab_stop: None,
             id: Uuid::new_v4(),
         }
     }
@@ -1228,11 +1432,13 @@ impl WidgetBuilder {
 
     pub fn with_background(mut self, brush: Brush) -> Self {
         self.background = Some(brush);
+        self.override_style_property(Style::BACKGROUND);
         self
     }
 
     pub fn with_foreground(mut self, brush: Brush) -> Self {
         self.foreground = Some(brush);
+        self.override_style_property(Style::FOREGROUND);
         self
     }
 
@@ -1248,6 +1454,7 @@ impl WidgetBuilder {
 
     pub fn with_margin(mut self, margin: Thickness) -> Self {
         self.margin = margin;
+        self.override_style_property(Style::MARGIN);
         self
     }
 
@@ -1297,6 +1504,35 @@ impl WidgetBuilder {
         self
     }
 
+    /// Sets a name of the style of the widget. The style will be resolved from the theme of the user
+    /// interface when the widget is added to it. See [`crate::style`] for more info.
+    pub fn with_style(mut self, style: &str) -> Self {
+        self.style = Some(String::from(style));
+        self
+    }
+
+    /// Marks the property with the given name as set explicitly, so it will not be changed by the style
+    /// of the widget. Background, foreground and margin are marked automatically when they are set using
+    /// the builder.
+    pub fn override_style_property(&mut self, property: &str) {
+        if !self.style_overrides.contains(property) {
+            self.style_overrides.insert(property.to_owned());
+        }
+    }
+
+    /// Sets a position of the widget in the tab order, see [`crate::navigation`] for more info.
+    pub fn with_tab_index(mut self, tab_index: Option<usize>) -> Self {
+        self.tab_index = tab_index;
+        self
+    }
+
+    /// Defines whether the widget could be focused by navigation or not. Interactive controls (buttons,
+    /// check boxes, etc.) are tab stops by default. See [`crate::navigation`] for more info.
+    pub fn with_tab_stop(mut self, tab_stop: bool) -> Self {
+        self.tab_stop = Some(tab_stop);
+        self
+    }
+
     pub fn with_hit_test_visibility(mut self, state: bool) -> Self {
         self.is_hit_test_visible = state;
         self
@@ -1374,6 +1610,8 @@ impl WidgetBuilder {
         Widget {
             handle: Default::default(),
             name: self.name,
+            style: self.style,
+            style_overrides: self.style_overrides,
             desired_local_position: self.desired_position,
             width: self.width,
             height: self.height,
@@ -1422,6 +1660,8 @@ impl WidgetBuilder {
             render_transform: self.render_transform,
             visual_transform: Matrix3::identity(),
             clip_to_bounds: self.clip_to_bounds,
+            tab_index: self.tab_index,
+            tab_stop: self.tab_stop.unwrap_or_default(),
             id: self.id,
         }
     }
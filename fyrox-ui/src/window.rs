@@ -1,17 +1,17 @@
 use crate::{
     border::BorderBuilder,
-    brush::Brush,
     button::{ButtonBuilder, ButtonMessage},
-    core::{algebra::Vector2, color::Color, math::Rect, pool::Handle},
+    core::{algebra::Vector2, math::Rect, pool::Handle},
     decorator::DecoratorBuilder,
     define_constructor,
     grid::{Column, GridBuilder, Row},
     message::{CursorIcon, MessageDirection, UiMessage},
+    style::Theme,
     text::{Text, TextBuilder, TextMessage},
     vector_image::{Primitive, VectorImageBuilder},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, NodeHandleMapping, RestrictionEntry, Thickness,
-    UiNode, UserInterface, VerticalAlignment, BRUSH_BRIGHT, BRUSH_LIGHT, BRUSH_LIGHTEST,
+    UiNode, UserInterface, VerticalAlignment, BRUSH_BRIGHT,
 };
 use std::{
     any::{Any, TypeId},
@@ -770,13 +770,9 @@ fn make_mark(ctx: &mut BuildContext, button: HeaderButton) -> Handle<UiNode> {
 fn make_header_button(ctx: &mut BuildContext, button: HeaderButton) -> Handle<UiNode> {
     ButtonBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
         .with_back(
-            DecoratorBuilder::new(
-                BorderBuilder::new(WidgetBuilder::new())
-                    .with_stroke_thickness(Thickness::uniform(0.0)),
-            )
-            .with_normal_brush(Brush::Solid(Color::TRANSPARENT))
-            .with_hover_brush(BRUSH_LIGHT)
-            .with_pressed_brush(BRUSH_LIGHTEST)
+            DecoratorBuilder::new(BorderBuilder::new(
+                WidgetBuilder::new().with_style(Theme::WINDOW_HEADER_BUTTON),
+            ))
             .build(ctx),
         )
         .with_content(make_mark(ctx, button))
@@ -867,15 +863,13 @@ impl WindowBuilder {
         let maximize_button;
         let close_button;
 
-        let blue_tint = Brush::Solid(Color::opaque(60, 60, 64));
-
         let title;
         let title_grid;
         let header = BorderBuilder::new(
             WidgetBuilder::new()
                 .with_horizontal_alignment(HorizontalAlignment::Stretch)
                 .with_height(22.0)
-                .with_background(blue_tint.clone())
+                .with_style(Theme::WINDOW_HEADER)
                 .with_child({
                     title_grid = GridBuilder::new(
                         WidgetBuilder::new()
@@ -933,7 +927,6 @@ impl WindowBuilder {
                 })
                 .on_row(0),
         )
-        .with_stroke_thickness(Thickness::uniform(0.0))
         .build(ctx);
 
         if self.content.is_some() {
@@ -945,7 +938,7 @@ impl WindowBuilder {
                 .with_visibility(self.open)
                 .with_child(
                     BorderBuilder::new(
-                        WidgetBuilder::new().with_foreground(blue_tint).with_child(
+                        WidgetBuilder::new().with_style(Theme::WINDOW).with_child(
                             GridBuilder::new(
                                 WidgetBuilder::new()
                                     .with_child(self.content)
@@ -957,7 +950,6 @@ impl WindowBuilder {
                             .build(ctx),
                         ),
                     )
-                    .with_stroke_thickness(Thickness::uniform(1.0))
                     .build(ctx),
                 )
                 .build(),
@@ -1,7 +1,14 @@
 #![allow(clippy::reversed_empty_ranges)]
 
 use crate::{
-    core::{algebra::Vector2, math::Rect, pool::Handle},
+    core::{
+        algebra::Vector2,
+        math::Rect,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     message::UiMessage,
     widget::{Widget, WidgetBuilder},
     BuildContext, Control, Orientation, UiNode, UserInterface,
@@ -12,15 +19,28 @@ use std::{
     ops::{Deref, DerefMut, Range},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct WrapPanel {
     pub widget: Widget,
     pub orientation: Orientation,
+    #[visit(skip)]
     pub lines: RefCell<Vec<Line>>,
 }
 
 crate::define_widget_deref!(WrapPanel);
 
+impl Default for WrapPanel {
+    fn default() -> Self {
+        Self::new(WidgetBuilder::new().build())
+    }
+}
+
+impl TypeUuidProvider for WrapPanel {
+    fn type_uuid() -> Uuid {
+        uuid!("d309dae7-02ea-4b86-82a3-b96bb4902118")
+    }
+}
+
 impl WrapPanel {
     pub fn new(widget: Widget) -> Self {
         Self {
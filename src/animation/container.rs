@@ -171,4 +171,63 @@ impl TrackDataContainer {
         }
         length
     }
+
+    /// Makes every key of the container relative to the given reference value, so the container could be used
+    /// as an additive one (see [`TrackValue::add_additive`]). Numeric keys are offset by respective components of
+    /// the reference value, rotation keys are replaced with Euler angles of the rotation relative to the reference.
+    /// The method does nothing if the type of the reference value does not match the kind of the container.
+    pub fn make_relative_to(&mut self, reference: &TrackValue) {
+        match (self.kind, reference) {
+            (TrackValueKind::UnitQuaternion, TrackValue::UnitQuaternion(reference)) => {
+                let inv_reference = reference.inverse();
+                // Rotation is defined by all three curves at once, so calculate new values first and only then
+                // modify the curves.
+                let new_values = self
+                    .curves
+                    .iter()
+                    .enumerate()
+                    .map(|(i, curve)| {
+                        curve
+                            .keys()
+                            .iter()
+                            .map(|key| match self.fetch(key.location()) {
+                                Some(TrackValue::UnitQuaternion(rotation)) => {
+                                    let (x, y, z) = (inv_reference * rotation).euler_angles();
+                                    [x, y, z].get(i).cloned().unwrap_or(key.value)
+                                }
+                                _ => key.value,
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+
+                for (curve, values) in self.curves.iter_mut().zip(new_values) {
+                    for (value, new_value) in curve.keys_values().zip(values) {
+                        *value = new_value;
+                    }
+                }
+            }
+            (TrackValueKind::Real, TrackValue::Real(reference)) => {
+                self.offset_keys(&[*reference]);
+            }
+            (TrackValueKind::Vector2, TrackValue::Vector2(reference)) => {
+                self.offset_keys(reference.as_slice());
+            }
+            (TrackValueKind::Vector3, TrackValue::Vector3(reference)) => {
+                self.offset_keys(reference.as_slice());
+            }
+            (TrackValueKind::Vector4, TrackValue::Vector4(reference)) => {
+                self.offset_keys(reference.as_slice());
+            }
+            _ => (),
+        }
+    }
+
+    fn offset_keys(&mut self, reference: &[f32]) {
+        for (curve, component) in self.curves.iter_mut().zip(reference) {
+            for value in curve.keys_values() {
+                *value -= *component;
+            }
+        }
+    }
 }
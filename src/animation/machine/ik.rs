@@ -0,0 +1,877 @@
+//! Inverse kinematics (IK) is used to post-process an animation pose to make some bone (or a chain of bones) reach
+//! a specific target. See [`IkChain`] docs for more info.
+
+use crate::{
+    animation::{
+        machine::{Parameter, ParameterContainer, PoseWeight},
+        value::{BoundValue, TrackValue, ValueBinding},
+        AnimationPose,
+    },
+    core::{
+        algebra::{Matrix3, Matrix4, Unit, UnitQuaternion, Vector3},
+        math::Matrix4Ext,
+        pool::Handle,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::{
+        graph::{Graph, NodePool},
+        node::Node,
+        transform::Transform,
+    },
+    utils::NameProvider,
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// A container of scene nodes that is used by IK solvers to fetch bones. It is implemented for both [`Graph`] and
+/// [`NodePool`], so the solvers could be used with or without a scene.
+pub trait IkNodeSource {
+    /// Tries to borrow a node using its handle.
+    fn try_get_node(&self, handle: Handle<Node>) -> Option<&Node>;
+}
+
+impl IkNodeSource for Graph {
+    fn try_get_node(&self, handle: Handle<Node>) -> Option<&Node> {
+        self.try_get(handle)
+    }
+}
+
+impl IkNodeSource for NodePool {
+    fn try_get_node(&self, handle: Handle<Node>) -> Option<&Node> {
+        self.try_borrow(handle)
+    }
+}
+
+/// A point in world space that an IK chain tries to reach.
+#[derive(Debug, Visit, Reflect, Clone, PartialEq, AsRefStr, EnumString, EnumVariantNames)]
+pub enum IkTarget {
+    /// Fixed position in world space. It is useful when the position is calculated by game code, for example by ray
+    /// casting the ground below a foot.
+    Position(Vector3<f32>),
+
+    /// World space position of a scene node. Animated nodes are taken at their animated position.
+    Node(Handle<Node>),
+}
+
+impl Default for IkTarget {
+    fn default() -> Self {
+        Self::Position(Default::default())
+    }
+}
+
+impl IkTarget {
+    fn position<N: IkNodeSource + ?Sized>(
+        &self,
+        pose: &AnimationPose,
+        nodes: &N,
+    ) -> Option<Vector3<f32>> {
+        match self {
+            IkTarget::Position(position) => Some(*position),
+            IkTarget::Node(node) => global_transform(pose, nodes, *node).map(|m| m.position()),
+        }
+    }
+}
+
+/// Analytic solver for chains of two bones (for example upper arm + forearm, or thigh + shin). It is the fastest and
+/// the most stable solver, it should be preferred for limbs.
+#[derive(Default, Debug, Visit, Reflect, Clone, PartialEq)]
+pub struct TwoBoneIk {
+    /// The first bone of the chain (for example a thigh).
+    pub root: Handle<Node>,
+
+    /// The second bone of the chain (for example a shin), it must be a descendant of the root bone.
+    pub middle: Handle<Node>,
+
+    /// The end of the chain (for example a foot), it must be a descendant of the middle bone. This bone will be placed
+    /// at the target position, its rotation is not modified.
+    pub end: Handle<Node>,
+
+    /// Optional point that defines the direction in which the middle joint bends (for example a point in front of a
+    /// knee). If not set, the bending plane of the animated pose is preserved.
+    pub pole: Option<IkTarget>,
+}
+
+impl TwoBoneIk {
+    /// Creates new two bone solver.
+    pub fn new(root: Handle<Node>, middle: Handle<Node>, end: Handle<Node>) -> Self {
+        Self {
+            root,
+            middle,
+            end,
+            pole: None,
+        }
+    }
+
+    /// Sets a point, that defines the direction in which the middle joint bends.
+    pub fn with_pole(mut self, pole: IkTarget) -> Self {
+        self.pole = Some(pole);
+        self
+    }
+
+    fn solve(&self, chain: &mut BoneChain, target: Vector3<f32>, pole: Option<Vector3<f32>>) {
+        let a = chain.position(0);
+        let b = chain.position(1);
+        let c = chain.position(2);
+
+        let upper_len = (b - a).norm();
+        let lower_len = (c - b).norm();
+        if upper_len <= f32::EPSILON || lower_len <= f32::EPSILON {
+            return;
+        }
+
+        let eps = 1.0e-4;
+        let target_len = (target - a)
+            .norm()
+            .clamp(eps, (upper_len + lower_len) * (1.0 - eps));
+
+        // Bend the middle joint first, so the distance between the root and the end will be equal to the distance
+        // between the root and the target.
+        let to_root = a - b;
+        let to_end = c - b;
+        let current_angle = to_root.angle(&to_end);
+        let desired_angle = ((upper_len * upper_len + lower_len * lower_len
+            - target_len * target_len)
+            / (2.0 * upper_len * lower_len))
+            .clamp(-1.0, 1.0)
+            .acos();
+        let bend_axis = to_root
+            .cross(&to_end)
+            .try_normalize(f32::EPSILON)
+            .or_else(|| {
+                // The chain is fully stretched, use the pole to find the bending plane.
+                pole.and_then(|pole| to_root.cross(&(pole - b)).try_normalize(f32::EPSILON))
+            })
+            .unwrap_or_else(|| any_orthogonal(&to_root));
+        chain.rotate(
+            1,
+            UnitQuaternion::from_axis_angle(
+                &Unit::new_unchecked(bend_axis),
+                desired_angle - current_angle,
+            ),
+        );
+
+        // Then aim the whole chain at the target.
+        let a = chain.position(0);
+        let c = chain.position(2);
+        if let Some(rotation) = UnitQuaternion::rotation_between(&(c - a), &(target - a)) {
+            chain.rotate(0, rotation);
+        }
+
+        // Finally twist the chain around root-target axis so the middle joint will look at the pole.
+        if let Some(pole) = pole {
+            let axis = target - a;
+            if let Some(axis) = axis.try_normalize(f32::EPSILON) {
+                let b = chain.position(1);
+                let project = |v: Vector3<f32>| v - axis.scale(v.dot(&axis));
+                if let Some(rotation) =
+                    UnitQuaternion::rotation_between(&project(b - a), &project(pole - a))
+                {
+                    chain.rotate(0, rotation);
+                }
+            }
+        }
+    }
+}
+
+/// Iterative solver for chains of arbitrary length (for example a spine, a tail or a tentacle). See [`IkSolver::Fabrik`]
+/// and [`IkSolver::Ccd`] for the description of available algorithms.
+#[derive(Debug, Visit, Reflect, Clone, PartialEq)]
+pub struct ChainIk {
+    /// Bones of the chain starting from the root. Each bone must be a descendant of the previous one. The last bone is
+    /// the end of the chain, it will be placed at the target position, its rotation is not modified.
+    pub bones: Vec<Handle<Node>>,
+
+    /// Maximum amount of iterations of the solver.
+    #[reflect(min_value = 1.0)]
+    pub iterations: u32,
+
+    /// The solver stops when the distance between the end of the chain and the target is less than this value.
+    #[reflect(min_value = 0.0)]
+    pub tolerance: f32,
+}
+
+impl Default for ChainIk {
+    fn default() -> Self {
+        Self {
+            bones: Default::default(),
+            iterations: 10,
+            tolerance: 0.001,
+        }
+    }
+}
+
+impl ChainIk {
+    /// Creates new chain solver with 10 iterations and 1 millimeter tolerance.
+    pub fn new(bones: Vec<Handle<Node>>) -> Self {
+        Self {
+            bones,
+            ..Default::default()
+        }
+    }
+
+    /// Sets maximum amount of iterations of the solver.
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the distance at which the target is considered reached.
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    fn solve_fabrik(&self, chain: &mut BoneChain, target: Vector3<f32>) {
+        let count = chain.len();
+        let mut positions = (0..count).map(|i| chain.position(i)).collect::<Vec<_>>();
+        let lengths = positions
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).norm())
+            .collect::<Vec<_>>();
+        let root = positions[0];
+        let total_length = lengths.iter().sum::<f32>();
+
+        let direction = |from: Vector3<f32>, to: Vector3<f32>| {
+            (to - from)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::y)
+        };
+
+        if (target - root).norm() >= total_length {
+            // Target is unreachable, stretch the chain towards it.
+            let dir = direction(root, target);
+            for i in 1..count {
+                positions[i] = positions[i - 1] + dir.scale(lengths[i - 1]);
+            }
+        } else {
+            for _ in 0..self.iterations {
+                if (positions[count - 1] - target).norm() <= self.tolerance {
+                    break;
+                }
+
+                // Backward pass - from the end to the root.
+                positions[count - 1] = target;
+                for i in (0..count - 1).rev() {
+                    positions[i] = positions[i + 1]
+                        + direction(positions[i + 1], positions[i]).scale(lengths[i]);
+                }
+
+                // Forward pass - from the root to the end.
+                positions[0] = root;
+                for i in 1..count {
+                    positions[i] = positions[i - 1]
+                        + direction(positions[i - 1], positions[i]).scale(lengths[i - 1]);
+                }
+            }
+        }
+
+        // Convert new positions to rotations of the bones.
+        for i in 0..count - 1 {
+            let current = chain.position(i + 1) - chain.position(i);
+            let desired = positions[i + 1] - positions[i];
+            if let Some(rotation) = UnitQuaternion::rotation_between(&current, &desired) {
+                chain.rotate(i, rotation);
+            }
+        }
+    }
+
+    fn solve_ccd(&self, chain: &mut BoneChain, target: Vector3<f32>) {
+        let end = chain.len() - 1;
+        for _ in 0..self.iterations {
+            if (chain.position(end) - target).norm() <= self.tolerance {
+                break;
+            }
+
+            for i in (0..end).rev() {
+                let joint = chain.position(i);
+                let to_end = chain.position(end) - joint;
+                let to_target = target - joint;
+                if let Some(rotation) = UnitQuaternion::rotation_between(&to_end, &to_target) {
+                    chain.rotate(i, rotation);
+                }
+            }
+        }
+    }
+}
+
+/// Rotates a single bone so that its axis points at the target. It could be used to make a head look at something,
+/// or to aim a weapon held in hands.
+#[derive(Debug, Visit, Reflect, Clone, PartialEq)]
+pub struct LookAtIk {
+    /// A bone to rotate.
+    pub bone: Handle<Node>,
+
+    /// An axis of the bone (in its local space) that should point at the target.
+    pub axis: Vector3<f32>,
+
+    /// Maximum angle (in radians) the bone can be rotated by from its animated orientation. Zero means no limit.
+    #[reflect(min_value = 0.0)]
+    pub max_angle: f32,
+}
+
+impl Default for LookAtIk {
+    fn default() -> Self {
+        Self {
+            bone: Default::default(),
+            axis: Vector3::z(),
+            max_angle: 0.0,
+        }
+    }
+}
+
+impl LookAtIk {
+    /// Creates new look-at solver for the given bone, that will point its local Z axis at the target.
+    pub fn new(bone: Handle<Node>) -> Self {
+        Self {
+            bone,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the axis of the bone (in its local space) that should point at the target.
+    pub fn with_axis(mut self, axis: Vector3<f32>) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    /// Sets maximum angle (in radians) the bone can be rotated by. Zero means no limit.
+    pub fn with_max_angle(mut self, max_angle: f32) -> Self {
+        self.max_angle = max_angle;
+        self
+    }
+
+    fn solve(&self, chain: &mut BoneChain, target: Vector3<f32>) {
+        let axis = chain.global(0).transform_vector(&self.axis);
+        let to_target = target - chain.position(0);
+        if let Some(rotation) = UnitQuaternion::rotation_between(&axis, &to_target) {
+            let rotation = match rotation.axis_angle() {
+                Some((axis, angle)) if self.max_angle > 0.0 && angle > self.max_angle => {
+                    UnitQuaternion::from_axis_angle(&axis, self.max_angle)
+                }
+                _ => rotation,
+            };
+            chain.rotate(0, rotation);
+        }
+    }
+}
+
+/// Inverse kinematics algorithm with its settings.
+#[derive(Debug, Visit, Reflect, Clone, PartialEq, AsRefStr, EnumString, EnumVariantNames)]
+pub enum IkSolver {
+    /// See [`TwoBoneIk`] docs.
+    TwoBone(TwoBoneIk),
+
+    /// Forward And Backward Reaching Inverse Kinematics. Moves joints along straight lines in turns from the end
+    /// and from the root of the chain. Converges fast and produces natural-looking smooth poses.
+    Fabrik(ChainIk),
+
+    /// Cyclic Coordinate Descent. Rotates each joint (starting from the end of the chain) to point the end at the
+    /// target. Simple and robust, but tends to bend the joints closer to the end more.
+    Ccd(ChainIk),
+
+    /// See [`LookAtIk`] docs.
+    LookAt(LookAtIk),
+}
+
+impl Default for IkSolver {
+    fn default() -> Self {
+        Self::TwoBone(Default::default())
+    }
+}
+
+impl IkSolver {
+    fn bones(&self) -> Vec<Handle<Node>> {
+        match self {
+            IkSolver::TwoBone(two_bone) => vec![two_bone.root, two_bone.middle, two_bone.end],
+            IkSolver::Fabrik(chain) | IkSolver::Ccd(chain) => chain.bones.clone(),
+            IkSolver::LookAt(look_at) => vec![look_at.bone],
+        }
+    }
+}
+
+/// IK chain post-processes an animation pose and rotates a set of bones, so the end of the chain will reach a specific
+/// target. Typical use cases are planting feet on uneven ground, making hands reach an object, or making a head look
+/// at something.
+///
+/// IK chains are defined on animation layers (see [`super::MachineLayer::add_ik_chain`]) and applied to the final pose
+/// of the animation blending state machine in layer order, which means that a chain defined on a layer will see the
+/// results of the chains of previous layers. The influence of a chain is defined by its weight, which could be
+/// taken from a machine parameter. This way IK could be smoothly enabled and disabled from game code or by the state
+/// machine itself.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     animation::machine::{
+///         ik::{IkChain, IkSolver, IkTarget, TwoBoneIk},
+///         MachineLayer, PoseWeight,
+///     },
+///     core::{algebra::Vector3, pool::Handle},
+/// };
+///
+/// // Assume that these are correct handles.
+/// let thigh = Handle::default();
+/// let shin = Handle::default();
+/// let foot = Handle::default();
+///
+/// let mut layer = MachineLayer::new();
+///
+/// layer.add_ik_chain(
+///     IkChain::new(
+///         "LeftFoot",
+///         IkSolver::TwoBone(TwoBoneIk::new(thigh, shin, foot)),
+///         // This position should be updated every frame, for example by ray casting the ground.
+///         IkTarget::Position(Vector3::new(0.1, 0.0, 0.3)),
+///     )
+///     .with_weight(PoseWeight::Parameter("LeftFootIkWeight".to_string())),
+/// );
+/// ```
+#[derive(Debug, Visit, Reflect, Clone, PartialEq)]
+pub struct IkChain {
+    /// Name of the chain, it could be used to find the chain in a layer.
+    pub name: String,
+
+    /// Algorithm that is used to solve the chain.
+    pub solver: IkSolver,
+
+    /// A point the end of the chain tries to reach.
+    pub target: IkTarget,
+
+    /// Influence of the chain on the animation pose in `[0; 1]` range. `0.0` - animated pose is not modified,
+    /// `1.0` - pose is fully defined by the solver.
+    pub weight: PoseWeight,
+
+    /// Whether the chain is enabled or not. Disabled chains does not modify animation pose.
+    pub enabled: bool,
+}
+
+impl Default for IkChain {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            solver: Default::default(),
+            target: Default::default(),
+            weight: PoseWeight::Constant(1.0),
+            enabled: true,
+        }
+    }
+}
+
+impl NameProvider for IkChain {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl IkChain {
+    /// Creates new enabled IK chain with unit weight.
+    pub fn new<S: AsRef<str>>(name: S, solver: IkSolver, target: IkTarget) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            solver,
+            target,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the weight of the chain.
+    pub fn with_weight(mut self, weight: PoseWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Calculates actual weight of the chain using the given set of parameters.
+    pub fn weight(&self, parameters: &ParameterContainer) -> f32 {
+        let weight = match self.weight {
+            PoseWeight::Constant(value) => value,
+            PoseWeight::Parameter(ref param_id) => {
+                if let Some(Parameter::Weight(weight)) = parameters.get(param_id) {
+                    *weight
+                } else {
+                    0.0
+                }
+            }
+        };
+        weight.clamp(0.0, 1.0)
+    }
+
+    /// Modifies rotations of the bones of the chain in the given pose, so the end of the chain will reach the target.
+    /// `nodes` are used to fetch the bones that are not animated and parent nodes of the chain. `scale` is an additional
+    /// factor for the weight of the chain (for example, a weight of a layer).
+    pub fn solve<N: IkNodeSource + ?Sized>(
+        &self,
+        pose: &mut AnimationPose,
+        nodes: &N,
+        parameters: &ParameterContainer,
+        scale: f32,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        let weight = self.weight(parameters) * scale;
+        if weight <= 0.0 {
+            return;
+        }
+
+        let target = match self.target.position(pose, nodes) {
+            Some(target) => target,
+            None => return,
+        };
+
+        let mut chain = match BoneChain::new(self.solver.bones(), pose, nodes) {
+            Some(chain) => chain,
+            None => return,
+        };
+
+        match self.solver {
+            IkSolver::TwoBone(ref two_bone) => {
+                let pole = two_bone
+                    .pole
+                    .as_ref()
+                    .and_then(|pole| pole.position(pose, nodes));
+                two_bone.solve(&mut chain, target, pole)
+            }
+            IkSolver::Fabrik(ref fabrik) => fabrik.solve_fabrik(&mut chain, target),
+            IkSolver::Ccd(ref ccd) => ccd.solve_ccd(&mut chain, target),
+            IkSolver::LookAt(ref look_at) => look_at.solve(&mut chain, target),
+        }
+
+        chain.write(pose, weight);
+    }
+}
+
+fn any_orthogonal(v: &Vector3<f32>) -> Vector3<f32> {
+    let other = if v.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    v.cross(&other)
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(Vector3::z)
+}
+
+fn rotation_of(matrix: &Matrix4<f32>) -> UnitQuaternion<f32> {
+    let basis = matrix.basis();
+    let normalize = |v: Vector3<f32>| v.try_normalize(f32::EPSILON).unwrap_or_default();
+    let basis = Matrix3::from_columns(&[
+        normalize(basis.column(0).into_owned()),
+        normalize(basis.column(1).into_owned()),
+        normalize(basis.column(2).into_owned()),
+    ]);
+    UnitQuaternion::from_matrix(&basis)
+}
+
+/// Returns local transform of the node with animated properties from the pose.
+fn posed_local_transform(pose: &AnimationPose, handle: Handle<Node>, node: &Node) -> Transform {
+    let mut transform = node.local_transform().clone();
+    if let Some(node_pose) = pose.poses().get(&handle) {
+        for bound_value in node_pose.values.values.iter() {
+            match (&bound_value.binding, &bound_value.value) {
+                (ValueBinding::Position, TrackValue::Vector3(position)) => {
+                    transform.set_position(*position);
+                }
+                (ValueBinding::Rotation, TrackValue::UnitQuaternion(rotation)) => {
+                    transform.set_rotation(*rotation);
+                }
+                (ValueBinding::Scale, TrackValue::Vector3(scale)) => {
+                    transform.set_scale(*scale);
+                }
+                _ => (),
+            }
+        }
+    }
+    transform
+}
+
+/// Calculates global transform of the node using animated properties from the pose. Cached global transforms of nodes
+/// could not be used here, because they're calculated for the previous pose.
+fn global_transform<N: IkNodeSource + ?Sized>(
+    pose: &AnimationPose,
+    nodes: &N,
+    handle: Handle<Node>,
+) -> Option<Matrix4<f32>> {
+    let mut node = nodes.try_get_node(handle)?;
+    let mut transform = posed_local_transform(pose, handle, node).matrix();
+    while node.parent().is_some() {
+        let parent = node.parent();
+        node = nodes.try_get_node(parent)?;
+        transform = posed_local_transform(pose, parent, node).matrix() * transform;
+    }
+    Some(transform)
+}
+
+fn set_rotation(pose: &mut AnimationPose, node: Handle<Node>, rotation: UnitQuaternion<f32>) {
+    if let Some(bound_value) = pose.poses_mut().get_mut(&node).and_then(|node_pose| {
+        node_pose
+            .values
+            .values
+            .iter_mut()
+            .find(|bound_value| bound_value.binding == ValueBinding::Rotation)
+    }) {
+        bound_value.value = TrackValue::UnitQuaternion(rotation);
+        return;
+    }
+
+    pose.add_to_node_pose(
+        node,
+        BoundValue {
+            binding: ValueBinding::Rotation,
+            value: TrackValue::UnitQuaternion(rotation),
+        },
+    );
+}
+
+/// Intermediate state of a chain of bones, that is used by the solvers.
+struct BoneChain {
+    handles: Vec<Handle<Node>>,
+    locals: Vec<Transform>,
+    initial_rotations: Vec<UnitQuaternion<f32>>,
+    // Transforms from the global space of the previous bone to the parent space of a bone. For the first bone it
+    // is global transform of its parent.
+    links: Vec<Matrix4<f32>>,
+    globals: Vec<Matrix4<f32>>,
+}
+
+impl BoneChain {
+    fn new<N: IkNodeSource + ?Sized>(
+        handles: Vec<Handle<Node>>,
+        pose: &AnimationPose,
+        nodes: &N,
+    ) -> Option<Self> {
+        if handles.is_empty() {
+            return None;
+        }
+
+        let mut locals = Vec::with_capacity(handles.len());
+        let mut links = Vec::with_capacity(handles.len());
+        let mut globals = Vec::<Matrix4<f32>>::with_capacity(handles.len());
+
+        for &handle in handles.iter() {
+            let node = nodes.try_get_node(handle)?;
+            let local = posed_local_transform(pose, handle, node);
+            let parent_global = if node.parent().is_some() {
+                global_transform(pose, nodes, node.parent())?
+            } else {
+                Matrix4::identity()
+            };
+            let link = match globals.last() {
+                Some(previous) => previous.try_inverse()? * parent_global,
+                None => parent_global,
+            };
+            globals.push(parent_global * local.matrix());
+            links.push(link);
+            locals.push(local);
+        }
+
+        Some(Self {
+            initial_rotations: locals.iter().map(|l| **l.rotation()).collect(),
+            handles,
+            locals,
+            links,
+            globals,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    fn parent_global(&self, i: usize) -> Matrix4<f32> {
+        if i == 0 {
+            self.links[0]
+        } else {
+            self.globals[i - 1] * self.links[i]
+        }
+    }
+
+    fn global(&self, i: usize) -> &Matrix4<f32> {
+        &self.globals[i]
+    }
+
+    fn position(&self, i: usize) -> Vector3<f32> {
+        self.globals[i].position()
+    }
+
+    /// Rotates a bone by the given world space rotation around its origin.
+    fn rotate(&mut self, i: usize, rotation: UnitQuaternion<f32>) {
+        let frame = rotation_of(&self.parent_global(i)) * **self.locals[i].pre_rotation();
+        let local_rotation = **self.locals[i].rotation();
+        self.locals[i].set_rotation(frame.inverse() * rotation * frame * local_rotation);
+
+        for j in i..self.len() {
+            self.globals[j] = self.parent_global(j) * self.locals[j].matrix();
+        }
+    }
+
+    fn write(&self, pose: &mut AnimationPose, weight: f32) {
+        for ((handle, local), initial) in self
+            .handles
+            .iter()
+            .zip(self.locals.iter())
+            .zip(self.initial_rotations.iter())
+        {
+            let solved = **local.rotation();
+            if solved != *initial {
+                set_rotation(pose, *handle, initial.nlerp(&solved, weight));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            machine::{
+                ik::{ChainIk, IkChain, IkSolver, IkTarget, LookAtIk, TwoBoneIk},
+                Parameter, ParameterContainer, PoseWeight,
+            },
+            AnimationPose,
+        },
+        core::{algebra::Vector3, math::Matrix4Ext, pool::Handle},
+        scene::{
+            base::BaseBuilder, graph::Graph, node::Node, pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+
+    // Creates a straight chain of bones along Y axis with the given length of each bone.
+    fn make_chain(graph: &mut Graph, count: usize, length: f32) -> Vec<Handle<Node>> {
+        let mut bones = Vec::new();
+        let mut parent = graph.get_root();
+        for i in 0..count {
+            let bone = PivotBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(
+                            0.0,
+                            if i == 0 { 0.0 } else { length },
+                            0.0,
+                        ))
+                        .build(),
+                ),
+            )
+            .build(graph);
+            graph.link_nodes(bone, parent);
+            bones.push(bone);
+            parent = bone;
+        }
+        bones
+    }
+
+    fn solve(graph: &mut Graph, chain: &IkChain, parameters: &ParameterContainer) {
+        let mut pose = AnimationPose::default();
+        chain.solve(&mut pose, graph, parameters, 1.0);
+        pose.apply(graph);
+        graph.update_hierarchical_data();
+    }
+
+    fn end_position(graph: &Graph, bones: &[Handle<Node>]) -> Vector3<f32> {
+        graph[*bones.last().unwrap()].global_position()
+    }
+
+    #[test]
+    fn test_two_bone() {
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 3, 1.0);
+        let target = Vector3::new(1.0, 1.0, 0.0);
+        let chain = IkChain::new(
+            "Test",
+            IkSolver::TwoBone(
+                TwoBoneIk::new(bones[0], bones[1], bones[2])
+                    .with_pole(IkTarget::Position(Vector3::new(0.0, 1.0, 1.0))),
+            ),
+            IkTarget::Position(target),
+        );
+        solve(&mut graph, &chain, &Default::default());
+        assert!((end_position(&graph, &bones) - target).norm() < 1.0e-3);
+        // Middle joint must bend towards the pole.
+        assert!(graph[bones[1]].global_position().z > 0.1);
+    }
+
+    #[test]
+    fn test_unreachable_target() {
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 3, 1.0);
+        let chain = IkChain::new(
+            "Test",
+            IkSolver::TwoBone(TwoBoneIk::new(bones[0], bones[1], bones[2])),
+            IkTarget::Position(Vector3::new(5.0, 0.0, 0.0)),
+        );
+        solve(&mut graph, &chain, &Default::default());
+        let end = end_position(&graph, &bones);
+        assert!((end - Vector3::new(2.0, 0.0, 0.0)).norm() < 1.0e-2);
+    }
+
+    #[test]
+    fn test_fabrik_and_ccd() {
+        for make_solver in [IkSolver::Fabrik, IkSolver::Ccd] {
+            let mut graph = Graph::new();
+            let bones = make_chain(&mut graph, 5, 0.5);
+            let target = Vector3::new(1.0, 1.0, 0.5);
+            let chain = IkChain::new(
+                "Test",
+                make_solver(ChainIk::new(bones.clone()).with_iterations(64)),
+                IkTarget::Position(target),
+            );
+            solve(&mut graph, &chain, &Default::default());
+            assert!((end_position(&graph, &bones) - target).norm() < 1.0e-2);
+            // Lengths of bones must be preserved.
+            for pair in bones.windows(2) {
+                let length =
+                    (graph[pair[1]].global_position() - graph[pair[0]].global_position()).norm();
+                assert!((length - 0.5).abs() < 1.0e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_look_at() {
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 1, 1.0);
+        let chain = IkChain::new(
+            "Test",
+            IkSolver::LookAt(LookAtIk::new(bones[0])),
+            IkTarget::Position(Vector3::new(10.0, 0.0, 0.0)),
+        );
+        solve(&mut graph, &chain, &Default::default());
+        let look = graph[bones[0]].global_transform().look().normalize();
+        assert!((look - Vector3::x()).norm() < 1.0e-3);
+    }
+
+    #[test]
+    fn test_node_target_and_weight_parameter() {
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 1, 1.0);
+        let target = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 10.0, 0.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        let chain = IkChain::new(
+            "Test",
+            IkSolver::LookAt(LookAtIk::new(bones[0])),
+            IkTarget::Node(target),
+        )
+        .with_weight(PoseWeight::Parameter("Weight".to_string()));
+
+        let mut parameters = ParameterContainer::default();
+        parameters.add("Weight", Parameter::Weight(0.0));
+
+        // Zero weight must not modify the pose.
+        let mut pose = AnimationPose::default();
+        chain.solve(&mut pose, &graph, &parameters, 1.0);
+        assert!(pose.poses().is_empty());
+
+        *parameters.get_mut("Weight").unwrap() = Parameter::Weight(1.0);
+        solve(&mut graph, &chain, &parameters);
+        let look = graph[bones[0]].global_transform().look().normalize();
+        assert!((look - Vector3::y()).norm() < 1.0e-3);
+    }
+}
@@ -4,8 +4,8 @@
 use crate::{
     animation::{
         machine::{
-            event::FixedEventQueue, Event, LayerMask, ParameterContainer, PoseNode, State,
-            Transition,
+            event::FixedEventQueue, ik::IkChain, Event, LayerMask, ParameterContainer, PoseNode,
+            State, Transition,
         },
         Animation, AnimationContainer, AnimationPose,
     },
@@ -17,6 +17,31 @@ use crate::{
     },
     utils::{self, NameProvider},
 };
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines how a pose of a layer is combined with the poses of the previous layers of a state machine.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum LayerBlendMode {
+    /// The pose of the layer is blended with the poses of the previous layers using the weight of the layer.
+    #[default]
+    Override,
+    /// The pose of the layer is added on top of the poses of the previous layers, scaled by the weight of the layer.
+    /// Layers of this kind should play additive animations (see [`Animation::make_additive`]), for example breathing
+    /// or recoil, that will be combined with any animation of the previous layers.
+    Additive,
+}
 
 /// Layer is a separate state graph. Layers mainly used to animate different parts of humanoid (but not only) characters. For
 /// example there could a layer for upper body and a layer for lower body. Upper body layer could contain animations for aiming,
@@ -70,6 +95,12 @@ pub struct MachineLayer {
 
     mask: LayerMask,
 
+    #[visit(optional)] // Backward compatibility
+    blend_mode: LayerBlendMode,
+
+    #[visit(optional)] // Backward compatibility
+    ik_chains: Vec<IkChain>,
+
     #[reflect(hidden)]
     nodes: Pool<PoseNode>,
 
@@ -124,6 +155,8 @@ impl MachineLayer {
             events: FixedEventQueue::new(2048),
             debug: false,
             mask: Default::default(),
+            blend_mode: Default::default(),
+            ik_chains: Default::default(),
         }
     }
 
@@ -384,6 +417,59 @@ impl MachineLayer {
         &self.mask
     }
 
+    /// Sets new blend mode of the layer and returns the previous one. See docs of [`LayerBlendMode`] for more info.
+    #[inline]
+    pub fn set_blend_mode(&mut self, blend_mode: LayerBlendMode) -> LayerBlendMode {
+        std::mem::replace(&mut self.blend_mode, blend_mode)
+    }
+
+    /// Returns current blend mode of the layer.
+    #[inline]
+    pub fn blend_mode(&self) -> LayerBlendMode {
+        self.blend_mode
+    }
+
+    /// Adds a new IK chain to the layer and returns its index. IK chains of all layers are applied to the final pose of the
+    /// parent state machine, see [`IkChain`] docs for more info.
+    #[inline]
+    pub fn add_ik_chain(&mut self, chain: IkChain) -> usize {
+        self.ik_chains.push(chain);
+        self.ik_chains.len() - 1
+    }
+
+    /// Removes an IK chain at given index. Panics if index is out-of-bounds.
+    #[inline]
+    pub fn remove_ik_chain(&mut self, index: usize) -> IkChain {
+        self.ik_chains.remove(index)
+    }
+
+    /// Returns a shared reference to the list of IK chains of the layer.
+    #[inline]
+    pub fn ik_chains(&self) -> &[IkChain] {
+        &self.ik_chains
+    }
+
+    /// Returns a mutable reference to the list of IK chains of the layer. It could be used to change targets of the chains.
+    #[inline]
+    pub fn ik_chains_mut(&mut self) -> &mut [IkChain] {
+        &mut self.ik_chains
+    }
+
+    /// Tries to find an IK chain by its name. Returns index of the chain and its reference.
+    #[inline]
+    pub fn find_ik_chain_by_name_ref<S: AsRef<str>>(&self, name: S) -> Option<(usize, &IkChain)> {
+        utils::find_by_name_ref(self.ik_chains.iter().enumerate(), name)
+    }
+
+    /// Tries to find an IK chain by its name. Returns index of the chain and its reference.
+    #[inline]
+    pub fn find_ik_chain_by_name_mut<S: AsRef<str>>(
+        &mut self,
+        name: S,
+    ) -> Option<(usize, &mut IkChain)> {
+        utils::find_by_name_mut(self.ik_chains.iter_mut().enumerate(), name)
+    }
+
     /// Returns final pose of the layer.
     #[inline]
     pub fn pose(&self) -> &AnimationPose {
@@ -15,7 +15,8 @@ use crate::{
 };
 
 pub use event::Event;
-pub use layer::MachineLayer;
+pub use ik::{IkChain, IkNodeSource, IkSolver, IkTarget};
+pub use layer::{LayerBlendMode, MachineLayer};
 pub use mask::LayerMask;
 pub use node::{
     blend::{BlendAnimations, BlendAnimationsByIndex, BlendPose, IndexedBlendInput},
@@ -27,6 +28,7 @@ pub use state::State;
 pub use transition::Transition;
 
 pub mod event;
+pub mod ik;
 pub mod layer;
 pub mod mask;
 pub mod node;
@@ -52,6 +54,8 @@ pub mod transition;
 /// `Layer` - is a separate state graph, there could be any number of layers - each with its own mask.
 /// `Mask` - a set of handles to nodes which will be excluded from animation on a layer.
 /// `Pose` - a final result of blending multiple animation into one.
+/// `IK Chain` - a set of bones that is rotated after blending, so the end of the chain will reach a specific target
+/// (see [`IkChain`] docs for more info).
 ///
 /// Summarizing everything of this, we can describe animation blending state machine as a state graph, where each state has its
 /// own sub-graph (tree) that provides animation for blending. States can be connected via transitions.
@@ -295,9 +299,32 @@ impl Machine {
 
         for layer in self.layers.iter_mut() {
             let weight = layer.weight();
+            let blend_mode = layer.blend_mode();
             let pose = layer.evaluate_pose(animations, &self.parameters, dt);
 
-            self.final_pose.blend_with(pose, weight);
+            match blend_mode {
+                LayerBlendMode::Override => self.final_pose.blend_with(pose, weight),
+                LayerBlendMode::Additive => self.final_pose.add_additive(pose, weight),
+            }
+        }
+
+        &self.final_pose
+    }
+
+    /// Post-processes the final pose of the machine using IK chains of every layer (in layer order). Weight of each
+    /// chain is multiplied by the weight of its layer. This method must be called after [`Self::evaluate_pose`]; `nodes`
+    /// are used to fetch bones, that are not animated, and the parents of the chains.
+    #[inline]
+    pub fn solve_ik<N: IkNodeSource + ?Sized>(&mut self, nodes: &N) -> &AnimationPose {
+        for layer in self.layers.iter() {
+            for chain in layer.ik_chains() {
+                chain.solve(
+                    &mut self.final_pose,
+                    nodes,
+                    &self.parameters,
+                    layer.weight(),
+                );
+            }
         }
 
         &self.final_pose
@@ -779,6 +779,55 @@ impl Animation {
     pub fn pose(&self) -> &AnimationPose {
         &self.pose
     }
+
+    /// Calculates a pose of the animation at the given time position. Unlike [`Self::set_time_position`], this method
+    /// does not modify the animation.
+    pub fn pose_at(&self, time: f32) -> AnimationPose {
+        let mut pose = AnimationPose::default();
+        for track in self.tracks.iter() {
+            if track.is_enabled() {
+                if let Some(bound_value) = track.fetch(time) {
+                    pose.add_to_node_pose(track.target(), bound_value);
+                }
+            }
+        }
+        pose
+    }
+
+    /// Converts the animation into an additive animation, that stores differences between the animation and the
+    /// given reference pose. Additive animations could be added on top of any other animation (see
+    /// [`AnimationPose::add_additive`]) - it is useful for small motions like breathing, recoil, leaning and so on,
+    /// that should be combined with every other animation of a character. Tracks that are not present in the
+    /// reference pose are made relative to their own values at the beginning of the time slice of the animation.
+    ///
+    /// Typical reference pose is the first frame of the animation itself (see [`Self::pose_at`]), or a pose of some
+    /// "idle" animation.
+    pub fn make_additive(&mut self, reference: &AnimationPose) {
+        let start_pose = self.pose_at(self.time_slice.start);
+
+        for track in self.tracks.iter_mut() {
+            let find_reference = |pose: &AnimationPose| {
+                pose.poses().get(&track.target()).and_then(|node_pose| {
+                    node_pose
+                        .values
+                        .values
+                        .iter()
+                        .find(|bound_value| &bound_value.binding == track.binding())
+                        .map(|bound_value| bound_value.value.clone())
+                })
+            };
+
+            if let Some(reference_value) =
+                find_reference(reference).or_else(|| find_reference(&start_pose))
+            {
+                track
+                    .data_container_mut()
+                    .make_relative_to(&reference_value);
+            }
+        }
+
+        self.update_pose();
+    }
 }
 
 impl Default for Animation {
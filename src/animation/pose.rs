@@ -37,6 +37,12 @@ impl NodePose {
     pub fn blend_with(&mut self, other: &NodePose, weight: f32) {
         self.values.blend_with(&other.values, weight)
     }
+
+    /// Adds an additive pose scaled by the given weight to the current pose. See [`super::value::TrackValue::add_additive`]
+    /// docs for more info.
+    pub fn add_additive(&mut self, additive: &NodePose, weight: f32) {
+        self.values.add_additive(&additive.values, weight)
+    }
 }
 
 /// Animations pose is a set of node poses. See [`NodePose`] docs for more info.
@@ -83,6 +89,18 @@ impl AnimationPose {
             .blend_with(&other.root_motion.clone().unwrap_or_default(), weight);
     }
 
+    /// Adds an additive animation pose (a pose of an additive animation, see [`super::Animation::make_additive`]) scaled
+    /// by the weight coefficient to the current animation pose. Unlike blending, additive poses are layered on top of the
+    /// current pose, so small motions (for example breathing, or recoil) could be combined with any other animation.
+    /// Only the properties that exist in both poses are affected, root motion is not changed.
+    pub fn add_additive(&mut self, additive: &AnimationPose, weight: f32) {
+        for (handle, additive_pose) in additive.poses.iter() {
+            if let Some(current_pose) = self.poses.get_mut(handle) {
+                current_pose.add_additive(additive_pose, weight);
+            }
+        }
+    }
+
     fn add_node_pose(&mut self, local_pose: NodePose) {
         self.poses.insert(local_pose.node, local_pose);
     }
@@ -154,3 +172,129 @@ impl AnimationPose {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            pose::AnimationPose,
+            value::{BoundValue, TrackValue, ValueBinding},
+        },
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            pool::Handle,
+        },
+        scene::node::Node,
+    };
+
+    fn make_pose(
+        node: Handle<Node>,
+        position: Vector3<f32>,
+        rotation: UnitQuaternion<f32>,
+    ) -> AnimationPose {
+        let mut pose = AnimationPose::default();
+        pose.add_to_node_pose(
+            node,
+            BoundValue {
+                binding: ValueBinding::Position,
+                value: TrackValue::Vector3(position),
+            },
+        );
+        pose.add_to_node_pose(
+            node,
+            BoundValue {
+                binding: ValueBinding::Rotation,
+                value: TrackValue::UnitQuaternion(rotation),
+            },
+        );
+        pose
+    }
+
+    fn position_and_rotation(
+        pose: &AnimationPose,
+        node: Handle<Node>,
+    ) -> (Vector3<f32>, UnitQuaternion<f32>) {
+        let values = &pose.poses()[&node].values.values;
+        match (&values[0].value, &values[1].value) {
+            (TrackValue::Vector3(position), TrackValue::UnitQuaternion(rotation)) => {
+                (*position, *rotation)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_relative_value() {
+        let reference = TrackValue::Vector3(Vector3::new(1.0, 2.0, 3.0));
+        let value = TrackValue::Vector3(Vector3::new(2.0, 2.0, 1.0));
+        assert_eq!(
+            value.relative_to(&reference),
+            TrackValue::Vector3(Vector3::new(1.0, 0.0, -2.0))
+        );
+
+        let reference_rotation = UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3);
+        let rotation = UnitQuaternion::from_euler_angles(0.5, -0.2, 0.0);
+        if let TrackValue::UnitQuaternion(delta) = TrackValue::UnitQuaternion(rotation)
+            .relative_to(&TrackValue::UnitQuaternion(reference_rotation))
+        {
+            assert!((reference_rotation * delta).angle_to(&rotation) < 1.0e-5);
+        } else {
+            unreachable!()
+        }
+
+        // Mismatched types are kept as is.
+        assert_eq!(
+            TrackValue::Real(1.0).relative_to(&reference),
+            TrackValue::Real(1.0)
+        );
+    }
+
+    #[test]
+    fn test_additive_pose() {
+        let node = Handle::new(1, 1);
+        let base_rotation = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 0.5);
+        let mut pose = make_pose(node, Vector3::new(1.0, 0.0, 0.0), base_rotation);
+
+        let delta_rotation = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.2);
+        let additive = make_pose(node, Vector3::new(0.0, 2.0, 0.0), delta_rotation);
+
+        pose.add_additive(&additive, 1.0);
+        let (position, rotation) = position_and_rotation(&pose, node);
+        assert_eq!(position, Vector3::new(1.0, 2.0, 0.0));
+        assert!(rotation.angle_to(&(base_rotation * delta_rotation)) < 1.0e-5);
+    }
+
+    #[test]
+    fn test_additive_pose_weight() {
+        let node = Handle::new(1, 1);
+        let mut pose = make_pose(node, Vector3::new(1.0, 0.0, 0.0), Default::default());
+        let additive = make_pose(
+            node,
+            Vector3::new(0.0, 2.0, 0.0),
+            UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.2),
+        );
+
+        // Zero weight must not change anything.
+        let copy = pose.clone();
+        pose.add_additive(&additive, 0.0);
+        assert_eq!(pose, copy);
+
+        pose.add_additive(&additive, 0.5);
+        let (position, rotation) = position_and_rotation(&pose, node);
+        assert_eq!(position, Vector3::new(1.0, 1.0, 0.0));
+        assert!((rotation.angle() - 0.1).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn test_additive_pose_ignores_missing_nodes() {
+        let node = Handle::new(1, 1);
+        let other = Handle::new(2, 1);
+        let mut pose = make_pose(node, Vector3::new(1.0, 0.0, 0.0), Default::default());
+        let copy = pose.clone();
+        pose.add_additive(
+            &make_pose(other, Vector3::new(0.0, 2.0, 0.0), Default::default()),
+            1.0,
+        );
+        assert_eq!(pose, copy);
+    }
+}
@@ -156,6 +156,37 @@ impl TrackValue {
         }
     }
 
+    /// Calculates an additive value, that represents a difference between the current value and the reference value.
+    /// Numbers and vectors are subtracted, while rotations are calculated as a relative rotation (`reference⁻¹ * self`).
+    /// If the types of the values are different, the current value is returned as is.
+    pub fn relative_to(&self, reference: &Self) -> Self {
+        match (self, reference) {
+            (Self::Real(a), Self::Real(b)) => Self::Real(*a - *b),
+            (Self::Vector2(a), Self::Vector2(b)) => Self::Vector2(a - b),
+            (Self::Vector3(a), Self::Vector3(b)) => Self::Vector3(a - b),
+            (Self::Vector4(a), Self::Vector4(b)) => Self::Vector4(a - b),
+            (Self::UnitQuaternion(a), Self::UnitQuaternion(b)) => {
+                Self::UnitQuaternion(b.inverse() * a)
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Adds an additive value (see [`Self::relative_to`]) scaled by the given weight to the current value. Numbers and
+    /// vectors are summed, rotations are combined (`self * additive`). Adding is possible only if the types are the same.
+    pub fn add_additive(&mut self, additive: &Self, weight: f32) {
+        match (self, additive) {
+            (Self::Real(a), Self::Real(b)) => *a += *b * weight,
+            (Self::Vector2(a), Self::Vector2(b)) => *a += b.scale(weight),
+            (Self::Vector3(a), Self::Vector3(b)) => *a += b.scale(weight),
+            (Self::Vector4(a), Self::Vector4(b)) => *a += b.scale(weight),
+            (Self::UnitQuaternion(a), Self::UnitQuaternion(b)) => {
+                *a *= UnitQuaternion::identity().nlerp(b, weight)
+            }
+            _ => (),
+        }
+    }
+
     /// Tries to perform a numeric type casting of the current value to some other and returns a boxed value, that can
     /// be used to set the value using reflection.
     pub fn numeric_type_cast(&self, value_type: ValueType) -> Option<Box<dyn Reflect>> {
@@ -308,6 +339,13 @@ impl BoundValue {
         assert_eq!(self.binding, other.binding);
         self.value.blend_with(&other.value, weight);
     }
+
+    /// Adds an additive value scaled by the given weight to the current value. See [`TrackValue::add_additive`] for
+    /// more info.
+    pub fn add_additive(&mut self, additive: &Self, weight: f32) {
+        assert_eq!(self.binding, additive.binding);
+        self.value.add_additive(&additive.value, weight);
+    }
 }
 
 /// A collection of values that are bounds to some properties.
@@ -328,6 +366,18 @@ impl BoundValueCollection {
         }
     }
 
+    /// Tries to add each additive value of the other collection to a respective (by binding) value in the current
+    /// collection. See [`TrackValue::add_additive`] docs for more info.
+    pub fn add_additive(&mut self, additive: &Self, weight: f32) {
+        for value in self.values.iter_mut() {
+            if let Some(additive_value) =
+                additive.values.iter().find(|v| v.binding == value.binding)
+            {
+                value.add_additive(additive_value, weight);
+            }
+        }
+    }
+
     /// Tries to set each value from the collection to the respective property (by binding) of the given scene node.
     pub fn apply(&self, node_ref: &mut Node) {
         for bound_value in self.values.iter() {
@@ -156,6 +156,8 @@ impl Executor {
         let mut lag = 0.0;
 
         event_loop.run(move |event, window_target, control_flow| {
+            engine.input.process_os_event(&event);
+
             engine.handle_os_event_by_plugins(&event, fixed_time_step, control_flow, &mut lag);
 
             let scenes = engine
@@ -13,7 +13,8 @@ use crate::{
     engine::error::EngineError,
     event::Event,
     event_loop::ControlFlow,
-    gui::UserInterface,
+    gui::{constructor::WidgetConstructorContainer, UserInterface},
+    input::Input,
     material::shader::{loader::ShaderLoader, Shader},
     plugin::{Plugin, PluginConstructor, PluginContext, PluginRegistrationContext},
     renderer::{framework::error::FrameworkError, Renderer},
@@ -21,6 +22,8 @@ use crate::{
         curve::{loader::CurveLoader, CurveResourceState},
         model::{loader::ModelLoader, Model, ModelResource},
         texture::{loader::TextureLoader, Texture, TextureKind},
+        tileset::{loader::TileSetLoader, TileSet},
+        ui::{loader::UiDocumentLoader, UiDocumentResourceState},
     },
     scene::{
         base::NodeScriptMessage,
@@ -80,6 +83,8 @@ pub struct SerializationContext {
     pub node_constructors: NodeConstructorContainer,
     /// A script constructor container.
     pub script_constructors: ScriptConstructorContainer,
+    /// A widget constructor container, that is used to load UI documents.
+    pub widget_constructors: Arc<WidgetConstructorContainer>,
 }
 
 impl Default for SerializationContext {
@@ -94,6 +99,7 @@ impl SerializationContext {
         Self {
             node_constructors: NodeConstructorContainer::new(),
             script_constructors: ScriptConstructorContainer::new(),
+            widget_constructors: Arc::new(WidgetConstructorContainer::new()),
         }
     }
 }
@@ -202,6 +208,10 @@ pub struct Engine {
 
     performance_statistics: PerformanceStatistics,
 
+    /// Input subsystem, that maps input events to named actions and axes. See [`Input`] docs for
+    /// more info.
+    pub input: Input,
+
     model_events_receiver: Receiver<ResourceEvent>,
 
     #[allow(dead_code)] // Keep engine instance alive.
@@ -415,6 +425,7 @@ impl ScriptProcessor {
         scenes: &mut SceneContainer,
         plugins: &mut Vec<Box<dyn Plugin>>,
         resource_manager: &ResourceManager,
+        input: &mut Input,
         dt: f32,
         elapsed_time: f32,
     ) {
@@ -462,6 +473,7 @@ impl ScriptProcessor {
                     resource_manager,
                     message_sender: &scripted_scene.message_sender,
                     message_dispatcher: &mut scripted_scene.message_dispatcher,
+                    input,
                 };
 
                 'init_loop: for init_loop_iteration in 0..max_iterations {
@@ -760,6 +772,7 @@ pub(crate) fn process_scripts<T>(
     resource_manager: &ResourceManager,
     message_sender: &ScriptMessageSender,
     message_dispatcher: &mut ScriptMessageDispatcher,
+    input: &mut Input,
     dt: f32,
     elapsed_time: f32,
     mut func: T,
@@ -775,6 +788,7 @@ pub(crate) fn process_scripts<T>(
         resource_manager,
         message_sender,
         message_dispatcher,
+        input,
     };
 
     for node_index in 0..context.scene.graph.capacity() {
@@ -790,7 +804,7 @@ pub(crate) fn initialize_resource_manager_loaders(
 ) {
     let model_loader = ModelLoader {
         resource_manager: resource_manager.clone(),
-        serialization_context,
+        serialization_context: serialization_context.clone(),
         default_import_options: Default::default(),
     };
 
@@ -807,6 +821,10 @@ pub(crate) fn initialize_resource_manager_loaders(
     state.constructors_container.add::<Model>();
     state.constructors_container.add::<CurveResourceState>();
     state.constructors_container.add::<SoundBuffer>();
+    state
+        .constructors_container
+        .add::<UiDocumentResourceState>();
+    state.constructors_container.add::<TileSet>();
 
     let loaders = &mut state.loaders;
     loaders.set(model_loader);
@@ -818,6 +836,12 @@ pub(crate) fn initialize_resource_manager_loaders(
     });
     loaders.set(ShaderLoader);
     loaders.set(CurveLoader);
+    loaders.set(UiDocumentLoader {
+        serialization_context,
+    });
+    loaders.set(TileSetLoader {
+        resource_manager: resource_manager.clone(),
+    });
 }
 
 impl Engine {
@@ -880,6 +904,7 @@ impl Engine {
             sound_engine,
             user_interface: UserInterface::new(Vector2::new(100.0, 100.0)),
             performance_statistics: Default::default(),
+            input: Default::default(),
             plugins: Default::default(),
             serialization_context,
             script_processor: Default::default(),
@@ -1202,6 +1227,8 @@ impl Engine {
 
             self.update_plugins(dt, control_flow, lag);
             self.handle_scripts(dt);
+
+            self.input.end_update();
         }
     }
 
@@ -1241,6 +1268,7 @@ impl Engine {
             &mut self.scenes,
             &mut self.plugins,
             &self.resource_manager,
+            &mut self.input,
             dt,
             self.elapsed_time,
         );
@@ -1260,6 +1288,7 @@ impl Engine {
                 user_interface: &mut self.user_interface,
                 serialization_context: &self.serialization_context,
                 performance_statistics: &self.performance_statistics,
+                input: &mut self.input,
             };
 
             for plugin in self.plugins.iter_mut() {
@@ -1276,6 +1305,7 @@ impl Engine {
                     user_interface: &mut self.user_interface,
                     serialization_context: &self.serialization_context,
                     performance_statistics: &self.performance_statistics,
+                    input: &mut self.input,
                 };
 
                 for plugin in self.plugins.iter_mut() {
@@ -1307,6 +1337,7 @@ impl Engine {
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
                         performance_statistics: &self.performance_statistics,
+                        input: &mut self.input,
                     },
                     control_flow,
                 );
@@ -1332,6 +1363,7 @@ impl Engine {
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
                         performance_statistics: &self.performance_statistics,
+                        input: &mut self.input,
                     },
                     control_flow,
                 );
@@ -1357,6 +1389,7 @@ impl Engine {
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
                         performance_statistics: &self.performance_statistics,
+                        input: &mut self.input,
                     },
                     control_flow,
                 );
@@ -1382,6 +1415,7 @@ impl Engine {
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
                         performance_statistics: &self.performance_statistics,
+                        input: &mut self.input,
                     },
                     control_flow,
                 );
@@ -1416,6 +1450,7 @@ impl Engine {
                     &self.resource_manager,
                     &scripted_scene.message_sender,
                     &mut scripted_scene.message_dispatcher,
+                    &mut self.input,
                     dt,
                     self.elapsed_time,
                     |script, context| {
@@ -1504,6 +1539,7 @@ impl Engine {
                             user_interface: &mut self.user_interface,
                             serialization_context: &self.serialization_context,
                             performance_statistics: &self.performance_statistics,
+                            input: &mut self.input,
                         },
                     ));
                 }
@@ -1521,6 +1557,7 @@ impl Engine {
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
                         performance_statistics: &self.performance_statistics,
+                        input: &mut self.input,
                     });
                 }
             }
@@ -1566,7 +1603,9 @@ mod test {
         asset::manager::ResourceManager,
         core::{pool::Handle, reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
         engine::ScriptProcessor,
+        event::VirtualKeyCode,
         impl_component_provider,
+        input::{Input, InputEvent, InputProfile, InputSource},
         scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene, SceneContainer},
         script::{
             Script, ScriptContext, ScriptDeinitContext, ScriptMessageContext, ScriptMessagePayload,
@@ -1710,6 +1749,7 @@ mod test {
                 &mut scene_container,
                 &mut Default::default(),
                 &resource_manager,
+                &mut Default::default(),
                 0.0,
                 0.0,
             );
@@ -1877,6 +1917,7 @@ mod test {
                 &mut scene_container,
                 &mut Default::default(),
                 &resource_manager,
+                &mut Default::default(),
                 0.0,
                 0.0,
             );
@@ -1894,4 +1935,88 @@ mod test {
             }
         }
     }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct ScriptReadingInput {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<(bool, bool)>,
+    }
+
+    impl_component_provider!(ScriptReadingInput);
+
+    impl ScriptTrait for ScriptReadingInput {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            self.sender
+                .send((
+                    ctx.input.is_action_pressed("Jump"),
+                    ctx.input.is_action_held("Jump"),
+                ))
+                .unwrap();
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_input() {
+        let resource_manager = ResourceManager::new();
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        PivotBuilder::new(
+            BaseBuilder::new().with_script(Script::new(ScriptReadingInput { sender: tx })),
+        )
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        let mut input = Input::new(
+            InputProfile::new().with_action("Jump", [InputSource::Key(VirtualKeyCode::Space)]),
+        );
+
+        let space = |pressed| InputEvent::Key {
+            key: VirtualKeyCode::Space,
+            pressed,
+        };
+
+        for iteration in 0..3 {
+            match iteration {
+                0 => input.process_event(&space(true)),
+                2 => input.process_event(&space(false)),
+                _ => (),
+            }
+
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut Default::default(),
+                &resource_manager,
+                &mut input,
+                0.0,
+                0.0,
+            );
+            input.end_update();
+
+            match iteration {
+                0 => assert_eq!(rx.try_recv(), Ok((true, true))),
+                1 => assert_eq!(rx.try_recv(), Ok((false, true))),
+                2 => assert_eq!(rx.try_recv(), Ok((false, false))),
+                _ => (),
+            }
+            assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+        }
+    }
 }
@@ -0,0 +1,912 @@
+//! Action-based input mapping. It allows you to define named actions and axes, bind them to keyboard
+//! keys, mouse buttons and axes, gamepad buttons and axes, and query their state on every update tick.
+//! See [`Input`] docs for more info.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{algebra::Vector2, io::FileLoadError},
+    event::{
+        DeviceEvent, ElementState, Event, MouseButton, MouseScrollDelta, VirtualKeyCode,
+        WindowEvent,
+    },
+};
+use fxhash::{FxHashMap, FxHashSet};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, io::Write, path::Path};
+
+/// A value of a gamepad axis at which the axis starts to be treated as a pressed button (see
+/// [`InputSource::GamepadAxis`]).
+pub const GAMEPAD_AXIS_PRESS_THRESHOLD: f32 = 0.5;
+
+/// A button of a gamepad. Names of the buttons follow the layout of the most common gamepads, the face
+/// buttons are named by their location.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GamepadButton {
+    /// Bottom face button (`A` on Xbox gamepads, `Cross` on PlayStation gamepads).
+    South,
+    /// Right face button (`B` on Xbox gamepads, `Circle` on PlayStation gamepads).
+    East,
+    /// Left face button (`X` on Xbox gamepads, `Square` on PlayStation gamepads).
+    West,
+    /// Top face button (`Y` on Xbox gamepads, `Triangle` on PlayStation gamepads).
+    North,
+    /// Left bumper (shoulder button).
+    LeftBumper,
+    /// Right bumper (shoulder button).
+    RightBumper,
+    /// Left trigger, when it is reported as a button.
+    LeftTrigger,
+    /// Right trigger, when it is reported as a button.
+    RightTrigger,
+    /// Select (back) button.
+    Select,
+    /// Start button.
+    Start,
+    /// Mode (guide) button.
+    Mode,
+    /// Left stick press.
+    LeftThumb,
+    /// Right stick press.
+    RightThumb,
+    /// Up on the directional pad.
+    DPadUp,
+    /// Down on the directional pad.
+    DPadDown,
+    /// Left on the directional pad.
+    DPadLeft,
+    /// Right on the directional pad.
+    DPadRight,
+}
+
+/// An axis of a gamepad. Values of stick axes are in `[-1; 1]` range, values of trigger axes are in
+/// `[0; 1]` range.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GamepadAxis {
+    /// Horizontal axis of the left stick, positive values are to the right.
+    LeftStickX,
+    /// Vertical axis of the left stick, positive values are up.
+    LeftStickY,
+    /// Horizontal axis of the right stick, positive values are to the right.
+    RightStickX,
+    /// Vertical axis of the right stick, positive values are up.
+    RightStickY,
+    /// Left trigger.
+    LeftTrigger,
+    /// Right trigger.
+    RightTrigger,
+}
+
+/// A direction of an axis.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum AxisDirection {
+    /// Negative values of an axis.
+    Negative,
+    /// Positive values of an axis.
+    Positive,
+}
+
+impl AxisDirection {
+    fn sign(self) -> f32 {
+        match self {
+            AxisDirection::Negative => -1.0,
+            AxisDirection::Positive => 1.0,
+        }
+    }
+}
+
+/// A digital input source, that could be either pressed or released. Sources are used as bindings of
+/// actions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputSource {
+    /// A key on a keyboard.
+    Key(VirtualKeyCode),
+    /// A mouse button.
+    MouseButton(MouseButton),
+    /// A gamepad button.
+    GamepadButton(GamepadButton),
+    /// A gamepad axis, that is treated as a button. It is pressed when the value of the axis exceeds
+    /// [`GAMEPAD_AXIS_PRESS_THRESHOLD`] in the given direction. It could be used, for example, to bind
+    /// a stick to menu navigation.
+    GamepadAxis {
+        /// An axis of a gamepad.
+        axis: GamepadAxis,
+        /// A direction in which the axis should be moved to press the source.
+        direction: AxisDirection,
+    },
+}
+
+/// An axis of a mouse.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum MouseAxis {
+    /// Horizontal mouse movement, positive values are to the right.
+    X,
+    /// Vertical mouse movement, positive values are down.
+    Y,
+    /// Vertical mouse wheel, positive values are away from the user.
+    Wheel,
+}
+
+/// A binding of an axis. An axis could have multiple bindings, the value of the axis is the sum of the
+/// values of all its bindings.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AxisBinding {
+    /// A pair of digital sources, that gives `-1.0` when `negative` source is held, `1.0` when
+    /// `positive` source is held and `0.0` when both or none of them are held. A common example is
+    /// `A` and `D` keys for horizontal movement.
+    Sources {
+        /// A source that moves the axis in negative direction.
+        negative: InputSource,
+        /// A source that moves the axis in positive direction.
+        positive: InputSource,
+    },
+    /// A mouse axis. Its value is the movement of the mouse (in raw device units) or the wheel (in
+    /// lines or pixels, depending on the device) accumulated during the last update tick, multiplied
+    /// by the `scale`.
+    Mouse {
+        /// An axis of a mouse.
+        axis: MouseAxis,
+        /// A multiplier of the value, it could be used as a sensitivity of the mouse. Use negative
+        /// value to invert the axis.
+        scale: f32,
+    },
+    /// A gamepad axis. Values that are less than `dead_zone` (by absolute value) are treated as zero,
+    /// the rest of the range is remapped to `[0; 1]` and multiplied by the `scale`.
+    Gamepad {
+        /// An axis of a gamepad.
+        axis: GamepadAxis,
+        /// A multiplier of the value. Use negative value to invert the axis.
+        scale: f32,
+        /// A dead zone of the axis, it should be in `[0; 1)` range.
+        dead_zone: f32,
+    },
+}
+
+/// An error that may occur during input profile loading or saving.
+#[derive(Debug)]
+pub enum InputProfileError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+    /// A parsing error has occurred.
+    RonSpanned(ron::error::SpannedError),
+    /// A serialization error has occurred.
+    Ron(ron::Error),
+}
+
+impl From<FileLoadError> for InputProfileError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ron::error::SpannedError> for InputProfileError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        Self::RonSpanned(e)
+    }
+}
+
+impl From<ron::Error> for InputProfileError {
+    fn from(e: ron::Error) -> Self {
+        Self::Ron(e)
+    }
+}
+
+/// Input profile is a serializable set of bindings of actions and axes. Actions and axes are identified
+/// by their names. It is possible to have multiple profiles (for example one per player) and switch
+/// between them using [`Input::set_profile`].
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     event::{MouseButton, VirtualKeyCode},
+///     input::{AxisBinding, GamepadAxis, GamepadButton, InputProfile, InputSource},
+/// };
+///
+/// fn default_profile() -> InputProfile {
+///     InputProfile::new()
+///         .with_action(
+///             "Jump",
+///             [
+///                 InputSource::Key(VirtualKeyCode::Space),
+///                 InputSource::GamepadButton(GamepadButton::South),
+///             ],
+///         )
+///         .with_action("Shoot", [InputSource::MouseButton(MouseButton::Left)])
+///         .with_axis(
+///             "MoveX",
+///             [
+///                 AxisBinding::Sources {
+///                     negative: InputSource::Key(VirtualKeyCode::A),
+///                     positive: InputSource::Key(VirtualKeyCode::D),
+///                 },
+///                 AxisBinding::Gamepad {
+///                     axis: GamepadAxis::LeftStickX,
+///                     scale: 1.0,
+///                     dead_zone: 0.15,
+///                 },
+///             ],
+///         )
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct InputProfile {
+    /// Bindings of actions. An action is held if any of its sources is held.
+    #[serde(default)]
+    pub actions: BTreeMap<String, Vec<InputSource>>,
+    /// Bindings of axes.
+    #[serde(default)]
+    pub axes: BTreeMap<String, Vec<AxisBinding>>,
+}
+
+impl InputProfile {
+    /// Creates new empty profile.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the given sources to the action with the given name.
+    pub fn with_action<I>(mut self, name: &str, sources: I) -> Self
+    where
+        I: IntoIterator<Item = InputSource>,
+    {
+        for source in sources {
+            self.bind_action(name, source);
+        }
+        self
+    }
+
+    /// Adds the given bindings to the axis with the given name.
+    pub fn with_axis<I>(mut self, name: &str, bindings: I) -> Self
+    where
+        I: IntoIterator<Item = AxisBinding>,
+    {
+        for binding in bindings {
+            self.bind_axis(name, binding);
+        }
+        self
+    }
+
+    /// Binds the source to the action with the given name. The action is created if it does not exist.
+    /// Duplicate bindings are ignored.
+    pub fn bind_action(&mut self, name: &str, source: InputSource) {
+        let sources = self.actions.entry(name.to_owned()).or_default();
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+
+    /// Removes the source from the bindings of the action with the given name. Returns `true` if the
+    /// binding existed.
+    pub fn unbind_action(&mut self, name: &str, source: InputSource) -> bool {
+        if let Some(sources) = self.actions.get_mut(name) {
+            if let Some(position) = sources.iter().position(|s| *s == source) {
+                sources.remove(position);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Replaces the `old` source of the action with the `new` one, keeping its position in the list of
+    /// bindings. Returns `true` if the `old` binding existed. This method is meant to be used in
+    /// "press a key to rebind" menus, see [`Input::last_pressed_source`].
+    pub fn rebind_action(&mut self, name: &str, old: InputSource, new: InputSource) -> bool {
+        if let Some(sources) = self.actions.get_mut(name) {
+            if let Some(position) = sources.iter().position(|s| *s == old) {
+                if sources.contains(&new) {
+                    sources.remove(position);
+                } else {
+                    sources[position] = new;
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Returns the sources bound to the action with the given name.
+    pub fn action_bindings(&self, name: &str) -> &[InputSource] {
+        self.actions
+            .get(name)
+            .map_or(&[], |sources| sources.as_slice())
+    }
+
+    /// Adds the binding to the axis with the given name. The axis is created if it does not exist.
+    pub fn bind_axis(&mut self, name: &str, binding: AxisBinding) {
+        self.axes.entry(name.to_owned()).or_default().push(binding);
+    }
+
+    /// Returns the bindings of the axis with the given name.
+    pub fn axis_bindings(&self, name: &str) -> &[AxisBinding] {
+        self.axes
+            .get(name)
+            .map_or(&[], |bindings| bindings.as_slice())
+    }
+
+    /// Returns a mutable reference to the bindings of the axis with the given name, if the axis exists.
+    pub fn axis_bindings_mut(&mut self, name: &str) -> Option<&mut Vec<AxisBinding>> {
+        self.axes.get_mut(name)
+    }
+
+    /// Loads a profile from the given file. The file is loaded through the [virtual file
+    /// system](crate::core::vfs), so it could be stored in a mounted directory or a pak archive.
+    pub async fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, InputProfileError> {
+        let data = crate::core::io::load_file(path).await?;
+        Self::from_bytes(&data)
+    }
+
+    /// Loads a profile from the given serialized data.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, InputProfileError> {
+        Ok(ron::de::from_bytes(data)?)
+    }
+
+    /// Saves the profile to the given writer. Use [`std::fs::File`] as the writer to save the profile
+    /// to a file.
+    pub fn save<W: Write>(&self, writer: W) -> Result<(), InputProfileError> {
+        ron::ser::to_writer_pretty(writer, self, PrettyConfig::default())?;
+        Ok(())
+    }
+}
+
+/// An engine-independent input event. The engine translates OS events to input events automatically,
+/// but it is also possible to pass input events directly to [`Input::process_event`]. It could be used
+/// to feed gamepad input (the engine does not read gamepads by itself, a crate like `gilrs` could be
+/// used for that) or to inject synthetic input, for example in tests or replays.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InputEvent {
+    /// A keyboard key was pressed or released.
+    Key {
+        /// A key code.
+        key: VirtualKeyCode,
+        /// `true` - the key was pressed, `false` - released.
+        pressed: bool,
+    },
+    /// A mouse button was pressed or released.
+    MouseButton {
+        /// A mouse button.
+        button: MouseButton,
+        /// `true` - the button was pressed, `false` - released.
+        pressed: bool,
+    },
+    /// The mouse was moved by the given amount of raw device units.
+    MouseMotion {
+        /// Movement delta.
+        delta: Vector2<f32>,
+    },
+    /// The mouse wheel was rotated.
+    MouseWheel {
+        /// Rotation delta.
+        delta: f32,
+    },
+    /// A gamepad button was pressed or released.
+    GamepadButton {
+        /// A gamepad button.
+        button: GamepadButton,
+        /// `true` - the button was pressed, `false` - released.
+        pressed: bool,
+    },
+    /// A gamepad axis has changed its value.
+    GamepadAxis {
+        /// A gamepad axis.
+        axis: GamepadAxis,
+        /// New value of the axis.
+        value: f32,
+    },
+    /// The window has lost focus, every held source will be released.
+    FocusLost,
+}
+
+impl InputEvent {
+    /// Tries to translate an OS event to input event.
+    pub fn from_os_event(event: &Event<()>) -> Option<Self> {
+        match event {
+            Event::WindowEvent { event, .. } => match event {
+                WindowEvent::KeyboardInput { input, .. } => {
+                    input.virtual_keycode.map(|key| InputEvent::Key {
+                        key,
+                        pressed: input.state == ElementState::Pressed,
+                    })
+                }
+                WindowEvent::MouseInput { state, button, .. } => Some(InputEvent::MouseButton {
+                    button: *button,
+                    pressed: *state == ElementState::Pressed,
+                }),
+                WindowEvent::MouseWheel { delta, .. } => Some(InputEvent::MouseWheel {
+                    delta: match delta {
+                        MouseScrollDelta::LineDelta(_, y) => *y,
+                        MouseScrollDelta::PixelDelta(pos) => pos.y as f32,
+                    },
+                }),
+                WindowEvent::Focused(false) => Some(InputEvent::FocusLost),
+                _ => None,
+            },
+            Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta },
+                ..
+            } => Some(InputEvent::MouseMotion {
+                delta: Vector2::new(delta.0 as f32, delta.1 as f32),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Input is an engine-level input subsystem, that tracks the state of every input source and maps them
+/// to named actions and axes using current [`InputProfile`]. It is available in scripts and plugins
+/// (see [`crate::script::ScriptContext::input`] and [`crate::plugin::PluginContext::input`]).
+///
+/// # Actions
+///
+/// Action is a named set of digital sources (keys, buttons, etc.). The action is *held* while any of
+/// its sources is held, it is *pressed* on the update tick in which any of its sources was pressed and
+/// it is *released* on the update tick in which its last held source was released. Pressed and released
+/// states are kept until the end of the update tick, so they won't be missed even if press and release
+/// happen between two ticks.
+///
+/// # Axes
+///
+/// Axis is a named set of [`AxisBinding`]s, its value is the sum of the values of all the bindings. Mouse
+/// bindings accumulate the movement of the mouse during an update tick.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::{algebra::Vector3, reflect::prelude::*, uuid::{uuid, Uuid}, visitor::prelude::*},
+///     impl_component_provider,
+///     script::{ScriptContext, ScriptTrait},
+/// };
+///
+/// #[derive(Visit, Reflect, Default, Debug, Clone)]
+/// struct Player;
+///
+/// impl_component_provider!(Player);
+///
+/// impl ScriptTrait for Player {
+///     fn on_update(&mut self, ctx: &mut ScriptContext) {
+///         let speed = 5.0 * ctx.dt;
+///         let offset = Vector3::new(ctx.input.axis_value("MoveX") * speed, 0.0, 0.0);
+///         ctx.scene.graph[ctx.handle]
+///             .local_transform_mut()
+///             .offset(offset);
+///
+///         if ctx.input.is_action_pressed("Jump") {
+///             // Jump.
+///         }
+///     }
+///
+///     fn id(&self) -> Uuid {
+///         uuid!("b5c8ab5e-2a4b-4f1a-a9a4-8b4ac2f3d3e1")
+///     }
+/// }
+/// ```
+#[derive(Default, Debug)]
+pub struct Input {
+    profile: InputProfile,
+    held: FxHashSet<InputSource>,
+    pressed: FxHashSet<InputSource>,
+    released: FxHashSet<InputSource>,
+    gamepad_axes: FxHashMap<GamepadAxis, f32>,
+    mouse_delta: Vector2<f32>,
+    wheel_delta: f32,
+    last_pressed: Option<InputSource>,
+}
+
+impl Input {
+    /// Creates new input subsystem with the given profile.
+    pub fn new(profile: InputProfile) -> Self {
+        Self {
+            profile,
+            ..Default::default()
+        }
+    }
+
+    /// Returns a reference to current profile.
+    pub fn profile(&self) -> &InputProfile {
+        &self.profile
+    }
+
+    /// Returns a mutable reference to current profile. It could be used to rebind actions and axes at runtime.
+    pub fn profile_mut(&mut self) -> &mut InputProfile {
+        &mut self.profile
+    }
+
+    /// Sets new profile and returns the old one. The state of input sources is preserved.
+    pub fn set_profile(&mut self, profile: InputProfile) -> InputProfile {
+        std::mem::replace(&mut self.profile, profile)
+    }
+
+    /// Translates the OS event (if possible) and processes it.
+    pub fn process_os_event(&mut self, event: &Event<()>) {
+        if let Some(event) = InputEvent::from_os_event(event) {
+            self.process_event(&event);
+        }
+    }
+
+    /// Processes the input event and updates the state of the input sources.
+    pub fn process_event(&mut self, event: &InputEvent) {
+        match *event {
+            InputEvent::Key { key, pressed } => {
+                self.set_source_state(InputSource::Key(key), pressed)
+            }
+            InputEvent::MouseButton { button, pressed } => {
+                self.set_source_state(InputSource::MouseButton(button), pressed)
+            }
+            InputEvent::MouseMotion { delta } => self.mouse_delta += delta,
+            InputEvent::MouseWheel { delta } => self.wheel_delta += delta,
+            InputEvent::GamepadButton { button, pressed } => {
+                self.set_source_state(InputSource::GamepadButton(button), pressed)
+            }
+            InputEvent::GamepadAxis { axis, value } => {
+                self.gamepad_axes.insert(axis, value);
+                for direction in [AxisDirection::Negative, AxisDirection::Positive] {
+                    self.set_source_state(
+                        InputSource::GamepadAxis { axis, direction },
+                        value * direction.sign() >= GAMEPAD_AXIS_PRESS_THRESHOLD,
+                    );
+                }
+            }
+            InputEvent::FocusLost => {
+                self.released.extend(self.held.drain());
+            }
+        }
+    }
+
+    fn set_source_state(&mut self, source: InputSource, pressed: bool) {
+        if pressed {
+            // Repeated press events (produced by key auto-repeat) must not produce new presses.
+            if self.held.insert(source) {
+                self.pressed.insert(source);
+                self.last_pressed = Some(source);
+            }
+        } else if self.held.remove(&source) {
+            self.released.insert(source);
+        }
+    }
+
+    /// Clears pressed and released states of the sources and accumulated mouse movement. The engine
+    /// calls this method at the end of every update tick (see [`crate::engine::Engine::pre_update`]), you
+    /// need to call it manually only if you're updating the engine in some other way.
+    pub fn end_update(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+        self.mouse_delta = Vector2::zeros();
+        self.wheel_delta = 0.0;
+        self.last_pressed = None;
+    }
+
+    /// Returns `true` if the source is held.
+    pub fn is_source_held(&self, source: InputSource) -> bool {
+        self.held.contains(&source)
+    }
+
+    /// Returns `true` if the source was pressed during current update tick.
+    pub fn is_source_pressed(&self, source: InputSource) -> bool {
+        self.pressed.contains(&source)
+    }
+
+    /// Returns `true` if the source was released during current update tick.
+    pub fn is_source_released(&self, source: InputSource) -> bool {
+        self.released.contains(&source)
+    }
+
+    /// Returns the last source that was pressed during current update tick. It could be used to
+    /// implement "press a key to rebind" menus.
+    pub fn last_pressed_source(&self) -> Option<InputSource> {
+        self.last_pressed
+    }
+
+    /// Returns the movement of the mouse accumulated during current update tick.
+    pub fn mouse_delta(&self) -> Vector2<f32> {
+        self.mouse_delta
+    }
+
+    /// Returns the rotation of the mouse wheel accumulated during current update tick.
+    pub fn mouse_wheel_delta(&self) -> f32 {
+        self.wheel_delta
+    }
+
+    /// Returns current raw value of the gamepad axis.
+    pub fn gamepad_axis_value(&self, axis: GamepadAxis) -> f32 {
+        self.gamepad_axes.get(&axis).cloned().unwrap_or_default()
+    }
+
+    /// Returns `true` if any source of the action is held.
+    pub fn is_action_held(&self, name: &str) -> bool {
+        self.profile
+            .action_bindings(name)
+            .iter()
+            .any(|source| self.is_source_held(*source))
+    }
+
+    /// Returns `true` if any source of the action was pressed during current update tick.
+    pub fn is_action_pressed(&self, name: &str) -> bool {
+        self.profile
+            .action_bindings(name)
+            .iter()
+            .any(|source| self.is_source_pressed(*source))
+    }
+
+    /// Returns `true` if a source of the action was released during current update tick and the action
+    /// is not held anymore.
+    pub fn is_action_released(&self, name: &str) -> bool {
+        !self.is_action_held(name)
+            && self
+                .profile
+                .action_bindings(name)
+                .iter()
+                .any(|source| self.is_source_released(*source))
+    }
+
+    /// Returns the value of the axis, which is the sum of the values of all its bindings.
+    pub fn axis_value(&self, name: &str) -> f32 {
+        self.profile
+            .axis_bindings(name)
+            .iter()
+            .map(|binding| self.binding_value(binding))
+            .sum()
+    }
+
+    fn binding_value(&self, binding: &AxisBinding) -> f32 {
+        match *binding {
+            AxisBinding::Sources { negative, positive } => {
+                let mut value = 0.0;
+                if self.is_source_held(negative) {
+                    value -= 1.0;
+                }
+                if self.is_source_held(positive) {
+                    value += 1.0;
+                }
+                value
+            }
+            AxisBinding::Mouse { axis, scale } => {
+                let value = match axis {
+                    MouseAxis::X => self.mouse_delta.x,
+                    MouseAxis::Y => self.mouse_delta.y,
+                    MouseAxis::Wheel => self.wheel_delta,
+                };
+                value * scale
+            }
+            AxisBinding::Gamepad {
+                axis,
+                scale,
+                dead_zone,
+            } => {
+                let value = self.gamepad_axis_value(axis);
+                let dead_zone = dead_zone.clamp(0.0, 0.99);
+                if value.abs() <= dead_zone {
+                    0.0
+                } else {
+                    value.signum() * (value.abs() - dead_zone) / (1.0 - dead_zone) * scale
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        event::{MouseButton, VirtualKeyCode},
+        input::{
+            AxisBinding, AxisDirection, GamepadAxis, GamepadButton, Input, InputEvent,
+            InputProfile, InputSource, MouseAxis,
+        },
+    };
+
+    fn key(key: VirtualKeyCode, pressed: bool) -> InputEvent {
+        InputEvent::Key { key, pressed }
+    }
+
+    fn test_profile() -> InputProfile {
+        InputProfile::new()
+            .with_action(
+                "Jump",
+                [
+                    InputSource::Key(VirtualKeyCode::Space),
+                    InputSource::GamepadButton(GamepadButton::South),
+                ],
+            )
+            .with_action("Shoot", [InputSource::MouseButton(MouseButton::Left)])
+            .with_axis(
+                "MoveX",
+                [
+                    AxisBinding::Sources {
+                        negative: InputSource::Key(VirtualKeyCode::A),
+                        positive: InputSource::Key(VirtualKeyCode::D),
+                    },
+                    AxisBinding::Gamepad {
+                        axis: GamepadAxis::LeftStickX,
+                        scale: 1.0,
+                        dead_zone: 0.2,
+                    },
+                ],
+            )
+            .with_axis(
+                "LookX",
+                [AxisBinding::Mouse {
+                    axis: MouseAxis::X,
+                    scale: 0.5,
+                }],
+            )
+    }
+
+    #[test]
+    fn test_action_states() {
+        let mut input = Input::new(test_profile());
+
+        input.process_event(&key(VirtualKeyCode::Space, true));
+        assert!(input.is_action_pressed("Jump"));
+        assert!(input.is_action_held("Jump"));
+        assert!(!input.is_action_released("Jump"));
+        assert_eq!(
+            input.last_pressed_source(),
+            Some(InputSource::Key(VirtualKeyCode::Space))
+        );
+
+        input.end_update();
+        // Auto-repeat must not produce new presses.
+        input.process_event(&key(VirtualKeyCode::Space, true));
+        assert!(!input.is_action_pressed("Jump"));
+        assert!(input.is_action_held("Jump"));
+
+        // The action is still held by another source.
+        input.process_event(&InputEvent::GamepadButton {
+            button: GamepadButton::South,
+            pressed: true,
+        });
+        input.end_update();
+        input.process_event(&key(VirtualKeyCode::Space, false));
+        assert!(input.is_action_held("Jump"));
+        assert!(!input.is_action_released("Jump"));
+
+        input.process_event(&InputEvent::GamepadButton {
+            button: GamepadButton::South,
+            pressed: false,
+        });
+        assert!(!input.is_action_held("Jump"));
+        assert!(input.is_action_released("Jump"));
+
+        input.end_update();
+        assert!(!input.is_action_released("Jump"));
+
+        // Press and release between two ticks must not be lost.
+        input.process_event(&InputEvent::MouseButton {
+            button: MouseButton::Left,
+            pressed: true,
+        });
+        input.process_event(&InputEvent::MouseButton {
+            button: MouseButton::Left,
+            pressed: false,
+        });
+        assert!(input.is_action_pressed("Shoot"));
+        assert!(input.is_action_released("Shoot"));
+        assert!(!input.is_action_held("Shoot"));
+
+        input.end_update();
+        input.process_event(&key(VirtualKeyCode::Space, true));
+        input.process_event(&InputEvent::FocusLost);
+        assert!(!input.is_action_held("Jump"));
+        assert!(input.is_action_released("Jump"));
+
+        assert!(!input.is_action_held("Unknown"));
+    }
+
+    #[test]
+    fn test_axis_values() {
+        let mut input = Input::new(test_profile());
+
+        input.process_event(&key(VirtualKeyCode::D, true));
+        assert_eq!(input.axis_value("MoveX"), 1.0);
+        input.process_event(&key(VirtualKeyCode::A, true));
+        assert_eq!(input.axis_value("MoveX"), 0.0);
+        input.process_event(&key(VirtualKeyCode::D, false));
+        assert_eq!(input.axis_value("MoveX"), -1.0);
+        input.process_event(&key(VirtualKeyCode::A, false));
+
+        // Dead zone.
+        input.process_event(&InputEvent::GamepadAxis {
+            axis: GamepadAxis::LeftStickX,
+            value: 0.1,
+        });
+        assert_eq!(input.axis_value("MoveX"), 0.0);
+        input.process_event(&InputEvent::GamepadAxis {
+            axis: GamepadAxis::LeftStickX,
+            value: -0.6,
+        });
+        assert!((input.axis_value("MoveX") + 0.5).abs() < 1.0e-6);
+
+        input.process_event(&InputEvent::MouseMotion {
+            delta: Vector2::new(4.0, 1.0),
+        });
+        input.process_event(&InputEvent::MouseMotion {
+            delta: Vector2::new(2.0, 1.0),
+        });
+        assert_eq!(input.axis_value("LookX"), 3.0);
+        assert_eq!(input.mouse_delta(), Vector2::new(6.0, 2.0));
+
+        input.end_update();
+        assert_eq!(input.axis_value("LookX"), 0.0);
+        // Gamepad axes keep their values between ticks.
+        assert!((input.axis_value("MoveX") + 0.5).abs() < 1.0e-6);
+
+        assert_eq!(input.axis_value("Unknown"), 0.0);
+    }
+
+    #[test]
+    fn test_gamepad_axis_as_source() {
+        let mut input = Input::new(InputProfile::new().with_action(
+            "MenuDown",
+            [InputSource::GamepadAxis {
+                axis: GamepadAxis::LeftStickY,
+                direction: AxisDirection::Negative,
+            }],
+        ));
+
+        input.process_event(&InputEvent::GamepadAxis {
+            axis: GamepadAxis::LeftStickY,
+            value: -0.3,
+        });
+        assert!(!input.is_action_held("MenuDown"));
+        input.process_event(&InputEvent::GamepadAxis {
+            axis: GamepadAxis::LeftStickY,
+            value: -0.8,
+        });
+        assert!(input.is_action_pressed("MenuDown"));
+        input.end_update();
+        input.process_event(&InputEvent::GamepadAxis {
+            axis: GamepadAxis::LeftStickY,
+            value: 0.9,
+        });
+        assert!(input.is_action_released("MenuDown"));
+    }
+
+    #[test]
+    fn test_rebinding() {
+        let mut input = Input::new(test_profile());
+
+        assert!(input.profile_mut().rebind_action(
+            "Jump",
+            InputSource::Key(VirtualKeyCode::Space),
+            InputSource::Key(VirtualKeyCode::W),
+        ));
+        assert_eq!(
+            input.profile().action_bindings("Jump"),
+            &[
+                InputSource::Key(VirtualKeyCode::W),
+                InputSource::GamepadButton(GamepadButton::South)
+            ]
+        );
+
+        input.process_event(&key(VirtualKeyCode::Space, true));
+        assert!(!input.is_action_pressed("Jump"));
+        input.process_event(&key(VirtualKeyCode::W, true));
+        assert!(input.is_action_pressed("Jump"));
+
+        assert!(input
+            .profile_mut()
+            .unbind_action("Jump", InputSource::Key(VirtualKeyCode::W)));
+        assert!(!input
+            .profile_mut()
+            .unbind_action("Jump", InputSource::Key(VirtualKeyCode::W)));
+        assert!(!input.is_action_held("Jump"));
+    }
+
+    #[test]
+    fn test_profile_serialization() {
+        let profile = test_profile();
+        let text = ron::ser::to_string(&profile).unwrap();
+        let loaded: InputProfile = ron::de::from_str(&text).unwrap();
+        assert_eq!(profile, loaded);
+
+        let empty: InputProfile = ron::de::from_str("()").unwrap();
+        assert_eq!(empty, InputProfile::default());
+
+        let mut data = Vec::new();
+        profile.save(&mut data).unwrap();
+        assert_eq!(InputProfile::from_bytes(&data).unwrap(), profile);
+    }
+}
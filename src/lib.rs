@@ -11,6 +11,7 @@
 
 pub mod animation;
 pub mod engine;
+pub mod input;
 pub mod material;
 pub mod plugin;
 pub mod renderer;
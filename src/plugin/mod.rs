@@ -9,6 +9,7 @@ use crate::{
     event::Event,
     event_loop::ControlFlow,
     gui::{message::UiMessage, UserInterface},
+    input::Input,
     scene::{Scene, SceneContainer},
 };
 use std::{any::Any, sync::Arc};
@@ -83,6 +84,10 @@ pub struct PluginContext<'a, 'b> {
 
     /// Performance statistics from the last frame.
     pub performance_statistics: &'a PerformanceStatistics,
+
+    /// A reference to the input subsystem of the engine. It allows to query the state of actions and
+    /// axes, and to change their bindings at runtime. See [`Input`] docs for more info.
+    pub input: &'a mut Input,
 }
 
 /// Base plugin automatically implements type casting for plugins.
@@ -142,9 +142,11 @@ pub struct RenderDataBatchStorage {
 }
 
 impl RenderDataBatchStorage {
-    /// Creates a new render batch storage from the given graph and observer info. It "asks" every node in the
-    /// graph one-by-one to give render data which is then put in the storage, sorted and ready for rendering.
-    /// Frustum culling is done on scene node side ([`crate::scene::node::NodeTrait::collect_render_data`]).
+    /// Creates a new render batch storage from the given graph and observer info. It "asks" every potentially
+    /// visible node in the graph one-by-one to give render data which is then put in the storage, sorted and
+    /// ready for rendering. Potentially visible nodes are fetched from the spatial index of the graph
+    /// ([`Graph::spatial_index`]), precise frustum culling is done on scene node side
+    /// ([`crate::scene::node::NodeTrait::collect_render_data`]).
     pub fn from_graph(
         graph: &Graph,
         observer_info: ObserverInfo,
@@ -195,11 +197,22 @@ impl RenderDataBatchStorage {
             node_handle: Default::default(),
         };
 
-        for (handle, node) in graph.pair_iter() {
-            ctx.node_handle = handle;
+        // Only the nodes with bounding boxes intersecting the frustum could produce render data, so
+        // use the spatial index of the graph to skip everything else.
+        let mut candidates = Vec::new();
+        graph
+            .spatial_index()
+            .frustum_query(&frustum, &mut candidates);
+        // Preserve the order of nodes in the graph, so the batches will be sorted in stable order.
+        candidates.sort_unstable_by_key(|handle| handle.index());
 
-            if lod_filter[handle.index() as usize] {
-                node.collect_render_data(&mut ctx);
+        for handle in candidates {
+            if let Some(node) = graph.try_get(handle) {
+                ctx.node_handle = handle;
+
+                if lod_filter[handle.index() as usize] {
+                    node.collect_render_data(&mut ctx);
+                }
             }
         }
 
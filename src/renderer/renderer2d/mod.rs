@@ -21,13 +21,14 @@ use crate::{
     },
     scene::{
         camera::Camera,
-        dim2::rectangle::Rectangle,
+        dim2::{rectangle::Rectangle, tilemap::TileMap},
         graph::Graph,
         light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
     },
     utils::value_as_u8_slice,
 };
 use fxhash::{FxHashMap, FxHasher};
+use fyrox_resource::ResourceStateRef;
 use std::{cell::RefCell, cmp::Ordering, hash::Hasher, rc::Rc};
 
 mod cache;
@@ -86,10 +87,46 @@ struct SpriteBatchStorage {
 }
 
 impl SpriteBatchStorage {
+    fn batch_mut(
+        &mut self,
+        batch_count: &mut usize,
+        z: f32,
+        texture: &Rc<RefCell<GpuTexture>>,
+    ) -> &mut Batch {
+        let mut hasher = FxHasher::default();
+        // Objects with different Z coordinate will go into separate batches.
+        hasher.write(value_as_u8_slice(&z));
+        // Objects with different textures will go into separate batches.
+        hasher.write_u64(&*texture.borrow() as *const _ as u64);
+        let batch_id = hasher.finish();
+
+        let index = *self.index_map.entry(batch_id).or_insert_with(|| {
+            let index = *batch_count;
+            *batch_count += 1;
+            index
+        });
+
+        // Reuse old batches to prevent redundant memory allocations
+        if index < self.batches.len() {
+            let batch = &mut self.batches[index];
+            batch.texture = texture.clone();
+            batch.z = z;
+            batch
+        } else {
+            self.batches.push(Batch {
+                instances: Default::default(),
+                texture: texture.clone(),
+                z,
+            });
+            self.batches.last_mut().unwrap()
+        }
+    }
+
     fn generate_batches(
         &mut self,
         state: &mut PipelineState,
         graph: &Graph,
+        frustum: &Frustum,
         texture_cache: &mut TextureCache,
         white_dummy: Rc<RefCell<GpuTexture>>,
     ) {
@@ -98,7 +135,7 @@ impl SpriteBatchStorage {
             batch.instances.clear();
         }
 
-        let mut batch_index = 0;
+        let mut batch_count = 0;
         for node in graph.linear_iter() {
             if let Some(rectangle) = node.cast::<Rectangle>() {
                 if !rectangle.global_visibility() {
@@ -116,32 +153,7 @@ impl SpriteBatchStorage {
 
                 let z = rectangle.global_position().z;
 
-                let mut hasher = FxHasher::default();
-                // Objects with different Z coordinate will go into separate batches.
-                hasher.write(value_as_u8_slice(&z));
-                // Objects with different textures will go into separate batches.
-                hasher.write_u64(&*texture.borrow() as *const _ as u64);
-                let batch_id = hasher.finish();
-
-                let index = *self.index_map.entry(batch_id).or_insert_with(|| {
-                    let index = batch_index;
-                    batch_index += 1;
-                    index as usize
-                });
-
-                // Reuse old batches to prevent redundant memory allocations
-                let batch = if let Some(batch) = self.batches.get_mut(index) {
-                    batch.texture = texture.clone();
-                    batch.z = z;
-                    batch
-                } else {
-                    self.batches.push(Batch {
-                        instances: Default::default(),
-                        texture: texture.clone(),
-                        z,
-                    });
-                    self.batches.last_mut().unwrap()
-                };
+                let batch = self.batch_mut(&mut batch_count, z, &texture);
 
                 let uv_rect = rectangle.uv_rect();
                 let uv_transform = Vector4::new(uv_rect.x(), uv_rect.y(), uv_rect.w(), uv_rect.h());
@@ -154,6 +166,61 @@ impl SpriteBatchStorage {
                     },
                     aabb: rectangle.world_bounding_box(),
                 });
+            } else if let Some(tile_map) = node.cast::<TileMap>() {
+                if !tile_map.global_visibility() {
+                    continue;
+                }
+
+                let tile_set_resource = match tile_map.tile_set() {
+                    Some(tile_set) => tile_set,
+                    None => continue,
+                };
+                let tile_set_state = tile_set_resource.state();
+                let tile_set = match tile_set_state.get() {
+                    ResourceStateRef::Ok(tile_set) => tile_set,
+                    _ => continue,
+                };
+
+                let texture = tile_set.texture.as_ref().map_or_else(
+                    || white_dummy.clone(),
+                    |t| {
+                        texture_cache
+                            .get(state, t)
+                            .unwrap_or_else(|| white_dummy.clone())
+                    },
+                );
+
+                let z = tile_map.global_position().z;
+
+                // Whole chunks are culled here, so every tile of a visible chunk shares the
+                // bounding box of the chunk and passes per-instance culling for free.
+                for (chunk_pos, chunk) in tile_map.chunks() {
+                    let aabb = tile_map.chunk_world_bounding_box(chunk_pos);
+                    if !frustum.is_intersects_aabb(&aabb) {
+                        continue;
+                    }
+
+                    let batch = self.batch_mut(&mut batch_count, z, &texture);
+
+                    for (position, tile) in chunk.tiles() {
+                        if let Some(definition) = tile_set.tile(tile) {
+                            let uv_rect = definition.uv_rect;
+                            batch.instances.push(Instance {
+                                gpu_data: InstanceData {
+                                    color: definition.color.srgb_to_linear(),
+                                    uv_transform: Vector4::new(
+                                        uv_rect.x(),
+                                        uv_rect.y(),
+                                        uv_rect.w(),
+                                        uv_rect.h(),
+                                    ),
+                                    world_matrix: tile_map.tile_world_matrix(position),
+                                },
+                                aabb,
+                            });
+                        }
+                    }
+                }
             }
         }
 
@@ -210,14 +277,14 @@ impl Renderer2d {
         let mut stats = RenderPassStatistics::default();
         let quad = self.geometry_cache.get(state, &self.quad);
 
-        self.batch_storage
-            .generate_batches(state, graph, texture_cache, white_dummy);
-
         let view_projection = camera.view_projection_matrix();
 
         let frustum = Frustum::from_view_projection_matrix(camera.view_projection_matrix())
             .unwrap_or_default();
 
+        self.batch_storage
+            .generate_batches(state, graph, &frustum, texture_cache, white_dummy);
+
         const MAX_LIGHTS: usize = 16;
         let mut light_count = 0;
         let mut light_color_radius = [Vector4::default(); MAX_LIGHTS];
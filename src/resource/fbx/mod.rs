@@ -17,7 +17,6 @@ use crate::{
         algebra::{Matrix4, Point3, UnitQuaternion, Vector2, Vector3, Vector4},
         curve::{CurveKey, CurveKeyKind},
         instant::Instant,
-        io,
         log::{Log, MessageKind},
         math::{self, triangulator::triangulate, RotationOrder},
         pool::Handle,
@@ -36,7 +35,7 @@ use crate::{
                 FbxComponent, FbxMapping, FbxScene,
             },
         },
-        model::{MaterialSearchOptions, ModelImportOptions},
+        model::ModelImportOptions,
         texture::Texture,
     },
     scene::{
@@ -66,7 +65,6 @@ use std::{
     hash::{Hash, Hasher},
     path::Path,
 };
-use walkdir::WalkDir;
 
 /// Input angles in degrees
 fn quat_from_euler(euler: Vector3<f32>) -> UnitQuaternion<f32> {
@@ -315,38 +313,10 @@ async fn create_surfaces(
                 let texture = fbx_scene.get(*texture_handle).as_texture()?;
                 let path = texture.get_file_path();
                 if let Some(filename) = path.file_name() {
-                    let texture_path = match model_import_options.material_search_options {
-                        MaterialSearchOptions::MaterialsDirectory(ref directory) => {
-                            Some(directory.join(filename))
-                        }
-                        MaterialSearchOptions::RecursiveUp => {
-                            let mut texture_path = None;
-                            let mut path = model_path.to_owned();
-                            while let Some(parent) = path.parent() {
-                                let candidate = parent.join(filename);
-                                if io::exists(&candidate).await {
-                                    texture_path = Some(candidate);
-                                    break;
-                                }
-                                path.pop();
-                            }
-                            texture_path
-                        }
-                        MaterialSearchOptions::WorkingDirectory => {
-                            let mut texture_path = None;
-                            for dir in WalkDir::new(".").into_iter().flatten() {
-                                if dir.path().is_dir() {
-                                    let candidate = dir.path().join(filename);
-                                    if candidate.exists() {
-                                        texture_path = Some(candidate);
-                                        break;
-                                    }
-                                }
-                            }
-                            texture_path
-                        }
-                        MaterialSearchOptions::UsePathDirectly => Some(path.clone()),
-                    };
+                    let texture_path = model_import_options
+                        .material_search_options
+                        .resolve_path(model_path, path)
+                        .await;
 
                     if let Some(texture_path) = texture_path {
                         let texture =
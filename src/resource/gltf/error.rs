@@ -0,0 +1,69 @@
+//! Contains all possible errors that can occur during glTF parsing and conversion.
+
+use crate::core::io::FileLoadError;
+use std::fmt::{Display, Formatter};
+
+/// See module docs.
+#[derive(Debug)]
+pub enum GltfError {
+    /// An error occurred while parsing glTF document (invalid JSON, corrupted GLB container, etc.)
+    Gltf(gltf::Error),
+
+    /// An error occurred during file loading.
+    FileLoadError(FileLoadError),
+
+    /// Embedded data (`data:` URI) cannot be decoded.
+    InvalidDataUri(String),
+
+    /// A buffer referenced by some accessor is missing.
+    MissingBuffer(usize),
+
+    /// Document does not have any scenes.
+    NoScenes,
+
+    /// Arbitrary error that can have any meaning.
+    Custom(Box<String>),
+}
+
+impl Display for GltfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GltfError::Gltf(v) => {
+                write!(f, "glTF: Parsing error: {v}")
+            }
+            GltfError::FileLoadError(v) => {
+                write!(f, "glTF: File load error {v:?}.")
+            }
+            GltfError::InvalidDataUri(v) => {
+                write!(f, "glTF: Unable to decode data URI: {v}")
+            }
+            GltfError::MissingBuffer(v) => {
+                write!(f, "glTF: Buffer {v} is missing.")
+            }
+            GltfError::NoScenes => {
+                write!(f, "glTF: Document does not have any scenes.")
+            }
+            GltfError::Custom(v) => {
+                write!(f, "glTF: An error has occurred: {v}")
+            }
+        }
+    }
+}
+
+impl From<gltf::Error> for GltfError {
+    fn from(err: gltf::Error) -> Self {
+        GltfError::Gltf(err)
+    }
+}
+
+impl From<FileLoadError> for GltfError {
+    fn from(err: FileLoadError) -> Self {
+        GltfError::FileLoadError(err)
+    }
+}
+
+impl From<String> for GltfError {
+    fn from(err: String) -> Self {
+        GltfError::Custom(Box::new(err))
+    }
+}
@@ -0,0 +1,1220 @@
+//! Contains all methods to load and convert glTF 2.0 model format.
+//!
+//! glTF is an open, royalty-free format for 3D scenes and models, it is the default export format
+//! of Blender and many other tools. Both variants of the format are supported: text `.gltf` (with
+//! external or `data:` URI buffers and images) and binary `.glb`. The importer converts node hierarchy,
+//! meshes (including skinned meshes and morph targets), PBR metallic-roughness materials and
+//! keyframe animations to native engine representation.
+//!
+//! Normally you should never use methods from this module directly, use resource manager to load
+//! models and create their instances.
+
+pub mod error;
+
+use crate::{
+    animation::{
+        container::{TrackDataContainer, TrackValueKind},
+        track::Track,
+        value::{ValueBinding, ValueType},
+        Animation, AnimationContainer,
+    },
+    asset::{manager::ResourceManager, Resource},
+    core::{
+        algebra::{Matrix4, Quaternion, UnitQuaternion, Vector2, Vector3, Vector4},
+        color::Color,
+        curve::{Curve, CurveKey, CurveKeyKind},
+        instant::Instant,
+        io,
+        log::{Log, MessageKind},
+        math::TriangleDefinition,
+        pool::Handle,
+        sstorage::ImmutableString,
+        uuid::Uuid,
+    },
+    material::{shader::SamplerFallback, Material, PropertyValue, SharedMaterial},
+    resource::{
+        gltf::error::GltfError,
+        model::ModelImportOptions,
+        texture::{
+            CompressionOptions, MipFilter, Texture, TextureError, TextureKind, TexturePixelKind,
+            TextureResource, TextureResourceExtension,
+        },
+    },
+    scene::{
+        animation::AnimationPlayerBuilder,
+        base::{BaseBuilder, InstanceId},
+        graph::Graph,
+        mesh::{
+            buffer::{TriangleBuffer, VertexBuffer},
+            surface::{
+                BlendShape, BlendShapesContainer, InputBlendShapeData, Surface, SurfaceData,
+                SurfaceSharedData,
+            },
+            vertex::{AnimatedVertex, StaticVertex},
+            Mesh, MeshBuilder,
+        },
+        node::Node,
+        pivot::PivotBuilder,
+        transform::TransformBuilder,
+        Scene,
+    },
+    utils,
+};
+use base64::Engine;
+use fxhash::{FxHashMap, FxHashSet};
+use gltf::{
+    animation::{util::ReadOutputs, Interpolation},
+    mesh::Mode,
+    Document, Gltf,
+};
+use half::f16;
+use image::{DynamicImage, GrayImage, Luma};
+use serde::Deserialize;
+use std::{
+    collections::hash_map::DefaultHasher,
+    f32::consts::PI,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+/// Rotation tracks are stored as Euler angles which are interpolated linearly, while glTF stores
+/// quaternions. To keep the result close to spherical interpolation, each segment of a rotation
+/// channel is subdivided so that no step rotates more than this angle (in radians).
+const MAX_ROTATION_STEP: f32 = PI / 12.0;
+
+/// Morph target names are not part of the glTF spec, but every major exporter stores them in
+/// `extras.targetNames` of a mesh.
+#[derive(Deserialize, Default)]
+struct MeshExtras {
+    #[serde(rename = "targetNames", default)]
+    target_names: Vec<String>,
+}
+
+enum ImageData {
+    Embedded(Vec<u8>),
+    External(PathBuf),
+}
+
+struct ConversionContext<'a> {
+    document: &'a Document,
+    buffers: Vec<Vec<u8>>,
+    resource_manager: ResourceManager,
+    model_path: &'a Path,
+    model_import_options: &'a ModelImportOptions,
+    embedded_textures: FxHashMap<usize, TextureResource>,
+}
+
+fn decode_data_uri(uri: &str) -> Option<Result<Vec<u8>, GltfError>> {
+    let data = uri.strip_prefix("data:")?;
+    Some(match data.split_once(";base64,") {
+        Some((_, encoded)) => base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|e| GltfError::InvalidDataUri(e.to_string())),
+        None => Err(GltfError::InvalidDataUri(
+            "Only base64-encoded data URIs are supported.".to_string(),
+        )),
+    })
+}
+
+fn uri_to_path(model_path: &Path, uri: &str) -> PathBuf {
+    // Relative URIs are resolved against the directory of the model. Spaces are the only
+    // percent-encoded characters that are commonly produced by exporters.
+    let uri = uri.replace("%20", " ");
+    model_path
+        .parent()
+        .map(|dir| dir.join(&uri))
+        .unwrap_or_else(|| PathBuf::from(uri))
+}
+
+async fn load_buffers(gltf: &Gltf, model_path: &Path) -> Result<Vec<Vec<u8>>, GltfError> {
+    let mut buffers = Vec::new();
+    for buffer in gltf.document.buffers() {
+        let data = match buffer.source() {
+            gltf::buffer::Source::Bin => gltf
+                .blob
+                .clone()
+                .ok_or(GltfError::MissingBuffer(buffer.index()))?,
+            gltf::buffer::Source::Uri(uri) => match decode_data_uri(uri) {
+                Some(data) => data?,
+                None => io::load_file(uri_to_path(model_path, uri)).await?,
+            },
+        };
+
+        if data.len() < buffer.length() {
+            return Err(GltfError::MissingBuffer(buffer.index()));
+        }
+
+        buffers.push(data);
+    }
+    Ok(buffers)
+}
+
+fn set_material_property(material: &mut Material, name: &str, value: PropertyValue) {
+    if let Err(e) = material.set_property(&ImmutableString::new(name), value) {
+        Log::writeln(
+            MessageKind::Error,
+            format!(
+                "Unable to set material property {} for glTF material! Reason: {:?}",
+                name, e
+            ),
+        );
+    }
+}
+
+/// Creates 1x1 single-channel texture filled with the given value in `[0; 1]` range.
+fn make_constant_texture(value: f32) -> Option<TextureResource> {
+    TextureResource::from_bytes(
+        TextureKind::Rectangle {
+            width: 1,
+            height: 1,
+        },
+        TexturePixelKind::R8,
+        vec![(value.clamp(0.0, 1.0) * 255.0) as u8],
+        false,
+    )
+}
+
+/// glTF packs roughness in the green channel and metalness in the blue channel of a single texture,
+/// while the standard shader samples them from the red channel of separate textures. This function
+/// splits the texture in two (and applies respective factors).
+fn split_metallic_roughness(
+    data: &[u8],
+    metallic_factor: f32,
+    roughness_factor: f32,
+) -> Result<(TextureResource, TextureResource), TextureError> {
+    let image = image::load_from_memory(data)?.to_rgba8();
+    let (width, height) = image.dimensions();
+
+    let mut metallic = GrayImage::new(width, height);
+    let mut roughness = GrayImage::new(width, height);
+    for (x, y, pixel) in image.enumerate_pixels() {
+        roughness.put_pixel(x, y, Luma([(pixel[1] as f32 * roughness_factor) as u8]));
+        metallic.put_pixel(x, y, Luma([(pixel[2] as f32 * metallic_factor) as u8]));
+    }
+
+    let make = |image: GrayImage| {
+        Texture::load_from_image(
+            DynamicImage::ImageLuma8(image),
+            CompressionOptions::NoCompression,
+            true,
+            MipFilter::default(),
+        )
+        .map(Resource::new_ok)
+    };
+
+    Ok((make(metallic)?, make(roughness)?))
+}
+
+impl<'a> ConversionContext<'a> {
+    async fn image_data(&self, image: gltf::Image<'_>) -> Option<ImageData> {
+        match image.source() {
+            gltf::image::Source::View { view, .. } => {
+                let buffer = self.buffers.get(view.buffer().index())?;
+                buffer
+                    .get(view.offset()..view.offset() + view.length())
+                    .map(|data| ImageData::Embedded(data.to_vec()))
+            }
+            gltf::image::Source::Uri { uri, .. } => match decode_data_uri(uri) {
+                Some(Ok(data)) => Some(ImageData::Embedded(data)),
+                Some(Err(e)) => {
+                    Log::err(format!("Unable to load glTF image. Reason: {}", e));
+                    None
+                }
+                None => {
+                    let path = uri_to_path(self.model_path, uri);
+                    let mut resolved = self
+                        .model_import_options
+                        .material_search_options
+                        .resolve_path(self.model_path, &path)
+                        .await;
+                    // Unlike FBX, glTF stores relative paths, so they're valid most of the time and
+                    // could be used if the search has failed.
+                    if resolved.is_none() && io::exists(&path).await {
+                        resolved = Some(path.clone());
+                    }
+                    if resolved.is_none() {
+                        Log::writeln(
+                            MessageKind::Warning,
+                            format!(
+                                "Unable to find a texture {:?} for 3D model {:?} using {:?} option!",
+                                path, self.model_path, self.model_import_options
+                            ),
+                        );
+                    }
+                    resolved.map(ImageData::External)
+                }
+            },
+        }
+    }
+
+    async fn image_bytes(&self, image: gltf::Image<'_>) -> Option<Vec<u8>> {
+        match self.image_data(image).await? {
+            ImageData::Embedded(data) => Some(data),
+            ImageData::External(path) => match io::load_file(&path).await {
+                Ok(data) => Some(data),
+                Err(e) => {
+                    Log::err(format!(
+                        "Unable to load glTF image {:?}. Reason: {:?}",
+                        path, e
+                    ));
+                    None
+                }
+            },
+        }
+    }
+
+    async fn texture(&mut self, texture: gltf::Texture<'_>) -> Option<TextureResource> {
+        let image = texture.source();
+        let index = image.index();
+
+        if let Some(texture) = self.embedded_textures.get(&index) {
+            return Some(texture.clone());
+        }
+
+        match self.image_data(image).await? {
+            ImageData::External(path) => Some(self.resource_manager.request::<Texture, _>(path)),
+            ImageData::Embedded(data) => {
+                match TextureResource::load_from_memory(
+                    &data,
+                    CompressionOptions::NoCompression,
+                    true,
+                    MipFilter::default(),
+                ) {
+                    Ok(texture) => {
+                        self.embedded_textures.insert(index, texture.clone());
+                        Some(texture)
+                    }
+                    Err(e) => {
+                        Log::err(format!(
+                            "Unable to load embedded glTF texture. Reason: {:?}",
+                            e
+                        ));
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    async fn set_texture(
+        &mut self,
+        material: &mut Material,
+        name: &str,
+        texture: gltf::Texture<'_>,
+        tex_coord: u32,
+        fallback: SamplerFallback,
+    ) {
+        if tex_coord != 0 {
+            Log::warn(format!(
+                "glTF: Texture {} uses texture coordinates set {}, but only the first set is supported.",
+                name, tex_coord
+            ));
+        }
+
+        if let Some(texture) = self.texture(texture).await {
+            set_material_property(
+                material,
+                name,
+                PropertyValue::Sampler {
+                    value: Some(texture),
+                    fallback,
+                },
+            );
+        }
+    }
+
+    async fn convert_material(&mut self, gltf_material: gltf::Material<'_>) -> SharedMaterial {
+        let mut material = if gltf_material.double_sided() {
+            Material::standard_two_sides()
+        } else {
+            Material::standard()
+        };
+
+        let pbr = gltf_material.pbr_metallic_roughness();
+
+        set_material_property(
+            &mut material,
+            "diffuseColor",
+            PropertyValue::Color(Color::from(Vector4::from(pbr.base_color_factor()))),
+        );
+
+        if let Some(info) = pbr.base_color_texture() {
+            self.set_texture(
+                &mut material,
+                "diffuseTexture",
+                info.texture(),
+                info.tex_coord(),
+                SamplerFallback::White,
+            )
+            .await;
+        }
+
+        if let Some(normal) = gltf_material.normal_texture() {
+            self.set_texture(
+                &mut material,
+                "normalTexture",
+                normal.texture(),
+                normal.tex_coord(),
+                SamplerFallback::Normal,
+            )
+            .await;
+        }
+
+        if let Some(occlusion) = gltf_material.occlusion_texture() {
+            self.set_texture(
+                &mut material,
+                "aoTexture",
+                occlusion.texture(),
+                occlusion.tex_coord(),
+                SamplerFallback::White,
+            )
+            .await;
+        }
+
+        let emissive_factor = Vector3::from(gltf_material.emissive_factor());
+        if let Some(info) = gltf_material.emissive_texture() {
+            self.set_texture(
+                &mut material,
+                "emissionTexture",
+                info.texture(),
+                info.tex_coord(),
+                SamplerFallback::Black,
+            )
+            .await;
+            set_material_property(
+                &mut material,
+                "emissionStrength",
+                PropertyValue::Vector3(emissive_factor),
+            );
+        } else if emissive_factor != Vector3::default() {
+            // Constant emission: white fallback multiplied by the factor.
+            set_material_property(
+                &mut material,
+                "emissionTexture",
+                PropertyValue::Sampler {
+                    value: None,
+                    fallback: SamplerFallback::White,
+                },
+            );
+            set_material_property(
+                &mut material,
+                "emissionStrength",
+                PropertyValue::Vector3(emissive_factor),
+            );
+        }
+
+        let metallic_factor = pbr.metallic_factor();
+        let roughness_factor = pbr.roughness_factor();
+        let mut metallic_roughness = None;
+        if let Some(info) = pbr.metallic_roughness_texture() {
+            if let Some(data) = self.image_bytes(info.texture().source()).await {
+                match split_metallic_roughness(&data, metallic_factor, roughness_factor) {
+                    Ok(textures) => metallic_roughness = Some(textures),
+                    Err(e) => Log::err(format!(
+                        "Unable to load glTF metallic-roughness texture. Reason: {:?}",
+                        e
+                    )),
+                }
+            }
+        }
+        let (metallic, roughness) = match metallic_roughness {
+            Some((metallic, roughness)) => (Some(metallic), Some(roughness)),
+            None => (
+                make_constant_texture(metallic_factor),
+                make_constant_texture(roughness_factor),
+            ),
+        };
+        set_material_property(
+            &mut material,
+            "metallicTexture",
+            PropertyValue::Sampler {
+                value: metallic,
+                fallback: SamplerFallback::Black,
+            },
+        );
+        set_material_property(
+            &mut material,
+            "roughnessTexture",
+            PropertyValue::Sampler {
+                value: roughness,
+                fallback: SamplerFallback::White,
+            },
+        );
+
+        SharedMaterial::new(material)
+    }
+
+    fn buffer(&self, buffer: gltf::Buffer<'_>) -> Option<&[u8]> {
+        self.buffers.get(buffer.index()).map(|data| data.as_slice())
+    }
+}
+
+/// Calculates smooth per-vertex normals by averaging normals of adjacent triangles.
+fn calculate_smooth_normals(
+    positions: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+) -> Vec<Vector3<f32>> {
+    let mut normals = vec![Vector3::default(); positions.len()];
+    for triangle in triangles {
+        let [a, b, c] = triangle.0.map(|i| i as usize);
+        let normal = (positions[b] - positions[a]).cross(&(positions[c] - positions[a]));
+        normals[a] += normal;
+        normals[b] += normal;
+        normals[c] += normal;
+    }
+    for normal in normals.iter_mut() {
+        *normal = normal
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector3::y);
+    }
+    normals
+}
+
+/// Morph targets store offsets for every vertex, but only changed ones are needed.
+fn collect_offsets(
+    offsets: impl Iterator<Item = [f32; 3]>,
+    dest: &mut FxHashMap<u32, Vector3<f16>>,
+) {
+    for (index, offset) in offsets.enumerate() {
+        if offset != [0.0; 3] {
+            dest.insert(
+                index as u32,
+                utils::vec3_f16_from_f32(Vector3::from(offset)),
+            );
+        }
+    }
+}
+
+fn convert_primitive(
+    ctx: &ConversionContext,
+    primitive: &gltf::Primitive,
+    skinned: bool,
+    blend_shape_names: &[String],
+    default_weights: &[f32],
+) -> Result<Option<SurfaceData>, GltfError> {
+    if primitive.mode() != Mode::Triangles {
+        Log::warn(format!(
+            "glTF: Primitive {} uses unsupported mode {:?} and will be skipped.",
+            primitive.index(),
+            primitive.mode()
+        ));
+        return Ok(None);
+    }
+
+    let reader = primitive.reader(|buffer| ctx.buffer(buffer));
+
+    let positions = match reader.read_positions() {
+        Some(positions) => positions.map(Vector3::from).collect::<Vec<_>>(),
+        None => return Ok(None),
+    };
+
+    let indices = match reader.read_indices() {
+        Some(indices) => indices.into_u32().collect::<Vec<_>>(),
+        None => (0..positions.len() as u32).collect(),
+    };
+    if indices.iter().any(|&i| i as usize >= positions.len()) {
+        return Err(GltfError::from(format!(
+            "Primitive {} has out-of-bounds vertex indices.",
+            primitive.index()
+        )));
+    }
+    let triangles = indices
+        .chunks_exact(3)
+        .map(|t| TriangleDefinition([t[0], t[1], t[2]]))
+        .collect::<Vec<_>>();
+
+    let normals = match reader.read_normals() {
+        Some(normals) => normals.map(Vector3::from).collect::<Vec<_>>(),
+        None => calculate_smooth_normals(&positions, &triangles),
+    };
+    let tangents = reader
+        .read_tangents()
+        .map(|tangents| tangents.map(Vector4::from).collect::<Vec<_>>());
+    let tex_coords = reader
+        .read_tex_coords(0)
+        .map(|uvs| uvs.into_f32().map(Vector2::from).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let vertex = |i: usize| StaticVertex {
+        position: positions[i],
+        tex_coord: tex_coords.get(i).cloned().unwrap_or_default(),
+        normal: normals.get(i).cloned().unwrap_or_else(Vector3::y),
+        tangent: tangents
+            .as_ref()
+            .and_then(|t| t.get(i).cloned())
+            .unwrap_or_default(),
+    };
+
+    let vertex_buffer = if skinned {
+        let joints = reader
+            .read_joints(0)
+            .map(|joints| joints.into_u16().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let weights = reader
+            .read_weights(0)
+            .map(|weights| weights.into_f32().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        if joints.iter().flatten().any(|&j| j > u8::MAX as u16) {
+            Log::warn(format!(
+                "glTF: Primitive {} references more than 256 joints, extra joints will be ignored.",
+                primitive.index()
+            ));
+        }
+
+        let vertices = (0..positions.len())
+            .map(|i| {
+                let StaticVertex {
+                    position,
+                    tex_coord,
+                    normal,
+                    tangent,
+                } = vertex(i);
+                AnimatedVertex {
+                    position,
+                    tex_coord,
+                    normal,
+                    tangent,
+                    bone_weights: weights.get(i).cloned().unwrap_or_default(),
+                    bone_indices: joints
+                        .get(i)
+                        .map(|j| j.map(|index| index.min(u8::MAX as u16) as u8))
+                        .unwrap_or_default(),
+                }
+            })
+            .collect::<Vec<_>>();
+        VertexBuffer::new(vertices.len(), AnimatedVertex::layout(), vertices)
+    } else {
+        let vertices = (0..positions.len()).map(vertex).collect::<Vec<_>>();
+        VertexBuffer::new(vertices.len(), StaticVertex::layout(), vertices)
+    }
+    .map_err(|e| GltfError::from(format!("Invalid vertex buffer: {:?}", e)))?;
+
+    let mut data = SurfaceData::new(vertex_buffer, TriangleBuffer::new(triangles), false);
+
+    if tangents.is_none() {
+        if let Err(e) = data.calculate_tangents() {
+            Log::err(format!("glTF: Unable to calculate tangents: {:?}", e));
+        }
+    }
+
+    let mut blend_shapes = Vec::new();
+    for (i, (positions, normals, tangents)) in reader.read_morph_targets().enumerate() {
+        let mut blend_shape = InputBlendShapeData {
+            default_weight: default_weights.get(i).cloned().unwrap_or_default() * 100.0,
+            name: blend_shape_names
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| format!("Shape{}", i)),
+            positions: Default::default(),
+            normals: Default::default(),
+            tangents: Default::default(),
+        };
+
+        if let Some(offsets) = positions {
+            collect_offsets(offsets, &mut blend_shape.positions);
+        }
+        if let Some(offsets) = normals {
+            collect_offsets(offsets, &mut blend_shape.normals);
+        }
+        if let Some(offsets) = tangents {
+            collect_offsets(offsets, &mut blend_shape.tangents);
+        }
+
+        blend_shapes.push(blend_shape);
+    }
+
+    if !blend_shapes.is_empty() {
+        data.blend_shapes_container = Some(BlendShapesContainer::from_lists(
+            &data.vertex_buffer,
+            &blend_shapes,
+        ));
+    }
+
+    Ok(Some(data))
+}
+
+fn node_name(node: &gltf::Node) -> String {
+    node.name()
+        .map(|name| name.to_owned())
+        .unwrap_or_else(|| format!("Node{}", node.index()))
+}
+
+fn convert_node_to_base(node: &gltf::Node) -> BaseBuilder {
+    let name = node_name(node);
+
+    // Use name of the node to generate instance id, the same way as the FBX importer does. Indices of
+    // nodes could change on every export, while names are usually stable.
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    let hash = hasher.finish();
+    let instance_id = InstanceId(Uuid::from_u64_pair(hash, hash));
+
+    let (translation, rotation, scale) = node.transform().decomposed();
+
+    BaseBuilder::new()
+        .with_name(name)
+        .with_instance_id(instance_id)
+        .with_local_transform(
+            TransformBuilder::new()
+                .with_local_position(Vector3::from(translation))
+                .with_local_rotation(UnitQuaternion::from_quaternion(Quaternion::new(
+                    rotation[3],
+                    rotation[0],
+                    rotation[1],
+                    rotation[2],
+                )))
+                .with_local_scale(Vector3::from(scale))
+                .build(),
+        )
+}
+
+fn convert_mesh(
+    ctx: &ConversionContext,
+    base: BaseBuilder,
+    gltf_mesh: gltf::Mesh,
+    skinned: bool,
+    materials: &[SharedMaterial],
+    graph: &mut Graph,
+) -> Result<Handle<Node>, GltfError> {
+    let target_names = gltf_mesh
+        .extras()
+        .as_ref()
+        .and_then(|extras| gltf::json::deserialize::from_str::<MeshExtras>(extras.get()).ok())
+        .unwrap_or_default()
+        .target_names;
+    let default_weights = gltf_mesh.weights().unwrap_or_default();
+
+    let mut surfaces = Vec::new();
+    let mut blend_shape_count = 0;
+    for primitive in gltf_mesh.primitives() {
+        if let Some(data) =
+            convert_primitive(ctx, &primitive, skinned, &target_names, default_weights)?
+        {
+            if let Some(container) = data.blend_shapes_container.as_ref() {
+                blend_shape_count = blend_shape_count.max(container.blend_shapes.len());
+            }
+
+            let mut surface = Surface::new(SurfaceSharedData::new(data));
+            if let Some(material) = primitive
+                .material()
+                .index()
+                .and_then(|index| materials.get(index))
+            {
+                surface.set_material(material.clone());
+            }
+            surfaces.push(surface);
+        }
+    }
+
+    let blend_shapes = (0..blend_shape_count)
+        .map(|i| BlendShape {
+            weight: default_weights.get(i).cloned().unwrap_or_default() * 100.0,
+            name: target_names
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| format!("Shape{}", i)),
+        })
+        .collect();
+
+    Ok(MeshBuilder::new(base)
+        .with_blend_shapes(blend_shapes)
+        .with_surfaces(surfaces)
+        .build(graph))
+}
+
+fn convert_skin(
+    ctx: &ConversionContext,
+    mesh_handle: Handle<Node>,
+    skin: gltf::Skin,
+    node_map: &FxHashMap<usize, Handle<Node>>,
+    graph: &mut Graph,
+) -> Result<(), GltfError> {
+    let mut bones = Vec::new();
+    for joint in skin.joints() {
+        bones.push(
+            *node_map
+                .get(&joint.index())
+                .ok_or_else(|| format!("Skin joint {} is not in the scene.", joint.index()))?,
+        );
+    }
+
+    let reader = skin.reader(|buffer| ctx.buffer(buffer));
+    let inverse_bind_matrices = reader
+        .read_inverse_bind_matrices()
+        .map(|matrices| matrices.map(Matrix4::from).collect::<Vec<_>>())
+        .unwrap_or_else(|| vec![Matrix4::identity(); bones.len()]);
+
+    for (&bone, inverse_bind_matrix) in bones.iter().zip(inverse_bind_matrices) {
+        let bone_node = &mut graph[bone];
+        // A node can be a joint of multiple skins, the engine stores only one bind pose per node.
+        if bone_node.inv_bind_pose_transform != Matrix4::identity()
+            && bone_node.inv_bind_pose_transform != inverse_bind_matrix
+        {
+            Log::warn(format!(
+                "glTF: Joint {} has different bind poses in different skins, only the first one is used.",
+                bone_node.name()
+            ));
+        } else {
+            bone_node.inv_bind_pose_transform = inverse_bind_matrix;
+        }
+    }
+
+    if let Some(mesh) = graph[mesh_handle].cast_mut::<Mesh>() {
+        for surface in mesh.surfaces_mut() {
+            surface.bones.set_value_silent(bones.clone());
+        }
+    }
+
+    Ok(())
+}
+
+/// Extracts values of key frames from sampler output. Cubic spline samplers store in-tangent, value and
+/// out-tangent for each key, tangents are discarded and such samplers are imported as linear.
+fn key_frame_values<T: Copy>(values: Vec<T>, interpolation: Interpolation) -> Vec<T> {
+    if interpolation == Interpolation::CubicSpline {
+        values.chunks_exact(3).map(|triple| triple[1]).collect()
+    } else {
+        values
+    }
+}
+
+fn curve_key_kind(interpolation: Interpolation) -> CurveKeyKind {
+    match interpolation {
+        Interpolation::Step => CurveKeyKind::Constant,
+        Interpolation::Linear | Interpolation::CubicSpline => CurveKeyKind::Linear,
+    }
+}
+
+fn make_vector3_track(
+    mut track: Track,
+    times: &[f32],
+    values: &[Vector3<f32>],
+    interpolation: Interpolation,
+) -> Track {
+    let kind = curve_key_kind(interpolation);
+    let curves = track.data_container_mut().curves_mut();
+    for (&time, value) in times.iter().zip(values) {
+        for (curve, &component) in curves.iter_mut().zip(value.iter()) {
+            curve.add_key(CurveKey::new(time, component, kind.clone()));
+        }
+    }
+    track
+}
+
+/// Returns the angle closest to `previous` that is equivalent to the given one.
+fn unwrap_angle(angle: f32, previous: f32) -> f32 {
+    let two_pi = 2.0 * PI;
+    angle + ((previous - angle) / two_pi).round() * two_pi
+}
+
+fn make_rotation_track(
+    times: &[f32],
+    values: &[UnitQuaternion<f32>],
+    interpolation: Interpolation,
+) -> Track {
+    let mut samples = Vec::new();
+    for (i, (&time, &rotation)) in times.iter().zip(values).enumerate() {
+        samples.push((time, rotation));
+
+        if interpolation == Interpolation::Step {
+            continue;
+        }
+
+        if let (Some(&next_time), Some(next_rotation)) = (times.get(i + 1), values.get(i + 1)) {
+            let steps = (rotation.angle_to(next_rotation) / MAX_ROTATION_STEP).ceil() as usize;
+            for step in 1..steps {
+                let t = step as f32 / steps as f32;
+                samples.push((
+                    time + (next_time - time) * t,
+                    rotation.slerp(next_rotation, t),
+                ));
+            }
+        }
+    }
+
+    let kind = curve_key_kind(interpolation);
+    let mut track = Track::new_rotation();
+    let curves = track.data_container_mut().curves_mut();
+    let mut previous: Option<Vector3<f32>> = None;
+    for (time, rotation) in samples {
+        let (x, y, z) = rotation.euler_angles();
+        let mut angles = Vector3::new(x, y, z);
+        if let Some(previous) = previous {
+            for (angle, previous) in angles.iter_mut().zip(previous.iter()) {
+                *angle = unwrap_angle(*angle, *previous);
+            }
+        }
+        for (curve, &angle) in curves.iter_mut().zip(angles.iter()) {
+            curve.add_key(CurveKey::new(time, angle, kind.clone()));
+        }
+        previous = Some(angles);
+    }
+    track
+}
+
+fn make_blend_shape_tracks(
+    times: &[f32],
+    weights: &[f32],
+    interpolation: Interpolation,
+) -> Vec<Track> {
+    let count = if times.is_empty() {
+        0
+    } else {
+        weights.len() / times.len()
+    };
+    if count == 0 {
+        return Default::default();
+    }
+
+    let kind = curve_key_kind(interpolation);
+    (0..count)
+        .map(|i| {
+            let mut data_container = TrackDataContainer::new(TrackValueKind::Real);
+            data_container.curves_mut()[0] = Curve::from(
+                times
+                    .iter()
+                    .zip(weights.chunks_exact(count))
+                    .map(|(&time, weights)| CurveKey::new(time, weights[i] * 100.0, kind.clone()))
+                    .collect::<Vec<_>>(),
+            );
+            Track::new(
+                data_container,
+                ValueBinding::Property {
+                    name: format!("blend_shapes[{}].weight", i),
+                    value_type: ValueType::F32,
+                },
+            )
+        })
+        .collect()
+}
+
+fn convert_animation(
+    ctx: &ConversionContext,
+    gltf_animation: gltf::Animation,
+    node_map: &FxHashMap<usize, Handle<Node>>,
+) -> Animation {
+    let mut animation = Animation::default();
+    animation.set_name(
+        gltf_animation
+            .name()
+            .map(|name| name.to_owned())
+            .unwrap_or_else(|| format!("Animation{}", gltf_animation.index())),
+    );
+
+    for channel in gltf_animation.channels() {
+        let target = match node_map.get(&channel.target().node().index()) {
+            Some(target) => *target,
+            None => continue,
+        };
+
+        let reader = channel.reader(|buffer| ctx.buffer(buffer));
+        let (times, outputs) = match (reader.read_inputs(), reader.read_outputs()) {
+            (Some(inputs), Some(outputs)) => (inputs.collect::<Vec<_>>(), outputs),
+            _ => continue,
+        };
+        let interpolation = channel.sampler().interpolation();
+
+        let tracks = match outputs {
+            ReadOutputs::Translations(translations) => vec![make_vector3_track(
+                Track::new_position(),
+                &times,
+                &key_frame_values(translations.map(Vector3::from).collect(), interpolation),
+                interpolation,
+            )],
+            ReadOutputs::Scales(scales) => vec![make_vector3_track(
+                Track::new_scale(),
+                &times,
+                &key_frame_values(scales.map(Vector3::from).collect(), interpolation),
+                interpolation,
+            )],
+            ReadOutputs::Rotations(rotations) => {
+                let rotations = rotations
+                    .into_f32()
+                    .map(|[x, y, z, w]| {
+                        UnitQuaternion::from_quaternion(Quaternion::new(w, x, y, z))
+                    })
+                    .collect();
+                vec![make_rotation_track(
+                    &times,
+                    &key_frame_values(rotations, interpolation),
+                    interpolation,
+                )]
+            }
+            ReadOutputs::MorphTargetWeights(weights) => make_blend_shape_tracks(
+                &times,
+                &key_frame_values(weights.into_f32().collect(), interpolation),
+                interpolation,
+            ),
+        };
+
+        for mut track in tracks {
+            track.set_target(target);
+            animation.add_track(track);
+        }
+    }
+
+    animation.fit_length_to_content();
+
+    animation
+}
+
+///
+/// Converts glTF document to native engine representation.
+///
+async fn convert(ctx: &mut ConversionContext<'_>, scene: &mut Scene) -> Result<(), GltfError> {
+    let document = ctx.document;
+
+    let gltf_scene = document
+        .default_scene()
+        .or_else(|| document.scenes().next())
+        .ok_or(GltfError::NoScenes)?;
+
+    let mut materials = Vec::new();
+    for gltf_material in document.materials() {
+        materials.push(ctx.convert_material(gltf_material).await);
+    }
+
+    let root = scene.graph.get_root();
+    let mut node_map = FxHashMap::default();
+    let mut skinned_meshes = Vec::new();
+    let mut stack = gltf_scene
+        .nodes()
+        .map(|node| (node, root))
+        .collect::<Vec<_>>();
+    while let Some((gltf_node, parent)) = stack.pop() {
+        let base = convert_node_to_base(&gltf_node);
+
+        let node_handle = if let Some(gltf_mesh) = gltf_node.mesh() {
+            let handle = convert_mesh(
+                ctx,
+                base,
+                gltf_mesh,
+                gltf_node.skin().is_some(),
+                &materials,
+                &mut scene.graph,
+            )?;
+            if let Some(skin) = gltf_node.skin() {
+                skinned_meshes.push((handle, skin));
+            }
+            handle
+        } else {
+            PivotBuilder::new(base).build(&mut scene.graph)
+        };
+
+        scene.graph.link_nodes(node_handle, parent);
+        node_map.insert(gltf_node.index(), node_handle);
+
+        stack.extend(gltf_node.children().map(|child| (child, node_handle)));
+    }
+
+    for (mesh_handle, skin) in skinned_meshes {
+        convert_skin(ctx, mesh_handle, skin, &node_map, &mut scene.graph)?;
+    }
+
+    let mut animations_container = AnimationContainer::new();
+    for gltf_animation in document.animations() {
+        let animation = convert_animation(ctx, gltf_animation, &node_map);
+        if !animation.tracks().is_empty() {
+            animations_container.add(animation);
+        }
+    }
+
+    // Do not create animation player if there's no animation content.
+    if animations_container.iter().next().is_some() {
+        AnimationPlayerBuilder::new(BaseBuilder::new().with_name("AnimationPlayer"))
+            .with_animations(animations_container)
+            .build(&mut scene.graph);
+    }
+
+    scene.graph.update_hierarchical_data();
+
+    Ok(())
+}
+
+/// Tries to load and convert glTF (or GLB) from given path.
+///
+/// Normally you should never use this method, use resource manager to load models.
+pub async fn load_to_scene<P: AsRef<Path>>(
+    scene: &mut Scene,
+    resource_manager: ResourceManager,
+    path: P,
+    model_import_options: &ModelImportOptions,
+) -> Result<(), GltfError> {
+    let start_time = Instant::now();
+
+    Log::writeln(
+        MessageKind::Information,
+        format!("Trying to load {:?}", path.as_ref()),
+    );
+
+    let now = Instant::now();
+    let data = io::load_file(path.as_ref()).await?;
+    let gltf = Gltf::from_slice(&data)?;
+    let buffers = load_buffers(&gltf, path.as_ref()).await?;
+    let parsing_time = now.elapsed().as_millis();
+
+    let now = Instant::now();
+    let mut ctx = ConversionContext {
+        document: &gltf.document,
+        buffers,
+        resource_manager,
+        model_path: path.as_ref(),
+        model_import_options,
+        embedded_textures: Default::default(),
+    };
+    convert(&mut ctx, scene).await?;
+    let conversion_time = now.elapsed().as_millis();
+
+    Log::writeln(
+        MessageKind::Information,
+        format!(
+            "glTF {:?} loaded in {} ms\n\t- Parsing - {} ms\n\t- Conversion - {} ms",
+            path.as_ref(),
+            start_time.elapsed().as_millis(),
+            parsing_time,
+            conversion_time
+        ),
+    );
+
+    // Animations are retargeted by names, so warn about duplicates the same way as for FBX.
+    let mut names = FxHashSet::<String>::default();
+    for node in scene.graph.linear_iter() {
+        if !names.insert(node.name_owned()) {
+            Log::writeln(
+                MessageKind::Warning,
+                format!(
+                    "A node with existing name {} was found during the load of {} resource! \
+                    Animation retargeting and property inheritance may not work correctly.",
+                    node.name(),
+                    path.as_ref().display()
+                ),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::manager::ResourceManager,
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            futures::executor::block_on,
+        },
+        material::SharedMaterial,
+        resource::{
+            gltf::{
+                decode_data_uri, key_frame_values, load_to_scene, make_blend_shape_tracks,
+                make_rotation_track, unwrap_angle,
+            },
+            model::ModelImportOptions,
+        },
+        scene::{mesh::Mesh, Scene},
+    };
+    use gltf::animation::Interpolation;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_load_to_scene() {
+        let mut scene = Scene::new();
+        block_on(load_to_scene(
+            &mut scene,
+            ResourceManager::new(),
+            "test_data/triangles.gltf",
+            &ModelImportOptions::default(),
+        ))
+        .unwrap();
+
+        // Scene root + three nodes of the file, there are no animations.
+        assert_eq!(scene.graph.node_count(), 4);
+        for name in ["Root", "TriangleA", "TriangleB"] {
+            assert!(scene.graph.find_by_name_from_root(name).is_some());
+        }
+
+        let meshes = scene
+            .graph
+            .linear_iter()
+            .filter_map(|node| node.cast::<Mesh>())
+            .collect::<Vec<_>>();
+        assert_eq!(meshes.len(), 2);
+
+        let surfaces = meshes
+            .iter()
+            .flat_map(|mesh| mesh.surfaces())
+            .collect::<Vec<_>>();
+        assert_eq!(surfaces.len(), 3);
+
+        // Surfaces that use the same glTF material share the same material instance.
+        let mut materials = Vec::<SharedMaterial>::new();
+        for surface in surfaces {
+            if !materials.contains(surface.material()) {
+                materials.push(surface.material().clone());
+            }
+        }
+        assert_eq!(materials.len(), 2);
+    }
+
+    #[test]
+    fn test_decode_data_uri() {
+        assert_eq!(
+            decode_data_uri("data:application/octet-stream;base64,AQID")
+                .unwrap()
+                .unwrap(),
+            vec![1, 2, 3]
+        );
+        assert!(decode_data_uri("data:text/plain,foo").unwrap().is_err());
+        assert!(decode_data_uri("foo.bin").is_none());
+    }
+
+    #[test]
+    fn test_cubic_spline_values() {
+        assert_eq!(
+            key_frame_values(vec![0, 1, 2, 3, 4, 5], Interpolation::CubicSpline),
+            vec![1, 4]
+        );
+        assert_eq!(
+            key_frame_values(vec![0, 1, 2], Interpolation::Linear),
+            vec![0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn test_unwrap_angle() {
+        assert!((unwrap_angle(-PI + 0.1, PI - 0.1) - (PI + 0.1)).abs() < 1.0e-5);
+        assert!((unwrap_angle(0.5, 0.4) - 0.5).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn test_rotation_track_matches_slerp() {
+        let a = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 0.0);
+        let b = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 170.0f32.to_radians());
+        let track = make_rotation_track(&[0.0, 1.0], &[a, b], Interpolation::Linear);
+
+        // The segment must be subdivided to keep Euler interpolation close to slerp.
+        assert!(track.data_container().curves_ref()[0].keys().len() > 2);
+
+        for t in [0.0, 0.25, 0.5, 0.75, 1.0] {
+            let expected = a.slerp(&b, t);
+            let value = track.fetch(t).unwrap().value;
+            let actual = match value {
+                crate::animation::value::TrackValue::UnitQuaternion(q) => q,
+                _ => unreachable!(),
+            };
+            assert!(actual.angle_to(&expected) < 0.05);
+        }
+    }
+
+    #[test]
+    fn test_blend_shape_tracks() {
+        let tracks =
+            make_blend_shape_tracks(&[0.0, 1.0], &[0.0, 0.5, 1.0, 0.25], Interpolation::Linear);
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(
+            tracks[1].data_container().curves_ref()[0].keys()[1].value,
+            25.0
+        );
+    }
+}
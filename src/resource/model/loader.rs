@@ -27,7 +27,7 @@ pub struct ModelLoader {
 
 impl ResourceLoader for ModelLoader {
     fn extensions(&self) -> &[&str] {
-        &["rgs", "fbx"]
+        &["rgs", "fbx", "gltf", "glb"]
     }
 
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
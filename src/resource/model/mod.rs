@@ -15,8 +15,9 @@
 //!
 //! # Supported formats
 //!
-//! Currently only FBX (common format in game industry for storing complex 3d models)
-//! and RGS (native Fyroxed format) formats are supported.
+//! Currently FBX (common format in game industry for storing complex 3d models), glTF 2.0
+//! (both `.gltf` and binary `.glb` variants) and RGS (native Fyroxed format) formats are
+//! supported.
 
 use crate::{
     animation::Animation,
@@ -26,6 +27,7 @@ use crate::{
     },
     core::{
         algebra::{UnitQuaternion, Vector3},
+        io,
         log::{Log, MessageKind},
         pool::Handle,
         reflect::prelude::*,
@@ -35,7 +37,10 @@ use crate::{
         TypeUuidProvider,
     },
     engine::SerializationContext,
-    resource::fbx::{self, error::FbxError},
+    resource::{
+        fbx::{self, error::FbxError},
+        gltf::{self, error::GltfError},
+    },
     scene::{
         animation::AnimationPlayer,
         graph::{map::NodeHandleMap, Graph},
@@ -52,6 +57,7 @@ use std::{
     sync::Arc,
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+use walkdir::WalkDir;
 
 pub mod loader;
 
@@ -404,6 +410,41 @@ impl MaterialSearchOptions {
     pub fn materials_directory<P: AsRef<Path>>(path: P) -> Self {
         Self::MaterialsDirectory(path.as_ref().to_path_buf())
     }
+
+    /// Tries to find an external resource (usually a texture) referenced by a 3D model at `model_path`.
+    /// The file name is stripped from `path` and then it is searched using the current search option.
+    /// Returns `None` if nothing was found.
+    pub(crate) async fn resolve_path(&self, model_path: &Path, path: &Path) -> Option<PathBuf> {
+        let filename = path.file_name()?;
+        match self {
+            MaterialSearchOptions::MaterialsDirectory(ref directory) => {
+                Some(directory.join(filename))
+            }
+            MaterialSearchOptions::RecursiveUp => {
+                let mut path = model_path.to_owned();
+                while let Some(parent) = path.parent() {
+                    let candidate = parent.join(filename);
+                    if io::exists(&candidate).await {
+                        return Some(candidate);
+                    }
+                    path.pop();
+                }
+                None
+            }
+            MaterialSearchOptions::WorkingDirectory => {
+                for dir in WalkDir::new(".").into_iter().flatten() {
+                    if dir.path().is_dir() {
+                        let candidate = dir.path().join(filename);
+                        if candidate.exists() {
+                            return Some(candidate);
+                        }
+                    }
+                }
+                None
+            }
+            MaterialSearchOptions::UsePathDirectly => Some(path.to_path_buf()),
+        }
+    }
 }
 
 /// A set of options that will be applied to a model resource when loading it from external source.
@@ -440,6 +481,8 @@ pub enum ModelLoadError {
     NotSupported(String),
     /// An error occurred while loading FBX file.
     Fbx(FbxError),
+    /// An error occurred while loading glTF file.
+    Gltf(GltfError),
 }
 
 impl Display for ModelLoadError {
@@ -452,6 +495,7 @@ impl Display for ModelLoadError {
                 write!(f, "Model format is not supported: {v}")
             }
             ModelLoadError::Fbx(v) => v.fmt(f),
+            ModelLoadError::Gltf(v) => v.fmt(f),
         }
     }
 }
@@ -462,6 +506,12 @@ impl From<FbxError> for ModelLoadError {
     }
 }
 
+impl From<GltfError> for ModelLoadError {
+    fn from(gltf: GltfError) -> Self {
+        ModelLoadError::Gltf(gltf)
+    }
+}
+
 impl From<VisitError> for ModelLoadError {
     fn from(e: VisitError) -> Self {
         ModelLoadError::Visit(e)
@@ -500,6 +550,23 @@ impl Model {
                 // any persistent unique ids, and we have to use names.
                 (scene, NodeMapping::UseNames)
             }
+            "gltf" | "glb" => {
+                let mut scene = Scene::new();
+                if let Some(filename) = path.as_ref().file_name() {
+                    let root = scene.graph.get_root();
+                    scene.graph[root].set_name(&filename.to_string_lossy());
+                }
+                gltf::load_to_scene(
+                    &mut scene,
+                    resource_manager,
+                    path.as_ref(),
+                    &model_import_options,
+                )
+                .await?;
+                // glTF node indices are not stable across re-exports, so names are used here
+                // the same way as for FBX.
+                (scene, NodeMapping::UseNames)
+            }
             // Scene can be used directly as model resource. Such scenes can be created in
             // Fyroxed.
             "rgs" => (
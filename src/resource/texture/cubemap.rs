@@ -0,0 +1,286 @@
+//! Conversion of equirectangular (latitude-longitude) panoramas into cube maps.
+//!
+//! Most HDR environment captures are distributed as equirectangular panoramas, while the renderer
+//! uses cube maps for sky boxes and reflections. The functions in this module do the conversion
+//! on CPU side, see [`equirectangular_to_cube_faces`] for details.
+
+use crate::{
+    core::algebra::Vector3,
+    resource::texture::{TextureError, TexturePixelKind},
+};
+use std::f32::consts::PI;
+
+/// Converts an equirectangular panorama into six square cube map faces of `face_size` x `face_size`
+/// pixels each. Faces are stored one after another in the same order the renderer expects for
+/// [`super::TextureKind::Cube`] textures: +X, -X, +Y, -Y, +Z, -Z. The output has the same pixel kind
+/// as the input, only `RGB8`, `RGBA8`, `RGB32F` and `RGBA32F` pixel kinds are supported.
+///
+/// # Panorama layout
+///
+/// The center of the panorama is mapped to the +Z direction (the direction a camera looks by
+/// default), the top row of the panorama is mapped to +Y. Moving right in the panorama turns the
+/// view to the right (towards -X). Each face pixel takes a bilinearly filtered sample of the
+/// panorama, the panorama wraps horizontally and is clamped vertically.
+pub fn equirectangular_to_cube_faces(
+    pixel_kind: TexturePixelKind,
+    width: u32,
+    height: u32,
+    data: &[u8],
+    face_size: u32,
+) -> Result<Vec<u8>, TextureError> {
+    let pixel_size = match pixel_kind {
+        TexturePixelKind::RGB8
+        | TexturePixelKind::RGBA8
+        | TexturePixelKind::RGB32F
+        | TexturePixelKind::RGBA32F => pixel_kind.size_in_bytes().unwrap(),
+        _ => return Err(TextureError::UnsupportedFormat),
+    };
+
+    if width == 0
+        || height == 0
+        || face_size == 0
+        || data.len() < width as usize * height as usize * pixel_size
+    {
+        return Err(TextureError::UnsupportedFormat);
+    }
+
+    let panorama = Panorama {
+        pixel_kind,
+        pixel_size,
+        width: width as usize,
+        height: height as usize,
+        data,
+    };
+
+    let mut bytes = Vec::with_capacity(6 * face_size as usize * face_size as usize * pixel_size);
+    for face in 0..6 {
+        for y in 0..face_size {
+            for x in 0..face_size {
+                let s = (x as f32 + 0.5) / face_size as f32;
+                let t = (y as f32 + 0.5) / face_size as f32;
+                let (u, v) = direction_to_equirectangular(cube_face_direction(face, s, t));
+                write_pixel(pixel_kind, panorama.sample(u, v), &mut bytes);
+            }
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Returns a (non-normalized) direction that corresponds to the given texture coordinates of a
+/// cube map face. Face indices and orientation follow the OpenGL specification.
+fn cube_face_direction(face: usize, s: f32, t: f32) -> Vector3<f32> {
+    let a = 2.0 * s - 1.0;
+    let b = 2.0 * t - 1.0;
+    match face {
+        0 => Vector3::new(1.0, -b, -a),
+        1 => Vector3::new(-1.0, -b, a),
+        2 => Vector3::new(a, 1.0, b),
+        3 => Vector3::new(a, -1.0, -b),
+        4 => Vector3::new(a, -b, 1.0),
+        _ => Vector3::new(-a, -b, -1.0),
+    }
+}
+
+/// Maps a direction to the normalized coordinates of an equirectangular panorama.
+fn direction_to_equirectangular(direction: Vector3<f32>) -> (f32, f32) {
+    let direction = direction
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(Vector3::z);
+    let u = 0.5 + (-direction.x).atan2(direction.z) / (2.0 * PI);
+    let v = direction.y.clamp(-1.0, 1.0).acos() / PI;
+    (u, v)
+}
+
+struct Panorama<'a> {
+    pixel_kind: TexturePixelKind,
+    pixel_size: usize,
+    width: usize,
+    height: usize,
+    data: &'a [u8],
+}
+
+impl<'a> Panorama<'a> {
+    fn fetch(&self, x: usize, y: usize) -> [f32; 4] {
+        let offset = (y * self.width + x) * self.pixel_size;
+        read_pixel(
+            self.pixel_kind,
+            &self.data[offset..offset + self.pixel_size],
+        )
+    }
+
+    fn sample(&self, u: f32, v: f32) -> [f32; 4] {
+        let x = u * self.width as f32 - 0.5;
+        let y = v * self.height as f32 - 0.5;
+        let fx = x - x.floor();
+        let fy = y - y.floor();
+
+        let w = self.width as i64;
+        let x0 = (x.floor() as i64).rem_euclid(w) as usize;
+        let x1 = (x.floor() as i64 + 1).rem_euclid(w) as usize;
+        let max_y = self.height as i64 - 1;
+        let y0 = (y.floor() as i64).clamp(0, max_y) as usize;
+        let y1 = (y.floor() as i64 + 1).clamp(0, max_y) as usize;
+
+        let p00 = self.fetch(x0, y0);
+        let p10 = self.fetch(x1, y0);
+        let p01 = self.fetch(x0, y1);
+        let p11 = self.fetch(x1, y1);
+
+        let mut result = [0.0; 4];
+        for (i, channel) in result.iter_mut().enumerate() {
+            let top = p00[i] + (p10[i] - p00[i]) * fx;
+            let bottom = p01[i] + (p11[i] - p01[i]) * fx;
+            *channel = top + (bottom - top) * fy;
+        }
+        result
+    }
+}
+
+fn read_pixel(pixel_kind: TexturePixelKind, bytes: &[u8]) -> [f32; 4] {
+    let float = |i: usize| {
+        f32::from_ne_bytes([
+            bytes[i * 4],
+            bytes[i * 4 + 1],
+            bytes[i * 4 + 2],
+            bytes[i * 4 + 3],
+        ])
+    };
+    let byte = |i: usize| bytes[i] as f32 / 255.0;
+    match pixel_kind {
+        TexturePixelKind::RGB8 => [byte(0), byte(1), byte(2), 1.0],
+        TexturePixelKind::RGBA8 => [byte(0), byte(1), byte(2), byte(3)],
+        TexturePixelKind::RGB32F => [float(0), float(1), float(2), 1.0],
+        TexturePixelKind::RGBA32F => [float(0), float(1), float(2), float(3)],
+        _ => unreachable!(),
+    }
+}
+
+fn write_pixel(pixel_kind: TexturePixelKind, pixel: [f32; 4], bytes: &mut Vec<u8>) {
+    let channels = match pixel_kind {
+        TexturePixelKind::RGB8 | TexturePixelKind::RGB32F => &pixel[..3],
+        _ => &pixel[..],
+    };
+    for &channel in channels {
+        match pixel_kind {
+            TexturePixelKind::RGB8 | TexturePixelKind::RGBA8 => {
+                bytes.push((channel * 255.0).round().clamp(0.0, 255.0) as u8)
+            }
+            _ => bytes.extend_from_slice(&channel.to_ne_bytes()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn face_directions(face: usize) -> [Vector3<f32>; 3] {
+        [
+            cube_face_direction(face, 0.5, 0.5),
+            cube_face_direction(face, 1.0, 0.5),
+            cube_face_direction(face, 0.5, 1.0),
+        ]
+    }
+
+    #[test]
+    fn test_cube_face_directions() {
+        // Center of each face must point along its axis.
+        let axes = [
+            Vector3::x(),
+            -Vector3::x(),
+            Vector3::y(),
+            -Vector3::y(),
+            Vector3::z(),
+            -Vector3::z(),
+        ];
+        for (face, axis) in axes.iter().enumerate() {
+            let [center, right, bottom] = face_directions(face);
+            assert_eq!(center, *axis);
+            // Face basis must be orthogonal to the face axis.
+            assert_eq!((right - center).dot(axis), 0.0);
+            assert_eq!((bottom - center).dot(axis), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_direction_to_equirectangular() {
+        let (u, v) = direction_to_equirectangular(Vector3::z());
+        assert!((u - 0.5).abs() < 1e-6 && (v - 0.5).abs() < 1e-6);
+
+        let (u, _) = direction_to_equirectangular(-Vector3::x());
+        assert!((u - 0.75).abs() < 1e-6);
+
+        let (u, _) = direction_to_equirectangular(Vector3::x());
+        assert!((u - 0.25).abs() < 1e-6);
+
+        let (_, v) = direction_to_equirectangular(Vector3::y());
+        assert!(v.abs() < 1e-6);
+
+        let (_, v) = direction_to_equirectangular(-Vector3::y());
+        assert!((v - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_uniform_panorama() {
+        let pixel = [0.25f32, 2.0, 10.0];
+        let data = (0..8 * 4)
+            .flat_map(|_| pixel.iter().flat_map(|c| c.to_ne_bytes()))
+            .collect::<Vec<_>>();
+
+        let faces =
+            equirectangular_to_cube_faces(TexturePixelKind::RGB32F, 8, 4, &data, 4).unwrap();
+        assert_eq!(faces.len(), 6 * 4 * 4 * 12);
+
+        for texel in faces.chunks(12) {
+            assert_eq!(read_pixel(TexturePixelKind::RGB32F, texel)[..3], pixel);
+        }
+    }
+
+    #[test]
+    fn test_panorama_orientation() {
+        // Upper half of the panorama is white, lower half is black.
+        let (width, height) = (16, 8);
+        let data = (0..height)
+            .flat_map(|y| {
+                let value = if y < height / 2 { 255 } else { 0 };
+                (0..width).map(move |_| [value, value, value, 255])
+            })
+            .flatten()
+            .collect::<Vec<u8>>();
+
+        let face_size = 4;
+        let faces = equirectangular_to_cube_faces(
+            TexturePixelKind::RGBA8,
+            width as u32,
+            height as u32,
+            &data,
+            face_size,
+        )
+        .unwrap();
+
+        let face_bytes = face_size as usize * face_size as usize * 4;
+        let face = |index: usize| &faces[index * face_bytes..(index + 1) * face_bytes];
+
+        // +Y face must be white, -Y face must be black.
+        assert!(face(2).chunks(4).all(|p| p == [255, 255, 255, 255]));
+        assert!(face(3).chunks(4).all(|p| p == [0, 0, 0, 255]));
+
+        // Side faces must have white top row and black bottom row.
+        for index in [0, 1, 4, 5] {
+            let face = face(index);
+            let row = face_size as usize * 4;
+            assert!(face[..row].chunks(4).all(|p| p == [255, 255, 255, 255]));
+            assert!(face[face.len() - row..]
+                .chunks(4)
+                .all(|p| p == [0, 0, 0, 255]));
+        }
+    }
+
+    #[test]
+    fn test_unsupported_input() {
+        assert!(equirectangular_to_cube_faces(TexturePixelKind::R8, 2, 1, &[0, 0], 1).is_err());
+        assert!(equirectangular_to_cube_faces(TexturePixelKind::RGB8, 2, 1, &[0; 3], 1).is_err());
+        assert!(equirectangular_to_cube_faces(TexturePixelKind::RGB8, 2, 1, &[0; 6], 0).is_err());
+    }
+}
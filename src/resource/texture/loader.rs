@@ -8,7 +8,7 @@ use crate::{
         untyped::UntypedResource,
     },
     core::{instant, log::Log},
-    resource::texture::{Texture, TextureImportOptions},
+    resource::texture::{CompressionOptions, Texture, TextureImportOptions},
 };
 use std::any::Any;
 
@@ -20,7 +20,9 @@ pub struct TextureLoader {
 
 impl ResourceLoader for TextureLoader {
     fn extensions(&self) -> &[&str] {
-        &["jpg", "jpeg", "tga", "gif", "bmp", "png", "tiff", "dds"]
+        &[
+            "jpg", "jpeg", "tga", "gif", "bmp", "png", "tiff", "dds", "hdr", "exr",
+        ]
     }
 
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
@@ -50,17 +52,33 @@ impl ResourceLoader for TextureLoader {
                 .await
                 .unwrap_or(default_import_options);
 
-            let gen_mip_maps = import_options.minification_filter.is_using_mip_mapping();
+            // Panoramas are converted into cube maps using the first mip level only, there is no
+            // need to generate mips or compress the source image.
+            let cube_map_face_size = import_options.cube_map_face_size;
+            let (compression, gen_mip_maps) = if cube_map_face_size.is_some() {
+                (CompressionOptions::NoCompression, false)
+            } else {
+                (
+                    import_options.compression,
+                    import_options.minification_filter.is_using_mip_mapping(),
+                )
+            };
 
             let time = instant::Instant::now();
             match Texture::load_from_file(
                 &path,
-                import_options.compression,
+                compression,
                 gen_mip_maps,
                 import_options.mip_filter,
             )
             .await
-            {
+            .and_then(|texture| match cube_map_face_size {
+                Some(face_size) => texture.equirectangular_to_cube(face_size).map(|mut cube| {
+                    cube.path = texture.path;
+                    cube
+                }),
+                None => Ok(texture),
+            }) {
                 Ok(mut raw_texture) => {
                     Log::info(format!(
                         "Texture {:?} is loaded in {:?}!",
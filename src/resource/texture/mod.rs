@@ -7,7 +7,13 @@
 //! ## Supported formats
 //!
 //! To load images and decode them, Fyrox uses image and ddsfile crates. Here is the list of
-//! supported formats: png, tga, bmp, dds, jpg, gif, tiff, dds.
+//! supported formats: png, tga, bmp, dds, jpg, gif, tiff, hdr, exr. Radiance HDR and OpenEXR images are
+//! loaded into floating-point pixel formats (`RGB32F` and `RGBA32F`).
+//!
+//! ## Environment maps
+//!
+//! Equirectangular panoramas (for example, HDR environment captures) could be converted into cube maps,
+//! that are used by sky boxes and for reflections. See [`Texture::equirectangular_to_cube`] for more info.
 //!
 //! ## Compressed textures
 //!
@@ -48,6 +54,7 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+pub mod cubemap;
 pub mod loader;
 
 /// Texture kind.
@@ -336,6 +343,15 @@ impl MipFilter {
             MipFilter::Lanczos => fr::FilterType::Lanczos3,
         }
     }
+
+    fn into_image_filter_type(self) -> image::imageops::FilterType {
+        match self {
+            MipFilter::Nearest => image::imageops::FilterType::Nearest,
+            MipFilter::Bilinear | MipFilter::Hamming => image::imageops::FilterType::Triangle,
+            MipFilter::CatmullRom => image::imageops::FilterType::CatmullRom,
+            MipFilter::Lanczos => image::imageops::FilterType::Lanczos3,
+        }
+    }
 }
 
 /// Allows you to define a set of parameters for a texture resource.
@@ -355,6 +371,9 @@ impl MipFilter {
 ///     compression: NoCompression,    
 /// )
 /// ```
+///
+/// Equirectangular panoramas (usually `.hdr` or `.exr` environment captures) could be converted into cube maps
+/// on import by adding `cube_map_face_size: Some(512)` to the settings file.
 #[derive(Clone, Deserialize, Serialize, Debug, Reflect)]
 pub struct TextureImportOptions {
     #[serde(default)]
@@ -371,6 +390,8 @@ pub struct TextureImportOptions {
     pub(crate) compression: CompressionOptions,
     #[serde(default)]
     pub(crate) mip_filter: MipFilter,
+    #[serde(default)]
+    pub(crate) cube_map_face_size: Option<u32>,
 }
 
 impl Default for TextureImportOptions {
@@ -383,6 +404,7 @@ impl Default for TextureImportOptions {
             anisotropy: 16.0,
             compression: CompressionOptions::default(),
             mip_filter: Default::default(),
+            cube_map_face_size: None,
         }
     }
 }
@@ -471,6 +493,19 @@ impl TextureImportOptions {
     pub fn set_compression(&mut self, compression: CompressionOptions) {
         self.compression = compression;
     }
+
+    /// Tells the loader to treat imported image as equirectangular panorama and convert it into a
+    /// cube map with the given size of each face. See [`Texture::equirectangular_to_cube`] for more
+    /// info. Compression is ignored for converted textures.
+    pub fn with_cube_map_face_size(mut self, face_size: Option<u32>) -> Self {
+        self.cube_map_face_size = face_size;
+        self
+    }
+
+    /// Sets desired size of cube map faces for equirectangular panoramas.
+    pub fn set_cube_map_face_size(&mut self, face_size: Option<u32>) {
+        self.cube_map_face_size = face_size;
+    }
 }
 
 /// Type alias for texture resources.
@@ -1156,23 +1191,23 @@ fn mip_byte_offset(kind: TextureKind, pixel_kind: TexturePixelKind, mut mip: usi
     offset
 }
 
-fn convert_pixel_type_enum(pixel_kind: TexturePixelKind) -> fr::PixelType {
+fn convert_pixel_type_enum(pixel_kind: TexturePixelKind) -> Option<fr::PixelType> {
     match pixel_kind {
-        TexturePixelKind::R8 | TexturePixelKind::Luminance8 => fr::PixelType::U8,
-        TexturePixelKind::RGB8 | TexturePixelKind::BGR8 => fr::PixelType::U8x3,
-        TexturePixelKind::RGBA8 | TexturePixelKind::BGRA8 => fr::PixelType::U8x4,
-        TexturePixelKind::RG8 | TexturePixelKind::LuminanceAlpha8 => fr::PixelType::U8x2,
-        TexturePixelKind::R16 | TexturePixelKind::Luminance16 => fr::PixelType::U16,
-        TexturePixelKind::RG16 | TexturePixelKind::LuminanceAlpha16 => fr::PixelType::U16x2,
-        TexturePixelKind::RGB16 => fr::PixelType::U16x3,
-        TexturePixelKind::RGBA16 => fr::PixelType::U16x4,
-        TexturePixelKind::R32F => fr::PixelType::F32,
-        _ => unreachable!(),
+        TexturePixelKind::R8 | TexturePixelKind::Luminance8 => Some(fr::PixelType::U8),
+        TexturePixelKind::RGB8 | TexturePixelKind::BGR8 => Some(fr::PixelType::U8x3),
+        TexturePixelKind::RGBA8 | TexturePixelKind::BGRA8 => Some(fr::PixelType::U8x4),
+        TexturePixelKind::RG8 | TexturePixelKind::LuminanceAlpha8 => Some(fr::PixelType::U8x2),
+        TexturePixelKind::R16 | TexturePixelKind::Luminance16 => Some(fr::PixelType::U16),
+        TexturePixelKind::RG16 | TexturePixelKind::LuminanceAlpha16 => Some(fr::PixelType::U16x2),
+        TexturePixelKind::RGB16 => Some(fr::PixelType::U16x3),
+        TexturePixelKind::RGBA16 => Some(fr::PixelType::U16x4),
+        TexturePixelKind::R32F => Some(fr::PixelType::F32),
+        _ => None,
     }
 }
 
 impl Texture {
-    /// Tries to load a texture from given data in one of the following formats: PNG, BMP, TGA, JPG, DDS, GIF. Use
+    /// Tries to load a texture from given data in one of the following formats: PNG, BMP, TGA, JPG, DDS, GIF, HDR, EXR. Use
     /// this method if you want to load a texture from embedded data.
     ///
     /// # On-demand compression and mip-map generation
@@ -1291,107 +1326,138 @@ impl Texture {
                 // check if the file is really contains expected data.
                 .or_else(|_| image::load_from_memory_with_format(data, ImageFormat::Tga))?;
 
-            let width = dyn_img.width();
-            let height = dyn_img.height();
-
-            let mut pixel_kind = match dyn_img {
-                DynamicImage::ImageLuma8(_) => TexturePixelKind::Luminance8,
-                DynamicImage::ImageLumaA8(_) => TexturePixelKind::LuminanceAlpha8,
-                DynamicImage::ImageRgb8(_) => TexturePixelKind::RGB8,
-                DynamicImage::ImageRgba8(_) => TexturePixelKind::RGBA8,
-                DynamicImage::ImageLuma16(_) => TexturePixelKind::Luminance16,
-                DynamicImage::ImageLumaA16(_) => TexturePixelKind::LuminanceAlpha16,
-                DynamicImage::ImageRgb16(_) => TexturePixelKind::RGB16,
-                DynamicImage::ImageRgba16(_) => TexturePixelKind::RGBA16,
-                DynamicImage::ImageRgb32F(_) => TexturePixelKind::RGB32F,
-                DynamicImage::ImageRgba32F(_) => TexturePixelKind::RGBA32F,
-                _ => return Err(TextureError::UnsupportedFormat),
-            };
+            Self::load_from_image(dyn_img, compression, gen_mip_maps, mip_filter)
+        }
+    }
 
-            let mut mip_count = 0;
-            let mut bytes = Vec::with_capacity(
-                width as usize * height as usize * pixel_kind.size_in_bytes().unwrap_or(4),
-            );
-
-            if gen_mip_maps {
-                let pixel_type = convert_pixel_type_enum(pixel_kind);
-                let mut level_width = width;
-                let mut level_height = height;
-                let mut current_level = fr::Image::from_vec_u8(
-                    NonZeroU32::new(level_width).unwrap(),
-                    NonZeroU32::new(level_height).unwrap(),
-                    dyn_img.as_bytes().to_vec(),
-                    pixel_type,
-                )
-                .map_err(|_| TextureError::UnsupportedFormat)?;
-
-                while level_width != 0 && level_height != 0 {
-                    if mip_count != 0 {
-                        let mut dst_img = fr::Image::new(
-                            NonZeroU32::new(level_width).unwrap(),
-                            NonZeroU32::new(level_height).unwrap(),
-                            pixel_type,
-                        );
-
-                        let mut resizer = fr::Resizer::new(fr::ResizeAlg::Convolution(
-                            mip_filter.into_filter_type(),
-                        ));
-
-                        resizer
-                            .resize(&current_level.view(), &mut dst_img.view_mut())
-                            .expect("Pixel types must match!");
-
-                        current_level = dst_img;
-                    }
+    /// Creates a rectangle texture from already decoded image. The data can be compressed and mip-maps can be
+    /// generated the same way as in [`Self::load_from_memory`].
+    pub(crate) fn load_from_image(
+        dyn_img: DynamicImage,
+        compression: CompressionOptions,
+        gen_mip_maps: bool,
+        mip_filter: MipFilter,
+    ) -> Result<Self, TextureError> {
+        let width = dyn_img.width();
+        let height = dyn_img.height();
+
+        let mut pixel_kind = match dyn_img {
+            DynamicImage::ImageLuma8(_) => TexturePixelKind::Luminance8,
+            DynamicImage::ImageLumaA8(_) => TexturePixelKind::LuminanceAlpha8,
+            DynamicImage::ImageRgb8(_) => TexturePixelKind::RGB8,
+            DynamicImage::ImageRgba8(_) => TexturePixelKind::RGBA8,
+            DynamicImage::ImageLuma16(_) => TexturePixelKind::Luminance16,
+            DynamicImage::ImageLumaA16(_) => TexturePixelKind::LuminanceAlpha16,
+            DynamicImage::ImageRgb16(_) => TexturePixelKind::RGB16,
+            DynamicImage::ImageRgba16(_) => TexturePixelKind::RGBA16,
+            DynamicImage::ImageRgb32F(_) => TexturePixelKind::RGB32F,
+            DynamicImage::ImageRgba32F(_) => TexturePixelKind::RGBA32F,
+            _ => return Err(TextureError::UnsupportedFormat),
+        };
 
-                    mip_count += 1;
-
-                    if compression == CompressionOptions::NoCompression {
-                        bytes.extend_from_slice(current_level.buffer())
-                    } else if let Some((compressed_data, new_pixel_kind)) = try_compress(
-                        pixel_kind,
-                        current_level.buffer(),
-                        level_width as usize,
-                        level_height as usize,
-                        compression,
-                    ) {
-                        pixel_kind = new_pixel_kind;
-                        bytes.extend_from_slice(&compressed_data);
-                    } else {
-                        bytes.extend_from_slice(current_level.buffer())
-                    }
+        let mut mip_count = 0;
+        let mut bytes = Vec::with_capacity(
+            width as usize * height as usize * pixel_kind.size_in_bytes().unwrap_or(4),
+        );
+
+        if gen_mip_maps && convert_pixel_type_enum(pixel_kind).is_none() {
+            // fast_image_resize does not support multi-channel floating-point images (RGB32F and
+            // RGBA32F, typically HDR and EXR images), so use slower resizer from the image crate.
+            // Such images are never compressed, so there is no need to call `try_compress` here.
+            let filter = mip_filter.into_image_filter_type();
+            let mut level_width = width;
+            let mut level_height = height;
+            while level_width != 0 && level_height != 0 {
+                if mip_count == 0 {
+                    bytes.extend_from_slice(dyn_img.as_bytes());
+                } else {
+                    let level = dyn_img.resize_exact(level_width, level_height, filter);
+                    bytes.extend_from_slice(level.as_bytes());
+                }
+
+                mip_count += 1;
 
-                    level_width = level_width.checked_shr(1).unwrap_or_default();
-                    level_height = level_height.checked_shr(1).unwrap_or_default();
+                level_width = level_width.checked_shr(1).unwrap_or_default();
+                level_height = level_height.checked_shr(1).unwrap_or_default();
+            }
+        } else if let Some(pixel_type) =
+            convert_pixel_type_enum(pixel_kind).filter(|_| gen_mip_maps)
+        {
+            let mut level_width = width;
+            let mut level_height = height;
+            let mut current_level = fr::Image::from_vec_u8(
+                NonZeroU32::new(level_width).unwrap(),
+                NonZeroU32::new(level_height).unwrap(),
+                dyn_img.as_bytes().to_vec(),
+                pixel_type,
+            )
+            .map_err(|_| TextureError::UnsupportedFormat)?;
+
+            while level_width != 0 && level_height != 0 {
+                if mip_count != 0 {
+                    let mut dst_img = fr::Image::new(
+                        NonZeroU32::new(level_width).unwrap(),
+                        NonZeroU32::new(level_height).unwrap(),
+                        pixel_type,
+                    );
+
+                    let mut resizer =
+                        fr::Resizer::new(fr::ResizeAlg::Convolution(mip_filter.into_filter_type()));
+
+                    resizer
+                        .resize(&current_level.view(), &mut dst_img.view_mut())
+                        .expect("Pixel types must match!");
+
+                    current_level = dst_img;
                 }
-            } else {
-                mip_count = 1;
+
+                mip_count += 1;
 
                 if compression == CompressionOptions::NoCompression {
-                    bytes.extend_from_slice(dyn_img.as_bytes());
+                    bytes.extend_from_slice(current_level.buffer())
                 } else if let Some((compressed_data, new_pixel_kind)) = try_compress(
                     pixel_kind,
-                    dyn_img.as_bytes(),
-                    width as usize,
-                    height as usize,
+                    current_level.buffer(),
+                    level_width as usize,
+                    level_height as usize,
                     compression,
                 ) {
                     pixel_kind = new_pixel_kind;
                     bytes.extend_from_slice(&compressed_data);
                 } else {
-                    bytes.extend_from_slice(dyn_img.as_bytes())
+                    bytes.extend_from_slice(current_level.buffer())
                 }
+
+                level_width = level_width.checked_shr(1).unwrap_or_default();
+                level_height = level_height.checked_shr(1).unwrap_or_default();
             }
+        } else {
+            mip_count = 1;
 
-            Ok(Self {
+            if compression == CompressionOptions::NoCompression {
+                bytes.extend_from_slice(dyn_img.as_bytes());
+            } else if let Some((compressed_data, new_pixel_kind)) = try_compress(
                 pixel_kind,
-                kind: TextureKind::Rectangle { width, height },
-                data_hash: data_hash(&bytes),
-                bytes: bytes.into(),
-                mip_count,
-                ..Default::default()
-            })
+                dyn_img.as_bytes(),
+                width as usize,
+                height as usize,
+                compression,
+            ) {
+                pixel_kind = new_pixel_kind;
+                bytes.extend_from_slice(&compressed_data);
+            } else {
+                bytes.extend_from_slice(dyn_img.as_bytes())
+            }
         }
+
+        Ok(Self {
+            pixel_kind,
+            kind: TextureKind::Rectangle { width, height },
+            data_hash: data_hash(&bytes),
+            bytes: bytes.into(),
+            mip_count,
+            ..Default::default()
+        })
     }
 
     /// Tries to load a texture from a file.
@@ -1438,6 +1504,46 @@ impl Texture {
         }
     }
 
+    /// Converts the texture, which is treated as an equirectangular (latitude-longitude) panorama, into a cube
+    /// map texture with the given size of each face. It is the usual way of using HDR environment captures for
+    /// sky boxes and reflections. Only the first mip level of the source texture is used, the resulting texture
+    /// has a single mip level. See [`cubemap::equirectangular_to_cube_faces`] for panorama orientation.
+    ///
+    /// # Limitations
+    ///
+    /// The texture must be a rectangle texture with one of the following pixel kinds: `RGB8`, `RGBA8`, `RGB32F`,
+    /// `RGBA32F`. Compressed textures are not supported.
+    pub fn equirectangular_to_cube(&self, face_size: u32) -> Result<Self, TextureError> {
+        let (width, height) = match self.kind {
+            TextureKind::Rectangle { width, height } => (width, height),
+            _ => return Err(TextureError::UnsupportedFormat),
+        };
+
+        let bytes = cubemap::equirectangular_to_cube_faces(
+            self.pixel_kind,
+            width,
+            height,
+            self.mip_level_data(0),
+            face_size,
+        )?;
+
+        let mut cube = Self::from_bytes(
+            TextureKind::Cube {
+                width: face_size,
+                height: face_size,
+            },
+            self.pixel_kind,
+            bytes,
+            self.serialize_content,
+        )
+        .ok_or(TextureError::UnsupportedFormat)?;
+        cube.minification_filter = TextureMinificationFilter::Linear;
+        cube.magnification_filter = self.magnification_filter;
+        cube.s_wrap_mode = TextureWrapMode::ClampToEdge;
+        cube.t_wrap_mode = TextureWrapMode::ClampToEdge;
+        Ok(cube)
+    }
+
     /// Sets new minification filter. It is used when texture becomes smaller.
     pub fn set_minification_filter(&mut self, filter: TextureMinificationFilter) {
         self.minification_filter = filter;
@@ -0,0 +1,67 @@
+//! Tile set loader.
+
+use crate::{
+    asset::{
+        event::ResourceEventBroadcaster,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        manager::ResourceManager,
+        untyped::UntypedResource,
+    },
+    core::log::Log,
+    resource::tileset::TileSet,
+};
+use std::any::Any;
+
+/// Default implementation for tile set loading.
+pub struct TileSetLoader {
+    /// Resource manager to restore the atlas textures of tile sets.
+    pub resource_manager: ResourceManager,
+}
+
+impl ResourceLoader for TileSetLoader {
+    fn extensions(&self) -> &[&str] {
+        &["tileset"]
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn load(
+        &self,
+        tile_set: UntypedResource,
+        event_broadcaster: ResourceEventBroadcaster,
+        reload: bool,
+    ) -> BoxedLoaderFuture {
+        let resource_manager = self.resource_manager.clone();
+        Box::pin(async move {
+            let path = tile_set.0.lock().path().to_path_buf();
+
+            match TileSet::from_file(&path, resource_manager).await {
+                Ok(tile_set_state) => {
+                    Log::info(format!("Tile set {:?} is loaded!", path));
+
+                    tile_set.commit_ok(tile_set_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(tile_set, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load tile set from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    tile_set.commit_error(path, error);
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,328 @@
+//! Tile set resource holds a set of tiles (their appearance, collision shapes and custom properties)
+//! that could be used by [`crate::scene::dim2::tilemap::TileMap`] nodes. See [`TileSet`] docs for
+//! more info.
+
+use crate::{
+    asset::{
+        manager::ResourceManager, options::ImportOptions, Resource, ResourceData,
+        TILE_SET_RESOURCE_UUID,
+    },
+    core::{
+        algebra::Vector2, color::Color, io::FileLoadError, math::Rect, reflect::prelude::*,
+        uuid::Uuid, visitor::prelude::*, TypeUuidProvider,
+    },
+    resource::texture::TextureResource,
+    scene::base::{Property, PropertyValue},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    any::Any,
+    borrow::Cow,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+pub mod loader;
+
+/// An error that may occur during tile set resource loading.
+#[derive(Debug)]
+pub enum TileSetError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for TileSetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TileSetError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            TileSetError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for TileSetError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for TileSetError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// Collision shape of a tile. All coordinates are normalized and defined in the local space of a
+/// tile, which means that `[0; 0]` corresponds to the bottom-left corner of the tile and `[1; 1]`
+/// corresponds to the top-right corner. Tile maps scale the shapes by the size of their tiles.
+#[derive(
+    Clone, Debug, Default, Visit, Reflect, PartialEq, AsRefStr, EnumString, EnumVariantNames,
+)]
+pub enum TileCollider {
+    /// The tile does not have a collision shape.
+    #[default]
+    None,
+    /// The tile is fully solid. Adjacent solid tiles are merged together into larger rectangles when
+    /// a tile map generates its colliders, which significantly reduces the amount of shapes.
+    Full,
+    /// An axis-aligned rectangle inside the tile.
+    Rectangle(Rect<f32>),
+    /// A convex polygon, defined by its vertices. The polygon is replaced with its convex hull if
+    /// the vertices define a concave polygon.
+    Polygon(Vec<Vector2<f32>>),
+}
+
+/// Definition of a single tile in a tile set.
+#[derive(Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct TileDefinition {
+    /// Name of the tile. It is used only to simplify navigation in tile sets, it has no special
+    /// meaning for the engine.
+    pub name: String,
+    /// A rectangle that defines the region of the atlas texture of the tile set, that will be used
+    /// for rendering. The coordinates are normalized, which means `[0; 0]` corresponds to top-left
+    /// corner of the texture and `[1; 1]` corresponds to right-bottom corner.
+    pub uv_rect: Rect<f32>,
+    /// A color of the tile. It is multiplied with the atlas texture color.
+    pub color: Color,
+    /// Collision shape of the tile.
+    pub collider: TileCollider,
+    /// A set of custom properties of the tile. It could be used to store some game-specific data,
+    /// for example a damage that the tile deals to a player.
+    pub properties: Vec<Property>,
+}
+
+impl Default for TileDefinition {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            uv_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+            color: Color::WHITE,
+            collider: Default::default(),
+            properties: Default::default(),
+        }
+    }
+}
+
+impl TileDefinition {
+    /// Tries to find a custom property with the given name.
+    pub fn property(&self, name: &str) -> Option<&PropertyValue> {
+        self.properties
+            .iter()
+            .find(|property| property.name == name)
+            .map(|property| &property.value)
+    }
+}
+
+/// Tile set is a resource that holds a set of tiles, that share the same atlas texture. Each tile
+/// is defined by a region of the atlas texture, a color, a collision shape and a set of custom
+/// properties (see [`TileDefinition`] for more info). Tiles are referenced by their indices in
+/// the tile set, so keep in mind that removing a tile will change indices of the tiles after it.
+///
+/// # Example
+///
+/// The following example creates a tile set from an atlas with 8x8 tiles, all of them are solid.
+///
+/// ```rust
+/// use fyrox::resource::{
+///     texture::TextureResource,
+///     tileset::{TileCollider, TileSet, TileSetResource},
+/// };
+///
+/// fn create_tile_set(atlas: TextureResource) -> TileSetResource {
+///     let mut tile_set = TileSet::from_atlas(Some(atlas), 8, 8);
+///     for tile in tile_set.tiles_mut() {
+///         tile.collider = TileCollider::Full;
+///     }
+///     TileSetResource::new_ok(tile_set)
+/// }
+/// ```
+#[derive(Debug, Visit, Default, Reflect)]
+pub struct TileSet {
+    pub(crate) path: PathBuf,
+    /// An atlas texture, that is shared across all tiles of the tile set.
+    pub texture: Option<TextureResource>,
+    tiles: Vec<TileDefinition>,
+}
+
+impl ResourceData for TileSet {
+    fn path(&self) -> Cow<Path> {
+        Cow::Borrowed(&self.path)
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+}
+
+impl TypeUuidProvider for TileSet {
+    fn type_uuid() -> Uuid {
+        TILE_SET_RESOURCE_UUID
+    }
+}
+
+impl TileSet {
+    /// Creates new empty tile set, that uses the given atlas texture.
+    pub fn new(texture: Option<TextureResource>) -> Self {
+        Self {
+            path: Default::default(),
+            texture,
+            tiles: Default::default(),
+        }
+    }
+
+    /// Creates new tile set from an atlas texture, that is split into `columns` x `rows` grid of
+    /// equally sized tiles. Tiles are enumerated row by row, starting from the top-left corner of
+    /// the atlas. Created tiles do not have collision shapes.
+    pub fn from_atlas(texture: Option<TextureResource>, columns: u32, rows: u32) -> Self {
+        let columns = columns.max(1);
+        let rows = rows.max(1);
+        let w = 1.0 / columns as f32;
+        let h = 1.0 / rows as f32;
+
+        let mut tile_set = Self::new(texture);
+        for row in 0..rows {
+            for column in 0..columns {
+                tile_set.add_tile(TileDefinition {
+                    name: format!("Tile{}", row * columns + column),
+                    uv_rect: Rect::new(column as f32 * w, row as f32 * h, w, h),
+                    ..Default::default()
+                });
+            }
+        }
+        tile_set
+    }
+
+    /// Load a tile set resource from the specific file path. Resource manager is used to restore
+    /// the atlas texture of the tile set.
+    pub async fn from_file(
+        path: &Path,
+        resource_manager: ResourceManager,
+    ) -> Result<Self, TileSetError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        visitor.blackboard.register(Arc::new(resource_manager));
+        let mut tile_set = TileSet::default();
+        tile_set.visit("TileSet", &mut visitor)?;
+        tile_set.path = path.to_path_buf();
+        Ok(tile_set)
+    }
+
+    /// Saves the tile set to the given file.
+    pub fn save(&mut self, path: &Path) -> VisitResult {
+        self.path = path.to_path_buf();
+        let mut visitor = Visitor::new();
+        self.visit("TileSet", &mut visitor)?;
+        visitor.save_binary(path)
+    }
+
+    /// Adds new tile to the tile set and returns its index.
+    pub fn add_tile(&mut self, tile: TileDefinition) -> u32 {
+        let index = self.tiles.len() as u32;
+        self.tiles.push(tile);
+        index
+    }
+
+    /// Removes a tile at the given index. Keep in mind, that indices of the tiles after the removed
+    /// one will be shifted by one.
+    pub fn remove_tile(&mut self, index: u32) -> Option<TileDefinition> {
+        if (index as usize) < self.tiles.len() {
+            Some(self.tiles.remove(index as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to a tile at the given index.
+    pub fn tile(&self, index: u32) -> Option<&TileDefinition> {
+        self.tiles.get(index as usize)
+    }
+
+    /// Returns a reference to a tile at the given index.
+    pub fn tile_mut(&mut self, index: u32) -> Option<&mut TileDefinition> {
+        self.tiles.get_mut(index as usize)
+    }
+
+    /// Returns a slice with all the tiles of the tile set.
+    pub fn tiles(&self) -> &[TileDefinition] {
+        &self.tiles
+    }
+
+    /// Returns a mutable slice with all the tiles of the tile set.
+    pub fn tiles_mut(&mut self) -> &mut [TileDefinition] {
+        &mut self.tiles
+    }
+}
+
+/// Type alias for tile set resources.
+pub type TileSetResource = Resource<TileSet>;
+
+/// Import options for tile set resource.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct TileSetImportOptions {}
+
+impl ImportOptions for TileSetImportOptions {}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::math::Rect,
+        resource::tileset::{TileCollider, TileSet},
+        scene::base::{Property, PropertyValue},
+    };
+
+    #[test]
+    fn test_tile_set_from_atlas() {
+        let mut tile_set = TileSet::from_atlas(None, 4, 2);
+
+        assert_eq!(tile_set.tiles().len(), 8);
+        assert_eq!(
+            tile_set.tile(0).unwrap().uv_rect,
+            Rect::new(0.0, 0.0, 0.25, 0.5)
+        );
+        assert_eq!(
+            tile_set.tile(5).unwrap().uv_rect,
+            Rect::new(0.25, 0.5, 0.25, 0.5)
+        );
+        assert!(tile_set.tile(8).is_none());
+
+        let tile = tile_set.tile_mut(1).unwrap();
+        tile.collider = TileCollider::Full;
+        tile.properties.push(Property {
+            name: "Damage".to_string(),
+            value: PropertyValue::F32(10.0),
+        });
+
+        assert_eq!(
+            tile_set.tile(1).unwrap().property("Damage"),
+            Some(&PropertyValue::F32(10.0))
+        );
+        assert_eq!(tile_set.tile(1).unwrap().property("Speed"), None);
+
+        assert!(tile_set.remove_tile(0).is_some());
+        assert_eq!(tile_set.tile(0).unwrap().collider, TileCollider::Full);
+        assert!(tile_set.remove_tile(100).is_none());
+    }
+}
@@ -0,0 +1,68 @@
+//! UI document loader.
+
+use crate::{
+    asset::{
+        event::ResourceEventBroadcaster,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        untyped::UntypedResource,
+    },
+    core::log::Log,
+    engine::SerializationContext,
+    resource::ui::UiDocumentResourceState,
+};
+use std::{any::Any, sync::Arc};
+
+/// Default implementation for UI document loading.
+pub struct UiDocumentLoader {
+    /// Serialization context, that is used to create widgets of documents.
+    pub serialization_context: Arc<SerializationContext>,
+}
+
+impl ResourceLoader for UiDocumentLoader {
+    fn extensions(&self) -> &[&str] {
+        &["ui"]
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn load(
+        &self,
+        document: UntypedResource,
+        event_broadcaster: ResourceEventBroadcaster,
+        reload: bool,
+    ) -> BoxedLoaderFuture {
+        let widget_constructors = self.serialization_context.widget_constructors.clone();
+
+        Box::pin(async move {
+            let path = document.0.lock().path().to_path_buf();
+
+            match UiDocumentResourceState::from_file(&path, widget_constructors).await {
+                Ok(document_state) => {
+                    Log::info(format!("UI document {:?} is loaded!", path));
+
+                    document.commit_ok(document_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(document, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load UI document from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    document.commit_error(path, error);
+                }
+            }
+        })
+    }
+}
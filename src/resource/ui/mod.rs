@@ -0,0 +1,143 @@
+//! UI document resource holds a serialized [`UiDocument`], that could be instantiated in a user
+//! interface any number of times. See [`UiDocument`] docs for more info.
+
+use crate::{
+    asset::{options::ImportOptions, Resource, ResourceData, UI_DOCUMENT_RESOURCE_UUID},
+    core::{
+        io::{self, FileLoadError},
+        reflect::prelude::*,
+        uuid::Uuid,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    gui::{
+        constructor::WidgetConstructorContainer,
+        document::{UiDocument, UiDocumentInstance},
+        UserInterface,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    any::Any,
+    borrow::Cow,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+pub mod loader;
+
+/// An error that may occur during UI document resource loading.
+#[derive(Debug)]
+pub enum UiDocumentResourceError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities or unknown widget types.
+    Visit(VisitError),
+}
+
+impl Display for UiDocumentResourceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UiDocumentResourceError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            UiDocumentResourceError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities or unknown widget types. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for UiDocumentResourceError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for UiDocumentResourceError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// State of the [`UiDocumentResource`]. Widgets cannot be shared between threads, so the state
+/// holds serialized document and the actual widgets are created on instantiation.
+#[derive(Debug, Visit, Default, Reflect)]
+pub struct UiDocumentResourceState {
+    pub(crate) path: PathBuf,
+    #[reflect(hidden)]
+    data: Vec<u8>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    widget_constructors: Arc<WidgetConstructorContainer>,
+}
+
+impl ResourceData for UiDocumentResourceState {
+    fn path(&self) -> Cow<Path> {
+        Cow::Borrowed(&self.path)
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+}
+
+impl TypeUuidProvider for UiDocumentResourceState {
+    fn type_uuid() -> Uuid {
+        UI_DOCUMENT_RESOURCE_UUID
+    }
+}
+
+impl UiDocumentResourceState {
+    /// Load a UI document resource from the specific file path. Widget constructors are used to
+    /// create widgets of the document, the document is checked for validity during loading.
+    pub async fn from_file(
+        path: &Path,
+        widget_constructors: Arc<WidgetConstructorContainer>,
+    ) -> Result<Self, UiDocumentResourceError> {
+        let data = io::load_file(path).await?;
+        UiDocument::load_from_memory(data.clone(), widget_constructors.clone())?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            data,
+            widget_constructors,
+        })
+    }
+
+    /// Creates new document from the serialized data.
+    pub fn document(&self) -> Result<UiDocument, VisitError> {
+        UiDocument::load_from_memory(self.data.clone(), self.widget_constructors.clone())
+    }
+
+    /// Creates new instance of the document in the given user interface. See
+    /// [`UiDocument::instantiate`] for more info.
+    pub fn instantiate(&self, ui: &mut UserInterface) -> Result<UiDocumentInstance, VisitError> {
+        Ok(self.document()?.instantiate(ui))
+    }
+}
+
+/// Type alias for UI document resources.
+pub type UiDocumentResource = Resource<UiDocumentResourceState>;
+
+/// Import options for UI document resource.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct UiDocumentImportOptions {}
+
+impl ImportOptions for UiDocumentImportOptions {}
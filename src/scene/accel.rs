@@ -3,11 +3,13 @@
 use crate::{
     core::{
         algebra::Vector3,
-        math::aabb::AxisAlignedBoundingBox,
+        math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, ray::Ray},
         pool::{Handle, Pool},
     },
     scene::{graph::Graph, node::Node},
 };
+use fxhash::FxHashMap;
+use std::hash::Hash;
 
 #[allow(dead_code)]
 #[derive(Clone, Debug)]
@@ -181,3 +183,641 @@ fn build_recursive(
         nodes.spawn(OctreeNode::Branch { leaves, bounds })
     }
 }
+
+/// A node of [`DynamicBvh`]. Leaf nodes hold user data, branch nodes always have exactly two
+/// children.
+#[derive(Clone, Debug)]
+pub struct BvhNode<T> {
+    /// Enlarged (for leaves) or combined (for branches) bounding box of the node.
+    pub aabb: AxisAlignedBoundingBox,
+    /// Exact bounding box of a leaf, it is equal to `aabb` for branches.
+    pub tight_aabb: AxisAlignedBoundingBox,
+    /// A handle of the parent node.
+    pub parent: Handle<BvhNode<T>>,
+    /// Handles of the children of a branch, both are [`Handle::NONE`] for leaves.
+    pub children: [Handle<BvhNode<T>>; 2],
+    /// Height of the node in the tree, leaves have zero height.
+    pub height: i32,
+    /// User data of a leaf.
+    pub data: Option<T>,
+}
+
+impl<T> BvhNode<T> {
+    /// Returns `true` if the node is a leaf.
+    pub fn is_leaf(&self) -> bool {
+        self.children[0].is_none()
+    }
+}
+
+/// Returns `true` if the bounding box has finite, non-negative size. Bounding boxes of nodes without
+/// volume (pivots, cameras, etc.) are either inverted or infinite, such boxes can't be put in a
+/// [`DynamicBvh`].
+pub fn is_aabb_bounded(aabb: &AxisAlignedBoundingBox) -> bool {
+    let size = aabb.max - aabb.min;
+    size.iter().all(|v| v.is_finite() && *v >= 0.0)
+}
+
+fn merge(a: &AxisAlignedBoundingBox, b: &AxisAlignedBoundingBox) -> AxisAlignedBoundingBox {
+    AxisAlignedBoundingBox::from_min_max(a.min.inf(&b.min), a.max.sup(&b.max))
+}
+
+fn surface_area(aabb: &AxisAlignedBoundingBox) -> f32 {
+    let d = aabb.max - aabb.min;
+    2.0 * (d.x * d.y + d.y * d.z + d.z * d.x)
+}
+
+fn contains(outer: &AxisAlignedBoundingBox, inner: &AxisAlignedBoundingBox) -> bool {
+    outer.min.x <= inner.min.x
+        && outer.min.y <= inner.min.y
+        && outer.min.z <= inner.min.z
+        && outer.max.x >= inner.max.x
+        && outer.max.y >= inner.max.y
+        && outer.max.z >= inner.max.z
+}
+
+/// Dynamic bounding volume hierarchy is a binary tree of axis-aligned bounding boxes, that could be
+/// updated incrementally. Every entry is stored in a leaf with slightly enlarged ("fat") bounding box,
+/// which means that small movements of an entry do not require any changes in the tree. When an entry
+/// leaves its fat box, its leaf is re-inserted in the tree and the tree is re-balanced using tree
+/// rotations. Insertion uses surface area heuristic to keep the tree compact.
+///
+/// The tree is used by the scene graph to accelerate frustum culling and spatial queries, see
+/// [`Graph::spatial_index`].
+#[derive(Clone, Debug)]
+pub struct DynamicBvh<T>
+where
+    T: Copy + Eq + Hash + 'static,
+{
+    nodes: Pool<BvhNode<T>>,
+    root: Handle<BvhNode<T>>,
+    leaves: FxHashMap<T, Handle<BvhNode<T>>>,
+    margin: f32,
+}
+
+impl<T> Default for DynamicBvh<T>
+where
+    T: Copy + Eq + Hash + 'static,
+{
+    fn default() -> Self {
+        Self {
+            nodes: Default::default(),
+            root: Default::default(),
+            leaves: Default::default(),
+            margin: 0.1,
+        }
+    }
+}
+
+impl<T> DynamicBvh<T>
+where
+    T: Copy + Eq + Hash + 'static,
+{
+    /// Creates new empty tree.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a margin by which bounding boxes of leaves are enlarged. Larger margin means less updates
+    /// of the tree for moving entries, but less precise queries. Default value is `0.1`. The new margin
+    /// will be applied only to entries, that will be inserted or moved after the call.
+    pub fn set_margin(&mut self, margin: f32) {
+        self.margin = margin.max(0.0);
+    }
+
+    /// Returns current margin, by which bounding boxes of leaves are enlarged.
+    pub fn margin(&self) -> f32 {
+        self.margin
+    }
+
+    /// Returns total amount of entries in the tree.
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Returns `true` if the tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Returns `true` if the tree contains the given entry.
+    pub fn contains(&self, data: &T) -> bool {
+        self.leaves.contains_key(data)
+    }
+
+    /// Returns exact bounding box of the entry, if the entry exists.
+    pub fn entry_aabb(&self, data: &T) -> Option<AxisAlignedBoundingBox> {
+        self.leaves
+            .get(data)
+            .map(|leaf| self.nodes[*leaf].tight_aabb)
+    }
+
+    /// Returns height of the tree. Empty tree and a tree with one entry have zero height.
+    pub fn height(&self) -> i32 {
+        self.nodes
+            .try_borrow(self.root)
+            .map_or(0, |root| root.height)
+    }
+
+    /// Returns a handle of the root node of the tree.
+    pub fn root(&self) -> Handle<BvhNode<T>> {
+        self.root
+    }
+
+    /// Returns a reference to the pool of the nodes of the tree.
+    pub fn nodes(&self) -> &Pool<BvhNode<T>> {
+        &self.nodes
+    }
+
+    /// Removes every entry from the tree.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.leaves.clear();
+        self.root = Handle::NONE;
+    }
+
+    /// Inserts new entry in the tree or updates the bounding box of existing one. Returns `true` if
+    /// the tree was modified, `false` - if the new bounding box fits in the fat box of the existing
+    /// entry. The bounding box must be bounded (see [`is_aabb_bounded`]).
+    pub fn insert(&mut self, data: T, aabb: AxisAlignedBoundingBox) -> bool {
+        debug_assert!(is_aabb_bounded(&aabb));
+
+        if let Some(&leaf) = self.leaves.get(&data) {
+            let node = &mut self.nodes[leaf];
+            node.tight_aabb = aabb;
+            if contains(&node.aabb, &aabb) {
+                return false;
+            }
+
+            let fat_aabb = self.fat_aabb(&aabb);
+            self.remove_leaf(leaf);
+            self.nodes[leaf].aabb = fat_aabb;
+            self.insert_leaf(leaf);
+        } else {
+            let fat_aabb = self.fat_aabb(&aabb);
+            let leaf = self.nodes.spawn(BvhNode {
+                aabb: fat_aabb,
+                tight_aabb: aabb,
+                parent: Handle::NONE,
+                children: [Handle::NONE; 2],
+                height: 0,
+                data: Some(data),
+            });
+            self.leaves.insert(data, leaf);
+            self.insert_leaf(leaf);
+        }
+
+        true
+    }
+
+    /// Removes the entry from the tree. Returns `true` if the entry existed.
+    pub fn remove(&mut self, data: &T) -> bool {
+        if let Some(leaf) = self.leaves.remove(data) {
+            self.remove_leaf(leaf);
+            self.nodes.free(leaf);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes every entry, for which the given predicate returns `false`.
+    pub fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let to_remove = self
+            .leaves
+            .keys()
+            .filter(|data| !func(data))
+            .cloned()
+            .collect::<Vec<_>>();
+        for data in to_remove {
+            self.remove(&data);
+        }
+    }
+
+    /// Collects every entry, which bounding box intersects the frustum, into the buffer. The buffer is
+    /// cleared before the query.
+    pub fn frustum_query(&self, frustum: &Frustum, buffer: &mut Vec<T>) {
+        self.query(buffer, |aabb| frustum.is_intersects_aabb(aabb));
+    }
+
+    /// Collects every entry, which bounding box intersects the given bounding box, into the buffer. The
+    /// buffer is cleared before the query.
+    pub fn aabb_query(&self, aabb: &AxisAlignedBoundingBox, buffer: &mut Vec<T>) {
+        self.query(buffer, |other| other.intersect_aabb(aabb));
+    }
+
+    /// Collects every entry, which bounding box is intersected by the ray, into the buffer. The buffer
+    /// is cleared before the query. Keep in mind, that the ray is a segment (see [`Ray`] docs) and the
+    /// entries are not sorted by the distance.
+    pub fn ray_query(&self, ray: &Ray, buffer: &mut Vec<T>) {
+        self.query(buffer, |aabb| ray.aabb_intersection(aabb).is_some());
+    }
+
+    fn query<F>(&self, buffer: &mut Vec<T>, mut func: F)
+    where
+        F: FnMut(&AxisAlignedBoundingBox) -> bool,
+    {
+        buffer.clear();
+
+        if self.root.is_none() {
+            return;
+        }
+
+        let mut stack = Vec::with_capacity(64);
+        stack.push(self.root);
+        while let Some(handle) = stack.pop() {
+            let node = &self.nodes[handle];
+            if node.is_leaf() {
+                if func(&node.tight_aabb) {
+                    buffer.extend(node.data);
+                }
+            } else if func(&node.aabb) {
+                stack.extend_from_slice(&node.children);
+            }
+        }
+    }
+
+    fn fat_aabb(&self, aabb: &AxisAlignedBoundingBox) -> AxisAlignedBoundingBox {
+        let mut fat = *aabb;
+        fat.inflate(Vector3::repeat(2.0 * self.margin));
+        fat
+    }
+
+    fn insert_leaf(&mut self, leaf: Handle<BvhNode<T>>) {
+        if self.root.is_none() {
+            self.root = leaf;
+            self.nodes[leaf].parent = Handle::NONE;
+            return;
+        }
+
+        // Find the best sibling for the new leaf.
+        let leaf_aabb = self.nodes[leaf].aabb;
+        let mut index = self.root;
+        while !self.nodes[index].is_leaf() {
+            let node = &self.nodes[index];
+            let area = surface_area(&node.aabb);
+            let combined_area = surface_area(&merge(&node.aabb, &leaf_aabb));
+
+            // Cost of creating a new parent for this node and the new leaf.
+            let cost = 2.0 * combined_area;
+            // Minimum cost of pushing the leaf further down the tree.
+            let inheritance_cost = 2.0 * (combined_area - area);
+
+            let child_cost = |child: Handle<BvhNode<T>>| {
+                let child = &self.nodes[child];
+                let merged_area = surface_area(&merge(&leaf_aabb, &child.aabb));
+                if child.is_leaf() {
+                    merged_area + inheritance_cost
+                } else {
+                    merged_area - surface_area(&child.aabb) + inheritance_cost
+                }
+            };
+
+            let [left, right] = node.children;
+            let left_cost = child_cost(left);
+            let right_cost = child_cost(right);
+
+            if cost < left_cost && cost < right_cost {
+                break;
+            }
+
+            index = if left_cost < right_cost { left } else { right };
+        }
+
+        // Create new parent for the sibling and the leaf.
+        let sibling = index;
+        let old_parent = self.nodes[sibling].parent;
+        let new_parent = self.nodes.spawn(BvhNode {
+            aabb: merge(&leaf_aabb, &self.nodes[sibling].aabb),
+            tight_aabb: Default::default(),
+            parent: old_parent,
+            children: [sibling, leaf],
+            height: self.nodes[sibling].height + 1,
+            data: None,
+        });
+        self.nodes[sibling].parent = new_parent;
+        self.nodes[leaf].parent = new_parent;
+
+        if old_parent.is_some() {
+            self.replace_child(old_parent, sibling, new_parent);
+        } else {
+            self.root = new_parent;
+        }
+
+        self.refit(self.nodes[leaf].parent);
+    }
+
+    fn remove_leaf(&mut self, leaf: Handle<BvhNode<T>>) {
+        if leaf == self.root {
+            self.root = Handle::NONE;
+            return;
+        }
+
+        let parent = self.nodes[leaf].parent;
+        let grand_parent = self.nodes[parent].parent;
+        let [left, right] = self.nodes[parent].children;
+        let sibling = if left == leaf { right } else { left };
+
+        self.nodes[leaf].parent = Handle::NONE;
+        self.nodes.free(parent);
+
+        if grand_parent.is_some() {
+            self.replace_child(grand_parent, parent, sibling);
+            self.nodes[sibling].parent = grand_parent;
+            self.refit(grand_parent);
+        } else {
+            self.root = sibling;
+            self.nodes[sibling].parent = Handle::NONE;
+        }
+    }
+
+    fn replace_child(
+        &mut self,
+        parent: Handle<BvhNode<T>>,
+        old: Handle<BvhNode<T>>,
+        new: Handle<BvhNode<T>>,
+    ) {
+        let children = &mut self.nodes[parent].children;
+        if children[0] == old {
+            children[0] = new;
+        } else {
+            children[1] = new;
+        }
+    }
+
+    fn update_branch(&mut self, handle: Handle<BvhNode<T>>) {
+        let [left, right] = self.nodes[handle].children;
+        let (left, right) = (&self.nodes[left], &self.nodes[right]);
+        let aabb = merge(&left.aabb, &right.aabb);
+        let height = 1 + left.height.max(right.height);
+        let node = &mut self.nodes[handle];
+        node.aabb = aabb;
+        node.tight_aabb = aabb;
+        node.height = height;
+    }
+
+    // Walks up the tree from the given node, re-balancing the tree and fixing bounding boxes and
+    // heights of the nodes.
+    fn refit(&mut self, mut handle: Handle<BvhNode<T>>) {
+        while handle.is_some() {
+            handle = self.balance(handle);
+            self.update_branch(handle);
+            handle = self.nodes[handle].parent;
+        }
+    }
+
+    // Performs a left or right rotation if the node is imbalanced. Returns the new root of the sub-tree.
+    fn balance(&mut self, a: Handle<BvhNode<T>>) -> Handle<BvhNode<T>> {
+        if self.nodes[a].is_leaf() || self.nodes[a].height < 2 {
+            return a;
+        }
+
+        let [b, c] = self.nodes[a].children;
+        let balance = self.nodes[c].height - self.nodes[b].height;
+
+        if balance > 1 {
+            self.rotate(a, 1)
+        } else if balance < -1 {
+            self.rotate(a, 0)
+        } else {
+            a
+        }
+    }
+
+    // Promotes the child of `a` at `index` (which is the taller one) to the place of `a`.
+    fn rotate(&mut self, a: Handle<BvhNode<T>>, index: usize) -> Handle<BvhNode<T>> {
+        let other_index = 1 - index;
+        let promoted = self.nodes[a].children[index];
+        let other = self.nodes[a].children[other_index];
+        let [f, g] = self.nodes[promoted].children;
+
+        // Swap `a` and the promoted node.
+        let a_parent = self.nodes[a].parent;
+        self.nodes[promoted].children[0] = a;
+        self.nodes[promoted].parent = a_parent;
+        self.nodes[a].parent = promoted;
+
+        if a_parent.is_some() {
+            self.replace_child(a_parent, a, promoted);
+        } else {
+            self.root = promoted;
+        }
+
+        // The taller grandchild stays with the promoted node, the other one goes to `a`.
+        let (keep, give) = if self.nodes[f].height > self.nodes[g].height {
+            (f, g)
+        } else {
+            (g, f)
+        };
+
+        self.nodes[promoted].children[1] = keep;
+        self.nodes[a].children = if index == 1 {
+            [other, give]
+        } else {
+            [give, other]
+        };
+        self.nodes[give].parent = a;
+
+        self.update_branch(a);
+        self.update_branch(promoted);
+
+        promoted
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Matrix4, Point3, Vector3},
+            math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, ray::Ray},
+            pool::Handle,
+        },
+        scene::accel::{contains, BvhNode, DynamicBvh},
+    };
+
+    // Simple deterministic generator, so the tests are reproducible.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> f32 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (self.0 >> 40) as f32 / (1u64 << 24) as f32
+        }
+
+        fn aabb(&mut self) -> AxisAlignedBoundingBox {
+            let center = Vector3::new(self.next(), self.next(), self.next()).scale(100.0);
+            let size = Vector3::new(self.next(), self.next(), self.next()).scale(3.0);
+            AxisAlignedBoundingBox::from_min_max(center - size, center + size)
+        }
+    }
+
+    fn check_node(bvh: &DynamicBvh<u32>, handle: Handle<BvhNode<u32>>) -> (i32, usize) {
+        let node = &bvh.nodes()[handle];
+        if node.is_leaf() {
+            assert_eq!(node.height, 0);
+            assert!(contains(&node.aabb, &node.tight_aabb));
+            assert_eq!(bvh.leaves[&node.data.unwrap()], handle);
+            (0, 1)
+        } else {
+            let mut height = 0;
+            let mut count = 0;
+            for child in node.children {
+                assert_eq!(bvh.nodes()[child].parent, handle);
+                assert!(contains(&node.aabb, &bvh.nodes()[child].aabb));
+                let (child_height, child_count) = check_node(bvh, child);
+                height = height.max(child_height + 1);
+                count += child_count;
+            }
+            assert_eq!(node.height, height);
+            (height, count)
+        }
+    }
+
+    fn check_tree(bvh: &DynamicBvh<u32>) {
+        if bvh.root().is_some() {
+            assert!(bvh.nodes()[bvh.root()].parent.is_none());
+            let (_, count) = check_node(bvh, bvh.root());
+            assert_eq!(count, bvh.len());
+            // Branches of a binary tree with N leaves.
+            assert_eq!(bvh.nodes().alive_count() as usize, 2 * count - 1);
+        } else {
+            assert!(bvh.is_empty());
+        }
+    }
+
+    fn check_queries(bvh: &DynamicBvh<u32>, boxes: &[Option<AxisAlignedBoundingBox>]) {
+        let alive = || {
+            boxes
+                .iter()
+                .enumerate()
+                .filter_map(|(i, aabb)| aabb.map(|aabb| (i as u32, aabb)))
+        };
+
+        let mut result = Vec::new();
+        let mut expected;
+
+        let query_box = AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(20.0, 30.0, 10.0),
+            Vector3::new(60.0, 50.0, 70.0),
+        );
+        bvh.aabb_query(&query_box, &mut result);
+        expected = alive()
+            .filter(|(_, aabb)| aabb.intersect_aabb(&query_box))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        result.sort_unstable();
+        assert_eq!(result, expected);
+
+        let ray = Ray::from_two_points(
+            Vector3::new(-10.0, 5.0, 0.0),
+            Vector3::new(110.0, 90.0, 100.0),
+        );
+        bvh.ray_query(&ray, &mut result);
+        expected = alive()
+            .filter(|(_, aabb)| ray.aabb_intersection(aabb).is_some())
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        result.sort_unstable();
+        assert_eq!(result, expected);
+
+        let view = Matrix4::look_at_rh(
+            &Point3::new(50.0, 50.0, -20.0),
+            &Point3::new(50.0, 40.0, 50.0),
+            &Vector3::y(),
+        );
+        let projection = Matrix4::new_perspective(1.0, 0.8, 0.1, 60.0);
+        let frustum = Frustum::from_view_projection_matrix(projection * view).unwrap();
+        bvh.frustum_query(&frustum, &mut result);
+        expected = alive()
+            .filter(|(_, aabb)| frustum.is_intersects_aabb(aabb))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        result.sort_unstable();
+        assert_eq!(result, expected);
+        assert!(!result.is_empty());
+    }
+
+    #[test]
+    fn test_dynamic_bvh() {
+        let mut rng = Lcg(123);
+        let mut bvh = DynamicBvh::new();
+        let mut boxes = Vec::new();
+
+        for i in 0..1000 {
+            let aabb = rng.aabb();
+            assert!(bvh.insert(i, aabb));
+            boxes.push(Some(aabb));
+        }
+        check_tree(&bvh);
+        check_queries(&bvh, &boxes);
+        // Balanced tree must be shallow.
+        assert!(bvh.height() <= 16);
+
+        // Small movements must not touch the tree.
+        let mut aabb = boxes[0].unwrap();
+        aabb.offset(Vector3::new(0.01, 0.0, 0.0));
+        assert!(!bvh.insert(0, aabb));
+        boxes[0] = Some(aabb);
+        let entry_aabb = bvh.entry_aabb(&0).unwrap();
+        assert_eq!((entry_aabb.min, entry_aabb.max), (aabb.min, aabb.max));
+
+        // Move some entries far away.
+        for i in (0..1000).step_by(3) {
+            let aabb = rng.aabb();
+            bvh.insert(i, aabb);
+            boxes[i as usize] = Some(aabb);
+        }
+        check_tree(&bvh);
+        check_queries(&bvh, &boxes);
+
+        // Remove some entries.
+        for i in (0..1000).step_by(2) {
+            assert!(bvh.remove(&i));
+            boxes[i as usize] = None;
+        }
+        assert!(!bvh.remove(&0));
+        bvh.retain(|i| *i % 5 != 0);
+        for i in (0..1000).step_by(5) {
+            boxes[i] = None;
+        }
+        assert_eq!(bvh.len(), boxes.iter().filter(|b| b.is_some()).count());
+        check_tree(&bvh);
+        check_queries(&bvh, &boxes);
+
+        bvh.clear();
+        check_tree(&bvh);
+        assert_eq!(bvh.height(), 0);
+    }
+
+    #[test]
+    fn test_dynamic_bvh_sorted_insertion() {
+        // Sorted insertion is the worst case for a tree without rotations.
+        let mut bvh = DynamicBvh::new();
+        for i in 0..1024 {
+            let min = Vector3::new(i as f32, 0.0, 0.0);
+            bvh.insert(
+                i,
+                AxisAlignedBoundingBox::from_min_max(min, min + Vector3::repeat(0.5)),
+            );
+        }
+        check_tree(&bvh);
+        assert!(bvh.height() <= 16);
+
+        let mut result = Vec::new();
+        bvh.aabb_query(
+            &AxisAlignedBoundingBox::from_min_max(
+                Vector3::new(10.2, 0.0, 0.0),
+                Vector3::new(12.2, 0.1, 0.1),
+            ),
+            &mut result,
+        );
+        result.sort_unstable();
+        assert_eq!(result, vec![10, 11, 12]);
+    }
+}
@@ -184,12 +184,16 @@ impl NodeTrait for AnimationBlendingStateMachine {
             // do than instead.
             animation_player.set_auto_apply(false);
 
-            let pose = self.machine.get_value_mut_silent().evaluate_pose(
+            let machine = self.machine.get_value_mut_silent();
+
+            machine.evaluate_pose(
                 animation_player.animations.get_value_mut_silent(),
                 context.dt,
             );
 
-            pose.apply_internal(context.nodes);
+            machine
+                .solve_ik(&*context.nodes)
+                .apply_internal(context.nodes);
         }
     }
 
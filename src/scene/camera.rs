@@ -1009,6 +1009,7 @@ impl SkyBoxBuilder {
             bottom: self.bottom,
             front: self.front,
             back: self.back,
+            source_cube_map: None,
             cubemap: None,
         };
 
@@ -1023,6 +1024,10 @@ impl SkyBoxBuilder {
 /// skies and/or some other objects (mountains, buildings, etc.). Usually skyboxes used
 /// in outdoor scenes, however real use of it limited only by your imagination. Skybox
 /// will be drawn first, none of objects could be drawn before skybox.
+///
+/// Instead of six separate face textures, a skybox could also use a ready-to-use cube map texture,
+/// for example an HDR panorama converted with
+/// [`crate::resource::texture::Texture::equirectangular_to_cube`], see [`SkyBox::from_cube_map`].
 #[derive(Debug, Clone, Default, PartialEq, Reflect, Visit, Eq)]
 pub struct SkyBox {
     /// Texture for front face.
@@ -1049,6 +1054,11 @@ pub struct SkyBox {
     #[reflect(setter = "set_bottom")]
     pub(crate) bottom: Option<TextureResource>,
 
+    /// Ready-to-use cube map texture. If set, face textures are ignored.
+    #[reflect(setter = "set_source_cube_map")]
+    #[visit(optional)] // Backward compatibility
+    pub(crate) source_cube_map: Option<TextureResource>,
+
     /// Cubemap texture
     #[reflect(hidden)]
     #[visit(skip)]
@@ -1058,7 +1068,7 @@ pub struct SkyBox {
 /// An error that may occur during skybox creation.
 #[derive(Debug)]
 pub enum SkyBoxError {
-    /// Texture kind is not TextureKind::Rectangle
+    /// Texture kind is not TextureKind::Rectangle (or TextureKind::Cube for source cube map).
     UnsupportedTextureKind(TextureKind),
     /// Cube map was failed to build.
     UnableToBuildCubeMap,
@@ -1096,6 +1106,21 @@ pub enum SkyBoxError {
 }
 
 impl SkyBox {
+    /// Creates new skybox that uses the given cube map texture as is. The texture must have
+    /// [`TextureKind::Cube`] kind, HDR panoramas could be converted to such textures using
+    /// [`crate::resource::texture::Texture::equirectangular_to_cube`] or `cube_map_face_size`
+    /// import option.
+    pub fn from_cube_map(cube_map: TextureResource) -> Result<Self, SkyBoxError> {
+        let mut skybox = SkyBox {
+            source_cube_map: Some(cube_map),
+            ..Default::default()
+        };
+
+        skybox.create_cubemap()?;
+
+        Ok(skybox)
+    }
+
     /// Returns cubemap texture
     pub fn cubemap(&self) -> Option<TextureResource> {
         self.cubemap.clone()
@@ -1169,6 +1194,16 @@ impl SkyBox {
     ///
     /// It will fail if provided face's kind is not TextureKind::Rectangle.
     pub fn create_cubemap(&mut self) -> Result<(), SkyBoxError> {
+        if let Some(source_cube_map) = self.source_cube_map.as_ref() {
+            if let ResourceStateRef::Ok(texture) = source_cube_map.state().get() {
+                if !matches!(texture.kind(), TextureKind::Cube { .. }) {
+                    return Err(SkyBoxError::UnsupportedTextureKind(texture.kind()));
+                }
+            }
+            self.cubemap = Some(source_cube_map.clone());
+            return Ok(());
+        }
+
         self.validate()?;
 
         let (kind, pixel_kind, bytes_per_face) =
@@ -1236,6 +1271,22 @@ impl SkyBox {
         ]
     }
 
+    /// Sets new source cube map, that will be used instead of face textures. Set it to `None` to
+    /// build the cube map from face textures again.
+    pub fn set_source_cube_map(
+        &mut self,
+        cube_map: Option<TextureResource>,
+    ) -> Option<TextureResource> {
+        let prev = std::mem::replace(&mut self.source_cube_map, cube_map);
+        Log::verify(self.create_cubemap());
+        prev
+    }
+
+    /// Returns source cube map, if any.
+    pub fn source_cube_map(&self) -> Option<TextureResource> {
+        self.source_cube_map.clone()
+    }
+
     /// Set new texture for the left side of the skybox.
     pub fn set_left(&mut self, texture: Option<TextureResource>) -> Option<TextureResource> {
         let prev = std::mem::replace(&mut self.left, texture);
@@ -16,6 +16,7 @@ use crate::{
     },
     scene::{
         base::{Base, BaseBuilder},
+        character_controller::CharacterController,
         graph::{
             physics::{CoefficientCombineRule, ContactPair, IntersectionPair, PhysicsWorld},
             Graph,
@@ -785,12 +786,15 @@ impl NodeTrait for Collider {
         if scene
             .graph
             .try_get(self.parent())
-            .and_then(|p| p.query_component_ref::<RigidBody>())
+            .filter(|p| {
+                p.query_component_ref::<RigidBody>().is_some()
+                    || p.query_component_ref::<CharacterController>().is_some()
+            })
             .is_none()
         {
             Err(
-                "3D Collider must be a direct child of a 3D Rigid Body node, \
-            otherwise it will not have any effect!"
+                "3D Collider must be a direct child of a 3D Rigid Body or a 3D Character \
+            Controller node, otherwise it will not have any effect!"
                     .to_string(),
             )
         } else {
@@ -914,12 +918,16 @@ impl ColliderBuilder {
 
 #[cfg(test)]
 mod test {
-    use crate::core::algebra::Vector2;
+    use crate::core::algebra::{Point3, UnitQuaternion, Vector2, Vector3};
     use crate::scene::{
         base::BaseBuilder,
-        collider::{ColliderBuilder, ColliderShape},
-        graph::Graph,
+        collider::{ColliderBuilder, ColliderShape, InteractionGroups},
+        graph::{
+            physics::{CollisionEventKind, ShapeCastOptions, ShapeIntersectionOptions},
+            Graph,
+        },
         rigidbody::{RigidBodyBuilder, RigidBodyType},
+        transform::TransformBuilder,
     };
 
     #[test]
@@ -983,4 +991,157 @@ mod test {
                 .count()
         );
     }
+
+    #[test]
+    fn test_shape_queries() {
+        let mut graph = Graph::new();
+
+        // A floor with its top side at y = 0.
+        let floor = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(5.0, 0.5, 5.0))
+            .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, -0.5, 0.0))
+                        .build(),
+                )
+                .with_children(&[floor]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+
+        // need to call two times for the physics engine to execute
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+
+        let hit = graph
+            .physics
+            .cast_shape(ShapeCastOptions {
+                shape: ColliderShape::ball(0.5),
+                shape_position: Vector3::new(0.0, 5.0, 0.0),
+                shape_rotation: UnitQuaternion::identity(),
+                direction: Vector3::new(0.0, -2.0, 0.0),
+                max_len: 100.0,
+                groups: InteractionGroups::default(),
+                stop_at_penetration: true,
+            })
+            .unwrap();
+        assert_eq!(hit.collider, floor);
+        assert!((hit.toi - 4.5).abs() < 0.01);
+        assert!((hit.position.y).abs() < 0.01);
+        assert!((hit.normal - Vector3::y()).norm() < 0.01);
+        assert!(!hit.penetrating);
+
+        // Too short cast.
+        assert!(graph
+            .physics
+            .cast_shape(ShapeCastOptions {
+                shape: ColliderShape::ball(0.5),
+                shape_position: Vector3::new(0.0, 5.0, 0.0),
+                shape_rotation: UnitQuaternion::identity(),
+                direction: -Vector3::y(),
+                max_len: 1.0,
+                groups: InteractionGroups::default(),
+                stop_at_penetration: true,
+            })
+            .is_none());
+
+        let mut overlaps = Vec::new();
+        graph.physics.intersections_with_shape(
+            ShapeIntersectionOptions {
+                shape: ColliderShape::cuboid(0.5, 0.5, 0.5),
+                shape_position: Vector3::new(0.0, 0.25, 0.0),
+                shape_rotation: UnitQuaternion::identity(),
+                groups: InteractionGroups::default(),
+            },
+            &mut overlaps,
+        );
+        assert_eq!(overlaps, vec![floor]);
+
+        graph.physics.intersections_with_point(
+            Point3::new(0.0, 1.0, 0.0),
+            InteractionGroups::default(),
+            &mut overlaps,
+        );
+        assert!(overlaps.is_empty());
+
+        let projection = graph
+            .physics
+            .project_point(
+                Point3::new(1.0, 3.0, 1.0),
+                true,
+                InteractionGroups::default(),
+            )
+            .unwrap();
+        assert_eq!(projection.collider, floor);
+        assert!(!projection.is_inside);
+        assert!((projection.position - Point3::new(1.0, 0.0, 1.0)).norm() < 0.01);
+    }
+
+    #[test]
+    fn test_collision_events() {
+        let mut graph = Graph::new();
+
+        // A floor with its top side at y = 0 and a sensor above it.
+        let floor = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(5.0, 0.5, 5.0))
+            .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, -0.5, 0.0))
+                        .build(),
+                )
+                .with_children(&[floor]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+
+        let sensor = ColliderBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 2.0, 0.0))
+                    .build(),
+            ),
+        )
+        .with_shape(ColliderShape::cuboid(1.0, 0.5, 1.0))
+        .with_sensor(true)
+        .build(&mut graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[sensor]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+        // A ball that falls through the sensor on the floor.
+        let ball = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.25))
+            .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 4.0, 0.0))
+                        .build(),
+                )
+                .with_children(&[ball]),
+        )
+        .build(&mut graph);
+
+        let mut events = Vec::new();
+        for _ in 0..180 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+            events.extend_from_slice(graph.physics.collision_events());
+        }
+
+        let has_event = |kind, other| {
+            events
+                .iter()
+                .any(|e| e.kind == kind && e.other(ball) == Some(other))
+        };
+        assert!(has_event(CollisionEventKind::IntersectionStarted, sensor));
+        assert!(has_event(CollisionEventKind::IntersectionStopped, sensor));
+        assert!(has_event(CollisionEventKind::ContactStarted, floor));
+    }
 }
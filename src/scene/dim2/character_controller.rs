@@ -0,0 +1,581 @@
+//! Character controller 2D is a kinematic physics entity that moves a shape through the world using
+//! collision-aware movement (move-and-slide). See [`CharacterController`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector2},
+        log::Log,
+        math::{aabb::AxisAlignedBoundingBox, m4x4_approx_eq},
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        character_controller::CharacterContactKind,
+        dim2::collider::Collider,
+        graph::Graph,
+        node::{Node, NodeTrait, SyncContext, UpdateContext},
+        Scene,
+    },
+};
+use rapier2d::prelude::RigidBodyHandle;
+use std::{
+    cell::Cell,
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut},
+};
+
+/// A contact between the character and its environment, that has happened during the last movement.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CharacterContact {
+    /// A handle of the collider the character has hit.
+    pub collider: Handle<Node>,
+    /// A contact point in world coordinates.
+    pub position: Vector2<f32>,
+    /// A normal of the surface at the contact point, in world coordinates.
+    pub normal: Vector2<f32>,
+    /// A kind of the surface.
+    pub kind: CharacterContactKind,
+}
+
+/// A report about the last movement of a character controller. It is updated every frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CharacterMovementReport {
+    /// An actual translation that was applied to the character.
+    pub translation: Vector2<f32>,
+    /// `true` if the character stands on the ground.
+    pub grounded: bool,
+    /// `true` if the character has touched a ceiling.
+    pub ceiling: bool,
+    /// `true` if the character has touched a wall.
+    pub wall: bool,
+    /// A list of all the contacts that has happened during the movement.
+    pub contacts: Vec<CharacterContact>,
+}
+
+/// Character controller 2D is a kinematic physics entity that moves a shape through the world using
+/// collision-aware movement. It is a replacement for rigid bodies in cases when you need precise
+/// control over the movement of a character (player, NPC, etc.).
+///
+/// # How to use
+///
+/// Character controller must have at least one collider as a direct child node, the shape of the
+/// first such collider will be used as the shape of the character. Every frame you should set the
+/// desired translation using [`CharacterController::set_desired_translation`] and the controller
+/// will move the character as far as possible in the desired direction, sliding along obstacles,
+/// climbing small steps and slopes. The desired translation is consumed on each update. Character
+/// controller does not apply gravity on its own, you should add it to the desired translation.
+///
+/// The result of the movement can be fetched using [`CharacterController::movement_report`], it
+/// contains the actual translation and the list of contacts with the environment (ground, walls,
+/// ceiling).
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::algebra::Vector2,
+///     scene::{dim2::character_controller::CharacterController, node::Node},
+/// };
+///
+/// fn move_character(node: &mut Node, dt: f32) {
+///     if let Some(character) = node.cast_mut::<CharacterController>() {
+///         let gravity = if character.movement_report().grounded {
+///             0.0
+///         } else {
+///             -9.81 * dt
+///         };
+///         character.set_desired_translation(Vector2::new(2.0 * dt, gravity));
+///     }
+/// }
+/// ```
+#[derive(Visit, Reflect)]
+pub struct CharacterController {
+    base: Base,
+
+    /// A small gap to preserve between the character and its surroundings. Must not be zero to
+    /// improve numerical stability.
+    #[reflect(min_value = 0.0, step = 0.001)]
+    #[reflect(setter = "set_offset")]
+    pub(crate) offset: InheritableVariable<f32>,
+
+    /// Should the character slide along obstacles or stop on first contact.
+    #[reflect(setter = "set_slide")]
+    pub(crate) slide: InheritableVariable<bool>,
+
+    /// The maximum angle (in radians) between the floor's normal and the up vector that the
+    /// character is able to climb.
+    #[reflect(min_value = 0.0, max_value = 1.5708, step = 0.01)]
+    #[reflect(setter = "set_max_slope_climb_angle")]
+    pub(crate) max_slope_climb_angle: InheritableVariable<f32>,
+
+    /// The minimum angle (in radians) between the floor's normal and the up vector before the
+    /// character starts to slide down automatically.
+    #[reflect(min_value = 0.0, max_value = 1.5708, step = 0.01)]
+    #[reflect(setter = "set_min_slope_slide_angle")]
+    pub(crate) min_slope_slide_angle: InheritableVariable<f32>,
+
+    /// Should the character automatically step over small obstacles.
+    #[reflect(setter = "set_autostep_enabled")]
+    pub(crate) autostep_enabled: InheritableVariable<bool>,
+
+    /// The maximum height of a step the character can automatically step over.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    #[reflect(setter = "set_autostep_max_height")]
+    pub(crate) autostep_max_height: InheritableVariable<f32>,
+
+    /// The minimum width of free space that must be available after stepping on a stair.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    #[reflect(setter = "set_autostep_min_width")]
+    pub(crate) autostep_min_width: InheritableVariable<f32>,
+
+    /// Can the character automatically step over dynamic bodies too.
+    #[reflect(setter = "set_autostep_include_dynamic_bodies")]
+    pub(crate) autostep_include_dynamic_bodies: InheritableVariable<bool>,
+
+    /// Should the character be snapped to the ground if the distance to it is less than the
+    /// snapping distance.
+    #[reflect(setter = "set_snap_to_ground_enabled")]
+    pub(crate) snap_to_ground_enabled: InheritableVariable<bool>,
+
+    /// The maximum distance to the ground at which the character will be snapped to it.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    #[reflect(setter = "set_snap_to_ground_distance")]
+    pub(crate) snap_to_ground_distance: InheritableVariable<f32>,
+
+    /// Should the character push dynamic rigid bodies it collides with.
+    #[reflect(setter = "set_push_dynamic_bodies")]
+    pub(crate) push_dynamic_bodies: InheritableVariable<bool>,
+
+    /// A mass of the character, it is used to calculate impulses applied to dynamic rigid bodies.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    #[reflect(setter = "set_mass")]
+    pub(crate) mass: InheritableVariable<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) desired_translation: Vector2<f32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) movement_report: CharacterMovementReport,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) native: Cell<RigidBodyHandle>,
+}
+
+impl Debug for CharacterController {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CharacterController")
+    }
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        CharacterControllerBuilder::new(BaseBuilder::new()).build_character_controller()
+    }
+}
+
+impl Deref for CharacterController {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for CharacterController {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Clone for CharacterController {
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            offset: self.offset.clone(),
+            slide: self.slide.clone(),
+            max_slope_climb_angle: self.max_slope_climb_angle.clone(),
+            min_slope_slide_angle: self.min_slope_slide_angle.clone(),
+            autostep_enabled: self.autostep_enabled.clone(),
+            autostep_max_height: self.autostep_max_height.clone(),
+            autostep_min_width: self.autostep_min_width.clone(),
+            autostep_include_dynamic_bodies: self.autostep_include_dynamic_bodies.clone(),
+            snap_to_ground_enabled: self.snap_to_ground_enabled.clone(),
+            snap_to_ground_distance: self.snap_to_ground_distance.clone(),
+            push_dynamic_bodies: self.push_dynamic_bodies.clone(),
+            mass: self.mass.clone(),
+            desired_translation: self.desired_translation,
+            movement_report: self.movement_report.clone(),
+            // Do not copy. The copy will have its own native representation.
+            native: Cell::new(RigidBodyHandle::invalid()),
+        }
+    }
+}
+
+impl TypeUuidProvider for CharacterController {
+    fn type_uuid() -> Uuid {
+        uuid!("44fb3682-fab7-453f-8b2d-248fd63ccc0e")
+    }
+}
+
+impl CharacterController {
+    /// Sets a small gap to preserve between the character and its surroundings.
+    pub fn set_offset(&mut self, offset: f32) -> f32 {
+        self.offset.set_value_and_mark_modified(offset)
+    }
+
+    /// Returns current gap between the character and its surroundings.
+    pub fn offset(&self) -> f32 {
+        *self.offset
+    }
+
+    /// Sets whether the character should slide along obstacles or stop on first contact.
+    pub fn set_slide(&mut self, slide: bool) -> bool {
+        self.slide.set_value_and_mark_modified(slide)
+    }
+
+    /// Returns `true` if the character slides along obstacles.
+    pub fn is_slide(&self) -> bool {
+        *self.slide
+    }
+
+    /// Sets the maximum angle (in radians) between the floor's normal and the up vector that the
+    /// character is able to climb.
+    pub fn set_max_slope_climb_angle(&mut self, angle: f32) -> f32 {
+        self.max_slope_climb_angle
+            .set_value_and_mark_modified(angle)
+    }
+
+    /// Returns the maximum angle (in radians) of a slope the character is able to climb.
+    pub fn max_slope_climb_angle(&self) -> f32 {
+        *self.max_slope_climb_angle
+    }
+
+    /// Sets the minimum angle (in radians) between the floor's normal and the up vector before the
+    /// character starts to slide down automatically.
+    pub fn set_min_slope_slide_angle(&mut self, angle: f32) -> f32 {
+        self.min_slope_slide_angle
+            .set_value_and_mark_modified(angle)
+    }
+
+    /// Returns the minimum angle (in radians) of a slope at which the character starts to slide
+    /// down.
+    pub fn min_slope_slide_angle(&self) -> f32 {
+        *self.min_slope_slide_angle
+    }
+
+    /// Enables or disables automatic stepping over small obstacles.
+    pub fn set_autostep_enabled(&mut self, enabled: bool) -> bool {
+        self.autostep_enabled.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if the character automatically steps over small obstacles.
+    pub fn is_autostep_enabled(&self) -> bool {
+        *self.autostep_enabled
+    }
+
+    /// Sets the maximum height of a step the character can automatically step over.
+    pub fn set_autostep_max_height(&mut self, height: f32) -> f32 {
+        self.autostep_max_height.set_value_and_mark_modified(height)
+    }
+
+    /// Returns the maximum height of a step the character can automatically step over.
+    pub fn autostep_max_height(&self) -> f32 {
+        *self.autostep_max_height
+    }
+
+    /// Sets the minimum width of free space that must be available after stepping on a stair.
+    pub fn set_autostep_min_width(&mut self, width: f32) -> f32 {
+        self.autostep_min_width.set_value_and_mark_modified(width)
+    }
+
+    /// Returns the minimum width of free space that must be available after stepping on a stair.
+    pub fn autostep_min_width(&self) -> f32 {
+        *self.autostep_min_width
+    }
+
+    /// Sets whether the character can automatically step over dynamic bodies.
+    pub fn set_autostep_include_dynamic_bodies(&mut self, include: bool) -> bool {
+        self.autostep_include_dynamic_bodies
+            .set_value_and_mark_modified(include)
+    }
+
+    /// Returns `true` if the character can automatically step over dynamic bodies.
+    pub fn is_autostep_include_dynamic_bodies(&self) -> bool {
+        *self.autostep_include_dynamic_bodies
+    }
+
+    /// Enables or disables snapping to the ground.
+    pub fn set_snap_to_ground_enabled(&mut self, enabled: bool) -> bool {
+        self.snap_to_ground_enabled
+            .set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if the character snaps to the ground.
+    pub fn is_snap_to_ground_enabled(&self) -> bool {
+        *self.snap_to_ground_enabled
+    }
+
+    /// Sets the maximum distance to the ground at which the character will be snapped to it.
+    pub fn set_snap_to_ground_distance(&mut self, distance: f32) -> f32 {
+        self.snap_to_ground_distance
+            .set_value_and_mark_modified(distance)
+    }
+
+    /// Returns the maximum distance to the ground at which the character will be snapped to it.
+    pub fn snap_to_ground_distance(&self) -> f32 {
+        *self.snap_to_ground_distance
+    }
+
+    /// Sets whether the character should push dynamic rigid bodies it collides with.
+    pub fn set_push_dynamic_bodies(&mut self, push: bool) -> bool {
+        self.push_dynamic_bodies.set_value_and_mark_modified(push)
+    }
+
+    /// Returns `true` if the character pushes dynamic rigid bodies it collides with.
+    pub fn is_push_dynamic_bodies(&self) -> bool {
+        *self.push_dynamic_bodies
+    }
+
+    /// Sets the mass of the character, it is used to calculate impulses applied to dynamic rigid
+    /// bodies.
+    pub fn set_mass(&mut self, mass: f32) -> f32 {
+        self.mass.set_value_and_mark_modified(mass)
+    }
+
+    /// Returns the mass of the character.
+    pub fn mass(&self) -> f32 {
+        *self.mass
+    }
+
+    /// Sets the translation the character should try to perform on next update. The translation
+    /// is consumed on each update, so it must be set every frame.
+    pub fn set_desired_translation(&mut self, translation: Vector2<f32>) {
+        self.desired_translation = translation;
+    }
+
+    /// Returns the translation that will be performed on next update.
+    pub fn desired_translation(&self) -> Vector2<f32> {
+        self.desired_translation
+    }
+
+    /// Returns a report about the last movement of the character: its actual translation and the
+    /// list of contacts with the environment.
+    pub fn movement_report(&self) -> &CharacterMovementReport {
+        &self.movement_report
+    }
+}
+
+impl NodeTrait for CharacterController {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn on_removed_from_graph(&mut self, graph: &mut Graph) {
+        graph.physics2d.remove_body(self.native.get());
+        self.native.set(RigidBodyHandle::invalid());
+
+        Log::info(format!(
+            "Native rigid body was removed for character controller: {}",
+            self.name()
+        ));
+    }
+
+    fn sync_native(&self, self_handle: Handle<Node>, context: &mut SyncContext) {
+        context
+            .physics2d
+            .sync_to_character_controller_node(self_handle, self);
+    }
+
+    fn sync_transform(&self, new_global_transform: &Matrix4<f32>, context: &mut SyncContext) {
+        if !m4x4_approx_eq(new_global_transform, &self.global_transform()) {
+            context
+                .physics2d
+                .set_character_controller_position(self, new_global_transform);
+        }
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        // Character controller can be root node of a scene, in this case it does not have a parent.
+        let parent_transform = context
+            .nodes
+            .try_borrow(self.parent)
+            .map(|p| p.global_transform())
+            .unwrap_or_else(Matrix4::identity);
+
+        context
+            .physics2d
+            .move_character(context.nodes, self, context.dt, parent_transform);
+    }
+
+    fn validate(&self, scene: &Scene) -> Result<(), String> {
+        for &child in self.children() {
+            if scene.graph.try_get_of_type::<Collider>(child).is_some() {
+                return Ok(());
+            }
+        }
+
+        Err(
+            "The 2D character controller must have at least one 2D collider as a \
+        direct child node to work correctly!"
+                .to_string(),
+        )
+    }
+}
+
+/// Allows you to create character controller in declarative manner.
+pub struct CharacterControllerBuilder {
+    base_builder: BaseBuilder,
+    offset: f32,
+    slide: bool,
+    max_slope_climb_angle: f32,
+    min_slope_slide_angle: f32,
+    autostep_enabled: bool,
+    autostep_max_height: f32,
+    autostep_min_width: f32,
+    autostep_include_dynamic_bodies: bool,
+    snap_to_ground_enabled: bool,
+    snap_to_ground_distance: f32,
+    push_dynamic_bodies: bool,
+    mass: f32,
+}
+
+impl CharacterControllerBuilder {
+    /// Creates new character controller builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            offset: 0.01,
+            slide: true,
+            max_slope_climb_angle: std::f32::consts::FRAC_PI_4,
+            min_slope_slide_angle: std::f32::consts::FRAC_PI_4,
+            autostep_enabled: true,
+            autostep_max_height: 0.3,
+            autostep_min_width: 0.2,
+            autostep_include_dynamic_bodies: true,
+            snap_to_ground_enabled: true,
+            snap_to_ground_distance: 0.2,
+            push_dynamic_bodies: true,
+            mass: 80.0,
+        }
+    }
+
+    /// Sets the desired gap between the character and its surroundings.
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets whether the character should slide along obstacles or not.
+    pub fn with_slide(mut self, slide: bool) -> Self {
+        self.slide = slide;
+        self
+    }
+
+    /// Sets the maximum angle (in radians) of a slope the character is able to climb.
+    pub fn with_max_slope_climb_angle(mut self, angle: f32) -> Self {
+        self.max_slope_climb_angle = angle;
+        self
+    }
+
+    /// Sets the minimum angle (in radians) of a slope at which the character starts to slide down.
+    pub fn with_min_slope_slide_angle(mut self, angle: f32) -> Self {
+        self.min_slope_slide_angle = angle;
+        self
+    }
+
+    /// Sets whether the character should automatically step over small obstacles or not.
+    pub fn with_autostep_enabled(mut self, enabled: bool) -> Self {
+        self.autostep_enabled = enabled;
+        self
+    }
+
+    /// Sets the maximum height of a step the character can automatically step over.
+    pub fn with_autostep_max_height(mut self, height: f32) -> Self {
+        self.autostep_max_height = height;
+        self
+    }
+
+    /// Sets the minimum width of free space that must be available after stepping on a stair.
+    pub fn with_autostep_min_width(mut self, width: f32) -> Self {
+        self.autostep_min_width = width;
+        self
+    }
+
+    /// Sets whether the character can automatically step over dynamic bodies.
+    pub fn with_autostep_include_dynamic_bodies(mut self, include: bool) -> Self {
+        self.autostep_include_dynamic_bodies = include;
+        self
+    }
+
+    /// Sets whether the character should be snapped to the ground or not.
+    pub fn with_snap_to_ground_enabled(mut self, enabled: bool) -> Self {
+        self.snap_to_ground_enabled = enabled;
+        self
+    }
+
+    /// Sets the maximum distance to the ground at which the character will be snapped to it.
+    pub fn with_snap_to_ground_distance(mut self, distance: f32) -> Self {
+        self.snap_to_ground_distance = distance;
+        self
+    }
+
+    /// Sets whether the character should push dynamic rigid bodies or not.
+    pub fn with_push_dynamic_bodies(mut self, push: bool) -> Self {
+        self.push_dynamic_bodies = push;
+        self
+    }
+
+    /// Sets the desired mass of the character.
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// Creates CharacterController node but does not add it to the graph.
+    pub fn build_character_controller(self) -> CharacterController {
+        CharacterController {
+            base: self.base_builder.build_base(),
+            offset: self.offset.into(),
+            slide: self.slide.into(),
+            max_slope_climb_angle: self.max_slope_climb_angle.into(),
+            min_slope_slide_angle: self.min_slope_slide_angle.into(),
+            autostep_enabled: self.autostep_enabled.into(),
+            autostep_max_height: self.autostep_max_height.into(),
+            autostep_min_width: self.autostep_min_width.into(),
+            autostep_include_dynamic_bodies: self.autostep_include_dynamic_bodies.into(),
+            snap_to_ground_enabled: self.snap_to_ground_enabled.into(),
+            snap_to_ground_distance: self.snap_to_ground_distance.into(),
+            push_dynamic_bodies: self.push_dynamic_bodies.into(),
+            mass: self.mass.into(),
+            desired_translation: Default::default(),
+            movement_report: Default::default(),
+            native: Cell::new(RigidBodyHandle::invalid()),
+        }
+    }
+
+    /// Creates CharacterController node but does not add it to the graph.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_character_controller())
+    }
+
+    /// Creates CharacterController node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
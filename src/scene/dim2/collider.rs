@@ -17,6 +17,7 @@ use crate::{
         base::{Base, BaseBuilder},
         collider::InteractionGroups,
         dim2::{
+            character_controller::CharacterController,
             physics::{ContactPair, IntersectionPair, PhysicsWorld},
             rigidbody::RigidBody,
         },
@@ -226,6 +227,29 @@ impl ColliderShape {
     }
 }
 
+/// One-way platform lets other bodies pass through the collider in one direction, but collide with
+/// it in the other. It is a typical thing for 2D platformers, where a character can jump on a platform
+/// from below.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct OneWayPlatform {
+    /// Direction (in local coordinates of the collider) to the solid side of the platform. Bodies
+    /// that come from this side will collide with the platform, others will pass through it.
+    pub normal: Vector2<f32>,
+    /// Max angle (in radians) between [`Self::normal`] and a contact normal, at which the contact is
+    /// still considered as the contact with the solid side.
+    #[reflect(min_value = 0.0, max_value = 3.14, step = 0.01)]
+    pub allowed_angle: f32,
+}
+
+impl Default for OneWayPlatform {
+    fn default() -> Self {
+        Self {
+            normal: Vector2::new(0.0, 1.0),
+            allowed_angle: std::f32::consts::FRAC_PI_4,
+        }
+    }
+}
+
 /// Collider is a geometric entity that can be attached to a rigid body to allow participate it
 /// participate in contact generation, collision response and proximity queries.
 #[derive(Reflect, Visit, Debug)]
@@ -259,6 +283,10 @@ pub struct Collider {
     #[reflect(setter = "set_restitution_combine_rule")]
     pub(crate) restitution_combine_rule: InheritableVariable<CoefficientCombineRule>,
 
+    #[visit(optional)] // Backward compatibility
+    #[reflect(setter = "set_one_way_platform")]
+    pub(crate) one_way_platform: InheritableVariable<Option<OneWayPlatform>>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     pub(crate) native: Cell<ColliderHandle>,
@@ -277,6 +305,7 @@ impl Default for Collider {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            one_way_platform: Default::default(),
             native: Cell::new(ColliderHandle::invalid()),
         }
     }
@@ -309,6 +338,7 @@ impl Clone for Collider {
             solver_groups: self.solver_groups.clone(),
             friction_combine_rule: self.friction_combine_rule.clone(),
             restitution_combine_rule: self.restitution_combine_rule.clone(),
+            one_way_platform: self.one_way_platform.clone(),
             // Do not copy. The copy will have its own native representation.
             native: Cell::new(ColliderHandle::invalid()),
         }
@@ -501,6 +531,22 @@ impl Collider {
         *self.restitution_combine_rule
     }
 
+    /// Makes the collider a one-way platform (or a regular collider if `None` is passed). See
+    /// [`OneWayPlatform`] docs for more info. One-way platforms work with dynamic rigid bodies and
+    /// character controllers.
+    pub fn set_one_way_platform(
+        &mut self,
+        one_way_platform: Option<OneWayPlatform>,
+    ) -> Option<OneWayPlatform> {
+        self.one_way_platform
+            .set_value_and_mark_modified(one_way_platform)
+    }
+
+    /// Returns one-way platform options of the collider, if any.
+    pub fn one_way_platform(&self) -> Option<&OneWayPlatform> {
+        self.one_way_platform.as_ref()
+    }
+
     /// Returns an iterator that yields contact information for the collider.
     /// Contacts checks between two regular colliders
     pub fn contacts<'a>(
@@ -529,6 +575,7 @@ impl Collider {
             || self.solver_groups.need_sync()
             || self.friction_combine_rule.need_sync()
             || self.restitution_combine_rule.need_sync()
+            || self.one_way_platform.need_sync()
     }
 }
 
@@ -567,12 +614,15 @@ impl NodeTrait for Collider {
         if scene
             .graph
             .try_get(self.parent())
-            .and_then(|p| p.query_component_ref::<RigidBody>())
+            .filter(|p| {
+                p.query_component_ref::<RigidBody>().is_some()
+                    || p.query_component_ref::<CharacterController>().is_some()
+            })
             .is_none()
         {
             Err(
-                "2D Collider must be a direct child of a 3D Rigid Body node, \
-            otherwise it will not have any effect!"
+                "2D Collider must be a direct child of a 2D Rigid Body or a 2D Character \
+            Controller node, otherwise it will not have any effect!"
                     .to_string(),
             )
         } else {
@@ -593,6 +643,7 @@ pub struct ColliderBuilder {
     solver_groups: InteractionGroups,
     friction_combine_rule: CoefficientCombineRule,
     restitution_combine_rule: CoefficientCombineRule,
+    one_way_platform: Option<OneWayPlatform>,
 }
 
 impl ColliderBuilder {
@@ -609,6 +660,7 @@ impl ColliderBuilder {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            one_way_platform: None,
         }
     }
 
@@ -666,6 +718,12 @@ impl ColliderBuilder {
         self
     }
 
+    /// Makes the collider a one-way platform. See [`OneWayPlatform`] docs for more info.
+    pub fn with_one_way_platform(mut self, one_way_platform: OneWayPlatform) -> Self {
+        self.one_way_platform = Some(one_way_platform);
+        self
+    }
+
     /// Creates collider node, but does not add it to a graph.
     pub fn build_collider(self) -> Collider {
         Collider {
@@ -679,6 +737,7 @@ impl ColliderBuilder {
             solver_groups: self.solver_groups.into(),
             friction_combine_rule: self.friction_combine_rule.into(),
             restitution_combine_rule: self.restitution_combine_rule.into(),
+            one_way_platform: self.one_way_platform.into(),
             native: Cell::new(ColliderHandle::invalid()),
         }
     }
@@ -697,15 +756,18 @@ impl ColliderBuilder {
 #[cfg(test)]
 mod test {
 
-    use crate::core::algebra::Vector2;
+    use crate::core::algebra::{Point2, UnitComplex, Vector2, Vector3};
     use crate::scene::{
         base::BaseBuilder,
+        collider::InteractionGroups,
         dim2::{
-            collider::{ColliderBuilder, ColliderShape},
+            collider::{ColliderBuilder, ColliderShape, OneWayPlatform},
+            physics::{ShapeCastOptions, ShapeIntersectionOptions},
             rigidbody::RigidBodyBuilder,
         },
-        graph::Graph,
+        graph::{physics::CollisionEventKind, Graph},
         rigidbody::RigidBodyType,
+        transform::TransformBuilder,
     };
 
     #[test]
@@ -765,4 +827,190 @@ mod test {
                 .count()
         );
     }
+
+    #[test]
+    fn test_shape_queries_2d() {
+        let mut graph = Graph::new();
+
+        // A floor with its top side at y = 0.
+        let floor = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(5.0, 0.5))
+            .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, -0.5, 0.0))
+                        .build(),
+                )
+                .with_children(&[floor]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+
+        // need to call two times for the physics engine to execute
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+
+        let hit = graph
+            .physics2d
+            .cast_shape(ShapeCastOptions {
+                shape: ColliderShape::ball(0.5),
+                shape_position: Vector2::new(0.0, 5.0),
+                shape_rotation: UnitComplex::identity(),
+                direction: Vector2::new(0.0, -2.0),
+                max_len: 100.0,
+                groups: InteractionGroups::default(),
+                stop_at_penetration: true,
+            })
+            .unwrap();
+        assert_eq!(hit.collider, floor);
+        assert!((hit.toi - 4.5).abs() < 0.01);
+        assert!((hit.position.y).abs() < 0.01);
+        assert!((hit.normal - Vector2::y()).norm() < 0.01);
+        assert!(!hit.penetrating);
+
+        let mut overlaps = Vec::new();
+        graph.physics2d.intersections_with_shape(
+            ShapeIntersectionOptions {
+                shape: ColliderShape::cuboid(0.5, 0.5),
+                shape_position: Vector2::new(0.0, 0.25),
+                shape_rotation: UnitComplex::identity(),
+                groups: InteractionGroups::default(),
+            },
+            &mut overlaps,
+        );
+        assert_eq!(overlaps, vec![floor]);
+
+        graph.physics2d.intersections_with_point(
+            Point2::new(0.0, -0.5),
+            InteractionGroups::default(),
+            &mut overlaps,
+        );
+        assert_eq!(overlaps, vec![floor]);
+
+        let projection = graph
+            .physics2d
+            .project_point(Point2::new(1.0, 3.0), true, InteractionGroups::default())
+            .unwrap();
+        assert_eq!(projection.collider, floor);
+        assert!(!projection.is_inside);
+        assert!((projection.position - Point2::new(1.0, 0.0)).norm() < 0.01);
+    }
+
+    #[test]
+    fn test_collision_events_2d() {
+        let mut graph = Graph::new();
+
+        let floor = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(5.0, 0.5))
+            .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, -0.5, 0.0))
+                        .build(),
+                )
+                .with_children(&[floor]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+
+        let sensor = ColliderBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 2.0, 0.0))
+                    .build(),
+            ),
+        )
+        .with_shape(ColliderShape::cuboid(1.0, 0.5))
+        .with_sensor(true)
+        .build(&mut graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[sensor]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+        let ball = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.25))
+            .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 4.0, 0.0))
+                        .build(),
+                )
+                .with_children(&[ball]),
+        )
+        .build(&mut graph);
+
+        let mut events = Vec::new();
+        for _ in 0..180 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+            events.extend_from_slice(graph.physics2d.collision_events());
+        }
+
+        let has_event = |kind, other| {
+            events
+                .iter()
+                .any(|e| e.kind == kind && e.other(ball) == Some(other))
+        };
+        assert!(has_event(CollisionEventKind::IntersectionStarted, sensor));
+        assert!(has_event(CollisionEventKind::IntersectionStopped, sensor));
+        assert!(has_event(CollisionEventKind::ContactStarted, floor));
+        assert_eq!(
+            graph.physics2d.collision_events_with(sensor).count(),
+            0,
+            "events must be cleared on every step"
+        );
+    }
+
+    #[test]
+    fn test_one_way_platform() {
+        let mut graph = Graph::new();
+
+        // A thin one-way platform at y = 0 that is solid from above.
+        let platform = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(3.0, 0.1))
+            .with_one_way_platform(OneWayPlatform::default())
+            .build(&mut graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[platform]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+        let mut create_ball = |position: Vector2<f32>, lin_vel: Vector2<f32>| {
+            let collider = ColliderBuilder::new(BaseBuilder::new())
+                .with_shape(ColliderShape::ball(0.25))
+                .build(&mut graph);
+            RigidBodyBuilder::new(
+                BaseBuilder::new()
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(Vector3::new(position.x, position.y, 0.0))
+                            .build(),
+                    )
+                    .with_children(&[collider]),
+            )
+            .with_lin_vel(lin_vel)
+            .build(&mut graph)
+        };
+
+        // The first ball falls on the platform from above, the second one is thrown from below,
+        // passes through the platform and lands on it too.
+        let falling = create_ball(Vector2::new(-1.5, 2.0), Vector2::default());
+        let thrown = create_ball(Vector2::new(1.5, -1.0), Vector2::new(0.0, 8.0));
+
+        for _ in 0..300 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        for ball in [falling, thrown] {
+            let y = graph[ball].global_position().y;
+            assert!(
+                (y - 0.35).abs() < 0.05,
+                "ball must rest on the platform, y = {y}"
+            );
+        }
+    }
 }
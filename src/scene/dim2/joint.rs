@@ -16,6 +16,7 @@ use crate::{
         base::{Base, BaseBuilder},
         dim2::rigidbody::RigidBody,
         graph::Graph,
+        joint::{JointDof, JointMotor},
         node::{Node, NodeTrait, SyncContext},
         Scene,
     },
@@ -41,6 +42,11 @@ pub struct BallJoint {
     #[reflect(description = "Allowed angles range for the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub limits_angles: Range<f32>,
+
+    /// A motor that drives rotation of the joint.
+    #[reflect(description = "A motor that drives rotation of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for BallJoint {
@@ -48,6 +54,7 @@ impl Default for BallJoint {
         Self {
             limits_enabled: false,
             limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
@@ -72,6 +79,11 @@ pub struct PrismaticJoint {
     #[reflect(description = "Allowed linear distance range along local X axis of the joint.")]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// A motor that drives translation along local X axis of the joint.
+    #[reflect(description = "A motor that drives translation along local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for PrismaticJoint {
@@ -79,10 +91,49 @@ impl Default for PrismaticJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
 
+/// Three degrees of freedom joint is the most generic 2D joint, every degree of freedom of it (two
+/// translations along local axes of the joint and a rotation) could be locked, limited or driven by
+/// a motor individually. It could be used to create custom joints, that cannot be made using other
+/// joint types. By default all degrees of freedom are locked.
+#[derive(Clone, Debug, Default, Visit, PartialEq, Reflect)]
+pub struct ThreeDofJoint {
+    /// Translation along local X axis of the joint.
+    #[reflect(description = "Translation along local X axis of the joint.")]
+    pub linear_x: JointDof,
+
+    /// Translation along local Y axis of the joint.
+    #[reflect(description = "Translation along local Y axis of the joint.")]
+    pub linear_y: JointDof,
+
+    /// Rotation of the joint.
+    #[reflect(description = "Rotation of the joint.")]
+    pub angular: JointDof,
+}
+
+/// Rope joint limits the maximum distance between the anchor points of two rigid bodies, but allows
+/// them to move freely when the distance is less than the maximum. The anchor points coincide with
+/// the position of the joint at the moment of binding. The real world example is a rope or a chain.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct RopeJoint {
+    /// The maximum distance between the anchor points of the attached bodies.
+    #[reflect(
+        description = "The maximum distance between the anchor points of the attached bodies."
+    )]
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub max_distance: f32,
+}
+
+impl Default for RopeJoint {
+    fn default() -> Self {
+        Self { max_distance: 1.0 }
+    }
+}
+
 /// The exact kind of the joint.
 #[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames)]
 pub enum JointParams {
@@ -92,6 +143,10 @@ pub enum JointParams {
     FixedJoint(FixedJoint),
     /// See [`PrismaticJoint`] for more info.
     PrismaticJoint(PrismaticJoint),
+    /// See [`ThreeDofJoint`] for more info.
+    ThreeDofJoint(ThreeDofJoint),
+    /// See [`RopeJoint`] for more info.
+    RopeJoint(RopeJoint),
 }
 
 impl Default for JointParams {
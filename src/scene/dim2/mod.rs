@@ -1,8 +1,10 @@
 //! The module contains 2D scene nodes and physics. Despite the naming, scene nodes are still 3D
 //! but physics simulation is in true 2D.
 
+pub mod character_controller;
 pub mod collider;
 pub mod joint;
 pub mod physics;
 pub mod rectangle;
 pub mod rigidbody;
+pub mod tilemap;
@@ -3,7 +3,7 @@
 use crate::{
     core::{
         algebra::{
-            Isometry2, Isometry3, Matrix4, Point2, Rotation3, Translation2, Translation3,
+            Isometry2, Isometry3, Matrix4, Point2, Point3, Rotation3, Translation2, Translation3,
             UnitComplex, UnitQuaternion, Vector2, Vector3,
         },
         arrayvec::ArrayVec,
@@ -19,17 +19,33 @@ use crate::{
     },
     scene::{
         self,
+        character_controller::CharacterContactKind,
         collider::{self},
         debug::SceneDrawingContext,
-        dim2::{self, collider::ColliderShape, joint::JointParams, rigidbody::ApplyAction},
+        dim2::{
+            self,
+            character_controller::{
+                CharacterContact, CharacterController, CharacterMovementReport,
+            },
+            collider::{ColliderShape, OneWayPlatform},
+            joint::JointParams,
+            rigidbody::ApplyAction,
+            tilemap::TileMap,
+        },
         graph::{
-            physics::{FeatureId, IntegrationParameters, PhysicsPerformanceStatistics},
+            physics::{
+                CollisionEvent, CollisionEventCollector, FeatureId, IntegrationParameters,
+                PhysicsPerformanceStatistics,
+            },
             NodePool,
         },
         node::{Node, NodeTrait},
     },
 };
+use fxhash::FxHashMap;
+use fyrox_resource::ResourceStateRef;
 use rapier2d::{
+    control::{CharacterAutostep, CharacterLength, KinematicCharacterController},
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
         IslandManager, JointAxesMask, JointAxis, MultibodyJointHandle, MultibodyJointSet,
@@ -40,10 +56,14 @@ use rapier2d::{
         BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid,
         InteractionGroups, NarrowPhase, Ray, SharedShape,
     },
-    pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter, QueryPipeline},
+    parry::query::{intersection_test, TOIStatus},
+    pipeline::{
+        ActiveEvents, ActiveHooks, ContactModificationContext, DebugRenderPipeline, PhysicsHooks,
+        PhysicsPipeline, QueryFilter, QueryPipeline,
+    },
 };
 use std::{
-    cell::RefCell,
+    cell::{RefCell, RefMut},
     cmp::Ordering,
     fmt::{Debug, Formatter},
     hash::Hash,
@@ -200,6 +220,79 @@ pub struct IntersectionPair {
     pub has_any_active_contact: bool,
 }
 
+/// A set of options for the shape cast.
+pub struct ShapeCastOptions {
+    /// A shape to cast. Trimesh and heightfield shapes are not supported and will be ignored.
+    pub shape: ColliderShape,
+
+    /// Initial position of the shape in world coordinates.
+    pub shape_position: Vector2<f32>,
+
+    /// Rotation of the shape in world coordinates.
+    pub shape_rotation: UnitComplex<f32>,
+
+    /// A direction of the cast. Can be non-normalized.
+    pub direction: Vector2<f32>,
+
+    /// Maximum distance of cast.
+    pub max_len: f32,
+
+    /// Groups to check.
+    pub groups: collider::InteractionGroups,
+
+    /// If `true`, the cast will stop at a collider that the shape already intersects at its initial
+    /// position. Otherwise such colliders will be hit only if the shape moves deeper into them.
+    pub stop_at_penetration: bool,
+}
+
+/// A result of the shape cast.
+#[derive(Debug, Clone)]
+pub struct ShapeCastResult {
+    /// A handle of the collider that was hit by the shape.
+    pub collider: Handle<Node>,
+
+    /// Distance that the shape has traveled along the cast direction before the hit.
+    pub toi: f32,
+
+    /// A position of the contact point on the hit collider in world coordinates.
+    pub position: Point2<f32>,
+
+    /// A normal of the hit collider at the contact point in world coordinates.
+    pub normal: Vector2<f32>,
+
+    /// `true` if the shape already intersected the collider at its initial position. In this case
+    /// `toi` is zero and the position and the normal are undefined.
+    pub penetrating: bool,
+}
+
+/// A set of options for the shape intersection query.
+pub struct ShapeIntersectionOptions {
+    /// A shape to test. Trimesh and heightfield shapes are not supported and will be ignored.
+    pub shape: ColliderShape,
+
+    /// A position of the shape in world coordinates.
+    pub shape_position: Vector2<f32>,
+
+    /// Rotation of the shape in world coordinates.
+    pub shape_rotation: UnitComplex<f32>,
+
+    /// Groups to check.
+    pub groups: collider::InteractionGroups,
+}
+
+/// A result of the point projection.
+#[derive(Debug, Clone)]
+pub struct PointProjection {
+    /// A handle of the collider on which the point was projected.
+    pub collider: Handle<Node>,
+
+    /// A projected point in world coordinates.
+    pub position: Point2<f32>,
+
+    /// Whether the point was inside the collider.
+    pub is_inside: bool,
+}
+
 pub(super) struct Container<S, A>
 where
     A: Hash + Eq + Clone,
@@ -217,6 +310,20 @@ fn convert_joint_params(
         JointParams::BallJoint(_) => JointAxesMask::LOCKED_REVOLUTE_AXES,
         JointParams::FixedJoint(_) => JointAxesMask::LOCKED_FIXED_AXES,
         JointParams::PrismaticJoint(_) => JointAxesMask::LOCKED_PRISMATIC_AXES,
+        JointParams::ThreeDofJoint(ref v) => {
+            let mut mask = JointAxesMask::empty();
+            for (dof, axis) in [
+                (&v.linear_x, JointAxesMask::X),
+                (&v.linear_y, JointAxesMask::Y),
+                (&v.angular, JointAxesMask::ANG_X),
+            ] {
+                if dof.locked {
+                    mask |= axis;
+                }
+            }
+            mask
+        }
+        JointParams::RopeJoint(_) => JointAxesMask::FREE_FIXED_AXES,
     };
 
     let mut joint = GenericJointBuilder::new(locked_axis)
@@ -232,18 +339,59 @@ fn convert_joint_params(
                     [v.limits_angles.start, v.limits_angles.end],
                 );
             }
+            set_joint_motor(&mut joint, JointAxis::AngX, &v.motor);
         }
         scene::dim2::joint::JointParams::FixedJoint(_) => {}
         scene::dim2::joint::JointParams::PrismaticJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::X, [v.limits.start, v.limits.end]);
             }
+            set_joint_motor(&mut joint, JointAxis::X, &v.motor);
+        }
+        scene::dim2::joint::JointParams::ThreeDofJoint(v) => {
+            for (dof, axis) in [
+                (&v.linear_x, JointAxis::X),
+                (&v.linear_y, JointAxis::Y),
+                (&v.angular, JointAxis::AngX),
+            ] {
+                if !dof.locked {
+                    if dof.limits_enabled {
+                        joint.set_limits(axis, [dof.limits.start, dof.limits.end]);
+                    }
+                    set_joint_motor(&mut joint, axis, &dof.motor);
+                }
+            }
+        }
+        scene::dim2::joint::JointParams::RopeJoint(v) => {
+            // The distance between the anchors is limited by coupling all linear axes. The solver
+            // takes the length of the vector of per-axis limits as the distance limit, so the
+            // per-axis limit must be scaled accordingly.
+            joint.coupled_axes = JointAxesMask::LIN_AXES;
+            let limit = v.max_distance / 2.0f32.sqrt();
+            for axis in [JointAxis::X, JointAxis::Y] {
+                joint.set_limits(axis, [0.0, limit]);
+            }
         }
     }
 
     joint
 }
 
+fn set_joint_motor(joint: &mut GenericJoint, axis: JointAxis, motor: &scene::joint::JointMotor) {
+    if motor.enabled {
+        joint
+            .set_motor(
+                axis,
+                motor.target_position,
+                motor.target_velocity,
+                motor.stiffness,
+                motor.damping,
+            )
+            .set_motor_max_force(axis, motor.max_force)
+            .set_motor_model(axis, motor.model.into());
+    }
+}
+
 // Converts descriptor in a shared shape.
 fn collider_shape_into_native_shape(shape: &ColliderShape) -> Option<SharedShape> {
     match shape {
@@ -340,10 +488,23 @@ pub struct PhysicsWorld {
     #[visit(skip)]
     #[reflect(hidden)]
     multibody_joints: Container<MultibodyJointSet, MultibodyJointHandle>,
-    // Event handler collects info about contacts and proximity events.
+    // Event collector gathers native collision events during simulation step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    event_collector: CollisionEventCollector<rapier2d::geometry::CollisionEvent>,
+    // Collision events of the last simulation step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
+    // Owners of the colliders that were removed since last simulation step. The physics pipeline
+    // emits "stopped" events for such colliders during the next step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    removed_colliders: FxHashMap<ColliderHandle, Handle<Node>>,
+    // One-way platform options of colliders, used by contact modification hooks.
     #[visit(skip)]
     #[reflect(hidden)]
-    event_handler: Box<dyn EventHandler>,
+    one_way_platforms: FxHashMap<ColliderHandle, OneWayPlatform>,
     #[visit(skip)]
     #[reflect(hidden)]
     query: RefCell<QueryPipeline>,
@@ -363,6 +524,54 @@ fn isometry_from_global_transform(transform: &Matrix4<f32>) -> Isometry2<f32> {
     }
 }
 
+// Returns a handle of a native rigid body of a node that can have colliders attached to it (a
+// rigid body or a character controller).
+fn native_body_of(node: &Node) -> Option<RigidBodyHandle> {
+    if let Some(rigid_body) = node.cast::<dim2::rigidbody::RigidBody>() {
+        Some(rigid_body.native.get())
+    } else {
+        node.cast::<CharacterController>()
+            .map(|controller| controller.native.get())
+    }
+}
+
+// Contact modification hooks that turn colliders into one-way platforms.
+struct OneWayPlatformHooks<'a> {
+    platforms: &'a FxHashMap<ColliderHandle, OneWayPlatform>,
+}
+
+impl<'a> PhysicsHooks for OneWayPlatformHooks<'a> {
+    fn modify_solver_contacts(&self, context: &mut ContactModificationContext) {
+        let (platform, is_first) = match (
+            self.platforms.get(&context.collider1),
+            self.platforms.get(&context.collider2),
+        ) {
+            (Some(platform), _) => (platform, true),
+            (None, Some(platform)) => (platform, false),
+            (None, None) => return,
+        };
+
+        // Contact normal of the manifold is expressed in the local space of the first collider and
+        // points towards the second collider.
+        let allowed_local_n1 = if is_first {
+            platform.normal
+        } else {
+            match (
+                context.colliders.get(context.collider1),
+                context.colliders.get(context.collider2),
+            ) {
+                (Some(collider1), Some(collider2)) => {
+                    collider1.position().rotation.inverse()
+                        * (collider2.position().rotation * -platform.normal)
+                }
+                _ => return,
+            }
+        };
+
+        context.update_as_oneway_platform(&allowed_local_n1, platform.allowed_angle);
+    }
+}
+
 fn calculate_local_frames(
     joint: &dyn NodeTrait,
     body1: &dyn NodeTrait,
@@ -376,10 +585,45 @@ fn calculate_local_frames(
     )
 }
 
+fn one_way_platform_hooks(is_one_way_platform: bool) -> ActiveHooks {
+    if is_one_way_platform {
+        ActiveHooks::MODIFY_SOLVER_CONTACTS
+    } else {
+        ActiveHooks::empty()
+    }
+}
+
+fn set_one_way_platform(
+    platforms: &mut FxHashMap<ColliderHandle, OneWayPlatform>,
+    collider: ColliderHandle,
+    platform: Option<OneWayPlatform>,
+) {
+    match platform {
+        Some(mut platform) => {
+            // Contact normals are compared by dot product, so the normal must have unit length.
+            platform.normal = platform
+                .normal
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector2::y);
+            platforms.insert(collider, platform);
+        }
+        None => {
+            platforms.remove(&collider);
+        }
+    }
+}
+
 fn u32_to_group(v: u32) -> rapier2d::geometry::Group {
     rapier2d::geometry::Group::from_bits(v).unwrap_or_else(rapier2d::geometry::Group::all)
 }
 
+fn query_filter<'a>(groups: collider::InteractionGroups) -> QueryFilter<'a> {
+    QueryFilter::new().groups(InteractionGroups::new(
+        u32_to_group(groups.memberships.0),
+        u32_to_group(groups.filter.0),
+    ))
+}
+
 impl PhysicsWorld {
     /// Creates a new instance of the physics world.
     pub(crate) fn new() -> Self {
@@ -408,7 +652,10 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_collector: Default::default(),
+            collision_events: Default::default(),
+            removed_colliders: Default::default(),
+            one_way_platforms: Default::default(),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
@@ -418,6 +665,8 @@ impl PhysicsWorld {
     pub(crate) fn update(&mut self, dt: f32) {
         let time = instant::Instant::now();
 
+        self.collision_events.clear();
+
         if self.enabled {
             let integration_parameters = rapier2d::dynamics::IntegrationParameters {
                 dt: self.integration_parameters.dt.unwrap_or(dt),
@@ -460,9 +709,23 @@ impl PhysicsWorld {
                 // In Rapier 0.17 passing query pipeline here sometimes causing panic in numeric overflow,
                 // so we keep updating it manually.
                 None,
-                &(),
-                &*self.event_handler,
+                &OneWayPlatformHooks {
+                    platforms: &self.one_way_platforms,
+                },
+                &self.event_collector,
             );
+
+            let colliders = &self.colliders.map;
+            let removed_colliders = &self.removed_colliders;
+            self.event_collector
+                .drain_into(&mut self.collision_events, |collider| {
+                    colliders
+                        .value_of(&collider)
+                        .or_else(|| removed_colliders.get(&collider))
+                        .cloned()
+                        .unwrap_or_default()
+                });
+            self.removed_colliders.clear();
         }
 
         self.performance_statistics.step_time += instant::Instant::now() - time;
@@ -513,7 +776,11 @@ impl PhysicsWorld {
             .remove(handle, &mut self.islands, &mut self.bodies.set, false)
             .is_some()
         {
-            assert!(self.colliders.map.remove_by_key(&handle).is_some());
+            self.one_way_platforms.remove(&handle);
+            let owner = self.colliders.map.remove_by_key(&handle);
+            assert!(owner.is_some());
+            self.removed_colliders
+                .extend(owner.map(|owner| (handle, owner)));
             true
         } else {
             false
@@ -538,6 +805,39 @@ impl PhysicsWorld {
         }
     }
 
+    /// Returns collision events (started or stopped contacts and intersections) that happened
+    /// during the last simulation step. The events are available until the next step, which means
+    /// that scripts could read them in their `on_update` method:
+    ///
+    /// ```rust
+    /// # use fyrox::{
+    /// #     core::pool::Handle,
+    /// #     scene::{graph::physics::CollisionEventKind, node::Node},
+    /// #     script::ScriptContext,
+    /// # };
+    /// fn on_update(context: &mut ScriptContext, collider: Handle<Node>) {
+    ///     for event in context.scene.graph.physics2d.collision_events_with(collider) {
+    ///         if event.kind == CollisionEventKind::IntersectionStarted {
+    ///             println!("{:?} entered the trigger", event.other(collider));
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
+    /// Returns an iterator over collision events of the last simulation step that involve the given
+    /// collider node.
+    pub fn collision_events_with(
+        &self,
+        collider: Handle<Node>,
+    ) -> impl Iterator<Item = &CollisionEvent> + '_ {
+        self.collision_events
+            .iter()
+            .filter(move |event| event.involves(collider))
+    }
+
     /// Draws physics world. Very useful for debugging, it allows you to see where are
     /// rigid bodies, which colliders they have and so on.
     pub fn draw(&self, context: &mut SceneDrawingContext) {
@@ -551,10 +851,7 @@ impl PhysicsWorld {
         );
     }
 
-    /// Casts a ray with given options.
-    pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
-        let time = instant::Instant::now();
-
+    fn updated_query(&self) -> RefMut<QueryPipeline> {
         let mut query = self.query.borrow_mut();
 
         // TODO: Ideally this must be called once per frame, but it seems to be impossible because
@@ -564,6 +861,15 @@ impl PhysicsWorld {
         // of the frame.
         query.update(&self.bodies.set, &self.colliders.set);
 
+        query
+    }
+
+    /// Casts a ray with given options.
+    pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
+        let time = instant::Instant::now();
+
+        let query = self.updated_query();
+
         query_buffer.clear();
         let ray = Ray::new(
             opts.ray_origin,
@@ -577,10 +883,7 @@ impl PhysicsWorld {
             &ray,
             opts.max_len,
             true,
-            QueryFilter::new().groups(InteractionGroups::new(
-                u32_to_group(opts.groups.memberships.0),
-                u32_to_group(opts.groups.filter.0),
-            )),
+            query_filter(opts.groups),
             |handle, intersection| {
                 query_buffer.push(Intersection {
                     collider: self.colliders.map.value_of(&handle).cloned().unwrap(),
@@ -609,12 +912,141 @@ impl PhysicsWorld {
         );
     }
 
+    /// Moves a shape along the given direction and returns the first collider that was hit by the
+    /// shape (if any). Could be used for sweep tests, for example to check whether a character could
+    /// move to some position.
+    pub fn cast_shape(&self, opts: ShapeCastOptions) -> Option<ShapeCastResult> {
+        let shape = collider_shape_into_native_shape(&opts.shape)?;
+        let direction = opts.direction.try_normalize(f32::EPSILON)?;
+        let shape_isometry = Isometry2 {
+            translation: Translation2::from(opts.shape_position),
+            rotation: opts.shape_rotation,
+        };
+
+        let query = self.updated_query();
+
+        query
+            .cast_shape(
+                &self.bodies.set,
+                &self.colliders.set,
+                &shape_isometry,
+                &direction,
+                &*shape,
+                opts.max_len,
+                opts.stop_at_penetration,
+                query_filter(opts.groups),
+            )
+            .and_then(|(handle, toi)| {
+                // Witness and normal 1 refer to the hit collider and are already in world space.
+                Some(ShapeCastResult {
+                    collider: self.colliders.map.value_of(&handle).cloned()?,
+                    toi: toi.toi,
+                    position: toi.witness1,
+                    normal: toi.normal1.into_inner(),
+                    penetrating: toi.status == TOIStatus::Penetrating,
+                })
+            })
+    }
+
+    /// Searches for every collider that intersects the given shape and writes their handles in the
+    /// given buffer. The buffer is cleared before the query.
+    pub fn intersections_with_shape(
+        &self,
+        opts: ShapeIntersectionOptions,
+        query_buffer: &mut Vec<Handle<Node>>,
+    ) {
+        query_buffer.clear();
+
+        let shape = match collider_shape_into_native_shape(&opts.shape) {
+            Some(shape) => shape,
+            None => return,
+        };
+        let shape_isometry = Isometry2 {
+            translation: Translation2::from(opts.shape_position),
+            rotation: opts.shape_rotation,
+        };
+
+        let query = self.updated_query();
+
+        query.intersections_with_shape(
+            &self.bodies.set,
+            &self.colliders.set,
+            &shape_isometry,
+            &*shape,
+            query_filter(opts.groups),
+            |handle| {
+                if let Some(collider) = self.colliders.map.value_of(&handle) {
+                    query_buffer.push(*collider);
+                }
+                true
+            },
+        );
+    }
+
+    /// Searches for every collider that contains the given point (in world coordinates) and writes
+    /// their handles in the given buffer. The buffer is cleared before the query.
+    pub fn intersections_with_point(
+        &self,
+        point: Point2<f32>,
+        groups: collider::InteractionGroups,
+        query_buffer: &mut Vec<Handle<Node>>,
+    ) {
+        query_buffer.clear();
+
+        let query = self.updated_query();
+
+        query.intersections_with_point(
+            &self.bodies.set,
+            &self.colliders.set,
+            &point,
+            query_filter(groups),
+            |handle| {
+                if let Some(collider) = self.colliders.map.value_of(&handle) {
+                    query_buffer.push(*collider);
+                }
+                true
+            },
+        );
+    }
+
+    /// Projects a point (in world coordinates) on the closest collider. If `solid` is `true`, then
+    /// colliders are treated as solid objects and a point inside a collider will be "projected" on
+    /// itself. Otherwise the point will be projected on the boundary of the collider.
+    pub fn project_point(
+        &self,
+        point: Point2<f32>,
+        solid: bool,
+        groups: collider::InteractionGroups,
+    ) -> Option<PointProjection> {
+        let query = self.updated_query();
+
+        query
+            .project_point(
+                &self.bodies.set,
+                &self.colliders.set,
+                &point,
+                solid,
+                query_filter(groups),
+            )
+            .and_then(|(handle, projection)| {
+                Some(PointProjection {
+                    collider: self.colliders.map.value_of(&handle).cloned()?,
+                    position: projection.point,
+                    is_inside: projection.is_inside,
+                })
+            })
+    }
+
     pub(crate) fn set_rigid_body_position(
         &mut self,
         rigid_body: &scene::dim2::rigidbody::RigidBody,
         new_global_transform: &Matrix4<f32>,
     ) {
-        if let Some(native) = self.bodies.set.get_mut(rigid_body.native.get()) {
+        self.set_body_position(rigid_body.native.get(), new_global_transform)
+    }
+
+    fn set_body_position(&mut self, handle: RigidBodyHandle, new_global_transform: &Matrix4<f32>) {
+        if let Some(native) = self.bodies.set.get_mut(handle) {
             native.set_position(
                 isometry_from_global_transform(new_global_transform),
                 // Do not wake up body, it is too expensive and must be done **only** by explicit
@@ -804,6 +1236,218 @@ impl PhysicsWorld {
         }
     }
 
+    pub(crate) fn set_character_controller_position(
+        &mut self,
+        controller: &CharacterController,
+        new_global_transform: &Matrix4<f32>,
+    ) {
+        if let Some(native) = self.bodies.set.get(controller.native.get()) {
+            // The controller moves its body by setting next kinematic position on each update, the
+            // body must be teleported only if the node was moved by something else.
+            let next_position = native.next_position();
+            let new_position = isometry_from_global_transform(new_global_transform);
+            if (next_position.translation.vector - new_position.translation.vector).norm()
+                <= f32::EPSILON
+                && next_position
+                    .rotation
+                    .angle_to(&new_position.rotation)
+                    .abs()
+                    <= f32::EPSILON
+            {
+                return;
+            }
+        }
+
+        self.set_body_position(controller.native.get(), new_global_transform)
+    }
+
+    pub(crate) fn sync_to_character_controller_node(
+        &mut self,
+        handle: Handle<Node>,
+        controller: &CharacterController,
+    ) {
+        if !controller.is_globally_enabled() {
+            self.remove_body(controller.native.get());
+            controller.native.set(Default::default());
+            return;
+        }
+
+        if controller.native.get() == RigidBodyHandle::invalid() {
+            // Character controller is backed by a kinematic body, its position is fully controlled
+            // by the controller itself. It must not sleep, otherwise its movement will be ignored.
+            let body = RigidBodyBuilder::kinematic_position_based()
+                .position(isometry_from_global_transform(
+                    &controller.global_transform(),
+                ))
+                .can_sleep(false)
+                .build();
+
+            controller.native.set(self.add_body(handle, body));
+
+            Log::writeln(
+                MessageKind::Information,
+                format!(
+                    "Native rigid body was created for character controller {}",
+                    controller.name()
+                ),
+            );
+        }
+    }
+
+    pub(crate) fn move_character(
+        &mut self,
+        nodes: &NodePool,
+        controller: &mut CharacterController,
+        dt: f32,
+        parent_transform: Matrix4<f32>,
+    ) {
+        let desired_translation = std::mem::take(&mut controller.desired_translation);
+
+        if !self.enabled {
+            return;
+        }
+
+        let body_handle = controller.native.get();
+
+        // The shape of the first collider is used as the shape of the character.
+        let collider = match controller.children().iter().find_map(|c| {
+            nodes
+                .try_borrow(*c)
+                .and_then(|n| n.cast::<dim2::collider::Collider>())
+                .and_then(|c| self.colliders.set.get(c.native.get()))
+        }) {
+            Some(collider) => collider,
+            None => return,
+        };
+        let shape = collider.shared_shape().clone();
+        let character_position = *collider.position();
+        // Character controller does not use contact modification hooks, so one-way platforms are
+        // filtered out when the character moves away from their solid side or is inside them.
+        let one_way_platforms = &self.one_way_platforms;
+        let one_way_platform_filter =
+            |handle: ColliderHandle, platform_collider: &Collider| match one_way_platforms
+                .get(&handle)
+            {
+                Some(platform) => {
+                    let normal = platform_collider.position().rotation * platform.normal;
+                    desired_translation.dot(&normal) <= 0.0
+                        && !intersection_test(
+                            &character_position,
+                            &*shape,
+                            platform_collider.position(),
+                            platform_collider.shape(),
+                        )
+                        .unwrap_or_default()
+                }
+                None => true,
+            };
+        let filter = QueryFilter::new()
+            .exclude_rigid_body(body_handle)
+            .exclude_sensors()
+            .groups(collider.collision_groups())
+            .predicate(&one_way_platform_filter);
+
+        let native_controller = KinematicCharacterController {
+            up: Vector2::y_axis(),
+            offset: CharacterLength::Absolute(controller.offset()),
+            slide: controller.is_slide(),
+            autostep: if controller.is_autostep_enabled() {
+                Some(CharacterAutostep {
+                    max_height: CharacterLength::Absolute(controller.autostep_max_height()),
+                    min_width: CharacterLength::Absolute(controller.autostep_min_width()),
+                    include_dynamic_bodies: controller.is_autostep_include_dynamic_bodies(),
+                })
+            } else {
+                None
+            },
+            max_slope_climb_angle: controller.max_slope_climb_angle(),
+            min_slope_slide_angle: controller.min_slope_slide_angle(),
+            snap_to_ground: if controller.is_snap_to_ground_enabled() {
+                Some(CharacterLength::Absolute(
+                    controller.snap_to_ground_distance(),
+                ))
+            } else {
+                None
+            },
+        };
+
+        let mut collisions = Vec::new();
+        let movement = native_controller.move_shape(
+            dt,
+            &self.bodies.set,
+            &self.colliders.set,
+            &self.updated_query(),
+            &*shape,
+            &character_position,
+            desired_translation,
+            filter,
+            |collision| collisions.push(collision),
+        );
+
+        if controller.is_push_dynamic_bodies() {
+            let query = self.query.borrow();
+            for collision in collisions.iter() {
+                native_controller.solve_character_collision_impulses(
+                    dt,
+                    &mut self.bodies.set,
+                    &self.colliders.set,
+                    &query,
+                    &*shape,
+                    controller.mass(),
+                    collision,
+                    filter,
+                );
+            }
+        }
+
+        let mut report = CharacterMovementReport {
+            translation: movement.translation,
+            grounded: movement.grounded,
+            ..Default::default()
+        };
+        let max_slope_cos = controller.max_slope_climb_angle().cos();
+        for collision in collisions.iter() {
+            if let Some(collider) = self.colliders.map.value_of(&collision.handle) {
+                // The normal is on the surface of the collider that was hit, it points towards
+                // the character.
+                let normal = *collision.toi.normal1;
+                let kind = if normal.y >= max_slope_cos {
+                    report.grounded = true;
+                    CharacterContactKind::Ground
+                } else if -normal.y >= max_slope_cos {
+                    report.ceiling = true;
+                    CharacterContactKind::Ceiling
+                } else {
+                    report.wall = true;
+                    CharacterContactKind::Wall
+                };
+                report.contacts.push(CharacterContact {
+                    collider: *collider,
+                    position: collision.toi.witness1.coords,
+                    normal,
+                    kind,
+                });
+            }
+        }
+        controller.movement_report = report;
+
+        if let Some(body) = self.bodies.set.get_mut(body_handle) {
+            let new_position = body.position().translation.vector + movement.translation;
+            body.set_next_kinematic_translation(new_position);
+
+            // Preserve depth of the node, 2D physics knows nothing about it.
+            let depth = controller.global_transform()[14];
+            let local_position = parent_transform
+                .try_inverse()
+                .unwrap_or_else(Matrix4::identity)
+                .transform_point(&Point3::new(new_position.x, new_position.y, depth))
+                .coords;
+            controller
+                .local_transform_mut()
+                .set_position(local_position);
+        }
+    }
+
     pub(crate) fn sync_to_collider_node(
         &mut self,
         nodes: &NodePool,
@@ -870,14 +1514,18 @@ impl PhysicsWorld {
                     collider_node
                         .restitution_combine_rule
                         .try_sync_model(|v| native.set_restitution_combine_rule(v.into()));
+                    let one_way_platforms = &mut self.one_way_platforms;
+                    collider_node.one_way_platform.try_sync_model(|v| {
+                        native.set_active_hooks(one_way_platform_hooks(v.is_some()));
+                        set_one_way_platform(one_way_platforms, collider_node.native.get(), v);
+                    });
                 }
             }
-        } else if let Some(parent_body) = nodes
+        } else if let Some(rigid_body_native) = nodes
             .try_borrow(collider_node.parent())
-            .and_then(|n| n.cast::<dim2::rigidbody::RigidBody>())
+            .and_then(native_body_of)
         {
-            if parent_body.native.get() != RigidBodyHandle::invalid() {
-                let rigid_body_native = parent_body.native.get();
+            if rigid_body_native != RigidBodyHandle::invalid() {
                 if let Some(shape) = collider_shape_into_native_shape(collider_node.shape()) {
                     let mut builder = ColliderBuilder::new(shape)
                         .position(Isometry2 {
@@ -900,7 +1548,11 @@ impl PhysicsWorld {
                             u32_to_group(collider_node.solver_groups().memberships.0),
                             u32_to_group(collider_node.solver_groups().filter.0),
                         ))
-                        .sensor(collider_node.is_sensor());
+                        .sensor(collider_node.is_sensor())
+                        .active_events(ActiveEvents::COLLISION_EVENTS)
+                        .active_hooks(one_way_platform_hooks(
+                            collider_node.one_way_platform().is_some(),
+                        ));
 
                     if let Some(density) = collider_node.density() {
                         builder = builder.density(density);
@@ -911,6 +1563,12 @@ impl PhysicsWorld {
 
                     collider_node.native.set(native_handle);
 
+                    set_one_way_platform(
+                        &mut self.one_way_platforms,
+                        native_handle,
+                        collider_node.one_way_platform().cloned(),
+                    );
+
                     Log::writeln(
                         MessageKind::Information,
                         format!(
@@ -923,6 +1581,106 @@ impl PhysicsWorld {
         }
     }
 
+    pub(crate) fn set_tile_map_position(
+        &mut self,
+        tile_map: &TileMap,
+        new_global_transform: &Matrix4<f32>,
+    ) {
+        self.set_body_position(tile_map.native.get(), new_global_transform)
+    }
+
+    fn remove_tile_map_body(&mut self, tile_map: &TileMap) {
+        for (_, collider) in tile_map.native_colliders.lock().drain() {
+            self.remove_collider(collider);
+        }
+        self.remove_body(tile_map.native.get());
+        tile_map.native.set(RigidBodyHandle::invalid());
+    }
+
+    pub(crate) fn sync_to_tile_map_node(&mut self, handle: Handle<Node>, tile_map: &TileMap) {
+        if !tile_map.is_globally_enabled() || !tile_map.is_collision_enabled() {
+            if tile_map.native.get() != RigidBodyHandle::invalid() {
+                self.remove_tile_map_body(tile_map);
+            }
+            // Drop the flag, there is nothing to sync.
+            tile_map.collision_enabled.try_sync_model(|_| ());
+            return;
+        }
+
+        if tile_map.native.get() == RigidBodyHandle::invalid() {
+            let body = RigidBodyBuilder::fixed()
+                .position(isometry_from_global_transform(&tile_map.global_transform()))
+                .build();
+
+            tile_map.native.set(self.add_body(handle, body));
+            tile_map.invalidate_colliders();
+
+            Log::writeln(
+                MessageKind::Information,
+                format!(
+                    "Native rigid body was created for tile map {}",
+                    tile_map.name()
+                ),
+            );
+        }
+
+        let tile_set_changed = tile_map.tile_set.try_sync_model(|_| ());
+        let tile_size_changed = tile_map.tile_size.try_sync_model(|_| ());
+        let collision_enabled_changed = tile_map.collision_enabled.try_sync_model(|_| ());
+        if tile_set_changed || tile_size_changed || collision_enabled_changed {
+            tile_map.invalidate_colliders();
+        }
+
+        let mut dirty_chunks = tile_map.dirty_chunks.lock();
+        if dirty_chunks.is_empty() {
+            return;
+        }
+
+        let tile_set_resource = tile_map.tile_set();
+        let tile_set_state = tile_set_resource.map(|tile_set| tile_set.state());
+        let tile_set = match tile_set_state.as_ref().map(|state| state.get()) {
+            Some(ResourceStateRef::Ok(tile_set)) => Some(tile_set),
+            // Wait until the tile set is loaded, chunks will stay dirty until then.
+            Some(ResourceStateRef::Pending { .. }) => return,
+            Some(ResourceStateRef::LoadError { .. }) | None => None,
+        };
+
+        let body = tile_map.native.get();
+        let mut native_colliders = tile_map.native_colliders.lock();
+        for chunk in dirty_chunks.drain() {
+            if let Some(collider) = native_colliders.remove(&chunk) {
+                self.remove_collider(collider);
+            }
+
+            let shapes = match tile_set {
+                Some(tile_set) => tile_map.chunk_collision_shapes(chunk, tile_set),
+                None => continue,
+            };
+
+            let mut compound = Vec::new();
+            for rect in shapes.rectangles {
+                let center = rect.center();
+                compound.push((
+                    Isometry2::translation(center.x, center.y),
+                    SharedShape::cuboid(rect.w() * 0.5, rect.h() * 0.5),
+                ));
+            }
+            for polygon in shapes.polygons {
+                let points = polygon.into_iter().map(Point2::from).collect::<Vec<_>>();
+                if let Some(shape) = SharedShape::convex_hull(&points) {
+                    compound.push((Isometry2::identity(), shape));
+                }
+            }
+
+            if !compound.is_empty() {
+                let collider = ColliderBuilder::new(SharedShape::compound(compound))
+                    .active_events(ActiveEvents::COLLISION_EVENTS)
+                    .build();
+                native_colliders.insert(chunk, self.add_collider(handle, body, collider));
+            }
+        }
+    }
+
     pub(crate) fn sync_to_joint_node(
         &mut self,
         nodes: &NodePool,
@@ -952,10 +1710,14 @@ impl PhysicsWorld {
                     native.body2 = rigid_body_node.native.get();
                 }
             });
+            let mut params_changed = false;
             joint.params.try_sync_model(|v| {
+                let contacts_enabled = native.data.contacts_enabled;
                 native.data =
                     // Preserve local frames.
-                    convert_joint_params(v, native.data.local_frame1, native.data.local_frame2)
+                    convert_joint_params(v, native.data.local_frame1, native.data.local_frame2);
+                native.data.set_contacts_enabled(contacts_enabled);
+                params_changed = true;
             });
             joint.contacts_enabled.try_sync_model(|v| {
                 native.data.set_contacts_enabled(v);
@@ -972,9 +1734,20 @@ impl PhysicsWorld {
                     let (local_frame1, local_frame2) = calculate_local_frames(joint, body1, body2);
                     native.data =
                         convert_joint_params((*joint.params).clone(), local_frame1, local_frame2);
+                    native
+                        .data
+                        .set_contacts_enabled(joint.is_contacts_enabled());
                     joint.need_rebind.set(false);
                 }
             }
+            if params_changed {
+                // Motors and limits have no effect on sleeping bodies.
+                for body in [native.body1, native.body2] {
+                    if let Some(body) = self.bodies.set.get_mut(body) {
+                        body.wake_up(true);
+                    }
+                }
+            }
         } else {
             let body1_handle = joint.body1();
             let body2_handle = joint.body2();
@@ -0,0 +1,797 @@
+//! Tile map is a 2D grid of tiles, that is used to create 2D levels. See [`TileMap`] docs for more
+//! info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Vector2, Vector3},
+        math::{aabb::AxisAlignedBoundingBox, m4x4_approx_eq, Rect},
+        parking_lot::Mutex,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    resource::tileset::{TileCollider, TileSet, TileSetResource},
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        node::{Node, NodeTrait, SyncContext},
+    },
+};
+use fxhash::{FxHashMap, FxHashSet};
+use rapier2d::{dynamics::RigidBodyHandle, geometry::ColliderHandle};
+use std::{
+    cell::Cell,
+    collections::VecDeque,
+    ops::{Deref, DerefMut},
+};
+
+/// Size of a tile map chunk (in tiles) along each axis.
+pub const CHUNK_SIZE: i32 = 16;
+
+/// Returns a position of a chunk, that contains a tile at the given position.
+pub fn chunk_position(tile: Vector2<i32>) -> Vector2<i32> {
+    Vector2::new(tile.x.div_euclid(CHUNK_SIZE), tile.y.div_euclid(CHUNK_SIZE))
+}
+
+/// Chunk is a square block of [`CHUNK_SIZE`] x [`CHUNK_SIZE`] tiles. Tile maps store, render and
+/// generate colliders for their tiles per chunk, which allows to quickly skip invisible parts of
+/// a tile map and to rebuild colliders only for the modified parts.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct TileMapChunk {
+    // Maps a position of a tile to the index of its definition in the tile set.
+    tiles: FxHashMap<Vector2<i32>, u32>,
+}
+
+impl TileMapChunk {
+    /// Returns an iterator over the tiles of the chunk. Each item is a tuple of a tile position and
+    /// an index of the tile in the tile set.
+    pub fn tiles(&self) -> impl Iterator<Item = (Vector2<i32>, u32)> + '_ {
+        self.tiles.iter().map(|(position, tile)| (*position, *tile))
+    }
+
+    /// Returns the amount of tiles in the chunk.
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Returns `true` if the chunk does not have any tiles.
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+}
+
+/// A set of collision primitives of a chunk, defined in the local space of a tile map.
+#[derive(Default, Debug, PartialEq)]
+pub(crate) struct ChunkCollisionShapes {
+    pub rectangles: Vec<Rect<f32>>,
+    pub polygons: Vec<Vec<Vector2<f32>>>,
+}
+
+/// Tile map is a 2D grid of tiles, that is used to create 2D levels. Every tile references a tile
+/// definition in a [`TileSet`] resource, which defines its appearance, collision shape and custom
+/// properties.
+///
+/// ## Coordinates
+///
+/// Tiles are addressed by integer coordinates, the tile at `(x, y)` occupies the rectangle from
+/// `(x * w, y * h)` to `((x + 1) * w, (y + 1) * h)` in the local coordinates of the tile map, where
+/// `w` and `h` are the width and the height of a tile (see [`Self::set_tile_size`]). The map is
+/// unbounded, coordinates could be negative. Use [`Self::world_to_tile`] to convert a point in
+/// world coordinates to a tile position.
+///
+/// ## Performance
+///
+/// Tiles are stored in chunks (see [`TileMapChunk`]) of [`CHUNK_SIZE`] x [`CHUNK_SIZE`] tiles.
+/// The 2D renderer culls tile maps per chunk and batches visible tiles together with other 2D
+/// objects, that use the same texture, so a large level could be drawn with a few draw calls.
+///
+/// ## Physics
+///
+/// Tile map automatically creates a fixed 2D rigid body with one collider per chunk. Adjacent
+/// fully solid tiles (see [`TileCollider::Full`]) are merged into larger rectangles. Colliders are
+/// rebuilt only for the chunks that were modified. Collision generation could be disabled by
+/// [`Self::set_collision_enabled`]. Keep in mind, that the scale of the tile map is not applied to
+/// the colliders, use the tile size to change the size of the tiles instead.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::{algebra::Vector2, pool::Handle},
+///     resource::tileset::TileSetResource,
+///     scene::{base::BaseBuilder, dim2::tilemap::TileMapBuilder, graph::Graph, node::Node},
+/// };
+///
+/// fn create_floor(tile_set: TileSetResource, graph: &mut Graph) -> Handle<Node> {
+///     let mut builder = TileMapBuilder::new(BaseBuilder::new())
+///         .with_tile_set(tile_set)
+///         .with_tile_size(Vector2::new(0.5, 0.5));
+///
+///     for x in -10..10 {
+///         builder = builder.with_tile(Vector2::new(x, 0), 0);
+///     }
+///
+///     builder.build(graph)
+/// }
+/// ```
+#[derive(Visit, Reflect, Debug)]
+pub struct TileMap {
+    base: Base,
+
+    #[reflect(setter = "set_tile_set")]
+    pub(crate) tile_set: InheritableVariable<Option<TileSetResource>>,
+
+    #[reflect(setter = "set_tile_size")]
+    pub(crate) tile_size: InheritableVariable<Vector2<f32>>,
+
+    #[reflect(setter = "set_collision_enabled")]
+    pub(crate) collision_enabled: InheritableVariable<bool>,
+
+    #[reflect(hidden)]
+    chunks: InheritableVariable<FxHashMap<Vector2<i32>, TileMapChunk>>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) native: Cell<RigidBodyHandle>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) native_colliders: Mutex<FxHashMap<Vector2<i32>, ColliderHandle>>,
+
+    // Chunks whose colliders must be rebuilt.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) dirty_chunks: Mutex<FxHashSet<Vector2<i32>>>,
+}
+
+impl Default for TileMap {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            tile_set: Default::default(),
+            tile_size: InheritableVariable::new_modified(Vector2::new(1.0, 1.0)),
+            collision_enabled: InheritableVariable::new_modified(true),
+            chunks: Default::default(),
+            native: Cell::new(RigidBodyHandle::invalid()),
+            native_colliders: Default::default(),
+            dirty_chunks: Default::default(),
+        }
+    }
+}
+
+impl Clone for TileMap {
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            tile_set: self.tile_set.clone(),
+            tile_size: self.tile_size.clone(),
+            collision_enabled: self.collision_enabled.clone(),
+            chunks: self.chunks.clone(),
+            // Do not copy. The copy will have its own native representation.
+            native: Cell::new(RigidBodyHandle::invalid()),
+            native_colliders: Default::default(),
+            dirty_chunks: Default::default(),
+        }
+    }
+}
+
+impl Deref for TileMap {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for TileMap {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for TileMap {
+    fn type_uuid() -> Uuid {
+        uuid!("3b1f6e2a-9d7c-4c58-a0e4-7f2b8d6c1e95")
+    }
+}
+
+impl TileMap {
+    /// Returns a tile set used by the tile map.
+    pub fn tile_set(&self) -> Option<&TileSetResource> {
+        self.tile_set.as_ref()
+    }
+
+    /// Sets new tile set for the tile map. Tiles store indices of their definitions in the tile
+    /// set, so the new tile set should have compatible layout.
+    pub fn set_tile_set(&mut self, tile_set: Option<TileSetResource>) -> Option<TileSetResource> {
+        self.tile_set.set_value_and_mark_modified(tile_set)
+    }
+
+    /// Returns current size of a tile in local coordinates of the tile map.
+    pub fn tile_size(&self) -> Vector2<f32> {
+        *self.tile_size
+    }
+
+    /// Sets new size of a tile in local coordinates of the tile map. Default is `(1.0, 1.0)`.
+    pub fn set_tile_size(&mut self, tile_size: Vector2<f32>) -> Vector2<f32> {
+        self.tile_size.set_value_and_mark_modified(tile_size)
+    }
+
+    /// Returns `true` if the tile map generates colliders for its tiles, `false` - otherwise.
+    pub fn is_collision_enabled(&self) -> bool {
+        *self.collision_enabled
+    }
+
+    /// Enables or disables generation of colliders for the tiles of the tile map.
+    pub fn set_collision_enabled(&mut self, enabled: bool) -> bool {
+        self.collision_enabled.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns an index of a tile (in the tile set) at the given position.
+    pub fn tile(&self, position: Vector2<i32>) -> Option<u32> {
+        self.chunks
+            .get(&chunk_position(position))
+            .and_then(|chunk| chunk.tiles.get(&position).cloned())
+    }
+
+    /// Puts a tile with the given index (in the tile set) at the given position. `None` removes a
+    /// tile at the position. Returns previous tile at the position.
+    pub fn set_tile(&mut self, position: Vector2<i32>, tile: Option<u32>) -> Option<u32> {
+        let chunk_pos = chunk_position(position);
+        let chunks = self.chunks.get_value_mut_and_mark_modified();
+        let prev = match tile {
+            Some(tile) => chunks
+                .entry(chunk_pos)
+                .or_default()
+                .tiles
+                .insert(position, tile),
+            None => {
+                let prev = chunks
+                    .get_mut(&chunk_pos)
+                    .and_then(|chunk| chunk.tiles.remove(&position));
+                if chunks
+                    .get(&chunk_pos)
+                    .map_or(false, |chunk| chunk.is_empty())
+                {
+                    chunks.remove(&chunk_pos);
+                }
+                prev
+            }
+        };
+        if prev != tile {
+            self.dirty_chunks.get_mut().insert(chunk_pos);
+        }
+        prev
+    }
+
+    /// Fills the given rectangle (in tile coordinates) with the tile. `None` removes all the tiles
+    /// in the rectangle.
+    pub fn fill_rect(&mut self, rect: Rect<i32>, tile: Option<u32>) {
+        for y in rect.y()..(rect.y() + rect.h()) {
+            for x in rect.x()..(rect.x() + rect.w()) {
+                self.set_tile(Vector2::new(x, y), tile);
+            }
+        }
+    }
+
+    /// Removes every tile of the tile map.
+    pub fn clear(&mut self) {
+        let chunks = self.chunks.get_value_mut_and_mark_modified();
+        self.dirty_chunks.get_mut().extend(chunks.keys().cloned());
+        chunks.clear();
+    }
+
+    /// Returns an iterator over every tile of the tile map. Each item is a tuple of a tile position
+    /// and an index of the tile in the tile set.
+    pub fn tiles(&self) -> impl Iterator<Item = (Vector2<i32>, u32)> + '_ {
+        self.chunks.values().flat_map(|chunk| chunk.tiles())
+    }
+
+    /// Returns an iterator over the chunks of the tile map. Each item is a tuple of a chunk position
+    /// and a reference to the chunk.
+    pub fn chunks(&self) -> impl Iterator<Item = (Vector2<i32>, &TileMapChunk)> + '_ {
+        self.chunks
+            .iter()
+            .map(|(position, chunk)| (*position, chunk))
+    }
+
+    /// Returns a rectangle (in tile coordinates) that contains every tile of the tile map, or `None`
+    /// if the tile map is empty.
+    pub fn tile_bounds(&self) -> Option<Rect<i32>> {
+        let mut tiles = self.tiles();
+        let (first, _) = tiles.next()?;
+        let mut min = first;
+        let mut max = first;
+        for (position, _) in tiles {
+            min = min.inf(&position);
+            max = max.sup(&position);
+        }
+        Some(Rect::new(
+            min.x,
+            min.y,
+            max.x - min.x + 1,
+            max.y - min.y + 1,
+        ))
+    }
+
+    /// Returns a region of 4-connected tiles that starts at the given position and have the same
+    /// tile (or the lack of it) as the start position. The search is limited by the bounds of the
+    /// tile map, so filling an empty area never goes beyond existing tiles.
+    pub fn flood_fill_region(&self, start: Vector2<i32>) -> Vec<Vector2<i32>> {
+        let (min, max) = match self.tile_bounds() {
+            Some(bounds) => (
+                bounds.position.inf(&start),
+                (bounds.position + bounds.size - Vector2::new(1, 1)).sup(&start),
+            ),
+            None => (start, start),
+        };
+
+        let target = self.tile(start);
+        let mut region = Vec::new();
+        let mut visited = FxHashSet::default();
+        let mut queue = VecDeque::new();
+        visited.insert(start);
+        queue.push_back(start);
+        while let Some(position) = queue.pop_front() {
+            region.push(position);
+            for offset in [
+                Vector2::new(1, 0),
+                Vector2::new(-1, 0),
+                Vector2::new(0, 1),
+                Vector2::new(0, -1),
+            ] {
+                let neighbour = position + offset;
+                if neighbour.x >= min.x
+                    && neighbour.x <= max.x
+                    && neighbour.y >= min.y
+                    && neighbour.y <= max.y
+                    && self.tile(neighbour) == target
+                    && visited.insert(neighbour)
+                {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        region
+    }
+
+    /// Returns a rectangle of a tile at the given position in local coordinates of the tile map.
+    pub fn tile_rect(&self, position: Vector2<i32>) -> Rect<f32> {
+        let size = self.tile_size();
+        Rect::new(
+            position.x as f32 * size.x,
+            position.y as f32 * size.y,
+            size.x,
+            size.y,
+        )
+    }
+
+    /// Converts a point in world coordinates to a position of a tile, that contains the point.
+    pub fn world_to_tile(&self, point: Vector3<f32>) -> Vector2<i32> {
+        let local = self
+            .global_transform()
+            .try_inverse()
+            .unwrap_or_else(Matrix4::identity)
+            .transform_point(&Point3::from(point));
+        let size = self.tile_size();
+        Vector2::new(
+            (local.x / size.x).floor() as i32,
+            (local.y / size.y).floor() as i32,
+        )
+    }
+
+    /// Returns a bounding box of a chunk at the given position in local coordinates of the tile map.
+    pub fn chunk_local_bounding_box(&self, chunk: Vector2<i32>) -> AxisAlignedBoundingBox {
+        let size = self.tile_size() * CHUNK_SIZE as f32;
+        let min = Vector3::new(chunk.x as f32 * size.x, chunk.y as f32 * size.y, 0.0);
+        AxisAlignedBoundingBox::from_min_max(min, min + Vector3::new(size.x, size.y, 0.0))
+    }
+
+    /// Returns a bounding box of a chunk at the given position in world coordinates.
+    pub fn chunk_world_bounding_box(&self, chunk: Vector2<i32>) -> AxisAlignedBoundingBox {
+        self.chunk_local_bounding_box(chunk)
+            .transform(&self.global_transform())
+    }
+
+    /// Returns a world matrix of a tile at the given position. The matrix transforms a unit quad
+    /// centered at the origin into the rectangle of the tile.
+    pub fn tile_world_matrix(&self, position: Vector2<i32>) -> Matrix4<f32> {
+        let rect = self.tile_rect(position);
+        let center = rect.center();
+        self.global_transform()
+            * Matrix4::new_translation(&Vector3::new(center.x, center.y, 0.0))
+            * Matrix4::new_nonuniform_scaling(&Vector3::new(rect.w(), rect.h(), 1.0))
+    }
+
+    /// Marks colliders of every chunk as invalid, so they will be rebuilt on next update.
+    pub(crate) fn invalidate_colliders(&self) {
+        let mut dirty_chunks = self.dirty_chunks.lock();
+        dirty_chunks.extend(self.chunks.keys().cloned());
+        dirty_chunks.extend(self.native_colliders.lock().keys().cloned());
+    }
+
+    /// Collects collision shapes of a chunk. Adjacent fully solid tiles are merged into larger
+    /// rectangles using greedy meshing.
+    pub(crate) fn chunk_collision_shapes(
+        &self,
+        chunk_pos: Vector2<i32>,
+        tile_set: &TileSet,
+    ) -> ChunkCollisionShapes {
+        let mut shapes = ChunkCollisionShapes::default();
+
+        let chunk = match self.chunks.get(&chunk_pos) {
+            Some(chunk) => chunk,
+            None => return shapes,
+        };
+
+        let size = self.tile_size();
+        let origin = chunk_pos * CHUNK_SIZE;
+        let mut solid = [false; (CHUNK_SIZE * CHUNK_SIZE) as usize];
+        let index = |x: i32, y: i32| (y * CHUNK_SIZE + x) as usize;
+
+        for (position, tile) in chunk.tiles() {
+            let definition = match tile_set.tile(tile) {
+                Some(definition) => definition,
+                None => continue,
+            };
+            let rect = self.tile_rect(position);
+            match &definition.collider {
+                TileCollider::None => (),
+                TileCollider::Full => {
+                    let local = position - origin;
+                    solid[index(local.x, local.y)] = true;
+                }
+                TileCollider::Rectangle(r) => shapes.rectangles.push(Rect::new(
+                    rect.x() + r.x() * size.x,
+                    rect.y() + r.y() * size.y,
+                    r.w() * size.x,
+                    r.h() * size.y,
+                )),
+                TileCollider::Polygon(points) => shapes.polygons.push(
+                    points
+                        .iter()
+                        .map(|p| rect.position + p.component_mul(&size))
+                        .collect(),
+                ),
+            }
+        }
+
+        for y in 0..CHUNK_SIZE {
+            let mut x = 0;
+            while x < CHUNK_SIZE {
+                if !solid[index(x, y)] {
+                    x += 1;
+                    continue;
+                }
+
+                let mut w = 1;
+                while x + w < CHUNK_SIZE && solid[index(x + w, y)] {
+                    w += 1;
+                }
+
+                let mut h = 1;
+                while y + h < CHUNK_SIZE && (x..x + w).all(|cx| solid[index(cx, y + h)]) {
+                    h += 1;
+                }
+
+                for cy in y..y + h {
+                    for cx in x..x + w {
+                        solid[index(cx, cy)] = false;
+                    }
+                }
+
+                let min = self.tile_rect(origin + Vector2::new(x, y));
+                shapes.rectangles.push(Rect::new(
+                    min.x(),
+                    min.y(),
+                    w as f32 * size.x,
+                    h as f32 * size.y,
+                ));
+
+                x += w;
+            }
+        }
+
+        shapes
+    }
+}
+
+impl NodeTrait for TileMap {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        match self.tile_bounds() {
+            Some(bounds) => {
+                let min = self.tile_rect(bounds.position);
+                let max = self.tile_rect(bounds.position + bounds.size - Vector2::new(1, 1));
+                AxisAlignedBoundingBox::from_min_max(
+                    Vector3::new(min.x(), min.y(), 0.0),
+                    Vector3::new(max.x() + max.w(), max.y() + max.h(), 0.0),
+                )
+            }
+            None => self.base.local_bounding_box(),
+        }
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn on_removed_from_graph(&mut self, graph: &mut Graph) {
+        for (_, collider) in self.native_colliders.get_mut().drain() {
+            graph.physics2d.remove_collider(collider);
+        }
+        graph.physics2d.remove_body(self.native.get());
+        self.native.set(RigidBodyHandle::invalid());
+    }
+
+    fn sync_native(&self, self_handle: Handle<Node>, context: &mut SyncContext) {
+        context.physics2d.sync_to_tile_map_node(self_handle, self);
+    }
+
+    fn sync_transform(&self, new_global_transform: &Matrix4<f32>, context: &mut SyncContext) {
+        if !m4x4_approx_eq(new_global_transform, &self.global_transform()) {
+            context
+                .physics2d
+                .set_tile_map_position(self, new_global_transform);
+        }
+    }
+}
+
+/// Allows you to create tile map in declarative manner.
+pub struct TileMapBuilder {
+    base_builder: BaseBuilder,
+    tile_set: Option<TileSetResource>,
+    tile_size: Vector2<f32>,
+    collision_enabled: bool,
+    tiles: Vec<(Vector2<i32>, u32)>,
+}
+
+impl TileMapBuilder {
+    /// Creates new tile map builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            tile_set: None,
+            tile_size: Vector2::new(1.0, 1.0),
+            collision_enabled: true,
+            tiles: Default::default(),
+        }
+    }
+
+    /// Sets desired tile set of the tile map.
+    pub fn with_tile_set(mut self, tile_set: TileSetResource) -> Self {
+        self.tile_set = Some(tile_set);
+        self
+    }
+
+    /// Sets desired size of a tile of the tile map.
+    pub fn with_tile_size(mut self, tile_size: Vector2<f32>) -> Self {
+        self.tile_size = tile_size;
+        self
+    }
+
+    /// Enables or disables generation of colliders for the tile map.
+    pub fn with_collision_enabled(mut self, enabled: bool) -> Self {
+        self.collision_enabled = enabled;
+        self
+    }
+
+    /// Puts a tile with the given index (in the tile set) at the given position.
+    pub fn with_tile(mut self, position: Vector2<i32>, tile: u32) -> Self {
+        self.tiles.push((position, tile));
+        self
+    }
+
+    /// Creates new [`TileMap`] instance.
+    pub fn build_tile_map(self) -> TileMap {
+        let mut tile_map = TileMap {
+            base: self.base_builder.build_base(),
+            tile_set: self.tile_set.into(),
+            tile_size: self.tile_size.into(),
+            collision_enabled: self.collision_enabled.into(),
+            chunks: Default::default(),
+            native: Cell::new(RigidBodyHandle::invalid()),
+            native_colliders: Default::default(),
+            dirty_chunks: Default::default(),
+        };
+        for (position, tile) in self.tiles {
+            tile_map.set_tile(position, Some(tile));
+        }
+        tile_map
+    }
+
+    /// Creates new [`TileMap`] instance.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_tile_map())
+    }
+
+    /// Creates new [`TileMap`] instance and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Vector2, Vector3},
+            math::Rect,
+        },
+        resource::tileset::{TileCollider, TileDefinition, TileSet, TileSetResource},
+        scene::{
+            base::BaseBuilder,
+            dim2::{
+                collider::{ColliderBuilder, ColliderShape},
+                rigidbody::RigidBodyBuilder,
+                tilemap::{chunk_position, TileMap, TileMapBuilder},
+            },
+            graph::Graph,
+            transform::TransformBuilder,
+        },
+    };
+
+    fn tile_set() -> TileSet {
+        let mut tile_set = TileSet::new(None);
+        tile_set.add_tile(TileDefinition {
+            collider: TileCollider::Full,
+            ..Default::default()
+        });
+        tile_set.add_tile(TileDefinition::default());
+        tile_set.add_tile(TileDefinition {
+            collider: TileCollider::Rectangle(Rect::new(0.0, 0.0, 1.0, 0.5)),
+            ..Default::default()
+        });
+        tile_set
+    }
+
+    #[test]
+    fn test_tile_map_tiles() {
+        assert_eq!(chunk_position(Vector2::new(-1, -1)), Vector2::new(-1, -1));
+        assert_eq!(chunk_position(Vector2::new(15, 0)), Vector2::new(0, 0));
+        assert_eq!(chunk_position(Vector2::new(16, -16)), Vector2::new(1, -1));
+
+        let mut tile_map = TileMap::default();
+        assert_eq!(tile_map.tile_bounds(), None);
+
+        assert_eq!(tile_map.set_tile(Vector2::new(-1, -1), Some(0)), None);
+        assert_eq!(tile_map.set_tile(Vector2::new(16, 2), Some(1)), None);
+        assert_eq!(tile_map.set_tile(Vector2::new(16, 2), Some(2)), Some(1));
+        assert_eq!(tile_map.tile(Vector2::new(16, 2)), Some(2));
+        assert_eq!(tile_map.tile(Vector2::new(0, 0)), None);
+        assert_eq!(tile_map.chunks().count(), 2);
+        assert_eq!(tile_map.tile_bounds(), Some(Rect::new(-1, -1, 18, 4)));
+
+        assert_eq!(tile_map.set_tile(Vector2::new(-1, -1), None), Some(0));
+        // Empty chunks must be removed.
+        assert_eq!(tile_map.chunks().count(), 1);
+
+        tile_map.fill_rect(Rect::new(0, 0, 3, 2), Some(1));
+        assert_eq!(tile_map.tiles().count(), 7);
+        tile_map.clear();
+        assert_eq!(tile_map.tiles().count(), 0);
+    }
+
+    #[test]
+    fn test_flood_fill_region() {
+        let mut tile_map = TileMap::default();
+
+        // A closed ring of tiles with a 2x2 hole inside.
+        tile_map.fill_rect(Rect::new(0, 0, 4, 4), Some(0));
+        tile_map.fill_rect(Rect::new(1, 1, 2, 2), None);
+
+        let mut hole = tile_map.flood_fill_region(Vector2::new(1, 1));
+        hole.sort_by_key(|p| (p.y, p.x));
+        assert_eq!(
+            hole,
+            vec![
+                Vector2::new(1, 1),
+                Vector2::new(2, 1),
+                Vector2::new(1, 2),
+                Vector2::new(2, 2)
+            ]
+        );
+
+        assert_eq!(tile_map.flood_fill_region(Vector2::new(0, 0)).len(), 12);
+
+        // Filling outside of the tile map is limited by its bounds, which are extended to the
+        // start position.
+        assert_eq!(tile_map.flood_fill_region(Vector2::new(6, 0)).len(), 3 * 4);
+    }
+
+    #[test]
+    fn test_chunk_collision_shapes() {
+        let tile_set = tile_set();
+        let mut tile_map = TileMapBuilder::new(BaseBuilder::new())
+            .with_tile_size(Vector2::new(0.5, 0.5))
+            .build_tile_map();
+
+        // Solid tiles must be merged into a single rectangle.
+        tile_map.fill_rect(Rect::new(0, 0, 4, 2), Some(0));
+        // Tiles without colliders must be ignored.
+        tile_map.set_tile(Vector2::new(4, 0), Some(1));
+        tile_map.set_tile(Vector2::new(5, 0), Some(2));
+        // Tiles from other chunks must be ignored too.
+        tile_map.set_tile(Vector2::new(-1, 0), Some(0));
+
+        let shapes = tile_map.chunk_collision_shapes(Vector2::new(0, 0), &tile_set);
+        assert!(shapes.polygons.is_empty());
+        assert_eq!(shapes.rectangles.len(), 2);
+        assert!(shapes.rectangles.contains(&Rect::new(0.0, 0.0, 2.0, 1.0)));
+        assert!(shapes.rectangles.contains(&Rect::new(2.5, 0.0, 0.5, 0.25)));
+    }
+
+    #[test]
+    fn test_tile_map_collision() {
+        let mut graph = Graph::new();
+
+        let mut builder = TileMapBuilder::new(BaseBuilder::new())
+            .with_tile_set(TileSetResource::new_ok(tile_set()));
+        for x in -40..40 {
+            builder = builder.with_tile(Vector2::new(x, -1), 0);
+        }
+        let tile_map = builder.build(&mut graph);
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.25))
+            .build(&mut graph);
+        let ball = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 2.0, 0.0))
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .build(&mut graph);
+
+        for _ in 0..180 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        // 80 tiles from -40 to 39 span over 6 chunks (from -3 to 2).
+        assert_eq!(
+            graph[tile_map]
+                .cast::<TileMap>()
+                .unwrap()
+                .native_colliders
+                .lock()
+                .len(),
+            6
+        );
+
+        let y = graph[ball].global_position().y;
+        assert!(
+            (y - 0.25).abs() < 0.05,
+            "ball must rest on the tiles, y = {y}"
+        );
+
+        // Removed tiles must not collide anymore.
+        graph[tile_map]
+            .cast_mut::<TileMap>()
+            .unwrap()
+            .fill_rect(Rect::new(-40, -1, 80, 1), None);
+
+        for _ in 0..60 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        assert!(graph[tile_map]
+            .cast::<TileMap>()
+            .unwrap()
+            .native_colliders
+            .lock()
+            .is_empty());
+        assert!(graph[ball].global_position().y < 0.0);
+    }
+}
@@ -38,6 +38,7 @@ use crate::{
     resource::model::{ModelResource, ModelResourceExtension, NodeMapping},
     scene::{
         self,
+        accel::{is_aabb_bounded, DynamicBvh},
         base::NodeScriptMessage,
         camera::Camera,
         dim2::{self},
@@ -88,6 +89,9 @@ pub struct GraphPerformanceStatistics {
 
     /// A time which was required to render sounds.
     pub sound_update_time: Duration,
+
+    /// Amount of time that was needed to update the spatial index of the graph.
+    pub spatial_index_time: Duration,
 }
 
 impl GraphPerformanceStatistics {
@@ -98,6 +102,7 @@ impl GraphPerformanceStatistics {
             + self.physics.total()
             + self.physics2d.total()
             + self.sound_update_time
+            + self.spatial_index_time
     }
 }
 
@@ -115,6 +120,9 @@ pub struct Graph {
     #[reflect(hidden)]
     stack: Vec<Handle<Node>>,
 
+    #[reflect(hidden)]
+    spatial_index: DynamicBvh<Handle<Node>>,
+
     /// Backing physics "world". It is responsible for the physics simulation.
     pub physics: PhysicsWorld,
 
@@ -149,6 +157,7 @@ impl Default for Graph {
             root: Handle::NONE,
             pool: Pool::new(),
             stack: Vec::new(),
+            spatial_index: Default::default(),
             sound_context: Default::default(),
             performance_statistics: Default::default(),
             event_broadcaster: Default::default(),
@@ -257,6 +266,7 @@ impl Graph {
         Self {
             physics: Default::default(),
             stack: Vec::new(),
+            spatial_index: Default::default(),
             root,
             pool,
             physics2d: Default::default(),
@@ -463,6 +473,8 @@ impl Graph {
             let mut node = self.pool.free(handle);
             node.on_removed_from_graph(self);
 
+            self.spatial_index.remove(&handle);
+
             self.event_broadcaster
                 .broadcast(GraphEvent::Removed(handle));
         }
@@ -1258,6 +1270,67 @@ impl Graph {
                 );
             }
         }
+
+        let last_time = instant::Instant::now();
+        self.update_spatial_index();
+        self.performance_statistics.spatial_index_time = instant::Instant::now() - last_time;
+    }
+
+    /// Synchronizes the spatial index of the graph with world bounding boxes of the nodes. Only the nodes,
+    /// that left enlarged bounding boxes of their entries, are moved in the index, so the update is cheap
+    /// for mostly static scenes. Normally you don't need to call this method directly, it is called
+    /// automatically at the end of [`Self::update`]. However, it could be useful if you need to make
+    /// spatial queries for a hierarchy, that was just created, before entering update loop (call
+    /// [`Self::update_hierarchical_data`] first).
+    pub fn update_spatial_index(&mut self) {
+        let mut bounded_count = 0;
+        for (handle, node) in self.pool.pair_iter() {
+            let aabb = node.world_bounding_box();
+            if is_aabb_bounded(&aabb) {
+                self.spatial_index.insert(handle, aabb);
+                bounded_count += 1;
+            } else {
+                self.spatial_index.remove(&handle);
+            }
+        }
+
+        // Nodes could be extracted from the graph without removal (see `take_reserve`), their entries
+        // must be removed too.
+        if self.spatial_index.len() > bounded_count {
+            let pool = &self.pool;
+            self.spatial_index
+                .retain(|handle| pool.is_valid_handle(*handle));
+        }
+    }
+
+    /// Returns a reference to the spatial index of the graph. It is a dynamic bounding volume hierarchy
+    /// of world bounding boxes of the nodes, that could be used to quickly find nodes in some region of
+    /// space (frustum, bounding box or along a ray). Nodes without volume (pivots, cameras, etc.) are not
+    /// included in the index. The index is updated at the end of [`Self::update`], so it may contain
+    /// handles of the nodes that were removed or extracted from the graph after the last update.
+    ///
+    /// ```rust
+    /// use fyrox::{
+    ///     core::{algebra::Vector3, math::aabb::AxisAlignedBoundingBox},
+    ///     scene::graph::Graph,
+    /// };
+    ///
+    /// fn nodes_around(graph: &Graph, position: Vector3<f32>) -> Vec<&str> {
+    ///     let region = AxisAlignedBoundingBox::from_min_max(
+    ///         position - Vector3::repeat(10.0),
+    ///         position + Vector3::repeat(10.0),
+    ///     );
+    ///     let mut nodes = Vec::new();
+    ///     graph.spatial_index().aabb_query(&region, &mut nodes);
+    ///     nodes
+    ///         .into_iter()
+    ///         .filter_map(|handle| graph.try_get(handle))
+    ///         .map(|node| node.name())
+    ///         .collect()
+    /// }
+    /// ```
+    pub fn spatial_index(&self) -> &DynamicBvh<Handle<Node>> {
+        &self.spatial_index
     }
 
     /// Returns capacity of internal pool. Can be used to iterate over all **potentially**
@@ -1732,8 +1805,14 @@ mod test {
     use crate::scene::base::BaseBuilder;
     use crate::scene::pivot::PivotBuilder;
     use crate::{
-        core::pool::Handle,
-        scene::{graph::Graph, node::Node, pivot::Pivot},
+        core::{algebra::Vector3, math::aabb::AxisAlignedBoundingBox, pool::Handle},
+        scene::{
+            graph::Graph,
+            light::{point::PointLightBuilder, BaseLightBuilder},
+            node::Node,
+            pivot::Pivot,
+            transform::TransformBuilder,
+        },
     };
 
     #[test]
@@ -1862,4 +1941,63 @@ mod test {
 
         assert!(graph[b].children.is_empty());
     }
+
+    #[test]
+    fn test_spatial_index() {
+        let mut graph = Graph::new();
+
+        let make_light = |graph: &mut Graph, position: Vector3<f32>| {
+            PointLightBuilder::new(BaseLightBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .build(),
+                ),
+            ))
+            .build(graph)
+        };
+
+        let a = make_light(&mut graph, Vector3::new(0.0, 0.0, 0.0));
+        let b = make_light(&mut graph, Vector3::new(10.0, 0.0, 0.0));
+        let pivot = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        graph.update_hierarchical_data();
+        graph.update_spatial_index();
+
+        // Nodes without volume must not be in the index.
+        assert_eq!(graph.spatial_index().len(), 2);
+        assert!(!graph.spatial_index().contains(&pivot));
+        assert!(!graph.spatial_index().contains(&graph.root));
+
+        let query = |graph: &Graph, min: Vector3<f32>, max: Vector3<f32>| {
+            let mut result = Vec::new();
+            graph
+                .spatial_index()
+                .aabb_query(&AxisAlignedBoundingBox::from_min_max(min, max), &mut result);
+            result
+        };
+
+        let near_origin = (Vector3::repeat(-1.0), Vector3::repeat(1.0));
+        assert_eq!(query(&graph, near_origin.0, near_origin.1), vec![a]);
+
+        // Move `b` close to `a` and check that the index follows it.
+        graph[b]
+            .local_transform_mut()
+            .set_position(Vector3::new(0.5, 0.0, 0.0));
+        graph.update_hierarchical_data();
+        graph.update_spatial_index();
+        let mut result = query(&graph, near_origin.0, near_origin.1);
+        result.sort_by_key(|h| h.index());
+        assert_eq!(result, vec![a, b]);
+        assert!(query(
+            &graph,
+            Vector3::new(9.0, -1.0, -1.0),
+            Vector3::new(11.0, 1.0, 1.0)
+        )
+        .is_empty());
+
+        graph.remove_node(a);
+        assert_eq!(query(&graph, near_origin.0, near_origin.1), vec![b]);
+        assert_eq!(graph.spatial_index().len(), 1);
+    }
 }
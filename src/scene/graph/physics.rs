@@ -19,6 +19,9 @@ use crate::{
     },
     scene::{
         self,
+        character_controller::{
+            CharacterContact, CharacterContactKind, CharacterController, CharacterMovementReport,
+        },
         collider::{self, ColliderShape, GeometrySource},
         debug::SceneDrawingContext,
         graph::{isometric_global_transform, NodePool},
@@ -33,7 +36,9 @@ use crate::{
     },
     utils::raw_mesh::{RawMeshBuilder, RawVertex},
 };
+use fxhash::FxHashMap;
 use rapier3d::{
+    control::{CharacterAutostep, CharacterLength, KinematicCharacterController},
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
         IslandManager, JointAxesMask, MultibodyJointHandle, MultibodyJointSet, RigidBody,
@@ -43,11 +48,12 @@ use rapier3d::{
         BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid,
         InteractionGroups, NarrowPhase, Ray, SharedShape,
     },
-    pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter, QueryPipeline},
+    parry::query::TOIStatus,
+    pipeline::{ActiveEvents, DebugRenderPipeline, PhysicsPipeline, QueryFilter, QueryPipeline},
     prelude::JointAxis,
 };
 use std::{
-    cell::{Cell, RefCell},
+    cell::{Cell, RefCell, RefMut},
     cmp::Ordering,
     fmt::{Debug, Formatter},
     hash::Hash,
@@ -90,6 +96,138 @@ impl From<rapier2d::geometry::FeatureId> for FeatureId {
     }
 }
 
+/// Kind of a collision event.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum CollisionEventKind {
+    /// Two regular colliders started touching each other.
+    ContactStarted,
+    /// Two regular colliders stopped touching each other.
+    ContactStopped,
+    /// A collider started intersecting with a sensor collider.
+    IntersectionStarted,
+    /// A collider stopped intersecting with a sensor collider.
+    IntersectionStopped,
+}
+
+/// Collision event is emitted when two colliders start or stop touching (or intersecting in case of
+/// sensors) each other. Physics worlds collect such events during every simulation step, see
+/// [`PhysicsWorld::collision_events`] and [`crate::scene::dim2::physics::PhysicsWorld::collision_events`].
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CollisionEvent {
+    /// Kind of the event.
+    pub kind: CollisionEventKind,
+    /// Handle of the first collider node involved in the event.
+    pub collider1: Handle<Node>,
+    /// Handle of the second collider node involved in the event.
+    pub collider2: Handle<Node>,
+    /// `true` if the event was emitted because one of the colliders was removed (or disabled).
+    /// Keep in mind, that handle of such collider node could be already invalid.
+    pub removed: bool,
+}
+
+impl CollisionEvent {
+    /// Returns `true` if the colliders started touching (or intersecting) each other.
+    pub fn is_started(&self) -> bool {
+        matches!(
+            self.kind,
+            CollisionEventKind::ContactStarted | CollisionEventKind::IntersectionStarted
+        )
+    }
+
+    /// Returns `true` if the event involves a sensor collider.
+    pub fn is_intersection(&self) -> bool {
+        matches!(
+            self.kind,
+            CollisionEventKind::IntersectionStarted | CollisionEventKind::IntersectionStopped
+        )
+    }
+
+    /// Returns `true` if the given collider is involved in the event.
+    pub fn involves(&self, collider: Handle<Node>) -> bool {
+        self.collider1 == collider || self.collider2 == collider
+    }
+
+    /// Returns the other collider involved in the event, if the given collider is involved in the event.
+    pub fn other(&self, collider: Handle<Node>) -> Option<Handle<Node>> {
+        if self.collider1 == collider {
+            Some(self.collider2)
+        } else if self.collider2 == collider {
+            Some(self.collider1)
+        } else {
+            None
+        }
+    }
+}
+
+/// Collects native collision events during a simulation step. Physics pipeline requires event
+/// handler to be `Send + Sync`, so the events are stored behind a mutex.
+pub(crate) struct CollisionEventCollector<E> {
+    pub(crate) events: Mutex<Vec<E>>,
+}
+
+impl<E> Default for CollisionEventCollector<E> {
+    fn default() -> Self {
+        Self {
+            events: Default::default(),
+        }
+    }
+}
+
+macro_rules! define_collision_event_conversion {
+    ($rapier:ident) => {
+        impl $rapier::pipeline::EventHandler
+            for CollisionEventCollector<$rapier::geometry::CollisionEvent>
+        {
+            fn handle_collision_event(
+                &self,
+                _bodies: &$rapier::dynamics::RigidBodySet,
+                _colliders: &$rapier::geometry::ColliderSet,
+                event: $rapier::geometry::CollisionEvent,
+                _contact_pair: Option<&$rapier::geometry::ContactPair>,
+            ) {
+                self.events.lock().push(event);
+            }
+
+            fn handle_contact_force_event(
+                &self,
+                _dt: f32,
+                _bodies: &$rapier::dynamics::RigidBodySet,
+                _colliders: &$rapier::geometry::ColliderSet,
+                _contact_pair: &$rapier::geometry::ContactPair,
+                _total_force_magnitude: f32,
+            ) {
+            }
+        }
+
+        impl CollisionEventCollector<$rapier::geometry::CollisionEvent> {
+            /// Converts collected native events into collision events with node handles and clears
+            /// the collector.
+            pub(crate) fn drain_into<F>(&self, events: &mut Vec<CollisionEvent>, node_of: F)
+            where
+                F: Fn($rapier::geometry::ColliderHandle) -> Handle<Node>,
+            {
+                events.extend(self.events.lock().drain(..).map(|event| {
+                    let kind = match (event.started(), event.sensor()) {
+                        (true, false) => CollisionEventKind::ContactStarted,
+                        (false, false) => CollisionEventKind::ContactStopped,
+                        (true, true) => CollisionEventKind::IntersectionStarted,
+                        (false, true) => CollisionEventKind::IntersectionStopped,
+                    };
+                    CollisionEvent {
+                        kind,
+                        collider1: node_of(event.collider1()),
+                        collider2: node_of(event.collider2()),
+                        removed: event.removed(),
+                    }
+                }));
+            }
+        }
+    };
+}
+
+define_collision_event_conversion!(rapier2d);
+define_collision_event_conversion!(rapier3d);
+
 /// Rules used to combine two coefficients.
 ///
 /// # Notes
@@ -329,6 +467,81 @@ pub struct IntersectionPair {
     pub has_any_active_contact: bool,
 }
 
+/// A set of options for the shape cast.
+pub struct ShapeCastOptions {
+    /// A shape to cast. Only primitive shapes are supported (the ones that do not use geometry
+    /// sources), so trimesh, heightfield and polyhedron shapes will be ignored.
+    pub shape: ColliderShape,
+
+    /// Initial position of the shape in world coordinates.
+    pub shape_position: Vector3<f32>,
+
+    /// Rotation of the shape in world coordinates.
+    pub shape_rotation: UnitQuaternion<f32>,
+
+    /// A direction of the cast. Can be non-normalized.
+    pub direction: Vector3<f32>,
+
+    /// Maximum distance of cast.
+    pub max_len: f32,
+
+    /// Groups to check.
+    pub groups: collider::InteractionGroups,
+
+    /// If `true`, the cast will stop at a collider that the shape already intersects at its initial
+    /// position. Otherwise such colliders will be hit only if the shape moves deeper into them.
+    pub stop_at_penetration: bool,
+}
+
+/// A result of the shape cast.
+#[derive(Debug, Clone)]
+pub struct ShapeCastResult {
+    /// A handle of the collider that was hit by the shape.
+    pub collider: Handle<Node>,
+
+    /// Distance that the shape has traveled along the cast direction before the hit.
+    pub toi: f32,
+
+    /// A position of the contact point on the hit collider in world coordinates.
+    pub position: Point3<f32>,
+
+    /// A normal of the hit collider at the contact point in world coordinates.
+    pub normal: Vector3<f32>,
+
+    /// `true` if the shape already intersected the collider at its initial position. In this case
+    /// `toi` is zero and the position and the normal are undefined.
+    pub penetrating: bool,
+}
+
+/// A set of options for the shape intersection query.
+pub struct ShapeIntersectionOptions {
+    /// A shape to test. Only primitive shapes are supported (the ones that do not use geometry
+    /// sources), so trimesh, heightfield and polyhedron shapes will be ignored.
+    pub shape: ColliderShape,
+
+    /// A position of the shape in world coordinates.
+    pub shape_position: Vector3<f32>,
+
+    /// Rotation of the shape in world coordinates.
+    pub shape_rotation: UnitQuaternion<f32>,
+
+    /// Groups to check.
+    pub groups: collider::InteractionGroups,
+}
+
+/// A result of the point projection.
+#[derive(Debug, Clone)]
+pub struct PointProjection {
+    /// A handle of the collider on which the point was projected.
+    pub collider: Handle<Node>,
+
+    /// A projected point in world coordinates.
+    pub position: Point3<f32>,
+
+    /// Whether the point was inside the collider.
+    pub is_inside: bool,
+}
+
 pub(super) struct Container<S, A>
 where
     A: Hash + Eq + Clone,
@@ -347,6 +560,23 @@ fn convert_joint_params(
         JointParams::FixedJoint(_) => JointAxesMask::LOCKED_FIXED_AXES,
         JointParams::PrismaticJoint(_) => JointAxesMask::LOCKED_PRISMATIC_AXES,
         JointParams::RevoluteJoint(_) => JointAxesMask::LOCKED_REVOLUTE_AXES,
+        JointParams::SixDofJoint(ref v) => {
+            let mut mask = JointAxesMask::empty();
+            for (dof, axis) in [
+                (&v.linear_x, JointAxesMask::X),
+                (&v.linear_y, JointAxesMask::Y),
+                (&v.linear_z, JointAxesMask::Z),
+                (&v.angular_x, JointAxesMask::ANG_X),
+                (&v.angular_y, JointAxesMask::ANG_Y),
+                (&v.angular_z, JointAxesMask::ANG_Z),
+            ] {
+                if dof.locked {
+                    mask |= axis;
+                }
+            }
+            mask
+        }
+        JointParams::RopeJoint(_) => JointAxesMask::FREE_FIXED_AXES,
     };
 
     let mut joint = GenericJointBuilder::new(locked_axis)
@@ -374,23 +604,70 @@ fn convert_joint_params(
                     [v.z_limits_angles.start, v.z_limits_angles.end],
                 );
             }
+            set_joint_motor(&mut joint, JointAxis::AngX, &v.x_motor);
+            set_joint_motor(&mut joint, JointAxis::AngY, &v.y_motor);
+            set_joint_motor(&mut joint, JointAxis::AngZ, &v.z_motor);
         }
         scene::joint::JointParams::FixedJoint(_) => {}
         scene::joint::JointParams::PrismaticJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::X, [v.limits.start, v.limits.end]);
             }
+            set_joint_motor(&mut joint, JointAxis::X, &v.motor);
         }
         scene::joint::JointParams::RevoluteJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::AngX, [v.limits.start, v.limits.end]);
             }
+            set_joint_motor(&mut joint, JointAxis::AngX, &v.motor);
+        }
+        scene::joint::JointParams::SixDofJoint(v) => {
+            for (dof, axis) in [
+                (&v.linear_x, JointAxis::X),
+                (&v.linear_y, JointAxis::Y),
+                (&v.linear_z, JointAxis::Z),
+                (&v.angular_x, JointAxis::AngX),
+                (&v.angular_y, JointAxis::AngY),
+                (&v.angular_z, JointAxis::AngZ),
+            ] {
+                if !dof.locked {
+                    if dof.limits_enabled {
+                        joint.set_limits(axis, [dof.limits.start, dof.limits.end]);
+                    }
+                    set_joint_motor(&mut joint, axis, &dof.motor);
+                }
+            }
+        }
+        scene::joint::JointParams::RopeJoint(v) => {
+            // The distance between the anchors is limited by coupling all linear axes. The solver
+            // takes the length of the vector of per-axis limits as the distance limit, so the
+            // per-axis limit must be scaled accordingly.
+            joint.coupled_axes = JointAxesMask::LIN_AXES;
+            let limit = v.max_distance / 3.0f32.sqrt();
+            for axis in [JointAxis::X, JointAxis::Y, JointAxis::Z] {
+                joint.set_limits(axis, [0.0, limit]);
+            }
         }
     }
 
     joint
 }
 
+fn set_joint_motor(joint: &mut GenericJoint, axis: JointAxis, motor: &scene::joint::JointMotor) {
+    if motor.enabled {
+        joint
+            .set_motor(
+                axis,
+                motor.target_position,
+                motor.target_velocity,
+                motor.stiffness,
+                motor.damping,
+            )
+            .set_motor_max_force(axis, motor.max_force)
+            .set_motor_model(axis, motor.model.into());
+    }
+}
+
 /// Creates new trimesh collider shape from given mesh node. It also bakes scale into
 /// vertices of trimesh because rapier does not support collider scaling yet.
 fn make_trimesh(
@@ -615,13 +892,9 @@ fn make_heightfield(terrain: &Terrain) -> SharedShape {
     )
 }
 
-// Converts descriptor in a shared shape.
-fn collider_shape_into_native_shape(
-    shape: &ColliderShape,
-    owner_inv_global_transform: Matrix4<f32>,
-    owner_collider: Handle<Node>,
-    pool: &NodePool,
-) -> Option<SharedShape> {
+// Converts descriptor of a primitive shape (the one that does not need any geometry sources) in a
+// shared shape.
+fn primitive_shape_into_native_shape(shape: &ColliderShape) -> Option<SharedShape> {
     match shape {
         ColliderShape::Ball(ball) => Some(SharedShape::ball(ball.radius)),
 
@@ -646,6 +919,20 @@ fn collider_shape_into_native_shape(
             Point3::from(triangle.b),
             Point3::from(triangle.c),
         )),
+        ColliderShape::Trimesh(_)
+        | ColliderShape::Heightfield(_)
+        | ColliderShape::Polyhedron(_) => None,
+    }
+}
+
+// Converts descriptor in a shared shape.
+fn collider_shape_into_native_shape(
+    shape: &ColliderShape,
+    owner_inv_global_transform: Matrix4<f32>,
+    owner_collider: Handle<Node>,
+    pool: &NodePool,
+) -> Option<SharedShape> {
+    match shape {
         ColliderShape::Trimesh(trimesh) => {
             if trimesh.sources.is_empty() {
                 None
@@ -666,6 +953,7 @@ fn collider_shape_into_native_shape(
             .try_borrow(polyhedron.geometry_source.0)
             .and_then(|n| n.cast::<Mesh>())
             .map(|mesh| make_polyhedron_shape(owner_inv_global_transform, mesh)),
+        _ => primitive_shape_into_native_shape(shape),
     }
 }
 
@@ -884,10 +1172,19 @@ pub struct PhysicsWorld {
     #[visit(skip)]
     #[reflect(hidden)]
     multibody_joints: Container<MultibodyJointSet, MultibodyJointHandle>,
-    // Event handler collects info about contacts and proximity events.
+    // Event collector gathers native collision events during simulation step.
     #[visit(skip)]
     #[reflect(hidden)]
-    event_handler: Box<dyn EventHandler>,
+    event_collector: CollisionEventCollector<rapier3d::geometry::CollisionEvent>,
+    // Collision events of the last simulation step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
+    // Owners of the colliders that were removed since last simulation step. The physics pipeline
+    // emits "stopped" events for such colliders during the next step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    removed_colliders: FxHashMap<ColliderHandle, Handle<Node>>,
     #[visit(skip)]
     #[reflect(hidden)]
     query: RefCell<QueryPipeline>,
@@ -908,6 +1205,17 @@ fn isometry_from_global_transform(transform: &Matrix4<f32>) -> Isometry3<f32> {
     }
 }
 
+// Returns a handle of a native rigid body of a node that can have colliders attached to it (a
+// rigid body or a character controller).
+fn native_body_of(node: &Node) -> Option<RigidBodyHandle> {
+    if let Some(rigid_body) = node.cast::<scene::rigidbody::RigidBody>() {
+        Some(rigid_body.native.get())
+    } else {
+        node.cast::<CharacterController>()
+            .map(|controller| controller.native.get())
+    }
+}
+
 fn calculate_local_frames(
     joint: &dyn NodeTrait,
     body1: &dyn NodeTrait,
@@ -925,6 +1233,13 @@ fn u32_to_group(v: u32) -> rapier3d::geometry::Group {
     rapier3d::geometry::Group::from_bits(v).unwrap_or_else(rapier3d::geometry::Group::all)
 }
 
+fn query_filter<'a>(groups: collider::InteractionGroups) -> QueryFilter<'a> {
+    QueryFilter::new().groups(InteractionGroups::new(
+        u32_to_group(groups.memberships.0),
+        u32_to_group(groups.filter.0),
+    ))
+}
+
 impl PhysicsWorld {
     /// Creates a new instance of the physics world.
     pub(super) fn new() -> Self {
@@ -953,7 +1268,9 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_collector: Default::default(),
+            collision_events: Default::default(),
+            removed_colliders: Default::default(),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
@@ -963,6 +1280,8 @@ impl PhysicsWorld {
     pub(super) fn update(&mut self, dt: f32) {
         let time = instant::Instant::now();
 
+        self.collision_events.clear();
+
         if self.enabled {
             let integration_parameters = rapier3d::dynamics::IntegrationParameters {
                 dt: self.integration_parameters.dt.unwrap_or(dt),
@@ -1006,8 +1325,20 @@ impl PhysicsWorld {
                 // so we keep updating it manually.
                 None,
                 &(),
-                &*self.event_handler,
+                &self.event_collector,
             );
+
+            let colliders = &self.colliders.map;
+            let removed_colliders = &self.removed_colliders;
+            self.event_collector
+                .drain_into(&mut self.collision_events, |collider| {
+                    colliders
+                        .value_of(&collider)
+                        .or_else(|| removed_colliders.get(&collider))
+                        .cloned()
+                        .unwrap_or_default()
+                });
+            self.removed_colliders.clear();
         }
 
         self.performance_statistics.step_time += instant::Instant::now() - time;
@@ -1058,7 +1389,10 @@ impl PhysicsWorld {
             .remove(handle, &mut self.islands, &mut self.bodies.set, false)
             .is_some()
         {
-            assert!(self.colliders.map.remove_by_key(&handle).is_some());
+            let owner = self.colliders.map.remove_by_key(&handle);
+            assert!(owner.is_some());
+            self.removed_colliders
+                .extend(owner.map(|owner| (handle, owner)));
             true
         } else {
             false
@@ -1083,6 +1417,39 @@ impl PhysicsWorld {
         }
     }
 
+    /// Returns collision events (started or stopped contacts and intersections) that happened
+    /// during the last simulation step. The events are available until the next step, which means
+    /// that scripts could read them in their `on_update` method:
+    ///
+    /// ```rust
+    /// # use fyrox::{
+    /// #     core::pool::Handle,
+    /// #     scene::{graph::physics::CollisionEventKind, node::Node},
+    /// #     script::ScriptContext,
+    /// # };
+    /// fn on_update(context: &mut ScriptContext, collider: Handle<Node>) {
+    ///     for event in context.scene.graph.physics.collision_events_with(collider) {
+    ///         if event.kind == CollisionEventKind::IntersectionStarted {
+    ///             println!("{:?} entered the trigger", event.other(collider));
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
+    /// Returns an iterator over collision events of the last simulation step that involve the given
+    /// collider node.
+    pub fn collision_events_with(
+        &self,
+        collider: Handle<Node>,
+    ) -> impl Iterator<Item = &CollisionEvent> + '_ {
+        self.collision_events
+            .iter()
+            .filter(move |event| event.involves(collider))
+    }
+
     /// Draws physics world. Very useful for debugging, it allows you to see where are
     /// rigid bodies, which colliders they have and so on.
     pub fn draw(&self, context: &mut SceneDrawingContext) {
@@ -1096,10 +1463,7 @@ impl PhysicsWorld {
         );
     }
 
-    /// Casts a ray with given options.
-    pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
-        let time = instant::Instant::now();
-
+    fn updated_query(&self) -> RefMut<QueryPipeline> {
         let mut query = self.query.borrow_mut();
 
         // TODO: Ideally this must be called once per frame, but it seems to be impossible because
@@ -1109,6 +1473,15 @@ impl PhysicsWorld {
         // of the frame.
         query.update(&self.bodies.set, &self.colliders.set);
 
+        query
+    }
+
+    /// Casts a ray with given options.
+    pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
+        let time = instant::Instant::now();
+
+        let query = self.updated_query();
+
         query_buffer.clear();
         let ray = Ray::new(
             opts.ray_origin,
@@ -1122,10 +1495,7 @@ impl PhysicsWorld {
             &ray,
             opts.max_len,
             true,
-            QueryFilter::new().groups(InteractionGroups::new(
-                u32_to_group(opts.groups.memberships.0),
-                u32_to_group(opts.groups.filter.0),
-            )),
+            query_filter(opts.groups),
             |handle, intersection| {
                 query_buffer.push(Intersection {
                     collider: self.colliders.map.value_of(&handle).cloned().unwrap(),
@@ -1154,12 +1524,141 @@ impl PhysicsWorld {
         );
     }
 
+    /// Moves a shape along the given direction and returns the first collider that was hit by the
+    /// shape (if any). Could be used for sweep tests, for example to check whether a character could
+    /// move to some position.
+    pub fn cast_shape(&self, opts: ShapeCastOptions) -> Option<ShapeCastResult> {
+        let shape = primitive_shape_into_native_shape(&opts.shape)?;
+        let direction = opts.direction.try_normalize(f32::EPSILON)?;
+        let shape_isometry = Isometry3 {
+            translation: Translation3::from(opts.shape_position),
+            rotation: opts.shape_rotation,
+        };
+
+        let query = self.updated_query();
+
+        query
+            .cast_shape(
+                &self.bodies.set,
+                &self.colliders.set,
+                &shape_isometry,
+                &direction,
+                &*shape,
+                opts.max_len,
+                opts.stop_at_penetration,
+                query_filter(opts.groups),
+            )
+            .and_then(|(handle, toi)| {
+                // Witness and normal 1 refer to the hit collider and are already in world space.
+                Some(ShapeCastResult {
+                    collider: self.colliders.map.value_of(&handle).cloned()?,
+                    toi: toi.toi,
+                    position: toi.witness1,
+                    normal: toi.normal1.into_inner(),
+                    penetrating: toi.status == TOIStatus::Penetrating,
+                })
+            })
+    }
+
+    /// Searches for every collider that intersects the given shape and writes their handles in the
+    /// given buffer. The buffer is cleared before the query.
+    pub fn intersections_with_shape(
+        &self,
+        opts: ShapeIntersectionOptions,
+        query_buffer: &mut Vec<Handle<Node>>,
+    ) {
+        query_buffer.clear();
+
+        let shape = match primitive_shape_into_native_shape(&opts.shape) {
+            Some(shape) => shape,
+            None => return,
+        };
+        let shape_isometry = Isometry3 {
+            translation: Translation3::from(opts.shape_position),
+            rotation: opts.shape_rotation,
+        };
+
+        let query = self.updated_query();
+
+        query.intersections_with_shape(
+            &self.bodies.set,
+            &self.colliders.set,
+            &shape_isometry,
+            &*shape,
+            query_filter(opts.groups),
+            |handle| {
+                if let Some(collider) = self.colliders.map.value_of(&handle) {
+                    query_buffer.push(*collider);
+                }
+                true
+            },
+        );
+    }
+
+    /// Searches for every collider that contains the given point (in world coordinates) and writes
+    /// their handles in the given buffer. The buffer is cleared before the query.
+    pub fn intersections_with_point(
+        &self,
+        point: Point3<f32>,
+        groups: collider::InteractionGroups,
+        query_buffer: &mut Vec<Handle<Node>>,
+    ) {
+        query_buffer.clear();
+
+        let query = self.updated_query();
+
+        query.intersections_with_point(
+            &self.bodies.set,
+            &self.colliders.set,
+            &point,
+            query_filter(groups),
+            |handle| {
+                if let Some(collider) = self.colliders.map.value_of(&handle) {
+                    query_buffer.push(*collider);
+                }
+                true
+            },
+        );
+    }
+
+    /// Projects a point (in world coordinates) on the closest collider. If `solid` is `true`, then
+    /// colliders are treated as solid objects and a point inside a collider will be "projected" on
+    /// itself. Otherwise the point will be projected on the boundary of the collider.
+    pub fn project_point(
+        &self,
+        point: Point3<f32>,
+        solid: bool,
+        groups: collider::InteractionGroups,
+    ) -> Option<PointProjection> {
+        let query = self.updated_query();
+
+        query
+            .project_point(
+                &self.bodies.set,
+                &self.colliders.set,
+                &point,
+                solid,
+                query_filter(groups),
+            )
+            .and_then(|(handle, projection)| {
+                Some(PointProjection {
+                    collider: self.colliders.map.value_of(&handle).cloned()?,
+                    position: projection.point,
+                    is_inside: projection.is_inside,
+                })
+            })
+    }
+
     pub(crate) fn set_rigid_body_position(
         &mut self,
         rigid_body: &scene::rigidbody::RigidBody,
         new_global_transform: &Matrix4<f32>,
     ) {
-        if let Some(native) = self.bodies.set.get_mut(rigid_body.native.get()) {
+        self.set_body_position(rigid_body.native.get(), new_global_transform)
+    }
+
+    fn set_body_position(&mut self, handle: RigidBodyHandle, new_global_transform: &Matrix4<f32>) {
+        if let Some(native) = self.bodies.set.get_mut(handle) {
             native.set_position(
                 isometry_from_global_transform(new_global_transform),
                 // Do not wake up body, it is too expensive and must be done **only** by explicit
@@ -1370,6 +1869,191 @@ impl PhysicsWorld {
         }
     }
 
+    pub(crate) fn set_character_controller_position(
+        &mut self,
+        controller: &CharacterController,
+        new_global_transform: &Matrix4<f32>,
+    ) {
+        if let Some(native) = self.bodies.set.get(controller.native.get()) {
+            // The controller moves its body by setting next kinematic position on each update, the
+            // body must be teleported only if the node was moved by something else.
+            let next_position = native.next_position();
+            let new_position = isometry_from_global_transform(new_global_transform);
+            if (next_position.translation.vector - new_position.translation.vector).norm()
+                <= f32::EPSILON
+                && next_position.rotation.angle_to(&new_position.rotation) <= f32::EPSILON
+            {
+                return;
+            }
+        }
+
+        self.set_body_position(controller.native.get(), new_global_transform)
+    }
+
+    pub(crate) fn sync_to_character_controller_node(
+        &mut self,
+        handle: Handle<Node>,
+        controller: &CharacterController,
+    ) {
+        if !controller.is_globally_enabled() {
+            self.remove_body(controller.native.get());
+            controller.native.set(Default::default());
+            return;
+        }
+
+        if controller.native.get() == RigidBodyHandle::invalid() {
+            // Character controller is backed by a kinematic body, its position is fully controlled
+            // by the controller itself. It must not sleep, otherwise its movement will be ignored.
+            let body = RigidBodyBuilder::kinematic_position_based()
+                .position(isometry_from_global_transform(
+                    &controller.global_transform(),
+                ))
+                .can_sleep(false)
+                .build();
+
+            controller.native.set(self.add_body(handle, body));
+
+            Log::writeln(
+                MessageKind::Information,
+                format!(
+                    "Native rigid body was created for character controller {}",
+                    controller.name()
+                ),
+            );
+        }
+    }
+
+    pub(crate) fn move_character(
+        &mut self,
+        nodes: &NodePool,
+        controller: &mut CharacterController,
+        dt: f32,
+        parent_transform: Matrix4<f32>,
+    ) {
+        let desired_translation = std::mem::take(&mut controller.desired_translation);
+
+        if !self.enabled {
+            return;
+        }
+
+        let body_handle = controller.native.get();
+
+        // The shape of the first collider is used as the shape of the character.
+        let collider = match controller.children().iter().find_map(|c| {
+            nodes
+                .try_borrow(*c)
+                .and_then(|n| n.cast::<scene::collider::Collider>())
+                .and_then(|c| self.colliders.set.get(c.native.get()))
+        }) {
+            Some(collider) => collider,
+            None => return,
+        };
+        let shape = collider.shared_shape().clone();
+        let character_position = *collider.position();
+        let filter = QueryFilter::new()
+            .exclude_rigid_body(body_handle)
+            .exclude_sensors()
+            .groups(collider.collision_groups());
+
+        let native_controller = KinematicCharacterController {
+            up: Vector3::y_axis(),
+            offset: CharacterLength::Absolute(controller.offset()),
+            slide: controller.is_slide(),
+            autostep: if controller.is_autostep_enabled() {
+                Some(CharacterAutostep {
+                    max_height: CharacterLength::Absolute(controller.autostep_max_height()),
+                    min_width: CharacterLength::Absolute(controller.autostep_min_width()),
+                    include_dynamic_bodies: controller.is_autostep_include_dynamic_bodies(),
+                })
+            } else {
+                None
+            },
+            max_slope_climb_angle: controller.max_slope_climb_angle(),
+            min_slope_slide_angle: controller.min_slope_slide_angle(),
+            snap_to_ground: if controller.is_snap_to_ground_enabled() {
+                Some(CharacterLength::Absolute(
+                    controller.snap_to_ground_distance(),
+                ))
+            } else {
+                None
+            },
+        };
+
+        let mut collisions = Vec::new();
+        let movement = native_controller.move_shape(
+            dt,
+            &self.bodies.set,
+            &self.colliders.set,
+            &self.updated_query(),
+            &*shape,
+            &character_position,
+            desired_translation,
+            filter,
+            |collision| collisions.push(collision),
+        );
+
+        if controller.is_push_dynamic_bodies() {
+            let query = self.query.borrow();
+            for collision in collisions.iter() {
+                native_controller.solve_character_collision_impulses(
+                    dt,
+                    &mut self.bodies.set,
+                    &self.colliders.set,
+                    &query,
+                    &*shape,
+                    controller.mass(),
+                    collision,
+                    filter,
+                );
+            }
+        }
+
+        let mut report = CharacterMovementReport {
+            translation: movement.translation,
+            grounded: movement.grounded,
+            ..Default::default()
+        };
+        let max_slope_cos = controller.max_slope_climb_angle().cos();
+        for collision in collisions.iter() {
+            if let Some(collider) = self.colliders.map.value_of(&collision.handle) {
+                // The normal is on the surface of the collider that was hit, it points towards
+                // the character.
+                let normal = *collision.toi.normal1;
+                let kind = if normal.y >= max_slope_cos {
+                    report.grounded = true;
+                    CharacterContactKind::Ground
+                } else if -normal.y >= max_slope_cos {
+                    report.ceiling = true;
+                    CharacterContactKind::Ceiling
+                } else {
+                    report.wall = true;
+                    CharacterContactKind::Wall
+                };
+                report.contacts.push(CharacterContact {
+                    collider: *collider,
+                    position: collision.toi.witness1.coords,
+                    normal,
+                    kind,
+                });
+            }
+        }
+        controller.movement_report = report;
+
+        if let Some(body) = self.bodies.set.get_mut(body_handle) {
+            let new_position = body.position().translation.vector + movement.translation;
+            body.set_next_kinematic_translation(new_position);
+
+            let local_position = parent_transform
+                .try_inverse()
+                .unwrap_or_else(Matrix4::identity)
+                .transform_point(&Point3::from(new_position))
+                .coords;
+            controller
+                .local_transform_mut()
+                .set_position(local_position);
+        }
+    }
+
     pub(crate) fn sync_to_collider_node(
         &mut self,
         nodes: &NodePool,
@@ -1444,15 +2128,14 @@ impl PhysicsWorld {
                         .try_sync_model(|v| native.set_restitution_combine_rule(v.into()));
                 }
             }
-        } else if let Some(parent_body) = nodes
+        } else if let Some(rigid_body_native) = nodes
             .try_borrow(collider_node.parent())
-            .and_then(|n| n.cast::<scene::rigidbody::RigidBody>())
+            .and_then(native_body_of)
         {
-            if parent_body.native.get() != RigidBodyHandle::invalid() {
+            if rigid_body_native != RigidBodyHandle::invalid() {
                 let inv_global_transform = isometric_global_transform(nodes, handle)
                     .try_inverse()
                     .unwrap();
-                let rigid_body_native = parent_body.native.get();
                 if let Some(shape) = collider_shape_into_native_shape(
                     collider_node.shape(),
                     inv_global_transform,
@@ -1478,7 +2161,8 @@ impl PhysicsWorld {
                             u32_to_group(collider_node.solver_groups().memberships.0),
                             u32_to_group(collider_node.solver_groups().filter.0),
                         ))
-                        .sensor(collider_node.is_sensor());
+                        .sensor(collider_node.is_sensor())
+                        .active_events(ActiveEvents::COLLISION_EVENTS);
 
                     if let Some(density) = collider_node.density() {
                         builder = builder.density(density);
@@ -1530,10 +2214,14 @@ impl PhysicsWorld {
                     native.body2 = rigid_body_node.native.get();
                 }
             });
+            let mut params_changed = false;
             joint.params.try_sync_model(|v| {
+                let contacts_enabled = native.data.contacts_enabled;
                 native.data =
                     // Preserve local frames.
-                    convert_joint_params(v, native.data.local_frame1, native.data.local_frame2)
+                    convert_joint_params(v, native.data.local_frame1, native.data.local_frame2);
+                native.data.set_contacts_enabled(contacts_enabled);
+                params_changed = true;
             });
             joint.contacts_enabled.try_sync_model(|v| {
                 native.data.set_contacts_enabled(v);
@@ -1550,9 +2238,20 @@ impl PhysicsWorld {
                     let (local_frame1, local_frame2) = calculate_local_frames(joint, body1, body2);
                     native.data =
                         convert_joint_params((*joint.params).clone(), local_frame1, local_frame2);
+                    native
+                        .data
+                        .set_contacts_enabled(joint.is_contacts_enabled());
                     joint.need_rebind.set(false);
                 }
             }
+            if params_changed {
+                // Motors and limits have no effect on sleeping bodies.
+                for body in [native.body1, native.body2] {
+                    if let Some(body) = self.bodies.set.get_mut(body) {
+                        body.wake_up(true);
+                    }
+                }
+            }
         } else {
             let body1_handle = joint.body1();
             let body2_handle = joint.body2();
@@ -27,6 +27,137 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+/// A model used by a joint motor to reach its target position and velocity.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Hash, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames,
+)]
+#[repr(u32)]
+pub enum JointMotorModel {
+    /// The stiffness and damping of the motor are automatically scaled to take into account the
+    /// masses of the attached bodies, so the motor behaves the same way for any masses.
+    AccelerationBased = 0,
+    /// The motor produces a force, its behaviour depends on the masses of the attached bodies.
+    ForceBased = 1,
+}
+
+impl Default for JointMotorModel {
+    fn default() -> Self {
+        Self::AccelerationBased
+    }
+}
+
+impl From<JointMotorModel> for rapier3d::dynamics::MotorModel {
+    fn from(v: JointMotorModel) -> Self {
+        match v {
+            JointMotorModel::AccelerationBased => rapier3d::dynamics::MotorModel::AccelerationBased,
+            JointMotorModel::ForceBased => rapier3d::dynamics::MotorModel::ForceBased,
+        }
+    }
+}
+
+impl From<JointMotorModel> for rapier2d::dynamics::MotorModel {
+    fn from(v: JointMotorModel) -> Self {
+        match v {
+            JointMotorModel::AccelerationBased => rapier2d::dynamics::MotorModel::AccelerationBased,
+            JointMotorModel::ForceBased => rapier2d::dynamics::MotorModel::ForceBased,
+        }
+    }
+}
+
+/// Joint motor drives a single degree of freedom of a joint towards the target position and
+/// velocity. The motor works as a spring-damper: `force = stiffness * (target_position - position)
+/// + damping * (target_velocity - velocity)`, so it could be used to make springs (non-zero
+/// stiffness) as well as velocity-driven motors (zero stiffness, non-zero damping).
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct JointMotor {
+    /// Whether the motor is enabled or not. Default is `false`
+    #[reflect(description = "Whether the motor is enabled or not.")]
+    pub enabled: bool,
+
+    /// Target position of the motor (in radians for angular degrees of freedom).
+    #[reflect(
+        description = "Target position of the motor (in radians for angular degrees of freedom)."
+    )]
+    pub target_position: f32,
+
+    /// Target velocity of the motor (in radians per second for angular degrees of freedom).
+    #[reflect(
+        description = "Target velocity of the motor (in radians per second for angular degrees of freedom)."
+    )]
+    pub target_velocity: f32,
+
+    /// Stiffness of the motor, defines how strong the motor tries to reach the target position.
+    #[reflect(
+        description = "Stiffness of the motor, defines how strong the motor tries to reach the target position."
+    )]
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub stiffness: f32,
+
+    /// Damping of the motor, defines how strong the motor tries to reach the target velocity.
+    #[reflect(
+        description = "Damping of the motor, defines how strong the motor tries to reach the target velocity."
+    )]
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub damping: f32,
+
+    /// The maximum force the motor can produce.
+    #[reflect(description = "The maximum force the motor can produce.")]
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub max_force: f32,
+
+    /// A model of the motor.
+    #[reflect(description = "A model of the motor.")]
+    pub model: JointMotorModel,
+}
+
+impl Default for JointMotor {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_position: 0.0,
+            target_velocity: 0.0,
+            stiffness: 0.0,
+            damping: 0.0,
+            max_force: f32::MAX,
+            model: Default::default(),
+        }
+    }
+}
+
+/// A single degree of freedom of a generic joint (see [`SixDofJoint`]).
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct JointDof {
+    /// Whether the degree of freedom is locked or not. Locked degree of freedom does not allow
+    /// any relative motion of the attached bodies. Default is `true`
+    #[reflect(description = "Whether the degree of freedom is locked or not.")]
+    pub locked: bool,
+
+    /// Whether limits of the degree of freedom are enabled or not. Default is `false`
+    #[reflect(description = "Whether limits of the degree of freedom are enabled or not.")]
+    pub limits_enabled: bool,
+
+    /// Allowed range of the degree of freedom (in radians for angular degrees of freedom).
+    #[reflect(
+        description = "Allowed range of the degree of freedom (in radians for angular degrees of freedom)."
+    )]
+    pub limits: Range<f32>,
+
+    /// A motor that drives the degree of freedom.
+    #[reflect(description = "A motor that drives the degree of freedom.")]
+    pub motor: JointMotor,
+}
+
+impl Default for JointDof {
+    fn default() -> Self {
+        Self {
+            locked: true,
+            limits_enabled: false,
+            limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
+        }
+    }
+}
+
 /// Ball joint locks any translational moves between two objects on the axis between objects, but
 /// allows rigid bodies to perform relative rotations. The real world example is a human shoulder,
 /// pendulum, etc.
@@ -61,6 +192,21 @@ pub struct BallJoint {
     #[reflect(description = "Allowed angle range around local Z axis of the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub z_limits_angles: Range<f32>,
+
+    /// A motor that drives rotation around local X axis of the joint.
+    #[reflect(description = "A motor that drives rotation around local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub x_motor: JointMotor,
+
+    /// A motor that drives rotation around local Y axis of the joint.
+    #[reflect(description = "A motor that drives rotation around local Y axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub y_motor: JointMotor,
+
+    /// A motor that drives rotation around local Z axis of the joint.
+    #[reflect(description = "A motor that drives rotation around local Z axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub z_motor: JointMotor,
 }
 
 impl Default for BallJoint {
@@ -72,6 +218,9 @@ impl Default for BallJoint {
             y_limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
             z_limits_enabled: false,
             z_limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
+            x_motor: Default::default(),
+            y_motor: Default::default(),
+            z_motor: Default::default(),
         }
     }
 }
@@ -96,6 +245,11 @@ pub struct PrismaticJoint {
     )]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// A motor that drives translation along local X axis of the joint.
+    #[reflect(description = "A motor that drives translation along local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for PrismaticJoint {
@@ -103,6 +257,7 @@ impl Default for PrismaticJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
@@ -123,6 +278,11 @@ pub struct RevoluteJoint {
     #[reflect(description = "Allowed angle range around local X axis of the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// A motor that drives rotation around local X axis of the joint.
+    #[reflect(description = "A motor that drives rotation around local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for RevoluteJoint {
@@ -130,10 +290,61 @@ impl Default for RevoluteJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
 
+/// Six degrees of freedom joint is the most generic joint, every degree of freedom of it (three
+/// translations and three rotations along local axes of the joint) could be locked, limited or
+/// driven by a motor individually. It could be used to create custom joints, that cannot be made
+/// using other joint types. By default all degrees of freedom are locked.
+#[derive(Clone, Debug, Default, Visit, PartialEq, Reflect)]
+pub struct SixDofJoint {
+    /// Translation along local X axis of the joint.
+    #[reflect(description = "Translation along local X axis of the joint.")]
+    pub linear_x: JointDof,
+
+    /// Translation along local Y axis of the joint.
+    #[reflect(description = "Translation along local Y axis of the joint.")]
+    pub linear_y: JointDof,
+
+    /// Translation along local Z axis of the joint.
+    #[reflect(description = "Translation along local Z axis of the joint.")]
+    pub linear_z: JointDof,
+
+    /// Rotation around local X axis of the joint.
+    #[reflect(description = "Rotation around local X axis of the joint.")]
+    pub angular_x: JointDof,
+
+    /// Rotation around local Y axis of the joint.
+    #[reflect(description = "Rotation around local Y axis of the joint.")]
+    pub angular_y: JointDof,
+
+    /// Rotation around local Z axis of the joint.
+    #[reflect(description = "Rotation around local Z axis of the joint.")]
+    pub angular_z: JointDof,
+}
+
+/// Rope joint limits the maximum distance between the anchor points of two rigid bodies, but allows
+/// them to move freely when the distance is less than the maximum. The anchor points coincide with
+/// the position of the joint at the moment of binding. The real world example is a rope or a chain.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct RopeJoint {
+    /// The maximum distance between the anchor points of the attached bodies.
+    #[reflect(
+        description = "The maximum distance between the anchor points of the attached bodies."
+    )]
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub max_distance: f32,
+}
+
+impl Default for RopeJoint {
+    fn default() -> Self {
+        Self { max_distance: 1.0 }
+    }
+}
+
 /// The exact kind of the joint.
 #[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames)]
 pub enum JointParams {
@@ -145,6 +356,10 @@ pub enum JointParams {
     PrismaticJoint(PrismaticJoint),
     /// See [`RevoluteJoint`] for more info.
     RevoluteJoint(RevoluteJoint),
+    /// See [`SixDofJoint`] for more info.
+    SixDofJoint(SixDofJoint),
+    /// See [`RopeJoint`] for more info.
+    RopeJoint(RopeJoint),
 }
 
 impl Default for JointParams {
@@ -417,3 +632,82 @@ impl JointBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::core::algebra::{Vector2, Vector3};
+    use crate::core::pool::Handle;
+    use crate::scene::{
+        base::BaseBuilder,
+        collider::{ColliderBuilder, ColliderShape},
+        graph::Graph,
+        joint::{JointBuilder, JointMotor, JointParams, RevoluteJoint, RopeJoint},
+        node::Node,
+        rigidbody::{RigidBody, RigidBodyBuilder, RigidBodyType},
+    };
+
+    fn create_body(graph: &mut Graph, body_type: RigidBodyType) -> Handle<Node> {
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(0.25, 0.25, 0.25))
+            .build(graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[collider]))
+            .with_body_type(body_type)
+            .with_can_sleep(false)
+            .build(graph)
+    }
+
+    fn connect(graph: &mut Graph, params: JointParams) -> Handle<Node> {
+        let body1 = create_body(graph, RigidBodyType::Static);
+        let body2 = create_body(graph, RigidBodyType::Dynamic);
+        JointBuilder::new(BaseBuilder::new())
+            .with_params(params)
+            .with_body1(body1)
+            .with_body2(body2)
+            .with_contacts_enabled(false)
+            .build(graph);
+        body2
+    }
+
+    #[test]
+    fn test_revolute_joint_motor() {
+        let mut graph = Graph::new();
+
+        let body = connect(
+            &mut graph,
+            JointParams::RevoluteJoint(RevoluteJoint {
+                motor: JointMotor {
+                    enabled: true,
+                    target_velocity: 2.0,
+                    damping: 100.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        );
+
+        for _ in 0..60 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        let ang_vel = graph[body].cast::<RigidBody>().unwrap().ang_vel();
+        assert!((ang_vel - Vector3::new(2.0, 0.0, 0.0)).norm() < 0.1);
+    }
+
+    #[test]
+    fn test_rope_joint() {
+        let mut graph = Graph::new();
+
+        let body = connect(
+            &mut graph,
+            JointParams::RopeJoint(RopeJoint { max_distance: 1.0 }),
+        );
+
+        for _ in 0..120 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        // The body must fall down and hang on the rope.
+        let position = graph[body].global_position();
+        assert!((position - Vector3::new(0.0, -1.0, 0.0)).norm() < 0.05);
+    }
+}
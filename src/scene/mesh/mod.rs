@@ -34,7 +34,7 @@ use crate::{
             buffer::{VertexAttributeUsage, VertexReadTrait},
             surface::{BlendShape, Surface},
         },
-        node::{Node, NodeTrait, UpdateContext},
+        node::{Node, NodeTrait, SyncContext, UpdateContext},
     },
 };
 use std::{
@@ -315,6 +315,14 @@ impl NodeTrait for Mesh {
         Self::type_uuid()
     }
 
+    fn sync_transform(&self, new_global_transform: &Matrix4<f32>, _context: &mut SyncContext) {
+        // Keep world bounding box in sync with the transform even if the node is not updated (for
+        // example in the editor), otherwise the mesh won't get into the spatial index of the graph.
+        // Skinned meshes will refine the bounding box in `update`.
+        self.world_bounding_box
+            .set(self.local_bounding_box().transform(new_global_transform));
+    }
+
     fn update(&mut self, context: &mut UpdateContext) {
         if self.surfaces.iter().any(|s| !s.bones.is_empty()) {
             let mut world_aabb = self
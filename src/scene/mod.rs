@@ -8,6 +8,7 @@ pub mod accel;
 pub mod animation;
 pub mod base;
 pub mod camera;
+pub mod character_controller;
 pub mod collider;
 pub mod debug;
 pub mod decal;
@@ -250,7 +251,8 @@ pub struct SceneLoader {
 
 impl SceneLoader {
     /// Tries to load scene from given file. File can contain any scene in native engine format.
-    /// Such scenes can be made in rusty editor.
+    /// Such scenes can be made in rusty editor. Both binary and text forms of the format are
+    /// supported, the form is detected automatically.
     pub async fn from_file<P: AsRef<Path>>(
         path: P,
         serialization_context: Arc<SerializationContext>,
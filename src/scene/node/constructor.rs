@@ -35,10 +35,12 @@ impl NodeConstructorContainer {
     pub fn new() -> Self {
         let container = NodeConstructorContainer::default();
 
+        container.add::<dim2::character_controller::CharacterController>();
         container.add::<dim2::collider::Collider>();
         container.add::<dim2::joint::Joint>();
         container.add::<Rectangle>();
         container.add::<dim2::rigidbody::RigidBody>();
+        container.add::<dim2::tilemap::TileMap>();
         container.add::<DirectionalLight>();
         container.add::<PointLight>();
         container.add::<SpotLight>();
@@ -47,6 +49,7 @@ impl NodeConstructorContainer {
         container.add::<Sound>();
         container.add::<Listener>();
         container.add::<Camera>();
+        container.add::<scene::character_controller::CharacterController>();
         container.add::<scene::collider::Collider>();
         container.add::<Decal>();
         container.add::<scene::joint::Joint>();
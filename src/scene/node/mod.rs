@@ -201,7 +201,8 @@ pub trait NodeTrait: BaseNodeTrait + Reflect + Visit {
 
     /// Allows the node to emit a set of render data. This is a high-level rendering method which can only
     /// do culling and provide render data. Render data is just a surface (vertex + index buffers) and a
-    /// material.
+    /// material. The method is called only for the nodes, which world bounding box is bounded and intersects
+    /// the frustum of an observer (see [`crate::scene::graph::Graph::spatial_index`]).
     fn collect_render_data(&self, #[allow(unused_variables)] ctx: &mut RenderContext) {}
 
     /// Allows the node to draw simple shapes to visualize internal data structures for debugging purposes.
@@ -0,0 +1,538 @@
+//! Foliage is a set of small detail objects (grass, flowers, bushes, rocks, etc.) scattered over the surface
+//! of a terrain. See [`FoliageLayer`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, UnitQuaternion, Vector2, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    material::{Material, SharedMaterial},
+    scene::mesh::{
+        buffer::{VertexAttributeUsage, VertexReadTrait},
+        surface::{SurfaceData, SurfaceSharedData},
+    },
+};
+use std::ops::Range;
+
+/// Maximum amount of instances of a single foliage layer per chunk. If density of a layer is too high for
+/// a chunk, it will be decreased to fit in this limit.
+pub const MAX_FOLIAGE_INSTANCES_PER_CHUNK: usize = 65536;
+
+/// Foliage mesh is a detail mesh that will be scattered over the surface of a terrain. Every foliage layer
+/// can have multiple meshes, each instance picks one of them randomly.
+#[derive(Debug, Clone, Visit, Reflect, PartialEq)]
+pub struct FoliageMesh {
+    /// Surface data of the mesh. Keep foliage meshes as simple as possible, there could be thousands of
+    /// instances on screen.
+    pub data: SurfaceSharedData,
+
+    /// Material of the mesh.
+    pub material: SharedMaterial,
+
+    /// Relative probability of the mesh to be picked for an instance.
+    #[reflect(min_value = 0.0)]
+    pub weight: f32,
+
+    /// Range of random uniform scale of instances.
+    #[reflect(min_value = 0.0)]
+    pub scale: Range<f32>,
+
+    /// Whether instances should be randomly rotated around vertical axis or not.
+    pub random_rotation: bool,
+
+    /// Range of slope angles (in degrees) of the terrain surface, the mesh will be placed only on the parts of the
+    /// terrain with the slope within the range.
+    pub slope_range: Range<f32>,
+
+    /// Whether instances should be aligned with the normal of the terrain surface or not. Useful for rocks, but
+    /// grass usually looks better when it grows straight up.
+    pub align_to_normal: bool,
+}
+
+impl Default for FoliageMesh {
+    fn default() -> Self {
+        Self {
+            // Vertical quad which "stands" on the ground.
+            data: SurfaceSharedData::new(SurfaceData::make_quad(&Matrix4::new_translation(
+                &Vector3::new(0.0, 0.5, 0.0),
+            ))),
+            material: SharedMaterial::new(Material::standard()),
+            weight: 1.0,
+            scale: 0.8..1.2,
+            random_rotation: true,
+            slope_range: 0.0..35.0,
+            align_to_normal: false,
+        }
+    }
+}
+
+impl FoliageMesh {
+    /// Calculates bounding box of the mesh in its local coordinates.
+    pub fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        let data = self.data.lock();
+        let mut bounding_box = AxisAlignedBoundingBox::default();
+        for view in data.vertex_buffer.iter() {
+            if let Ok(position) = view.read_3_f32(VertexAttributeUsage::Position) {
+                bounding_box.add_point(position);
+            }
+        }
+        bounding_box
+    }
+}
+
+/// Foliage layer defines a set of detail meshes that will be scattered over the surface of a terrain. Every
+/// chunk of the terrain has a density mask per foliage layer, which could be painted using
+/// [`super::BrushMode::DrawOnFoliageMask`].
+///
+/// ## Scattering
+///
+/// Instances are not saved, instead they're generated from the density masks every time when a mask, a height
+/// map or a layer is changed (including loading). Scattering is deterministic: every chunk is split into a grid
+/// of cells and each cell uses its own random number generator, which is seeded by the seed of the layer and
+/// global coordinates of the cell. It means that painting a mask in one place does not shuffle instances in
+/// other places.
+///
+/// ## Rendering
+///
+/// Instances are grouped per chunk, chunks outside of the view frustum or farther than the end of the fade
+/// distance are skipped entirely. Instances within the fade distance range are smoothly scaled down to zero.
+#[derive(Debug, Clone, Visit, Reflect, PartialEq)]
+pub struct FoliageLayer {
+    /// Name of the layer.
+    pub name: String,
+
+    /// A set of meshes of the layer.
+    pub meshes: Vec<FoliageMesh>,
+
+    /// Maximum amount of instances per square meter, it is reached on the parts of the terrain where density
+    /// mask is fully opaque.
+    #[reflect(min_value = 0.0)]
+    pub density: f32,
+
+    /// Seed of the random number generator.
+    pub seed: u32,
+
+    /// Range of distances from an observer, in which instances fade out. Instances farther than the end of the
+    /// range won't be rendered.
+    #[reflect(min_value = 0.0)]
+    pub fade_distance: Range<f32>,
+}
+
+impl Default for FoliageLayer {
+    fn default() -> Self {
+        Self {
+            name: "Foliage".to_string(),
+            meshes: vec![Default::default()],
+            density: 4.0,
+            seed: 0,
+            fade_distance: 40.0..60.0,
+        }
+    }
+}
+
+impl FoliageLayer {
+    /// Returns a value in `[0; 1]` range which defines how much an instance at the given distance from an
+    /// observer is visible.
+    pub fn fade_factor(&self, distance: f32) -> f32 {
+        let Range { start, end } = self.fade_distance;
+        if distance <= start {
+            1.0
+        } else if distance >= end {
+            0.0
+        } else {
+            1.0 - (distance - start) / (end - start)
+        }
+    }
+
+    /// Generates instances for a chunk.
+    pub(super) fn scatter(
+        &self,
+        source: &FoliageSource,
+        mesh_bounds: &[AxisAlignedBoundingBox],
+    ) -> ChunkFoliage {
+        let mut foliage = ChunkFoliage::default();
+
+        let total_weight = self
+            .meshes
+            .iter()
+            .map(|mesh| mesh.weight.max(0.0))
+            .sum::<f32>();
+
+        let size = source.physical_size;
+        if self.density <= 0.0
+            || total_weight <= 0.0
+            || size.x <= 0.0
+            || size.y <= 0.0
+            || !source.is_valid()
+        {
+            return foliage;
+        }
+
+        let mut spacing = 1.0 / self.density.sqrt();
+        let max_count = (size.x / spacing).ceil() * (size.y / spacing).ceil();
+        if max_count > MAX_FOLIAGE_INSTANCES_PER_CHUNK as f32 {
+            spacing *= (max_count / MAX_FOLIAGE_INSTANCES_PER_CHUNK as f32).sqrt();
+        }
+
+        // Adjust the spacing a bit, so the chunk will have whole number of cells. This way cells of adjacent
+        // chunks won't overlap.
+        let cells = Vector2::new(
+            ((size.x / spacing).round() as usize).max(1),
+            ((size.y / spacing).round() as usize).max(1),
+        );
+        let cell_size = Vector2::new(size.x / cells.x as f32, size.y / cells.y as f32);
+
+        for cz in 0..cells.y {
+            for cx in 0..cells.x {
+                let mut rng = FoliageRng::new(
+                    self.seed,
+                    source.grid_position.x as i64 * cells.x as i64 + cx as i64,
+                    source.grid_position.y as i64 * cells.y as i64 + cz as i64,
+                );
+
+                // Always take the same amount of random numbers, so every cell is fully independent.
+                let jitter = Vector2::new(rng.next_f32(), rng.next_f32());
+                let acceptance = rng.next_f32();
+                let pick = rng.next_f32() * total_weight;
+                let scale = rng.next_f32();
+                let yaw = rng.next_f32();
+
+                let point = Vector2::new(
+                    (cx as f32 + jitter.x) * cell_size.x,
+                    (cz as f32 + jitter.y) * cell_size.y,
+                );
+
+                if acceptance >= source.density(point) {
+                    continue;
+                }
+
+                let mut mesh_index = None;
+                let mut accumulated_weight = 0.0;
+                for (i, mesh) in self.meshes.iter().enumerate() {
+                    if mesh.weight > 0.0 {
+                        mesh_index = Some(i);
+                        accumulated_weight += mesh.weight;
+                        if pick < accumulated_weight {
+                            break;
+                        }
+                    }
+                }
+                let mesh_index = mesh_index.unwrap();
+                let mesh = &self.meshes[mesh_index];
+
+                let normal = source.normal(point);
+                let slope = normal.y.clamp(-1.0, 1.0).acos().to_degrees();
+                if slope < mesh.slope_range.start || slope > mesh.slope_range.end {
+                    continue;
+                }
+
+                let yaw = if mesh.random_rotation {
+                    yaw * std::f32::consts::TAU
+                } else {
+                    0.0
+                };
+                let mut rotation = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), yaw);
+                if mesh.align_to_normal {
+                    rotation = UnitQuaternion::rotation_between(&Vector3::y(), &normal)
+                        .unwrap_or_default()
+                        * rotation;
+                }
+
+                let scale = mesh.scale.start + (mesh.scale.end - mesh.scale.start) * scale;
+
+                let position =
+                    source.position + Vector3::new(point.x, source.height(point), point.y);
+
+                let transform = Matrix4::new_translation(&position)
+                    * rotation.to_homogeneous()
+                    * Matrix4::new_scaling(scale);
+
+                match mesh_bounds.get(mesh_index) {
+                    Some(mesh_bounds) if mesh_bounds.min.x <= mesh_bounds.max.x => {
+                        foliage.bounds.add_box(mesh_bounds.transform(&transform))
+                    }
+                    _ => foliage.bounds.add_point(position),
+                }
+
+                foliage.instances.push(FoliageInstance {
+                    transform,
+                    mesh: mesh_index,
+                });
+            }
+        }
+
+        foliage
+    }
+}
+
+/// Foliage instance is a single detail mesh placed on the surface of a terrain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoliageInstance {
+    /// Transform of the instance in local coordinates of the terrain.
+    pub transform: Matrix4<f32>,
+
+    /// Index of the mesh in the foliage layer.
+    pub mesh: usize,
+}
+
+impl FoliageInstance {
+    /// Returns position of the instance in local coordinates of the terrain.
+    pub fn position(&self) -> Vector3<f32> {
+        self.transform.transform_point(&Point3::origin()).coords
+    }
+}
+
+/// Instances of a foliage layer of a chunk.
+#[derive(Debug, Clone, Default)]
+pub(super) struct ChunkFoliage {
+    pub instances: Vec<FoliageInstance>,
+    /// Bounds of every instance in local coordinates of the terrain.
+    pub bounds: AxisAlignedBoundingBox,
+}
+
+impl PartialEq for ChunkFoliage {
+    fn eq(&self, other: &Self) -> bool {
+        self.instances == other.instances
+            && self.bounds.min == other.bounds.min
+            && self.bounds.max == other.bounds.max
+    }
+}
+
+/// Data of a chunk that is needed to scatter foliage over it.
+pub(super) struct FoliageSource<'a> {
+    pub heights: &'a [f32],
+    pub height_map_size: Vector2<u32>,
+    pub mask: &'a [u8],
+    pub mask_size: Vector2<u32>,
+    /// Position of the chunk in local coordinates of the terrain.
+    pub position: Vector3<f32>,
+    pub physical_size: Vector2<f32>,
+    pub grid_position: Vector2<i32>,
+}
+
+impl<'a> FoliageSource<'a> {
+    fn is_valid(&self) -> bool {
+        self.height_map_size.x >= 2
+            && self.height_map_size.y >= 2
+            && self.heights.len() >= (self.height_map_size.x * self.height_map_size.y) as usize
+            && self.mask_size.x >= 1
+            && self.mask_size.y >= 1
+            && self.mask.len() >= (self.mask_size.x * self.mask_size.y) as usize
+    }
+
+    fn normalize(&self, point: Vector2<f32>) -> Vector2<f32> {
+        Vector2::new(
+            (point.x / self.physical_size.x).clamp(0.0, 1.0),
+            (point.y / self.physical_size.y).clamp(0.0, 1.0),
+        )
+    }
+
+    /// Returns interpolated height at the given point (in local coordinates of the chunk).
+    fn height(&self, point: Vector2<f32>) -> f32 {
+        sample_bilinear(self.height_map_size, self.normalize(point), |i| {
+            self.heights[i]
+        })
+    }
+
+    /// Returns interpolated density in `[0; 1]` range at the given point (in local coordinates of the chunk).
+    fn density(&self, point: Vector2<f32>) -> f32 {
+        sample_bilinear(self.mask_size, self.normalize(point), |i| {
+            self.mask[i] as f32 / 255.0
+        })
+    }
+
+    /// Returns normal of the surface at the given point (in local coordinates of the chunk).
+    fn normal(&self, point: Vector2<f32>) -> Vector3<f32> {
+        let step = Vector2::new(
+            self.physical_size.x / (self.height_map_size.x - 1) as f32,
+            self.physical_size.y / (self.height_map_size.y - 1) as f32,
+        );
+
+        let left = (point.x - step.x).max(0.0);
+        let right = (point.x + step.x).min(self.physical_size.x);
+        let back = (point.y - step.y).max(0.0);
+        let front = (point.y + step.y).min(self.physical_size.y);
+
+        let dx = (self.height(Vector2::new(right, point.y))
+            - self.height(Vector2::new(left, point.y)))
+            / (right - left).max(f32::EPSILON);
+        let dz = (self.height(Vector2::new(point.x, front))
+            - self.height(Vector2::new(point.x, back)))
+            / (front - back).max(f32::EPSILON);
+
+        Vector3::new(-dx, 1.0, -dz)
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector3::y)
+    }
+}
+
+fn sample_bilinear<F>(size: Vector2<u32>, k: Vector2<f32>, fetch: F) -> f32
+where
+    F: Fn(usize) -> f32,
+{
+    let x = k.x * (size.x - 1) as f32;
+    let y = k.y * (size.y - 1) as f32;
+
+    let x0 = (x.floor() as u32).min(size.x - 1);
+    let y0 = (y.floor() as u32).min(size.y - 1);
+    let x1 = (x0 + 1).min(size.x - 1);
+    let y1 = (y0 + 1).min(size.y - 1);
+
+    let tx = x - x0 as f32;
+    let ty = y - y0 as f32;
+
+    let index = |x: u32, y: u32| (y * size.x + x) as usize;
+
+    let top = fetch(index(x0, y0)) + (fetch(index(x1, y0)) - fetch(index(x0, y0))) * tx;
+    let bottom = fetch(index(x0, y1)) + (fetch(index(x1, y1)) - fetch(index(x0, y1))) * tx;
+
+    top + (bottom - top) * ty
+}
+
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Tiny random number generator (SplitMix64), it is used instead of a "proper" one because foliage needs to
+/// be reproducible across platforms and versions of dependencies.
+struct FoliageRng(u64);
+
+impl FoliageRng {
+    fn new(seed: u32, x: i64, z: i64) -> Self {
+        Self(mix(mix(mix(seed as u64) ^ x as u64) ^ z as u64))
+    }
+
+    fn next_f32(&mut self) -> f32 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        (mix(self.0) >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Vector2, Vector3},
+            math::aabb::AxisAlignedBoundingBox,
+        },
+        scene::terrain::foliage::{FoliageLayer, FoliageSource},
+    };
+
+    fn source<'a>(
+        heights: &'a [f32],
+        mask: &'a [u8],
+        grid_position: Vector2<i32>,
+    ) -> FoliageSource<'a> {
+        FoliageSource {
+            heights,
+            height_map_size: Vector2::new(3, 3),
+            mask,
+            mask_size: Vector2::new(2, 2),
+            position: Vector3::new(
+                grid_position.x as f32 * 10.0,
+                0.0,
+                grid_position.y as f32 * 10.0,
+            ),
+            physical_size: Vector2::new(10.0, 10.0),
+            grid_position,
+        }
+    }
+
+    fn bounds() -> Vec<AxisAlignedBoundingBox> {
+        vec![AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(-0.5, 0.0, -0.5),
+            Vector3::new(0.5, 1.0, 0.5),
+        )]
+    }
+
+    #[test]
+    fn test_scatter_is_deterministic() {
+        let layer = FoliageLayer::default();
+        let heights = [0.0; 9];
+        let mask = [255; 4];
+
+        let a = layer.scatter(&source(&heights, &mask, Vector2::new(1, 2)), &bounds());
+        let b = layer.scatter(&source(&heights, &mask, Vector2::new(1, 2)), &bounds());
+        assert!(!a.instances.is_empty());
+        assert_eq!(a, b);
+
+        // Full mask on a flat chunk accepts every cell.
+        assert_eq!(a.instances.len(), 400);
+
+        // Other chunks must have different layout.
+        let c = layer.scatter(&source(&heights, &mask, Vector2::new(2, 2)), &bounds());
+        assert_ne!(
+            a.instances[0].position().x - 10.0,
+            c.instances[0].position().x - 20.0
+        );
+
+        // Different seed gives different layout.
+        let other = FoliageLayer {
+            seed: 123,
+            ..Default::default()
+        };
+        let d = other.scatter(&source(&heights, &mask, Vector2::new(1, 2)), &bounds());
+        assert_ne!(a, d);
+
+        for instance in a.instances.iter() {
+            let position = instance.position();
+            assert!(position.x >= 10.0 && position.x <= 20.0);
+            assert!(position.z >= 20.0 && position.z <= 30.0);
+            assert!(a.bounds.is_contains_point(position));
+        }
+    }
+
+    #[test]
+    fn test_scatter_respects_mask() {
+        let layer = FoliageLayer::default();
+        let heights = [0.0; 9];
+
+        let empty = layer.scatter(&source(&heights, &[0; 4], Vector2::new(0, 0)), &bounds());
+        assert!(empty.instances.is_empty());
+
+        let full = layer.scatter(&source(&heights, &[255; 4], Vector2::new(0, 0)), &bounds());
+        let half = layer.scatter(&source(&heights, &[128; 4], Vector2::new(0, 0)), &bounds());
+        assert!(half.instances.len() > full.instances.len() / 4);
+        assert!(half.instances.len() < full.instances.len() * 3 / 4);
+
+        // Instances accepted with lower density must be accepted with higher density too.
+        for instance in half.instances.iter() {
+            assert!(full.instances.contains(instance));
+        }
+    }
+
+    #[test]
+    fn test_scatter_respects_slope() {
+        let layer = FoliageLayer::default();
+        // 45 degrees slope along X axis.
+        let heights = [0.0, 5.0, 10.0, 0.0, 5.0, 10.0, 0.0, 5.0, 10.0];
+        let mask = [255; 4];
+
+        let steep = layer.scatter(&source(&heights, &mask, Vector2::new(0, 0)), &bounds());
+        assert!(steep.instances.is_empty());
+
+        let mut layer = layer;
+        layer.meshes[0].slope_range = 40.0..50.0;
+        let allowed = layer.scatter(&source(&heights, &mask, Vector2::new(0, 0)), &bounds());
+        assert_eq!(allowed.instances.len(), 400);
+        for instance in allowed.instances.iter() {
+            let position = instance.position();
+            assert!((position.y - position.x).abs() < 1.0e-3);
+        }
+    }
+
+    #[test]
+    fn test_fade_factor() {
+        let layer = FoliageLayer {
+            fade_distance: 10.0..20.0,
+            ..Default::default()
+        };
+        assert_eq!(layer.fade_factor(5.0), 1.0);
+        assert_eq!(layer.fade_factor(15.0), 0.5);
+        assert_eq!(layer.fade_factor(25.0), 0.0);
+    }
+}
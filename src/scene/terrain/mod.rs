@@ -32,21 +32,34 @@ use crate::{
         graph::Graph,
         mesh::RenderPath,
         node::{Node, NodeTrait},
-        terrain::{geometry::TerrainGeometry, quadtree::QuadTree},
+        terrain::{
+            foliage::{
+                ChunkFoliage, FoliageInstance, FoliageLayer, FoliageSource,
+                MAX_FOLIAGE_INSTANCES_PER_CHUNK,
+            },
+            geometry::TerrainGeometry,
+            quadtree::QuadTree,
+            tools::{
+                FractalNoise, HeightGrid, HeightMapError, HeightMapFormat, HydraulicErosion,
+                ThermalErosion,
+            },
+        },
     },
     utils::{self},
 };
 use half::f16;
 use image::{imageops::FilterType, ImageBuffer, Luma};
 use std::{
-    cell::Cell,
+    cell::{Cell, RefCell},
     cmp::Ordering,
     collections::HashMap,
     ops::{Deref, DerefMut, Range},
 };
 
+pub mod foliage;
 mod geometry;
 mod quadtree;
+pub mod tools;
 
 /// Current implementation version marker.
 pub const VERSION: u8 = 1;
@@ -146,6 +159,13 @@ pub struct Chunk {
     /// Layer blending masks of the chunk.
     #[reflect(hidden)]
     pub layer_masks: Vec<TextureResource>,
+    /// Foliage density masks of the chunk.
+    #[reflect(hidden)]
+    pub foliage_masks: Vec<TextureResource>,
+    #[reflect(hidden)]
+    foliage_dirty: Cell<bool>,
+    #[reflect(hidden)]
+    foliage: RefCell<Vec<ChunkFoliage>>,
 }
 
 impl Clone for Chunk {
@@ -164,6 +184,13 @@ impl Clone for Chunk {
                 .iter()
                 .map(|m| m.deep_clone())
                 .collect::<Vec<_>>(),
+            foliage_masks: self
+                .foliage_masks
+                .iter()
+                .map(|m| m.deep_clone())
+                .collect::<Vec<_>>(),
+            foliage_dirty: Cell::new(true),
+            foliage: Default::default(),
             quad_tree: make_quad_tree(&self.heightmap, self.height_map_size, self.block_size),
         }
     }
@@ -219,11 +246,13 @@ impl Visit for Chunk {
                 self.layer_masks.visit("LayerMasks", &mut region)?;
                 self.grid_position.visit("GridPosition", &mut region)?;
                 let _ = self.block_size.visit("BlockSize", &mut region);
+                let _ = self.foliage_masks.visit("FoliageMasks", &mut region);
             }
             _ => (),
         }
 
         self.quad_tree = make_quad_tree(&self.heightmap, self.height_map_size, self.block_size);
+        self.foliage_dirty.set(true);
 
         Ok(())
     }
@@ -241,6 +270,9 @@ impl Default for Chunk {
             block_size: Vector2::new(32, 32),
             grid_position: Default::default(),
             layer_masks: Default::default(),
+            foliage_masks: Default::default(),
+            foliage_dirty: Cell::new(true),
+            foliage: Default::default(),
         }
     }
 }
@@ -420,6 +452,7 @@ impl Chunk {
                             if let Some(texture) =
                                 make_height_map_texture_internal(pixels, self.height_map_size)
                             {
+                                self.foliage_dirty.set(true);
                                 return std::mem::replace(&mut self.heightmap, Some(texture));
                             }
                         }
@@ -456,6 +489,9 @@ impl Chunk {
             {
                 drop(data);
                 self.heightmap = Some(heightmap);
+                self.quad_tree =
+                    make_quad_tree(&self.heightmap, self.height_map_size, self.block_size);
+                self.foliage_dirty.set(true);
                 return Ok(());
             }
         }
@@ -487,6 +523,58 @@ impl Chunk {
     }
 }
 
+/// Draws on the given mask using the brush. Returns `true` if at least one pixel of the mask was affected
+/// by the brush.
+fn draw_on_mask(
+    mask: &TextureResource,
+    chunk_position: Vector2<f32>,
+    chunk_size: Vector2<f32>,
+    brush: &Brush,
+    center: Vector2<f32>,
+    alpha: f32,
+) -> bool {
+    let alpha = alpha.clamp(-1.0, 1.0);
+
+    let mut texture_data = mask.data_ref();
+    let mut texture_data_mut = texture_data.modify();
+
+    let (texture_width, texture_height) =
+        if let TextureKind::Rectangle { width, height } = texture_data_mut.kind() {
+            (width as usize, height as usize)
+        } else {
+            unreachable!("Mask must be a 2D greyscale image!")
+        };
+
+    let mut modified = false;
+
+    for z in 0..texture_height {
+        let kz = z as f32 / (texture_height - 1) as f32;
+        for x in 0..texture_width {
+            let kx = x as f32 / (texture_width - 1) as f32;
+
+            let pixel_position =
+                chunk_position + Vector2::new(kx * chunk_size.x, kz * chunk_size.y);
+
+            let k = match brush.shape {
+                BrushShape::Circle { radius } => {
+                    1.0 - ((center - pixel_position).norm() / radius).powf(4.0)
+                }
+                BrushShape::Rectangle { .. } => 1.0,
+            };
+
+            if brush.shape.contains(center, pixel_position) {
+                // We can draw on mask directly, without any problems because it has R8 pixel format.
+                let data = texture_data_mut.data_mut();
+                let pixel = &mut data[z * texture_width + x];
+                *pixel = (*pixel as f32 + k * alpha * 255.0).min(255.0) as u8;
+                modified = true;
+            }
+        }
+    }
+
+    modified
+}
+
 fn map_to_local(v: Vector3<f32>) -> Vector2<f32> {
     // Terrain is a XZ oriented surface so we can map X -> X, Z -> Y
     Vector2::new(v.x, v.z)
@@ -546,10 +634,18 @@ pub struct TerrainRayCastResult {
 /// Current implementation uses modified version of CDLOD algorithm without patch morphing. Apparently it is not
 /// needed, since bilinear filtration in vertex shader prevents seams to occur.
 ///
+/// ## Foliage
+///
+/// Terrain can be covered with grass, bushes, rocks and other small detail objects using foliage layers. Each
+/// foliage layer has a density mask per chunk (painted the same way as layer masks) and a set of meshes.
+/// Instances are not saved, they're scattered deterministically from the masks and the height maps. See
+/// [`FoliageLayer`] docs for more info.
+///
 /// ## Painting
 ///
 /// Terrain has a single method for "painting" - [`Terrain::draw`], it accepts a brush with specific parameters,
-/// which can either alternate height map or a layer mask. See method's documentation for more info.
+/// which can either alternate height map, a layer mask or a foliage density mask. See method's documentation
+/// for more info.
 ///
 /// ## Ray casting
 ///
@@ -572,6 +668,9 @@ pub struct Terrain {
     #[reflect(setter = "set_layers")]
     layers: InheritableVariable<Vec<Layer>>,
 
+    #[reflect(setter = "set_foliage_layers")]
+    foliage_layers: InheritableVariable<Vec<FoliageLayer>>,
+
     #[reflect(setter = "set_decal_layer_index")]
     decal_layer_index: InheritableVariable<u8>,
 
@@ -627,6 +726,11 @@ pub struct Terrain {
     #[reflect(hidden)]
     geometry: TerrainGeometry,
 
+    // Foliage layers that were used to generate foliage instances last time. Used to detect changes
+    // of the layers made via reflection.
+    #[reflect(hidden)]
+    foliage_layers_snapshot: RefCell<Vec<FoliageLayer>>,
+
     #[reflect(hidden)]
     version: u8,
 }
@@ -636,6 +740,7 @@ impl Default for Terrain {
         Self {
             base: Default::default(),
             layers: Default::default(),
+            foliage_layers: Default::default(),
             decal_layer_index: Default::default(),
             chunk_size: Vector2::new(16.0, 16.0).into(),
             width_chunks: Default::default(),
@@ -647,6 +752,7 @@ impl Default for Terrain {
             bounding_box_dirty: Cell::new(true),
             bounding_box: Cell::new(Default::default()),
             geometry: Default::default(),
+            foliage_layers_snapshot: Default::default(),
             version: VERSION,
         }
     }
@@ -763,6 +869,7 @@ impl Visit for Terrain {
                 let _ = self.block_size.visit("BlockSize", &mut region);
                 self.mask_size.visit("MaskSize", &mut region)?;
                 self.chunks.visit("Chunks", &mut region)?;
+                let _ = self.foliage_layers.visit("FoliageLayers", &mut region);
             }
             _ => (),
         }
@@ -834,6 +941,7 @@ impl Terrain {
                 let chunk = &mut self.chunks[iy * self.width_chunks.len() + ix];
                 chunk.position = position;
                 chunk.physical_size = chunk_size;
+                chunk.foliage_dirty.set(true);
             }
         }
 
@@ -957,6 +1065,13 @@ impl Terrain {
                                 )
                             })
                             .collect::<Vec<_>>(),
+                        foliage_masks: self
+                            .foliage_layers
+                            .iter()
+                            .map(|_| create_layer_mask(self.mask_size.x, self.mask_size.y, 0))
+                            .collect::<Vec<_>>(),
+                        foliage_dirty: Cell::new(true),
+                        foliage: Default::default(),
                         version: VERSION,
                     };
 
@@ -978,6 +1093,7 @@ impl Terrain {
     /// Returns a mutable reference to chunks of the terrain.
     pub fn chunks_mut(&mut self) -> &mut [Chunk] {
         self.bounding_box_dirty.set(true);
+        self.invalidate_foliage();
         &mut self.chunks
     }
 
@@ -1011,7 +1127,7 @@ impl Terrain {
 
             for iy in 0..chunk.height_map_size.y {
                 let kz = iy as f32 / (chunk.height_map_size.y - 1) as f32;
-                for ix in 0..chunk.height_map_size.y {
+                for ix in 0..chunk.height_map_size.x {
                     let kx = ix as f32 / (chunk.height_map_size.x - 1) as f32;
 
                     let pixel_position = chunk.local_position()
@@ -1028,11 +1144,166 @@ impl Terrain {
 
             chunk.quad_tree =
                 make_quad_tree(&chunk.heightmap, chunk.height_map_size, chunk.block_size);
+            chunk.foliage_dirty.set(true);
+        }
+
+        self.bounding_box_dirty.set(true);
+    }
+
+    /// Returns total amount of pixels in the height map of the whole terrain along each axis. Adjacent chunks share
+    /// their border pixels, so the size is `chunks * (height_map_size - 1) + 1` along each axis.
+    pub fn total_height_map_size(&self) -> Vector2<u32> {
+        if self.chunks.is_empty() {
+            return Vector2::default();
+        }
+
+        Vector2::new(
+            self.width_chunks.len() as u32 * (self.height_map_size.x - 1) + 1,
+            self.length_chunks.len() as u32 * (self.height_map_size.y - 1) + 1,
+        )
+    }
+
+    fn chunk_height_grid_offset(&self, chunk: &Chunk) -> Vector2<usize> {
+        Vector2::new(
+            (chunk.grid_position.x - self.width_chunks.start) as usize
+                * (chunk.height_map_size.x as usize - 1),
+            (chunk.grid_position.y - self.length_chunks.start) as usize
+                * (chunk.height_map_size.y as usize - 1),
+        )
+    }
+
+    /// Returns position of a pixel of the height grid in local 2D coordinates of the terrain.
+    fn height_grid_pixel_position(&self, x: usize, y: usize) -> Vector2<f32> {
+        let cell_size = self.height_grid_cell_size();
+        Vector2::new(
+            self.width_chunks.start as f32 * self.chunk_size.x + x as f32 * cell_size.x,
+            self.length_chunks.start as f32 * self.chunk_size.y + y as f32 * cell_size.y,
+        )
+    }
+
+    /// Returns distance between adjacent pixels of the height map (in meters).
+    fn height_grid_cell_size(&self) -> Vector2<f32> {
+        Vector2::new(
+            self.chunk_size.x / (self.height_map_size.x.max(2) - 1) as f32,
+            self.chunk_size.y / (self.height_map_size.y.max(2) - 1) as f32,
+        )
+    }
+
+    /// Collects height maps of every chunk in a single grid.
+    fn gather_height_grid(&self) -> HeightGrid {
+        let size = self.total_height_map_size();
+        let mut grid = HeightGrid::new(Vector2::new(size.x as usize, size.y as usize));
+
+        for chunk in self.chunks.iter() {
+            let offset = self.chunk_height_grid_offset(chunk);
+            let texture = chunk.heightmap.as_ref().unwrap().data_ref();
+            let height_map = texture.data_of_type::<f32>().unwrap();
+
+            for iy in 0..chunk.height_map_size.y as usize {
+                for ix in 0..chunk.height_map_size.x as usize {
+                    grid.set(
+                        offset.x + ix,
+                        offset.y + iy,
+                        height_map[iy * chunk.height_map_size.x as usize + ix],
+                    );
+                }
+            }
+        }
+
+        grid
+    }
+
+    /// Writes the grid back to height maps of every chunk. Border pixels of adjacent chunks will have
+    /// the same heights, so there won't be any seams.
+    fn scatter_height_grid(&mut self, grid: &HeightGrid) {
+        let offsets = self
+            .chunks
+            .iter()
+            .map(|chunk| self.chunk_height_grid_offset(chunk))
+            .collect::<Vec<_>>();
+
+        for (chunk, offset) in self.chunks.iter_mut().zip(offsets) {
+            let mut texture_data = chunk.heightmap.as_ref().unwrap().data_ref();
+            let mut texture_modifier = texture_data.modify();
+            let height_map = texture_modifier.data_mut_of_type::<f32>().unwrap();
+
+            for iy in 0..chunk.height_map_size.y as usize {
+                for ix in 0..chunk.height_map_size.x as usize {
+                    height_map[iy * chunk.height_map_size.x as usize + ix] =
+                        grid.get(offset.x + ix, offset.y + iy);
+                }
+            }
+
+            drop(texture_modifier);
+            drop(texture_data);
+
+            chunk.quad_tree =
+                make_quad_tree(&chunk.heightmap, chunk.height_map_size, chunk.block_size);
+            chunk.foliage_dirty.set(true);
         }
 
         self.bounding_box_dirty.set(true);
     }
 
+    /// Replaces heights of the whole terrain with the values of the given fractal noise. It could be used to
+    /// generate a "base" for a landscape, which then could be eroded (see [`Self::apply_hydraulic_erosion`] and
+    /// [`Self::apply_thermal_erosion`]) and refined with brushes.
+    pub fn generate_height_map(&mut self, noise: &FractalNoise) {
+        self.for_each_height_map_pixel(|pixel, pixel_position| {
+            *pixel = noise.sample(pixel_position);
+        });
+    }
+
+    /// Simulates hydraulic erosion over the whole terrain. See [`HydraulicErosion`] docs for more info.
+    /// **Warning:** This method is heavy and should not be used at every frame!
+    pub fn apply_hydraulic_erosion(&mut self, erosion: &HydraulicErosion) {
+        let mut grid = self.gather_height_grid();
+        grid.erode_hydraulic(erosion);
+        self.scatter_height_grid(&grid);
+    }
+
+    /// Simulates thermal erosion over the whole terrain. See [`ThermalErosion`] docs for more info.
+    /// **Warning:** This method is heavy and should not be used at every frame!
+    pub fn apply_thermal_erosion(&mut self, erosion: &ThermalErosion) {
+        let mut grid = self.gather_height_grid();
+        grid.erode_thermal(erosion, self.height_grid_cell_size());
+        self.scatter_height_grid(&grid);
+    }
+
+    /// Replaces heights of the whole terrain with the heights from the given height map image. Normalized values
+    /// of the image are mapped to the given height range. The image will be resampled if its size does not match
+    /// [`Self::total_height_map_size`].
+    pub fn import_height_map(
+        &mut self,
+        data: &[u8],
+        format: HeightMapFormat,
+        height_range: Range<f32>,
+    ) -> Result<(), HeightMapError> {
+        if self.chunks.is_empty() {
+            return Err(HeightMapError::EmptyTerrain);
+        }
+
+        let mut grid = self.gather_height_grid();
+        grid.decode(data, format, height_range)?;
+        self.scatter_height_grid(&grid);
+
+        Ok(())
+    }
+
+    /// Encodes heights of the whole terrain as an image of [`Self::total_height_map_size`] size. The heights are
+    /// normalized using the given height range, the heights outside of the range will be clamped.
+    pub fn export_height_map(
+        &self,
+        format: HeightMapFormat,
+        height_range: Range<f32>,
+    ) -> Result<Vec<u8>, HeightMapError> {
+        if self.chunks.is_empty() {
+            return Err(HeightMapError::EmptyTerrain);
+        }
+
+        self.gather_height_grid().encode(format, height_range)
+    }
+
     /// Multi-functional drawing method. It uses given brush to modify terrain, see [`Brush`] docs for
     /// more info.
     pub fn draw(&mut self, brush: &Brush) {
@@ -1058,44 +1329,33 @@ impl Terrain {
                     return;
                 }
 
-                let alpha = alpha.clamp(-1.0, 1.0);
-
-                for chunk in self.chunks.iter_mut() {
-                    let chunk_position = chunk.local_position();
-                    let mut texture_data = chunk.layer_masks[layer].data_ref();
-                    let mut texture_data_mut = texture_data.modify();
-
-                    let (texture_width, texture_height) =
-                        if let TextureKind::Rectangle { width, height } = texture_data_mut.kind() {
-                            (width as usize, height as usize)
-                        } else {
-                            unreachable!("Mask must be a 2D greyscale image!")
-                        };
-
-                    for z in 0..texture_height {
-                        let kz = z as f32 / (texture_height - 1) as f32;
-                        for x in 0..texture_width {
-                            let kx = x as f32 / (texture_width - 1) as f32;
-
-                            let pixel_position = chunk_position
-                                + Vector2::new(
-                                    kx * chunk.physical_size.x,
-                                    kz * chunk.physical_size.y,
-                                );
+                for chunk in self.chunks.iter() {
+                    draw_on_mask(
+                        &chunk.layer_masks[layer],
+                        chunk.local_position(),
+                        chunk.physical_size,
+                        brush,
+                        center,
+                        alpha,
+                    );
+                }
+            }
+            BrushMode::DrawOnFoliageMask { layer, alpha } => {
+                if layer >= self.foliage_layers.len() {
+                    return;
+                }
 
-                            let k = match brush.shape {
-                                BrushShape::Circle { radius } => {
-                                    1.0 - ((center - pixel_position).norm() / radius).powf(4.0)
-                                }
-                                BrushShape::Rectangle { .. } => 1.0,
-                            };
-
-                            if brush.shape.contains(center, pixel_position) {
-                                // We can draw on mask directly, without any problems because it has R8 pixel format.
-                                let data = texture_data_mut.data_mut();
-                                let pixel = &mut data[z * texture_width + x];
-                                *pixel = (*pixel as f32 + k * alpha * 255.0).min(255.0) as u8;
-                            }
+                for chunk in self.chunks.iter() {
+                    if let Some(mask) = chunk.foliage_masks.get(layer) {
+                        if draw_on_mask(
+                            mask,
+                            chunk.local_position(),
+                            chunk.physical_size,
+                            brush,
+                            center,
+                            alpha,
+                        ) {
+                            chunk.foliage_dirty.set(true);
                         }
                     }
                 }
@@ -1107,6 +1367,51 @@ impl Terrain {
                     }
                 });
             }
+            BrushMode::SmoothHeightMap { factor } => {
+                let mut grid = self.gather_height_grid();
+                grid.smooth(|x, y| {
+                    let pixel_position = self.height_grid_pixel_position(x, y);
+
+                    if !brush.shape.contains(center, pixel_position) {
+                        return 0.0;
+                    }
+
+                    let k = match brush.shape {
+                        BrushShape::Circle { radius } => {
+                            1.0 - ((center - pixel_position).norm() / radius).powf(2.0)
+                        }
+                        BrushShape::Rectangle { .. } => 1.0,
+                    };
+
+                    (k * factor).clamp(0.0, 1.0)
+                });
+                self.scatter_height_grid(&grid);
+            }
+            BrushMode::NoiseHeightMap {
+                amount,
+                frequency,
+                seed,
+            } => {
+                let noise = FractalNoise {
+                    seed,
+                    frequency,
+                    amplitude: 1.0,
+                    ..Default::default()
+                };
+
+                self.for_each_height_map_pixel(|pixel, pixel_position| {
+                    let k = match brush.shape {
+                        BrushShape::Circle { radius } => {
+                            1.0 - ((center - pixel_position).norm() / radius).powf(2.0)
+                        }
+                        BrushShape::Rectangle { .. } => 1.0,
+                    };
+
+                    if brush.shape.contains(center, pixel_position) {
+                        *pixel += k * amount * noise.sample(pixel_position);
+                    }
+                });
+            }
         }
     }
 
@@ -1289,11 +1594,246 @@ impl Terrain {
         }
     }
 
+    /// Sets new foliage layers of the terrain.
+    pub fn set_foliage_layers(&mut self, layers: Vec<FoliageLayer>) -> Vec<FoliageLayer> {
+        self.foliage_layers.set_value_and_mark_modified(layers)
+    }
+
+    /// Returns a reference to a slice with foliage layers of the terrain.
+    pub fn foliage_layers(&self) -> &[FoliageLayer] {
+        &self.foliage_layers
+    }
+
+    /// Returns a mutable reference to a slice with foliage layers of the terrain. Foliage will be regenerated
+    /// automatically if any of the layers was changed.
+    pub fn foliage_layers_mut(&mut self) -> &mut [FoliageLayer] {
+        self.foliage_layers.get_value_mut_and_mark_modified()
+    }
+
+    /// Adds new foliage layer together with its density masks for each chunk.
+    pub fn add_foliage_layer(&mut self, layer: FoliageLayer, masks: Vec<TextureResource>) {
+        self.insert_foliage_layer(layer, masks, self.foliage_layers.len())
+    }
+
+    /// Removes a foliage layer at the given index together with its respective density masks from each chunk.
+    pub fn remove_foliage_layer(
+        &mut self,
+        layer_index: usize,
+    ) -> (FoliageLayer, Vec<TextureResource>) {
+        let layer = self
+            .foliage_layers
+            .get_value_mut_and_mark_modified()
+            .remove(layer_index);
+        let mut masks = Vec::new();
+        for chunk in self.chunks_mut() {
+            masks.push(chunk.foliage_masks.remove(layer_index));
+        }
+        (layer, masks)
+    }
+
+    /// Removes last foliage layer together with its respective density masks from each chunk.
+    pub fn pop_foliage_layer(&mut self) -> Option<(FoliageLayer, Vec<TextureResource>)> {
+        if self.foliage_layers.is_empty() {
+            None
+        } else {
+            Some(self.remove_foliage_layer(self.foliage_layers.len() - 1))
+        }
+    }
+
+    /// Inserts the foliage layer at the given index together with its density masks for each chunk. Empty
+    /// masks will be created for chunks without a mask.
+    pub fn insert_foliage_layer(
+        &mut self,
+        layer: FoliageLayer,
+        mut masks: Vec<TextureResource>,
+        index: usize,
+    ) {
+        self.foliage_layers
+            .get_value_mut_and_mark_modified()
+            .insert(index, layer);
+
+        for chunk in self.chunks.iter_mut().rev() {
+            let mask = masks
+                .pop()
+                .unwrap_or_else(|| create_layer_mask(self.mask_size.x, self.mask_size.y, 0));
+            chunk.foliage_masks.insert(index, mask);
+            chunk.foliage_dirty.set(true);
+        }
+    }
+
+    /// Forces regeneration of foliage instances of every chunk. Terrain tracks its changes by itself, so
+    /// usually there's no need to call this method. However, if you've modified surface data of a foliage
+    /// mesh or a density mask directly, you need to call this method.
+    pub fn invalidate_foliage(&self) {
+        for chunk in self.chunks.iter() {
+            chunk.foliage_dirty.set(true);
+        }
+    }
+
+    /// Returns every instance of the given foliage layer. Transforms of the instances are in local coordinates
+    /// of the terrain. It could be useful to add colliders for large foliage objects (trees, rocks, etc.).
+    pub fn foliage_instances(&self, layer_index: usize) -> Vec<FoliageInstance> {
+        self.sync_foliage();
+
+        self.chunks
+            .iter()
+            .filter_map(|chunk| {
+                chunk
+                    .foliage
+                    .borrow()
+                    .get(layer_index)
+                    .map(|foliage| foliage.instances.clone())
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Regenerates foliage instances of every chunk, that was modified since last call.
+    fn sync_foliage(&self) {
+        let mut snapshot = self.foliage_layers_snapshot.borrow_mut();
+        let layers_changed = *snapshot != *self.foliage_layers;
+        if layers_changed {
+            *snapshot = (*self.foliage_layers).clone();
+        }
+
+        if !layers_changed && !self.chunks.iter().any(|chunk| chunk.foliage_dirty.get()) {
+            return;
+        }
+
+        let mesh_bounds = self
+            .foliage_layers
+            .iter()
+            .map(|layer| {
+                layer
+                    .meshes
+                    .iter()
+                    .map(|mesh| mesh.local_bounding_box())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for chunk in self.chunks.iter() {
+            if !layers_changed && !chunk.foliage_dirty.get() {
+                continue;
+            }
+
+            chunk.foliage_dirty.set(false);
+            self.bounding_box_dirty.set(true);
+
+            let height_map_texture = chunk.heightmap().data_ref();
+            let heights = height_map_texture.data_of_type::<f32>().unwrap_or_default();
+
+            let mut foliage = chunk.foliage.borrow_mut();
+            foliage.clear();
+
+            for (layer_index, layer) in self.foliage_layers.iter().enumerate() {
+                let chunk_foliage = if let Some(mask) = chunk.foliage_masks.get(layer_index) {
+                    let mask = mask.data_ref();
+                    if let TextureKind::Rectangle { width, height } = mask.kind() {
+                        layer.scatter(
+                            &FoliageSource {
+                                heights,
+                                height_map_size: chunk.height_map_size,
+                                mask: mask.data(),
+                                mask_size: Vector2::new(width, height),
+                                position: chunk.position,
+                                physical_size: chunk.physical_size,
+                                grid_position: chunk.grid_position,
+                            },
+                            &mesh_bounds[layer_index],
+                        )
+                    } else {
+                        Default::default()
+                    }
+                } else {
+                    Default::default()
+                };
+
+                foliage.push(chunk_foliage);
+            }
+        }
+    }
+
+    fn collect_foliage_render_data(&self, ctx: &mut RenderContext) {
+        self.sync_foliage();
+
+        let global_transform = self.global_transform();
+
+        for (chunk_index, chunk) in self.chunks.iter().enumerate() {
+            let foliage = chunk.foliage.borrow();
+
+            for (layer_index, (layer, chunk_foliage)) in
+                self.foliage_layers.iter().zip(foliage.iter()).enumerate()
+            {
+                if chunk_foliage.instances.is_empty() {
+                    continue;
+                }
+
+                let bounds = chunk_foliage.bounds.transform(&global_transform);
+                if !ctx.frustum.is_intersects_aabb(&bounds) {
+                    continue;
+                }
+
+                let closest_point = ctx.observer_position.sup(&bounds.min).inf(&bounds.max);
+                if closest_point.metric_distance(ctx.observer_position) >= layer.fade_distance.end {
+                    continue;
+                }
+
+                for (instance_index, instance) in chunk_foliage.instances.iter().enumerate() {
+                    let mesh = if let Some(mesh) = layer.meshes.get(instance.mesh) {
+                        mesh
+                    } else {
+                        continue;
+                    };
+
+                    let mut world_transform = global_transform * instance.transform;
+
+                    let position = world_transform.transform_point(&Point3::origin()).coords;
+                    let fade = layer.fade_factor(position.metric_distance(ctx.observer_position));
+                    if fade <= 0.0 {
+                        continue;
+                    } else if fade < 1.0 {
+                        // Shrink distant instances, so they won't pop out suddenly.
+                        world_transform *= Matrix4::new_scaling(fade);
+                    }
+
+                    ctx.storage.push(
+                        &mesh.data,
+                        &mesh.material,
+                        RenderPath::Deferred,
+                        self.decal_layer_index(),
+                        mesh.material.key(),
+                        SurfaceInstanceData {
+                            world_transform,
+                            bone_matrices: Default::default(),
+                            depth_offset: self.depth_offset_factor(),
+                            blend_shapes_weights: Default::default(),
+                            element_range: ElementRange::Full,
+                            persistent_identifier: PersistentIdentifier::new_combined(
+                                &mesh.data,
+                                ctx.node_handle,
+                                (chunk_index * self.foliage_layers.len() + layer_index)
+                                    .wrapping_mul(MAX_FOLIAGE_INSTANCES_PER_CHUNK)
+                                    .wrapping_add(instance_index),
+                            ),
+                        },
+                    );
+                }
+            }
+        }
+    }
+
     fn resize_masks(&mut self, mut new_size: Vector2<u32>) {
         new_size = new_size.sup(&Vector2::repeat(1));
 
         for chunk in self.chunks.iter_mut() {
-            for mask in chunk.layer_masks.iter_mut() {
+            chunk.foliage_dirty.set(true);
+
+            for mask in chunk
+                .layer_masks
+                .iter_mut()
+                .chain(chunk.foliage_masks.iter_mut())
+            {
                 let data = mask.data_ref();
 
                 let mask_image = ImageBuffer::<Luma<u8>, Vec<u8>>::from_vec(
@@ -1374,6 +1914,7 @@ impl Terrain {
 
             chunk.height_map_size = new_size;
             chunk.heightmap = Some(make_height_map_texture(resampled_heightmap, new_size));
+            chunk.foliage_dirty.set(true);
         }
 
         self.height_map_size.set_value_and_mark_modified(new_size);
@@ -1390,8 +1931,12 @@ impl NodeTrait for Terrain {
     crate::impl_query_component!();
 
     /// Returns pre-cached bounding axis-aligned bounding box of the terrain. Keep in mind that
-    /// if you're modified terrain, bounding box will be recalculated and it is not fast.
+    /// if you're modified terrain, bounding box will be recalculated and it is not fast. The
+    /// bounding box includes foliage instances, because they could stick out of the terrain.
     fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        // Foliage must be up-to-date, otherwise its bounds will be stale.
+        self.sync_foliage();
+
         if self.bounding_box_dirty.get() {
             let mut max_height = -f32::MAX;
             let mut min_height = f32::MAX;
@@ -1408,7 +1953,7 @@ impl NodeTrait for Terrain {
                 }
             }
 
-            let bounding_box = AxisAlignedBoundingBox::from_min_max(
+            let mut bounding_box = AxisAlignedBoundingBox::from_min_max(
                 Vector3::new(
                     self.chunk_size.x * self.width_chunks.start as f32,
                     min_height,
@@ -1420,6 +1965,13 @@ impl NodeTrait for Terrain {
                     self.chunk_size.y * self.length_chunks.end as f32,
                 ),
             );
+            for chunk in self.chunks.iter() {
+                for chunk_foliage in chunk.foliage.borrow().iter() {
+                    if !chunk_foliage.instances.is_empty() {
+                        bounding_box.add_box(chunk_foliage.bounds);
+                    }
+                }
+            }
             self.bounding_box.set(bounding_box);
             self.bounding_box_dirty.set(false);
 
@@ -1440,10 +1992,7 @@ impl NodeTrait for Terrain {
     }
 
     fn collect_render_data(&self, ctx: &mut RenderContext) {
-        if !self.global_visibility()
-            || !self.is_globally_enabled()
-            || !ctx.frustum.is_intersects_aabb(&self.world_bounding_box())
-        {
+        if !self.global_visibility() || !self.is_globally_enabled() {
             return;
         }
 
@@ -1451,6 +2000,13 @@ impl NodeTrait for Terrain {
             return;
         }
 
+        // Foliage does its own per-chunk culling.
+        self.collect_foliage_render_data(ctx);
+
+        if !ctx.frustum.is_intersects_aabb(&self.world_bounding_box()) {
+            return;
+        }
+
         for (layer_index, layer) in self.layers().iter().enumerate() {
             for chunk in self.chunks_ref().iter() {
                 let levels = (0..chunk.quad_tree.max_level)
@@ -1631,6 +2187,21 @@ pub enum BrushMode {
         /// Fixed height value for flattening.
         height: f32,
     },
+    /// Smooths height map by blending heights with the average height of neighbour pixels. Works across
+    /// chunk borders.
+    SmoothHeightMap {
+        /// Strength of smoothing. Range is [0.0; 1.0].
+        factor: f32,
+    },
+    /// Stamps fractal noise on height map. It is useful to add some roughness on flat areas.
+    NoiseHeightMap {
+        /// Max offset for height map.
+        amount: f32,
+        /// Frequency of the noise (amount of "bumps" per meter).
+        frequency: f32,
+        /// Seed of the noise.
+        seed: u32,
+    },
     /// Draws on a given layer.
     DrawOnMask {
         /// A layer to draw on.
@@ -1639,6 +2210,14 @@ pub enum BrushMode {
         /// values from mask, and positive - paints.
         alpha: f32,
     },
+    /// Draws on a density mask of a given foliage layer.
+    DrawOnFoliageMask {
+        /// A foliage layer to draw on.
+        layer: usize,
+        /// A value to put on mask. Range is [-1.0; 1.0] where negative values "erase"
+        /// foliage, and positive - paints.
+        alpha: f32,
+    },
 }
 
 /// Brush is used to modify terrain. It supports multiple shapes and modes.
@@ -1663,6 +2242,7 @@ pub struct TerrainBuilder {
     height_map_size: Vector2<u32>,
     block_size: Vector2<u32>,
     layers: Vec<Layer>,
+    foliage_layers: Vec<FoliageLayer>,
     decal_layer_index: u8,
 }
 
@@ -1696,6 +2276,7 @@ impl TerrainBuilder {
             height_map_size: Vector2::new(256, 256),
             block_size: Vector2::new(32, 32),
             layers: Default::default(),
+            foliage_layers: Default::default(),
             decal_layer_index: 0,
         }
     }
@@ -1736,6 +2317,12 @@ impl TerrainBuilder {
         self
     }
 
+    /// Sets desired foliage layers that will be used for each chunk in the terrain.
+    pub fn with_foliage_layers(mut self, foliage_layers: Vec<FoliageLayer>) -> Self {
+        self.foliage_layers = foliage_layers;
+        self
+    }
+
     /// Sets desired decal layer index.
     pub fn with_decal_layer_index(mut self, decal_layer_index: u8) -> Self {
         self.decal_layer_index = decal_layer_index;
@@ -1780,6 +2367,13 @@ impl TerrainBuilder {
                             )
                         })
                         .collect::<Vec<_>>(),
+                    foliage_masks: self
+                        .foliage_layers
+                        .iter()
+                        .map(|_| create_layer_mask(self.mask_size.x, self.mask_size.y, 0))
+                        .collect::<Vec<_>>(),
+                    foliage_dirty: Cell::new(true),
+                    foliage: Default::default(),
                     version: VERSION,
                     block_size: self.block_size,
                 };
@@ -1792,6 +2386,7 @@ impl TerrainBuilder {
             chunk_size: self.chunk_size.into(),
             base: self.base_builder.build_base(),
             layers: self.layers.into(),
+            foliage_layers: self.foliage_layers.into(),
             chunks: chunks.into(),
             bounding_box_dirty: Cell::new(true),
             bounding_box: Default::default(),
@@ -1803,6 +2398,7 @@ impl TerrainBuilder {
             version: VERSION,
             geometry: TerrainGeometry::new(self.block_size),
             block_size: self.block_size.into(),
+            foliage_layers_snapshot: Default::default(),
         };
         Node::new(terrain)
     }
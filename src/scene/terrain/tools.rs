@@ -0,0 +1,753 @@
+//! Procedural tools for terrains: fractal noise, erosion and import/export of height maps. See
+//! [`super::Terrain::generate_height_map`], [`super::Terrain::apply_hydraulic_erosion`],
+//! [`super::Terrain::apply_thermal_erosion`], [`super::Terrain::import_height_map`] and
+//! [`super::Terrain::export_height_map`] for more info.
+
+use crate::core::{
+    algebra::Vector2,
+    rand::{prelude::StdRng, Rng, SeedableRng},
+    reflect::prelude::*,
+};
+use image::{
+    codecs::png::PngEncoder, imageops::FilterType, ColorType, ImageBuffer, ImageEncoder,
+    ImageError, ImageFormat, Luma,
+};
+use std::{
+    fmt::{Display, Formatter},
+    ops::Range,
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Fractal (fBm) gradient noise. It is a sum of multiple "octaves" of gradient noise, each next octave has
+/// higher frequency and lower amplitude. It is used to generate natural-looking height maps.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Reflect)]
+pub struct FractalNoise {
+    /// Seed of the noise. Different seeds produce different patterns.
+    pub seed: u32,
+    /// Frequency of the first octave (amount of "hills" per meter).
+    #[reflect(min_value = 0.0, step = 0.001)]
+    pub frequency: f32,
+    /// Amount of octaves of the noise. More octaves adds more fine details.
+    #[reflect(min_value = 1.0, max_value = 16.0, step = 1.0)]
+    pub octaves: u32,
+    /// Frequency multiplier for each next octave.
+    #[reflect(min_value = 1.0, step = 0.1)]
+    pub lacunarity: f32,
+    /// Amplitude multiplier for each next octave.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub gain: f32,
+    /// Max height of the noise (in meters). Noise values are in `[-amplitude; amplitude]` range.
+    pub amplitude: f32,
+}
+
+impl Default for FractalNoise {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            frequency: 0.02,
+            octaves: 6,
+            lacunarity: 2.0,
+            gain: 0.5,
+            amplitude: 10.0,
+        }
+    }
+}
+
+fn hash(x: i32, y: i32, seed: u32) -> u32 {
+    let mut h = seed ^ (x as u32).wrapping_mul(0x27d4_eb2d) ^ (y as u32).wrapping_mul(0x1656_67b1);
+    h = (h ^ (h >> 15)).wrapping_mul(0x2c1b_3c6d);
+    h = (h ^ (h >> 12)).wrapping_mul(0x297a_2d39);
+    h ^ (h >> 15)
+}
+
+fn gradient(x: i32, y: i32, seed: u32, dx: f32, dy: f32) -> f32 {
+    const DIAGONAL: f32 = std::f32::consts::FRAC_1_SQRT_2;
+    match hash(x, y, seed) & 7 {
+        0 => dx,
+        1 => -dx,
+        2 => dy,
+        3 => -dy,
+        4 => (dx + dy) * DIAGONAL,
+        5 => (dx - dy) * DIAGONAL,
+        6 => (-dx + dy) * DIAGONAL,
+        _ => (-dx - dy) * DIAGONAL,
+    }
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Classic 2D gradient noise, the result is in `[-1; 1]` range.
+fn gradient_noise(position: Vector2<f32>, seed: u32) -> f32 {
+    let x0 = position.x.floor();
+    let y0 = position.y.floor();
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let (dx, dy) = (position.x - x0, position.y - y0);
+
+    let n00 = gradient(ix, iy, seed, dx, dy);
+    let n10 = gradient(ix + 1, iy, seed, dx - 1.0, dy);
+    let n01 = gradient(ix, iy + 1, seed, dx, dy - 1.0);
+    let n11 = gradient(ix + 1, iy + 1, seed, dx - 1.0, dy - 1.0);
+
+    let (u, v) = (fade(dx), fade(dy));
+
+    (lerp(lerp(n00, n10, u), lerp(n01, n11, u), v) * std::f32::consts::SQRT_2).clamp(-1.0, 1.0)
+}
+
+impl FractalNoise {
+    /// Samples the noise at the given position (in local coordinates of a terrain). The result is
+    /// in `[-amplitude; amplitude]` range.
+    pub fn sample(&self, position: Vector2<f32>) -> f32 {
+        let mut frequency = self.frequency;
+        let mut amplitude = 1.0;
+        let mut sum = 0.0;
+        let mut total_amplitude = 0.0;
+
+        for octave in 0..self.octaves.max(1) {
+            sum += amplitude * gradient_noise(position * frequency, self.seed.wrapping_add(octave));
+            total_amplitude += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+
+        if total_amplitude > 0.0 {
+            self.amplitude * sum / total_amplitude
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Hydraulic erosion simulates water droplets that flow down the terrain, picking up sediment on steep
+/// slopes and depositing it in flat areas and pits. It produces gullies, river beds and smooth valleys.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub struct HydraulicErosion {
+    /// Seed of droplets placement. The same seed on the same terrain always produces the same result.
+    pub seed: u32,
+    /// Total amount of simulated droplets.
+    #[reflect(min_value = 0.0, step = 1000.0)]
+    pub droplets: u32,
+    /// Max amount of steps that a droplet can do.
+    #[reflect(min_value = 1.0, step = 1.0)]
+    pub max_lifetime: u32,
+    /// Defines how much a droplet keeps its previous direction. Zero means that droplets always flow
+    /// strictly downhill.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub inertia: f32,
+    /// Max amount of sediment that a droplet can carry (relative to its speed, water amount and slope).
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub sediment_capacity: f32,
+    /// Minimal sediment capacity, it prevents capacity from dropping to zero on flat areas.
+    #[reflect(min_value = 0.0, step = 0.001)]
+    pub min_sediment_capacity: f32,
+    /// Defines how fast droplets erode terrain.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub erode_speed: f32,
+    /// Defines how fast droplets deposit excess sediment.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub deposit_speed: f32,
+    /// Defines how fast water of droplets evaporates.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub evaporate_speed: f32,
+    /// Gravity acceleration that speeds up droplets on slopes.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub gravity: f32,
+}
+
+impl Default for HydraulicErosion {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            droplets: 50000,
+            max_lifetime: 30,
+            inertia: 0.05,
+            sediment_capacity: 4.0,
+            min_sediment_capacity: 0.01,
+            erode_speed: 0.3,
+            deposit_speed: 0.3,
+            evaporate_speed: 0.01,
+            gravity: 4.0,
+        }
+    }
+}
+
+/// Thermal erosion simulates weathering of material on steep slopes - material falls down until the slope
+/// is not steeper than the talus angle. It produces scree slopes at the base of cliffs.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub struct ThermalErosion {
+    /// Amount of simulation passes over the whole terrain.
+    #[reflect(min_value = 0.0, step = 1.0)]
+    pub iterations: u32,
+    /// Max angle of stable slope (in degrees). Steeper slopes will be eroded.
+    #[reflect(min_value = 0.0, max_value = 89.0, step = 1.0)]
+    pub talus_angle: f32,
+    /// Fraction of excess material that will be moved on each iteration.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub factor: f32,
+}
+
+impl Default for ThermalErosion {
+    fn default() -> Self {
+        Self {
+            iterations: 50,
+            talus_angle: 35.0,
+            factor: 0.5,
+        }
+    }
+}
+
+/// Height map file format for [`super::Terrain::import_height_map`] and [`super::Terrain::export_height_map`].
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Default, Reflect, AsRefStr, EnumString, EnumVariantNames,
+)]
+pub enum HeightMapFormat {
+    /// Greyscale PNG image with 16 bits per pixel. Import also accepts 8-bit and color images, only luminance
+    /// is used in this case.
+    #[default]
+    Png16,
+    /// Headerless array of 16-bit little-endian unsigned integers in row-major order. Width and length of the
+    /// image are deduced from the file size: it must either match the size of the height map of a terrain, or
+    /// be square.
+    Raw16,
+}
+
+/// An error that may occur during import or export of height maps.
+#[derive(Debug)]
+pub enum HeightMapError {
+    /// Internal image crate error.
+    Image(ImageError),
+    /// Size of the data does not match the expected size of the height map.
+    InvalidSize,
+    /// The terrain has no chunks.
+    EmptyTerrain,
+}
+
+impl Display for HeightMapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeightMapError::Image(v) => {
+                write!(f, "Image error {v}")
+            }
+            HeightMapError::InvalidSize => {
+                write!(f, "Size of the height map data is invalid!")
+            }
+            HeightMapError::EmptyTerrain => {
+                write!(f, "Terrain has no chunks!")
+            }
+        }
+    }
+}
+
+impl From<ImageError> for HeightMapError {
+    fn from(v: ImageError) -> Self {
+        Self::Image(v)
+    }
+}
+
+/// Heights of the whole terrain gathered from every chunk in a single grid, so the operations that
+/// need neighbour pixels could work across chunk borders.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct HeightGrid {
+    pub size: Vector2<usize>,
+    pub data: Vec<f32>,
+}
+
+impl HeightGrid {
+    pub fn new(size: Vector2<usize>) -> Self {
+        Self {
+            size,
+            data: vec![0.0; size.x * size.y],
+        }
+    }
+
+    #[inline]
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * self.size.x + x
+    }
+
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> f32 {
+        self.data[self.index(x, y)]
+    }
+
+    #[inline]
+    pub fn set(&mut self, x: usize, y: usize, height: f32) {
+        let index = self.index(x, y);
+        self.data[index] = height;
+    }
+
+    /// Blends every pixel towards average height of its neighbours. `weight` defines the blending
+    /// weight for a pixel, it should return a value in `[0; 1]` range.
+    pub fn smooth<F>(&mut self, mut weight: F)
+    where
+        F: FnMut(usize, usize) -> f32,
+    {
+        let source = self.data.clone();
+        for y in 0..self.size.y {
+            for x in 0..self.size.x {
+                let k = weight(x, y);
+                if k <= 0.0 {
+                    continue;
+                }
+
+                let mut sum = 0.0;
+                let mut count = 0.0;
+                for ny in y.saturating_sub(1)..(y + 2).min(self.size.y) {
+                    for nx in x.saturating_sub(1)..(x + 2).min(self.size.x) {
+                        sum += source[ny * self.size.x + nx];
+                        count += 1.0;
+                    }
+                }
+
+                let index = self.index(x, y);
+                self.data[index] = lerp(source[index], sum / count, k.min(1.0));
+            }
+        }
+    }
+
+    /// Bilinear interpolation of height and its gradient at the given point (in pixels).
+    fn height_and_gradient(&self, position: Vector2<f32>) -> (f32, Vector2<f32>) {
+        let x = (position.x as usize).min(self.size.x - 2);
+        let y = (position.y as usize).min(self.size.y - 2);
+        let u = position.x - x as f32;
+        let v = position.y - y as f32;
+
+        let h00 = self.get(x, y);
+        let h10 = self.get(x + 1, y);
+        let h01 = self.get(x, y + 1);
+        let h11 = self.get(x + 1, y + 1);
+
+        let gradient = Vector2::new(
+            (h10 - h00) * (1.0 - v) + (h11 - h01) * v,
+            (h01 - h00) * (1.0 - u) + (h11 - h10) * u,
+        );
+
+        let height =
+            h00 * (1.0 - u) * (1.0 - v) + h10 * u * (1.0 - v) + h01 * (1.0 - u) * v + h11 * u * v;
+
+        (height, gradient)
+    }
+
+    /// Adds the amount to four pixels around the point (in pixels) using bilinear weights.
+    fn add_bilinear(&mut self, position: Vector2<f32>, amount: f32) {
+        let x = (position.x as usize).min(self.size.x - 2);
+        let y = (position.y as usize).min(self.size.y - 2);
+        let u = position.x - x as f32;
+        let v = position.y - y as f32;
+
+        let i00 = self.index(x, y);
+        let i10 = self.index(x + 1, y);
+        let i01 = self.index(x, y + 1);
+        let i11 = self.index(x + 1, y + 1);
+
+        self.data[i00] += amount * (1.0 - u) * (1.0 - v);
+        self.data[i10] += amount * u * (1.0 - v);
+        self.data[i01] += amount * (1.0 - u) * v;
+        self.data[i11] += amount * u * v;
+    }
+
+    /// Simulates droplets as described in "Implementation of a method for hydraulic erosion" by Hans Theobald
+    /// Beyer. Positions of droplets are measured in pixels.
+    pub fn erode_hydraulic(&mut self, params: &HydraulicErosion) {
+        if self.size.x < 2 || self.size.y < 2 {
+            return;
+        }
+
+        let max_x = (self.size.x - 1) as f32;
+        let max_y = (self.size.y - 1) as f32;
+
+        let mut rng = StdRng::seed_from_u64(params.seed as u64);
+
+        for _ in 0..params.droplets {
+            let mut position = Vector2::new(rng.gen_range(0.0..max_x), rng.gen_range(0.0..max_y));
+            let mut direction = Vector2::<f32>::default();
+            let mut speed = 1.0;
+            let mut water = 1.0;
+            let mut sediment = 0.0;
+
+            for _ in 0..params.max_lifetime {
+                let (height, gradient) = self.height_and_gradient(position);
+
+                direction = direction * params.inertia - gradient * (1.0 - params.inertia);
+                let length = direction.norm();
+                if length <= f32::EPSILON {
+                    // The droplet is on a flat area, pick random direction.
+                    let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                    direction = Vector2::new(angle.cos(), angle.sin());
+                } else {
+                    direction /= length;
+                }
+
+                let new_position = position + direction;
+                if new_position.x < 0.0
+                    || new_position.y < 0.0
+                    || new_position.x >= max_x
+                    || new_position.y >= max_y
+                {
+                    // Put the sediment back, otherwise it will be lost.
+                    self.add_bilinear(position, sediment);
+                    break;
+                }
+
+                let (new_height, _) = self.height_and_gradient(new_position);
+                let height_delta = new_height - height;
+
+                let capacity = (-height_delta * speed * water * params.sediment_capacity)
+                    .max(params.min_sediment_capacity);
+
+                if sediment > capacity || height_delta > 0.0 {
+                    // Fill the pit if moving uphill, or drop excess sediment.
+                    let amount = if height_delta > 0.0 {
+                        height_delta.min(sediment)
+                    } else {
+                        (sediment - capacity) * params.deposit_speed
+                    };
+                    sediment -= amount;
+                    self.add_bilinear(position, amount);
+                } else {
+                    // Never erode more than the height difference, otherwise it will dig holes.
+                    let amount = ((capacity - sediment) * params.erode_speed).min(-height_delta);
+                    sediment += amount;
+                    self.add_bilinear(position, -amount);
+                }
+
+                speed = (speed * speed - height_delta * params.gravity)
+                    .max(0.0)
+                    .sqrt();
+                water *= 1.0 - params.evaporate_speed;
+                position = new_position;
+            }
+        }
+    }
+
+    /// Moves material from the pixels which slopes are steeper than the talus angle to their lower
+    /// neighbours. `cell_size` is the distance between neighbour pixels (in meters).
+    pub fn erode_thermal(&mut self, params: &ThermalErosion, cell_size: Vector2<f32>) {
+        let tan = params.talus_angle.to_radians().tan();
+        let talus = [
+            tan * cell_size.x,
+            tan * cell_size.x,
+            tan * cell_size.y,
+            tan * cell_size.y,
+        ];
+        let mut deltas = vec![0.0; self.data.len()];
+
+        for _ in 0..params.iterations {
+            for delta in deltas.iter_mut() {
+                *delta = 0.0;
+            }
+
+            for y in 0..self.size.y {
+                for x in 0..self.size.x {
+                    let height = self.get(x, y);
+
+                    let neighbours = [
+                        (x > 0).then(|| (x - 1, y)),
+                        (x + 1 < self.size.x).then(|| (x + 1, y)),
+                        (y > 0).then(|| (x, y - 1)),
+                        (y + 1 < self.size.y).then(|| (x, y + 1)),
+                    ];
+
+                    let mut total_excess = 0.0;
+                    let mut max_excess = 0.0f32;
+                    let mut excesses = [0.0; 4];
+                    for (i, neighbour) in neighbours.iter().enumerate() {
+                        if let Some((nx, ny)) = *neighbour {
+                            let excess = height - self.get(nx, ny) - talus[i];
+                            if excess > 0.0 {
+                                excesses[i] = excess;
+                                total_excess += excess;
+                                max_excess = max_excess.max(excess);
+                            }
+                        }
+                    }
+
+                    if total_excess <= 0.0 {
+                        continue;
+                    }
+
+                    // Move only half of the excess, so the slope won't "flip" to the other side.
+                    let amount = params.factor * max_excess * 0.5;
+                    deltas[self.index(x, y)] -= amount;
+                    for (i, neighbour) in neighbours.iter().enumerate() {
+                        if let Some((nx, ny)) = *neighbour {
+                            deltas[ny * self.size.x + nx] += amount * excesses[i] / total_excess;
+                        }
+                    }
+                }
+            }
+
+            for (height, delta) in self.data.iter_mut().zip(deltas.iter()) {
+                *height += *delta;
+            }
+        }
+    }
+
+    /// Normalizes heights using the given range and encodes them in the specified format.
+    pub fn encode(
+        &self,
+        format: HeightMapFormat,
+        height_range: Range<f32>,
+    ) -> Result<Vec<u8>, HeightMapError> {
+        let span = height_range.end - height_range.start;
+        let pixels = self
+            .data
+            .iter()
+            .map(|height| {
+                let k = if span.abs() > f32::EPSILON {
+                    (height - height_range.start) / span
+                } else {
+                    0.0
+                };
+                (k.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16
+            })
+            .collect::<Vec<_>>();
+
+        match format {
+            HeightMapFormat::Png16 => {
+                let mut bytes = Vec::new();
+                let native_bytes = pixels
+                    .iter()
+                    .flat_map(|pixel| pixel.to_ne_bytes())
+                    .collect::<Vec<_>>();
+                PngEncoder::new(&mut bytes).write_image(
+                    &native_bytes,
+                    self.size.x as u32,
+                    self.size.y as u32,
+                    ColorType::L16,
+                )?;
+                Ok(bytes)
+            }
+            HeightMapFormat::Raw16 => Ok(pixels
+                .iter()
+                .flat_map(|pixel| pixel.to_le_bytes())
+                .collect()),
+        }
+    }
+
+    /// Decodes the data in the specified format, maps the values to the given range and resamples the
+    /// result to the size of the grid if needed.
+    pub fn decode(
+        &mut self,
+        data: &[u8],
+        format: HeightMapFormat,
+        height_range: Range<f32>,
+    ) -> Result<(), HeightMapError> {
+        let (width, length, pixels) = match format {
+            HeightMapFormat::Png16 => {
+                let image = image::load_from_memory_with_format(data, ImageFormat::Png)?;
+                let image = image.into_luma16();
+                let (width, length) = image.dimensions();
+                (width as usize, length as usize, image.into_raw())
+            }
+            HeightMapFormat::Raw16 => {
+                let bytes = data.chunks_exact(2);
+                if !bytes.remainder().is_empty() {
+                    return Err(HeightMapError::InvalidSize);
+                }
+                let pixels = bytes
+                    .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+                    .collect::<Vec<_>>();
+                let (width, length) = if pixels.len() == self.size.x * self.size.y {
+                    (self.size.x, self.size.y)
+                } else {
+                    let side = (pixels.len() as f64).sqrt().round() as usize;
+                    if side * side != pixels.len() {
+                        return Err(HeightMapError::InvalidSize);
+                    }
+                    (side, side)
+                };
+                (width, length, pixels)
+            }
+        };
+
+        if width == 0 || length == 0 {
+            return Err(HeightMapError::InvalidSize);
+        }
+
+        let normalized = pixels
+            .into_iter()
+            .map(|pixel| pixel as f32 / u16::MAX as f32)
+            .collect::<Vec<_>>();
+
+        let normalized = if width != self.size.x || length != self.size.y {
+            let image = ImageBuffer::<Luma<f32>, Vec<f32>>::from_vec(
+                width as u32,
+                length as u32,
+                normalized,
+            )
+            .ok_or(HeightMapError::InvalidSize)?;
+            image::imageops::resize(
+                &image,
+                self.size.x as u32,
+                self.size.y as u32,
+                FilterType::Triangle,
+            )
+            .into_raw()
+        } else {
+            normalized
+        };
+
+        for (height, k) in self.data.iter_mut().zip(normalized) {
+            *height = lerp(height_range.start, height_range.end, k);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn make_cone(size: usize, height: f32) -> HeightGrid {
+        let mut grid = HeightGrid::new(Vector2::new(size, size));
+        let center = (size - 1) as f32 * 0.5;
+        for y in 0..size {
+            for x in 0..size {
+                let distance = Vector2::new(x as f32 - center, y as f32 - center).norm();
+                grid.set(x, y, (height - distance * height / center).max(0.0));
+            }
+        }
+        grid
+    }
+
+    fn max_slope(grid: &HeightGrid) -> f32 {
+        let mut max = 0.0f32;
+        for y in 0..grid.size.y {
+            for x in 0..grid.size.x - 1 {
+                max = max.max((grid.get(x, y) - grid.get(x + 1, y)).abs());
+            }
+        }
+        for y in 0..grid.size.y - 1 {
+            for x in 0..grid.size.x {
+                max = max.max((grid.get(x, y) - grid.get(x, y + 1)).abs());
+            }
+        }
+        max
+    }
+
+    #[test]
+    fn test_fractal_noise() {
+        let noise = FractalNoise {
+            amplitude: 5.0,
+            ..Default::default()
+        };
+
+        let mut min = f32::MAX;
+        let mut max = -f32::MAX;
+        for y in 0..100 {
+            for x in 0..100 {
+                let position = Vector2::new(x as f32 * 1.7, y as f32 * 1.3);
+                let value = noise.sample(position);
+                assert!(value.abs() <= 5.0);
+                assert_eq!(value, noise.sample(position));
+                min = min.min(value);
+                max = max.max(value);
+            }
+        }
+        // The noise must not be constant.
+        assert!(max - min > 1.0);
+
+        let other = FractalNoise {
+            seed: 123,
+            ..noise.clone()
+        };
+        assert_ne!(
+            noise.sample(Vector2::new(10.5, 20.5)),
+            other.sample(Vector2::new(10.5, 20.5))
+        );
+    }
+
+    #[test]
+    fn test_smooth() {
+        let mut grid = HeightGrid::new(Vector2::new(5, 5));
+        grid.set(2, 2, 9.0);
+
+        grid.smooth(|_, _| 1.0);
+
+        assert_eq!(grid.get(2, 2), 1.0);
+        assert_eq!(grid.get(1, 1), 1.0);
+        assert_eq!(grid.get(0, 0), 0.0);
+
+        // Zero weight must keep pixels untouched.
+        let mut grid = make_cone(9, 4.0);
+        let copy = grid.clone();
+        grid.smooth(|_, _| 0.0);
+        assert_eq!(grid, copy);
+    }
+
+    #[test]
+    fn test_thermal_erosion() {
+        let mut grid = make_cone(33, 32.0);
+        let mass_before = grid.data.iter().sum::<f32>();
+        let slope_before = max_slope(&grid);
+
+        grid.erode_thermal(
+            &ThermalErosion {
+                iterations: 200,
+                talus_angle: 30.0,
+                factor: 0.5,
+            },
+            Vector2::new(1.0, 1.0),
+        );
+
+        let mass_after = grid.data.iter().sum::<f32>();
+        assert!((mass_before - mass_after).abs() / mass_before < 1.0e-3);
+        assert!(max_slope(&grid) < slope_before);
+    }
+
+    #[test]
+    fn test_hydraulic_erosion() {
+        let mut grid = make_cone(64, 20.0);
+        let params = HydraulicErosion {
+            droplets: 2000,
+            ..Default::default()
+        };
+
+        let mut other = grid.clone();
+        grid.erode_hydraulic(&params);
+        other.erode_hydraulic(&params);
+
+        // Must be deterministic.
+        assert_eq!(grid, other);
+        assert_ne!(grid, make_cone(64, 20.0));
+        assert!(grid.data.iter().all(|h| h.is_finite()));
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let source = make_cone(17, 10.0);
+
+        for format in [HeightMapFormat::Png16, HeightMapFormat::Raw16] {
+            let data = source.encode(format, 0.0..10.0).unwrap();
+
+            let mut grid = HeightGrid::new(source.size);
+            grid.decode(&data, format, 0.0..10.0).unwrap();
+            for (a, b) in grid.data.iter().zip(source.data.iter()) {
+                assert!((a - b).abs() < 1.0e-3);
+            }
+
+            // Resampling.
+            let mut grid = HeightGrid::new(Vector2::new(9, 9));
+            grid.decode(&data, format, 0.0..10.0).unwrap();
+            assert!(grid.get(4, 4) > 8.0, "{}", grid.get(4, 4));
+            assert!(grid.get(0, 0) < 1.0);
+        }
+
+        let mut grid = HeightGrid::new(Vector2::new(4, 4));
+        assert!(matches!(
+            grid.decode(&[0, 0, 0], HeightMapFormat::Raw16, 0.0..1.0),
+            Err(HeightMapError::InvalidSize)
+        ));
+        assert!(matches!(
+            grid.decode(&[0; 6], HeightMapFormat::Raw16, 0.0..1.0),
+            Err(HeightMapError::InvalidSize)
+        ));
+    }
+}
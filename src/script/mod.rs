@@ -13,6 +13,7 @@ use crate::{
     },
     engine::ScriptMessageDispatcher,
     event::Event,
+    input::Input,
     plugin::Plugin,
     scene::{node::Node, Scene},
     utils::component::ComponentProvider,
@@ -206,7 +207,8 @@ pub struct ScriptContext<'a, 'b, 'c> {
     pub handle: Handle<Node>,
 
     /// A reference to a scene the script instance belongs to. You have full mutable access to scene content
-    /// in most of the script methods.
+    /// in most of the script methods. Collision events of the last physics step are available in physics worlds
+    /// of the scene graph, see [`crate::scene::graph::physics::PhysicsWorld::collision_events`].
     pub scene: &'b mut Scene,
 
     /// A reference to resource manager, use it to load resources.
@@ -219,6 +221,10 @@ pub struct ScriptContext<'a, 'b, 'c> {
     /// A message dispatcher. If you need to receive messages of a particular type, you must subscribe to a type
     /// explicitly. See [`ScriptTrait::on_message`] for more examples.
     pub message_dispatcher: &'c mut ScriptMessageDispatcher,
+
+    /// A reference to the input subsystem of the engine. Use it to query the state of actions and axes
+    /// instead of tracking raw OS events. See [`Input`] docs for more info.
+    pub input: &'a mut Input,
 }
 
 /// A set of data, that provides contextual information for script methods.
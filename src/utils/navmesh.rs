@@ -786,26 +786,26 @@ mod test {
         navmesh.remove_triangle(0); // A
 
         assert_eq!(navmesh.vertices()[0].neighbours, vec![4, 2, 3]);
-        assert_eq!(navmesh.vertices()[1].neighbours, vec![]);
+        assert_eq!(navmesh.vertices()[1].neighbours, Vec::<u32>::new());
         assert_eq!(navmesh.vertices()[2].neighbours, vec![3, 0, 4]);
         assert_eq!(navmesh.vertices()[3].neighbours, vec![4, 2, 0]);
         assert_eq!(navmesh.vertices()[4].neighbours, vec![3, 0, 2]);
 
         navmesh.remove_triangle(0); // C
 
-        assert_eq!(navmesh.vertices()[0].neighbours, vec![]);
-        assert_eq!(navmesh.vertices()[1].neighbours, vec![]);
+        assert_eq!(navmesh.vertices()[0].neighbours, Vec::<u32>::new());
+        assert_eq!(navmesh.vertices()[1].neighbours, Vec::<u32>::new());
         assert_eq!(navmesh.vertices()[2].neighbours, vec![3, 4]);
         assert_eq!(navmesh.vertices()[3].neighbours, vec![4, 2]);
         assert_eq!(navmesh.vertices()[4].neighbours, vec![3, 2]);
 
         navmesh.remove_triangle(0); // D
 
-        assert_eq!(navmesh.vertices()[0].neighbours, vec![]);
-        assert_eq!(navmesh.vertices()[1].neighbours, vec![]);
-        assert_eq!(navmesh.vertices()[2].neighbours, vec![]);
-        assert_eq!(navmesh.vertices()[3].neighbours, vec![]);
-        assert_eq!(navmesh.vertices()[4].neighbours, vec![]);
+        assert_eq!(navmesh.vertices()[0].neighbours, Vec::<u32>::new());
+        assert_eq!(navmesh.vertices()[1].neighbours, Vec::<u32>::new());
+        assert_eq!(navmesh.vertices()[2].neighbours, Vec::<u32>::new());
+        assert_eq!(navmesh.vertices()[3].neighbours, Vec::<u32>::new());
+        assert_eq!(navmesh.vertices()[4].neighbours, Vec::<u32>::new());
     }
 
     #[test]
@@ -839,14 +839,14 @@ mod test {
 
         assert_eq!(navmesh.triangles().len(), 0);
 
-        assert_eq!(navmesh.vertices()[0].neighbours, vec![]);
-        assert_eq!(navmesh.vertices()[1].neighbours, vec![]);
+        assert_eq!(navmesh.vertices()[0].neighbours, Vec::<u32>::new());
+        assert_eq!(navmesh.vertices()[1].neighbours, Vec::<u32>::new());
 
         navmesh.remove_vertex(1);
 
         assert_eq!(navmesh.triangles().len(), 0);
 
-        assert_eq!(navmesh.vertices()[0].neighbours, vec![]);
+        assert_eq!(navmesh.vertices()[0].neighbours, Vec::<u32>::new());
 
         navmesh.remove_vertex(0);
 
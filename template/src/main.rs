@@ -2,6 +2,7 @@
 
 use clap::{Parser, Subcommand};
 use convert_case::{Case, Casing};
+use fyrox_core::vfs::pak::{pack_directory, PackOptions};
 use regex::Regex;
 use std::{
     collections::HashMap,
@@ -45,6 +46,15 @@ enum Commands {
         #[clap(short, long)]
         version: String,
     },
+    /// Packs every file of the project folder (except sources, manifests and build artifacts)
+    /// into a pak archive, that could be mounted by the engine's virtual file system.
+    Pack {
+        #[clap(short, long, default_value = ".")]
+        path: String,
+
+        #[clap(short, long, default_value = "data.pak")]
+        output: String,
+    },
 }
 
 // Ideally, this should be take from respective Cargo.toml of the engine and the editor.
@@ -740,5 +750,14 @@ fn main() {
 
             println!("Fyrox version was successfully set to '{}'!", version);
         }
+        Commands::Pack { path, output } => {
+            match pack_directory(&path, &output, &PackOptions::default()) {
+                Ok(count) => println!("{} files were packed into {}!", count, output),
+                Err(e) => {
+                    println!("Unable to pack {} into {}. Reason: {}", path, output, e);
+                    exit(1);
+                }
+            }
+        }
     }
 }